@@ -5,7 +5,7 @@ use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use just_storage::application::gc::worker::GarbageCollector;
 use just_storage::application::ports::{BlobRepository, BlobStore, RepositoryError, StorageError};
 use just_storage::domain::entities::Blob;
-use just_storage::domain::value_objects::{ContentHash, StorageClass};
+use just_storage::domain::value_objects::{ContentHash, ContentHashPrefix, StorageClass, TenantId};
 use just_storage::infrastructure::storage::LocalFilesystemStore;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -52,6 +52,15 @@ impl BlobRepository for MockBlobRepository {
         unreachable!("Not used in GC benchmarks")
     }
 
+    async fn reference_existing(
+        &self,
+        _content_hash: &ContentHash,
+        _storage_class: StorageClass,
+        _tenant_id: &TenantId,
+    ) -> Result<Option<Blob>, RepositoryError> {
+        unreachable!("Not used in GC benchmarks")
+    }
+
     async fn increment_ref(&self, _content_hash: &ContentHash) -> Result<(), RepositoryError> {
         unreachable!("Not used in GC benchmarks")
     }
@@ -76,6 +85,47 @@ impl BlobRepository for MockBlobRepository {
         blobs.retain(|b| b.content_hash() != content_hash);
         Ok(())
     }
+
+    async fn move_storage_class(
+        &self,
+        _content_hash: &ContentHash,
+        _from: StorageClass,
+        _to: StorageClass,
+    ) -> Result<bool, RepositoryError> {
+        unreachable!("Not used in GC benchmarks")
+    }
+
+    async fn find_known(
+        &self,
+        _content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        unreachable!("Not used in GC benchmarks")
+    }
+
+    async fn list_hashes(
+        &self,
+        _after: Option<ContentHash>,
+        _limit: i64,
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        unreachable!("Not used in GC benchmarks")
+    }
+
+    async fn list(
+        &self,
+        _after: Option<ContentHash>,
+        _orphaned_only: bool,
+        _limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        unreachable!("Not used in GC benchmarks")
+    }
+
+    async fn find_by_prefix(
+        &self,
+        _prefix: &ContentHashPrefix,
+        _limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        unreachable!("Not used in GC benchmarks")
+    }
 }
 
 // Mock blob store that tracks deletions
@@ -158,6 +208,54 @@ impl BlobStore for MockBlobStore {
     async fn get_total_size(&self, _storage_class: StorageClass) -> Result<u64, StorageError> {
         Ok(0)
     }
+
+    async fn append_staged(
+        &self,
+        _upload_id: uuid::Uuid,
+        _offset: u64,
+        _reader: just_storage::application::ports::BlobReader,
+        _storage_class: StorageClass,
+    ) -> Result<u64, StorageError> {
+        Err(StorageError::NotFound("not used".to_string()))
+    }
+
+    async fn staged_len(
+        &self,
+        _upload_id: uuid::Uuid,
+        _storage_class: StorageClass,
+    ) -> Result<Option<u64>, StorageError> {
+        Err(StorageError::NotFound("not used".to_string()))
+    }
+
+    async fn read_staged(
+        &self,
+        _upload_id: uuid::Uuid,
+        _storage_class: StorageClass,
+    ) -> Result<just_storage::application::ports::BlobReader, StorageError> {
+        Err(StorageError::NotFound("not used".to_string()))
+    }
+
+    async fn discard_staged(
+        &self,
+        _upload_id: uuid::Uuid,
+        _storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotFound("not used".to_string()))
+    }
+
+    async fn initiate_restore(
+        &self,
+        _content_hash: &ContentHash,
+    ) -> Result<just_storage::application::ports::RestoreJob, StorageError> {
+        Err(StorageError::NotFound("not used".to_string()))
+    }
+
+    async fn restore_status(
+        &self,
+        _content_hash: &ContentHash,
+    ) -> Result<just_storage::application::ports::RestoreStatus, StorageError> {
+        Err(StorageError::NotFound("not used".to_string()))
+    }
 }
 
 fn gc_worker_benchmarks(c: &mut Criterion) {
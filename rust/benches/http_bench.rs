@@ -2,16 +2,23 @@ use async_trait::async_trait;
 /// HTTP handler benchmarks
 /// Measures end-to-end handler performance including middleware
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use just_storage::application::dto::{SearchRequest, TextSearchRequest};
+use just_storage::application::dto::{
+    ObjectAccess, ObjectField, ObjectHead, ObjectProjection, ObjectRecord, SearchRequest,
+    SortDirection, SortField, TextSearchPage, TextSearchRequest,
+};
+use just_storage::application::key_prefix_query::KeyPrefixQuery;
+use just_storage::application::list_cursor::ListCursor;
+use just_storage::application::metadata_query::MetadataQuery;
 use just_storage::application::ports::{
-    BlobRepository, BlobStore, ObjectRepository, RepositoryError,
+    BlobRepository, BlobStore, ObjectRepository, ObjectStream, RepositoryError,
 };
 use just_storage::application::use_cases::{
     DownloadObjectUseCase, ListObjectsUseCase, UploadObjectUseCase,
 };
 use just_storage::domain::entities::{Blob, Object};
 use just_storage::domain::value_objects::{
-    ContentHash, Namespace, ObjectId, StorageClass, TenantId,
+    ContentHash, ContentHashPrefix, Namespace, ObjectId, ObjectMetadata, ObjectStatus,
+    StorageClass, TenantId,
 };
 use just_storage::infrastructure::storage::LocalFilesystemStore;
 use std::collections::HashMap;
@@ -49,6 +56,87 @@ impl ObjectRepository for MockObjectRepository {
         Ok(objects.get(&id.to_string()).cloned())
     }
 
+    async fn find_writing(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError> {
+        let objects = self.objects.lock().await;
+        Ok(objects
+            .get(&id.to_string())
+            .filter(|object| object.status() == ObjectStatus::Writing)
+            .cloned())
+    }
+
+    async fn head(&self, id: &ObjectId) -> Result<Option<ObjectHead>, RepositoryError> {
+        let objects = self.objects.lock().await;
+        Ok(objects
+            .get(&id.to_string())
+            .and_then(ObjectHead::from_committed))
+    }
+
+    async fn find_record(&self, id: &ObjectId) -> Result<Option<ObjectRecord>, RepositoryError> {
+        Ok(self.find_by_id(id).await?.map(|object| ObjectRecord {
+            object,
+            blob_ref_count: None,
+        }))
+    }
+
+    async fn status(
+        &self,
+        id: &ObjectId,
+    ) -> Result<Option<(TenantId, ObjectStatus)>, RepositoryError> {
+        let objects = self.objects.lock().await;
+        Ok(objects
+            .get(&id.to_string())
+            .map(|object| (object.tenant_id().clone(), object.status())))
+    }
+
+    async fn head_by_key(
+        &self,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+        _key: &str,
+    ) -> Result<Option<ObjectHead>, RepositoryError> {
+        Ok(None)
+    }
+
+    async fn replace_content_if_match(
+        &self,
+        object: &Object,
+        _expected: &ContentHash,
+    ) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().await;
+        objects.insert(object.id().to_string(), object.clone());
+        Ok(true)
+    }
+
+    async fn update_metadata_if_match(
+        &self,
+        object: &Object,
+        _expected: &ObjectMetadata,
+    ) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().await;
+        objects.insert(object.id().to_string(), object.clone());
+        Ok(true)
+    }
+
+    async fn update_retention(&self, object: &Object) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().await;
+        objects.insert(object.id().to_string(), object.clone());
+        Ok(true)
+    }
+
+    async fn mark_deleting_if_unlocked(&self, object: &Object) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().await;
+        objects.insert(object.id().to_string(), object.clone());
+        Ok(true)
+    }
+
+    async fn set_extracted_text(
+        &self,
+        _id: &ObjectId,
+        _text: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        Ok(())
+    }
+
     async fn find_by_key(
         &self,
         _namespace: &Namespace,
@@ -62,23 +150,100 @@ impl ObjectRepository for MockObjectRepository {
         &self,
         _namespace: &Namespace,
         _tenant_id: &TenantId,
+        _keys: &KeyPrefixQuery,
+        _metadata: &MetadataQuery,
+        _sort_by: SortField,
+        _sort_direction: SortDirection,
+        _after: Option<ListCursor>,
         _limit: i64,
         _offset: i64,
     ) -> Result<Vec<Object>, RepositoryError> {
         Ok(vec![])
     }
 
-    async fn search(&self, _request: &SearchRequest) -> Result<Vec<Object>, RepositoryError> {
+    async fn list_projected(
+        &self,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+        _keys: &KeyPrefixQuery,
+        _metadata: &MetadataQuery,
+        _sort_by: SortField,
+        _sort_direction: SortDirection,
+        _fields: &[ObjectField],
+        _limit: i64,
+        _offset: i64,
+    ) -> Result<Vec<ObjectProjection>, RepositoryError> {
         Ok(vec![])
     }
 
-    async fn text_search(
+    fn stream(
         &self,
-        _request: &TextSearchRequest,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+        _keys: &KeyPrefixQuery,
+        _metadata: &MetadataQuery,
+        _sort_by: SortField,
+        _sort_direction: SortDirection,
+    ) -> ObjectStream {
+        Box::pin(futures_util::stream::empty())
+    }
+
+    async fn count(
+        &self,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+        _keys: &KeyPrefixQuery,
+        _metadata: &MetadataQuery,
+        _max: i64,
+    ) -> Result<i64, RepositoryError> {
+        Ok(0)
+    }
+
+    async fn common_prefixes(
+        &self,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+        _keys: &KeyPrefixQuery,
+        _metadata: &MetadataQuery,
+        _max: i64,
+    ) -> Result<Vec<String>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    async fn namespaces(&self, _tenant_id: &TenantId) -> Result<Vec<Namespace>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    async fn search(
+        &self,
+        _request: &SearchRequest,
+        _keys: &KeyPrefixQuery,
+        _metadata: &MetadataQuery,
+        _after: Option<ListCursor>,
     ) -> Result<Vec<Object>, RepositoryError> {
         Ok(vec![])
     }
 
+    async fn text_search(
+        &self,
+        _request: &TextSearchRequest,
+    ) -> Result<TextSearchPage, RepositoryError> {
+        Ok(TextSearchPage::default())
+    }
+
+    async fn record_access(&self, _accesses: &[ObjectAccess]) -> Result<(), RepositoryError> {
+        Ok(())
+    }
+
+    async fn move_storage_class(
+        &self,
+        _content_hash: &ContentHash,
+        _from: StorageClass,
+        _to: StorageClass,
+    ) -> Result<u64, RepositoryError> {
+        Ok(0)
+    }
+
     async fn delete(&self, _id: &ObjectId) -> Result<(), RepositoryError> {
         Ok(())
     }
@@ -91,9 +256,24 @@ impl ObjectRepository for MockObjectRepository {
         Ok(vec![])
     }
 
+    async fn find_stuck_staged_objects(
+        &self,
+        _age_minutes: i64,
+        _limit: i64,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        Ok(vec![])
+    }
+
     async fn cleanup_stuck_uploads(&self, _age_hours: i64) -> Result<usize, RepositoryError> {
         Ok(0)
     }
+
+    async fn find_locked_hashes(
+        &self,
+        _content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        Ok(vec![])
+    }
 }
 
 struct MockBlobRepository {
@@ -129,6 +309,15 @@ impl BlobRepository for MockBlobRepository {
         Ok(blob)
     }
 
+    async fn reference_existing(
+        &self,
+        _content_hash: &ContentHash,
+        _storage_class: StorageClass,
+        _tenant_id: &TenantId,
+    ) -> Result<Option<Blob>, RepositoryError> {
+        Ok(None)
+    }
+
     async fn increment_ref(&self, _content_hash: &ContentHash) -> Result<(), RepositoryError> {
         Ok(())
     }
@@ -144,6 +333,47 @@ impl BlobRepository for MockBlobRepository {
     async fn delete(&self, _content_hash: &ContentHash) -> Result<(), RepositoryError> {
         Ok(())
     }
+
+    async fn move_storage_class(
+        &self,
+        _content_hash: &ContentHash,
+        _from: StorageClass,
+        _to: StorageClass,
+    ) -> Result<bool, RepositoryError> {
+        Ok(false)
+    }
+
+    async fn find_known(
+        &self,
+        _content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    async fn list_hashes(
+        &self,
+        _after: Option<ContentHash>,
+        _limit: i64,
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    async fn list(
+        &self,
+        _after: Option<ContentHash>,
+        _orphaned_only: bool,
+        _limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    async fn find_by_prefix(
+        &self,
+        _prefix: &ContentHashPrefix,
+        _limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        Ok(vec![])
+    }
 }
 
 fn http_handler_benchmarks(c: &mut Criterion) {
@@ -195,6 +425,10 @@ fn http_handler_benchmarks(c: &mut Criterion) {
                             tenant_id: Uuid::new_v4().to_string(),
                             key: Some(format!("key_{}", i)),
                             storage_class: Some(StorageClass::Hot),
+                            content_type: None,
+                            content_encoding: None,
+                            expected_hash: None,
+                            idempotency_key: None,
                         };
 
                         let _ = use_case.execute(request, reader).await;
@@ -273,6 +507,13 @@ fn http_handler_benchmarks(c: &mut Criterion) {
                     tenant_id: Uuid::new_v4().to_string(),
                     limit: Some(10),
                     offset: Some(0),
+                    sort_by: None,
+                    sort_direction: None,
+                    cursor: None,
+                    prefix: None,
+                    delimiter: None,
+                    metadata_filters: None,
+                    metadata_has_keys: None,
                 };
                 let _ = use_case.execute(request).await;
             }
@@ -0,0 +1,6 @@
+-- Supports the per-tenant deduplication statistics query (/v1/stats), which
+-- collects the distinct content hashes each tenant references.
+CREATE INDEX IF NOT EXISTS idx_objects_tenant_content_hash
+    ON objects(tenant_id, content_hash)
+    INCLUDE (size_bytes)
+    WHERE status = 'COMMITTED' AND content_hash IS NOT NULL;
@@ -0,0 +1,29 @@
+-- Full-text index over designated custom metadata (tag) values.
+--
+-- Which tag keys are indexed is configured per namespace
+-- (TEXT_SEARCH_METADATA_KEYS); the application builds the vector on every
+-- save. The key selection is not known to the database, so existing rows are
+-- backfilled from all their scalar tag values; their next save narrows the
+-- vector to the designated keys.
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS metadata_search TSVECTOR NOT NULL DEFAULT ''::tsvector;
+
+UPDATE objects
+SET metadata_search = to_tsvector('simple', tags.text)
+FROM (
+    SELECT objects.id, string_agg(tag.value #>> '{}', ' ') AS text
+    FROM objects,
+        jsonb_each(
+            CASE WHEN jsonb_typeof(metadata -> 'tags') = 'object'
+                THEN metadata -> 'tags'
+                ELSE '{}'::jsonb
+            END
+        ) AS tag
+    WHERE jsonb_typeof(tag.value) IN ('string', 'number', 'boolean')
+    GROUP BY objects.id
+) AS tags
+WHERE objects.id = tags.id;
+
+CREATE INDEX IF NOT EXISTS idx_objects_metadata_search
+    ON objects USING GIN (metadata_search)
+    WHERE status = 'COMMITTED';
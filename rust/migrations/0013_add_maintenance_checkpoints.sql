@@ -0,0 +1,7 @@
+-- Resume points for long-running maintenance tasks (e.g. refcount
+-- reconciliation), so an interrupted run continues where it stopped.
+CREATE TABLE IF NOT EXISTS maintenance_checkpoints (
+    task        TEXT PRIMARY KEY,
+    cursor      TEXT NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,14 @@
+-- Text extracted from object content on upload (see TEXT_EXTRACTOR).
+--
+-- Objects uploaded without a configured extractor, or with content types the
+-- extractor does not handle, keep a NULL extracted_text and an empty vector.
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS extracted_text TEXT;
+
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS content_search TSVECTOR
+    GENERATED ALWAYS AS (to_tsvector('simple', coalesce(extracted_text, ''))) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_objects_content_search
+    ON objects USING GIN (content_search)
+    WHERE status = 'COMMITTED';
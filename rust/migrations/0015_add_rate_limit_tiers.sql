@@ -0,0 +1,12 @@
+-- Rate limit tiers (e.g. free vs paid). Tenants without a tier use the
+-- configured default of authenticated requests per minute.
+CREATE TABLE IF NOT EXISTS rate_limit_tiers (
+    name                 TEXT PRIMARY KEY,
+    requests_per_minute  INTEGER NOT NULL CHECK (requests_per_minute > 0)
+);
+
+CREATE TABLE IF NOT EXISTS tenant_rate_limit_tiers (
+    tenant_id   TEXT PRIMARY KEY,
+    tier        TEXT NOT NULL REFERENCES rate_limit_tiers(name) ON UPDATE CASCADE,
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,11 @@
+-- Let the application build content_search with the configured text search
+-- config (TEXT_SEARCH_CONFIG) instead of the hard-coded 'simple'.
+--
+-- Existing vectors and the GIN index are kept. They were built with 'simple',
+-- which is also the default; after switching configs, rows pick up the new
+-- one the next time their extracted text is written.
+ALTER TABLE objects
+ALTER COLUMN content_search DROP EXPRESSION;
+
+ALTER TABLE objects
+ALTER COLUMN content_search SET DEFAULT ''::tsvector;
@@ -0,0 +1,22 @@
+-- Download statistics per object, written in batches by the access recorder.
+--
+-- last_access_at already exists (003) but was never written; it now holds
+-- the time of the latest recorded download.
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS download_count BIGINT NOT NULL DEFAULT 0;
+
+-- Sorting listings by popularity
+CREATE INDEX IF NOT EXISTS idx_objects_tenant_ns_download_count
+    ON objects(tenant_id, namespace, download_count DESC)
+    WHERE status = 'COMMITTED';
+
+-- Recording an access must not count as modifying the object: only bump
+-- updated_at when a column other than the access statistics is written.
+DROP TRIGGER IF EXISTS update_objects_updated_at ON objects;
+
+CREATE TRIGGER update_objects_updated_at
+    BEFORE UPDATE OF namespace, tenant_id, key, status, storage_class,
+                     content_hash, size_bytes, content_type, metadata,
+                     metadata_search, extracted_text, content_search
+    ON objects
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,30 @@
+-- Uploads now stage their content hash on the WRITING row once the blob is
+-- written and its reference taken. Cleaning up a stuck staged upload must
+-- release that reference, or the blob is never garbage collected.
+CREATE OR REPLACE FUNCTION cleanup_stuck_uploads(p_age_hours BIGINT DEFAULT 1)
+RETURNS BIGINT AS $$
+DECLARE
+    v_deleted BIGINT;
+BEGIN
+    WITH deleted AS (
+        DELETE FROM objects
+        WHERE status = 'WRITING'
+          AND created_at < now() - (p_age_hours || ' hours')::interval
+        RETURNING id, content_hash
+    ),
+    released AS (
+        SELECT content_hash, COUNT(*) AS refs
+        FROM deleted
+        WHERE content_hash IS NOT NULL
+        GROUP BY content_hash
+    ),
+    updated AS (
+        UPDATE blobs b
+        SET ref_count = GREATEST(b.ref_count - r.refs, 0)
+        FROM released r
+        WHERE b.content_hash = r.content_hash
+    )
+    SELECT COUNT(*) INTO v_deleted FROM deleted;
+    RETURN v_deleted;
+END;
+$$ LANGUAGE plpgsql;
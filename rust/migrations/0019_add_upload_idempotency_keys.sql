@@ -0,0 +1,18 @@
+-- Idempotency keys for uploads: a retried upload with the same key and
+-- content returns the object the first attempt created. Rows are short-lived
+-- and removed once expired.
+CREATE TABLE IF NOT EXISTS upload_idempotency_keys (
+    tenant_id        TEXT NOT NULL,
+    idempotency_key  TEXT NOT NULL,
+    namespace        TEXT NOT NULL,
+    object_key       TEXT,
+    -- NULL until the first upload commits
+    object_id        UUID,
+    content_hash     TEXT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at       TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tenant_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_upload_idempotency_keys_expires_at
+    ON upload_idempotency_keys(expires_at);
@@ -0,0 +1,14 @@
+-- Operator defaults per namespace: the storage class for uploads that do not
+-- name one, and an optional hot-to-cold tiering policy.
+CREATE TABLE IF NOT EXISTS namespace_configs (
+    namespace              TEXT PRIMARY KEY,
+    default_storage_class  TEXT NOT NULL CHECK (default_storage_class IN ('hot', 'cold')),
+    -- Objects not downloaded for this many days belong in cold storage
+    cold_after_days        INTEGER CHECK (cold_after_days > 0),
+    created_at             TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TRIGGER update_namespace_configs_updated_at
+    BEFORE UPDATE ON namespace_configs
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
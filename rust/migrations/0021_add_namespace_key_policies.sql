@@ -0,0 +1,7 @@
+-- Per-namespace constraints on object keys, enforced at upload. NULL leaves
+-- a constraint unset.
+ALTER TABLE namespace_configs
+    ADD COLUMN IF NOT EXISTS key_max_length INTEGER CHECK (key_max_length > 0),
+    -- Allowed characters and x-y ranges, e.g. 'a-z0-9._/-'
+    ADD COLUMN IF NOT EXISTS key_allowed_chars TEXT,
+    ADD COLUMN IF NOT EXISTS key_required_prefix TEXT;
@@ -0,0 +1,10 @@
+-- API keys are stored as salted hashes, which cannot be looked up by value.
+-- Instead a key's record is found by the first characters of the key, kept
+-- in clear, and the secret is then checked against the hash.
+--
+-- Keys hashed before this (unsalted SHA-256, see 0010) have no prefix. They
+-- are still found by their digest and get a prefix and salted hash the next
+-- time they are used.
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS key_prefix TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_api_keys_prefix ON api_keys(key_prefix);
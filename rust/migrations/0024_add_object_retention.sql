@@ -0,0 +1,13 @@
+-- Write-once (WORM) locks: an object can't be overwritten or deleted before
+-- retention_until, nor at all while under legal hold. Retention is only ever
+-- extended, and the garbage collector leaves locked objects' blobs alone.
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS retention_until TIMESTAMPTZ;
+
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS legal_hold BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Finding the locked objects that reference a blob
+CREATE INDEX IF NOT EXISTS idx_objects_locked_content_hash
+    ON objects(content_hash)
+    WHERE legal_hold OR retention_until IS NOT NULL;
@@ -0,0 +1,6 @@
+-- Compression the stored bytes were uploaded with (Content-Encoding).
+-- content_hash and size_bytes describe the stored, encoded bytes; downloads
+-- decode them for clients that don't accept the encoding.
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS content_encoding TEXT
+    CHECK (content_encoding IN ('gzip', 'zstd'));
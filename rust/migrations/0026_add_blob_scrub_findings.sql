@@ -0,0 +1,13 @@
+-- Blobs the integrity scrubber could not verify against their content hash.
+-- Findings are for operator action only: nothing is deleted or repaired, and
+-- a row is dropped once its blob verifies again.
+CREATE TABLE IF NOT EXISTS blob_scrub_findings (
+    content_hash   TEXT PRIMARY KEY,
+    storage_class  TEXT NOT NULL CHECK (storage_class IN ('hot', 'cold')),
+    error          TEXT NOT NULL,
+    detected_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_seen_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_blob_scrub_findings_last_seen
+    ON blob_scrub_findings(last_seen_at DESC);
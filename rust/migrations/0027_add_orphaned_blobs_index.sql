@@ -0,0 +1,6 @@
+-- Supports paging through orphaned blobs in hash order (GET /v1/admin/blobs
+-- with orphaned=true) without scanning the referenced ones. The full listing
+-- pages on the primary key.
+CREATE INDEX IF NOT EXISTS idx_blobs_orphaned
+    ON blobs(content_hash)
+    WHERE ref_count = 0;
@@ -0,0 +1,20 @@
+-- Grants sharing a committed object with another tenant. The grantee gets an
+-- object of its own that references the same blob (taking a blob reference),
+-- so the blob stays until both the original and every shared copy are gone.
+-- Revoking a grant deletes the grantee's object and drops the row.
+CREATE TABLE IF NOT EXISTS object_shares (
+    id                 UUID PRIMARY KEY,
+    object_id          UUID NOT NULL,
+    owner_tenant_id    TEXT NOT NULL,
+    grantee_tenant_id  TEXT NOT NULL,
+    grantee_object_id  UUID NOT NULL UNIQUE,
+    grantee_namespace  TEXT NOT NULL,
+    grantee_key        TEXT,
+    content_hash       TEXT NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_object_shares_object
+    ON object_shares(object_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_object_shares_grantee
+    ON object_shares(grantee_tenant_id);
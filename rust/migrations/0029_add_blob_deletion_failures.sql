@@ -0,0 +1,19 @@
+-- Orphaned blobs whose blob store file the garbage collector could not
+-- delete. The blob row is kept so the file is not leaked; the collector skips
+-- the blob until next_attempt_at and retries it with a growing backoff. Once
+-- retries are exhausted the row is dead-lettered and the blob is left alone
+-- until an operator removes the file and deletes this row.
+CREATE TABLE IF NOT EXISTS blob_deletion_failures (
+    content_hash      TEXT PRIMARY KEY REFERENCES blobs(content_hash) ON DELETE CASCADE,
+    storage_class     TEXT NOT NULL CHECK (storage_class IN ('hot', 'cold')),
+    attempts          INTEGER NOT NULL,
+    last_error        TEXT NOT NULL,
+    next_attempt_at   TIMESTAMPTZ,
+    dead_lettered_at  TIMESTAMPTZ,
+    first_failed_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_failed_at    TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_blob_deletion_failures_dead_lettered
+    ON blob_deletion_failures(dead_lettered_at)
+    WHERE dead_lettered_at IS NOT NULL;
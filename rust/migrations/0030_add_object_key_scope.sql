@@ -0,0 +1,18 @@
+-- Configurable key uniqueness (KEY_UNIQUENESS). A key is unique among the
+-- live objects of its tenant that share its key_scope: the namespace for
+-- per-namespace keys, '' for keys unique across the tenant, and NULL for
+-- objects whose key may repeat. The scope is written when an object is
+-- created, so changing KEY_UNIQUENESS only applies to new objects; see the
+-- README for moving existing objects to another scope.
+ALTER TABLE objects ADD COLUMN IF NOT EXISTS key_scope TEXT;
+
+-- Existing keys were unique per namespace
+UPDATE objects SET key_scope = namespace
+WHERE key IS NOT NULL AND key_scope IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS unique_key_per_tenant_scope
+    ON objects(tenant_id, key_scope, key)
+    WHERE key IS NOT NULL AND key_scope IS NOT NULL AND status != 'DELETED';
+
+-- Superseded by unique_key_per_tenant_scope
+DROP INDEX IF EXISTS unique_key_per_tenant_ns;
@@ -0,0 +1,7 @@
+-- Grants are offered by the owner and accepted by the grantee; the grantee's
+-- object only exists once the grant is accepted. Grants made before this
+-- created their object right away and count as accepted.
+ALTER TABLE object_shares ALTER COLUMN grantee_object_id DROP NOT NULL;
+ALTER TABLE object_shares ADD COLUMN IF NOT EXISTS accepted_at TIMESTAMPTZ;
+
+UPDATE object_shares SET accepted_at = created_at WHERE accepted_at IS NULL;
@@ -0,0 +1,5 @@
+-- Serves `key LIKE 'prefix%'` for hierarchical listings. text_pattern_ops
+-- lets the btree answer prefix matches whatever the database collation.
+CREATE INDEX IF NOT EXISTS idx_objects_key_prefix
+    ON objects(namespace, tenant_id, key text_pattern_ops)
+    WHERE status = 'COMMITTED';
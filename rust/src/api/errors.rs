@@ -1,21 +1,41 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
+use std::backtrace::Backtrace;
+use std::sync::Arc;
 
+use crate::api::middleware::validation::{FieldError, ValidationErrorResponse};
 use crate::application::{
     errors::{
-        DeleteUseCaseError, DownloadUseCaseError, ObjectUseCaseError, TextSearchUseCaseError,
+        DeleteUseCaseError, DownloadUseCaseError, GhostObjectPolicy, ObjectUseCaseError,
+        TextSearchUseCaseError,
     },
+    ports::StorageError,
     use_cases::ApiKeyUseCaseError,
+    webhooks::WebhookError,
 };
+use crate::domain::errors::DomainError;
+
+/// Backtrace of where a server error was raised, carried as a response
+/// extension for the error handling middleware
+///
+/// Only captured when `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` is set.
+#[derive(Debug, Clone)]
+pub struct ErrorBacktrace(pub Arc<Backtrace>);
 
 /// API error response
 pub struct ApiError {
     status: StatusCode,
     message: String,
+    /// Per-field reasons; when present the body is a `ValidationErrorResponse`
+    field_errors: Vec<FieldError>,
+    /// Seconds the client should wait before retrying, sent as `Retry-After`
+    retry_after: Option<u64>,
+    /// Where a server error was raised
+    backtrace: Option<ErrorBacktrace>,
 }
 
 impl ApiError {
@@ -23,6 +43,19 @@ impl ApiError {
         Self {
             status,
             message: message.into(),
+            field_errors: Vec::new(),
+            retry_after: None,
+            backtrace: status
+                .is_server_error()
+                .then(|| ErrorBacktrace(Arc::new(Backtrace::capture()))),
+        }
+    }
+
+    /// Validation failure listing each invalid field
+    pub fn validation(status: StatusCode, field_errors: Vec<FieldError>) -> Self {
+        Self {
+            field_errors,
+            ..Self::new(status, "Validation failed")
         }
     }
 
@@ -30,6 +63,10 @@ impl ApiError {
         Self::new(StatusCode::BAD_REQUEST, message)
     }
 
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, message)
+    }
+
     pub fn not_found(message: impl Into<String>) -> Self {
         Self::new(StatusCode::NOT_FOUND, message)
     }
@@ -38,6 +75,10 @@ impl ApiError {
         Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
     }
 
+    pub fn gone(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::GONE, message)
+    }
+
     pub fn conflict(message: impl Into<String>) -> Self {
         Self::new(StatusCode::CONFLICT, message)
     }
@@ -45,10 +86,61 @@ impl ApiError {
     pub fn service_unavailable(message: impl Into<String>) -> Self {
         Self::new(StatusCode::SERVICE_UNAVAILABLE, message)
     }
+
+    /// Ask the client to retry after `secs` seconds
+    pub fn with_retry_after(mut self, secs: u64) -> Self {
+        self.retry_after = Some(secs);
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn field_errors(&self) -> &[FieldError] {
+        &self.field_errors
+    }
+
+    pub fn retry_after(&self) -> Option<u64> {
+        self.retry_after
+    }
 }
 
 impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
+    fn into_response(mut self) -> Response {
+        let backtrace = self.backtrace.take();
+        let mut response = self.into_body_response();
+        if let Some(backtrace) = backtrace {
+            response.extensions_mut().insert(backtrace);
+        }
+        response
+    }
+}
+
+impl ApiError {
+    fn into_body_response(self) -> Response {
+        if !self.field_errors.is_empty() {
+            let body = Json(ValidationErrorResponse::new(self.field_errors));
+            return (self.status, body).into_response();
+        }
+
+        if let Some(retry_after) = self.retry_after {
+            let body = Json(json!({
+                "error": self.message,
+                "retry_after": retry_after,
+            }));
+            return (
+                self.status,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                body,
+            )
+                .into_response();
+        }
+
         let body = Json(json!({
             "error": self.message,
         }));
@@ -57,17 +149,55 @@ impl IntoResponse for ApiError {
     }
 }
 
+impl From<(StatusCode, ValidationErrorResponse)> for ApiError {
+    fn from((status, response): (StatusCode, ValidationErrorResponse)) -> Self {
+        Self::validation(status, response.errors)
+    }
+}
+
+impl From<DomainError> for ApiError {
+    fn from(err: DomainError) -> Self {
+        match err {
+            DomainError::ValidationError { field, message } => Self::validation(
+                StatusCode::BAD_REQUEST,
+                vec![FieldError::new(field, message)],
+            ),
+            e @ DomainError::SizeExceedsMaximum { .. } => {
+                Self::new(StatusCode::PAYLOAD_TOO_LARGE, e.to_string())
+            }
+            e @ (DomainError::ObjectLocked(_) | DomainError::RetentionShortened(_)) => {
+                Self::forbidden(e.to_string())
+            }
+            e @ DomainError::AlreadyExists(_) => Self::conflict(e.to_string()),
+            e => Self::bad_request(e.to_string()),
+        }
+    }
+}
+
 // Convert use case errors to API errors
 
 impl From<ObjectUseCaseError> for ApiError {
     fn from(err: ObjectUseCaseError) -> Self {
         match err {
             ObjectUseCaseError::InvalidRequest(msg) => Self::bad_request(msg),
-            ObjectUseCaseError::Domain(e) => Self::bad_request(e.to_string()),
+            ObjectUseCaseError::Validation(e) => Self::validation(
+                StatusCode::BAD_REQUEST,
+                vec![FieldError::new(e.field(), e.to_string())],
+            ),
+            ObjectUseCaseError::Domain(e) => e.into(),
             ObjectUseCaseError::Repository(e) => {
                 Self::internal_error(format!("Repository error: {e}"))
             }
-            ObjectUseCaseError::Storage(e) => Self::internal_error(format!("Storage error: {e}")),
+            ObjectUseCaseError::Storage(e) => e.into(),
+            ObjectUseCaseError::PreconditionFailed(msg) => {
+                Self::new(StatusCode::PRECONDITION_FAILED, msg)
+            }
+            ObjectUseCaseError::NotFound(msg) => Self::not_found(msg),
+            ObjectUseCaseError::Conflict(msg) => Self::conflict(msg),
+            e @ ObjectUseCaseError::ContentRejected(_) => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            e @ ObjectUseCaseError::ScanFailed(_) => Self::service_unavailable(e.to_string()),
         }
     }
 }
@@ -82,7 +212,14 @@ impl From<DownloadUseCaseError> for ApiError {
             DownloadUseCaseError::Repository(e) => {
                 Self::internal_error(format!("Repository error: {e}"))
             }
-            DownloadUseCaseError::Storage(e) => Self::internal_error(format!("Storage error: {e}")),
+            DownloadUseCaseError::Storage(e) => e.into(),
+            ghost @ DownloadUseCaseError::GhostObject { policy, .. } => match policy {
+                GhostObjectPolicy::Gone => Self::gone(ghost.to_string()),
+                GhostObjectPolicy::InternalError => Self::internal_error(ghost.to_string()),
+            },
+            restoring @ DownloadUseCaseError::Restoring {
+                retry_after_secs, ..
+            } => Self::conflict(restoring.to_string()).with_retry_after(retry_after_secs),
         }
     }
 }
@@ -90,12 +227,26 @@ impl From<DownloadUseCaseError> for ApiError {
 impl From<DeleteUseCaseError> for ApiError {
     fn from(err: DeleteUseCaseError) -> Self {
         match err {
+            DeleteUseCaseError::Domain(e @ DomainError::ObjectLocked(_)) => e.into(),
             DeleteUseCaseError::Domain(e) => Self::internal_error(format!("Domain error: {e}")),
             DeleteUseCaseError::NotFound(msg) => Self::not_found(msg),
+            DeleteUseCaseError::Conflict(msg) => Self::conflict(msg),
             DeleteUseCaseError::Repository(e) => {
                 Self::internal_error(format!("Repository error: {e}"))
             }
-            DeleteUseCaseError::Storage(e) => Self::internal_error(format!("Storage error: {e}")),
+            DeleteUseCaseError::Storage(e) => e.into(),
+        }
+    }
+}
+
+impl From<StorageError> for ApiError {
+    fn from(err: StorageError) -> Self {
+        match err {
+            unavailable @ StorageError::Unavailable { retry_after_secs } => {
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, unavailable.to_string())
+                    .with_retry_after(retry_after_secs)
+            }
+            e => Self::internal_error(format!("Storage error: {e}")),
         }
     }
 }
@@ -106,7 +257,7 @@ impl From<TextSearchUseCaseError> for ApiError {
     fn from(err: TextSearchUseCaseError) -> Self {
         match err {
             TextSearchUseCaseError::InvalidRequest(msg) => Self::bad_request(msg),
-            TextSearchUseCaseError::Domain(e) => Self::bad_request(e.to_string()),
+            TextSearchUseCaseError::Domain(e) => e.into(),
             TextSearchUseCaseError::Repository(e) => {
                 Self::internal_error(format!("Repository error: {e}"))
             }
@@ -127,3 +278,12 @@ impl From<ApiKeyUseCaseError> for ApiError {
         }
     }
 }
+
+impl From<WebhookError> for ApiError {
+    fn from(err: WebhookError) -> Self {
+        match err {
+            WebhookError::InvalidSignature => Self::new(StatusCode::UNAUTHORIZED, err.to_string()),
+            WebhookError::StaleTimestamp { .. } => Self::bad_request(err.to_string()),
+        }
+    }
+}
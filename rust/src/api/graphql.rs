@@ -0,0 +1,580 @@
+//! Read-only GraphQL API (`graphql` feature)
+//!
+//! Served at `/graphql` behind the same middleware stack as the REST routes.
+//! Resolvers call the existing use cases and apply the REST handlers'
+//! permission and tenant checks to the caller's `UserContext`.
+
+use std::sync::Arc;
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Enum, ErrorExtensions, InputObject, Object, Schema,
+    SimpleObject, ID,
+};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{extract::State, Extension};
+use validator::Validate;
+
+use crate::api::errors::ApiError;
+use crate::api::middleware::validation::{validate_and_respond, VALIDATION_ERROR_CODE};
+use crate::application::dto::{
+    DedupStats, ListRequest, ListResponse, ObjectDto, SearchRequest, SearchResponse, StatsResponse,
+    TenantDedupStats, TextSearchHit, TextSearchRequest, TextSearchResponse,
+};
+use crate::application::use_cases::{
+    DownloadObjectUseCase, ListObjectsUseCase, SearchObjectsUseCase, StatsUseCase,
+    TextSearchObjectsUseCase,
+};
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::ObjectId;
+
+pub type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema over the use cases the REST routes use
+pub fn build_schema(
+    download_use_case: Arc<DownloadObjectUseCase>,
+    list_use_case: Arc<ListObjectsUseCase>,
+    search_use_case: Arc<SearchObjectsUseCase>,
+    text_search_use_case: Arc<TextSearchObjectsUseCase>,
+    stats_use_case: Arc<StatsUseCase>,
+) -> ApiSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(download_use_case)
+        .data(list_use_case)
+        .data(search_use_case)
+        .data(text_search_use_case)
+        .data(stats_use_case)
+        .finish()
+}
+
+/// POST /graphql
+pub async fn graphql_handler(
+    State(schema): State<ApiSchema>,
+    Extension(user_context): Extension<UserContext>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema
+        .execute(request.into_inner().data(user_context))
+        .await
+        .into()
+}
+
+/// Convert a use case error into a GraphQL error carrying the REST status code
+///
+/// Validation failures also carry the REST `code` and per-field `errors`.
+fn to_gql_error(err: impl Into<ApiError>) -> async_graphql::Error {
+    let err = err.into();
+    async_graphql::Error::new(err.message()).extend_with(|_, e| {
+        e.set("status", err.status().as_u16());
+        if !err.field_errors().is_empty() {
+            e.set("code", VALIDATION_ERROR_CODE);
+            if let Ok(errors) = async_graphql::Value::from_json(
+                serde_json::to_value(err.field_errors()).unwrap_or_default(),
+            ) {
+                e.set("errors", errors);
+            }
+        }
+    })
+}
+
+/// The caller, if they may read objects of `tenant_id`
+///
+/// Admins may read any tenant; everyone else only their own.
+fn authorize_read<'a>(
+    ctx: &'a Context<'_>,
+    tenant_id: &str,
+) -> async_graphql::Result<&'a UserContext> {
+    let user_context = ctx.data::<UserContext>()?;
+    if !user_context.can_read_objects() {
+        return Err(to_gql_error(ApiError::forbidden(
+            "Object read access required",
+        )));
+    }
+    if !user_context.is_admin() && tenant_id != user_context.tenant_id {
+        return Err(to_gql_error(ApiError::forbidden(
+            "Cannot read objects from other tenants",
+        )));
+    }
+    Ok(user_context)
+}
+
+fn validate(request: &impl Validate) -> async_graphql::Result<()> {
+    validate_and_respond(request).map_err(to_gql_error)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// A committed object by ID
+    async fn object(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        tenant_id: String,
+    ) -> async_graphql::Result<Option<GqlObject>> {
+        authorize_read(ctx, &tenant_id)?;
+        let Ok(object_id) = id.parse::<ObjectId>() else {
+            return Err(to_gql_error(ApiError::bad_request("Invalid object ID")));
+        };
+
+        let use_case = ctx.data::<Arc<DownloadObjectUseCase>>()?;
+        match use_case.metadata_by_id(&object_id, &tenant_id).await {
+            Ok(object) => Ok(Some(object.into())),
+            Err(crate::application::errors::DownloadUseCaseError::NotFound(_)) => Ok(None),
+            Err(e) => Err(to_gql_error(e)),
+        }
+    }
+
+    /// Committed objects of a namespace and tenant, one page at a time
+    #[allow(clippy::too_many_arguments)]
+    async fn objects(
+        &self,
+        ctx: &Context<'_>,
+        namespace: String,
+        tenant_id: String,
+        #[graphql(default = 100)] limit: i64,
+        #[graphql(default = 0)] offset: i64,
+        sort_by: Option<GqlSortField>,
+        sort_direction: Option<GqlSortDirection>,
+        prefix: Option<String>,
+        delimiter: Option<String>,
+    ) -> async_graphql::Result<ObjectPage> {
+        authorize_read(ctx, &tenant_id)?;
+
+        let request = ListRequest {
+            namespace,
+            tenant_id,
+            limit: Some(limit),
+            offset: Some(offset),
+            sort_by: sort_by.map(Into::into),
+            sort_direction: sort_direction.map(Into::into),
+            cursor: None,
+            prefix,
+            delimiter,
+            metadata_filters: None,
+            metadata_has_keys: None,
+        };
+        let use_case = ctx.data::<Arc<ListObjectsUseCase>>()?;
+        let response = use_case.execute(request).await.map_err(to_gql_error)?;
+
+        Ok(response.into())
+    }
+
+    /// Committed objects matching filters
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        filter: SearchFilter,
+    ) -> async_graphql::Result<SearchPage> {
+        authorize_read(ctx, &filter.tenant_id)?;
+
+        let request = SearchRequest::from(filter);
+        validate(&request)?;
+        let use_case = ctx.data::<Arc<SearchObjectsUseCase>>()?;
+        let response = use_case.execute(request).await.map_err(to_gql_error)?;
+
+        Ok(response.into())
+    }
+
+    /// Full-text search over keys, metadata and extracted content
+    async fn text_search(
+        &self,
+        ctx: &Context<'_>,
+        namespace: String,
+        tenant_id: String,
+        query: String,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> async_graphql::Result<TextSearchPage> {
+        authorize_read(ctx, &tenant_id)?;
+
+        let request = TextSearchRequest {
+            namespace,
+            tenant_id,
+            limit,
+            offset,
+            query,
+            search_in_metadata: None,
+            search_in_key: None,
+            search_in_custom_metadata: None,
+            search_in_content: None,
+            prefix: None,
+            min_rank: None,
+        };
+        validate(&request)?;
+        let use_case = ctx.data::<Arc<TextSearchObjectsUseCase>>()?;
+        let response = use_case.execute(request).await.map_err(to_gql_error)?;
+
+        Ok(response.into())
+    }
+
+    /// Deduplication statistics, admin only
+    async fn stats(&self, ctx: &Context<'_>) -> async_graphql::Result<Stats> {
+        if !ctx.data::<UserContext>()?.is_admin() {
+            return Err(to_gql_error(ApiError::forbidden("Admin access required")));
+        }
+
+        let use_case = ctx.data::<Arc<StatsUseCase>>()?;
+        let response = use_case.execute().await.map_err(to_gql_error)?;
+
+        Ok(response.into())
+    }
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(
+    name = "StorageClass",
+    remote = "crate::domain::value_objects::StorageClass"
+)]
+pub enum GqlStorageClass {
+    Hot,
+    Cold,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(
+    name = "ObjectStatus",
+    remote = "crate::domain::value_objects::ObjectStatus"
+)]
+pub enum GqlObjectStatus {
+    Writing,
+    Committed,
+    Deleting,
+    Deleted,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(name = "SortField", remote = "crate::application::dto::SortField")]
+pub enum GqlSortField {
+    CreatedAt,
+    UpdatedAt,
+    SizeBytes,
+    Key,
+    ContentType,
+    DownloadCount,
+    LastAccessedAt,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(
+    name = "SortDirection",
+    remote = "crate::application::dto::SortDirection"
+)]
+pub enum GqlSortDirection {
+    Asc,
+    Desc,
+}
+
+/// A stored object with its metadata
+#[derive(SimpleObject)]
+#[graphql(name = "Object")]
+pub struct GqlObject {
+    id: ID,
+    namespace: String,
+    tenant_id: String,
+    key: Option<String>,
+    status: GqlObjectStatus,
+    storage_class: GqlStorageClass,
+    content_hash: Option<String>,
+    size_bytes: Option<u64>,
+    content_type: Option<String>,
+    /// Full metadata document
+    metadata: async_graphql::Json<serde_json::Value>,
+    /// Custom tags from the metadata
+    tags: async_graphql::Json<serde_json::Value>,
+    created_at: String,
+    updated_at: String,
+    download_count: u64,
+    last_accessed_at: Option<String>,
+}
+
+impl From<ObjectDto> for GqlObject {
+    fn from(dto: ObjectDto) -> Self {
+        Self {
+            id: ID(dto.id),
+            namespace: dto.namespace,
+            tenant_id: dto.tenant_id,
+            key: dto.key,
+            status: dto.status.into(),
+            storage_class: dto.storage_class.into(),
+            content_hash: dto.content_hash,
+            size_bytes: dto.size_bytes,
+            content_type: dto.content_type,
+            metadata: async_graphql::Json(dto.metadata.to_json().unwrap_or_default()),
+            tags: async_graphql::Json(serde_json::to_value(&dto.metadata.tags).unwrap_or_default()),
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+            download_count: dto.download_count,
+            last_accessed_at: dto.last_accessed_at,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ObjectPage {
+    objects: Vec<GqlObject>,
+    /// Objects matching the listing, across all pages
+    total: usize,
+    /// False when counting stopped early and `total` is a lower bound
+    total_exact: bool,
+    limit: i64,
+    offset: i64,
+    has_more: bool,
+    /// Key prefixes grouping further objects when a delimiter is given
+    common_prefixes: Vec<String>,
+}
+
+impl From<ListResponse> for ObjectPage {
+    fn from(response: ListResponse) -> Self {
+        Self {
+            objects: response.objects.into_iter().map(Into::into).collect(),
+            total: response.total,
+            total_exact: response.total_exact,
+            limit: response.limit,
+            offset: response.offset,
+            has_more: response.has_more,
+            common_prefixes: response.common_prefixes,
+        }
+    }
+}
+
+/// Filters for `search`; mirrors the REST search request
+#[derive(InputObject)]
+pub struct SearchFilter {
+    namespace: String,
+    tenant_id: String,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    sort_by: Option<GqlSortField>,
+    sort_direction: Option<GqlSortDirection>,
+    key_contains: Option<String>,
+    /// Keep only keys starting with this prefix
+    key_prefix: Option<String>,
+    content_type: Option<String>,
+    storage_class: Option<GqlStorageClass>,
+    min_size_bytes: Option<u64>,
+    max_size_bytes: Option<u64>,
+    /// Dotted metadata paths that must exist, e.g. `tags.author`
+    metadata_has_keys: Option<Vec<String>>,
+}
+
+impl From<SearchFilter> for SearchRequest {
+    fn from(filter: SearchFilter) -> Self {
+        let size_range = (filter.min_size_bytes.is_some() || filter.max_size_bytes.is_some())
+            .then_some(crate::application::dto::SizeRange {
+                min: filter.min_size_bytes,
+                max: filter.max_size_bytes,
+            });
+        Self {
+            namespace: filter.namespace,
+            tenant_id: filter.tenant_id,
+            limit: filter.limit,
+            offset: filter.offset,
+            sort_by: filter.sort_by.map(Into::into),
+            sort_direction: filter.sort_direction.map(Into::into),
+            key_contains: filter.key_contains,
+            key_prefix: filter.key_prefix,
+            content_type: filter.content_type,
+            storage_class: filter.storage_class.map(Into::into),
+            size_range,
+            created_at_range: None,
+            updated_at_range: None,
+            metadata_filters: None,
+            metadata_has_keys: filter.metadata_has_keys,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct SearchPage {
+    objects: Vec<GqlObject>,
+    total: usize,
+    limit: i64,
+    offset: i64,
+}
+
+impl From<SearchResponse> for SearchPage {
+    fn from(response: SearchResponse) -> Self {
+        Self {
+            objects: response.objects.into_iter().map(Into::into).collect(),
+            total: response.total,
+            limit: response.limit,
+            offset: response.offset,
+        }
+    }
+}
+
+/// A ranked full-text match
+#[derive(SimpleObject)]
+pub struct TextSearchMatch {
+    object: GqlObject,
+    rank: f32,
+    /// Matching excerpt with matched words wrapped in `<mark>`; not HTML-escaped
+    highlight: Option<String>,
+}
+
+impl From<TextSearchHit> for TextSearchMatch {
+    fn from(hit: TextSearchHit) -> Self {
+        Self {
+            object: hit.object.into(),
+            rank: hit.rank,
+            highlight: hit.highlight,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TextSearchPage {
+    matches: Vec<TextSearchMatch>,
+    total: usize,
+    limit: i64,
+    offset: i64,
+}
+
+impl From<TextSearchResponse> for TextSearchPage {
+    fn from(response: TextSearchResponse) -> Self {
+        Self {
+            matches: response.objects.into_iter().map(Into::into).collect(),
+            total: response.total,
+            limit: response.limit,
+            offset: response.offset,
+        }
+    }
+}
+
+/// Logical vs physical storage usage
+#[derive(SimpleObject)]
+pub struct UsageStats {
+    object_count: i64,
+    logical_bytes: i64,
+    blob_count: i64,
+    physical_bytes: i64,
+    bytes_saved: i64,
+    dedup_ratio: f64,
+}
+
+impl From<DedupStats> for UsageStats {
+    fn from(stats: DedupStats) -> Self {
+        Self {
+            object_count: stats.object_count,
+            logical_bytes: stats.logical_bytes,
+            blob_count: stats.blob_count,
+            physical_bytes: stats.physical_bytes,
+            bytes_saved: stats.bytes_saved,
+            dedup_ratio: stats.dedup_ratio,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TenantUsageStats {
+    tenant_id: String,
+    stats: UsageStats,
+}
+
+impl From<TenantDedupStats> for TenantUsageStats {
+    fn from(tenant: TenantDedupStats) -> Self {
+        Self {
+            tenant_id: tenant.tenant_id,
+            stats: tenant.stats.into(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Stats {
+    global: UsageStats,
+    tenants: Vec<TenantUsageStats>,
+    generated_at: String,
+}
+
+impl From<StatsResponse> for Stats {
+    fn from(response: StatsResponse) -> Self {
+        Self {
+            global: response.global.into(),
+            tenants: response.tenants.into_iter().map(Into::into).collect(),
+            generated_at: response.generated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockObjectRepository;
+
+    fn schema() -> ApiSchema {
+        // No repository expectations: any resolver reaching a use case panics
+        let object_repo = Arc::new(MockObjectRepository::new());
+        build_schema(
+            Arc::new(DownloadObjectUseCase::new(
+                object_repo.clone(),
+                Arc::new(crate::application::ports::MockBlobStore::new()),
+            )),
+            Arc::new(ListObjectsUseCase::new(object_repo.clone())),
+            Arc::new(SearchObjectsUseCase::new(object_repo.clone())),
+            Arc::new(TextSearchObjectsUseCase::new(object_repo)),
+            Arc::new(StatsUseCase::new(Arc::new(
+                crate::application::ports::MockStatsRepository::new(),
+            ))),
+        )
+    }
+
+    fn user(tenant_id: &str, permissions: &[&str]) -> UserContext {
+        UserContext::new(
+            "user".to_string(),
+            tenant_id.to_string(),
+            vec![],
+            permissions.iter().map(|p| p.to_string()).collect(),
+            false,
+            None,
+        )
+    }
+
+    async fn execute(query: &str, user_context: UserContext) -> async_graphql::Response {
+        schema()
+            .execute(async_graphql::Request::new(query).data(user_context))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_objects_rejects_other_tenants() {
+        let response = execute(
+            r#"{ objects(namespace: "models", tenantId: "tenant-b") { total } }"#,
+            user(
+                "tenant-a",
+                &[crate::domain::authorization::permissions::OBJECTS_READ],
+            ),
+        )
+        .await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("other tenants"));
+    }
+
+    #[tokio::test]
+    async fn test_objects_requires_read_permission() {
+        let response = execute(
+            r#"{ objects(namespace: "models", tenantId: "tenant-a") { total } }"#,
+            user("tenant-a", &[]),
+        )
+        .await;
+
+        assert_eq!(response.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stats_requires_admin() {
+        let response = execute(
+            "{ stats { generatedAt } }",
+            user(
+                "tenant-a",
+                &[crate::domain::authorization::permissions::OBJECTS_READ],
+            ),
+        )
+        .await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("Admin"));
+    }
+}
@@ -3,29 +3,20 @@ use axum::{
     http::StatusCode,
     response::Json,
 };
-use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::api::errors::ApiError;
+use crate::api::handlers::pagination::PageParams;
 use crate::api::middleware::validation::validate_and_respond;
 use crate::application::{
     dto::{ApiKeyDto, ApiKeyListResponse, CreateApiKeyRequest, UpdateApiKeyRequest},
     use_cases::{
         CreateApiKeyUseCase, DeleteApiKeyUseCase, GetApiKeyUseCase, ListApiKeysUseCase,
-        UpdateApiKeyUseCase,
+        RotateApiKeyUseCase, UpdateApiKeyUseCase,
     },
 };
 use crate::domain::authorization::UserContext;
 
-/// Query parameters for listing API keys
-#[derive(Deserialize, utoipa::ToSchema)]
-pub struct ListApiKeysQuery {
-    /// Number of results per page (default: 50, max: 100)
-    limit: Option<i64>,
-    /// Pagination offset (default: 0)
-    offset: Option<i64>,
-}
-
 /// POST /v1/api-keys
 /// Create a new API key
 #[utoipa::path(
@@ -47,13 +38,7 @@ pub async fn create_api_key_handler(
     Json(request): Json<CreateApiKeyRequest>,
 ) -> Result<(StatusCode, Json<ApiKeyDto>), ApiError> {
     // Validate the request
-    if let Err((status, error_response)) = validate_and_respond(&request) {
-        return Err(ApiError::new(
-            status,
-            serde_json::to_string(&error_response)
-                .unwrap_or_else(|_| "Validation error".to_string()),
-        ));
-    }
+    validate_and_respond(&request)?;
 
     // Get tenant_id from authentication context
     let tenant_id = user_context.tenant_id.clone();
@@ -81,14 +66,12 @@ pub async fn create_api_key_handler(
 pub async fn list_api_keys_handler(
     State(use_case): State<Arc<ListApiKeysUseCase>>,
     axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
-    Query(query): Query<ListApiKeysQuery>,
+    Query(page): Query<PageParams>,
 ) -> Result<Json<ApiKeyListResponse>, ApiError> {
     // Get tenant_id from authentication context
     let tenant_id = user_context.tenant_id.clone();
 
-    let response = use_case
-        .execute(tenant_id, query.limit, query.offset)
-        .await?;
+    let response = use_case.execute(tenant_id, page.limit, page.offset).await?;
     Ok(Json(response))
 }
 
@@ -146,13 +129,7 @@ pub async fn update_api_key_handler(
     Json(request): Json<UpdateApiKeyRequest>,
 ) -> Result<Json<ApiKeyDto>, ApiError> {
     // Validate the request
-    if let Err((status, error_response)) = validate_and_respond(&request) {
-        return Err(ApiError::new(
-            status,
-            serde_json::to_string(&error_response)
-                .unwrap_or_else(|_| "Validation error".to_string()),
-        ));
-    }
+    validate_and_respond(&request)?;
 
     // Get tenant_id from authentication context
     let tenant_id = &user_context.tenant_id;
@@ -188,3 +165,34 @@ pub async fn delete_api_key_handler(
     use_case.execute(tenant_id, &api_key_id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// POST /v1/api-keys/{id}/rotate
+/// Issue a new secret for an API key
+///
+/// The key keeps its ID and permissions. The new secret is returned in
+/// `key` only in this response, and the old secret stops working at once.
+#[utoipa::path(
+    post,
+    path = "/v1/api-keys/{id}/rotate",
+    tag = "api-keys",
+    params(
+        ("id" = String, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 200, description = "API key secret rotated successfully", body = ApiKeyDto),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "API key not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn rotate_api_key_handler(
+    State(use_case): State<Arc<RotateApiKeyUseCase>>,
+    axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    Path(api_key_id): Path<String>,
+) -> Result<Json<ApiKeyDto>, ApiError> {
+    // Get tenant_id from authentication context
+    let tenant_id = &user_context.tenant_id;
+
+    let api_key = use_case.execute(tenant_id, &api_key_id).await?;
+    Ok(Json(api_key))
+}
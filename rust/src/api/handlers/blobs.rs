@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use std::sync::Arc;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::{BlobDto, ListBlobsRequest, ListBlobsResponse};
+use crate::application::use_cases::ListBlobsUseCase;
+
+/// GET /v1/admin/blobs
+/// List blobs with their reference counts, admin only
+///
+/// Blobs come in content hash order; a page that has more after it returns
+/// `next_cursor`, to pass as `cursor` for the next page. `orphaned=true`
+/// keeps blobs without references, previewing what GC will collect.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/blobs",
+    tag = "admin",
+    params(
+        ("limit" = Option<i64>, Query, description = "Results per page (default: 100, max: 1000)"),
+        ("cursor" = Option<String>, Query, description = "next_cursor of the previous page"),
+        ("orphaned" = Option<bool>, Query, description = "Only blobs with ref_count 0 (default: false)")
+    ),
+    responses(
+        (status = 200, description = "Blobs retrieved successfully", body = ListBlobsResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_blobs_handler(
+    State(use_case): State<Arc<ListBlobsUseCase>>,
+    Query(request): Query<ListBlobsRequest>,
+) -> Result<Json<ListBlobsResponse>, ApiError> {
+    Ok(Json(use_case.execute(request).await?))
+}
+
+/// GET /v1/admin/blobs/{hash}
+/// Get a blob and its reference count by content hash, admin only
+///
+/// With `CONTENT_HASH_SHORT_LEN` set, a prefix of at least 4 hex characters
+/// works too, like a git short hash, and the response reports the blob's
+/// shortest unique prefix as `short_hash`. A prefix matching several blobs
+/// is rejected as ambiguous.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/blobs/{hash}",
+    tag = "admin",
+    params(
+        ("hash" = String, Path, description = "Content hash, or a unique prefix of it when short hashes are on")
+    ),
+    responses(
+        (status = 200, description = "Blob retrieved successfully", body = BlobDto),
+        (status = 400, description = "Invalid hash, ambiguous prefix, or prefix while short hashes are off"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "No blob with this hash"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_blob_handler(
+    State(use_case): State<Arc<ListBlobsUseCase>>,
+    Path(hash): Path<String>,
+) -> Result<Json<BlobDto>, ApiError> {
+    Ok(Json(use_case.get(&hash).await?))
+}
@@ -0,0 +1,298 @@
+//! Request bodies that have to be read whole before they can be parsed
+//!
+//! Uploads normally stream straight into the blob store and never come
+//! through here. Formats that cannot be parsed front to back, like a zip
+//! archive whose index sits at its end, are buffered first: in memory up to
+//! `REQUEST_BODY_MEMORY_LIMIT_BYTES`, then spilled to a file under
+//! `BLOB_TEMP_DIR` (or the system temp directory). The file is removed when
+//! the buffered body is dropped, so a failed read or parse leaves nothing
+//! behind.
+
+use std::fmt::Display;
+use std::io::{self, Cursor, SeekFrom};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::http::StatusCode;
+use bytes::{Bytes, BytesMut};
+use futures_util::{Stream, StreamExt};
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWriteExt, ReadBuf};
+use uuid::Uuid;
+
+use crate::api::errors::ApiError;
+
+/// Default size a request body may reach in memory before it spills to disk
+pub const DEFAULT_BODY_MEMORY_LIMIT_BYTES: usize = 8 * 1024 * 1024;
+
+/// Where buffered request bodies are kept
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyBufferConfig {
+    /// Bodies larger than this are written to a temp file
+    pub memory_limit_bytes: usize,
+    /// Directory of spilled bodies; the system temp directory when unset
+    pub temp_dir: Option<PathBuf>,
+}
+
+impl Default for BodyBufferConfig {
+    fn default() -> Self {
+        Self {
+            memory_limit_bytes: DEFAULT_BODY_MEMORY_LIMIT_BYTES,
+            temp_dir: None,
+        }
+    }
+}
+
+impl BodyBufferConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spill bodies larger than `memory_limit_bytes` to disk
+    pub fn with_memory_limit_bytes(mut self, memory_limit_bytes: usize) -> Self {
+        self.memory_limit_bytes = memory_limit_bytes;
+        self
+    }
+
+    /// Write spilled bodies under `temp_dir`
+    pub fn with_temp_dir(mut self, temp_dir: Option<PathBuf>) -> Self {
+        self.temp_dir = temp_dir;
+        self
+    }
+}
+
+/// A request body read to its end, readable and seekable from the start
+#[derive(Debug)]
+pub enum BufferedBody {
+    Memory(Cursor<Bytes>),
+    File(SpillFile),
+}
+
+impl BufferedBody {
+    /// Whether the body was spilled to disk
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, Self::File(_))
+    }
+}
+
+/// Temp file holding a spilled body, removed on drop
+#[derive(Debug)]
+pub struct SpillFile {
+    file: File,
+    path: PathBuf,
+}
+
+impl SpillFile {
+    async fn create(config: &BodyBufferConfig) -> io::Result<Self> {
+        let dir = config.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(format!("body-{}", Uuid::new_v4()));
+        let file = tokio::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await?;
+        Ok(Self { file, path })
+    }
+
+    /// Path of the temp file
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                tracing::warn!(path = %self.path.display(), error = %e, "Failed to remove spilled request body");
+            }
+        }
+    }
+}
+
+/// Read `body` to its end, spilling it to disk past the memory limit
+///
+/// Bodies over `max_bytes` are refused with `413 Payload Too Large` as soon
+/// as they cross it, without reading the rest.
+pub async fn buffer_body<S, E>(
+    mut body: S,
+    config: &BodyBufferConfig,
+    max_bytes: u64,
+) -> Result<BufferedBody, ApiError>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+    E: Display,
+{
+    let spill_error =
+        |e: io::Error| ApiError::internal_error(format!("Failed to buffer request body: {e}"));
+
+    let mut memory = BytesMut::new();
+    let mut spill: Option<SpillFile> = None;
+    let mut total = 0u64;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk
+            .map_err(|e| ApiError::bad_request(format!("Failed to read request body: {e}")))?;
+        total += chunk.len() as u64;
+        if total > max_bytes {
+            return Err(ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Request body exceeds {max_bytes} bytes"),
+            ));
+        }
+
+        match &mut spill {
+            Some(spilled) => spilled.file.write_all(&chunk).await.map_err(spill_error)?,
+            None if memory.len() + chunk.len() > config.memory_limit_bytes => {
+                let mut spilled = SpillFile::create(config).await.map_err(spill_error)?;
+                spilled.file.write_all(&memory).await.map_err(spill_error)?;
+                spilled.file.write_all(&chunk).await.map_err(spill_error)?;
+                memory = BytesMut::new();
+                spill = Some(spilled);
+            }
+            None => memory.extend_from_slice(&chunk),
+        }
+    }
+
+    match spill {
+        Some(mut spilled) => {
+            spilled.file.flush().await.map_err(spill_error)?;
+            spilled.file.rewind().await.map_err(spill_error)?;
+            tracing::debug!(
+                path = %spilled.path.display(),
+                size_bytes = total,
+                "Request body spilled to disk"
+            );
+            Ok(BufferedBody::File(spilled))
+        }
+        None => Ok(BufferedBody::Memory(Cursor::new(memory.freeze()))),
+    }
+}
+
+impl AsyncRead for BufferedBody {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Memory(cursor) => Pin::new(cursor).poll_read(cx, buf),
+            Self::File(spilled) => Pin::new(&mut spilled.file).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncSeek for BufferedBody {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        match self.get_mut() {
+            Self::Memory(cursor) => Pin::new(cursor).start_seek(position),
+            Self::File(spilled) => Pin::new(&mut spilled.file).start_seek(position),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        match self.get_mut() {
+            Self::Memory(cursor) => Pin::new(cursor).poll_complete(cx),
+            Self::File(spilled) => Pin::new(&mut spilled.file).poll_complete(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio::io::AsyncReadExt;
+
+    fn chunks(parts: &[&'static [u8]]) -> impl Stream<Item = Result<Bytes, io::Error>> + Unpin {
+        futures_util::stream::iter(
+            parts
+                .iter()
+                .map(|part| Ok(Bytes::from_static(part)))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn config(temp_dir: &TempDir) -> BodyBufferConfig {
+        BodyBufferConfig::new()
+            .with_memory_limit_bytes(8)
+            .with_temp_dir(Some(temp_dir.path().to_path_buf()))
+    }
+
+    fn files_in(temp_dir: &TempDir) -> usize {
+        std::fs::read_dir(temp_dir.path()).unwrap().count()
+    }
+
+    async fn read_all(body: &mut BufferedBody) -> Vec<u8> {
+        let mut data = Vec::new();
+        body.read_to_end(&mut data).await.unwrap();
+        data
+    }
+
+    #[tokio::test]
+    async fn test_small_body_stays_in_memory() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut body = buffer_body(chunks(&[b"abc", b"def"]), &config(&temp_dir), 1024)
+            .await
+            .unwrap();
+
+        assert!(!body.is_spilled());
+        assert_eq!(read_all(&mut body).await, b"abcdef");
+        assert_eq!(files_in(&temp_dir), 0);
+    }
+
+    #[tokio::test]
+    async fn test_large_body_spills_and_is_removed_on_drop() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut body = buffer_body(
+            chunks(&[b"abcdef", b"ghijkl", b"mn"]),
+            &config(&temp_dir),
+            1024,
+        )
+        .await
+        .unwrap();
+
+        assert!(body.is_spilled());
+        assert_eq!(files_in(&temp_dir), 1);
+        assert_eq!(read_all(&mut body).await, b"abcdefghijklmn");
+        body.seek(SeekFrom::Start(12)).await.unwrap();
+        assert_eq!(read_all(&mut body).await, b"mn");
+
+        drop(body);
+        assert_eq!(files_in(&temp_dir), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_read_removes_spilled_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let body = futures_util::stream::iter(vec![
+            Ok(Bytes::from_static(b"abcdefghijkl")),
+            Err(io::Error::other("connection reset")),
+        ]);
+
+        let result = buffer_body(body, &config(&temp_dir), 1024).await;
+
+        assert_eq!(result.unwrap_err().status(), StatusCode::BAD_REQUEST);
+        assert_eq!(files_in(&temp_dir), 0);
+    }
+
+    #[tokio::test]
+    async fn test_body_over_limit_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = buffer_body(
+            chunks(&[b"abcdefghij", b"klmnopqrst"]),
+            &config(&temp_dir),
+            16,
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(files_in(&temp_dir), 0);
+    }
+}
@@ -0,0 +1,175 @@
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::Json;
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use std::io;
+use std::sync::Arc;
+use tokio::io::BufReader;
+use tokio_util::io::StreamReader;
+use utoipa::ToSchema;
+
+use crate::api::errors::ApiError;
+use crate::api::handlers::body_buffer::{buffer_body, BodyBufferConfig};
+use crate::api::handlers::operations::operation_id;
+use crate::application::dto::{ArchiveFormat, BulkUploadManifest, BulkUploadRequest};
+use crate::application::operation_progress::{OperationProgress, OperationReporter};
+use crate::application::use_cases::BulkUploadUseCase;
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::{StorageClass, TenantId};
+
+#[derive(Clone)]
+pub struct BulkUploadState {
+    pub use_case: Arc<BulkUploadUseCase>,
+    pub operations: Arc<OperationProgress>,
+    /// Where zip archives are buffered before they are unpacked
+    pub body_buffer: Arc<BodyBufferConfig>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct BulkUploadQuery {
+    /// Namespace for the created objects
+    namespace: String,
+    /// Tenant identifier
+    tenant_id: String,
+    /// Storage class for the created objects ('hot' or 'cold')
+    storage_class: Option<String>,
+}
+
+/// POST /v1/objects/archive
+/// Unpack a tar or zip archive into objects
+///
+/// Each regular file becomes an object whose key is its path inside the
+/// archive. The format is taken from `Content-Type`. Tar archives are
+/// unpacked as they arrive; a zip archive is buffered first, on disk past
+/// `REQUEST_BODY_MEMORY_LIMIT_BYTES`, and unpacked from its central
+/// directory. With an `X-Operation-Id` header the import can be followed
+/// on `GET /v1/operations/{id}/events`, counting archive bytes read.
+#[utoipa::path(
+    post,
+    path = "/v1/objects/archive",
+    tag = "objects",
+    params(
+        ("namespace" = String, Query, description = "Namespace for the created objects"),
+        ("tenant_id" = String, Query, description = "Tenant identifier"),
+        ("storage_class" = Option<String>, Query, description = "Storage class ('hot' or 'cold')"),
+        ("X-Operation-Id" = Option<String>, Header, description = "UUID to follow the import under")
+    ),
+    request_body(
+        content = Vec<u8>,
+        content_type = "application/x-tar",
+        description = "tar (application/x-tar) or zip (application/zip) archive"
+    ),
+    responses(
+        (status = 200, description = "Per-entry manifest of the unpacked archive", body = BulkUploadManifest),
+        (status = 400, description = "Invalid request parameters or unreadable archive"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 409, description = "X-Operation-Id is already in use"),
+        (status = 413, description = "Archive exceeds the upload size limit"),
+        (status = 415, description = "Content-Type is not a supported archive format"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn bulk_upload_handler(
+    State(state): State<BulkUploadState>,
+    axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    Query(query): Query<BulkUploadQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<BulkUploadManifest>, ApiError> {
+    // Validate tenant ownership - users can only upload to their own tenant
+    // Admins can upload to any tenant
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Cannot upload objects to other tenants".to_string(),
+        ));
+    }
+
+    let format = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(ArchiveFormat::from_content_type)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Content-Type must be application/x-tar or application/zip".to_string(),
+            )
+        })?;
+
+    let storage_class = query
+        .storage_class
+        .map(|sc| sc.parse::<StorageClass>())
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+
+    let progress = match operation_id(&headers)? {
+        Some(id) => {
+            let owner = TenantId::from_string(&query.tenant_id)
+                .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+            let progress = state
+                .operations
+                .start(id, "bulk_upload", owner)
+                .map_err(ApiError::conflict)?;
+            if let Some(length) = headers
+                .get(header::CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+            {
+                progress.set_total(length);
+            }
+            progress
+        }
+        None => OperationReporter::detached(),
+    };
+
+    let request = BulkUploadRequest {
+        namespace: query.namespace,
+        tenant_id: query.tenant_id,
+        storage_class,
+    };
+
+    let stream = body
+        .into_data_stream()
+        .inspect_ok(|chunk| progress.advance(chunk.len() as u64))
+        .map_err(io::Error::other);
+    let result = match format {
+        // The index of a zip archive is at its end, so it is read whole first;
+        // the buffer is removed when it goes out of scope
+        ArchiveFormat::Zip => {
+            let buffered = buffer_body(
+                stream,
+                &state.body_buffer,
+                state.use_case.max_archive_size_bytes(),
+            )
+            .await
+            .inspect_err(|e| progress.fail(e.message().to_string()))?;
+            state
+                .use_case
+                .execute_zip_reporting(request, BufReader::new(buffered), &progress)
+                .await
+        }
+        // A tar archive is parsed as it arrives; nothing is buffered whole
+        ArchiveFormat::Tar => {
+            state
+                .use_case
+                .execute_reporting(request, format, StreamReader::new(stream), &progress)
+                .await
+        }
+    };
+    match result {
+        Ok(manifest) => {
+            match &manifest.error {
+                Some(error) => progress.fail(error.clone()),
+                None => progress.finish(),
+            }
+            Ok(Json(manifest))
+        }
+        Err(e) => {
+            progress.fail(e.to_string());
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Limits and features of this server, for clients to adapt to
+//!
+//! Built once from the effective configuration when the router is created,
+//! so it reports what requests are actually held to. Secrets, credentials
+//! and internal addresses are never part of it.
+
+use axum::{extract::State, response::Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::api::middleware::api_version::ApiVersion;
+use crate::api::middleware::config::MiddlewareConfig;
+use crate::api::middleware::response_format::MSGPACK_CONTENT_TYPE;
+use crate::application::pagination::PageLimits;
+use crate::application::use_cases::{
+    DEFAULT_MAX_ARCHIVE_DOWNLOAD_OBJECTS, DEFAULT_MAX_ARCHIVE_ENTRIES,
+};
+use crate::config::Config;
+use crate::domain::value_objects::{ContentEncoding, StorageClass};
+
+/// What this server supports and the limits requests are held to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CapabilitiesResponse {
+    /// Versions of the object routes served, e.g. `["v1", "v2"]`
+    pub api_versions: Vec<String>,
+    /// Storage classes objects can be stored in
+    pub storage_classes: Vec<StorageClass>,
+    /// Algorithm of content hashes (`ETag`, `expected_hash`)
+    pub hash_algorithm: String,
+    pub limits: CapabilityLimits,
+    pub uploads: UploadCapabilities,
+    pub compression: CompressionCapabilities,
+    pub rate_limits: RateLimitCapabilities,
+    pub auth: AuthCapabilities,
+    pub features: FeatureCapabilities,
+}
+
+/// Size, count and time limits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CapabilityLimits {
+    /// Largest request body; larger objects need a resumable upload
+    pub max_upload_size_bytes: u64,
+    /// Largest object content, across all requests of a resumable upload
+    pub max_object_size_bytes: u64,
+    /// Most entries unpacked from one archive upload
+    pub max_archive_entries: usize,
+    /// Most objects in one archive download
+    pub max_archive_download_objects: usize,
+    /// Largest serialized object metadata
+    pub metadata_max_bytes: usize,
+    /// Most tags on one object
+    pub metadata_max_tags: usize,
+    /// Longest tag key, in characters
+    pub metadata_max_tag_key_chars: usize,
+    /// Longest metadata string value, in characters
+    pub metadata_max_string_chars: usize,
+    /// Largest `limit` of object listings and searches
+    pub max_page_size: i64,
+    /// Most request headers
+    pub max_request_headers: usize,
+    /// Largest total size of the request headers
+    pub max_request_header_bytes: usize,
+    /// Longest path and query string
+    pub max_url_length: usize,
+    /// Timeout of most requests, in seconds
+    pub request_timeout_secs: u64,
+    /// Timeout of metadata reads (list, search), in seconds
+    pub request_timeout_short_secs: u64,
+    /// Timeout of uploads and of downloads until they start streaming, in seconds
+    pub request_timeout_transfer_secs: u64,
+}
+
+/// Ways to upload content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct UploadCapabilities {
+    /// Uploads in several requests (`/v1/objects/uploads`), with no limit on
+    /// their number
+    pub resumable: bool,
+    /// Archive formats unpacked by `POST /v1/objects/archive`
+    pub archive_formats: Vec<String>,
+    /// Whether `Idempotency-Key` is honoured
+    pub idempotency_keys: bool,
+    /// How long idempotency keys are remembered, in hours
+    pub idempotency_ttl_hours: i64,
+    /// Whether uploads are checked against content-type allow or block lists
+    pub content_type_policy: bool,
+    /// Whether uploads need a declared or recognisable content type
+    pub content_type_required: bool,
+    /// Whether uploads are scanned for malware before they commit
+    pub content_scanning: bool,
+}
+
+/// Compression of uploads and responses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CompressionCapabilities {
+    /// `Content-Encoding` values accepted on uploads and stored as sent
+    pub upload_encodings: Vec<ContentEncoding>,
+    /// `Accept-Encoding` values downloads can be compressed with
+    pub response_encodings: Vec<String>,
+    /// Response body media types besides `application/json`
+    pub response_formats: Vec<String>,
+}
+
+/// Request rate and concurrency limits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RateLimitCapabilities {
+    /// Requests per window for an authenticated user
+    pub authenticated_requests_per_window: u32,
+    /// Requests per window for an unauthenticated client, by IP
+    pub unauthenticated_requests_per_window: u32,
+    /// Length of the rate limit window, in seconds
+    pub window_secs: u64,
+    /// Requests one user may have in flight (0 = unlimited)
+    pub max_concurrent_per_user: usize,
+    /// Requests one tenant may have in flight (0 = unlimited)
+    pub max_concurrent_per_tenant: usize,
+}
+
+/// How requests authenticate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuthCapabilities {
+    /// Whether requests must authenticate
+    pub required: bool,
+    /// Accepted bearer credentials: `api_key` and/or `jwt`
+    pub modes: Vec<String>,
+    /// Signing algorithms accepted on JWTs
+    pub jwt_algorithms: Vec<String>,
+}
+
+/// Optional features and whether they are on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FeatureCapabilities {
+    /// Read-only GraphQL endpoint at `/graphql`
+    pub graphql: bool,
+    /// Text extracted from uploads is searchable
+    pub text_extraction: bool,
+    /// Inbound webhooks at `/v1/webhooks/{tenant_id}`
+    pub webhooks: bool,
+    /// `X-Storage-Class` on object responses
+    pub storage_class_headers: bool,
+}
+
+impl CapabilitiesResponse {
+    /// Describe a server running with `config`, whose requests pass through
+    /// `middleware`
+    pub fn new(config: &Config, middleware: &MiddlewareConfig) -> Self {
+        let rate_limiting = &middleware.rate_limiting;
+        let compression = &middleware.response_compression;
+
+        let api_versions = [ApiVersion::V1, ApiVersion::V2]
+            .into_iter()
+            .filter(|version| middleware.api_version.serves(*version))
+            .map(|version| version.prefix().trim_start_matches('/').to_string())
+            .collect();
+        let response_encodings = [
+            ("gzip", compression.gzip),
+            ("br", compression.br),
+            ("zstd", compression.zstd),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(encoding, _)| encoding.to_string())
+        .collect();
+        let response_formats = middleware
+            .response_format
+            .msgpack_enabled
+            .then(|| MSGPACK_CONTENT_TYPE.to_string())
+            .into_iter()
+            .collect();
+
+        let mut modes = Vec::new();
+        if middleware.auth.enabled && middleware.auth.legacy_auth_enabled {
+            modes.push("api_key".to_string());
+        }
+        let jwt = middleware.auth.enabled && middleware.oidc.verifies_tokens();
+        if jwt {
+            modes.push("jwt".to_string());
+        }
+        let jwt_algorithms = if jwt {
+            middleware
+                .oidc
+                .algorithms
+                .iter()
+                .map(|algorithm| format!("{algorithm:?}"))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            api_versions,
+            storage_classes: vec![StorageClass::Hot, StorageClass::Cold],
+            hash_algorithm: "sha256".to_string(),
+            limits: CapabilityLimits {
+                max_upload_size_bytes: config.max_upload_size_bytes,
+                max_object_size_bytes: config.max_object_size_bytes,
+                max_archive_entries: DEFAULT_MAX_ARCHIVE_ENTRIES,
+                max_archive_download_objects: DEFAULT_MAX_ARCHIVE_DOWNLOAD_OBJECTS,
+                metadata_max_bytes: config.metadata_max_bytes,
+                metadata_max_tags: config.metadata_max_tags,
+                metadata_max_tag_key_chars: config.metadata_max_tag_key_chars,
+                metadata_max_string_chars: config.metadata_max_string_chars,
+                max_page_size: PageLimits::OBJECTS.max,
+                max_request_headers: config.max_request_headers,
+                max_request_header_bytes: config.max_request_header_bytes,
+                max_url_length: config.max_url_length,
+                request_timeout_secs: config.request_timeout_secs,
+                request_timeout_short_secs: config.request_timeout_short_secs,
+                request_timeout_transfer_secs: config.request_timeout_transfer_secs,
+            },
+            uploads: UploadCapabilities {
+                resumable: true,
+                archive_formats: vec!["tar".to_string(), "zip".to_string()],
+                idempotency_keys: config.upload_idempotency_ttl_hours > 0,
+                idempotency_ttl_hours: config.upload_idempotency_ttl_hours.max(0),
+                content_type_policy: config.upload_allowed_types.is_some()
+                    || config.upload_blocked_types.is_some(),
+                content_type_required: config.require_content_type,
+                content_scanning: config.content_scanner != "none",
+            },
+            compression: CompressionCapabilities {
+                upload_encodings: vec![ContentEncoding::Gzip, ContentEncoding::Zstd],
+                response_encodings,
+                response_formats,
+            },
+            rate_limits: RateLimitCapabilities {
+                authenticated_requests_per_window: rate_limiting.authenticated_requests_per_minute,
+                unauthenticated_requests_per_window: rate_limiting
+                    .unauthenticated_requests_per_minute,
+                window_secs: rate_limiting.window_seconds,
+                max_concurrent_per_user: config.max_concurrent_per_user,
+                max_concurrent_per_tenant: config.max_concurrent_per_tenant,
+            },
+            auth: AuthCapabilities {
+                required: middleware.auth.enabled,
+                modes,
+                jwt_algorithms,
+            },
+            features: FeatureCapabilities {
+                graphql: cfg!(feature = "graphql"),
+                text_extraction: config.text_extractor != "none",
+                webhooks: false,
+                storage_class_headers: middleware.storage_class_headers.enabled,
+            },
+        }
+    }
+
+    /// Report the entry limit archive uploads are unpacked with
+    pub fn with_max_archive_entries(mut self, max_archive_entries: usize) -> Self {
+        self.limits.max_archive_entries = max_archive_entries;
+        self
+    }
+
+    /// Report the object limit of archive downloads
+    pub fn with_max_archive_download_objects(mut self, max_objects: usize) -> Self {
+        self.limits.max_archive_download_objects = max_objects;
+        self
+    }
+
+    /// Report whether inbound webhooks are accepted
+    pub fn with_webhooks(mut self, enabled: bool) -> Self {
+        self.features.webhooks = enabled;
+        self
+    }
+}
+
+/// GET /v1/capabilities
+/// Limits and features of this server
+///
+/// Lets clients adapt without trial and error, e.g. switching to a
+/// resumable upload above `limits.max_upload_size_bytes`. Available to any
+/// authenticated caller.
+#[utoipa::path(
+    get,
+    path = "/v1/capabilities",
+    tag = "capabilities",
+    responses(
+        (status = 200, description = "Server capabilities and limits", body = CapabilitiesResponse),
+        (status = 401, description = "Authentication required")
+    )
+)]
+pub async fn capabilities_handler(
+    State(capabilities): State<Arc<CapabilitiesResponse>>,
+) -> Json<CapabilitiesResponse> {
+    Json(capabilities.as_ref().clone())
+}
@@ -0,0 +1,333 @@
+//! Conditional reads: `If-Match`, `If-None-Match`, `If-Modified-Since` and
+//! `If-Unmodified-Since` on object GET and HEAD
+//!
+//! An object has two validators: the strong ETag (its quoted content hash)
+//! and `Last-Modified` (its `updated_at`, to the second). Preconditions are
+//! evaluated in RFC 9110 order, and a date is only consulted when the
+//! matching ETag header is absent, since the ETag is the exact validator.
+
+use axum::http::{header, HeaderMap, HeaderName, StatusCode};
+use time::{macros::format_description, Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+use crate::api::errors::ApiError;
+
+/// Preconditions of a read request
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReadPreconditions {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_modified_since: Option<OffsetDateTime>,
+    if_unmodified_since: Option<OffsetDateTime>,
+}
+
+impl ReadPreconditions {
+    /// Read the conditional headers of a request
+    ///
+    /// Unparseable dates are ignored, as RFC 9110 requires; ETag lists
+    /// that are not valid header text are rejected.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, ApiError> {
+        let etags = |name: HeaderName| {
+            headers
+                .get(&name)
+                .map(|v| {
+                    v.to_str()
+                        .map(|list| list.trim().to_string())
+                        .map_err(|_| ApiError::bad_request(format!("Invalid {name} header")))
+                })
+                .transpose()
+        };
+        let date = |name: HeaderName| {
+            headers
+                .get(&name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_http_date)
+        };
+
+        Ok(Self {
+            if_match: etags(header::IF_MATCH)?,
+            if_none_match: etags(header::IF_NONE_MATCH)?,
+            if_modified_since: date(header::IF_MODIFIED_SINCE),
+            if_unmodified_since: date(header::IF_UNMODIFIED_SINCE),
+        })
+    }
+
+    /// Whether the request carries no precondition at all
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Evaluate against an object's validators
+    ///
+    /// Returns `None` when the read proceeds, otherwise `304 Not Modified`
+    /// or `412 Precondition Failed`.
+    pub fn evaluate(&self, etag: &str, last_modified: OffsetDateTime) -> Option<StatusCode> {
+        // Last-Modified is sent with second precision
+        let modified = last_modified.unix_timestamp();
+
+        match (&self.if_match, self.if_unmodified_since) {
+            (Some(list), _) if !etag_list_matches(list, etag, false) => {
+                return Some(StatusCode::PRECONDITION_FAILED)
+            }
+            (None, Some(since)) if modified > since.unix_timestamp() => {
+                return Some(StatusCode::PRECONDITION_FAILED)
+            }
+            _ => {}
+        }
+
+        match (&self.if_none_match, self.if_modified_since) {
+            (Some(list), _) if etag_list_matches(list, etag, true) => {
+                Some(StatusCode::NOT_MODIFIED)
+            }
+            (None, Some(since)) if modified <= since.unix_timestamp() => {
+                Some(StatusCode::NOT_MODIFIED)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether an ETag header list (or `*`) matches `etag`
+///
+/// Weak comparison ignores the `W/` prefix; strong comparison never
+/// matches a weak tag.
+fn etag_list_matches(list: &str, etag: &str, weak: bool) -> bool {
+    let etag = etag.strip_prefix("W/").unwrap_or(etag);
+    list == "*"
+        || list
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| match candidate.strip_prefix("W/") {
+                Some(candidate) => weak && candidate == etag,
+                None => candidate == etag,
+            })
+}
+
+/// Format a timestamp as an HTTP-date (RFC 9110 IMF-fixdate)
+pub fn format_http_date(timestamp: OffsetDateTime) -> Result<String, ApiError> {
+    timestamp
+        .to_offset(time::UtcOffset::UTC)
+        .format(format_description!(
+            "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+        ))
+        .map_err(|e| ApiError::internal_error(format!("Failed to format date: {}", e)))
+}
+
+/// Parse an HTTP-date in any of the three RFC 9110 formats
+///
+/// - IMF-fixdate: `Sun, 06 Nov 1994 08:49:37 GMT`
+/// - RFC 850: `Sunday, 06-Nov-94 08:49:37 GMT`
+/// - asctime: `Sun Nov  6 08:49:37 1994`
+///
+/// The weekday is not checked against the date.
+pub fn parse_http_date(value: &str) -> Option<OffsetDateTime> {
+    let (day, month, year, hms) = match value.trim().split_once(',') {
+        Some((_weekday, rest)) => {
+            let parts: Vec<&str> = rest.split_whitespace().collect();
+            match parts.as_slice() {
+                [day, month, year, hms, "GMT"] if year.len() == 4 => {
+                    (*day, *month, year.parse().ok()?, *hms)
+                }
+                [date, hms, "GMT"] => {
+                    let mut date = date.splitn(3, '-');
+                    let (day, month, year) = (date.next()?, date.next()?, date.next()?);
+                    if year.len() != 2 {
+                        return None;
+                    }
+                    (day, month, rfc850_year(year.parse().ok()?), *hms)
+                }
+                _ => return None,
+            }
+        }
+        None => match value.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [_weekday, month, day, hms, year] if year.len() == 4 => {
+                (*day, *month, year.parse().ok()?, *hms)
+            }
+            _ => return None,
+        },
+    };
+
+    if day.is_empty() || day.len() > 2 {
+        return None;
+    }
+    let date = Date::from_calendar_date(year, parse_month(month)?, day.parse().ok()?).ok()?;
+
+    let mut clock = hms.split(':');
+    let mut component = || -> Option<u8> {
+        let digits = clock.next().filter(|digits| digits.len() == 2)?;
+        digits.parse().ok()
+    };
+    let time = Time::from_hms(component()?, component()?, component()?).ok()?;
+    if clock.next().is_some() {
+        return None;
+    }
+
+    Some(PrimitiveDateTime::new(date, time).assume_utc())
+}
+
+/// Expand a two-digit RFC 850 year: one more than 50 years in the future
+/// belongs to the previous century
+fn rfc850_year(two_digits: i32) -> i32 {
+    let current = OffsetDateTime::now_utc().year();
+    let year = current - current.rem_euclid(100) + two_digits;
+    if year > current + 50 {
+        year - 100
+    } else {
+        year
+    }
+}
+
+fn parse_month(name: &str) -> Option<Month> {
+    Some(match name {
+        "Jan" => Month::January,
+        "Feb" => Month::February,
+        "Mar" => Month::March,
+        "Apr" => Month::April,
+        "May" => Month::May,
+        "Jun" => Month::June,
+        "Jul" => Month::July,
+        "Aug" => Month::August,
+        "Sep" => Month::September,
+        "Oct" => Month::October,
+        "Nov" => Month::November,
+        "Dec" => Month::December,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use time::macros::datetime;
+
+    const ETAG: &str = "\"abc\"";
+    const MODIFIED: OffsetDateTime = datetime!(1994-11-06 08:49:37.250 UTC);
+
+    fn preconditions(pairs: &[(HeaderName, &str)]) -> ReadPreconditions {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(name.clone(), HeaderValue::from_str(value).unwrap());
+        }
+        ReadPreconditions::from_headers(&headers).unwrap()
+    }
+
+    fn evaluate(pairs: &[(HeaderName, &str)]) -> Option<StatusCode> {
+        preconditions(pairs).evaluate(ETAG, MODIFIED)
+    }
+
+    #[test]
+    fn test_parse_http_date_formats() {
+        let expected = datetime!(1994-11-06 08:49:37 UTC);
+
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"),
+            Some(expected)
+        );
+        assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), Some(expected));
+        assert_eq!(
+            parse_http_date(&format_http_date(expected).unwrap()),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_http_date_rejects_invalid() {
+        for value in [
+            "",
+            "yesterday",
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 31 Feb 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 25:00:00 GMT",
+            "Sun, 06 Nov 1994 08:49 GMT",
+            "Sun, 06 Foo 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-1994 08:49:37 GMT",
+        ] {
+            assert_eq!(parse_http_date(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_no_preconditions_proceed() {
+        assert_eq!(evaluate(&[]), None);
+    }
+
+    #[test]
+    fn test_if_none_match_uses_weak_comparison() {
+        assert_eq!(
+            evaluate(&[(header::IF_NONE_MATCH, "\"abc\"")]),
+            Some(StatusCode::NOT_MODIFIED)
+        );
+        assert_eq!(
+            evaluate(&[(header::IF_NONE_MATCH, "\"other\", W/\"abc\"")]),
+            Some(StatusCode::NOT_MODIFIED)
+        );
+        assert_eq!(
+            evaluate(&[(header::IF_NONE_MATCH, "*")]),
+            Some(StatusCode::NOT_MODIFIED)
+        );
+        assert_eq!(evaluate(&[(header::IF_NONE_MATCH, "\"other\"")]), None);
+    }
+
+    #[test]
+    fn test_if_match_uses_strong_comparison() {
+        assert_eq!(evaluate(&[(header::IF_MATCH, "\"other\", \"abc\"")]), None);
+        assert_eq!(evaluate(&[(header::IF_MATCH, "*")]), None);
+        assert_eq!(
+            evaluate(&[(header::IF_MATCH, "W/\"abc\"")]),
+            Some(StatusCode::PRECONDITION_FAILED)
+        );
+    }
+
+    #[test]
+    fn test_modified_since_compares_whole_seconds() {
+        assert_eq!(
+            evaluate(&[(header::IF_MODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:37 GMT")]),
+            Some(StatusCode::NOT_MODIFIED)
+        );
+        assert_eq!(
+            evaluate(&[(header::IF_MODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:36 GMT")]),
+            None
+        );
+        assert_eq!(
+            evaluate(&[(header::IF_UNMODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:37 GMT")]),
+            None
+        );
+        assert_eq!(
+            evaluate(&[(header::IF_UNMODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:36 GMT")]),
+            Some(StatusCode::PRECONDITION_FAILED)
+        );
+        // Invalid dates are ignored
+        assert_eq!(evaluate(&[(header::IF_UNMODIFIED_SINCE, "soon")]), None);
+    }
+
+    #[test]
+    fn test_etag_headers_take_precedence_over_dates() {
+        // A changed ETag wins over an unchanged date
+        assert_eq!(
+            evaluate(&[
+                (header::IF_NONE_MATCH, "\"other\""),
+                (header::IF_MODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:37 GMT"),
+            ]),
+            None
+        );
+        assert_eq!(
+            evaluate(&[
+                (header::IF_MATCH, "\"abc\""),
+                (header::IF_UNMODIFIED_SINCE, "Sun, 06 Nov 1994 08:00:00 GMT"),
+            ]),
+            None
+        );
+        // A failed If-Match is reported before a matching If-None-Match
+        assert_eq!(
+            evaluate(&[
+                (header::IF_MATCH, "\"other\""),
+                (header::IF_NONE_MATCH, "\"abc\""),
+            ]),
+            Some(StatusCode::PRECONDITION_FAILED)
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! `Content-Disposition` on object downloads
+//!
+//! The `disposition` query parameter picks `inline` or `attachment` and
+//! `filename` names the file. Without them, types a browser can display are
+//! served inline and everything else as an attachment, named after the last
+//! segment of the object key. Filenames are sanitized before they reach the
+//! header; non-ASCII names are sent as an RFC 5987 `filename*` next to an
+//! ASCII `filename` fallback.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::api::errors::ApiError;
+
+/// Longest filename sent, in characters
+const MAX_FILENAME_CHARS: usize = 255;
+
+/// Whether a browser should display the object or save it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispositionType {
+    Inline,
+    Attachment,
+}
+
+impl DispositionType {
+    /// Default for a stored content type: inline when browsers display it
+    ///
+    /// SVG is excluded, as it can carry scripts.
+    pub fn for_content_type(content_type: Option<&str>) -> Self {
+        let essence = content_type
+            .and_then(|ct| ct.split(';').next())
+            .map(|ct| ct.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let viewable = match essence.split_once('/') {
+            Some(("image", "svg+xml")) => false,
+            Some(("image" | "audio" | "video", _)) => true,
+            _ => matches!(essence.as_str(), "text/plain" | "application/pdf"),
+        };
+        if viewable {
+            DispositionType::Inline
+        } else {
+            DispositionType::Attachment
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DispositionType::Inline => "inline",
+            DispositionType::Attachment => "attachment",
+        }
+    }
+}
+
+impl FromStr for DispositionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "inline" => Ok(DispositionType::Inline),
+            "attachment" => Ok(DispositionType::Attachment),
+            other => Err(format!(
+                "Invalid disposition '{other}': expected 'inline' or 'attachment'"
+            )),
+        }
+    }
+}
+
+/// Resolved `Content-Disposition` of a download
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDisposition {
+    disposition: DispositionType,
+    filename: Option<String>,
+}
+
+impl ContentDisposition {
+    /// Combine the request's overrides with the object's defaults
+    ///
+    /// An empty `filename` parameter sends no filename at all.
+    pub fn resolve(
+        disposition: Option<&str>,
+        filename: Option<&str>,
+        content_type: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Self, ApiError> {
+        let disposition = match disposition {
+            Some(value) => value.parse().map_err(ApiError::bad_request)?,
+            None => DispositionType::for_content_type(content_type),
+        };
+        let filename = match filename {
+            Some(name) => sanitize_filename(name),
+            None => key
+                .and_then(|key| key.rsplit('/').find(|segment| !segment.is_empty()))
+                .and_then(sanitize_filename),
+        };
+        Ok(Self {
+            disposition,
+            filename,
+        })
+    }
+
+    /// Header value, e.g. `attachment; filename="a.csv"`
+    pub fn header_value(&self) -> String {
+        let mut value = self.disposition.as_str().to_string();
+        if let Some(name) = &self.filename {
+            let fallback: String = name
+                .chars()
+                .map(|c| if c.is_ascii() { c } else { '_' })
+                .collect();
+            let _ = write!(value, "; filename=\"{fallback}\"");
+            if !name.is_ascii() {
+                let _ = write!(value, "; filename*=UTF-8''{}", encode_ext_value(name));
+            }
+        }
+        value
+    }
+}
+
+/// Strip what could break out of the quoted filename or name a path
+///
+/// Control characters (CR and LF included), quotes, backslashes and path
+/// separators are dropped, surrounding whitespace and dots are trimmed and
+/// the result is capped at [`MAX_FILENAME_CHARS`]. `None` when nothing is
+/// left.
+pub fn sanitize_filename(name: &str) -> Option<String> {
+    let cleaned: String = name
+        .chars()
+        .filter(|c| !c.is_control() && !matches!(c, '"' | '\\' | '/'))
+        .take(MAX_FILENAME_CHARS)
+        .collect();
+    let trimmed = cleaned.trim_matches(|c: char| c.is_whitespace() || c == '.');
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// Percent-encode as an RFC 5987 `value-chars`
+fn encode_ext_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        let attr_char = byte.is_ascii_alphanumeric()
+            || matches!(
+                byte,
+                b'!' | b'#' | b'$' | b'&' | b'+' | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~'
+            );
+        if attr_char {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(
+        disposition: Option<&str>,
+        filename: Option<&str>,
+        content_type: Option<&str>,
+        key: Option<&str>,
+    ) -> String {
+        ContentDisposition::resolve(disposition, filename, content_type, key)
+            .unwrap()
+            .header_value()
+    }
+
+    #[test]
+    fn test_default_follows_content_type() {
+        for viewable in [
+            "image/png",
+            "text/plain; charset=utf-8",
+            "application/pdf",
+            "VIDEO/mp4",
+        ] {
+            assert_eq!(
+                DispositionType::for_content_type(Some(viewable)),
+                DispositionType::Inline,
+                "{viewable}"
+            );
+        }
+        for other in ["text/html", "image/svg+xml", "application/zip", ""] {
+            assert_eq!(
+                DispositionType::for_content_type(Some(other)),
+                DispositionType::Attachment,
+                "{other}"
+            );
+        }
+        assert_eq!(
+            DispositionType::for_content_type(None),
+            DispositionType::Attachment
+        );
+    }
+
+    #[test]
+    fn test_filename_defaults_to_last_key_segment() {
+        assert_eq!(
+            header(None, None, Some("text/csv"), Some("reports/2024/q1.csv")),
+            "attachment; filename=\"q1.csv\""
+        );
+        assert_eq!(
+            header(None, None, Some("image/png"), Some("photos/")),
+            "inline; filename=\"photos\""
+        );
+        assert_eq!(header(None, None, Some("image/png"), None), "inline");
+    }
+
+    #[test]
+    fn test_query_overrides_defaults() {
+        assert_eq!(
+            header(
+                Some("Attachment"),
+                Some("cat.png"),
+                Some("image/png"),
+                Some("x")
+            ),
+            "attachment; filename=\"cat.png\""
+        );
+        // An empty filename sends none
+        assert_eq!(header(Some("inline"), Some(""), None, Some("x")), "inline");
+        assert!(ContentDisposition::resolve(Some("download"), None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_non_ascii_filename_uses_rfc5987() {
+        assert_eq!(
+            header(Some("attachment"), Some("résumé 2024.pdf"), None, None),
+            "attachment; filename=\"r_sum_ 2024.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9%202024.pdf"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_filename_prevents_header_injection() {
+        assert_eq!(
+            sanitize_filename("a\r\nSet-Cookie: x=1.txt").as_deref(),
+            Some("aSet-Cookie: x=1.txt")
+        );
+        assert_eq!(
+            sanitize_filename("\"evil\"; filename=x").as_deref(),
+            Some("evil; filename=x")
+        );
+        assert_eq!(
+            sanitize_filename("../../etc/passwd").as_deref(),
+            Some("etcpasswd")
+        );
+        assert_eq!(sanitize_filename(" .. "), None);
+        assert_eq!(
+            sanitize_filename(&"a".repeat(300)).map(|n| n.len()),
+            Some(MAX_FILENAME_CHARS)
+        );
+    }
+}
@@ -33,6 +33,7 @@ pub struct DeleteQuery {
         (status = 401, description = "Authentication required"),
         (status = 403, description = "Access forbidden"),
         (status = 404, description = "Object not found"),
+        (status = 409, description = "Object was locked or changed while being deleted"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -1,7 +1,7 @@
 use axum::{
     body::Body,
     extract::{Path, Query, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::Response,
 };
 use serde::Deserialize;
@@ -9,15 +9,34 @@ use std::sync::Arc;
 use tokio_util::io::ReaderStream;
 use utoipa::ToSchema;
 
+use super::conditional::{format_http_date, ReadPreconditions};
+use super::content_disposition::ContentDisposition;
 use crate::api::errors::ApiError;
+use crate::api::middleware::response_compression::{
+    CompressionMode, StoredContentType, UncompressedLength,
+};
+use crate::application::content_encoding;
+use crate::application::dto::{DownloadMetadata, ObjectHead};
+use crate::application::ports::BlobReader;
 use crate::application::use_cases::DownloadObjectUseCase;
 use crate::domain::authorization::UserContext;
-use crate::domain::value_objects::ObjectId;
+use crate::domain::value_objects::{ContentEncoding, ObjectId};
 
 #[derive(Deserialize, ToSchema)]
 pub struct DownloadQuery {
     /// Tenant identifier for authorization
     tenant_id: String,
+    #[serde(flatten)]
+    disposition: DispositionQuery,
+}
+
+/// Overrides of the `Content-Disposition` sent with a download
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct DispositionQuery {
+    /// 'inline' or 'attachment'
+    disposition: Option<String>,
+    /// Filename to suggest; empty for none
+    filename: Option<String>,
 }
 
 /// GET /v1/objects/{id}
@@ -28,14 +47,30 @@ pub struct DownloadQuery {
     tag = "objects",
     params(
         ("id" = String, Path, description = "Object UUID"),
-        ("tenant_id" = String, Query, description = "Tenant identifier for authorization")
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization"),
+        ("disposition" = Option<String>, Query, description = "'inline' or 'attachment'; defaults to inline for images, audio, video, plain text and PDF"),
+        ("filename" = Option<String>, Query, description = "Filename for Content-Disposition; defaults to the last segment of the object key"),
+        ("X-Download-Compression" = Option<String>, Header, description = "'force' or 'off' to override whether an object stored uncompressed is compressed for Accept-Encoding ('auto' decides by content type)")
     ),
     responses(
-        (status = 200, description = "Object downloaded successfully", content_type = "application/octet-stream"),
-        (status = 400, description = "Invalid object ID"),
+        (status = 200, description = "Object downloaded successfully", content_type = "application/octet-stream",
+            headers(
+                ("Content-Disposition" = String, description = "'inline' or 'attachment', with the sanitized filename"),
+                ("ETag" = String, description = "Quoted content hash; weak when the response is compressed or decoded"),
+                ("Content-Encoding" = String, description = "Encoding the object was uploaded with, when Accept-Encoding allows it; otherwise the content is decoded"),
+                ("Last-Modified" = String, description = "Time of the last change to the object"),
+                ("X-Storage-Class" = String, description = "Storage class of the object ('hot' or 'cold')"),
+                ("X-Tier-Latency-Hint" = String, description = "Expected retrieval latency ('low' or 'high'), when enabled")
+            )
+        ),
+        (status = 304, description = "Not modified (If-None-Match or If-Modified-Since)"),
+        (status = 400, description = "Invalid object ID, disposition or X-Download-Compression value"),
         (status = 401, description = "Authentication required"),
         (status = 403, description = "Access forbidden"),
         (status = 404, description = "Object not found"),
+        (status = 409, description = "Cold object is being restored; retry after the Retry-After delay"),
+        (status = 410, description = "Object blob is missing (GHOST_OBJECT)"),
+        (status = 412, description = "Precondition failed (If-Match or If-Unmodified-Since)"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -44,6 +79,7 @@ pub async fn download_handler(
     axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
     Path(id): Path<String>,
     Query(query): Query<DownloadQuery>,
+    headers: HeaderMap,
 ) -> Result<Response, ApiError> {
     // Validate tenant ownership - users can only download from their own tenant
     // Admins can download from any tenant
@@ -58,24 +94,27 @@ pub async fn download_handler(
     let object_id = id
         .parse::<ObjectId>()
         .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+    let preconditions = ReadPreconditions::from_headers(&headers)?;
+
+    // Answer preconditions from the metadata record, before the blob is
+    // opened and the download counted
+    if !preconditions.is_empty() {
+        let head = use_case.head_by_id(&object_id, &query.tenant_id).await?;
+        if let Some(response) = answer_preconditions(&head, &preconditions)? {
+            return Ok(response);
+        }
+    }
 
     // Execute use case
     let (metadata, reader) = use_case.execute_by_id(&object_id).await?;
 
-    // Convert reader to stream
-    let stream = ReaderStream::new(reader);
-    let body = Body::from_stream(stream);
-
-    // Build response with headers
-    let response = Response::builder()
-        .status(StatusCode::OK)
-        .header(header::CONTENT_LENGTH, metadata.size_bytes.to_string())
-        .header(header::CONTENT_TYPE, "application/octet-stream")
-        .header("X-Content-Hash", metadata.content_hash)
-        .body(body)
-        .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))?;
-
-    Ok(response)
+    download_response(
+        metadata,
+        reader,
+        &preconditions,
+        &query.disposition,
+        &headers,
+    )
 }
 
 /// GET /v1/objects/by-key/{namespace}/{tenant_id}/{key}
@@ -87,13 +126,30 @@ pub async fn download_handler(
     params(
         ("namespace" = String, Path, description = "Object namespace"),
         ("tenant_id" = String, Path, description = "Tenant identifier"),
-        ("key" = String, Path, description = "Object key")
+        ("key" = String, Path, description = "Object key"),
+        ("disposition" = Option<String>, Query, description = "'inline' or 'attachment'; defaults to inline for images, audio, video, plain text and PDF"),
+        ("filename" = Option<String>, Query, description = "Filename for Content-Disposition; defaults to the last segment of the object key"),
+        ("X-Download-Compression" = Option<String>, Header, description = "'force' or 'off' to override whether an object stored uncompressed is compressed for Accept-Encoding ('auto' decides by content type)")
     ),
     responses(
-        (status = 200, description = "Object downloaded successfully", content_type = "application/octet-stream"),
+        (status = 200, description = "Object downloaded successfully", content_type = "application/octet-stream",
+            headers(
+                ("Content-Disposition" = String, description = "'inline' or 'attachment', with the sanitized filename"),
+                ("ETag" = String, description = "Quoted content hash; weak when the response is compressed or decoded"),
+                ("Content-Encoding" = String, description = "Encoding the object was uploaded with, when Accept-Encoding allows it; otherwise the content is decoded"),
+                ("Last-Modified" = String, description = "Time of the last change to the object"),
+                ("X-Storage-Class" = String, description = "Storage class of the object ('hot' or 'cold')"),
+                ("X-Tier-Latency-Hint" = String, description = "Expected retrieval latency ('low' or 'high'), when enabled")
+            )
+        ),
+        (status = 304, description = "Not modified (If-None-Match or If-Modified-Since)"),
+        (status = 400, description = "Invalid disposition or X-Download-Compression value"),
         (status = 401, description = "Authentication required"),
         (status = 403, description = "Access forbidden"),
         (status = 404, description = "Object not found"),
+        (status = 409, description = "Cold object is being restored; retry after the Retry-After delay"),
+        (status = 410, description = "Object blob is missing (GHOST_OBJECT)"),
+        (status = 412, description = "Precondition failed (If-Match or If-Unmodified-Since)"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -101,6 +157,8 @@ pub async fn download_by_key_handler(
     State(use_case): State<Arc<DownloadObjectUseCase>>,
     axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
     Path((namespace, tenant_id, key)): Path<(String, String, String)>,
+    Query(disposition): Query<DispositionQuery>,
+    headers: HeaderMap,
 ) -> Result<Response, ApiError> {
     // Validate tenant ownership - users can only download from their own tenant
     // Admins can download from any tenant
@@ -110,23 +168,348 @@ pub async fn download_by_key_handler(
             "Cannot download objects from other tenants".to_string(),
         ));
     }
+    let preconditions = ReadPreconditions::from_headers(&headers)?;
+
+    // Answer preconditions from the metadata record, before the blob is
+    // opened and the download counted
+    if !preconditions.is_empty() {
+        let head = use_case.head_by_key(&namespace, &tenant_id, &key).await?;
+        if let Some(response) = answer_preconditions(&head, &preconditions)? {
+            return Ok(response);
+        }
+    }
+
     // Execute use case
     let (metadata, reader) = use_case
         .execute_by_key(&namespace, &tenant_id, &key)
         .await?;
 
+    download_response(metadata, reader, &preconditions, &disposition, &headers)
+}
+
+/// HEAD /v1/objects/{id}
+/// Check that an object exists and read its headers without downloading it
+#[utoipa::path(
+    head,
+    path = "/v1/objects/{id}",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization")
+    ),
+    responses(
+        (status = 200, description = "Object exists and is committed",
+            headers(
+                ("Content-Length" = u64, description = "Object size in bytes; absent when an encoded object would be decoded"),
+                ("Content-Type" = String, description = "Content type recorded at upload"),
+                ("Content-Encoding" = String, description = "Encoding the object was uploaded with, when Accept-Encoding allows it"),
+                ("ETag" = String, description = "Quoted content hash"),
+                ("Last-Modified" = String, description = "Time of the last change to the object"),
+                ("X-Storage-Class" = String, description = "Storage class of the object ('hot' or 'cold')")
+            )
+        ),
+        (status = 304, description = "Not modified (If-None-Match or If-Modified-Since)"),
+        (status = 400, description = "Invalid object ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Object not found or not committed"),
+        (status = 412, description = "Precondition failed (If-Match or If-Unmodified-Since)"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn head_handler(
+    State(use_case): State<Arc<DownloadObjectUseCase>>,
+    axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<DownloadQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    // Same tenant ownership rules as GET
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Cannot read objects from other tenants".to_string(),
+        ));
+    }
+
+    let object_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+    let preconditions = ReadPreconditions::from_headers(&headers)?;
+
+    let head = use_case.head_by_id(&object_id, &query.tenant_id).await?;
+
+    head_response(head, &preconditions, &headers)
+}
+
+/// HEAD /v1/objects/by-key/{namespace}/{tenant_id}/{key}
+/// Check that an object exists by key and read its headers
+#[utoipa::path(
+    head,
+    path = "/v1/objects/by-key/{namespace}/{tenant_id}/{key}",
+    tag = "objects",
+    params(
+        ("namespace" = String, Path, description = "Object namespace"),
+        ("tenant_id" = String, Path, description = "Tenant identifier"),
+        ("key" = String, Path, description = "Object key")
+    ),
+    responses(
+        (status = 200, description = "Object exists and is committed",
+            headers(
+                ("Content-Length" = u64, description = "Object size in bytes; absent when an encoded object would be decoded"),
+                ("Content-Type" = String, description = "Content type recorded at upload"),
+                ("Content-Encoding" = String, description = "Encoding the object was uploaded with, when Accept-Encoding allows it"),
+                ("ETag" = String, description = "Quoted content hash"),
+                ("Last-Modified" = String, description = "Time of the last change to the object"),
+                ("X-Storage-Class" = String, description = "Storage class of the object ('hot' or 'cold')")
+            )
+        ),
+        (status = 304, description = "Not modified (If-None-Match or If-Modified-Since)"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Object not found or not committed"),
+        (status = 412, description = "Precondition failed (If-Match or If-Unmodified-Since)"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn head_by_key_handler(
+    State(use_case): State<Arc<DownloadObjectUseCase>>,
+    axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    Path((namespace, tenant_id, key)): Path<(String, String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    // Same tenant ownership rules as GET
+    if !user_context.is_admin() && tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Cannot read objects from other tenants".to_string(),
+        ));
+    }
+
+    let preconditions = ReadPreconditions::from_headers(&headers)?;
+
+    let head = use_case.head_by_key(&namespace, &tenant_id, &key).await?;
+
+    head_response(head, &preconditions, &headers)
+}
+
+/// How an object stored with a `Content-Encoding` is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Delivery {
+    /// No encoding recorded: the stored bytes are the content
+    Plain,
+    /// The client accepts the encoding: the stored bytes are sent as-is
+    Encoded(ContentEncoding),
+    /// The client doesn't accept it: the content is decoded while streaming
+    Decoded(ContentEncoding),
+}
+
+impl Delivery {
+    fn negotiate(stored: Option<ContentEncoding>, request_headers: &HeaderMap) -> Self {
+        match stored {
+            None => Delivery::Plain,
+            Some(encoding) if accepts_encoding(request_headers, encoding) => {
+                Delivery::Encoded(encoding)
+            }
+            Some(encoding) => Delivery::Decoded(encoding),
+        }
+    }
+}
+
+/// Whether `Accept-Encoding` allows `encoding`, by name or through `*`
+///
+/// Without the header only the identity is assumed acceptable, like the
+/// response compression layer does: clients that don't ask for an encoding
+/// get the content decoded.
+fn accepts_encoding(request_headers: &HeaderMap, encoding: ContentEncoding) -> bool {
+    let mut wildcard = None;
+    for value in request_headers.get_all(header::ACCEPT_ENCODING) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        for item in value.split(',') {
+            let mut params = item.split(';');
+            let coding = params.next().unwrap_or_default().trim();
+            let accepted = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .all(|q| !matches!(q.trim().parse::<f32>(), Ok(q) if q <= 0.0));
+            if coding == "*" {
+                wildcard = Some(accepted);
+            } else if coding.parse::<ContentEncoding>().ok() == Some(encoding) {
+                // A named coding overrides the wildcard
+                return accepted;
+            }
+        }
+    }
+    wildcard.unwrap_or(false)
+}
+
+/// Stream the object, unless a precondition answers the request first
+///
+/// Preconditions are checked against the metadata record before the blob
+/// is opened; they are checked again here in case the object changed in
+/// between, and the reader is then dropped unread on `304` and `412`.
+/// Preconditions compare against the stored bytes' ETag; a decoded
+/// response carries it weak.
+fn download_response(
+    metadata: DownloadMetadata,
+    reader: BlobReader,
+    preconditions: &ReadPreconditions,
+    disposition: &DispositionQuery,
+    request_headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    let etag = format!("\"{}\"", metadata.content_hash);
+    let last_modified = format_http_date(metadata.updated_at)?;
+    if let Some(status) = preconditions.evaluate(&etag, metadata.updated_at) {
+        return precondition_response(status, etag, last_modified);
+    }
+
+    let content_disposition = ContentDisposition::resolve(
+        disposition.disposition.as_deref(),
+        disposition.filename.as_deref(),
+        metadata.content_type.as_deref(),
+        metadata.key.as_deref(),
+    )?;
+
+    let compression =
+        CompressionMode::from_request(request_headers).map_err(ApiError::bad_request)?;
+    let delivery = Delivery::negotiate(metadata.content_encoding, request_headers);
+    let (reader, etag) = match delivery {
+        Delivery::Decoded(encoding) => (
+            content_encoding::decode(reader, encoding),
+            format!("W/{etag}"),
+        ),
+        Delivery::Plain | Delivery::Encoded(_) => (reader, etag),
+    };
+
     // Convert reader to stream
     let stream = ReaderStream::new(reader);
     let body = Body::from_stream(stream);
 
-    // Build response with headers
-    let response = Response::builder()
-        .status(StatusCode::OK)
-        .header(header::CONTENT_LENGTH, metadata.size_bytes.to_string())
-        .header(header::CONTENT_TYPE, "application/octet-stream")
-        .header("X-Content-Hash", metadata.content_hash)
-        .body(body)
-        .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))?;
+    let mut response = encoding_headers(
+        Response::builder().status(StatusCode::OK),
+        delivery,
+        metadata.size_bytes,
+    )
+    .header(header::CONTENT_TYPE, "application/octet-stream")
+    .header(
+        header::CONTENT_DISPOSITION,
+        content_disposition.header_value(),
+    )
+    .header(header::ETAG, etag)
+    .header(header::LAST_MODIFIED, last_modified)
+    .header("X-Content-Hash", metadata.content_hash)
+    // Surfaced as X-Storage-Class by the storage class headers layer
+    .extension(metadata.storage_class)
+    // Lets the compression layer skip already-compressed objects
+    .extension(StoredContentType(metadata.content_type.unwrap_or_default()))
+    .body(body)
+    .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))?;
 
+    // The request may force or disable compression of the stored bytes
+    if let Some(mode) = compression {
+        response.extensions_mut().insert(mode);
+    }
+    if delivery == Delivery::Plain {
+        response
+            .extensions_mut()
+            .insert(UncompressedLength(metadata.size_bytes));
+    }
     Ok(response)
 }
+
+/// The `304` or `412` a precondition answers the request with, if any
+fn answer_preconditions(
+    head: &ObjectHead,
+    preconditions: &ReadPreconditions,
+) -> Result<Option<Response>, ApiError> {
+    let etag = format!("\"{}\"", head.content_hash);
+    match preconditions.evaluate(&etag, head.updated_at) {
+        Some(status) => {
+            precondition_response(status, etag, format_http_date(head.updated_at)?).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Build a body-less response describing the object
+///
+/// Unlike downloads, `Content-Type` carries the stored type: there is no
+/// body for a browser to render. The encoding headers match what `GET`
+/// would send.
+fn head_response(
+    head: ObjectHead,
+    preconditions: &ReadPreconditions,
+    request_headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    if let Some(response) = answer_preconditions(&head, preconditions)? {
+        return Ok(response);
+    }
+    let etag = format!("\"{}\"", head.content_hash);
+    let last_modified = format_http_date(head.updated_at)?;
+
+    let content_type = head
+        .content_type
+        .as_deref()
+        .unwrap_or("application/octet-stream");
+    let delivery = Delivery::negotiate(head.content_encoding, request_headers);
+    let etag = match delivery {
+        Delivery::Decoded(_) => format!("W/{etag}"),
+        Delivery::Plain | Delivery::Encoded(_) => etag,
+    };
+
+    encoding_headers(
+        Response::builder().status(StatusCode::OK),
+        delivery,
+        head.size_bytes,
+    )
+    .header(header::CONTENT_TYPE, content_type)
+    .header(header::ETAG, etag)
+    .header(header::LAST_MODIFIED, last_modified)
+    .header("X-Content-Hash", head.content_hash)
+    // Surfaced as X-Storage-Class by the storage class headers layer
+    .extension(head.storage_class)
+    .body(Body::empty())
+    .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))
+}
+
+/// Headers describing the body's length and encoding
+///
+/// Decoded content has no known length. Stored-encoded bytes keep their
+/// strong ETag: the `ContentEncoding` extension tells the response
+/// compression layer these are the bytes the content hash names.
+fn encoding_headers(
+    response: axum::http::response::Builder,
+    delivery: Delivery,
+    size_bytes: u64,
+) -> axum::http::response::Builder {
+    match delivery {
+        Delivery::Plain => response.header(header::CONTENT_LENGTH, size_bytes.to_string()),
+        Delivery::Encoded(encoding) => response
+            .header(header::CONTENT_LENGTH, size_bytes.to_string())
+            .header(header::CONTENT_ENCODING, encoding.to_string())
+            .header(header::VARY, "accept-encoding")
+            .extension(encoding),
+        Delivery::Decoded(_) => response.header(header::VARY, "accept-encoding"),
+    }
+}
+
+/// Answer a request whose precondition decided the outcome
+///
+/// `304` carries the validators so caches can refresh their copy.
+fn precondition_response(
+    status: StatusCode,
+    etag: String,
+    last_modified: String,
+) -> Result<Response, ApiError> {
+    if status == StatusCode::PRECONDITION_FAILED {
+        return Err(ApiError::new(status, "Precondition failed"));
+    }
+
+    Response::builder()
+        .status(status)
+        .header(header::ETAG, etag)
+        .header(header::LAST_MODIFIED, last_modified)
+        .body(Body::empty())
+        .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))
+}
@@ -0,0 +1,95 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::Response,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::content_disposition::ContentDisposition;
+use crate::api::errors::ApiError;
+use crate::application::dto::{ArchiveDownloadRequest, ArchiveFormat};
+use crate::application::use_cases::DownloadArchiveUseCase;
+use crate::domain::authorization::UserContext;
+
+#[derive(Deserialize, ToSchema)]
+pub struct ArchiveDownloadQuery {
+    /// Namespace to download
+    namespace: String,
+    /// Tenant identifier
+    tenant_id: String,
+    /// Keep only keys starting with this prefix
+    prefix: Option<String>,
+    /// Archive format ('tar' or 'zip'); defaults to tar
+    format: Option<ArchiveFormat>,
+}
+
+/// GET /v1/objects:archive
+/// Download the objects of a namespace as a tar or zip archive
+///
+/// Each committed object becomes an entry named after its key, in key
+/// order, followed by a `_manifest.json` entry describing every object.
+/// Objects whose content cannot be read are listed in the manifest with the
+/// error instead of failing the download. The archive is written while it
+/// is sent; a failure midway ends the response early, so a truncated
+/// archive must be treated as failed.
+#[utoipa::path(
+    get,
+    path = "/v1/objects:archive",
+    tag = "objects",
+    params(
+        ("namespace" = String, Query, description = "Namespace to download"),
+        ("tenant_id" = String, Query, description = "Tenant identifier"),
+        ("prefix" = Option<String>, Query, description = "Keep only keys starting with this prefix"),
+        ("format" = Option<ArchiveFormat>, Query, description = "Archive format ('tar' or 'zip'); defaults to tar")
+    ),
+    responses(
+        (status = 200, description = "Archive of the matching objects; its last entry is the manifest", content_type = "application/x-tar",
+            headers(
+                ("Content-Disposition" = String, description = "Attachment named after the namespace")
+            )
+        ),
+        (status = 400, description = "Invalid request parameters, or more objects match than ARCHIVE_DOWNLOAD_MAX_OBJECTS"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn download_archive_handler(
+    State(use_case): State<Arc<DownloadArchiveUseCase>>,
+    axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    Query(query): Query<ArchiveDownloadQuery>,
+) -> Result<Response, ApiError> {
+    // Validate tenant ownership - users can only download from their own tenant
+    // Admins can download from any tenant
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Cannot download objects from other tenants".to_string(),
+        ));
+    }
+
+    let format = query.format.unwrap_or(ArchiveFormat::Tar);
+    let filename = format!("{}.{}", query.namespace, format.extension());
+    let content_disposition =
+        ContentDisposition::resolve(Some("attachment"), Some(&filename), None, None)?;
+
+    let request = ArchiveDownloadRequest {
+        namespace: query.namespace,
+        tenant_id: query.tenant_id,
+        prefix: query.prefix,
+    };
+    let archive = use_case.execute(request, format).await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            content_disposition.header_value(),
+        )
+        .body(Body::from_stream(archive))
+        .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))
+}
@@ -1,20 +1,27 @@
 use axum::{extract::State, http::StatusCode, response::Json};
-use serde_json::json;
-use std::time::Instant;
+use serde_json::{json, Value};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 use utoipa::ToSchema;
 
 use crate::api::router::AppState;
+use crate::application::use_cases::DownloadObjectUseCase;
+use crate::infrastructure::persistence::PoolHealth;
+use crate::infrastructure::storage::BreakerState;
 
 use super::health_checks::{
-    perform_readiness_checks, perform_security_health_checks, sanitize_db_error,
+    check_gc_initialized, check_migrations, perform_readiness_checks,
+    perform_security_health_checks, sanitize_db_error, DependencyCheck, ReadinessCheckResult,
+    HEALTH_CHECK_TIMEOUT,
 };
 
-/// Basic health check response
+/// Liveness response
 #[derive(serde::Serialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub service: String,
     pub version: String,
+    pub uptime_seconds: u64,
 }
 
 /// Database readiness response
@@ -27,36 +34,63 @@ pub struct ReadinessResponse {
     pub error: Option<String>,
 }
 
-/// GET /health
-/// Basic health check endpoint (no database check)
+/// Startup response
+#[derive(serde::Serialize, ToSchema)]
+pub struct StartupResponse {
+    pub status: String,
+    pub service: String,
+    #[schema(value_type = Object)]
+    pub checks: Value,
+    pub issues: Vec<String>,
+}
+
+fn timestamp() -> String {
+    time::OffsetDateTime::now_utc()
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+/// GET /health/live (also served at /health)
+/// Liveness probe: the process is up and not draining (no dependency checks)
 #[utoipa::path(
     get,
-    path = "/health",
+    path = "/health/live",
     tag = "health",
     responses(
-        (status = 200, description = "Service is healthy", body = HealthResponse)
+        (status = 200, description = "Service is alive", body = HealthResponse),
+        (status = 503, description = "Service is shutting down", body = HealthResponse)
     )
 )]
-pub async fn health_handler() -> (StatusCode, Json<serde_json::Value>) {
-    let start_time = Instant::now();
+pub async fn liveness_handler(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    liveness_response(
+        state.shutting_down.load(Ordering::SeqCst),
+        state.start_time.elapsed(),
+    )
+}
 
-    let response_time = start_time.elapsed();
+/// Liveness answer for a process that has been up for `uptime`
+pub fn liveness_response(shutting_down: bool, uptime: Duration) -> (StatusCode, Json<Value>) {
+    let (status, process) = if shutting_down {
+        (StatusCode::SERVICE_UNAVAILABLE, "shutting_down")
+    } else {
+        (StatusCode::OK, "up")
+    };
 
     (
-        StatusCode::OK,
+        status,
         Json(json!({
-            "status": "healthy",
+            "status": if shutting_down { "shutting_down" } else { "healthy" },
             "service": "just_storage",
             "version": env!("CARGO_PKG_VERSION"),
-            "timestamp": time::OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339).unwrap_or_default(),
-            "uptime_seconds": std::process::id(), // Simplified uptime indicator
-            "response_time_ms": response_time.as_millis()
+            "timestamp": timestamp(),
+            "uptime_seconds": uptime.as_secs(),
+            "checks": { "process": process }
         })),
     )
 }
 
 /// GET /health/ready
-/// Readiness probe with database connectivity check
+/// Readiness probe: database, migrations and both storage roots
 #[utoipa::path(
     get,
     path = "/health/ready",
@@ -71,57 +105,126 @@ pub async fn readiness_handler(
 ) -> (StatusCode, Json<serde_json::Value>) {
     let start_time = Instant::now();
 
+    // Perform security checks
+    let security_checks = perform_security_health_checks(state.config.disable_auth);
+
+    // Draining: stop receiving traffic before the listener closes
+    if state.shutting_down.load(Ordering::SeqCst) {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "status": "not_ready",
+                "service": "just_storage",
+                "reason": "shutting_down",
+                "timestamp": timestamp(),
+                "response_time_ms": start_time.elapsed().as_millis(),
+                "security": security_checks
+            })),
+        );
+    }
+
     // Check database connectivity with timeout
     let db_check = tokio::time::timeout(
-        std::time::Duration::from_secs(2),
+        HEALTH_CHECK_TIMEOUT,
         sqlx::query("SELECT 1 as health_check").fetch_one(state.pool.as_ref()),
     )
     .await;
 
-    let response_time = start_time.elapsed();
-
-    // Perform security checks
-    let security_checks = perform_security_health_checks(state.config.disable_auth);
-
     match db_check {
         Ok(Ok(_)) => {
             // Additional readiness checks
-            let readiness_checks = perform_readiness_checks(
-                state.pool.as_ref(),
-                state.expected_migration_count,
-                &state.config.hot_storage_root,
-                &state.config.cold_storage_root,
+            let mut readiness_checks = tokio::time::timeout(
+                HEALTH_CHECK_TIMEOUT,
+                perform_readiness_checks(
+                    state.pool.as_ref(),
+                    state.expected_migration_count,
+                    &state.config.hot_storage_root,
+                    &state.config.cold_storage_root,
+                    state.config.blob_temp_dir.as_deref(),
+                ),
             )
-            .await;
-
-            if readiness_checks.healthy {
-                (
-                    StatusCode::OK,
-                    Json(json!({
-                        "status": "ready",
-                        "service": "just_storage",
-                        "database": "connected",
-                        "timestamp": time::OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339).unwrap_or_default(),
-                        "response_time_ms": response_time.as_millis(),
-                        "checks": readiness_checks.details,
-                        "security": security_checks
-                    })),
-                )
-            } else {
-                (
-                    StatusCode::SERVICE_UNAVAILABLE,
-                    Json(json!({
-                        "status": "not_ready",
-                        "service": "just_storage",
-                        "database": "connected",
-                        "timestamp": time::OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339).unwrap_or_default(),
-                        "response_time_ms": response_time.as_millis(),
-                        "checks": readiness_checks.details,
-                        "security": security_checks,
-                        "issues": readiness_checks.issues
-                    })),
-                )
+            .await
+            .unwrap_or_else(|_| {
+                let timed_out = DependencyCheck::timed_out("Readiness");
+                ReadinessCheckResult {
+                    healthy: false,
+                    details: json!({ "storage_and_migrations": timed_out.details }),
+                    issues: timed_out.issues,
+                }
+            });
+            // A saturated or dead pool can't serve more traffic
+            let pool_stats = state.pool_monitor.stats();
+            if pool_stats.health != PoolHealth::Healthy {
+                readiness_checks.healthy = false;
+                readiness_checks.issues.push(format!(
+                    "Database pool {}: {} of {} connections in use",
+                    pool_stats.health, pool_stats.in_use, pool_stats.max_connections
+                ));
+            }
+            // Requests to a backend with an open breaker fail fast
+            let mut breaker_stats = serde_json::Map::new();
+            for (name, breaker) in &state.blob_store_breakers {
+                let stats = breaker.stats();
+                if stats.state == BreakerState::Open {
+                    readiness_checks.healthy = false;
+                    readiness_checks.issues.push(format!(
+                        "Blob store {} circuit breaker open after {} consecutive failures",
+                        name, stats.consecutive_failures
+                    ));
+                }
+                breaker_stats.insert(name.clone(), json!(stats));
             }
+            if let Some(details) = readiness_checks.details.as_object_mut() {
+                details.insert("database".to_string(), json!({ "status": "connected" }));
+                details.insert("database_pool".to_string(), json!(pool_stats));
+                if let Some(blob_cache) = &state.blob_cache {
+                    details.insert("blob_cache".to_string(), json!(blob_cache.stats()));
+                }
+                if let Some(blob_filter) = &state.blob_filter {
+                    details.insert("blob_filter".to_string(), json!(blob_filter.stats()));
+                }
+                details.insert(
+                    "download_compression".to_string(),
+                    json!(state.download_compression.stats()),
+                );
+                details.extend(download_stats(&state.download_use_case));
+                if let Some(scrubber) = &state.scrubber {
+                    details.insert("blob_scrub".to_string(), json!(scrubber.stats()));
+                }
+                details.insert(
+                    "concurrency".to_string(),
+                    json!(state.concurrency_limiter.stats()),
+                );
+                details.insert(
+                    "connections".to_string(),
+                    json!(state.connection_limiter.stats()),
+                );
+                if !breaker_stats.is_empty() {
+                    details.insert(
+                        "blob_store_breakers".to_string(),
+                        Value::Object(breaker_stats),
+                    );
+                }
+            }
+
+            let (status, ready) = if readiness_checks.healthy {
+                (StatusCode::OK, "ready")
+            } else {
+                (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+            };
+            (
+                status,
+                Json(json!({
+                    "status": ready,
+                    "service": "just_storage",
+                    "database": "connected",
+                    "timestamp": timestamp(),
+                    "response_time_ms": start_time.elapsed().as_millis(),
+                    "checks": readiness_checks.details,
+                    "security": security_checks,
+                    "issues": readiness_checks.issues
+                })),
+            )
         }
         Ok(Err(e)) => (
             StatusCode::SERVICE_UNAVAILABLE,
@@ -130,8 +233,9 @@ pub async fn readiness_handler(
                 "service": "just_storage",
                 "database": "disconnected",
                 "error": format!("Database error: {}", sanitize_db_error(&e)),
-                "timestamp": time::OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339).unwrap_or_default(),
-                "response_time_ms": response_time.as_millis(),
+                "timestamp": timestamp(),
+                "response_time_ms": start_time.elapsed().as_millis(),
+                "checks": { "database": { "status": "disconnected" } },
                 "security": security_checks
             })),
         ),
@@ -141,15 +245,78 @@ pub async fn readiness_handler(
                 "status": "not_ready",
                 "service": "just_storage",
                 "database": "timeout",
-                "error": "Database query timed out after 2 seconds",
-                "timestamp": time::OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339).unwrap_or_default(),
-                "response_time_ms": response_time.as_millis(),
+                "error": format!(
+                    "Database query timed out after {} seconds",
+                    HEALTH_CHECK_TIMEOUT.as_secs()
+                ),
+                "timestamp": timestamp(),
+                "response_time_ms": start_time.elapsed().as_millis(),
+                "checks": { "database": { "status": "timeout" } },
                 "security": security_checks
             })),
         ),
     }
 }
 
+/// Download counters since startup, reported with the readiness checks
+pub fn download_stats(download_use_case: &DownloadObjectUseCase) -> serde_json::Map<String, Value> {
+    let mut stats = serde_json::Map::new();
+    stats.insert(
+        "read_verification".to_string(),
+        json!(download_use_case.read_verification_stats()),
+    );
+    stats.insert(
+        "ghost_objects".to_string(),
+        json!({ "detected": download_use_case.ghost_objects_detected() }),
+    );
+    stats
+}
+
+/// GET /health/startup
+/// Startup probe: migrations applied and the garbage collector initialized
+#[utoipa::path(
+    get,
+    path = "/health/startup",
+    tag = "health",
+    responses(
+        (status = 200, description = "Service has started", body = StartupResponse),
+        (status = 503, description = "Service is still starting", body = StartupResponse)
+    )
+)]
+pub async fn startup_handler(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let start_time = Instant::now();
+
+    let migrations = tokio::time::timeout(
+        HEALTH_CHECK_TIMEOUT,
+        check_migrations(state.pool.as_ref(), state.expected_migration_count),
+    )
+    .await
+    .unwrap_or_else(|_| DependencyCheck::timed_out("Migration"));
+    let gc = check_gc_initialized(state.gc.as_deref());
+
+    let started = migrations.is_healthy() && gc.is_healthy();
+    let issues: Vec<String> = migrations.issues.into_iter().chain(gc.issues).collect();
+
+    (
+        if started {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        },
+        Json(json!({
+            "status": if started { "started" } else { "starting" },
+            "service": "just_storage",
+            "timestamp": timestamp(),
+            "response_time_ms": start_time.elapsed().as_millis(),
+            "checks": {
+                "migrations": migrations.details,
+                "gc": gc.details
+            },
+            "issues": issues
+        })),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,7 +373,7 @@ mod tests {
         std::fs::create_dir_all(&cold_dir).expect("Failed to create cold dir");
         
         // Test with expected migrations = 0 to prevent the migration check from failing on empty DB
-        let result = perform_readiness_checks(&pool, 0, &hot_dir, &cold_dir).await;
+        let result = perform_readiness_checks(&pool, 0, &hot_dir, &cold_dir, None).await;
         
         assert!(result.healthy, "Readiness check should be healthy. Details: {:?}", result.details);
         assert!(result.details.is_object());
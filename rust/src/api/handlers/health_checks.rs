@@ -5,7 +5,14 @@
 
 use serde_json::{json, Map, Value};
 use sqlx::PgPool;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::application::gc::GarbageCollector;
+
+/// Upper bound on each dependency check, so probes answer promptly even
+/// when a dependency hangs
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Result of readiness checks
 #[derive(Debug)]
@@ -35,45 +42,23 @@ pub async fn perform_readiness_checks(
     expected_migration_count: usize,
     hot_storage_root: &Path,
     cold_storage_root: &Path,
+    blob_temp_dir: Option<&Path>,
 ) -> ReadinessCheckResult {
     let mut issues = Vec::new();
     let mut details = Map::new();
 
-    match sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM _sqlx_migrations")
-        .fetch_one(pool)
-        .await
-    {
-        Ok(applied) => {
-            let expected = expected_migration_count as i64;
-            details.insert(
-                "migrations".to_string(),
-                json!({
-                    "applied": applied,
-                    "expected": expected,
-                    "status": if applied >= expected { "ready" } else { "incomplete" }
-                }),
-            );
-
-            if applied < expected {
-                issues.push(format!(
-                    "Only {applied} of {expected} expected migrations are applied"
-                ));
-            }
-        }
-        Err(error) => {
-            details.insert(
-                "migrations".to_string(),
-                json!({
-                    "status": "error",
-                    "error": sanitize_db_error(&error)
-                }),
-            );
-            issues.push("Unable to read SQLx migration status".to_string());
-        }
-    }
+    let migrations = check_migrations(pool, expected_migration_count).await;
+    details.insert("migrations".to_string(), migrations.details);
+    issues.extend(migrations.issues);
 
-    let hot_ready = check_storage_root(hot_storage_root).await;
-    let cold_ready = check_storage_root(cold_storage_root).await;
+    // Temp files live under each root unless BLOB_TEMP_DIR moves them
+    let temp_root = |root: &Path, class: &str| match blob_temp_dir {
+        Some(dir) => dir.join(class),
+        None => root.join("temp"),
+    };
+    let hot_ready = check_storage_root(hot_storage_root, temp_root(hot_storage_root, "hot")).await;
+    let cold_ready =
+        check_storage_root(cold_storage_root, temp_root(cold_storage_root, "cold")).await;
 
     details.insert("hot_storage".to_string(), hot_ready.details);
     details.insert("cold_storage".to_string(), cold_ready.details);
@@ -100,12 +85,65 @@ pub async fn perform_readiness_checks(
     }
 }
 
-struct StorageRootCheck {
-    details: Value,
-    issues: Vec<String>,
+/// Status of one dependency
+#[derive(Debug)]
+pub struct DependencyCheck {
+    pub details: Value,
+    pub issues: Vec<String>,
 }
 
-async fn check_storage_root(root: &Path) -> StorageRootCheck {
+impl DependencyCheck {
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// A check that did not finish within [`HEALTH_CHECK_TIMEOUT`]
+    pub fn timed_out(what: &str) -> Self {
+        Self {
+            details: json!({ "status": "timeout" }),
+            issues: vec![format!(
+                "{what} check timed out after {} seconds",
+                HEALTH_CHECK_TIMEOUT.as_secs()
+            )],
+        }
+    }
+}
+
+/// Check that all migrations bundled with this build are applied
+pub async fn check_migrations(pool: &PgPool, expected_migration_count: usize) -> DependencyCheck {
+    match sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM _sqlx_migrations")
+        .fetch_one(pool)
+        .await
+    {
+        Ok(applied) => {
+            let expected = expected_migration_count as i64;
+            let issues = if applied < expected {
+                vec![format!(
+                    "Only {applied} of {expected} expected migrations are applied"
+                )]
+            } else {
+                Vec::new()
+            };
+            DependencyCheck {
+                details: json!({
+                    "applied": applied,
+                    "expected": expected,
+                    "status": if applied >= expected { "ready" } else { "incomplete" }
+                }),
+                issues,
+            }
+        }
+        Err(error) => DependencyCheck {
+            details: json!({
+                "status": "error",
+                "error": sanitize_db_error(&error)
+            }),
+            issues: vec!["Unable to read SQLx migration status".to_string()],
+        },
+    }
+}
+
+async fn check_storage_root(root: &Path, temp_root: PathBuf) -> DependencyCheck {
     let mut issues = Vec::new();
     let mut details = Map::new();
 
@@ -113,7 +151,7 @@ async fn check_storage_root(root: &Path) -> StorageRootCheck {
 
     for (name, path) in [
         ("root", root.to_path_buf()),
-        ("temp", root.join("temp")),
+        ("temp", temp_root),
         ("sha256", root.join("sha256")),
     ] {
         match tokio::fs::metadata(&path).await {
@@ -137,12 +175,32 @@ async fn check_storage_root(root: &Path) -> StorageRootCheck {
         }
     }
 
-    StorageRootCheck {
+    DependencyCheck {
         details: Value::Object(details),
         issues,
     }
 }
 
+/// Check that the garbage collector has completed its first cycle
+///
+/// A disabled collector has nothing to wait for.
+pub fn check_gc_initialized(gc: Option<&GarbageCollector>) -> DependencyCheck {
+    match gc.map(GarbageCollector::last_run) {
+        None => DependencyCheck {
+            details: json!({ "status": "disabled" }),
+            issues: Vec::new(),
+        },
+        Some(Some(_)) => DependencyCheck {
+            details: json!({ "status": "initialized" }),
+            issues: Vec::new(),
+        },
+        Some(None) => DependencyCheck {
+            details: json!({ "status": "pending" }),
+            issues: vec!["Garbage collector has not completed its first cycle".to_string()],
+        },
+    }
+}
+
 /// Sanitize database error messages to prevent information leakage
 pub fn sanitize_db_error(error: &sqlx::Error) -> String {
     match error {
@@ -0,0 +1,24 @@
+use axum::{extract::State, response::Json};
+use std::sync::Arc;
+
+use crate::api::middleware::request_latency::{LatencyReport, RequestLatency};
+
+/// GET /v1/admin/latency
+/// Handler latency histograms per route since startup, admin only
+///
+/// Times run until the response head, so downloads report their time to
+/// first byte. `slow_requests` counts requests over the slow threshold of
+/// their route's timeout class.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/latency",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Latency histograms per route", body = LatencyReport),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required")
+    )
+)]
+pub async fn latency_handler(State(latency): State<Arc<RequestLatency>>) -> Json<LatencyReport> {
+    Json(latency.report())
+}
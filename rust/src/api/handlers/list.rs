@@ -1,30 +1,77 @@
 use axum::{
+    body::{Body, Bytes},
     extract::{Query, State},
-    response::Json,
+    http::{header, HeaderMap},
+    response::{IntoResponse, Json, Response},
+    BoxError,
 };
+use futures_util::StreamExt;
 use serde::Deserialize;
 use std::sync::Arc;
 use utoipa::ToSchema;
 
 use crate::api::errors::ApiError;
-use crate::application::dto::{ListRequest, ListResponse};
+use crate::api::handlers::pagination::PageParams;
+use crate::application::dto::{
+    ListRequest, ListResponse, ObjectDto, ObjectField, ObjectProjection, SortDirection, SortField,
+};
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::pagination::PageLimits;
 use crate::application::use_cases::ListObjectsUseCase;
 use crate::domain::authorization::UserContext;
 
+/// Media type of streamed listings
+const NDJSON: &str = "application/x-ndjson";
+
 #[derive(Deserialize, ToSchema)]
 pub struct ListQuery {
     /// Filter by namespace
     namespace: String,
     /// Filter by tenant
     tenant_id: String,
-    /// Results per page (default: 100, max: 1000)
-    limit: Option<i64>,
-    /// Pagination offset (default: 0)
-    offset: Option<i64>,
+    /// Sort field (default: created_at)
+    sort_by: Option<SortField>,
+    /// Sort direction (default: desc)
+    sort_direction: Option<SortDirection>,
+    /// Sort as `field[:asc|desc]`, e.g. `size:asc`; replaces sort_by and sort_direction
+    sort: Option<String>,
+    /// next_cursor of the previous page
+    cursor: Option<String>,
+    /// Keep only keys starting with this prefix
+    prefix: Option<String>,
+    /// Group keys by this delimiter after the prefix
+    delimiter: Option<String>,
+    /// Comma-separated fields to return, e.g. `id,key,size,content_type`
+    fields: Option<String>,
 }
 
 /// GET /v1/objects
 /// List objects with pagination
+///
+/// Metadata filters: `metadata.<path>=<value>` keeps objects whose metadata
+/// holds the string `value` at the dotted `path` (e.g. `metadata.tags.author=jane`),
+/// and `metadata_has=<path>` keeps objects where `path` exists. Both repeat
+/// and combine with AND.
+///
+/// Hierarchy: `prefix=photos/` keeps keys starting with `photos/`, and
+/// `delimiter=/` lists keys with another `/` after the prefix only as
+/// `common_prefixes` (`photos/2024/`), like folders.
+///
+/// Sorting: `sort=size:asc` sorts by `created_at`, `updated_at`, `size`,
+/// `key` or `access_count`, descending unless `:asc` is given; other names
+/// are rejected. Ties are broken by object ID.
+///
+/// Cursor pagination: a page that has more after it returns `next_cursor`;
+/// pass it as `cursor` (with the same sort, without `offset`) to get the
+/// next page. Unlike offsets, cursors do not skip or repeat objects when
+/// others are added or removed in between, and deep pages stay fast.
+///
+/// Projection: `fields=id,key,size,content_type` loads and returns only the
+/// listed fields of each object (`size` is short for `size_bytes`).
+///
+/// Streaming: with `Accept: application/x-ndjson` the whole listing is
+/// streamed as one JSON object per line, read from the database as the
+/// client consumes it; `limit` and `offset` are ignored.
 #[utoipa::path(
     get,
     path = "/v1/objects",
@@ -33,11 +80,23 @@ pub struct ListQuery {
         ("namespace" = String, Query, description = "Filter by namespace"),
         ("tenant_id" = String, Query, description = "Filter by tenant"),
         ("limit" = Option<i64>, Query, description = "Results per page (default: 100, max: 1000)"),
-        ("offset" = Option<i64>, Query, description = "Pagination offset (default: 0)")
+        ("offset" = Option<i64>, Query, description = "Pagination offset (default: 0)"),
+        ("sort_by" = Option<SortField>, Query, description = "Sort field, e.g. 'download_count' or 'last_accessed_at' (default: created_at)"),
+        ("sort_direction" = Option<SortDirection>, Query, description = "'asc' or 'desc' (default: desc)"),
+        ("sort" = Option<String>, Query, description = "'created_at', 'updated_at', 'size', 'key' or 'access_count', optionally with ':asc' or ':desc', e.g. 'size:asc'"),
+        ("cursor" = Option<String>, Query, description = "next_cursor of the previous page, instead of offset"),
+        ("prefix" = Option<String>, Query, description = "Keep only keys starting with this prefix, e.g. 'photos/2024/'"),
+        ("delimiter" = Option<String>, Query, description = "Return keys with this delimiter after the prefix as common_prefixes instead of objects, e.g. '/'"),
+        ("metadata.<path>" = Option<String>, Query, description = "Require this string value at a dotted metadata path, e.g. 'metadata.tags.author=jane'"),
+        ("metadata_has" = Option<Vec<String>>, Query, description = "Require a dotted metadata path to exist, e.g. 'tags.author'"),
+        ("fields" = Option<String>, Query, description = "Comma-separated object fields to return, e.g. 'id,key,size,content_type' (default: all)")
     ),
     responses(
-        (status = 200, description = "Objects retrieved successfully", body = ListResponse),
-        (status = 400, description = "Invalid request parameters"),
+        (status = 200, description = "Objects retrieved successfully; a ProjectedListResponse when fields is set, or one object per line when streamed", content(
+            (ListResponse = "application/json"),
+            (ObjectDto = "application/x-ndjson")
+        )),
+        (status = 400, description = "Invalid request parameters or unknown field"),
         (status = 401, description = "Authentication required"),
         (status = 403, description = "Access forbidden"),
         (status = 500, description = "Internal server error")
@@ -46,8 +105,11 @@ pub struct ListQuery {
 pub async fn list_handler(
     State(use_case): State<Arc<ListObjectsUseCase>>,
     axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    headers: HeaderMap,
     Query(query): Query<ListQuery>,
-) -> Result<Json<ListResponse>, ApiError> {
+    Query(page): Query<PageParams>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Response, ApiError> {
     // Validate tenant ownership - users can only list objects from their own tenant
     // Admins can list objects from any tenant
     if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
@@ -57,18 +119,101 @@ pub async fn list_handler(
         ));
     }
 
-    // Validate pagination parameters
-    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
-    let offset = query.offset.unwrap_or(0).max(0);
+    let page = page.resolve(PageLimits::OBJECTS);
+
+    let (sort_by, sort_direction) = match query.sort.as_deref() {
+        Some(_) if query.sort_by.is_some() || query.sort_direction.is_some() => {
+            return Err(ApiError::bad_request(
+                "Use either sort or sort_by and sort_direction",
+            ))
+        }
+        Some(sort) => {
+            let (field, direction) = SortField::parse_sort(sort).map_err(ApiError::bad_request)?;
+            (Some(field), Some(direction))
+        }
+        None => (query.sort_by, query.sort_direction),
+    };
+
+    let metadata = MetadataQuery::from_params(
+        params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str())),
+    )
+    .map_err(ApiError::bad_request)?;
 
     let request = ListRequest {
         namespace: query.namespace,
         tenant_id: query.tenant_id,
-        limit: Some(limit),
-        offset: Some(offset),
+        limit: Some(page.limit),
+        offset: Some(page.offset),
+        sort_by,
+        sort_direction,
+        cursor: query.cursor,
+        prefix: query.prefix,
+        delimiter: query.delimiter,
+        metadata_filters: metadata.contains().cloned(),
+        metadata_has_keys: Some(metadata.has_keys()),
     };
 
-    let response = use_case.execute(request).await?;
+    let fields = query
+        .fields
+        .as_deref()
+        .map(ObjectField::parse_list)
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+
+    if accepts_ndjson(&headers) {
+        return ndjson_response(&use_case, request, fields);
+    }
+
+    match fields {
+        Some(fields) => {
+            let response = use_case.execute_projected(request, &fields).await?;
+            Ok(Json(response).into_response())
+        }
+        None => Ok(Json(use_case.execute(request).await?).into_response()),
+    }
+}
+
+/// Whether the client asked for newline-delimited JSON
+fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| {
+            media_type
+                .split(';')
+                .next()
+                .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(NDJSON))
+        })
+}
+
+/// Stream the whole listing, one JSON object per line
+///
+/// The status line is sent before any row is read, so a failure part way
+/// through aborts the body instead of ending it cleanly; clients must treat
+/// a truncated stream as an error.
+fn ndjson_response(
+    use_case: &ListObjectsUseCase,
+    request: ListRequest,
+    fields: Option<Vec<ObjectField>>,
+) -> Result<Response, ApiError> {
+    let objects = use_case.stream(request)?;
+
+    let lines = objects.map(move |object| -> Result<Bytes, BoxError> {
+        let object = object.inspect_err(|e| tracing::warn!("Object stream failed: {}", e))?;
+        let mut line = match &fields {
+            Some(fields) => serde_json::to_vec(&ObjectProjection::new(&object, fields))?,
+            None => serde_json::to_vec(&object)?,
+        };
+        line.push(b'\n');
+        Ok(Bytes::from(line))
+    });
 
-    Ok(Json(response))
+    Response::builder()
+        .header(header::CONTENT_TYPE, NDJSON)
+        .body(Body::from_stream(lines))
+        .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))
 }
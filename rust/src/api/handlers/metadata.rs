@@ -0,0 +1,132 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::ObjectRecordDto;
+use crate::application::use_cases::{DownloadObjectUseCase, UpdateObjectMetadataUseCase};
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::{ObjectId, ObjectMetadata, StorageClass, TenantId};
+
+#[derive(Deserialize, ToSchema)]
+pub struct ObjectMetadataQuery {
+    /// Tenant identifier for authorization
+    tenant_id: String,
+}
+
+/// GET /v1/objects/{id}/metadata
+/// Read an object's full metadata record without downloading it
+///
+/// Admins also see how many objects share the content (`dedup`).
+#[utoipa::path(
+    get,
+    path = "/v1/objects/{id}/metadata",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization")
+    ),
+    responses(
+        (status = 200, description = "Object metadata record", body = ObjectRecordDto,
+            headers(
+                ("X-Storage-Class" = String, description = "Storage class of the object ('hot' or 'cold')"),
+                ("X-Tier-Latency-Hint" = String, description = "Expected retrieval latency ('low' or 'high'), when enabled")
+            )
+        ),
+        (status = 400, description = "Invalid object ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Object not found or not committed"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_metadata_handler(
+    State(use_case): State<Arc<DownloadObjectUseCase>>,
+    axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<ObjectMetadataQuery>,
+) -> Result<(Extension<StorageClass>, Json<ObjectRecordDto>), ApiError> {
+    // Same tenant ownership rules as downloads
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            axum::http::StatusCode::FORBIDDEN,
+            "Cannot read objects of other tenants".to_string(),
+        ));
+    }
+
+    let object_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+
+    let record = use_case
+        .record_by_id(&object_id, &query.tenant_id, user_context.is_admin())
+        .await?;
+
+    // Surfaced as X-Storage-Class by the storage class headers layer
+    Ok((Extension(record.object.storage_class), Json(record)))
+}
+
+/// PATCH /v1/objects/{id}/metadata
+/// Update object metadata with a JSON Merge Patch (RFC 7386)
+///
+/// Members set to `null` are removed; nested objects are merged. The stored
+/// content is not modified.
+#[utoipa::path(
+    patch,
+    path = "/v1/objects/{id}/metadata",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization")
+    ),
+    request_body(
+        content = serde_json::Value,
+        content_type = "application/merge-patch+json",
+        description = "JSON Merge Patch applied to the object's metadata"
+    ),
+    responses(
+        (status = 200, description = "Merged metadata", body = ObjectMetadata,
+            headers(
+                ("X-Storage-Class" = String, description = "Storage class of the object ('hot' or 'cold')"),
+                ("X-Tier-Latency-Hint" = String, description = "Expected retrieval latency ('low' or 'high'), when enabled")
+            )
+        ),
+        (status = 400, description = "Invalid patch, unknown or oversized field"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Object not found"),
+        (status = 409, description = "Metadata is being modified concurrently"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn update_metadata_handler(
+    State(use_case): State<Arc<UpdateObjectMetadataUseCase>>,
+    axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<ObjectMetadataQuery>,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<(Extension<StorageClass>, Json<ObjectMetadata>), ApiError> {
+    // Validate tenant ownership - users can only modify their own tenant's objects
+    // Admins can modify any tenant
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            axum::http::StatusCode::FORBIDDEN,
+            "Cannot modify objects of other tenants".to_string(),
+        ));
+    }
+
+    let object_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+    let tenant_id = TenantId::from_string(&query.tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+
+    let object = use_case.execute(&object_id, &tenant_id, &patch).await?;
+
+    // Surfaced as X-Storage-Class by the storage class headers layer
+    Ok((Extension(object.storage_class), Json(object.metadata)))
+}
@@ -1,24 +1,69 @@
 pub mod api_keys;
+pub mod blobs;
+pub mod body_buffer;
+pub mod bulk_upload;
+pub mod capabilities;
+mod conditional;
+mod content_disposition;
 pub mod delete;
 pub mod download;
+pub mod download_archive;
 pub mod health;
 pub mod health_checks;
+pub mod latency;
 pub mod list;
+pub mod metadata;
+pub mod namespaces;
+pub mod operations;
+mod pagination;
+pub mod register_objects;
+pub mod resumable_upload;
+pub mod retention;
 pub mod search;
+pub mod shares;
+pub mod stats;
+pub mod status;
 pub mod text_search;
+pub mod touch;
 pub mod upload;
+pub mod webhooks;
 
 #[cfg(test)]
 mod tests;
 
 pub use api_keys::{
     create_api_key_handler, delete_api_key_handler, get_api_key_handler, list_api_keys_handler,
-    update_api_key_handler,
+    rotate_api_key_handler, update_api_key_handler,
 };
+pub use blobs::{get_blob_handler, list_blobs_handler};
+pub use bulk_upload::bulk_upload_handler;
+pub use capabilities::capabilities_handler;
 pub use delete::delete_handler;
-pub use download::{download_by_key_handler, download_handler};
-pub use health::{health_handler, readiness_handler};
+pub use download::{
+    download_by_key_handler, download_handler, head_by_key_handler, head_handler,
+};
+pub use download_archive::download_archive_handler;
+pub use health::{liveness_handler, readiness_handler, startup_handler};
+pub use latency::latency_handler;
 pub use list::list_handler;
+pub use metadata::{get_metadata_handler, update_metadata_handler};
+pub use namespaces::{
+    bulk_update_metadata_handler, delete_namespace_config_handler,
+    delete_namespace_objects_handler, get_namespace_config_handler,
+    list_namespace_configs_handler, put_namespace_config_handler,
+};
+pub use operations::operation_events_handler;
+pub use register_objects::register_objects_handler;
+pub use resumable_upload::{resume_upload_handler, start_upload_handler, upload_offset_handler};
+pub use retention::update_retention_handler;
 pub use search::search_handler;
+pub use shares::{
+    accept_share_handler, list_incoming_shares_handler, list_shares_handler, revoke_share_handler,
+    share_object_handler,
+};
+pub use stats::stats_handler;
+pub use status::object_status_handler;
 pub use text_search::text_search_handler;
+pub use touch::touch_handler;
 pub use upload::upload_handler;
+pub use webhooks::inbound_webhook_handler;
@@ -0,0 +1,281 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::api::errors::ApiError;
+use crate::api::handlers::operations::operation_id;
+use crate::api::middleware::validation::validate_and_respond;
+use crate::application::dto::{
+    BulkUpdateMetadataReport, BulkUpdateMetadataRequest, DeleteNamespaceReport, NamespaceConfigDto,
+    NamespaceConfigListResponse, PutNamespaceConfigRequest,
+};
+use crate::application::operation_progress::{OperationProgress, OperationReporter};
+use crate::application::use_cases::{
+    BulkUpdateMetadataUseCase, DeleteNamespaceUseCase, NamespaceConfigUseCase,
+};
+use crate::domain::value_objects::TenantId;
+
+#[derive(Clone)]
+pub struct DeleteNamespaceState {
+    pub use_case: Arc<DeleteNamespaceUseCase>,
+    pub operations: Arc<OperationProgress>,
+}
+
+#[derive(Clone)]
+pub struct BulkUpdateMetadataState {
+    pub use_case: Arc<BulkUpdateMetadataUseCase>,
+    pub operations: Arc<OperationProgress>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct DeleteNamespaceObjectsQuery {
+    /// Tenant whose objects are deleted
+    tenant_id: String,
+    /// Only count the objects and list the locked ones
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// GET /v1/namespaces
+/// List namespace configurations, admin only
+#[utoipa::path(
+    get,
+    path = "/v1/namespaces",
+    tag = "namespaces",
+    responses(
+        (status = 200, description = "Namespace configurations retrieved successfully", body = NamespaceConfigListResponse),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_namespace_configs_handler(
+    State(use_case): State<Arc<NamespaceConfigUseCase>>,
+) -> Result<Json<NamespaceConfigListResponse>, ApiError> {
+    Ok(Json(use_case.list().await?))
+}
+
+/// GET /v1/namespaces/{namespace}
+/// Get a namespace's configuration, admin only
+#[utoipa::path(
+    get,
+    path = "/v1/namespaces/{namespace}",
+    tag = "namespaces",
+    params(
+        ("namespace" = String, Path, description = "Namespace name")
+    ),
+    responses(
+        (status = 200, description = "Namespace configuration retrieved successfully", body = NamespaceConfigDto),
+        (status = 400, description = "Invalid namespace name"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Namespace has no configuration"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_namespace_config_handler(
+    State(use_case): State<Arc<NamespaceConfigUseCase>>,
+    Path(namespace): Path<String>,
+) -> Result<Json<NamespaceConfigDto>, ApiError> {
+    Ok(Json(use_case.get(namespace).await?))
+}
+
+/// PUT /v1/namespaces/{namespace}
+/// Create or replace a namespace's configuration, admin only
+///
+/// Uploads to the namespace that omit a storage class use its default class,
+/// and keyed uploads must satisfy its key policy, if any.
+#[utoipa::path(
+    put,
+    path = "/v1/namespaces/{namespace}",
+    tag = "namespaces",
+    params(
+        ("namespace" = String, Path, description = "Namespace name")
+    ),
+    request_body = PutNamespaceConfigRequest,
+    responses(
+        (status = 200, description = "Namespace configuration stored", body = NamespaceConfigDto),
+        (status = 400, description = "Invalid namespace name or policy"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn put_namespace_config_handler(
+    State(use_case): State<Arc<NamespaceConfigUseCase>>,
+    Path(namespace): Path<String>,
+    Json(request): Json<PutNamespaceConfigRequest>,
+) -> Result<Json<NamespaceConfigDto>, ApiError> {
+    Ok(Json(use_case.put(namespace, request).await?))
+}
+
+/// DELETE /v1/namespaces/{namespace}
+/// Remove a namespace's configuration, admin only
+#[utoipa::path(
+    delete,
+    path = "/v1/namespaces/{namespace}",
+    tag = "namespaces",
+    params(
+        ("namespace" = String, Path, description = "Namespace name")
+    ),
+    responses(
+        (status = 204, description = "Namespace configuration removed"),
+        (status = 400, description = "Invalid namespace name"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Namespace has no configuration"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn delete_namespace_config_handler(
+    State(use_case): State<Arc<NamespaceConfigUseCase>>,
+    Path(namespace): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    use_case.delete(namespace).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// DELETE /v1/namespaces/{namespace}/objects
+/// Delete every object of a tenant's namespace, admin only
+///
+/// Deletes a bounded number of batches per call; call again until the report
+/// is `completed`. Objects under retention or legal hold block the delete
+/// (403, listing them). Run with `dry_run=true` first to see the counts.
+/// With an `X-Operation-Id` header the run can be followed on
+/// `GET /v1/operations/{id}/events`, counting deleted objects.
+#[utoipa::path(
+    delete,
+    path = "/v1/namespaces/{namespace}/objects",
+    tag = "namespaces",
+    params(
+        ("namespace" = String, Path, description = "Namespace name"),
+        ("tenant_id" = String, Query, description = "Tenant whose objects are deleted"),
+        ("dry_run" = Option<bool>, Query, description = "Only count the objects and list the locked ones"),
+        ("X-Operation-Id" = Option<String>, Header, description = "UUID to follow the run under")
+    ),
+    responses(
+        (status = 200, description = "Objects counted or deleted", body = DeleteNamespaceReport),
+        (status = 400, description = "Invalid namespace name or tenant ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required, or objects are locked"),
+        (status = 409, description = "X-Operation-Id is already in use"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn delete_namespace_objects_handler(
+    State(state): State<DeleteNamespaceState>,
+    Path(namespace): Path<String>,
+    Query(query): Query<DeleteNamespaceObjectsQuery>,
+    headers: HeaderMap,
+) -> Result<Json<DeleteNamespaceReport>, ApiError> {
+    let progress = match operation_id(&headers)? {
+        Some(id) => {
+            let owner = TenantId::from_string(&query.tenant_id)
+                .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+            state
+                .operations
+                .start(id, "namespace_delete", owner)
+                .map_err(ApiError::conflict)?
+        }
+        None => OperationReporter::detached(),
+    };
+
+    let result = state
+        .use_case
+        .execute_reporting(namespace, &query.tenant_id, query.dry_run, &progress)
+        .await;
+    match result {
+        Ok(report) if report.completed || report.dry_run => {
+            progress.finish();
+            Ok(Json(report))
+        }
+        Ok(report) => {
+            progress.stop_partial(format!(
+                "{} objects remain; run the delete again to continue",
+                report.remaining_objects
+            ));
+            Ok(Json(report))
+        }
+        Err(e) => {
+            progress.fail(e.to_string());
+            Err(e.into())
+        }
+    }
+}
+
+/// PATCH /v1/namespaces/{namespace}/objects/metadata
+/// Apply a metadata patch to every matching object of a tenant's namespace,
+/// admin only
+///
+/// Objects are matched with the filters of search and patched with a JSON
+/// Merge Patch, as `PATCH /v1/objects/{id}/metadata` would; blobs and content
+/// hashes are never touched. Objects under retention or legal hold are
+/// skipped and counted. Each call writes a bounded number of batches; call
+/// again until the report is `completed`, objects already patched count as
+/// unchanged. With an `X-Operation-Id` header the run can be followed on
+/// `GET /v1/operations/{id}/events`.
+#[utoipa::path(
+    patch,
+    path = "/v1/namespaces/{namespace}/objects/metadata",
+    tag = "namespaces",
+    params(
+        ("namespace" = String, Path, description = "Namespace name"),
+        ("X-Operation-Id" = Option<String>, Header, description = "UUID to follow the run under")
+    ),
+    request_body = BulkUpdateMetadataRequest,
+    responses(
+        (status = 200, description = "Matching objects patched", body = BulkUpdateMetadataReport),
+        (status = 400, description = "Invalid namespace, tenant, filter or patch"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 409, description = "X-Operation-Id is already in use"),
+        (status = 422, description = "Validation failed"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn bulk_update_metadata_handler(
+    State(state): State<BulkUpdateMetadataState>,
+    Path(namespace): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<BulkUpdateMetadataRequest>,
+) -> Result<Json<BulkUpdateMetadataReport>, ApiError> {
+    validate_and_respond(&request)?;
+
+    let progress = match operation_id(&headers)? {
+        Some(id) => {
+            let owner = TenantId::from_string(&request.tenant_id)
+                .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+            state
+                .operations
+                .start(id, "bulk_metadata_update", owner)
+                .map_err(ApiError::conflict)?
+        }
+        None => OperationReporter::detached(),
+    };
+
+    let result = state
+        .use_case
+        .execute_reporting(namespace, &request, &progress)
+        .await;
+    match result {
+        Ok(report) if report.completed => {
+            progress.finish();
+            Ok(Json(report))
+        }
+        Ok(report) => {
+            progress.stop_partial(
+                "Stopped after its batch budget; run the update again to continue".to_string(),
+            );
+            Ok(Json(report))
+        }
+        Err(e) => {
+            progress.fail(e.to_string());
+            Err(e.into())
+        }
+    }
+}
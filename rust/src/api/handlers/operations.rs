@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::Serialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::{OperationProgressEvent, OperationState};
+use crate::application::operation_progress::{
+    OperationProgress, OperationSnapshot, OperationSubscription,
+};
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::TenantId;
+
+/// Header naming the operation a request starts, so it can be followed
+pub const OPERATION_ID_HEADER: &str = "x-operation-id";
+
+/// How long a followed operation may take to start
+const START_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Comment sent on idle streams so proxies keep them open
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Operation ID from the `X-Operation-Id` header, if the client sent one
+pub fn operation_id(headers: &HeaderMap) -> Result<Option<Uuid>, ApiError> {
+    headers
+        .get(OPERATION_ID_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| Uuid::parse_str(value.trim()).ok())
+                .ok_or_else(|| ApiError::bad_request("X-Operation-Id must be a UUID"))
+        })
+        .transpose()
+}
+
+/// GET /v1/operations/{id}/events
+/// Follow an operation's progress as Server-Sent Events
+///
+/// Start an archive import or namespace delete with an `X-Operation-Id`
+/// header, and follow that ID here; the stream may be opened before the
+/// operation starts. Sends an `item` event per finished item (archive entry,
+/// delete batch) and a `progress` event on every change, and ends after the
+/// final `progress` event (`COMPLETED`, `PARTIAL` or `FAILED`; a partial
+/// run stopped before all the work was done). An `error` event ends
+/// the stream if the operation does not start within a minute. Operations
+/// can only be followed on the instance running them.
+#[utoipa::path(
+    get,
+    path = "/v1/operations/{id}/events",
+    tag = "operations",
+    params(
+        ("id" = String, Path, description = "Operation ID sent as X-Operation-Id")
+    ),
+    responses(
+        (status = 200, description = "text/event-stream of item and progress events", body = OperationProgressEvent),
+        (status = 400, description = "Invalid operation ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "Operation belongs to another tenant")
+    )
+)]
+pub async fn operation_events_handler(
+    State(progress): State<Arc<OperationProgress>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let id = Uuid::parse_str(&id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid operation ID: {}", e)))?;
+
+    let mut subscription = progress.subscribe(id);
+    if !may_follow(&subscription.current(), &user_context) {
+        return Err(ApiError::not_found("Operation not found"));
+    }
+
+    let follow = Follow {
+        id,
+        subscription,
+        user_context,
+        state: OperationState::Pending,
+        sent_items: 0,
+        first: true,
+        finished: false,
+    };
+    // Dropping the stream when the client disconnects drops the subscription
+    let events = stream::unfold(follow, next_events)
+        .flat_map(|events| stream::iter(events.into_iter().map(Ok::<_, Infallible>)));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(HEARTBEAT_INTERVAL)))
+}
+
+/// State of one event stream
+struct Follow {
+    id: Uuid,
+    subscription: OperationSubscription,
+    user_context: UserContext,
+    /// State sent in the last `progress` event
+    state: OperationState,
+    /// Items already sent
+    sent_items: usize,
+    first: bool,
+    finished: bool,
+}
+
+/// Wait for the operation to change and turn the change into events
+async fn next_events(mut follow: Follow) -> Option<(Vec<Event>, Follow)> {
+    if follow.finished {
+        return None;
+    }
+
+    if follow.first {
+        follow.first = false;
+    } else if follow.state == OperationState::Pending {
+        if tokio::time::timeout(START_TIMEOUT, follow.subscription.changed())
+            .await
+            .is_err()
+        {
+            follow.finished = true;
+            return Some((vec![error_event("Operation did not start in time")], follow));
+        }
+    } else {
+        follow.subscription.changed().await;
+    }
+
+    let events = {
+        let snapshot = follow.subscription.current();
+        if !may_follow(&snapshot, &follow.user_context) {
+            // Another tenant started an operation with this ID
+            follow.finished = true;
+            vec![error_event("Operation not found")]
+        } else {
+            let mut events: Vec<Event> = snapshot
+                .items
+                .iter()
+                .skip(follow.sent_items)
+                .map(|item| json_event("item", item))
+                .collect();
+            follow.sent_items = snapshot.items.len();
+            events.push(json_event("progress", &snapshot.event(&follow.id)));
+            follow.state = snapshot.state;
+            follow.finished = snapshot.state.is_finished();
+            events
+        }
+    };
+
+    Some((events, follow))
+}
+
+/// Same ownership rules as the operation's objects
+fn may_follow(snapshot: &OperationSnapshot, user_context: &UserContext) -> bool {
+    snapshot.owner.as_ref().is_none_or(|owner| {
+        user_context.is_admin()
+            || TenantId::from_string(&user_context.tenant_id).is_ok_and(|tenant| &tenant == owner)
+    })
+}
+
+fn json_event<T: Serialize>(name: &str, data: &T) -> Event {
+    Event::default()
+        .event(name)
+        .data(serde_json::to_string(data).unwrap_or_default())
+}
+
+fn error_event(message: &str) -> Event {
+    json_event("error", &serde_json::json!({ "error": message }))
+}
@@ -0,0 +1,25 @@
+//! `limit` and `offset` query parameters of paged GET listings
+//!
+//! Extracted as a `Query<PageParams>` next to the handler's own query, and
+//! resolved with the listing's [`PageLimits`] so every endpoint clamps the
+//! same way.
+
+use serde::Deserialize;
+
+use crate::application::pagination::{PageLimits, Pagination};
+
+/// Requested page of a listing
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct PageParams {
+    /// Results per page; clamped to the listing's range
+    pub limit: Option<i64>,
+    /// Results to skip; negative values start from the first
+    pub offset: Option<i64>,
+}
+
+impl PageParams {
+    /// The page to return, within `limits`
+    pub fn resolve(self, limits: PageLimits) -> Pagination {
+        Pagination::new(self.limit, self.offset, limits)
+    }
+}
@@ -0,0 +1,77 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::header,
+    response::Response,
+};
+use futures_util::{StreamExt, TryStreamExt};
+use serde::Deserialize;
+use std::io;
+use std::sync::Arc;
+use tokio::io::BufReader;
+use tokio_util::io::StreamReader;
+use utoipa::ToSchema;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::{RegisterObjectLine, RegisterObjectResult};
+use crate::application::use_cases::RegisterObjectsUseCase;
+
+const NDJSON: &str = "application/x-ndjson";
+
+#[derive(Deserialize, ToSchema)]
+pub struct RegisterObjectsQuery {
+    /// Tenant owning the registered objects
+    tenant_id: String,
+}
+
+/// POST /v1/objects:register
+/// Register objects over blobs already in the blob store (admin only)
+///
+/// For migrations that place blobs in the store directly, e.g. with a
+/// filesystem import tool. Each request body line is a `RegisterObjectLine`
+/// naming an object and the content hash of its blob; the object is created
+/// committed and takes a reference on the blob, after checking the blob is
+/// in the store. Lines are processed as they arrive and answered with one
+/// `RegisterObjectResult` per line, so huge imports stream both ways. A
+/// failed line does not stop the import; a line longer than 64 KiB does.
+#[utoipa::path(
+    post,
+    path = "/v1/objects:register",
+    tag = "objects",
+    params(
+        ("tenant_id" = String, Query, description = "Tenant owning the registered objects")
+    ),
+    request_body(
+        content = RegisterObjectLine,
+        content_type = "application/x-ndjson",
+        description = "One object per line"
+    ),
+    responses(
+        (status = 200, description = "One result per non-blank line, in order", content(
+            (RegisterObjectResult = "application/x-ndjson")
+        )),
+        (status = 400, description = "Invalid tenant_id"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn register_objects_handler(
+    State(use_case): State<Arc<RegisterObjectsUseCase>>,
+    Query(query): Query<RegisterObjectsQuery>,
+    body: Body,
+) -> Result<Response, ApiError> {
+    let lines = BufReader::new(StreamReader::new(
+        body.into_data_stream().map_err(io::Error::other),
+    ));
+    let results = use_case.execute(&query.tenant_id, lines)?.map(|result| {
+        let mut line = serde_json::to_vec(&result)?;
+        line.push(b'\n');
+        Ok::<_, serde_json::Error>(Bytes::from(line))
+    });
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, NDJSON)
+        .body(Body::from_stream(results))
+        .map_err(|e| ApiError::internal_error(format!("Failed to build response: {}", e)))
+}
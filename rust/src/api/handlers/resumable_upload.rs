@@ -0,0 +1,322 @@
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
+use axum::Extension;
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use std::io;
+use std::sync::Arc;
+use tokio_util::io::StreamReader;
+use utoipa::ToSchema;
+
+use super::upload::{content_etag, expected_hash};
+use crate::api::errors::ApiError;
+use crate::application::dto::{
+    ObjectDto, ResumableUploadDto, ResumeOutcome, UploadChunk, UploadRequest,
+};
+use crate::application::use_cases::UploadObjectUseCase;
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::{ContentEncoding, ObjectId, StorageClass, TenantId};
+
+/// Response header carrying the bytes received so far
+const UPLOAD_OFFSET_HEADER: &str = "upload-offset";
+
+#[derive(Deserialize, ToSchema)]
+pub struct StartUploadQuery {
+    /// Object namespace
+    namespace: String,
+    /// Tenant identifier
+    tenant_id: String,
+    /// Human-readable key for retrieval
+    key: Option<String>,
+    /// Storage class ('hot' or 'cold')
+    storage_class: Option<String>,
+    /// MIME type of the content, used to pick a text extractor
+    content_type: Option<String>,
+    /// Compression of the content ('gzip' or 'zstd'), stored as sent
+    content_encoding: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UploadQuery {
+    /// Tenant identifier for authorization
+    tenant_id: String,
+}
+
+/// Read `Content-Range: bytes <first>-<last>/<total>` into a chunk
+///
+/// The total may be `*` while unknown; `bytes */<total>` sends no bytes and
+/// commits an upload whose bytes have all arrived.
+fn parse_content_range(headers: &HeaderMap) -> Result<UploadChunk, ApiError> {
+    let invalid = || {
+        ApiError::bad_request(
+            "Content-Range must be 'bytes <first>-<last>/<total>', 'bytes <first>-<last>/*' or 'bytes */<total>'",
+        )
+    };
+    let value = headers
+        .get(header::CONTENT_RANGE)
+        .ok_or_else(|| ApiError::bad_request("Content-Range is required"))?
+        .to_str()
+        .map_err(|_| invalid())?;
+
+    let (range, total) = value
+        .trim()
+        .strip_prefix("bytes ")
+        .and_then(|spec| spec.split_once('/'))
+        .ok_or_else(invalid)?;
+    let total_size = match total.trim() {
+        "*" => None,
+        total => Some(total.parse::<u64>().map_err(|_| invalid())?),
+    };
+    let range = match range.trim() {
+        "*" if total_size.is_some() => None,
+        range => {
+            let (first, last) = range.split_once('-').ok_or_else(invalid)?;
+            let first = first.parse::<u64>().map_err(|_| invalid())?;
+            let last = last.parse::<u64>().map_err(|_| invalid())?;
+            if first > last {
+                return Err(invalid());
+            }
+            Some((first, last))
+        }
+    };
+
+    Ok(UploadChunk {
+        range,
+        total_size,
+        expected_hash: None,
+    })
+}
+
+fn offset_header(offset: u64) -> (header::HeaderName, HeaderValue) {
+    (
+        header::HeaderName::from_static(UPLOAD_OFFSET_HEADER),
+        HeaderValue::from(offset),
+    )
+}
+
+/// Users may only reach uploads of their own tenant; admins any tenant's
+fn authorize_tenant(user_context: &UserContext, tenant_id: &str) -> Result<(), ApiError> {
+    if !user_context.is_admin() && tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Cannot upload objects to other tenants".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_upload(id: &str, tenant_id: &str) -> Result<(ObjectId, TenantId), ApiError> {
+    let upload_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid upload ID: {}", e)))?;
+    let tenant_id = TenantId::from_string(tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+    Ok((upload_id, tenant_id))
+}
+
+/// POST /v1/objects/uploads
+/// Start a resumable upload
+///
+/// Reserves the object and returns the upload ID. Send the content with
+/// `PUT /v1/objects/uploads/{id}` in one or more `Content-Range` chunks.
+#[utoipa::path(
+    post,
+    path = "/v1/objects/uploads",
+    tag = "objects",
+    params(
+        ("namespace" = String, Query, description = "Object namespace"),
+        ("tenant_id" = String, Query, description = "Tenant identifier"),
+        ("key" = Option<String>, Query, description = "Human-readable key for retrieval"),
+        ("storage_class" = Option<String>, Query, description = "Storage class ('hot' or 'cold')"),
+        ("content_type" = Option<String>, Query, description = "MIME type of the content"),
+        ("content_encoding" = Option<String>, Query, description = "Compression of the content ('gzip' or 'zstd'); stored as sent and decoded on download for clients that don't accept it")
+    ),
+    responses(
+        (status = 201, description = "Upload started", body = ResumableUploadDto),
+        (status = 400, description = "Invalid request parameters"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn start_upload_handler(
+    State(use_case): State<Arc<UploadObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Query(query): Query<StartUploadQuery>,
+) -> Result<(StatusCode, HeaderMap, Json<ResumableUploadDto>), ApiError> {
+    authorize_tenant(&user_context, &query.tenant_id)?;
+    let storage_class = query
+        .storage_class
+        .map(|sc| sc.parse::<StorageClass>())
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+    let content_encoding = query
+        .content_encoding
+        .as_deref()
+        .map(ContentEncoding::from_header)
+        .transpose()
+        .map_err(ApiError::bad_request)?
+        .flatten();
+
+    let upload = use_case
+        .start_resumable(UploadRequest {
+            namespace: query.namespace,
+            tenant_id: query.tenant_id,
+            key: query.key,
+            storage_class,
+            content_type: query.content_type,
+            content_encoding,
+            expected_hash: None,
+            idempotency_key: None,
+        })
+        .await?;
+
+    let mut headers = HeaderMap::from_iter([offset_header(upload.offset)]);
+    if let Ok(location) =
+        HeaderValue::from_str(&format!("/v1/objects/uploads/{}", upload.upload_id))
+    {
+        headers.insert(header::LOCATION, location);
+    }
+
+    Ok((StatusCode::CREATED, headers, Json(upload)))
+}
+
+/// PUT /v1/objects/uploads/{id}
+/// Send a chunk of a resumable upload
+///
+/// The chunk must start at the upload's offset. When a transfer breaks off,
+/// the bytes that arrived are kept: read the offset with `HEAD` and send
+/// the rest. The chunk that completes the total size commits the object,
+/// after checking the whole content against `X-Content-Hash` if sent.
+#[utoipa::path(
+    put,
+    path = "/v1/objects/uploads/{id}",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Upload ID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization"),
+        ("Content-Range" = String, Header, description = "'bytes <first>-<last>/<total>' ('*' total while unknown), or 'bytes */<total>' to commit once every byte has arrived"),
+        ("X-Content-Hash" = Option<String>, Header, description = "Expected SHA-256 of the whole content (hex), checked before the commit")
+    ),
+    request_body = Vec<u8>,
+    responses(
+        (status = 201, description = "Last chunk received and object committed", body = ObjectDto),
+        (status = 202, description = "Chunk received; `Upload-Offset` is where the next one starts", body = ResumableUploadDto),
+        (status = 400, description = "Invalid Content-Range or content hash mismatch"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Upload not found"),
+        (status = 409, description = "Chunk does not start at the upload's offset, or another chunk is being received"),
+        (status = 413, description = "Content exceeds the maximum object size"),
+        (status = 422, description = "Content rejected by the upload content scanner"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "Content scanner unavailable")
+    )
+)]
+pub async fn resume_upload_handler(
+    State(use_case): State<Arc<UploadObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<UploadQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, ApiError> {
+    authorize_tenant(&user_context, &query.tenant_id)?;
+    let (upload_id, tenant_id) = parse_upload(&id, &query.tenant_id)?;
+    let chunk = UploadChunk {
+        expected_hash: expected_hash(&headers, &use_case).await?,
+        ..parse_content_range(&headers)?
+    };
+
+    let stream = body.into_data_stream().map_err(io::Error::other);
+    let reader = Box::pin(StreamReader::new(stream));
+
+    match use_case
+        .resume(&upload_id, &tenant_id, chunk, reader)
+        .await?
+    {
+        ResumeOutcome::Incomplete(upload) => Ok((
+            StatusCode::ACCEPTED,
+            [offset_header(upload.offset)],
+            Json(upload),
+        )
+            .into_response()),
+        ResumeOutcome::Committed(object) => {
+            let mut response_headers = HeaderMap::new();
+            if let Some(etag) = object.content_hash.as_deref().and_then(content_etag) {
+                response_headers.insert(header::ETAG, etag);
+            }
+            Ok((StatusCode::CREATED, response_headers, Json(object)).into_response())
+        }
+    }
+}
+
+/// HEAD /v1/objects/uploads/{id}
+/// Read how many bytes of a resumable upload have arrived
+#[utoipa::path(
+    head,
+    path = "/v1/objects/uploads/{id}",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Upload ID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization")
+    ),
+    responses(
+        (status = 200, description = "`Upload-Offset` holds the bytes received so far"),
+        (status = 400, description = "Invalid upload ID or tenant"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Upload not found, or already committed"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn upload_offset_handler(
+    State(use_case): State<Arc<UploadObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<UploadQuery>,
+) -> Result<(StatusCode, [(header::HeaderName, HeaderValue); 1]), ApiError> {
+    authorize_tenant(&user_context, &query.tenant_id)?;
+    let (upload_id, tenant_id) = parse_upload(&id, &query.tenant_id)?;
+
+    let offset = use_case.upload_offset(&upload_id, &tenant_id).await?;
+
+    Ok((StatusCode::OK, [offset_header(offset)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content_range(value: &'static str) -> Result<UploadChunk, ApiError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_RANGE, HeaderValue::from_static(value));
+        parse_content_range(&headers)
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        let chunk = content_range("bytes 0-99/1000").ok().unwrap();
+        assert_eq!(chunk.range, Some((0, 99)));
+        assert_eq!(chunk.total_size, Some(1000));
+
+        let chunk = content_range("bytes 100-199/*").ok().unwrap();
+        assert_eq!(chunk.range, Some((100, 199)));
+        assert_eq!(chunk.total_size, None);
+
+        let chunk = content_range("bytes */1000").ok().unwrap();
+        assert_eq!(chunk.range, None);
+        assert_eq!(chunk.total_size, Some(1000));
+    }
+
+    #[test]
+    fn test_parse_content_range_rejects_invalid_forms() {
+        assert!(content_range("bytes */*").is_err());
+        assert!(content_range("bytes 10-5/100").is_err());
+        assert!(content_range("items 0-9/10").is_err());
+        assert!(content_range("bytes 0-/10").is_err());
+        assert!(parse_content_range(&HeaderMap::new()).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::{ObjectDto, ObjectRetentionRequest};
+use crate::application::use_cases::ObjectRetentionUseCase;
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::{ObjectId, TenantId};
+
+#[derive(Deserialize, ToSchema)]
+pub struct ObjectRetentionQuery {
+    /// Tenant identifier for authorization
+    tenant_id: String,
+}
+
+/// PUT /v1/objects/{id}/retention
+/// Lock an object against overwrite and deletion (WORM)
+///
+/// `retention_until` keeps the object until that time and can only be
+/// extended. `legal_hold` keeps it until the hold is released. Requires the
+/// `objects:retention` permission.
+#[utoipa::path(
+    put,
+    path = "/v1/objects/{id}/retention",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization")
+    ),
+    request_body = ObjectRetentionRequest,
+    responses(
+        (status = 200, description = "Object with its updated lock", body = ObjectDto),
+        (status = 400, description = "Invalid retention date, or nothing to set"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden, or retention would be shortened"),
+        (status = 404, description = "Object not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn update_retention_handler(
+    State(use_case): State<Arc<ObjectRetentionUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<ObjectRetentionQuery>,
+    Json(request): Json<ObjectRetentionRequest>,
+) -> Result<Json<ObjectDto>, ApiError> {
+    // Same tenant ownership rules as modifying the object
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            axum::http::StatusCode::FORBIDDEN,
+            "Cannot modify objects of other tenants".to_string(),
+        ));
+    }
+
+    let object_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+    let tenant_id = TenantId::from_string(&query.tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+
+    let object = use_case.execute(&object_id, &tenant_id, &request).await?;
+
+    Ok(Json(object))
+}
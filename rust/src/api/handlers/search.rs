@@ -29,13 +29,7 @@ pub async fn search_handler(
     Json(request): Json<SearchRequest>,
 ) -> Result<Json<SearchResponse>, ApiError> {
     // Validate the request
-    if let Err((status, error_response)) = validate_and_respond(&request) {
-        return Err(ApiError::new(
-            status,
-            serde_json::to_string(&error_response)
-                .unwrap_or_else(|_| "Validation error".to_string()),
-        ));
-    }
+    validate_and_respond(&request)?;
 
     // Validate tenant ownership - users can only search objects from their own tenant
     // Admins can search objects from any tenant
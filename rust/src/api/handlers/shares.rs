@@ -0,0 +1,245 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::{ObjectShareDto, ObjectShareListResponse, ShareObjectRequest};
+use crate::application::use_cases::ShareObjectUseCase;
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::{ObjectId, TenantId};
+
+#[derive(Deserialize, ToSchema)]
+pub struct ObjectShareQuery {
+    /// Tenant owning the shared object
+    tenant_id: String,
+}
+
+/// Only the owning tenant (or an admin) manages an object's shares
+fn authorize_owner(user_context: &UserContext, query: &ObjectShareQuery) -> Result<(), ApiError> {
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Cannot share objects of other tenants".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct IncomingShareQuery {
+    /// Tenant the shares are offered to
+    tenant_id: String,
+}
+
+/// Only the grantee (or an admin) sees and accepts the shares offered to it
+fn authorize_grantee(
+    user_context: &UserContext,
+    query: &IncomingShareQuery,
+) -> Result<TenantId, ApiError> {
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Cannot accept shares offered to other tenants".to_string(),
+        ));
+    }
+    TenantId::from_string(&query.tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))
+}
+
+fn parse_share_id(share_id: &str) -> Result<Uuid, ApiError> {
+    share_id
+        .parse::<Uuid>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid share ID: {}", e)))
+}
+
+fn parse_object(id: &str, query: &ObjectShareQuery) -> Result<(ObjectId, TenantId), ApiError> {
+    let object_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+    let tenant_id = TenantId::from_string(&query.tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+    Ok((object_id, tenant_id))
+}
+
+/// POST /v1/objects/{id}/shares
+/// Offer an object to another tenant
+///
+/// Records a pending grant; nothing is created for the grantee until it
+/// accepts the grant with `POST /v1/shares/{share_id}/accept`.
+#[utoipa::path(
+    post,
+    path = "/v1/objects/{id}/shares",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant owning the object")
+    ),
+    request_body = ShareObjectRequest,
+    responses(
+        (status = 201, description = "Share offered", body = ObjectShareDto),
+        (status = 400, description = "Invalid grantee, namespace or key"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Object not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn share_object_handler(
+    State(use_case): State<Arc<ShareObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<ObjectShareQuery>,
+    Json(request): Json<ShareObjectRequest>,
+) -> Result<(StatusCode, Json<ObjectShareDto>), ApiError> {
+    authorize_owner(&user_context, &query)?;
+    let (object_id, tenant_id) = parse_object(&id, &query)?;
+
+    let share = use_case.share(&object_id, &tenant_id, &request).await?;
+
+    Ok((StatusCode::CREATED, Json(share)))
+}
+
+/// GET /v1/objects/{id}/shares
+/// List the grants of an object
+#[utoipa::path(
+    get,
+    path = "/v1/objects/{id}/shares",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant owning the object")
+    ),
+    responses(
+        (status = 200, description = "Grants of the object, oldest first", body = ObjectShareListResponse),
+        (status = 400, description = "Invalid object ID or tenant_id"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_shares_handler(
+    State(use_case): State<Arc<ShareObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<ObjectShareQuery>,
+) -> Result<Json<ObjectShareListResponse>, ApiError> {
+    authorize_owner(&user_context, &query)?;
+    let (object_id, tenant_id) = parse_object(&id, &query)?;
+
+    let shares = use_case.list(&object_id, &tenant_id).await?;
+
+    Ok(Json(ObjectShareListResponse { shares }))
+}
+
+/// DELETE /v1/objects/{id}/shares/{share_id}
+/// Revoke a grant
+///
+/// Deletes the grantee's object of an accepted grant, unless the grantee has
+/// since replaced its content, and drops the grant.
+#[utoipa::path(
+    delete,
+    path = "/v1/objects/{id}/shares/{share_id}",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("share_id" = String, Path, description = "Grant UUID"),
+        ("tenant_id" = String, Query, description = "Tenant owning the object")
+    ),
+    responses(
+        (status = 204, description = "Grant revoked"),
+        (status = 400, description = "Invalid ID or tenant_id"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden, or the grantee's object is locked"),
+        (status = 404, description = "Grant not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn revoke_share_handler(
+    State(use_case): State<Arc<ShareObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path((id, share_id)): Path<(String, String)>,
+    Query(query): Query<ObjectShareQuery>,
+) -> Result<StatusCode, ApiError> {
+    authorize_owner(&user_context, &query)?;
+    let (object_id, tenant_id) = parse_object(&id, &query)?;
+    let share_id = parse_share_id(&share_id)?;
+
+    use_case.revoke(&object_id, &share_id, &tenant_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /v1/shares
+/// List the shares offered to a tenant, pending and accepted
+#[utoipa::path(
+    get,
+    path = "/v1/shares",
+    tag = "objects",
+    params(
+        ("tenant_id" = String, Query, description = "Tenant the shares are offered to")
+    ),
+    responses(
+        (status = 200, description = "Shares offered to the tenant, oldest first", body = ObjectShareListResponse),
+        (status = 400, description = "Invalid tenant_id"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_incoming_shares_handler(
+    State(use_case): State<Arc<ShareObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Query(query): Query<IncomingShareQuery>,
+) -> Result<Json<ObjectShareListResponse>, ApiError> {
+    let tenant_id = authorize_grantee(&user_context, &query)?;
+
+    let shares = use_case.list_incoming(&tenant_id).await?;
+
+    Ok(Json(ObjectShareListResponse { shares }))
+}
+
+/// POST /v1/shares/{share_id}/accept
+/// Accept a share offered to a tenant
+///
+/// Creates an object in the namespace and under the key the grant names,
+/// referencing the shared content without copying it. The object must pass
+/// the namespace's key policy and the content policy like an upload. The
+/// grantee reads it with its own `tenant_id`; the content stays stored until
+/// the owner's object and every shared one are deleted.
+#[utoipa::path(
+    post,
+    path = "/v1/shares/{share_id}/accept",
+    tag = "objects",
+    params(
+        ("share_id" = String, Path, description = "Grant UUID"),
+        ("tenant_id" = String, Query, description = "Tenant the share is offered to")
+    ),
+    responses(
+        (status = 200, description = "Share accepted", body = ObjectShareDto),
+        (status = 400, description = "Invalid ID or tenant_id, or the key or content is not allowed in the namespace"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Grant not found, or the shared object is gone"),
+        (status = 409, description = "Already accepted, or the tenant already has an object under the key"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn accept_share_handler(
+    State(use_case): State<Arc<ShareObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(share_id): Path<String>,
+    Query(query): Query<IncomingShareQuery>,
+) -> Result<Json<ObjectShareDto>, ApiError> {
+    let tenant_id = authorize_grantee(&user_context, &query)?;
+    let share_id = parse_share_id(&share_id)?;
+
+    let share = use_case.accept(&share_id, &tenant_id).await?;
+
+    Ok(Json(share))
+}
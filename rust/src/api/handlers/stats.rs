@@ -0,0 +1,27 @@
+use axum::{extract::State, response::Json};
+use std::sync::Arc;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::StatsResponse;
+use crate::application::use_cases::StatsUseCase;
+
+/// GET /v1/stats
+/// Deduplication statistics (logical vs physical bytes), admin only
+#[utoipa::path(
+    get,
+    path = "/v1/stats",
+    tag = "stats",
+    responses(
+        (status = 200, description = "Statistics retrieved successfully", body = StatsResponse),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Admin access required"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn stats_handler(
+    State(use_case): State<Arc<StatsUseCase>>,
+) -> Result<Json<StatsResponse>, ApiError> {
+    let response = use_case.execute().await?;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,72 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::ObjectStatusResponse;
+use crate::application::use_cases::ObjectStatusUseCase;
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::{ObjectId, TenantId};
+
+#[derive(Deserialize, ToSchema)]
+pub struct ObjectStatusQuery {
+    /// Tenant identifier for authorization
+    tenant_id: String,
+    /// Seconds to wait for an upload in progress to finish (at most 60)
+    wait: Option<u64>,
+}
+
+/// GET /v1/objects/{id}/status
+/// Read an object's lifecycle status, optionally waiting for its upload
+///
+/// With `wait`, a request for an object still `WRITING` is held until its
+/// upload commits or fails, or the wait runs out, whichever comes first.
+/// Without it the status is read once.
+#[utoipa::path(
+    get,
+    path = "/v1/objects/{id}/status",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization"),
+        ("wait" = Option<u64>, Query, description = "Seconds to wait for the upload to finish (at most 60)")
+    ),
+    responses(
+        (status = 200, description = "Object status; WRITING if the wait ran out", body = ObjectStatusResponse),
+        (status = 400, description = "Invalid object ID or tenant"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Object not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn object_status_handler(
+    State(use_case): State<Arc<ObjectStatusUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<ObjectStatusQuery>,
+) -> Result<Json<ObjectStatusResponse>, ApiError> {
+    // Same tenant ownership rules as reading the object
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            axum::http::StatusCode::FORBIDDEN,
+            "Cannot read objects from other tenants".to_string(),
+        ));
+    }
+
+    let object_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+    let tenant_id = TenantId::from_string(&query.tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+    let wait = Duration::from_secs(query.wait.unwrap_or(0));
+
+    let status = use_case.execute(&object_id, &tenant_id, wait).await?;
+
+    Ok(Json(status))
+}
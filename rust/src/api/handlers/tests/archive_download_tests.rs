@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::handlers::download_archive_handler;
+    use crate::application::ports::{MockBlobStore, MockObjectRepository};
+    use crate::application::use_cases::DownloadArchiveUseCase;
+    use crate::domain::authorization::UserContext;
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::get,
+        Extension, Router,
+    };
+    use futures_util::{stream, StreamExt};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    /// App over an empty namespace; without `list`, the repository panics
+    /// when touched
+    fn app(user_tenant: &str, list: bool) -> Router {
+        let mut object_repo = MockObjectRepository::new();
+        if list {
+            object_repo.expect_count().returning(|_, _, _, _, _| Ok(0));
+            object_repo
+                .expect_stream()
+                .returning(|_, _, _, _, _, _| stream::empty().boxed());
+        }
+
+        let use_case = Arc::new(DownloadArchiveUseCase::new(
+            Arc::new(object_repo),
+            Arc::new(MockBlobStore::new()),
+        ));
+
+        let user = UserContext::new(
+            "test-user".to_string(),
+            user_tenant.to_string(),
+            vec!["user".to_string()],
+            HashSet::new(),
+            false,
+            None,
+        );
+
+        Router::new()
+            .route(
+                "/v1/objects:archive",
+                get(download_archive_handler).with_state(use_case),
+            )
+            .layer(Extension(user))
+    }
+
+    async fn get_archive(app: Router, query: &str) -> axum::response::Response {
+        app.oneshot(
+            Request::builder()
+                .uri(format!("/v1/objects:archive?{query}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_archive_of_other_tenant_forbidden() {
+        let tenant = Uuid::new_v4().to_string();
+        let other = Uuid::new_v4().to_string();
+
+        let response = get_archive(
+            app(&tenant, false),
+            &format!("namespace=photos&tenant_id={other}"),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_zip_archive_is_an_attachment() {
+        let tenant = Uuid::new_v4().to_string();
+
+        let response = get_archive(
+            app(&tenant, true),
+            &format!("namespace=photos&tenant_id={tenant}&prefix=a/&format=zip"),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/zip");
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"photos.zip\""
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        // Local file header of the manifest entry
+        assert!(body.starts_with(b"PK\x03\x04"));
+    }
+}
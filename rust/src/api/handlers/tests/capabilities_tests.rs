@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::handlers::capabilities::CapabilitiesResponse;
+    use crate::api::handlers::capabilities_handler;
+    use crate::api::middleware::auth_config::AuthMiddlewareConfig;
+    use crate::api::middleware::config::MiddlewareConfig;
+    use crate::api::middleware::oidc_config::OidcConfig;
+    use crate::api::openapi::ApiDoc;
+    use crate::config::Config;
+    use crate::domain::value_objects::{ContentEncoding, StorageClass};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use utoipa::OpenApi;
+
+    const ADMIN_TOKEN: &str = "admin-token-do-not-leak";
+    const CLIENT_SECRET: &str = "oidc-secret-do-not-leak";
+
+    fn config() -> Config {
+        let mut config = Config::from_env();
+        config.max_upload_size_bytes = 64 * 1024 * 1024;
+        config.max_object_size_bytes = 1024 * 1024 * 1024;
+        config.metadata_max_tags = 25;
+        config.admin_token = Some(ADMIN_TOKEN.to_string());
+        config.oidc_client_secret = Some(CLIENT_SECRET.to_string());
+        config
+    }
+
+    fn middleware() -> MiddlewareConfig {
+        let mut middleware = MiddlewareConfig::default();
+        middleware.auth = AuthMiddlewareConfig::new(true, true, Some(ADMIN_TOKEN.to_string()));
+        middleware.oidc = OidcConfig {
+            jwks_url: Some("https://idp.internal.example/jwks".to_string()),
+            ..OidcConfig::new(true, None, None)
+        };
+        middleware
+    }
+
+    async fn get_capabilities(capabilities: CapabilitiesResponse) -> (StatusCode, String) {
+        let app = Router::new().route(
+            "/v1/capabilities",
+            get(capabilities_handler).with_state(Arc::new(capabilities)),
+        );
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/capabilities")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_report_effective_limits() {
+        let capabilities =
+            CapabilitiesResponse::new(&config(), &middleware()).with_max_archive_entries(500);
+
+        let (status, body) = get_capabilities(capabilities).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let response: CapabilitiesResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(response.limits.max_upload_size_bytes, 64 * 1024 * 1024);
+        assert_eq!(response.limits.max_object_size_bytes, 1024 * 1024 * 1024);
+        assert_eq!(response.limits.max_archive_entries, 500);
+        assert_eq!(response.limits.metadata_max_tags, 25);
+        assert_eq!(response.hash_algorithm, "sha256");
+        assert_eq!(
+            response.storage_classes,
+            [StorageClass::Hot, StorageClass::Cold]
+        );
+        assert!(response.uploads.resumable);
+        assert_eq!(response.uploads.archive_formats, ["tar", "zip"]);
+        assert_eq!(
+            response.compression.upload_encodings,
+            [ContentEncoding::Gzip, ContentEncoding::Zstd]
+        );
+        assert!(response.auth.required);
+        assert_eq!(response.auth.modes, ["api_key", "jwt"]);
+        assert!(!response.auth.jwt_algorithms.is_empty());
+        assert!(response.api_versions.contains(&"v1".to_string()));
+        assert!(!response.features.webhooks);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_leave_out_secrets() {
+        let (_, body) = get_capabilities(CapabilitiesResponse::new(&config(), &middleware())).await;
+
+        assert!(!body.contains(ADMIN_TOKEN));
+        assert!(!body.contains(CLIENT_SECRET));
+        assert!(!body.contains("idp.internal.example"));
+    }
+
+    #[test]
+    fn test_capabilities_without_auth_list_no_modes() {
+        let mut middleware = middleware();
+        middleware.auth.enabled = false;
+
+        let capabilities = CapabilitiesResponse::new(&config(), &middleware);
+
+        assert!(!capabilities.auth.required);
+        assert!(capabilities.auth.modes.is_empty());
+        assert!(capabilities.auth.jwt_algorithms.is_empty());
+    }
+
+    #[test]
+    fn test_capabilities_are_documented() {
+        let openapi = ApiDoc::openapi();
+
+        assert!(openapi.paths.paths.contains_key("/v1/capabilities"));
+        assert!(openapi
+            .components
+            .unwrap()
+            .schemas
+            .contains_key("CapabilitiesResponse"));
+    }
+}
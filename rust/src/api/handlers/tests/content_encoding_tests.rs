@@ -0,0 +1,255 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::handlers::{download_handler, head_handler};
+    use crate::application::dto::ObjectHead;
+    use crate::application::ports::{MockBlobStore, MockObjectRepository};
+    use crate::application::use_cases::DownloadObjectUseCase;
+    use crate::domain::authorization::UserContext;
+    use crate::domain::entities::Object;
+    use crate::domain::value_objects::{
+        ContentEncoding, ContentHash, Namespace, StorageClass, TenantId,
+    };
+    use async_compression::tokio::bufread::{GzipEncoder, ZstdEncoder};
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, Method, Request, StatusCode},
+        response::Response,
+        routing::get,
+        Extension, Router,
+    };
+    use std::collections::HashSet;
+    use std::io::Cursor;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use tokio::io::AsyncReadExt;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const TEXT: &[u8] = b"id,name\n1,alpha\n2,beta\n";
+
+    async fn encode(encoding: Option<ContentEncoding>) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        match encoding {
+            None => encoded.extend_from_slice(TEXT),
+            Some(ContentEncoding::Gzip) => {
+                GzipEncoder::new(TEXT)
+                    .read_to_end(&mut encoded)
+                    .await
+                    .unwrap();
+            }
+            Some(ContentEncoding::Zstd) => {
+                ZstdEncoder::new(TEXT)
+                    .read_to_end(&mut encoded)
+                    .await
+                    .unwrap();
+            }
+        }
+        encoded
+    }
+
+    /// A committed object whose blob holds `stored`, uploaded with `encoding`
+    fn stored_object(encoding: Option<ContentEncoding>, stored: &[u8]) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("test").unwrap(),
+            TenantId::new(Uuid::new_v4()),
+            Some("report.csv".to_string()),
+            StorageClass::Hot,
+        );
+        object.set_content_type("text/csv".to_string());
+        object.set_content_encoding(encoding);
+        object
+            .commit(
+                &ContentHash::from_str(&"a".repeat(64)).unwrap(),
+                stored.len() as u64,
+            )
+            .unwrap();
+        object
+    }
+
+    fn app(object: Object, stored: Vec<u8>) -> Router {
+        let mut object_repo = MockObjectRepository::new();
+        let found = object.clone();
+        object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(found.clone())));
+        let head = ObjectHead::from_committed(&object);
+        object_repo
+            .expect_head()
+            .returning(move |_| Ok(head.clone()));
+
+        let mut blob_store = MockBlobStore::new();
+        blob_store.expect_exists().returning(|_, _| Ok(true));
+        blob_store
+            .expect_read()
+            .returning(move |_, _| Ok(Box::pin(Cursor::new(stored.clone()))));
+
+        let use_case = Arc::new(DownloadObjectUseCase::new(
+            Arc::new(object_repo),
+            Arc::new(blob_store),
+        ));
+        let user = UserContext::new(
+            "test-user".to_string(),
+            object.tenant_id().to_string(),
+            vec!["user".to_string()],
+            HashSet::new(),
+            false,
+            None,
+        );
+
+        Router::new()
+            .route(
+                "/v1/objects/{id}",
+                get(download_handler)
+                    .head(head_handler)
+                    .with_state(use_case),
+            )
+            .layer(Extension(user))
+    }
+
+    async fn request(
+        method: Method,
+        encoding: Option<ContentEncoding>,
+        accept_encoding: Option<&str>,
+    ) -> (Response, Vec<u8>) {
+        let stored = encode(encoding).await;
+        let object = stored_object(encoding, &stored);
+        let uri = format!(
+            "/v1/objects/{}?tenant_id={}",
+            object.id(),
+            object.tenant_id()
+        );
+
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(accept_encoding) = accept_encoding {
+            request = request.header(header::ACCEPT_ENCODING, accept_encoding);
+        }
+        let response = app(object, stored.clone())
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        (response, stored)
+    }
+
+    async fn body(response: Response) -> Vec<u8> {
+        to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_stored_encoding_sent_as_is_when_accepted() {
+        let cases = [
+            (ContentEncoding::Gzip, "gzip"),
+            (ContentEncoding::Gzip, "br, gzip;q=0.5"),
+            (ContentEncoding::Gzip, "*"),
+            (ContentEncoding::Zstd, "gzip, zstd"),
+        ];
+
+        for (encoding, accept_encoding) in cases {
+            let (response, stored) =
+                request(Method::GET, Some(encoding), Some(accept_encoding)).await;
+
+            assert_eq!(response.status(), StatusCode::OK, "{accept_encoding}");
+            let headers = response.headers().clone();
+            assert_eq!(headers[header::CONTENT_ENCODING], encoding.to_string());
+            assert_eq!(headers[header::CONTENT_LENGTH], stored.len().to_string());
+            assert_eq!(headers[header::ETAG], format!("\"{}\"", "a".repeat(64)));
+            assert_eq!(body(response).await, stored);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stored_encoding_decoded_when_not_accepted() {
+        let cases = [
+            (ContentEncoding::Gzip, None),
+            (ContentEncoding::Gzip, Some("br")),
+            (ContentEncoding::Gzip, Some("gzip;q=0, *")),
+            (ContentEncoding::Zstd, Some("gzip")),
+            (ContentEncoding::Zstd, Some("identity")),
+        ];
+
+        for (encoding, accept_encoding) in cases {
+            let (response, _) = request(Method::GET, Some(encoding), accept_encoding).await;
+
+            assert_eq!(response.status(), StatusCode::OK, "{accept_encoding:?}");
+            let headers = response.headers().clone();
+            assert!(headers.get(header::CONTENT_ENCODING).is_none());
+            assert!(headers.get(header::CONTENT_LENGTH).is_none());
+            // The content hash names the stored bytes, not the decoded ones
+            assert_eq!(headers[header::ETAG], format!("W/\"{}\"", "a".repeat(64)));
+            assert_eq!(headers["x-content-hash"], "a".repeat(64));
+            assert_eq!(body(response).await, TEXT);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unencoded_object_ignores_accept_encoding() {
+        let (response, _) = request(Method::GET, None, Some("gzip")).await;
+
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(
+            response.headers()[header::ETAG],
+            format!("\"{}\"", "a".repeat(64))
+        );
+        assert_eq!(body(response).await, TEXT);
+    }
+
+    #[tokio::test]
+    async fn test_download_sets_content_disposition() {
+        let object = stored_object(None, TEXT);
+        let base = format!(
+            "/v1/objects/{}?tenant_id={}",
+            object.id(),
+            object.tenant_id()
+        );
+        let cases = [
+            (
+                "",
+                StatusCode::OK,
+                Some("attachment; filename=\"report.csv\""),
+            ),
+            (
+                "&disposition=inline&filename=q1%0D%0A.csv",
+                StatusCode::OK,
+                Some("inline; filename=\"q1.csv\""),
+            ),
+            ("&disposition=open", StatusCode::BAD_REQUEST, None),
+        ];
+
+        for (query, status, expected) in cases {
+            let response = app(object.clone(), TEXT.to_vec())
+                .oneshot(
+                    Request::get(format!("{base}{query}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), status, "{query}");
+            if let Some(expected) = expected {
+                assert_eq!(response.headers()[header::CONTENT_DISPOSITION], expected);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_head_matches_get_encoding_headers() {
+        let (accepted, stored) =
+            request(Method::HEAD, Some(ContentEncoding::Gzip), Some("gzip")).await;
+        let (decoded, _) = request(Method::HEAD, Some(ContentEncoding::Gzip), None).await;
+
+        assert_eq!(accepted.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(
+            accepted.headers()[header::CONTENT_LENGTH],
+            stored.len().to_string()
+        );
+        assert!(decoded.headers().get(header::CONTENT_ENCODING).is_none());
+        assert!(decoded.headers().get(header::CONTENT_LENGTH).is_none());
+        assert!(decoded.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .starts_with("W/"));
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::handlers::{download_handler, head_handler};
+    use crate::application::dto::ObjectHead;
+    use crate::application::ports::{MockBlobStore, MockObjectRepository};
+    use crate::application::use_cases::DownloadObjectUseCase;
+    use crate::domain::authorization::UserContext;
+    use crate::domain::entities::Object;
+    use crate::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
+    use axum::{
+        body::Body,
+        http::{header, Method, Request, StatusCode},
+        routing::head,
+        Extension, Router,
+    };
+    use std::collections::HashSet;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn committed_object(tenant_id: &TenantId) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("test").unwrap(),
+            tenant_id.clone(),
+            Some("report.csv".to_string()),
+            StorageClass::Cold,
+        );
+        object.set_content_type("text/csv".to_string());
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 42)
+            .unwrap();
+        object
+    }
+
+    /// App whose blob store has no expectations: touching it panics
+    fn app(head: Option<ObjectHead>, user_tenant: &str) -> Router {
+        let mut object_repo = MockObjectRepository::new();
+        object_repo
+            .expect_head()
+            .returning(move |_| Ok(head.clone()));
+
+        let use_case = Arc::new(DownloadObjectUseCase::new(
+            Arc::new(object_repo),
+            Arc::new(MockBlobStore::new()),
+        ));
+
+        let user = UserContext::new(
+            "test-user".to_string(),
+            user_tenant.to_string(),
+            vec!["user".to_string()],
+            HashSet::new(),
+            false,
+            None,
+        );
+
+        Router::new()
+            .route(
+                "/v1/objects/{id}",
+                head(head_handler)
+                    .get(download_handler)
+                    .with_state(use_case),
+            )
+            .layer(Extension(user))
+    }
+
+    async fn head_request(app: Router, id: &str, tenant_id: &str) -> axum::response::Response {
+        conditional_head_request(app, id, tenant_id, &[]).await
+    }
+
+    async fn conditional_head_request(
+        app: Router,
+        id: &str,
+        tenant_id: &str,
+        headers: &[(header::HeaderName, &str)],
+    ) -> axum::response::Response {
+        conditional_request(app, Method::HEAD, id, tenant_id, headers).await
+    }
+
+    async fn conditional_request(
+        app: Router,
+        method: Method,
+        id: &str,
+        tenant_id: &str,
+        headers: &[(header::HeaderName, &str)],
+    ) -> axum::response::Response {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("/v1/objects/{id}?tenant_id={tenant_id}"));
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_head_returns_object_headers() {
+        let tenant_id = TenantId::new(Uuid::new_v4()).to_string();
+        let object = committed_object(&TenantId::from_string(&tenant_id).unwrap());
+        let id = object.id().to_string();
+
+        let response = head_request(
+            app(ObjectHead::from_committed(&object), &tenant_id),
+            &id,
+            &tenant_id,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers[header::CONTENT_LENGTH], "42");
+        assert_eq!(headers[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(headers[header::ETAG], format!("\"{}\"", "a".repeat(64)));
+        assert!(headers[header::LAST_MODIFIED]
+            .to_str()
+            .unwrap()
+            .ends_with(" GMT"));
+        assert_eq!(
+            response.extensions().get::<StorageClass>(),
+            Some(&StorageClass::Cold)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_head_missing_object_is_not_found() {
+        let tenant_id = TenantId::new(Uuid::new_v4()).to_string();
+
+        let response = head_request(
+            app(None, &tenant_id),
+            &Uuid::new_v4().to_string(),
+            &tenant_id,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_head_other_tenant_is_forbidden() {
+        let object = committed_object(&TenantId::new(Uuid::new_v4()));
+        let id = object.id().to_string();
+        let other_tenant = object.tenant_id().to_string();
+
+        let response = head_request(
+            app(
+                ObjectHead::from_committed(&object),
+                &Uuid::new_v4().to_string(),
+            ),
+            &id,
+            &other_tenant,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_head_matching_if_none_match_is_not_modified() {
+        let tenant_id = TenantId::new(Uuid::new_v4()).to_string();
+        let object = committed_object(&TenantId::from_string(&tenant_id).unwrap());
+        let id = object.id().to_string();
+        let etag = format!("W/\"{}\"", "a".repeat(64));
+
+        let response = conditional_head_request(
+            app(ObjectHead::from_committed(&object), &tenant_id),
+            &id,
+            &tenant_id,
+            &[
+                (header::IF_NONE_MATCH, etag.as_str()),
+                // Ignored: If-None-Match takes precedence
+                (header::IF_MODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:37 GMT"),
+            ],
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            response.headers()[header::ETAG],
+            format!("\"{}\"", "a".repeat(64))
+        );
+        assert!(response.headers().contains_key(header::LAST_MODIFIED));
+    }
+
+    #[tokio::test]
+    async fn test_head_failed_if_unmodified_since_is_precondition_failed() {
+        let tenant_id = TenantId::new(Uuid::new_v4()).to_string();
+        let object = committed_object(&TenantId::from_string(&tenant_id).unwrap());
+        let id = object.id().to_string();
+
+        let response = conditional_head_request(
+            app(ObjectHead::from_committed(&object), &tenant_id),
+            &id,
+            &tenant_id,
+            &[(header::IF_UNMODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:37 GMT")],
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_get_answered_without_opening_blob() {
+        // Only the metadata record is read: loading the object for a
+        // download or opening its blob would hit a mock without expectations
+        let tenant_id = TenantId::new(Uuid::new_v4()).to_string();
+        let object = committed_object(&TenantId::from_string(&tenant_id).unwrap());
+        let id = object.id().to_string();
+        let etag = format!("\"{}\"", "a".repeat(64));
+
+        let not_modified = conditional_request(
+            app(ObjectHead::from_committed(&object), &tenant_id),
+            Method::GET,
+            &id,
+            &tenant_id,
+            &[(header::IF_NONE_MATCH, etag.as_str())],
+        )
+        .await;
+        assert_eq!(not_modified.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(not_modified.headers()[header::ETAG], etag);
+
+        let failed = conditional_request(
+            app(ObjectHead::from_committed(&object), &tenant_id),
+            Method::GET,
+            &id,
+            &tenant_id,
+            &[(header::IF_MATCH, "\"other\"")],
+        )
+        .await;
+        assert_eq!(failed.status(), StatusCode::PRECONDITION_FAILED);
+    }
+}
@@ -1,13 +1,60 @@
 #[cfg(test)]
 mod tests {
-    use crate::api::handlers::health_handler;
+    use crate::api::handlers::health::{download_stats, liveness_response};
+    use crate::application::ports::{MockBlobStore, MockObjectRepository};
+    use crate::application::use_cases::DownloadObjectUseCase;
+    use crate::domain::entities::Object;
+    use crate::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
     use axum::http::StatusCode;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
 
     #[tokio::test]
     async fn test_health_handler() {
-        let (status, body) = health_handler().await;
+        let (status, body) = liveness_response(false, Duration::from_secs(90));
 
         assert_eq!(status, StatusCode::OK);
         assert_eq!(body.0["status"], "healthy");
+        assert_eq!(body.0["uptime_seconds"], 90);
+    }
+
+    #[tokio::test]
+    async fn test_liveness_fails_while_shutting_down() {
+        let (status, body) = liveness_response(true, Duration::ZERO);
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body.0["checks"]["process"], "shutting_down");
+    }
+
+    #[tokio::test]
+    async fn test_download_stats_report_ghost_objects() {
+        let mut object = Object::new(
+            Namespace::from_str("test").unwrap(),
+            TenantId::new(Uuid::new_v4()),
+            Some("key".to_string()),
+            StorageClass::Hot,
+        );
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 123)
+            .unwrap();
+        let object_id = *object.id();
+
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store.expect_exists().returning(|_, _| Ok(false));
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store));
+
+        assert_eq!(download_stats(&use_case)["ghost_objects"]["detected"], 0);
+        assert!(use_case.execute_by_id(&object_id).await.is_err());
+
+        let stats = download_stats(&use_case);
+        assert_eq!(stats["ghost_objects"]["detected"], 1);
+        assert!(stats.contains_key("read_verification"));
     }
 }
@@ -1 +1,7 @@
+mod archive_download_tests;
+mod capabilities_tests;
+mod content_encoding_tests;
+mod head_tests;
 mod health_tests;
+mod storage_class_headers_tests;
+mod webhook_tests;
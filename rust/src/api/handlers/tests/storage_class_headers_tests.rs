@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::handlers::{download_handler, update_metadata_handler};
+    use crate::api::middleware::storage_class_headers::{
+        storage_class_headers_middleware, StorageClassHeadersConfig, STORAGE_CLASS_HEADER,
+        TIER_LATENCY_HINT_HEADER,
+    };
+    use crate::application::ports::{MockBlobStore, MockObjectRepository};
+    use crate::application::use_cases::{DownloadObjectUseCase, UpdateObjectMetadataUseCase};
+    use crate::domain::authorization::UserContext;
+    use crate::domain::entities::Object;
+    use crate::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
+    use axum::{
+        body::Body,
+        http::{header, Method, Request, StatusCode},
+        middleware,
+        routing::{get, patch},
+        Extension, Router,
+    };
+    use std::collections::HashSet;
+    use std::io::Cursor;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn committed_object(tenant_id: &TenantId, storage_class: StorageClass) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("test").unwrap(),
+            tenant_id.clone(),
+            Some("archive.bin".to_string()),
+            storage_class,
+        );
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 4)
+            .unwrap();
+        object
+    }
+
+    fn app(object: Object) -> Router {
+        let mut object_repo = MockObjectRepository::new();
+        let found = object.clone();
+        object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(found.clone())));
+        object_repo
+            .expect_update_metadata_if_match()
+            .returning(|_, _| Ok(true));
+        let object_repo = Arc::new(object_repo);
+
+        let mut blob_store = MockBlobStore::new();
+        blob_store.expect_exists().returning(|_, _| Ok(true));
+        blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"data".to_vec()))));
+
+        let download = Arc::new(DownloadObjectUseCase::new(
+            object_repo.clone(),
+            Arc::new(blob_store),
+        ));
+        let update_metadata = Arc::new(UpdateObjectMetadataUseCase::new(object_repo));
+
+        let user = UserContext::new(
+            "test-user".to_string(),
+            object.tenant_id().to_string(),
+            vec!["user".to_string()],
+            HashSet::new(),
+            false,
+            None,
+        );
+        let config = Arc::new(StorageClassHeadersConfig::new().with_latency_hint(true));
+
+        Router::new()
+            .route(
+                "/v1/objects/{id}",
+                get(download_handler).with_state(download),
+            )
+            .route(
+                "/v1/objects/{id}/metadata",
+                patch(update_metadata_handler).with_state(update_metadata),
+            )
+            .layer(Extension(user))
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                async move { storage_class_headers_middleware(&config, req, next).await }
+            }))
+    }
+
+    async fn download(object: Object) -> axum::response::Response {
+        let uri = format!(
+            "/v1/objects/{}?tenant_id={}",
+            object.id(),
+            object.tenant_id()
+        );
+        app(object)
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn patch_metadata(object: Object) -> axum::response::Response {
+        let uri = format!(
+            "/v1/objects/{}/metadata?tenant_id={}",
+            object.id(),
+            object.tenant_id()
+        );
+        app(object)
+            .oneshot(
+                Request::builder()
+                    .method(Method::PATCH)
+                    .uri(uri)
+                    .header(header::CONTENT_TYPE, "application/merge-patch+json")
+                    .body(Body::from(r#"{"description":"archived"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_download_reports_storage_class() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+
+        let hot = download(committed_object(&tenant_id, StorageClass::Hot)).await;
+        let cold = download(committed_object(&tenant_id, StorageClass::Cold)).await;
+
+        assert_eq!(hot.status(), StatusCode::OK);
+        assert_eq!(hot.headers()[STORAGE_CLASS_HEADER], "hot");
+        assert_eq!(hot.headers()[TIER_LATENCY_HINT_HEADER], "low");
+        assert_eq!(cold.status(), StatusCode::OK);
+        assert_eq!(cold.headers()[STORAGE_CLASS_HEADER], "cold");
+        assert_eq!(cold.headers()[TIER_LATENCY_HINT_HEADER], "high");
+    }
+
+    #[tokio::test]
+    async fn test_metadata_update_reports_storage_class() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+
+        let hot = patch_metadata(committed_object(&tenant_id, StorageClass::Hot)).await;
+        let cold = patch_metadata(committed_object(&tenant_id, StorageClass::Cold)).await;
+
+        assert_eq!(hot.status(), StatusCode::OK);
+        assert_eq!(hot.headers()[STORAGE_CLASS_HEADER], "hot");
+        assert_eq!(cold.status(), StatusCode::OK);
+        assert_eq!(cold.headers()[STORAGE_CLASS_HEADER], "cold");
+        assert_eq!(cold.headers()[TIER_LATENCY_HINT_HEADER], "high");
+    }
+}
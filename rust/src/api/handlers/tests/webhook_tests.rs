@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::handlers::inbound_webhook_handler;
+    use crate::api::handlers::webhooks::{
+        WebhookState, WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+    };
+    use crate::application::ports::{MockBlobRepository, MockBlobStore, MockObjectRepository};
+    use crate::application::use_cases::UploadObjectUseCase;
+    use crate::application::webhooks::{sign, WebhookVerifier};
+    use crate::domain::entities::Object;
+    use crate::domain::value_objects::{Namespace, StorageClass, TenantId};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use serde_json::json;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const SECRET: &str = "s3cret";
+
+    /// A resumable upload with 3 of its bytes staged
+    fn app(upload: &Object) -> Router {
+        let mut object_repo = MockObjectRepository::new();
+        let writing = upload.clone();
+        object_repo
+            .expect_find_writing()
+            .returning(move |_| Ok(Some(writing.clone())));
+        let mut blob_store = MockBlobStore::new();
+        blob_store.expect_staged_len().returning(|_, _| Ok(Some(3)));
+
+        let state = WebhookState {
+            verifier: Arc::new(
+                WebhookVerifier::parse(&format!("{}={SECRET}", upload.tenant_id()), 300).unwrap(),
+            ),
+            upload_use_case: Arc::new(UploadObjectUseCase::new(
+                Arc::new(object_repo),
+                Arc::new(MockBlobRepository::new()),
+                Arc::new(blob_store),
+            )),
+        };
+        Router::new().route(
+            "/v1/webhooks/{tenant_id}",
+            post(inbound_webhook_handler).with_state(state),
+        )
+    }
+
+    fn upload() -> Object {
+        Object::new(
+            Namespace::from_str("test").unwrap(),
+            TenantId::new(Uuid::new_v4()),
+            Some("video.mp4".to_string()),
+            StorageClass::Hot,
+        )
+    }
+
+    fn completed(upload: &Object, size_bytes: u64) -> Vec<u8> {
+        serde_json::to_vec(&json!({
+            "event": "upload.completed",
+            "upload_id": upload.id().to_string(),
+            "size_bytes": size_bytes,
+        }))
+        .unwrap()
+    }
+
+    async fn send(upload: &Object, timestamp: i64, signature: &str, body: Vec<u8>) -> StatusCode {
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/v1/webhooks/{}", upload.tenant_id()))
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(WEBHOOK_SIGNATURE_HEADER, signature)
+            .body(Body::from(body))
+            .unwrap();
+        app(upload).oneshot(request).await.unwrap().status()
+    }
+
+    fn now() -> i64 {
+        time::OffsetDateTime::now_utc().unix_timestamp()
+    }
+
+    #[tokio::test]
+    async fn test_invalid_signature_rejected() {
+        let upload = upload();
+        let body = completed(&upload, 3);
+        let signature = sign(b"not the secret", now(), &body);
+
+        assert_eq!(
+            send(&upload, now(), &signature, body).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replayed_timestamp_rejected() {
+        let upload = upload();
+        let body = completed(&upload, 3);
+        let sent = now() - 3600;
+        let signature = sign(SECRET.as_bytes(), sent, &body);
+
+        assert_eq!(
+            send(&upload, sent, &signature, body).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verified_callback_for_incomplete_upload_conflicts() {
+        let upload = upload();
+        let body = completed(&upload, 10);
+        let sent = now();
+        let signature = sign(SECRET.as_bytes(), sent, &body);
+
+        assert_eq!(
+            send(&upload, sent, &signature, body).await,
+            StatusCode::CONFLICT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verified_unknown_event_rejected() {
+        let upload = upload();
+        let body = br#"{"event":"object.deleted"}"#.to_vec();
+        let sent = now();
+        let signature = sign(SECRET.as_bytes(), sent, &body);
+
+        assert_eq!(
+            send(&upload, sent, &signature, body).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -8,7 +8,7 @@ use crate::application::use_cases::TextSearchObjectsUseCase;
 use crate::domain::authorization::UserContext;
 
 /// POST /v1/objects/search/text
-/// Full-text search across metadata and keys
+/// Full-text search across metadata and keys, ranked by relevance
 #[utoipa::path(
     post,
     path = "/v1/objects/search/text",
@@ -29,13 +29,7 @@ pub async fn text_search_handler(
     Json(request): Json<TextSearchRequest>,
 ) -> Result<Json<TextSearchResponse>, ApiError> {
     // Validate the request
-    if let Err((status, error_response)) = validate_and_respond(&request) {
-        return Err(ApiError::new(
-            status,
-            serde_json::to_string(&error_response)
-                .unwrap_or_else(|_| "Validation error".to_string()),
-        ));
-    }
+    validate_and_respond(&request)?;
 
     // Validate tenant ownership - users can only search objects from their own tenant
     // Admins can search objects from any tenant
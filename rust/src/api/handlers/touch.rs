@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::api::errors::ApiError;
+use crate::application::dto::TouchObjectResponse;
+use crate::application::use_cases::TouchObjectUseCase;
+use crate::domain::authorization::UserContext;
+use crate::domain::value_objects::{ObjectId, TenantId};
+
+#[derive(Deserialize, ToSchema)]
+pub struct TouchQuery {
+    /// Tenant identifier for authorization
+    tenant_id: String,
+    /// Move the object back to hot storage if it is cold
+    #[serde(default)]
+    promote: bool,
+}
+
+/// POST /v1/objects/{id}/touch
+/// Mark an object as accessed, optionally moving it back to hot storage
+///
+/// Advances `last_accessed_at` without counting a download, which restarts
+/// the idle time before the object moves to cold storage. With
+/// `promote=true` a cold object is copied back to hot storage; objects with
+/// the same content share the blob and move with it.
+///
+/// Promotion reads and rewrites the whole blob. On remote backends that is a
+/// full download from the cold tier (including any retrieval and egress
+/// charges) plus a full upload to the hot tier, and it may fail while a cold
+/// blob is still being restored. Requires the `objects:write` permission.
+#[utoipa::path(
+    post,
+    path = "/v1/objects/{id}/touch",
+    tag = "objects",
+    params(
+        ("id" = String, Path, description = "Object UUID"),
+        ("tenant_id" = String, Query, description = "Tenant identifier for authorization"),
+        ("promote" = Option<bool>, Query, description = "Move the object back to hot storage if it is cold")
+    ),
+    responses(
+        (status = 200, description = "Object touched; its storage class afterwards", body = TouchObjectResponse),
+        (status = 400, description = "Invalid object ID or tenant"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Access forbidden"),
+        (status = 404, description = "Object not found"),
+        (status = 409, description = "Cold blob is being restored; retry later"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn touch_handler(
+    State(use_case): State<Arc<TouchObjectUseCase>>,
+    Extension(user_context): Extension<UserContext>,
+    Path(id): Path<String>,
+    Query(query): Query<TouchQuery>,
+) -> Result<Json<TouchObjectResponse>, ApiError> {
+    // Same tenant ownership rules as modifying the object
+    if !user_context.is_admin() && query.tenant_id != user_context.tenant_id {
+        return Err(ApiError::new(
+            axum::http::StatusCode::FORBIDDEN,
+            "Cannot modify objects of other tenants".to_string(),
+        ));
+    }
+
+    let object_id = id
+        .parse::<ObjectId>()
+        .map_err(|e| ApiError::bad_request(format!("Invalid object ID: {}", e)))?;
+    let tenant_id = TenantId::from_string(&query.tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+
+    let response = use_case
+        .execute(&object_id, &tenant_id, query.promote)
+        .await?;
+
+    Ok(Json(response))
+}
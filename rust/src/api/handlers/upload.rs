@@ -1,21 +1,142 @@
 use axum::body::Body;
-use axum::http::StatusCode;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
 use futures_util::TryStreamExt;
 use std::io;
+use std::str::FromStr;
 use std::sync::Arc;
 use tokio_util::io::StreamReader;
 
 use crate::api::errors::ApiError;
-use crate::application::dto::{ObjectDto, UploadRequest};
+use crate::application::dto::{ObjectDto, UploadPrecondition, UploadRequest};
 use crate::application::use_cases::UploadObjectUseCase;
 use crate::domain::authorization::UserContext;
-use crate::domain::value_objects::StorageClass;
+use crate::domain::value_objects::{ContentEncoding, ContentHash, ContentHashPrefix, StorageClass};
 
 use axum::extract::{Query, State};
 use axum::response::Json;
 
+/// Request header carrying the SHA-256 the uploaded content must have
+const CONTENT_HASH_HEADER: &str = "x-content-hash";
+
+/// Request header making retries of an upload return the first result
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Strong ETag for an object's content: the quoted content hash
+pub(super) fn content_etag(content_hash: &str) -> Option<HeaderValue> {
+    HeaderValue::from_str(&format!("\"{content_hash}\"")).ok()
+}
+
+/// Read `If-Match` / `If-None-Match` into an upload precondition
+///
+/// `If-Match` takes a single strong ETag (or `*`); `If-None-Match` only
+/// supports `*` (create if absent).
+fn parse_upload_precondition(headers: &HeaderMap) -> Result<UploadPrecondition, ApiError> {
+    let header_str = |name: header::HeaderName| {
+        headers
+            .get(&name)
+            .map(|v| {
+                v.to_str()
+                    .map(str::trim)
+                    .map_err(|_| ApiError::bad_request(format!("Invalid {name} header")))
+            })
+            .transpose()
+    };
+
+    match (
+        header_str(header::IF_MATCH)?,
+        header_str(header::IF_NONE_MATCH)?,
+    ) {
+        (None, None) => Ok(UploadPrecondition::None),
+        (Some(_), Some(_)) => Err(ApiError::bad_request(
+            "If-Match and If-None-Match cannot be combined",
+        )),
+        (None, Some("*")) => Ok(UploadPrecondition::IfNoneMatch),
+        (None, Some(_)) => Err(ApiError::bad_request(
+            "If-None-Match only supports '*' for uploads",
+        )),
+        (Some("*"), None) => Ok(UploadPrecondition::IfMatch(None)),
+        (Some(etag), None) => {
+            let hash = etag
+                .strip_prefix('"')
+                .and_then(|e| e.strip_suffix('"'))
+                .unwrap_or(etag);
+            ContentHash::from_str(hash)
+                .map(|hash| UploadPrecondition::IfMatch(Some(hash)))
+                .map_err(|_| ApiError::bad_request("If-Match must be a single strong ETag or '*'"))
+        }
+    }
+}
+
+/// Read the optional `X-Content-Hash` header the content must hash to
+///
+/// A short hash is taken as is; see [`expected_hash`].
+fn parse_expected_hash(headers: &HeaderMap) -> Result<Option<ContentHashPrefix>, ApiError> {
+    headers
+        .get(CONTENT_HASH_HEADER)
+        .map(|v| {
+            v.to_str()
+                .ok()
+                .and_then(|hash| ContentHashPrefix::from_str(hash.trim()).ok())
+                .ok_or_else(|| {
+                    ApiError::bad_request("X-Content-Hash must be a hex SHA-256 digest or prefix")
+                })
+        })
+        .transpose()
+}
+
+/// Read the optional `X-Content-Hash` header, resolving a short hash to the
+/// stored content it names
+pub(super) async fn expected_hash(
+    headers: &HeaderMap,
+    use_case: &UploadObjectUseCase,
+) -> Result<Option<ContentHash>, ApiError> {
+    match parse_expected_hash(headers)? {
+        Some(reference) => Ok(Some(use_case.resolve_hash(&reference).await?)),
+        None => Ok(None),
+    }
+}
+
+/// Read the optional `Content-Encoding` the content was compressed with
+///
+/// The content is stored as sent and decoded on download for clients that
+/// don't accept the encoding.
+pub(super) fn parse_content_encoding(
+    headers: &HeaderMap,
+) -> Result<Option<ContentEncoding>, ApiError> {
+    let Some(value) = headers.get(header::CONTENT_ENCODING) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| ApiError::bad_request("Invalid Content-Encoding header"))?;
+    ContentEncoding::from_header(value).map_err(ApiError::bad_request)
+}
+
+/// Read the optional `Idempotency-Key` header
+fn parse_idempotency_key(headers: &HeaderMap) -> Result<Option<String>, ApiError> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|v| {
+            v.to_str()
+                .map(|key| key.trim().to_string())
+                .map_err(|_| ApiError::bad_request("Invalid Idempotency-Key header"))
+        })
+        .transpose()
+}
+
 /// POST /v1/objects
 /// Upload object with streaming body
+///
+/// Send `If-Match: "<etag>"` to overwrite the object under `key` only while
+/// it is unchanged, or `If-None-Match: *` to create it only if absent.
+/// Send `X-Content-Hash` to have the content verified while it streams. If
+/// the tenant already stores content with that hash, the object is created
+/// from it without reading the body; send `Expect: 100-continue` to avoid
+/// transmitting it at all.
+/// Send `Idempotency-Key` to make retries return the object the first
+/// attempt created.
+/// Send `Content-Encoding: gzip` or `zstd` for compressed content: it is
+/// stored as sent, and the content hash covers the compressed bytes.
 #[utoipa::path(
     post,
     path = "/v1/objects",
@@ -24,22 +145,33 @@ use axum::response::Json;
         ("namespace" = String, Query, description = "Object namespace"),
         ("tenant_id" = String, Query, description = "Tenant identifier"),
         ("key" = Option<String>, Query, description = "Human-readable key for retrieval"),
-        ("storage_class" = Option<String>, Query, description = "Storage class ('hot' or 'cold')")
+        ("storage_class" = Option<String>, Query, description = "Storage class ('hot' or 'cold')"),
+        ("If-Match" = Option<String>, Header, description = "Overwrite only if the current ETag matches (or '*' for any existing object)"),
+        ("If-None-Match" = Option<String>, Header, description = "'*' to create only if no object exists for the key"),
+        ("X-Content-Hash" = Option<String>, Header, description = "Expected SHA-256 of the content (hex), or a unique prefix of stored content when short hashes are on; the upload is rejected if it differs, and skips the body if the tenant already stores that content"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key and content return the original object"),
+        ("Content-Encoding" = Option<String>, Header, description = "'gzip' or 'zstd' if the content is compressed; stored as sent and decoded on download for clients that don't accept it")
     ),
     request_body = Vec<u8>,
     responses(
         (status = 201, description = "Object uploaded successfully", body = ObjectDto),
-        (status = 400, description = "Invalid request parameters"),
+        (status = 400, description = "Invalid request parameters, unsupported Content-Encoding or content hash mismatch"),
         (status = 401, description = "Authentication required"),
-        (status = 500, description = "Internal server error")
+        (status = 409, description = "Idempotency key reused with a different payload, or its first upload is still in progress"),
+        (status = 412, description = "If-Match / If-None-Match precondition failed"),
+        (status = 413, description = "Content exceeds the maximum upload size"),
+        (status = 422, description = "Content rejected by the upload content scanner"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "Content scanner unavailable")
     )
 )]
 pub async fn upload_handler(
     State(use_case): State<Arc<UploadObjectUseCase>>,
     axum::extract::Extension(user_context): axum::extract::Extension<UserContext>,
     query_params: Query<std::collections::HashMap<String, String>>,
+    headers: HeaderMap,
     body: Body,
-) -> Result<(StatusCode, Json<ObjectDto>), ApiError> {
+) -> Result<(StatusCode, HeaderMap, Json<ObjectDto>), ApiError> {
     let namespace = query_params.get("namespace").cloned().unwrap_or_default();
     let tenant_id = query_params.get("tenant_id").cloned().unwrap_or_default();
     let key = query_params.get("key").cloned();
@@ -61,6 +193,15 @@ pub async fn upload_handler(
         return Err(ApiError::bad_request("Invalid tenant_id format"));
     }
 
+    let precondition = parse_upload_precondition(&headers)?;
+    let expected_hash = expected_hash(&headers, &use_case).await?;
+    let idempotency_key = parse_idempotency_key(&headers)?;
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let content_encoding = parse_content_encoding(&headers)?;
+
     // Validate tenant ownership - users can only upload to their own tenant
     // Admins can upload to any tenant
     if !user_context.is_admin() && tenant_id != user_context.tenant_id {
@@ -82,10 +223,113 @@ pub async fn upload_handler(
         tenant_id,
         key,
         storage_class,
+        content_type,
+        content_encoding,
+        expected_hash,
+        idempotency_key,
     };
 
     // Execute use case, passing the async reader directly
-    let object = use_case.execute(request, reader).await?;
+    let object = use_case
+        .execute_with_precondition(request, reader, precondition)
+        .await?;
+
+    let mut response_headers = HeaderMap::new();
+    if let Some(etag) = object.content_hash.as_deref().and_then(content_etag) {
+        response_headers.insert(header::ETAG, etag);
+    }
+
+    Ok((StatusCode::CREATED, response_headers, Json(object)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(header::HeaderName, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(name.clone(), HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_no_precondition() {
+        assert_eq!(
+            parse_upload_precondition(&HeaderMap::new()).ok(),
+            Some(UploadPrecondition::None)
+        );
+    }
 
-    Ok((StatusCode::CREATED, Json(object)))
+    #[test]
+    fn test_parse_if_match_etag() {
+        let etag = format!("\"{}\"", "a".repeat(64));
+        let mut map = HeaderMap::new();
+        map.insert(header::IF_MATCH, HeaderValue::from_str(&etag).unwrap());
+
+        assert_eq!(
+            parse_upload_precondition(&map).ok(),
+            Some(UploadPrecondition::IfMatch(Some(
+                ContentHash::from_str(&"a".repeat(64)).unwrap()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_wildcards() {
+        assert_eq!(
+            parse_upload_precondition(&headers(&[(header::IF_MATCH, "*")])).ok(),
+            Some(UploadPrecondition::IfMatch(None))
+        );
+        assert_eq!(
+            parse_upload_precondition(&headers(&[(header::IF_NONE_MATCH, "*")])).ok(),
+            Some(UploadPrecondition::IfNoneMatch)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported_forms() {
+        assert!(parse_upload_precondition(&headers(&[(header::IF_MATCH, "W/\"abc\"")])).is_err());
+        assert!(
+            parse_upload_precondition(&headers(&[(header::IF_NONE_MATCH, "\"abc\"")])).is_err()
+        );
+        assert!(parse_upload_precondition(&headers(&[
+            (header::IF_MATCH, "*"),
+            (header::IF_NONE_MATCH, "*"),
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_expected_hash() {
+        let hash = "A".repeat(64);
+        let mut map = HeaderMap::new();
+        map.insert(CONTENT_HASH_HEADER, HeaderValue::from_str(&hash).unwrap());
+
+        assert_eq!(
+            parse_expected_hash(&map).ok(),
+            Some(Some(ContentHashPrefix::from_str(&hash).unwrap()))
+        );
+        map.insert(CONTENT_HASH_HEADER, HeaderValue::from_static("abcd12"));
+        assert!(!parse_expected_hash(&map).unwrap().unwrap().is_full());
+        assert_eq!(parse_expected_hash(&HeaderMap::new()).ok(), Some(None));
+        assert!(parse_expected_hash(&headers(&[(
+            header::HeaderName::from_static(CONTENT_HASH_HEADER),
+            "not-a-hash"
+        )]))
+        .is_err());
+    }
+    #[test]
+    fn test_parse_idempotency_key() {
+        assert_eq!(
+            parse_idempotency_key(&headers(&[(
+                header::HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
+                " retry-1 "
+            )]))
+            .ok(),
+            Some(Some("retry-1".to_string()))
+        );
+        assert_eq!(parse_idempotency_key(&HeaderMap::new()).ok(), Some(None));
+    }
 }
@@ -0,0 +1,137 @@
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::upload::content_etag;
+use crate::api::errors::ApiError;
+use crate::application::dto::{ObjectDto, ResumeOutcome, UploadChunk};
+use crate::application::use_cases::UploadObjectUseCase;
+use crate::application::webhooks::{WebhookError, WebhookVerifier};
+use crate::domain::value_objects::{ContentHash, ObjectId, TenantId};
+
+/// Unix seconds at which the webhook was sent
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+
+/// `sha256=<hex>` HMAC of `<timestamp>.<body>` with the tenant's secret
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Dependencies of the inbound webhook handler
+#[derive(Clone)]
+pub struct WebhookState {
+    pub verifier: Arc<WebhookVerifier>,
+    pub upload_use_case: Arc<UploadObjectUseCase>,
+}
+
+/// Event reported by an external system
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "event")]
+pub enum WebhookEvent {
+    /// Every byte of a resumable upload has been sent; commit it
+    #[serde(rename = "upload.completed")]
+    UploadCompleted {
+        /// Upload ID returned when the upload was started
+        upload_id: String,
+        /// Size of the whole content
+        size_bytes: u64,
+        /// Expected SHA-256 of the whole content (hex), checked before the commit
+        content_hash: Option<String>,
+    },
+}
+
+/// POST /v1/webhooks/{tenant_id}
+/// Receive a signed callback from an external system
+///
+/// Not authenticated with an API key: the request must carry an HMAC-SHA256
+/// signature made with the tenant's shared secret, and a timestamp within
+/// the configured tolerance. `upload.completed` commits a resumable upload
+/// of the tenant whose bytes have all been sent.
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks/{tenant_id}",
+    tag = "webhooks",
+    params(
+        ("tenant_id" = String, Path, description = "Tenant whose shared secret signed the request"),
+        ("X-Webhook-Timestamp" = String, Header, description = "Unix seconds at which the request was sent"),
+        ("X-Webhook-Signature" = String, Header, description = "'sha256=' followed by the hex HMAC-SHA256 of '<timestamp>.<body>'")
+    ),
+    request_body = WebhookEvent,
+    responses(
+        (status = 201, description = "Upload committed", body = ObjectDto),
+        (status = 400, description = "Invalid event, or timestamp outside the tolerance"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 404, description = "Upload not found, or already committed"),
+        (status = 409, description = "Not every byte of the upload has arrived"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn inbound_webhook_handler(
+    State(state): State<WebhookState>,
+    Path(tenant_id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let tenant_id = TenantId::from_string(&tenant_id)
+        .map_err(|e| ApiError::bad_request(format!("Invalid tenant_id: {}", e)))?;
+    let signed_header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(WebhookError::InvalidSignature)
+    };
+    state.verifier.verify(
+        &tenant_id,
+        signed_header(WEBHOOK_TIMESTAMP_HEADER)?,
+        signed_header(WEBHOOK_SIGNATURE_HEADER)?,
+        &body,
+        time::OffsetDateTime::now_utc().unix_timestamp(),
+    )?;
+
+    // Only parsed once the sender is known
+    let event: WebhookEvent = serde_json::from_slice(&body)
+        .map_err(|e| ApiError::bad_request(format!("Invalid webhook event: {}", e)))?;
+    match event {
+        WebhookEvent::UploadCompleted {
+            upload_id,
+            size_bytes,
+            content_hash,
+        } => {
+            let upload_id = upload_id
+                .parse::<ObjectId>()
+                .map_err(|e| ApiError::bad_request(format!("Invalid upload ID: {}", e)))?;
+            let expected_hash = content_hash
+                .as_deref()
+                .map(ContentHash::from_str)
+                .transpose()
+                .map_err(|e| ApiError::bad_request(format!("Invalid content_hash: {}", e)))?;
+            let chunk = UploadChunk {
+                range: None,
+                total_size: Some(size_bytes),
+                expected_hash,
+            };
+
+            // Commits exactly like `bytes */<total>` on the upload itself
+            match state
+                .upload_use_case
+                .resume(&upload_id, &tenant_id, chunk, Box::pin(tokio::io::empty()))
+                .await?
+            {
+                ResumeOutcome::Incomplete(upload) => Err(ApiError::conflict(format!(
+                    "Upload has {} of {} bytes",
+                    upload.offset, size_bytes
+                ))),
+                ResumeOutcome::Committed(object) => {
+                    let mut response_headers = HeaderMap::new();
+                    if let Some(etag) = object.content_hash.as_deref().and_then(content_etag) {
+                        response_headers.insert(header::ETAG, etag);
+                    }
+                    Ok((StatusCode::CREATED, response_headers, Json(object)).into_response())
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,18 @@
 use crate::api::middleware::audit::{AuditEventType, AuditLogEntry};
 use crate::api::router::AppState;
-use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::AuditQueryFilter;
+use crate::domain::value_objects::ObjectId;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
 use serde_json::json;
+use std::collections::BTreeSet;
+use std::str::FromStr;
 use time::OffsetDateTime;
 
 pub async fn clear_cache(State(state): State<AppState>) -> impl IntoResponse {
@@ -39,10 +50,7 @@ pub async fn reindex(State(state): State<AppState>) -> impl IntoResponse {
 
     let result_msg = if let Some(gc) = &state.gc {
         match gc.collect_once().await {
-            Ok(result) => format!(
-                "GC run successful: {} objects deleted",
-                result.total_deleted
-            ),
+            Ok(result) => result.summary(),
             Err(e) => format!("GC run failed: {}", e),
         }
     } else {
@@ -77,3 +85,262 @@ pub async fn reindex(State(state): State<AppState>) -> impl IntoResponse {
 
     (StatusCode::OK, result_msg)
 }
+
+/// Maximum number of ghost detection events inspected per reconcile run
+const GHOST_RECONCILE_LIMIT: i64 = 1000;
+
+pub async fn reconcile_ghosts(State(state): State<AppState>) -> impl IntoResponse {
+    // 1. Collect objects previously reported as ghosts
+    tracing::info!("Internal action: Ghost object reconcile triggered");
+
+    let filter = AuditQueryFilter {
+        event_types: Some(vec![AuditEventType::GhostObjectDetected.to_string()]),
+        ..Default::default()
+    };
+
+    let candidates: BTreeSet<String> = match state
+        .audit_repo
+        .query(filter, GHOST_RECONCILE_LIMIT, 0)
+        .await
+    {
+        Ok(entries) => entries
+            .into_iter()
+            .filter_map(|entry| {
+                entry
+                    .additional_data
+                    .and_then(|data| data.get("object_id")?.as_str().map(str::to_string))
+            })
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to query ghost object events: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to query ghost object events".to_string(),
+            );
+        }
+    };
+
+    // 2. Re-check each candidate; blobs may have been restored since detection
+    let mut confirmed = Vec::new();
+    let mut resolved = 0usize;
+    for raw_id in &candidates {
+        let Ok(object_id) = ObjectId::from_str(raw_id) else {
+            continue;
+        };
+        match state.download_use_case.is_ghost(&object_id).await {
+            Ok(true) => confirmed.push(raw_id.clone()),
+            Ok(false) => resolved += 1,
+            Err(e) => tracing::warn!(object_id = %raw_id, "Ghost re-check failed: {}", e),
+        }
+    }
+
+    let result_msg = format!(
+        "Ghost reconcile: {} candidates, {} still missing blobs, {} resolved",
+        candidates.len(),
+        confirmed.len(),
+        resolved
+    );
+
+    // 3. Log to AuditRepository, flagging the confirmed ghosts
+    let log_entry = AuditLogEntry {
+        timestamp: OffsetDateTime::now_utc(),
+        event_type: AuditEventType::ConfigurationChange,
+        user_id: Some("internal-admin".to_string()),
+        tenant_id: None,
+        api_key_id: None,
+        ip_address: None,
+        user_agent: None,
+        method: "POST".to_string(),
+        path: "/internal/actions/ghosts/reconcile".to_string(),
+        query: None,
+        status_code: Some(StatusCode::OK.as_u16()),
+        response_time_ms: Some(0),
+        error_message: None,
+        additional_data: Some(json!({
+            "action": "reconcile_ghosts",
+            "result": result_msg,
+            "ghost_object_ids": confirmed,
+        })),
+    };
+
+    if let Err(e) = state.audit_repo.store(log_entry).await {
+        tracing::error!("Failed to store audit log for reconcile_ghosts: {}", e);
+    }
+
+    (StatusCode::OK, result_msg)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReconcileRefcountsParams {
+    /// Report discrepancies without fixing them (default)
+    pub dry_run: Option<bool>,
+}
+
+pub async fn reconcile_refcounts(
+    State(state): State<AppState>,
+    Query(params): Query<ReconcileRefcountsParams>,
+) -> impl IntoResponse {
+    // 1. Run (or resume) reconciliation; dry run unless explicitly disabled
+    let dry_run = params.dry_run.unwrap_or(true);
+    tracing::info!(dry_run, "Internal action: Refcount reconcile triggered");
+
+    let result = state.reconcile_refcounts_use_case.execute(dry_run).await;
+
+    let (status, body, error_message) = match &result {
+        Ok(report) => (StatusCode::OK, json!(report), None),
+        Err(e) => {
+            tracing::error!("Refcount reconcile failed: {}", e);
+            let status = match e {
+                ObjectUseCaseError::InvalidRequest(_) => StatusCode::CONFLICT,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                json!({ "error": e.to_string() }),
+                Some(e.to_string()),
+            )
+        }
+    };
+
+    // 2. Log to AuditRepository
+    let log_entry = AuditLogEntry {
+        timestamp: OffsetDateTime::now_utc(),
+        event_type: AuditEventType::ConfigurationChange,
+        user_id: Some("internal-admin".to_string()),
+        tenant_id: None,
+        api_key_id: None,
+        ip_address: None,
+        user_agent: None,
+        method: "POST".to_string(),
+        path: "/internal/actions/refcounts/reconcile".to_string(),
+        query: None,
+        status_code: Some(status.as_u16()),
+        response_time_ms: Some(0),
+        error_message,
+        additional_data: Some(json!({
+            "action": "reconcile_refcounts",
+            "dry_run": dry_run,
+            "result": result.as_ref().ok().map(|report| json!({
+                "completed": report.completed,
+                "scanned": report.scanned,
+                "discrepancies": report.discrepancies,
+                "fixed": report.fixed,
+                "corrected_blobs": report
+                    .sample
+                    .iter()
+                    .filter(|d| d.fixed)
+                    .collect::<Vec<_>>(),
+            })),
+        })),
+    };
+
+    if let Err(e) = state.audit_repo.store(log_entry).await {
+        tracing::error!("Failed to store audit log for reconcile_refcounts: {}", e);
+    }
+
+    (status, Json(body))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompactBlobsParams {
+    /// Report unreferenced files without removing them (default)
+    pub dry_run: Option<bool>,
+}
+
+pub async fn compact_blobs(
+    State(state): State<AppState>,
+    Query(params): Query<CompactBlobsParams>,
+) -> impl IntoResponse {
+    // 1. Scan storage for files the database does not know; dry run unless explicitly disabled
+    let dry_run = params.dry_run.unwrap_or(true);
+    tracing::info!(dry_run, "Internal action: Blob compaction triggered");
+
+    let result = state.compaction_use_case.execute(dry_run).await;
+
+    let (status, body, error_message) = match &result {
+        Ok(report) => (StatusCode::OK, json!(report), None),
+        Err(e) => {
+            tracing::error!("Blob compaction failed: {}", e);
+            let status = match e {
+                ObjectUseCaseError::InvalidRequest(_) => StatusCode::CONFLICT,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                json!({ "error": e.to_string() }),
+                Some(e.to_string()),
+            )
+        }
+    };
+
+    // 2. Log to AuditRepository
+    let log_entry = AuditLogEntry {
+        timestamp: OffsetDateTime::now_utc(),
+        event_type: AuditEventType::ConfigurationChange,
+        user_id: Some("internal-admin".to_string()),
+        tenant_id: None,
+        api_key_id: None,
+        ip_address: None,
+        user_agent: None,
+        method: "POST".to_string(),
+        path: "/internal/actions/blobs/compact".to_string(),
+        query: None,
+        status_code: Some(status.as_u16()),
+        response_time_ms: Some(0),
+        error_message,
+        additional_data: Some(json!({
+            "action": "compact_blobs",
+            "dry_run": dry_run,
+            "result": result.as_ref().ok().map(|report| json!({
+                "scanned": report.scanned,
+                "orphaned_blobs": report.orphaned_blobs,
+                "stale_temp_files": report.stale_temp_files,
+                "removed": report.removed,
+                "bytes_reclaimed": report.bytes_reclaimed,
+                "errors": report.errors,
+            })),
+        })),
+    };
+
+    if let Err(e) = state.audit_repo.store(log_entry).await {
+        tracing::error!("Failed to store audit log for compact_blobs: {}", e);
+    }
+
+    (status, Json(body))
+}
+
+/// Maximum number of scrub findings listed at once
+const SCRUB_FINDINGS_LIMIT: i64 = 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct ScrubFindingsParams {
+    pub limit: Option<i64>,
+}
+
+/// Blobs the scrubber flagged, for an operator to restore or remove
+pub async fn scrub_findings(
+    State(state): State<AppState>,
+    Query(params): Query<ScrubFindingsParams>,
+) -> impl IntoResponse {
+    let Some(scrubber) = &state.scrubber else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Blob scrubber is not enabled (SCRUB_ENABLED)" })),
+        );
+    };
+
+    let limit = params.limit.unwrap_or(100).clamp(1, SCRUB_FINDINGS_LIMIT);
+    match scrubber.findings(limit).await {
+        Ok(findings) => (
+            StatusCode::OK,
+            Json(json!({ "stats": scrubber.stats(), "findings": findings })),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to list scrub findings: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": e.to_string() })),
+            )
+        }
+    }
+}
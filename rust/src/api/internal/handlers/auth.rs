@@ -14,6 +14,7 @@ use tracing::{error, info};
 
 use crate::api::router::AppState;
 use crate::domain::authorization::{roles, UserContext};
+use crate::infrastructure::telemetry;
 use std::collections::HashSet;
 
 #[derive(Deserialize)]
@@ -92,7 +93,9 @@ async fn oidc_http_client(
 
     let mut request_builder = client.request(method, url);
 
-    for (name, value) in request.headers() {
+    let mut headers = request.headers().clone();
+    telemetry::inject_trace_context(&mut headers);
+    for (name, value) in &headers {
         request_builder = request_builder.header(name.as_str(), value.as_bytes());
     }
 
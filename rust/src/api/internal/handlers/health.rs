@@ -18,6 +18,7 @@ pub async fn health_page(
         .await
         .is_ok();
     let db_latency = format!("{:?}", start.elapsed());
+    let pool_stats = state.pool_monitor.stats();
 
     // Get total objects count
     let total_objects: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM objects")
@@ -45,6 +46,44 @@ pub async fn health_page(
         .await
         .unwrap_or(0);
 
+    let blob_cache = match &state.blob_cache {
+        Some(cache) => {
+            let stats = cache.stats();
+            format!(
+                "{} hits, {} misses ({} in {} blobs)",
+                stats.hits,
+                stats.misses,
+                format_size(stats.size_bytes),
+                stats.entries
+            )
+        }
+        None => "Disabled".to_string(),
+    };
+
+    let blob_store_breakers = if state.blob_store_breakers.is_empty() {
+        "Disabled".to_string()
+    } else {
+        state
+            .blob_store_breakers
+            .iter()
+            .map(|(name, breaker)| {
+                let stats = breaker.stats();
+                format!(
+                    "{}: {} ({} retries, {} rejected)",
+                    name, stats.state, stats.retries, stats.rejected
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let compression = state.download_compression.stats();
+    let download_compression = format!(
+        "{} saved over {} downloads",
+        format_size(compression.bytes_saved),
+        compression.responses
+    );
+
     // GC Info
     let (gc_status, gc_last_run, gc_next_run, gc_total_deleted) = if let Some(gc) = &state.gc {
         let stats = gc.stats();
@@ -98,13 +137,20 @@ pub async fn health_page(
             "Disconnected".to_string()
         },
         db_latency,
-        db_pool_active: (state.pool.size() as usize - state.pool.num_idle()) as u32,
-        db_pool_idle: state.pool.num_idle() as u32,
-        db_pool_max: state.config.db_max_connections,
+        db_pool_active: pool_stats.in_use,
+        db_pool_idle: pool_stats.idle,
+        db_pool_max: pool_stats.max_connections,
+        db_pool_health: pool_stats.health.to_string(),
+        db_pool_wait_ms: pool_stats.acquire_wait_ms,
+        db_pool_reconnects: pool_stats.reconnects,
+        db_retries: state.db_retry_policy.retries(),
         hot_storage_usage: format_size(hot_usage),
         cold_storage_usage: format_size(cold_usage),
         hot_storage_path: state.config.hot_storage_root.to_string_lossy().to_string(),
         cold_storage_path: state.config.cold_storage_root.to_string_lossy().to_string(),
+        blob_cache,
+        blob_store_breakers,
+        download_compression,
         total_objects,
         gc_status,
         gc_last_run,
@@ -9,7 +9,9 @@ use tower_cookies::CookieManagerLayer;
 use tower_http::{services::ServeDir, set_header::SetResponseHeaderLayer};
 
 use crate::api::internal::auth::internal_admin_auth;
-use crate::api::internal::handlers::actions::{clear_cache, reindex};
+use crate::api::internal::handlers::actions::{
+    clear_cache, compact_blobs, reconcile_ghosts, reconcile_refcounts, reindex, scrub_findings,
+};
 use crate::api::internal::handlers::auth::{oidc_callback, oidc_login, oidc_logout};
 use crate::api::internal::handlers::health::health_page;
 use crate::api::internal::handlers::login::{login_handler, login_page};
@@ -94,6 +96,10 @@ pub async fn create_internal_router(state: AppState) -> Router {
         .route("/health", get(health_page))
         .route("/actions/cache/clear", post(clear_cache))
         .route("/actions/reindex", post(reindex))
+        .route("/actions/ghosts/reconcile", post(reconcile_ghosts))
+        .route("/actions/refcounts/reconcile", post(reconcile_refcounts))
+        .route("/actions/blobs/compact", post(compact_blobs))
+        .route("/scrub/findings", get(scrub_findings))
         .route("/login", get(login_page).post(login_handler))
         .route("/auth/login", get(oidc_login))
         .route("/auth/callback", get(oidc_callback))
@@ -16,10 +16,17 @@ pub struct HealthTemplate {
     pub db_pool_active: u32,
     pub db_pool_idle: u32,
     pub db_pool_max: u32,
+    pub db_pool_health: String,
+    pub db_pool_wait_ms: u64,
+    pub db_pool_reconnects: u64,
+    pub db_retries: u64,
     pub hot_storage_usage: String,
     pub cold_storage_usage: String,
     pub hot_storage_path: String,
     pub cold_storage_path: String,
+    pub blob_cache: String,
+    pub blob_store_breakers: String,
+    pub download_compression: String,
     pub total_objects: i64,
     pub gc_status: String,
     pub gc_last_run: String,
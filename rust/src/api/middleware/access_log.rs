@@ -0,0 +1,498 @@
+//! Structured access logs
+//!
+//! One record per request with its method, path, status, duration, bytes
+//! received and sent, tenant, request ID and client IP. Records are written
+//! as JSON lines to stdout for log shippers, or as `tracing` events under the
+//! `access_log` target.
+//!
+//! A record is written when the response body has been sent (or abandoned),
+//! so the duration and byte counts of a streamed download cover the whole
+//! transfer and are counted once, as the bytes leave the server. Health
+//! checks are noisy and can be sampled or skipped.
+//!
+//! The tenant is only known after authentication, which runs further in:
+//! `record_tenant` has to run inside the auth layer to pass it out.
+
+use std::io::Write;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::task::{ready, Context, Poll};
+use std::time::Instant;
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::Request,
+    middleware::Next,
+    response::Response,
+};
+use http_body::{Frame, SizeHint};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::audit_middleware::extract_ip_address;
+use super::request_id::RequestId;
+use crate::domain::authorization::UserContext;
+
+/// Format of access log records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLogFormat {
+    /// One JSON object per line on stdout
+    #[default]
+    Json,
+    /// `tracing` events, formatted like every other log line
+    Text,
+}
+
+impl FromStr for AccessLogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            other => Err(format!(
+                "unknown access log format '{other}' (expected json or text)"
+            )),
+        }
+    }
+}
+
+/// Access log configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessLogConfig {
+    pub enabled: bool,
+    pub format: AccessLogFormat,
+    /// Share of health check requests logged: 0 skips them, 1 logs them all
+    pub health_check_sample_rate: f64,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: AccessLogFormat::default(),
+            health_check_sample_rate: 0.0,
+        }
+    }
+}
+
+impl AccessLogConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable access logs
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the record format
+    pub fn with_format(mut self, format: AccessLogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the share of health check requests logged
+    pub fn with_health_check_sample_rate(mut self, rate: f64) -> Self {
+        self.health_check_sample_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Whether a request to `path` is logged
+    fn samples(&self, path: &str) -> bool {
+        if path != "/health" && !path.starts_with("/health/") {
+            return true;
+        }
+        match self.health_check_sample_rate {
+            rate if rate <= 0.0 => false,
+            rate if rate >= 1.0 => true,
+            rate => rand::rng().random::<f64>() < rate,
+        }
+    }
+}
+
+/// One request as written to the access log
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccessLogRecord {
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    pub request_id: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    /// From the request until the last response byte was sent
+    pub duration_ms: u64,
+    /// Request body bytes read by the server
+    pub bytes_in: u64,
+    /// Response body bytes sent
+    pub bytes_out: u64,
+    pub tenant_id: Option<String>,
+    pub client_ip: Option<String>,
+    /// False when the client went away or the body failed mid-stream
+    pub completed: bool,
+}
+
+/// Tenant of the request, filled in by `record_tenant` after authentication
+#[derive(Debug, Clone, Default)]
+pub struct AccessLogTenant(Arc<OnceLock<String>>);
+
+/// Log the request once its response has been sent
+///
+/// Runs inside the request ID layer, so the record carries the request's ID.
+pub async fn access_log_middleware(
+    config: &AccessLogConfig,
+    request: Request,
+    next: Next,
+) -> Response {
+    let format = config.format;
+    log_request(config, request, next, move |record| write(format, &record)).await
+}
+
+/// Pass the authenticated tenant out to the access log
+///
+/// Runs inside authentication; requests without a user context pass through.
+pub async fn record_tenant(request: Request, next: Next) -> Response {
+    let extensions = request.extensions();
+    if let (Some(slot), Some(user_context)) = (
+        extensions.get::<AccessLogTenant>(),
+        extensions.get::<UserContext>(),
+    ) {
+        let _ = slot.0.set(user_context.tenant_id.clone());
+    }
+    next.run(request).await
+}
+
+async fn log_request<F>(config: &AccessLogConfig, request: Request, next: Next, emit: F) -> Response
+where
+    F: FnOnce(AccessLogRecord) + Send + 'static,
+{
+    if !config.enabled || !config.samples(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let pending = PendingRecord {
+        started: Instant::now(),
+        timestamp: OffsetDateTime::now_utc(),
+        request_id: request
+            .extensions()
+            .get::<RequestId>()
+            .map(|id| id.to_string()),
+        method: request.method().to_string(),
+        path: request.uri().path().to_string(),
+        client_ip: extract_ip_address(request.headers()),
+        tenant: AccessLogTenant::default(),
+        bytes_in: Arc::new(AtomicU64::new(0)),
+        emit: Box::new(emit),
+    };
+
+    let (mut parts, body) = request.into_parts();
+    parts.extensions.insert(pending.tenant.clone());
+    let body = Body::new(CountedBody {
+        inner: body,
+        bytes: Arc::clone(&pending.bytes_in),
+    });
+    let response = next.run(Request::from_parts(parts, body)).await;
+
+    let status = response.status().as_u16();
+    response.map(|body| {
+        Body::new(LoggedBody {
+            inner: body,
+            bytes_out: 0,
+            ended: false,
+            pending: Some((pending, status)),
+        })
+    })
+}
+
+fn write(format: AccessLogFormat, record: &AccessLogRecord) {
+    match format {
+        AccessLogFormat::Json => match serde_json::to_string(record) {
+            Ok(line) => {
+                let _ = writeln!(std::io::stdout().lock(), "{line}");
+            }
+            Err(e) => tracing::warn!("Failed to serialize access log record: {}", e),
+        },
+        AccessLogFormat::Text => tracing::info!(
+            target: "access_log",
+            request_id = record.request_id.as_deref(),
+            method = %record.method,
+            path = %record.path,
+            status = record.status,
+            duration_ms = record.duration_ms,
+            bytes_in = record.bytes_in,
+            bytes_out = record.bytes_out,
+            tenant_id = record.tenant_id.as_deref(),
+            client_ip = record.client_ip.as_deref(),
+            completed = record.completed,
+            "request"
+        ),
+    }
+}
+
+/// What is known of a request before its response body is sent
+struct PendingRecord {
+    started: Instant,
+    timestamp: OffsetDateTime,
+    request_id: Option<String>,
+    method: String,
+    path: String,
+    client_ip: Option<String>,
+    tenant: AccessLogTenant,
+    bytes_in: Arc<AtomicU64>,
+    emit: Box<dyn FnOnce(AccessLogRecord) + Send>,
+}
+
+impl PendingRecord {
+    fn finish(self, status: u16, bytes_out: u64, completed: bool) {
+        let record = AccessLogRecord {
+            timestamp: self.timestamp,
+            request_id: self.request_id,
+            method: self.method,
+            path: self.path,
+            status,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out,
+            tenant_id: self.tenant.0.get().cloned(),
+            client_ip: self.client_ip,
+            completed,
+        };
+        (self.emit)(record);
+    }
+}
+
+/// Request body that counts the bytes the server reads
+///
+/// Keeps the size hint, so Content-Length based checks see the same body.
+struct CountedBody {
+    inner: Body,
+    bytes: Arc<AtomicU64>,
+}
+
+impl HttpBody for CountedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        if let Some(data) = frame.as_ref().and_then(|f| f.as_ref().ok()?.data_ref()) {
+            self.bytes.fetch_add(data.len() as u64, Ordering::Relaxed);
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Response body that writes the record once it is sent or dropped
+///
+/// Keeps the size hint, so responses keep their Content-Length.
+struct LoggedBody {
+    inner: Body,
+    bytes_out: u64,
+    ended: bool,
+    /// Taken when the record is written
+    pending: Option<(PendingRecord, u16)>,
+}
+
+impl HttpBody for LoggedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    self.bytes_out += data.len() as u64;
+                }
+            }
+            // The record is written as incomplete when the body is dropped
+            Some(Err(_)) => {}
+            None => self.ended = true,
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for LoggedBody {
+    fn drop(&mut self) {
+        if let Some((pending, status)) = self.pending.take() {
+            // Servers stop polling once a body reports its end
+            let completed = self.ended || self.inner.is_end_stream();
+            pending.finish(status, self.bytes_out, completed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Extension, Router};
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    type Records = Arc<Mutex<Vec<AccessLogRecord>>>;
+
+    fn app(config: AccessLogConfig, records: Records) -> Router {
+        let user_context = UserContext::new(
+            "user-1".to_string(),
+            "tenant-a".to_string(),
+            vec![],
+            HashSet::new(),
+            true,
+            None,
+        );
+        let config = Arc::new(config);
+        Router::new()
+            .route(
+                "/objects",
+                get(|| async { "listing" }).post(|body: String| async move { body.repeat(2) }),
+            )
+            .route("/health", get(|| async { "ok" }))
+            .layer(middleware::from_fn(record_tenant))
+            .layer(Extension(user_context))
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                let records = Arc::clone(&records);
+                async move {
+                    log_request(&config, req, next, move |record| {
+                        records.lock().unwrap().push(record)
+                    })
+                    .await
+                }
+            }))
+            .layer(Extension(RequestId("req-1".to_string())))
+    }
+
+    async fn send(app: Router, request: Request) -> Bytes {
+        let response = app.oneshot(request).await.unwrap();
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_counts_bytes_once_sent() {
+        let records = Records::default();
+        let app = app(
+            AccessLogConfig::new().with_enabled(true),
+            Arc::clone(&records),
+        );
+
+        let body = send(
+            app,
+            Request::builder()
+                .method("POST")
+                .uri("/objects")
+                .header("x-forwarded-for", "203.0.113.7")
+                .body(Body::from("hello"))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(body, "hellohello");
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(
+            (record.method.as_str(), record.path.as_str()),
+            ("POST", "/objects")
+        );
+        assert_eq!(record.status, 200);
+        assert_eq!((record.bytes_in, record.bytes_out), (5, 10));
+        assert_eq!(record.request_id.as_deref(), Some("req-1"));
+        assert_eq!(record.tenant_id.as_deref(), Some("tenant-a"));
+        assert_eq!(record.client_ip.as_deref(), Some("203.0.113.7"));
+        assert!(record.completed);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_body_is_logged_incomplete() {
+        let records = Records::default();
+        let app = app(
+            AccessLogConfig::new().with_enabled(true),
+            Arc::clone(&records),
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/objects")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(records.lock().unwrap().is_empty());
+        drop(response);
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].bytes_out, 0);
+        assert!(!records[0].completed);
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_skipped_by_default() {
+        let records = Records::default();
+        let config = AccessLogConfig::new().with_enabled(true);
+
+        send(
+            app(config.clone(), Arc::clone(&records)),
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert!(records.lock().unwrap().is_empty());
+
+        send(
+            app(
+                config.with_health_check_sample_rate(1.0),
+                Arc::clone(&records),
+            ),
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(records.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_format_parsing() {
+        assert_eq!("JSON".parse::<AccessLogFormat>(), Ok(AccessLogFormat::Json));
+        assert_eq!("text".parse::<AccessLogFormat>(), Ok(AccessLogFormat::Text));
+        assert!("xml".parse::<AccessLogFormat>().is_err());
+    }
+}
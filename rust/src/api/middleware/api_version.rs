@@ -0,0 +1,424 @@
+//! API versioning
+//!
+//! Routes live under a version prefix. Object routes are served as both
+//! `/v1/objects` and `/v2/objects`: v2 starts out as a copy of v1 over the
+//! same use cases, so its request and response DTOs can change without
+//! breaking v1 clients. A client that keeps its `/v1/` URLs can pick a
+//! version with `Accept-Version` instead; the header takes precedence over
+//! the prefix on versioned routes and is ignored elsewhere.
+//!
+//! Routes of a version scheduled for removal answer with `Deprecation`
+//! (RFC 9745) and `Sunset` (RFC 8594) headers, plus a `Link` to the same
+//! resource in the successor version.
+
+use std::str::FromStr;
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use time::{macros::format_description, OffsetDateTime, UtcOffset};
+
+use crate::api::errors::ApiError;
+
+/// Request header selecting the API version of a versioned route
+pub const ACCEPT_VERSION_HEADER: &str = "accept-version";
+
+/// Header with the time a route was (or will be) deprecated
+pub const DEPRECATION_HEADER: &str = "deprecation";
+
+/// Header with the time a route will be removed
+pub const SUNSET_HEADER: &str = "sunset";
+
+/// Resources served in more than one version, below the version prefix
+const VERSIONED_RESOURCES: &[&str] = &["/objects"];
+
+/// Version of the HTTP API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    /// Path prefix of the version's routes
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::V1 => "/v1",
+            Self::V2 => "/v2",
+        }
+    }
+
+    /// Split a request path into its version and the path below the prefix
+    pub fn split_path(path: &str) -> Option<(Self, &str)> {
+        [Self::V1, Self::V2].into_iter().find_map(|version| {
+            let rest = path.strip_prefix(version.prefix())?;
+            (rest.is_empty() || rest.starts_with('/')).then_some((version, rest))
+        })
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "1" | "v1" => Ok(Self::V1),
+            "2" | "v2" => Ok(Self::V2),
+            other => Err(format!("unknown API version '{other}' (expected 1 or 2)")),
+        }
+    }
+}
+
+/// Whether `path` is a route of `resource` (e.g. `/objects`) in any version
+pub fn is_resource_path(path: &str, resource: &str) -> bool {
+    ApiVersion::split_path(path).is_some_and(|(_, rest)| under(rest, resource))
+}
+
+/// Whether `path` is served in more than one version
+pub fn is_versioned_path(path: &str) -> bool {
+    VERSIONED_RESOURCES
+        .iter()
+        .any(|resource| is_resource_path(path, resource))
+}
+
+fn under(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// API version configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApiVersionConfig {
+    /// Serve v2 of the versioned routes
+    pub v2_enabled: bool,
+    /// When v1 of the versioned routes is deprecated, sent as `Deprecation`
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub v1_deprecation: Option<OffsetDateTime>,
+    /// When v1 of the versioned routes is removed, sent as `Sunset`
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub v1_sunset: Option<OffsetDateTime>,
+}
+
+impl ApiVersionConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the configuration from its settings, with RFC 3339 times
+    pub fn parse(
+        v2_enabled: bool,
+        v1_deprecation: Option<&str>,
+        v1_sunset: Option<&str>,
+    ) -> Result<Self, String> {
+        let parse_time = |value: &str| {
+            OffsetDateTime::parse(value.trim(), &time::format_description::well_known::Rfc3339)
+                .map_err(|e| format!("invalid RFC 3339 time '{value}': {e}"))
+        };
+        let config = Self::new()
+            .with_v2_enabled(v2_enabled)
+            .with_v1_deprecation(v1_deprecation.map(parse_time).transpose()?)
+            .with_v1_sunset(v1_sunset.map(parse_time).transpose()?);
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Enable or disable v2 of the versioned routes
+    pub fn with_v2_enabled(mut self, enabled: bool) -> Self {
+        self.v2_enabled = enabled;
+        self
+    }
+
+    /// Set when v1 of the versioned routes is deprecated
+    pub fn with_v1_deprecation(mut self, at: Option<OffsetDateTime>) -> Self {
+        self.v1_deprecation = at;
+        self
+    }
+
+    /// Set when v1 of the versioned routes is removed
+    pub fn with_v1_sunset(mut self, at: Option<OffsetDateTime>) -> Self {
+        self.v1_sunset = at;
+        self
+    }
+
+    /// A version can only be retired once its successor is served, and not
+    /// before it is deprecated
+    pub fn validate(&self) -> Result<(), String> {
+        let scheduled = self.v1_deprecation.is_some() || self.v1_sunset.is_some();
+        if scheduled && !self.v2_enabled {
+            return Err("v1 can only be deprecated once v2 is enabled".to_string());
+        }
+        if let (Some(deprecation), Some(sunset)) = (self.v1_deprecation, self.v1_sunset) {
+            if sunset < deprecation {
+                return Err("v1 sunset must not be before its deprecation".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether versioned routes are served in `version`
+    pub fn serves(&self, version: ApiVersion) -> bool {
+        match version {
+            ApiVersion::V1 => true,
+            ApiVersion::V2 => self.v2_enabled,
+        }
+    }
+
+    /// Deprecation and sunset times of `version`
+    fn schedule(&self, version: ApiVersion) -> (Option<OffsetDateTime>, Option<OffsetDateTime>) {
+        match version {
+            ApiVersion::V1 => (self.v1_deprecation, self.v1_sunset),
+            ApiVersion::V2 => (None, None),
+        }
+    }
+}
+
+/// Route versioned requests by `Accept-Version`
+///
+/// Runs before routing: the path of a versioned route is rewritten to the
+/// requested version's prefix. A version that is unknown or not enabled is
+/// refused with `406 Not Acceptable`.
+pub async fn accept_version_middleware(
+    config: &ApiVersionConfig,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(value) = request.headers().get(ACCEPT_VERSION_HEADER) else {
+        return next.run(request).await;
+    };
+    if !is_versioned_path(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let requested = value
+        .to_str()
+        .map_err(|_| "unreadable API version".to_string())
+        .and_then(ApiVersion::from_str);
+    let version = match requested {
+        Ok(version) if config.serves(version) => version,
+        Ok(version) => {
+            return not_acceptable(format!("API {} is not enabled", version.prefix()));
+        }
+        Err(e) => return not_acceptable(format!("Accept-Version: {e}")),
+    };
+
+    if let Some(uri) = rebase(request.uri(), version) {
+        *request.uri_mut() = uri;
+    }
+    next.run(request).await
+}
+
+fn not_acceptable(message: String) -> Response {
+    ApiError::new(StatusCode::NOT_ACCEPTABLE, message).into_response()
+}
+
+/// `uri` with its version prefix replaced by `version`'s
+fn rebase(uri: &Uri, version: ApiVersion) -> Option<Uri> {
+    let (current, rest) = ApiVersion::split_path(uri.path())?;
+    if current == version {
+        return None;
+    }
+    let query = uri.query().map(|q| format!("?{q}")).unwrap_or_default();
+    let path_and_query = format!("{}{rest}{query}", version.prefix());
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(path_and_query.parse().ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+/// Version response headers of a versioned route served as `version`
+///
+/// Responses vary by `Accept-Version`; a version scheduled for removal also
+/// gets `Deprecation`, `Sunset` and a successor `Link`.
+pub async fn version_headers_middleware(
+    config: &ApiVersionConfig,
+    version: ApiVersion,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (deprecation, sunset) = config.schedule(version);
+    let successor =
+        rebase(request.uri(), ApiVersion::V2).filter(|_| deprecation.is_some() || sunset.is_some());
+    let mut response = next.run(request).await;
+
+    let headers = response.headers_mut();
+    headers.append(
+        header::VARY,
+        HeaderValue::from_static(ACCEPT_VERSION_HEADER),
+    );
+    if let Some(deprecation) = deprecation {
+        if let Ok(value) = HeaderValue::from_str(&format!("@{}", deprecation.unix_timestamp())) {
+            headers.insert(DEPRECATION_HEADER, value);
+        }
+    }
+    if let Some(value) = sunset
+        .and_then(format_http_date)
+        .and_then(|date| HeaderValue::from_str(&date).ok())
+    {
+        headers.insert(SUNSET_HEADER, value);
+    }
+    if let Some(value) = successor.and_then(|uri| {
+        HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", uri.path())).ok()
+    }) {
+        headers.append(header::LINK, value);
+    }
+    response
+}
+
+/// Format a time as an HTTP-date (RFC 9110 IMF-fixdate)
+fn format_http_date(at: OffsetDateTime) -> Option<String> {
+    at.to_offset(UtcOffset::UTC)
+        .format(format_description!(
+            "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+        ))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use std::sync::Arc;
+    use time::macros::datetime;
+    use tower::ServiceExt;
+
+    fn scheduled() -> ApiVersionConfig {
+        ApiVersionConfig::new()
+            .with_v2_enabled(true)
+            .with_v1_deprecation(Some(datetime!(2026-01-01 00:00 UTC)))
+            .with_v1_sunset(Some(datetime!(2026-07-01 00:00 UTC)))
+    }
+
+    /// Object routes of both versions behind the same layers as the router
+    fn app(config: ApiVersionConfig) -> Router {
+        let config = Arc::new(config);
+        let mut routes = Router::new();
+        for version in [ApiVersion::V1, ApiVersion::V2] {
+            let config = Arc::clone(&config);
+            routes = routes.merge(
+                Router::new()
+                    .route(
+                        &format!("{}/objects/{{id}}", version.prefix()),
+                        get(move || async move { version.prefix() }),
+                    )
+                    .layer(middleware::from_fn(move |req, next| {
+                        let config = Arc::clone(&config);
+                        async move { version_headers_middleware(&config, version, req, next).await }
+                    })),
+            );
+        }
+        routes = routes.route("/v1/stats", get(|| async { "stats" }));
+
+        Router::new()
+            .fallback_service(routes)
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                async move { accept_version_middleware(&config, req, next).await }
+            }))
+    }
+
+    async fn get_path(app: Router, path: &str, version: Option<&str>) -> Response {
+        let mut request = Request::builder().uri(path);
+        if let Some(version) = version {
+            request = request.header(ACCEPT_VERSION_HEADER, version);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_version_sends_deprecation_headers() {
+        let response = get_path(app(scheduled()), "/v1/objects/abc?x=1", None).await;
+
+        assert_eq!(response.headers()[DEPRECATION_HEADER], "@1767225600");
+        assert_eq!(
+            response.headers()[SUNSET_HEADER],
+            "Wed, 01 Jul 2026 00:00:00 GMT"
+        );
+        assert_eq!(
+            response.headers()[header::LINK],
+            "</v2/objects/abc>; rel=\"successor-version\""
+        );
+        assert_eq!(response.headers()[header::VARY], ACCEPT_VERSION_HEADER);
+    }
+
+    #[tokio::test]
+    async fn test_successor_version_is_not_deprecated() {
+        let response = get_path(app(scheduled()), "/v2/objects/abc", None).await;
+
+        assert!(response.headers().get(DEPRECATION_HEADER).is_none());
+        assert!(response.headers().get(SUNSET_HEADER).is_none());
+        assert_eq!(body(response).await, "/v2");
+    }
+
+    #[tokio::test]
+    async fn test_no_deprecation_headers_unless_scheduled() {
+        let config = ApiVersionConfig::new().with_v2_enabled(true);
+
+        let response = get_path(app(config), "/v1/objects/abc", None).await;
+
+        assert!(response.headers().get(DEPRECATION_HEADER).is_none());
+        assert!(response.headers().get(SUNSET_HEADER).is_none());
+        assert!(response.headers().get(header::LINK).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_accept_version_selects_routes() {
+        let v2 = get_path(app(scheduled()), "/v1/objects/abc", Some("2")).await;
+        let v1 = get_path(app(scheduled()), "/v2/objects/abc", Some("v1")).await;
+        let unversioned = get_path(app(scheduled()), "/v1/stats", Some("2")).await;
+
+        assert!(v2.headers().get(DEPRECATION_HEADER).is_none());
+        assert_eq!(body(v2).await, "/v2");
+        assert!(v1.headers().get(DEPRECATION_HEADER).is_some());
+        assert_eq!(body(v1).await, "/v1");
+        assert_eq!(body(unversioned).await, "stats");
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_versions_are_not_acceptable() {
+        let disabled = get_path(app(ApiVersionConfig::new()), "/v1/objects/abc", Some("2")).await;
+        let unknown = get_path(app(scheduled()), "/v1/objects/abc", Some("3")).await;
+
+        assert_eq!(disabled.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(unknown.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[test]
+    fn test_schedule_is_validated() {
+        assert!(ApiVersionConfig::parse(true, Some("2026-01-01T00:00:00Z"), None).is_ok());
+        assert!(ApiVersionConfig::parse(true, Some("next year"), None).is_err());
+        assert!(ApiVersionConfig::parse(false, Some("2026-01-01T00:00:00Z"), None).is_err());
+        assert!(ApiVersionConfig::parse(
+            true,
+            Some("2026-07-01T00:00:00Z"),
+            Some("2026-01-01T00:00:00Z")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_paths_split_by_version() {
+        assert_eq!(
+            ApiVersion::split_path("/v2/objects/abc"),
+            Some((ApiVersion::V2, "/objects/abc"))
+        );
+        assert_eq!(ApiVersion::split_path("/v10/objects"), None);
+        assert!(is_versioned_path("/v1/objects"));
+        assert!(!is_versioned_path("/v1/objectsx"));
+        assert!(!is_versioned_path("/v1/api-keys"));
+    }
+}
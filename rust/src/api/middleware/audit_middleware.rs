@@ -10,6 +10,7 @@ use axum::{
 };
 use std::sync::Arc;
 
+use super::api_version;
 use super::audit_config::AuditConfig;
 use super::audit_types::{AuditEventType, AuditLogEntry, AuditLogger};
 use crate::domain::authorization::UserContext;
@@ -107,7 +108,7 @@ impl AuditMiddleware {
 }
 
 /// Extract IP address from request headers
-fn extract_ip_address(headers: &axum::http::HeaderMap) -> Option<String> {
+pub(crate) fn extract_ip_address(headers: &axum::http::HeaderMap) -> Option<String> {
     // Try X-Forwarded-For first (for proxies/load balancers)
     if let Some(forwarded_for) = headers.get("x-forwarded-for") {
         if let Ok(forwarded_str) = forwarded_for.to_str() {
@@ -157,9 +158,13 @@ fn determine_event_type(method: &Method, uri: &Uri, status_code: u16) -> AuditEv
     }
 
     // Object operations
-    if path.starts_with("/v1/objects") {
+    if api_version::is_resource_path(path, "/objects") {
         return match *method {
             Method::POST => AuditEventType::ObjectCreated,
+            // A precondition answered without sending the content
+            Method::GET if status_code == 304 || status_code == 412 => {
+                AuditEventType::PermissionChecked
+            }
             Method::GET => AuditEventType::ObjectRead,
             Method::PUT => AuditEventType::ObjectUpdated,
             Method::DELETE => AuditEventType::ObjectDeleted,
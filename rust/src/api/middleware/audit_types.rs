@@ -41,6 +41,8 @@ pub enum AuditEventType {
     HealthCheck,
     ConfigurationChange,
     BackupOperation,
+    GhostObjectDetected,
+    GarbageCollection,
 }
 
 impl std::fmt::Display for AuditEventType {
@@ -68,6 +70,8 @@ impl std::fmt::Display for AuditEventType {
             AuditEventType::HealthCheck => write!(f, "health_check"),
             AuditEventType::ConfigurationChange => write!(f, "configuration_change"),
             AuditEventType::BackupOperation => write!(f, "backup_operation"),
+            AuditEventType::GhostObjectDetected => write!(f, "ghost_object_detected"),
+            AuditEventType::GarbageCollection => write!(f, "garbage_collection"),
         }
     }
 }
@@ -1,6 +1,6 @@
 use axum::{extract::Request, http::header::AUTHORIZATION, response::Response};
 use futures_util::future::BoxFuture;
-use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+use jsonwebtoken::{decode, decode_header, DecodingKey};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -165,9 +165,8 @@ where
 
                     // 2b. Try API Key from Database
                     if auth_config.legacy_auth_enabled {
-                        use crate::domain::value_objects::ApiKeyValue;
-                        let token_hash = ApiKeyValue::hash(token);
-                        if let Ok(Some(api_key)) = api_key_repo.find_by_key(token_hash.as_str()).await {
+                        // The repository checks the token against the salted hash
+                        if let Ok(Some(api_key)) = api_key_repo.find_by_key(token).await {
                             if api_key.is_active() && !api_key.is_expired() {
                                 let mut permissions = HashSet::new();
                                 if api_key.permissions().read {
@@ -216,37 +215,22 @@ where
                         }
                     }
 
-                    // 2c. Try OIDC JWT Validation
-                    if oidc_config.enabled && oidc_config.issuer_url.is_some() {
+                    // 2c. Try JWT validation against the issuer's (or configured) JWKS
+                    if oidc_config.verifies_tokens() {
                         if let Ok(header) = decode_header(token) {
                             if let Some(kid) = header.kid {
+                                // Unknown or expired kid: no key, so the token is rejected
                                 if let Some(decoding_key) = jwks_cache.get(&kid).await {
-                                    // Mandate OIDC-compliant algorithms (Reject none, HS256, etc.)
-                                    use jsonwebtoken::Algorithm;
-                                    match header.alg {
-                                        Algorithm::RS256
-                                        | Algorithm::RS384
-                                        | Algorithm::RS512
-                                        | Algorithm::PS256
-                                        | Algorithm::PS384
-                                        | Algorithm::PS512 => {}
-                                        _ => {
-                                            tracing::warn!(
-                                                "OIDC token uses non-compliant algorithm: {:?}",
-                                                header.alg
-                                            );
-                                            let req = Request::from_parts(parts, body);
-                                            return inner.call(req).await;
-                                        }
-                                    }
-
-                                    let mut validation = Validation::new(header.alg);
-                                    if let Some(iss) = &oidc_config.issuer_url {
-                                        validation.set_issuer(&[iss]);
-                                    }
-                                    if let Some(aud) = &oidc_config.audience {
-                                        validation.set_audience(&[aud]);
-                                    }
+                                    // Only configured asymmetric algorithms (never none or HS*)
+                                    let Some(validation) = oidc_config.validation(header.alg)
+                                    else {
+                                        tracing::warn!(
+                                            "JWT uses non-accepted algorithm: {:?}",
+                                            header.alg
+                                        );
+                                        let req = Request::from_parts(parts, body);
+                                        return inner.call(req).await;
+                                    };
 
                                     match decode::<Claims>(token, &decoding_key, &validation) {
                                         Ok(token_data) => {
@@ -8,6 +8,7 @@ use axum::{
 use serde::Serialize;
 use std::collections::HashSet;
 
+use super::api_version;
 use crate::domain::authorization::{permissions, UserContext};
 
 /// Authorization error response
@@ -204,6 +205,13 @@ pub async fn require_object_delete(request: Request, next: Next) -> Response {
         .await
 }
 
+/// Require access to object retention and legal holds
+pub async fn require_object_retention(request: Request, next: Next) -> Response {
+    require_permissions(vec![permissions::OBJECTS_RETENTION])
+        .layer(request, next)
+        .await
+}
+
 /// Require API key management access
 pub async fn require_api_key_management(request: Request, next: Next) -> Response {
     require_any_permission(vec![
@@ -220,30 +228,115 @@ pub fn require_admin() -> PermissionMiddleware {
     require_permissions(vec![permissions::ADMIN])
 }
 
+/// Require admin access (route layer form)
+pub async fn require_admin_access(request: Request, next: Next) -> Response {
+    require_admin().layer(request, next).await
+}
+
 /// Require tenant admin access
 pub fn require_tenant_admin() -> PermissionMiddleware {
     require_any_permission(vec![permissions::ADMIN, permissions::TENANT_ADMIN])
 }
 
-/// Extract tenant_id from request path or query parameters
-pub fn extract_tenant_id_from_request(request: &Request) -> Option<String> {
-    // Try to extract from path parameters (for routes like /v1/objects/by-key/{namespace}/{tenant_id}/{key})
-    if let Some(tenant_id) = extract_from_path(request, 4) {
-        return Some(tenant_id);
+/// Largest JSON body buffered to look for a `tenant_id` field; matches
+/// axum's default `Json` extractor limit
+const MAX_TENANT_CHECK_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Reject requests whose path, query or JSON body names another tenant
+///
+/// Runs after authentication. Admins may address any tenant; everyone else
+/// only `UserContext::tenant_id`. Requests that name no tenant pass through.
+pub async fn require_tenant_isolation(request: Request, next: Next) -> Response {
+    let Some(user_context) = request.extensions().get::<UserContext>() else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(AuthorizationErrorResponse {
+                error: "Authentication required".to_string(),
+                code: "AUTHENTICATION_REQUIRED".to_string(),
+                details: Some("No user context found in request".to_string()),
+            }),
+        )
+            .into_response();
+    };
+    if user_context.is_admin() {
+        return next.run(request).await;
     }
-
-    // Try to extract from query parameters
-    if let Some(query) = request.uri().query() {
-        for pair in query.split('&') {
-            if let Some((key, value)) = pair.split_once('=') {
-                if key == "tenant_id" {
-                    return Some(value.to_string());
-                }
+    let own_tenant = user_context.tenant_id.clone();
+
+    let mut requested = extract_tenant_id_from_request(&request);
+    let request = if requested.is_none() && is_json(&request) {
+        let (parts, body) = request.into_parts();
+        let bytes = match axum::body::to_bytes(body, MAX_TENANT_CHECK_BODY_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(_) => {
+                return (
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    Json(AuthorizationErrorResponse {
+                        error: "Request body too large".to_string(),
+                        code: "PAYLOAD_TOO_LARGE".to_string(),
+                        details: None,
+                    }),
+                )
+                    .into_response();
             }
+        };
+        requested = extract_tenant_id_from_json(&bytes);
+        Request::from_parts(parts, axum::body::Body::from(bytes))
+    } else {
+        request
+    };
+
+    if let Some(tenant_id) = requested {
+        if tenant_id != own_tenant {
+            tracing::warn!(
+                user_tenant = %own_tenant,
+                requested_tenant = %tenant_id,
+                path = %request.uri().path(),
+                "Rejected cross-tenant request"
+            );
+            return (
+                StatusCode::FORBIDDEN,
+                Json(AuthorizationErrorResponse {
+                    error: "Access forbidden".to_string(),
+                    code: "TENANT_MISMATCH".to_string(),
+                    details: Some("Cannot access resources of other tenants".to_string()),
+                }),
+            )
+                .into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+fn is_json(request: &Request) -> bool {
+    request
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+/// Top-level `tenant_id` string of a JSON object body
+fn extract_tenant_id_from_json(body: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    value.get("tenant_id")?.as_str().map(str::to_string)
+}
+
+/// Extract tenant_id from request path or query parameters
+pub fn extract_tenant_id_from_request(request: &Request) -> Option<String> {
+    // Key-based routes carry the tenant in the path:
+    // /v1/objects/by-key/{namespace}/{tenant_id}/{key}, in every API version
+    if api_version::is_resource_path(request.uri().path(), "/objects/by-key") {
+        if let Some(tenant_id) = extract_from_path(request, 4) {
+            return Some(tenant_id);
         }
     }
 
-    None
+    // Decode the query the same way the handlers' `Query` extractors do
+    axum::extract::Query::<std::collections::HashMap<String, String>>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|query| query.0.get("tenant_id").cloned())
 }
 
 /// Extract parameter from path by index (0-based)
@@ -256,8 +349,134 @@ fn extract_from_path(request: &Request, index: usize) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::{body::Body, http::Request};
+    use axum::{
+        body::Body,
+        http::Request,
+        middleware,
+        routing::{get, post},
+        Router,
+    };
     use std::collections::HashSet;
+    use tower::ServiceExt;
+
+    fn user(tenant_id: &str, permissions: &[&str]) -> UserContext {
+        UserContext::new(
+            "user123".to_string(),
+            tenant_id.to_string(),
+            vec![],
+            permissions.iter().map(|p| p.to_string()).collect(),
+            false,
+            None,
+        )
+    }
+
+    /// Routes shaped like the object API behind the tenant guard
+    fn app(user_context: UserContext) -> Router {
+        Router::new()
+            .route("/v1/objects", get(|| async { "ok" }))
+            .route("/v1/objects/search", post(|body: String| async { body }))
+            .route(
+                "/v1/objects/by-key/{namespace}/{tenant_id}/{key}",
+                get(|| async { "ok" }),
+            )
+            .layer(middleware::from_fn(require_tenant_isolation))
+            .layer(axum::Extension(user_context))
+    }
+
+    async fn status(user_context: UserContext, request: Request<Body>) -> StatusCode {
+        app(user_context).oneshot(request).await.unwrap().status()
+    }
+
+    fn get_request(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    fn search_request(tenant_id: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/objects/search")
+            .header("content-type", "application/json")
+            .body(Body::from(format!(
+                r#"{{"namespace":"models","tenant_id":"{tenant_id}"}}"#
+            )))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation_rejects_other_tenant_in_query() {
+        let status = status(
+            user("tenant-a", &[permissions::OBJECTS_READ]),
+            get_request("/v1/objects?namespace=models&tenant_id=tenant-b"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation_rejects_other_tenant_in_path() {
+        let status = status(
+            user("tenant-a", &[permissions::OBJECTS_READ]),
+            get_request("/v1/objects/by-key/models/tenant-b/file.txt"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation_rejects_other_tenant_in_json_body() {
+        let status = status(
+            user("tenant-a", &[permissions::OBJECTS_READ]),
+            search_request("tenant-b"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation_passes_own_tenant_and_preserves_body() {
+        let response = app(user("tenant-a", &[permissions::OBJECTS_READ]))
+            .oneshot(search_request("tenant-a"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains(r#""tenant_id":"tenant-a""#));
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation_decodes_query_values() {
+        let status = status(
+            user("tenant a", &[permissions::OBJECTS_READ]),
+            get_request("/v1/objects?namespace=models&tenant_id=tenant%20a"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation_lets_admins_cross_tenants() {
+        let status = status(
+            user("tenant-a", &[permissions::ADMIN]),
+            get_request("/v1/objects/by-key/models/tenant-b/file.txt"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_extract_tenant_id_ignores_other_long_paths() {
+        let request = get_request("/v1/objects/abc/metadata/extra/segment");
+
+        assert_eq!(extract_tenant_id_from_request(&request), None);
+    }
 
     #[test]
     fn test_extract_tenant_id_from_path() {
@@ -272,6 +491,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_tenant_id_from_path_of_any_version() {
+        let request = Request::builder()
+            .uri("/v2/objects/by-key/models/tenant123/file.txt")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(
+            extract_tenant_id_from_request(&request),
+            Some("tenant123".to_string())
+        );
+    }
+
     #[test]
     fn test_extract_tenant_id_from_query() {
         let request = Request::builder()
@@ -6,10 +6,15 @@
 use serde::{Deserialize, Serialize};
 
 use super::{
-    audit_config::AuditConfig, auth_config::AuthMiddlewareConfig,
-    error_handling::ErrorHandlingConfig, input_sanitization::InputSanitizationConfig,
-    oidc_config::OidcConfig, rate_limiting::RateLimitConfig,
-    security_headers::SecurityHeadersConfig, size_limits::SizeLimitConfig,
+    access_log::AccessLogConfig, api_version::ApiVersionConfig, audit_config::AuditConfig, auth_config::AuthMiddlewareConfig,
+    cors::CorsConfig, error_handling::ErrorHandlingConfig, header_limits::HeaderLimitConfig,
+    https_redirect::HttpsRedirectConfig,
+    input_sanitization::InputSanitizationConfig, oidc_config::OidcConfig,
+    rate_limiting::RateLimitConfig, request_id::RequestIdConfig,
+    request_timeout::RequestTimeoutConfig,
+    response_compression::ResponseCompressionConfig, response_format::ResponseFormatConfig,
+    security_headers::SecurityHeadersConfig,
+    size_limits::SizeLimitConfig, storage_class_headers::StorageClassHeadersConfig,
 };
 
 /// Unified middleware configuration
@@ -31,6 +36,26 @@ pub struct MiddlewareConfig {
     pub security_headers: SecurityHeadersConfig,
     /// Size limits configuration
     pub size_limits: SizeLimitConfig,
+    /// Request header and URL length limits
+    pub header_limits: HeaderLimitConfig,
+    /// Request ID configuration
+    pub request_id: RequestIdConfig,
+    /// Per-route request timeout configuration
+    pub request_timeout: RequestTimeoutConfig,
+    /// HTTPS redirect and HSTS configuration
+    pub https_redirect: HttpsRedirectConfig,
+    /// Storage class response headers configuration
+    pub storage_class_headers: StorageClassHeadersConfig,
+    /// Download response compression configuration
+    pub response_compression: ResponseCompressionConfig,
+    /// JSON or MessagePack response bodies
+    pub response_format: ResponseFormatConfig,
+    /// CORS configuration
+    pub cors: CorsConfig,
+    /// API versions served and their deprecation schedule
+    pub api_version: ApiVersionConfig,
+    /// Structured access log configuration
+    pub access_log: AccessLogConfig,
 }
 
 impl MiddlewareConfig {
@@ -75,6 +100,66 @@ impl MiddlewareConfig {
         self
     }
 
+    /// Configure request header and URL length limits
+    pub fn with_header_limits(mut self, config: HeaderLimitConfig) -> Self {
+        self.header_limits = config;
+        self
+    }
+
+    /// Configure request ID propagation
+    pub fn with_request_id(mut self, config: RequestIdConfig) -> Self {
+        self.request_id = config;
+        self
+    }
+
+    /// Configure per-route request timeouts
+    pub fn with_request_timeout(mut self, config: RequestTimeoutConfig) -> Self {
+        self.request_timeout = config;
+        self
+    }
+
+    /// Configure HTTPS redirect and HSTS
+    pub fn with_https_redirect(mut self, config: HttpsRedirectConfig) -> Self {
+        self.https_redirect = config;
+        self
+    }
+
+    /// Configure storage class response headers
+    pub fn with_storage_class_headers(mut self, config: StorageClassHeadersConfig) -> Self {
+        self.storage_class_headers = config;
+        self
+    }
+
+    /// Configure download response compression
+    pub fn with_response_compression(mut self, config: ResponseCompressionConfig) -> Self {
+        self.response_compression = config;
+        self
+    }
+
+    /// Configure JSON or MessagePack response bodies
+    pub fn with_response_format(mut self, config: ResponseFormatConfig) -> Self {
+        self.response_format = config;
+        self
+    }
+
+    /// Configure CORS
+    pub fn with_cors(mut self, config: CorsConfig) -> Self {
+        self.cors = config;
+        self
+    }
+
+    /// Configure API versions
+    pub fn with_api_version(mut self, config: ApiVersionConfig) -> Self {
+        self.api_version = config;
+        self
+    }
+
+    /// Configure structured access logs
+    pub fn with_access_log(mut self, config: AccessLogConfig) -> Self {
+        self.access_log = config;
+        self
+    }
+
     /// Create a production-ready configuration
     pub fn production() -> Self {
         Self {
@@ -86,6 +171,16 @@ impl MiddlewareConfig {
             rate_limiting: RateLimitConfig::default(),
             security_headers: SecurityHeadersConfig::default(),
             size_limits: SizeLimitConfig::default(),
+            header_limits: HeaderLimitConfig::default(),
+            request_id: RequestIdConfig::default(),
+            request_timeout: RequestTimeoutConfig::default(),
+            https_redirect: HttpsRedirectConfig::default(),
+            storage_class_headers: StorageClassHeadersConfig::default(),
+            response_compression: ResponseCompressionConfig::default(),
+            response_format: ResponseFormatConfig::default(),
+            cors: CorsConfig::default(),
+            api_version: ApiVersionConfig::default(),
+            access_log: AccessLogConfig::default(),
         }
     }
 
@@ -104,6 +199,16 @@ impl MiddlewareConfig {
             },
             security_headers: SecurityHeadersConfig::default(),
             size_limits: SizeLimitConfig::default(),
+            header_limits: HeaderLimitConfig::default(),
+            request_id: RequestIdConfig::default(),
+            request_timeout: RequestTimeoutConfig::default(),
+            https_redirect: HttpsRedirectConfig::default(),
+            storage_class_headers: StorageClassHeadersConfig::default(),
+            response_compression: ResponseCompressionConfig::default(),
+            response_format: ResponseFormatConfig::default(),
+            cors: CorsConfig::permissive(),
+            api_version: ApiVersionConfig::default(),
+            access_log: AccessLogConfig::default(),
         }
     }
 }
@@ -149,16 +254,26 @@ mod tests {
         assert!(!config.error_handling.include_debug_info);
     }
 
+    #[test]
+    fn test_config_with_request_id() {
+        let config = MiddlewareConfig::new()
+            .with_request_id(RequestIdConfig::new().with_header_name("x-correlation-id"));
+
+        assert_eq!(config.request_id.header_name, "x-correlation-id");
+    }
+
     #[test]
     fn test_production_config() {
         let config = MiddlewareConfig::production();
         assert!(!config.error_handling.include_debug_info);
+        assert!(!config.cors.allows_any_origin());
     }
 
     #[test]
     fn test_development_config() {
         let config = MiddlewareConfig::development();
         assert!(config.error_handling.include_debug_info);
+        assert!(config.cors.allows_any_origin());
         assert_eq!(
             config.rate_limiting.unauthenticated_requests_per_minute,
             1000
@@ -4,13 +4,15 @@ use axum::{
     response::Response,
 };
 
+use super::api_version;
+
 /// Validate content-type and well-formed JSON for object endpoints
 pub async fn validate_json_for_objects(request: Request, next: Next) -> Response {
     let method = request.method().clone();
     let path = request.uri().path().to_string();
 
-    // Only validate POST requests to /v1/objects
-    if method == Method::POST && path.starts_with("/v1/objects") {
+    // Only validate POST requests to the object routes of any API version
+    if method == Method::POST && api_version::is_resource_path(&path, "/objects") {
         // Only enforce content-type validation when a Content-Type header is present
         let ct_opt = request
             .headers()
@@ -1,193 +1,277 @@
-use axum::http::{HeaderName, HeaderValue, Method};
+//! Cross-origin resource sharing
+//!
+//! Origins are matched against an allowlist and the request origin is echoed
+//! back only when it is on the list; other origins get no CORS headers, so
+//! browsers block the response. `*` allows any origin and is refused together
+//! with credentials, which browsers would reject anyway.
+
+use axum::http::{uri::Authority, HeaderName, HeaderValue, Method};
+use serde::{Deserialize, Serialize};
 use tower_http::cors::{AllowOrigin, CorsLayer};
 
-/// CORS configuration for production security
-pub fn create_cors_layer() -> CorsLayer {
-    // In production, you should configure this based on your actual domains
-    let allowed_origins = std::env::var("ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:3000,http://localhost:8080".to_string());
-
-    let origins: Vec<HeaderValue> = allowed_origins
-        .split(',')
-        .filter_map(|origin| origin.trim().parse::<HeaderValue>().ok())
-        .collect();
-
-    let origins = if origins.is_empty() {
-        AllowOrigin::any()
-    } else {
-        AllowOrigin::list(origins)
-    };
+/// Default allowed methods
+pub const DEFAULT_CORS_METHODS: &str = "GET,POST,PUT,PATCH,DELETE,OPTIONS,HEAD";
+
+/// Default allowed request headers
+pub const DEFAULT_CORS_HEADERS: &str = "authorization,content-type,x-request-id,x-api-key";
 
-    CorsLayer::new()
-        .allow_origin(origins)
-        .allow_methods([
-            Method::GET,
-            Method::POST,
-            Method::PUT,
-            Method::DELETE,
-            Method::OPTIONS,
-            Method::HEAD,
-        ])
-        .allow_headers([
-            HeaderName::from_static("authorization"),
-            HeaderName::from_static("content-type"),
-            HeaderName::from_static("x-request-id"),
-            HeaderName::from_static("x-api-key"),
-        ])
-        .allow_credentials(false) // Set to true only if you need to send cookies/auth headers
-        .max_age(std::time::Duration::from_secs(86400)) // 24 hours
+/// CORS configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// Allowed origins (`scheme://host[:port]`), or `*` for any origin
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    /// Send `Access-Control-Allow-Credentials: true`
+    pub allow_credentials: bool,
+    /// How long browsers may cache preflight results
+    pub max_age_secs: u64,
 }
 
-/// CORS configuration for development (more permissive)
-pub fn create_development_cors_layer() -> CorsLayer {
-    use tower_http::cors::{AllowHeaders, AllowMethods};
-
-    CorsLayer::new()
-        .allow_origin(AllowOrigin::any())
-        .allow_methods(AllowMethods::any())
-        .allow_headers(AllowHeaders::any())
-        // Note: Cannot use allow_credentials(true) with AllowHeaders::any()
-        // If credentials are needed, specify explicit headers instead
-        .allow_credentials(false)
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![
+                "http://localhost:3000".to_string(),
+                "http://localhost:8080".to_string(),
+            ],
+            allowed_methods: split_list(DEFAULT_CORS_METHODS),
+            allowed_headers: split_list(DEFAULT_CORS_HEADERS),
+            allow_credentials: false,
+            max_age_secs: 86400, // 24 hours
+        }
+    }
 }
 
-/// Select appropriate CORS layer based on environment
-pub fn create_cors_layer_for_environment() -> CorsLayer {
-    let is_development = std::env::var("ENVIRONMENT")
-        .unwrap_or_else(|_| "development".to_string())
-        .to_lowercase()
-        == "development";
+impl CorsConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    if is_development {
-        create_development_cors_layer()
-    } else {
-        create_cors_layer()
+    /// Any origin, without credentials
+    pub fn permissive() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            ..Self::default()
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Parse comma-separated origin, method and header lists
+    pub fn parse(origins: &str, methods: &str, headers: &str) -> Result<Self, String> {
+        let config = Self {
+            allowed_origins: split_list(origins),
+            allowed_methods: split_list(methods),
+            allowed_headers: split_list(headers),
+            ..Self::default()
+        };
 
-    #[test]
-    fn test_create_cors_layer_basic() {
-        let cors = create_cors_layer();
-        // Verify the layer is created successfully
-        assert!(matches!(cors, CorsLayer { .. }));
+        if config.allowed_origins.is_empty() {
+            return Err("at least one origin (or '*') is required".to_string());
+        }
+        for origin in &config.allowed_origins {
+            if origin != "*" && !is_valid_origin(origin) {
+                return Err(format!(
+                    "invalid origin '{origin}' (expected scheme://host[:port])"
+                ));
+            }
+        }
+        for method in &config.allowed_methods {
+            Method::from_bytes(method.as_bytes())
+                .map_err(|_| format!("invalid method '{method}'"))?;
+        }
+        for header in &config.allowed_headers {
+            HeaderName::from_bytes(header.as_bytes())
+                .map_err(|_| format!("invalid header name '{header}'"))?;
+        }
+        Ok(config)
     }
 
-    #[test]
-    fn test_create_development_cors_layer() {
-        let cors = create_development_cors_layer();
-        assert!(matches!(cors, CorsLayer { .. }));
+    /// Allow or disallow credentials
+    pub fn with_credentials(mut self, allow_credentials: bool) -> Self {
+        self.allow_credentials = allow_credentials;
+        self
     }
 
-    #[test]
-    fn test_create_cors_layer_for_environment_development() {
-        std::env::set_var("ENVIRONMENT", "development");
-        let cors = create_cors_layer_for_environment();
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ENVIRONMENT");
+    /// Set the preflight cache duration
+    pub fn with_max_age(mut self, max_age_secs: u64) -> Self {
+        self.max_age_secs = max_age_secs;
+        self
     }
 
-    #[test]
-    fn test_create_cors_layer_for_environment_production() {
-        std::env::set_var("ENVIRONMENT", "production");
-        let cors = create_cors_layer_for_environment();
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ENVIRONMENT");
+    /// Whether every origin is allowed
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == "*")
     }
 
-    #[test]
-    fn test_create_cors_layer_for_environment_default() {
-        std::env::remove_var("ENVIRONMENT");
-        let cors = create_cors_layer_for_environment();
-        assert!(matches!(cors, CorsLayer { .. }));
+    /// Check the combination of settings
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allow_credentials && self.allows_any_origin() {
+            return Err("credentials cannot be allowed for any origin ('*')".to_string());
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_cors_allowed_origins_from_env() {
-        // Test with multiple valid origins
-        std::env::set_var(
-            "ALLOWED_ORIGINS",
-            "https://example.com,https://app.example.com,http://localhost:3000",
-        );
-        let cors = create_cors_layer();
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ALLOWED_ORIGINS");
+    /// Build the CORS layer
+    ///
+    /// Credentials are never sent alongside a wildcard origin, even if
+    /// `validate` was skipped.
+    pub fn layer(&self) -> CorsLayer {
+        let origins = if self.allows_any_origin() {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(
+                self.allowed_origins
+                    .iter()
+                    .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+            )
+        };
+        let methods: Vec<Method> = self
+            .allowed_methods
+            .iter()
+            .filter_map(|method| Method::from_bytes(method.as_bytes()).ok())
+            .collect();
+        let headers: Vec<HeaderName> = self
+            .allowed_headers
+            .iter()
+            .filter_map(|header| HeaderName::from_bytes(header.as_bytes()).ok())
+            .collect();
+
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .allow_credentials(self.allow_credentials && !self.allows_any_origin())
+            .max_age(std::time::Duration::from_secs(self.max_age_secs))
     }
+}
 
-    #[test]
-    fn test_cors_allowed_origins_invalid_env() {
-        // Test with invalid origins - should fall back gracefully
-        std::env::set_var("ALLOWED_ORIGINS", "not-a-valid-url,also-invalid");
-        let cors = create_cors_layer();
-        // Should still create a valid layer (falls back to any origin)
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ALLOWED_ORIGINS");
+fn split_list(spec: &str) -> Vec<String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Browsers send origins as `scheme://host[:port]` with no path
+fn is_valid_origin(origin: &str) -> bool {
+    let Some((scheme, authority)) = origin.split_once("://") else {
+        return false;
+    };
+    matches!(scheme, "http" | "https")
+        && !authority.is_empty()
+        && !authority.contains(['/', '?', '#', '@'])
+        && authority.parse::<Authority>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+        response::Response,
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    fn app(config: &CorsConfig) -> Router {
+        Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(config.layer())
     }
 
-    #[test]
-    fn test_cors_allowed_origins_empty_env() {
-        // Test with empty origins - should allow any origin
-        std::env::set_var("ALLOWED_ORIGINS", "");
-        let cors = create_cors_layer();
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ALLOWED_ORIGINS");
+    async fn preflight(config: &CorsConfig, origin: &str) -> Response {
+        app(config)
+            .oneshot(
+                Request::builder()
+                    .method(Method::OPTIONS)
+                    .uri("/")
+                    .header(header::ORIGIN, origin)
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
     }
 
     #[test]
-    fn test_cors_allowed_origins_no_env() {
-        // Test without ALLOWED_ORIGINS - should use default localhost origins
-        std::env::remove_var("ALLOWED_ORIGINS");
-        let cors = create_cors_layer();
-        assert!(matches!(cors, CorsLayer { .. }));
+    fn test_parse_lists() {
+        let config = CorsConfig::parse(
+            " https://app.example.com , http://localhost:3000",
+            "GET, POST",
+            "authorization",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.allowed_origins,
+            vec!["https://app.example.com", "http://localhost:3000"]
+        );
+        assert_eq!(config.allowed_methods, vec!["GET", "POST"]);
+        assert_eq!(config.allowed_headers, vec!["authorization"]);
     }
 
     #[test]
-    fn test_cors_layer_with_mixed_valid_invalid_origins() {
-        // Test with mix of valid and invalid origins
-        std::env::set_var(
-            "ALLOWED_ORIGINS",
-            "https://valid.com,invalid-origin,https://another-valid.com",
-        );
-        let cors = create_cors_layer();
-        // Should create layer with valid origins only
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ALLOWED_ORIGINS");
+    fn test_parse_rejects_invalid_entries() {
+        let methods = DEFAULT_CORS_METHODS;
+        let headers = DEFAULT_CORS_HEADERS;
+
+        assert!(CorsConfig::parse("", methods, headers).is_err());
+        assert!(CorsConfig::parse("example.com", methods, headers).is_err());
+        assert!(CorsConfig::parse("https://example.com/app", methods, headers).is_err());
+        assert!(CorsConfig::parse("ftp://example.com", methods, headers).is_err());
+        assert!(CorsConfig::parse("*", "GET,NOT A METHOD", headers).is_err());
+        assert!(CorsConfig::parse("*", methods, "bad header").is_err());
     }
 
     #[test]
-    fn test_cors_layer_with_whitespace_origins() {
-        // Test with origins containing whitespace
-        std::env::set_var(
-            "ALLOWED_ORIGINS",
-            "  https://example.com  ,  https://app.example.com  ",
+    fn test_credentials_require_allowlist() {
+        assert!(CorsConfig::permissive()
+            .with_credentials(true)
+            .validate()
+            .is_err());
+        assert!(CorsConfig::new().with_credentials(true).validate().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reflects_allowlisted_origin() {
+        let config = CorsConfig::new().with_credentials(true).with_max_age(600);
+
+        let response = preflight(&config, "http://localhost:3000").await;
+
+        let headers = response.headers();
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "http://localhost:3000"
         );
-        let cors = create_cors_layer();
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ALLOWED_ORIGINS");
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
     }
 
-    #[test]
-    fn test_cors_layer_single_origin() {
-        // Test with single origin
-        std::env::set_var("ALLOWED_ORIGINS", "https://single-origin.com");
-        let cors = create_cors_layer();
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ALLOWED_ORIGINS");
+    #[tokio::test]
+    async fn test_omits_headers_for_disallowed_origin() {
+        let response = preflight(&CorsConfig::new(), "https://evil.example").await;
+
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
     }
 
-    #[test]
-    fn test_cors_layer_large_number_of_origins() {
-        // Test with many origins
-        let origins = (0..20)
-            .map(|i| format!("https://app{}.example.com", i))
-            .collect::<Vec<_>>()
-            .join(",");
-        std::env::set_var("ALLOWED_ORIGINS", origins);
-        let cors = create_cors_layer();
-        assert!(matches!(cors, CorsLayer { .. }));
-        std::env::remove_var("ALLOWED_ORIGINS");
+    #[tokio::test]
+    async fn test_wildcard_never_sends_credentials() {
+        let config = CorsConfig::permissive().with_credentials(true);
+
+        let response = preflight(&config, "https://anywhere.example").await;
+
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert!(headers
+            .get(header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+            .is_none());
     }
 }
@@ -1,10 +1,34 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+/// How much of an error reaches the client (`ERROR_DETAIL`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorDetail {
+    /// Generic bodies for server errors, sensitive client error messages
+    /// replaced
+    #[default]
+    Minimal,
+    /// Underlying error messages, plus a backtrace snippet for server errors
+    /// when `RUST_BACKTRACE` is set; for development and staging
+    Full,
+}
+
+impl ErrorDetail {
+    /// Parse `minimal` or `full`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "minimal" => Ok(Self::Minimal),
+            "full" => Ok(Self::Full),
+            other => Err(format!("expected 'minimal' or 'full', got '{other}'")),
+        }
+    }
+}
+
 /// Error handling configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ErrorHandlingConfig {
-    /// Whether to include stack traces in development
+    /// Whether to return underlying error messages and backtrace snippets
+    /// instead of sanitized ones (`ERROR_DETAIL=full`)
     pub include_debug_info: bool,
     /// Whether to log sensitive error details
     pub log_sensitive_errors: bool,
@@ -43,6 +67,11 @@ impl ErrorHandlingConfig {
         self
     }
 
+    /// Set how much of an error reaches the client
+    pub fn with_detail(self, detail: ErrorDetail) -> Self {
+        self.with_debug_info(detail == ErrorDetail::Full)
+    }
+
     /// Enable/disable sensitive error logging
     pub fn with_sensitive_logging(mut self, enabled: bool) -> Self {
         self.log_sensitive_errors = enabled;
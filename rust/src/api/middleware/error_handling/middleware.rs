@@ -1,12 +1,39 @@
-use axum::{extract::Request, http::StatusCode, response::Response};
+use std::backtrace::BacktraceStatus;
+use std::sync::Arc;
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+};
+use serde_json::{Map, Value};
 
 use super::config::ErrorHandlingConfig;
 use super::sanitizers::ErrorSanitizer;
 use super::utils::ErrorUtils;
+use crate::api::errors::ErrorBacktrace;
+use crate::api::middleware::request_id::RequestId;
+use crate::api::middleware::validation::VALIDATION_ERROR_CODE;
+
+/// Largest client error body inspected for sanitization
+const MAX_CLIENT_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// Backtrace lines returned with full error detail
+const MAX_BACKTRACE_LINES: usize = 30;
 
 /// Error handling middleware layer
 #[derive(Clone)]
-pub struct ErrorHandlingLayer;
+pub struct ErrorHandlingLayer {
+    config: Arc<ErrorHandlingConfig>,
+}
+
+impl ErrorHandlingLayer {
+    pub fn new(config: ErrorHandlingConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+}
 
 impl<S> tower::Layer<S> for ErrorHandlingLayer
 where
@@ -16,7 +43,10 @@ where
     type Service = ErrorHandlingService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        ErrorHandlingService { inner }
+        ErrorHandlingService {
+            inner,
+            config: Arc::clone(&self.config),
+        }
     }
 }
 
@@ -24,6 +54,7 @@ where
 #[derive(Clone)]
 pub struct ErrorHandlingService<S> {
     inner: S,
+    config: Arc<ErrorHandlingConfig>,
 }
 
 impl<S> tower::Service<Request> for ErrorHandlingService<S>
@@ -49,10 +80,12 @@ where
     fn call(&mut self, req: Request) -> Self::Future {
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = Arc::clone(&self.config);
 
         Box::pin(async move {
             let uri = req.uri().clone();
             let method = req.method().clone();
+            let request_id = req.extensions().get::<RequestId>().cloned();
 
             let result = inner.call(req).await;
 
@@ -64,17 +97,13 @@ where
                             response,
                             &uri,
                             &method,
-                            &ErrorHandlingConfig::default(),
+                            request_id.as_ref().map(RequestId::as_str),
+                            &config,
                         )
                         .await;
 
                         // Log the error for monitoring (but without sensitive details in production)
-                        ErrorUtils::log_error(
-                            &sanitized_response,
-                            &uri,
-                            &method,
-                            &ErrorHandlingConfig::default(),
-                        );
+                        ErrorUtils::log_error(&sanitized_response, &uri, &method, &config);
 
                         Ok(sanitized_response)
                     } else {
@@ -92,63 +121,357 @@ impl<S> ErrorHandlingService<S> {
         response: Response,
         uri: &axum::http::Uri,
         method: &axum::http::Method,
+        request_id: Option<&str>,
         config: &ErrorHandlingConfig,
     ) -> Response {
+        if config.include_debug_info {
+            return Self::detailed_error_response(response, request_id).await;
+        }
+
         let status = response.status();
+        if status.is_server_error() {
+            // Server error bodies may carry internals; keep only the status
+            // and headers (`Retry-After`, `Deprecation`, `Sunset`)
+            let (message, code) = match status {
+                StatusCode::INTERNAL_SERVER_ERROR => {
+                    ("Internal server error", Some("INTERNAL_ERROR"))
+                }
+                _ => (status.canonical_reason().unwrap_or("Server error"), None),
+            };
+            let (parts, _) = response.into_parts();
+            return Self::with_headers(
+                ErrorSanitizer::create_generic_error_response_with_request_id(
+                    status, message, code, request_id,
+                ),
+                parts.headers,
+            );
+        }
 
-        // For certain status codes, we want to provide generic responses
         match status {
-            StatusCode::INTERNAL_SERVER_ERROR => {
-                // Always return a generic 500 error
-                ErrorSanitizer::create_generic_error_response(
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Internal server error",
-                    Some("INTERNAL_ERROR"),
-                )
-            }
             StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
                 // For client errors, preserve some details but sanitize sensitive information
-                Self::sanitize_client_error(response, uri, method, config).await
+                Self::sanitize_client_error(response, uri, method, request_id, config).await
             }
-            StatusCode::UNAUTHORIZED => ErrorSanitizer::create_generic_error_response(
-                StatusCode::UNAUTHORIZED,
-                "Authentication required",
-                Some("AUTHENTICATION_REQUIRED"),
-            ),
-            StatusCode::FORBIDDEN => ErrorSanitizer::create_generic_error_response(
-                StatusCode::FORBIDDEN,
-                "Access denied",
-                Some("ACCESS_DENIED"),
-            ),
-            StatusCode::NOT_FOUND => ErrorSanitizer::create_generic_error_response(
-                StatusCode::NOT_FOUND,
-                "Resource not found",
-                Some("NOT_FOUND"),
-            ),
-            _ => response, // For other status codes, return as-is
+            // Other client errors say what the client got wrong; keep the
+            // body and add the request ID
+            _ => Self::edit_json_body(response, request_id, |_| {}).await,
+        }
+    }
+
+    /// Give a replacement error response the original response's headers,
+    /// except those describing the replaced body
+    fn with_headers(mut response: Response, mut headers: HeaderMap) -> Response {
+        headers.remove(header::CONTENT_LENGTH);
+        headers.remove(header::CONTENT_TYPE);
+        for (name, value) in &headers {
+            response.headers_mut().append(name, value.clone());
+        }
+        response
+    }
+
+    /// Keep the handler's own error body, with the request ID and, for
+    /// server errors, a snippet of the backtrace captured where it was raised
+    ///
+    /// Bodies that are not JSON objects are returned as the `error` message.
+    async fn detailed_error_response(response: Response, request_id: Option<&str>) -> Response {
+        let backtrace = response
+            .extensions()
+            .get::<ErrorBacktrace>()
+            .filter(|backtrace| backtrace.0.status() == BacktraceStatus::Captured)
+            .map(|backtrace| {
+                backtrace
+                    .0
+                    .to_string()
+                    .lines()
+                    .take(MAX_BACKTRACE_LINES)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
+
+        let (mut parts, body) = response.into_parts();
+        let Ok(bytes) = axum::body::to_bytes(body, MAX_CLIENT_ERROR_BODY_BYTES).await else {
+            return Self::with_headers(
+                Self::unreadable_body_response(parts.status, request_id),
+                parts.headers,
+            );
+        };
+        let mut body = match serde_json::from_slice::<Value>(&bytes) {
+            Ok(Value::Object(body)) => body,
+            _ => Map::from_iter([(
+                "error".to_string(),
+                String::from_utf8_lossy(&bytes).into_owned().into(),
+            )]),
+        };
+        if let Some(request_id) = request_id {
+            body.insert("request_id".to_string(), request_id.into());
+        }
+        if let Some(backtrace) = backtrace {
+            body.insert("backtrace".to_string(), backtrace.into());
         }
+
+        parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+        parts.headers.insert(
+            axum::http::header::CONTENT_TYPE,
+            axum::http::HeaderValue::from_static("application/json"),
+        );
+        Response::from_parts(
+            parts,
+            axum::body::Body::from(Value::Object(body).to_string()),
+        )
     }
 
+    /// Apply `edit` to a JSON object error body and add the request ID;
+    /// other bodies are returned unchanged
+    async fn edit_json_body(
+        response: Response,
+        request_id: Option<&str>,
+        edit: impl FnOnce(&mut Map<String, Value>),
+    ) -> Response {
+        let (mut parts, body) = response.into_parts();
+        let Ok(bytes) = axum::body::to_bytes(body, MAX_CLIENT_ERROR_BODY_BYTES).await else {
+            return Self::with_headers(
+                Self::unreadable_body_response(parts.status, request_id),
+                parts.headers,
+            );
+        };
+        let Ok(Value::Object(mut value)) = serde_json::from_slice::<Value>(&bytes) else {
+            return Response::from_parts(parts, axum::body::Body::from(bytes));
+        };
+
+        edit(&mut value);
+        if let Some(request_id) = request_id {
+            value.insert("request_id".to_string(), request_id.into());
+        }
+
+        parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+        Response::from_parts(
+            parts,
+            axum::body::Body::from(Value::Object(value).to_string()),
+        )
+    }
+
+    /// Generic response for an error body too large (or broken) to inspect
+    fn unreadable_body_response(status: StatusCode, request_id: Option<&str>) -> Response {
+        ErrorSanitizer::create_generic_error_response_with_request_id(
+            status,
+            status.canonical_reason().unwrap_or("Error"),
+            None,
+            request_id,
+        )
+    }
+
+    /// Keep client error bodies, scrubbed of sensitive details
+    ///
+    /// Validation errors keep their per-field list and stable code, with
+    /// each message sanitized. Field names come from our own request types.
+    /// Only messages leaking internals are replaced, so ones naming the key
+    /// or path at fault survive.
     async fn sanitize_client_error(
         response: Response,
         _uri: &axum::http::Uri,
         _method: &axum::http::Method,
+        request_id: Option<&str>,
         _config: &ErrorHandlingConfig,
     ) -> Response {
-        // For now, just return the response as-is
-        // In the future, we could parse the body and sanitize sensitive information
-        response
+        Self::edit_json_body(response, request_id, |value| {
+            if let Some(message) = value.get_mut("error") {
+                if let Some(text) = message.as_str() {
+                    *message = ErrorSanitizer::sanitize_client_message(text).into();
+                }
+            }
+            if let Some(Value::Array(errors)) = value.get_mut("errors") {
+                for error in errors.iter_mut() {
+                    if let Some(message) = error.get_mut("message") {
+                        let text = message.as_str().unwrap_or_default();
+                        *message = ErrorSanitizer::sanitize_client_message(text).into();
+                    }
+                }
+                value.insert("code".to_string(), VALIDATION_ERROR_CODE.into());
+            }
+        })
+        .await
     }
 }
 
 /// Create error handling middleware
-pub fn create_error_handling_middleware() -> ErrorHandlingLayer {
-    ErrorHandlingLayer
+pub fn create_error_handling_middleware(config: ErrorHandlingConfig) -> ErrorHandlingLayer {
+    ErrorHandlingLayer::new(config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::errors::ApiError;
+    use crate::api::middleware::error_handling::ErrorDetail;
+    use crate::api::middleware::validation::FieldError;
+    use axum::response::IntoResponse;
+
+    async fn sanitized_client_error(response: Response) -> serde_json::Value {
+        let response = ErrorHandlingService::<()>::sanitize_client_error(
+            response,
+            &axum::http::Uri::from_static("/v1/objects/search"),
+            &axum::http::Method::POST,
+            None,
+            &ErrorHandlingConfig::default(),
+        )
+        .await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_keep_field_details() {
+        let response = ApiError::validation(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            vec![
+                FieldError::new("namespace", "must not be empty"),
+                FieldError::new("limit", "Invalid value"),
+            ],
+        )
+        .into_response();
+
+        let body = sanitized_client_error(response).await;
+
+        assert_eq!(body["code"], "VALIDATION_ERROR");
+        assert_eq!(body["error"], "Validation failed");
+        assert_eq!(
+            body["errors"],
+            serde_json::json!([
+                {"field": "namespace", "message": "must not be empty"},
+                {"field": "limit", "message": "Invalid value"},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_are_sanitized() {
+        let response = ApiError::validation(
+            StatusCode::BAD_REQUEST,
+            vec![
+                FieldError::new("name", "sql syntax error near 'DROP'"),
+                FieldError::new("description", "must be at most 500 characters"),
+            ],
+        )
+        .into_response();
+
+        let body = sanitized_client_error(response).await;
+
+        assert_eq!(
+            body["errors"],
+            serde_json::json!([
+                {"field": "name", "message": "An error occurred"},
+                {"field": "description", "message": "must be at most 500 characters"},
+            ])
+        );
+    }
+
+    async fn error_body(response: Response, detail: ErrorDetail) -> serde_json::Value {
+        let response = ErrorHandlingService::<()>::sanitize_error_response(
+            response,
+            &axum::http::Uri::from_static("/v1/objects"),
+            &axum::http::Method::GET,
+            Some("req-123"),
+            &ErrorHandlingConfig::new().with_detail(detail),
+        )
+        .await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_full_detail_keeps_underlying_message() {
+        let response = ApiError::internal_error("connection to database refused").into_response();
+
+        let body = error_body(response, ErrorDetail::Full).await;
+
+        assert_eq!(body["error"], "connection to database refused");
+        assert_eq!(body["request_id"], "req-123");
+    }
+
+    #[tokio::test]
+    async fn test_minimal_detail_hides_underlying_message() {
+        let response = ApiError::internal_error("connection to database refused").into_response();
+
+        let body = error_body(response, ErrorDetail::Minimal).await;
+
+        assert_eq!(body["error"], "Internal server error");
+        assert_eq!(body["request_id"], "req-123");
+        assert!(body.get("backtrace").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_minimal_detail_adds_request_id_to_client_errors() {
+        let response = ApiError::bad_request("limit must be positive").into_response();
+
+        let body = error_body(response, ErrorDetail::Minimal).await;
+
+        assert_eq!(body["error"], "limit must be positive");
+        assert_eq!(body["request_id"], "req-123");
+    }
+
+    #[tokio::test]
+    async fn test_bad_request_naming_key_is_kept() {
+        let response =
+            ApiError::bad_request("Invalid key 'reports/2024.txt': must start with 'albums/'")
+                .into_response();
+
+        let body = sanitized_client_error(response).await;
+
+        assert_eq!(
+            body["error"],
+            "Invalid key 'reports/2024.txt': must start with 'albums/'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_minimal_detail_keeps_client_error_messages() {
+        let response = ApiError::not_found("Object not found: reports/2024.txt").into_response();
+
+        let body = error_body(response, ErrorDetail::Minimal).await;
+
+        assert_eq!(body["error"], "Object not found: reports/2024.txt");
+        assert_eq!(body["request_id"], "req-123");
+    }
+
+    #[tokio::test]
+    async fn test_minimal_detail_keeps_server_error_headers() {
+        let mut response = ApiError::service_unavailable("pool timed out on db-1")
+            .with_retry_after(5)
+            .into_response();
+        response
+            .headers_mut()
+            .insert("deprecation", "true".parse().unwrap());
+
+        let response = ErrorHandlingService::<()>::sanitize_error_response(
+            response,
+            &axum::http::Uri::from_static("/v1/objects"),
+            &axum::http::Method::GET,
+            Some("req-123"),
+            &ErrorHandlingConfig::new().with_detail(ErrorDetail::Minimal),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()["retry-after"], "5");
+        assert_eq!(response.headers()["deprecation"], "true");
+        assert_eq!(response.headers()["content-type"], "application/json");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Service Unavailable");
+    }
+
+    #[test]
+    fn test_error_detail_parse() {
+        assert_eq!(ErrorDetail::parse("minimal"), Ok(ErrorDetail::Minimal));
+        assert_eq!(ErrorDetail::parse(" FULL "), Ok(ErrorDetail::Full));
+        assert!(ErrorDetail::parse("verbose").is_err());
+    }
 
     #[test]
     fn test_error_handling_config() {
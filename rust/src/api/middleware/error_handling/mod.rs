@@ -15,7 +15,7 @@ pub mod sanitizers;
 pub mod utils;
 
 // Re-export main types for convenience
-pub use config::ErrorHandlingConfig;
+pub use config::{ErrorDetail, ErrorHandlingConfig};
 pub use middleware::{create_error_handling_middleware, ErrorHandlingLayer};
 pub use sanitizers::ErrorSanitizer;
 pub use utils::{DatabaseErrorUtils, ErrorUtils};
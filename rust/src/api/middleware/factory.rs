@@ -9,7 +9,7 @@ use std::sync::Arc;
 use super::audit_loggers::DatabaseAuditLogger;
 use super::audit_middleware::AuditMiddleware;
 use super::config::MiddlewareConfig;
-use super::{auth, cors::create_cors_layer_for_environment, metrics};
+use super::{auth, metrics};
 
 /// Cached default middleware configurations for performance
 static DEFAULT_MIDDLEWARE_CONFIG: Lazy<MiddlewareConfig> = Lazy::new(MiddlewareConfig::default);
@@ -31,7 +31,7 @@ impl MiddlewareFactory {
 
     /// Create CORS layer for the application
     pub fn create_cors_layer(&self) -> tower_http::cors::CorsLayer {
-        create_cors_layer_for_environment()
+        self.config.cors.layer()
     }
 
     /// Create auth layer for the application
@@ -58,6 +58,31 @@ impl MiddlewareFactory {
         super::rate_limiting::create_rate_limit_middleware(self.config.rate_limiting.clone())
     }
 
+    /// Create rate limit layer with per-tenant limits from `tenant_limit_provider`
+    pub fn create_tiered_rate_limit_layer(
+        &self,
+        tenant_limit_provider: Arc<dyn crate::application::ports::TenantLimitProvider>,
+    ) -> super::rate_limiting::RateLimitLayer {
+        super::rate_limiting::create_tiered_rate_limit_middleware(
+            self.config.rate_limiting.clone(),
+            tenant_limit_provider,
+        )
+    }
+
+    /// Create error handling layer for the application
+    pub fn create_error_handling_layer(&self) -> super::error_handling::ErrorHandlingLayer {
+        super::error_handling::create_error_handling_middleware(self.config.error_handling.clone())
+    }
+
+    /// Create input sanitization layer at the configured level
+    pub fn create_input_sanitization_layer(
+        &self,
+    ) -> super::input_sanitization::middleware::InputSanitizationLayer {
+        super::input_sanitization::middleware::InputSanitizationLayer::new(
+            self.config.input_sanitization.clone(),
+        )
+    }
+
     /// Create audit layer for the application
     pub fn create_audit_layer(
         &self,
@@ -0,0 +1,203 @@
+//! Limits on request headers and URL length
+//!
+//! Body size is bounded by the size limit middleware; this bounds the rest
+//! of the request head. A request with too many headers, or headers too
+//! large in total, is answered with `431 Request Header Fields Too Large`,
+//! and one whose path and query are too long with `414 URI Too Long`, before
+//! any routing, authentication or handler work is done for it.
+
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::ApiError;
+
+/// Default maximum number of request headers
+pub const DEFAULT_MAX_HEADER_COUNT: usize = 100;
+
+/// Default maximum size of all request header names and values together
+pub const DEFAULT_MAX_HEADER_BYTES: usize = 32 * 1024;
+
+/// Default maximum length of the request path and query
+pub const DEFAULT_MAX_URL_LENGTH: usize = 8 * 1024;
+
+/// Request head limits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderLimitConfig {
+    pub max_header_count: usize,
+    /// Sum of the lengths of every header name and value
+    pub max_header_bytes: usize,
+    /// Length of the path and query string
+    pub max_url_length: usize,
+}
+
+impl Default for HeaderLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_header_count: DEFAULT_MAX_HEADER_COUNT,
+            max_header_bytes: DEFAULT_MAX_HEADER_BYTES,
+            max_url_length: DEFAULT_MAX_URL_LENGTH,
+        }
+    }
+}
+
+impl HeaderLimitConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of request headers
+    pub fn with_max_header_count(mut self, max_header_count: usize) -> Self {
+        self.max_header_count = max_header_count;
+        self
+    }
+
+    /// Set the maximum size of all request headers together
+    pub fn with_max_header_bytes(mut self, max_header_bytes: usize) -> Self {
+        self.max_header_bytes = max_header_bytes;
+        self
+    }
+
+    /// Set the maximum length of the request path and query
+    pub fn with_max_url_length(mut self, max_url_length: usize) -> Self {
+        self.max_url_length = max_url_length;
+        self
+    }
+}
+
+/// Reject requests whose head exceeds the configured limits
+pub async fn header_limits_middleware(
+    config: &HeaderLimitConfig,
+    request: Request,
+    next: Next,
+) -> Response {
+    let url_length = request
+        .uri()
+        .path_and_query()
+        .map_or(0, |path_and_query| path_and_query.as_str().len());
+    if url_length > config.max_url_length {
+        tracing::warn!(
+            url_length,
+            max_url_length = config.max_url_length,
+            "Rejected request with an overlong URL"
+        );
+        return ApiError::new(
+            StatusCode::URI_TOO_LONG,
+            format!("URL exceeds {} bytes", config.max_url_length),
+        )
+        .into_response();
+    }
+
+    let headers = request.headers();
+    if headers.len() > config.max_header_count {
+        tracing::warn!(
+            header_count = headers.len(),
+            max_header_count = config.max_header_count,
+            "Rejected request with too many headers"
+        );
+        return ApiError::new(
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            format!("More than {} request headers", config.max_header_count),
+        )
+        .into_response();
+    }
+
+    let header_bytes: usize = headers
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len())
+        .sum();
+    if header_bytes > config.max_header_bytes {
+        tracing::warn!(
+            header_bytes,
+            max_header_bytes = config.max_header_bytes,
+            "Rejected request with oversized headers"
+        );
+        return ApiError::new(
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            format!("Request headers exceed {} bytes", config.max_header_bytes),
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        let config = Arc::new(
+            HeaderLimitConfig::new()
+                .with_max_header_count(4)
+                .with_max_header_bytes(64)
+                .with_max_url_length(32),
+        );
+        Router::new()
+            .route("/objects", get(|| async { "data" }))
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                async move { header_limits_middleware(&config, req, next).await }
+            }))
+    }
+
+    async fn send(request: axum::http::Request<Body>) -> StatusCode {
+        app().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_request_within_limits_passes() {
+        let request = Request::builder()
+            .uri("/objects?limit=10")
+            .header("x-api-key", "secret")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(send(request).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_long_url_is_rejected() {
+        let request = Request::builder()
+            .uri(format!("/objects?prefix={}", "a".repeat(32)))
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(send(request).await, StatusCode::URI_TOO_LONG);
+    }
+
+    #[tokio::test]
+    async fn test_too_many_headers_are_rejected() {
+        let mut request = Request::builder().uri("/objects");
+        for i in 0..5 {
+            request = request.header(format!("x-custom-{i}"), "1");
+        }
+
+        assert_eq!(
+            send(request.body(Body::empty()).unwrap()).await,
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_header_is_rejected() {
+        let request = Request::builder()
+            .uri("/objects")
+            .header("authorization", format!("Bearer {}", "x".repeat(64)))
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(
+            send(request).await,
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+        );
+    }
+}
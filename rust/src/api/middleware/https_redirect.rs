@@ -0,0 +1,242 @@
+//! HTTPS enforcement
+//!
+//! Deployments that serve HTTP directly can redirect plaintext requests to
+//! HTTPS and advertise HSTS. Deployments behind a TLS-terminating proxy must
+//! leave this off (the default) unless the proxy sets `X-Forwarded-Proto`,
+//! otherwise every request would redirect back to itself.
+
+use axum::{
+    extract::Request,
+    http::{header, uri::Authority, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+
+/// Header set by reverse proxies to report the client-facing scheme
+pub const FORWARDED_PROTO_HEADER: &str = "x-forwarded-proto";
+
+/// HTTPS redirect configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpsRedirectConfig {
+    /// Redirect plaintext requests with 308 and send HSTS on HTTPS responses
+    pub enabled: bool,
+    /// HSTS max age in seconds
+    pub hsts_max_age: u64,
+    /// Include subdomains in HSTS
+    pub hsts_include_subdomains: bool,
+}
+
+impl Default for HttpsRedirectConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hsts_max_age: 31_536_000, // 1 year
+            hsts_include_subdomains: true,
+        }
+    }
+}
+
+impl HttpsRedirectConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable HTTPS enforcement
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    fn hsts_value(&self) -> String {
+        let mut value = format!("max-age={}", self.hsts_max_age);
+        if self.hsts_include_subdomains {
+            value.push_str("; includeSubDomains");
+        }
+        value
+    }
+}
+
+/// Whether the request reached us over TLS
+///
+/// `X-Forwarded-Proto` wins when present (first value of a proxy chain);
+/// otherwise the scheme of the request URI is used.
+fn is_secure(request: &Request) -> bool {
+    if let Some(proto) = request
+        .headers()
+        .get(FORWARDED_PROTO_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        return proto
+            .split(',')
+            .next()
+            .map(|p| p.trim().eq_ignore_ascii_case("https"))
+            .unwrap_or(false);
+    }
+
+    request.uri().scheme_str() == Some("https")
+}
+
+fn https_location(request: &Request) -> Option<String> {
+    let authority = match request
+        .headers()
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(host) => host.parse::<Authority>().ok()?,
+        None => request.uri().authority()?.clone(),
+    };
+    let path = request
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+
+    // Drop an explicit plaintext port; the HTTPS default applies. IPv6
+    // hosts keep their brackets
+    Some(format!("https://{}{path}", authority.host()))
+}
+
+/// HTTPS redirect middleware
+pub async fn https_redirect_middleware(
+    config: &HttpsRedirectConfig,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !config.enabled {
+        return next.run(request).await;
+    }
+
+    if !is_secure(&request) {
+        return match https_location(&request)
+            .and_then(|location| HeaderValue::from_str(&location).ok())
+        {
+            Some(location) => (
+                StatusCode::PERMANENT_REDIRECT,
+                [(header::LOCATION, location)],
+            )
+                .into_response(),
+            None => (StatusCode::BAD_REQUEST, "Missing or invalid Host header").into_response(),
+        };
+    }
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&config.hsts_value()) {
+        response
+            .headers_mut()
+            .insert(header::STRICT_TRANSPORT_SECURITY, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn app(config: HttpsRedirectConfig) -> Router {
+        let config = Arc::new(config);
+        Router::new()
+            .route("/v1/objects", get(|| async { "ok" }))
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                async move { https_redirect_middleware(&config, req, next).await }
+            }))
+    }
+
+    fn plaintext_request() -> Request {
+        Request::builder()
+            .uri("/v1/objects?limit=5")
+            .header(header::HOST, "storage.example.com:8080")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_redirect_enabled_redirects_http() {
+        let response = app(HttpsRedirectConfig::new().with_enabled(true))
+            .oneshot(plaintext_request())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            "https://storage.example.com/v1/objects?limit=5"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_enabled_keeps_ipv6_host() {
+        let response = app(HttpsRedirectConfig::new().with_enabled(true))
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/objects")
+                    .header(header::HOST, "[2001:db8::1]:8080")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            "https://[2001:db8::1]/v1/objects"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_enabled_serves_forwarded_https_with_hsts() {
+        let response = app(HttpsRedirectConfig::new().with_enabled(true))
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/objects")
+                    .header(header::HOST, "storage.example.com")
+                    .header(FORWARDED_PROTO_HEADER, "https")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::STRICT_TRANSPORT_SECURITY],
+            "max-age=31536000; includeSubDomains"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_enabled_honours_forwarded_http() {
+        let mut request = plaintext_request();
+        request.headers_mut().insert(
+            FORWARDED_PROTO_HEADER,
+            HeaderValue::from_static("http, https"),
+        );
+
+        let response = app(HttpsRedirectConfig::new().with_enabled(true))
+            .oneshot(request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_disabled_serves_http_directly() {
+        let response = app(HttpsRedirectConfig::default())
+            .oneshot(plaintext_request())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .headers()
+            .get(header::STRICT_TRANSPORT_SECURITY)
+            .is_none());
+    }
+}
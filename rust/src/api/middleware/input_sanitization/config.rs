@@ -1,9 +1,115 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+use crate::application::content_policy::{ContentPolicy, DEFAULT_BLOCKED_UPLOAD_TYPES};
+
+/// How strictly inputs are sanitized (`SANITIZATION_LEVEL`)
+///
+/// What each level does with an input, where "rejected" means the
+/// `Validator` refuses the string and "stripped" that `Sanitizer` drops the
+/// characters:
+///
+/// | Input | `strict` | `standard` | `lenient` |
+/// |-------|----------|------------|-----------|
+/// | `<`, `>` | rejected; stripped by the sanitizer | HTML-encoded | allowed |
+/// | `"`, `'`, `` ` `` | rejected; stripped by the sanitizer | `"`, `'` HTML-encoded | allowed |
+/// | `&` | HTML-encoded | HTML-encoded | allowed |
+/// | `/`, `\` | rejected; stripped by the sanitizer | allowed | allowed |
+/// | `../`, `..\` | rejected | rejected | rejected |
+/// | `<script`, `javascript:`, `vbscript:`, `eval(` | rejected | rejected | rejected |
+/// | `data:`, `onload=`, `onerror=` | rejected | rejected | allowed |
+/// | `%2e%2e`, `%3c`, `&#` (encoded forms) | rejected | allowed | allowed |
+/// | `.` in identifiers | rejected | rejected | allowed |
+///
+/// NUL bytes and control characters other than `\n`, `\r` and `\t` are
+/// removed at every level (`remove_null_bytes`, `normalize_unicode`).
+/// Custom blocked patterns apply on top of the level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SanitizationLevel {
+    /// Untrusted public clients: anything markup- or path-like is refused
+    Strict,
+    /// Markup is encoded, known attack patterns are refused
+    #[default]
+    Standard,
+    /// Trusted internal clients: only script and traversal patterns are refused
+    Lenient,
+}
+
+impl SanitizationLevel {
+    /// Characters a validated string may not contain
+    pub fn rejected_chars(self) -> &'static [char] {
+        match self {
+            SanitizationLevel::Strict => &['<', '>', '"', '\'', '`', '/', '\\'],
+            SanitizationLevel::Standard | SanitizationLevel::Lenient => &[],
+        }
+    }
+
+    /// Whether the sanitizer HTML-encodes `<`, `>`, `&`, `"` and `'`
+    pub fn encodes_html(self) -> bool {
+        self != SanitizationLevel::Lenient
+    }
+
+    /// Patterns refused at this level
+    pub fn blocked_patterns(self) -> HashSet<String> {
+        let mut patterns = vec![
+            "<script",
+            "javascript:",
+            "vbscript:",
+            "eval(",
+            "../",
+            "..\\",
+        ];
+        if self != SanitizationLevel::Lenient {
+            patterns.extend(["data:", "onload=", "onerror="]);
+        }
+        if self == SanitizationLevel::Strict {
+            patterns.extend(["%2e%2e", "%3c", "&#"]);
+        }
+        patterns.into_iter().map(String::from).collect()
+    }
+
+    /// Allowed characters for identifiers (regex)
+    pub fn identifier_regex(self) -> &'static str {
+        match self {
+            SanitizationLevel::Lenient => r"^[a-zA-Z0-9_.-]+$",
+            SanitizationLevel::Strict | SanitizationLevel::Standard => r"^[a-zA-Z0-9_-]+$",
+        }
+    }
+}
+
+impl std::fmt::Display for SanitizationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SanitizationLevel::Strict => write!(f, "strict"),
+            SanitizationLevel::Standard => write!(f, "standard"),
+            SanitizationLevel::Lenient => write!(f, "lenient"),
+        }
+    }
+}
+
+impl std::str::FromStr for SanitizationLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "strict" => Ok(SanitizationLevel::Strict),
+            "standard" => Ok(SanitizationLevel::Standard),
+            "lenient" => Ok(SanitizationLevel::Lenient),
+            _ => Err(format!(
+                "Unknown sanitization level: {} (expected strict, standard or lenient)",
+                s
+            )),
+        }
+    }
+}
+
 /// Input sanitization configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InputSanitizationConfig {
+    /// Strictness level the defaults below were taken from
+    #[serde(default)]
+    pub level: SanitizationLevel,
     /// Maximum string length for text inputs
     pub max_string_length: usize,
     /// Maximum depth for nested structures
@@ -16,36 +122,50 @@ pub struct InputSanitizationConfig {
     pub blocked_patterns: HashSet<String>,
     /// Allowed characters for identifiers (regex)
     pub allowed_identifier_chars: String,
+    /// Content types and extensions accepted for file uploads
+    #[serde(skip, default = "default_content_policy")]
+    pub content_policy: ContentPolicy,
+}
+
+fn default_content_policy() -> ContentPolicy {
+    ContentPolicy::parse("", DEFAULT_BLOCKED_UPLOAD_TYPES)
+        .expect("Invalid default upload blocklist")
 }
 
 impl Default for InputSanitizationConfig {
     fn default() -> Self {
-        let mut blocked_patterns = HashSet::new();
-        blocked_patterns.insert("<script".to_string());
-        blocked_patterns.insert("javascript:".to_string());
-        blocked_patterns.insert("vbscript:".to_string());
-        blocked_patterns.insert("data:".to_string());
-        blocked_patterns.insert("onload=".to_string());
-        blocked_patterns.insert("onerror=".to_string());
-        blocked_patterns.insert("eval(".to_string());
-        blocked_patterns.insert("../".to_string());
-        blocked_patterns.insert("..\\".to_string());
+        Self::for_level(SanitizationLevel::default())
+    }
+}
+
+impl InputSanitizationConfig {
+    /// Create a new config with custom settings
+    pub fn new() -> Self {
+        Self::default()
+    }
 
+    /// Create a config with the blocked patterns and identifier rules of
+    /// `level`
+    pub fn for_level(level: SanitizationLevel) -> Self {
         Self {
+            level,
             max_string_length: 10000, // 10KB max for text inputs
             max_depth: 10,
             remove_null_bytes: true,
             normalize_unicode: true,
-            blocked_patterns,
-            allowed_identifier_chars: r"^[a-zA-Z0-9_-]+$".to_string(),
+            blocked_patterns: level.blocked_patterns(),
+            allowed_identifier_chars: level.identifier_regex().to_string(),
+            content_policy: default_content_policy(),
         }
     }
-}
 
-impl InputSanitizationConfig {
-    /// Create a new config with custom settings
-    pub fn new() -> Self {
-        Self::default()
+    /// Switch to `level`, replacing the blocked patterns and identifier regex
+    /// with its own; add custom patterns afterwards
+    pub fn with_level(mut self, level: SanitizationLevel) -> Self {
+        self.level = level;
+        self.blocked_patterns = level.blocked_patterns();
+        self.allowed_identifier_chars = level.identifier_regex().to_string();
+        self
     }
 
     /// Set maximum string length
@@ -78,9 +198,61 @@ impl InputSanitizationConfig {
         self
     }
 
+    /// Set the content types and extensions accepted for file uploads
+    pub fn with_content_policy(mut self, policy: ContentPolicy) -> Self {
+        self.content_policy = policy;
+        self
+    }
+
     /// Set allowed identifier regex
     pub fn with_identifier_regex(mut self, regex: String) -> Self {
         self.allowed_identifier_chars = regex;
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_sanitization_level_round_trip() {
+        for level in [
+            SanitizationLevel::Strict,
+            SanitizationLevel::Standard,
+            SanitizationLevel::Lenient,
+        ] {
+            assert_eq!(
+                SanitizationLevel::from_str(&level.to_string()).unwrap(),
+                level
+            );
+        }
+        assert!(SanitizationLevel::from_str("paranoid").is_err());
+    }
+
+    #[test]
+    fn test_default_is_standard_level() {
+        let config = InputSanitizationConfig::default();
+
+        assert_eq!(config.level, SanitizationLevel::Standard);
+        assert_eq!(
+            config,
+            InputSanitizationConfig::for_level(SanitizationLevel::Standard)
+        );
+        assert_eq!(config.blocked_patterns.len(), 9);
+    }
+
+    #[test]
+    fn test_with_level_replaces_level_rules() {
+        let config = InputSanitizationConfig::new()
+            .with_blocked_pattern("drop table".to_string())
+            .with_level(SanitizationLevel::Lenient)
+            .with_blocked_pattern("sleep(".to_string());
+
+        assert_eq!(config.allowed_identifier_chars, r"^[a-zA-Z0-9_.-]+$");
+        assert!(config.blocked_patterns.contains("sleep("));
+        assert!(!config.blocked_patterns.contains("drop table"));
+        assert!(!config.blocked_patterns.contains("onerror="));
+    }
+}
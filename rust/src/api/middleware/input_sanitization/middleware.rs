@@ -10,6 +10,7 @@ static DEFAULT_CONFIG: Lazy<std::sync::Arc<InputSanitizationConfig>> =
     Lazy::new(|| std::sync::Arc::new(InputSanitizationConfig::default()));
 
 use super::config::InputSanitizationConfig;
+use super::validators::Validator;
 
 /// Error type for input sanitization failures
 #[derive(Debug)]
@@ -70,7 +71,6 @@ where
 #[derive(Clone)]
 pub struct InputSanitizationService<S> {
     inner: S,
-    #[allow(dead_code)]
     config: std::sync::Arc<InputSanitizationConfig>,
 }
 
@@ -81,7 +81,9 @@ where
 {
     type Response = Response;
     type Error = S::Error;
-    type Future = S::Future;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
 
     fn poll_ready(
         &mut self,
@@ -91,9 +93,32 @@ where
     }
 
     fn call(&mut self, request: Request) -> Self::Future {
-        // For now, just pass through - sanitization logic can be added later
-        self.inner.call(request)
+        if let Err(error) = check_query(&request, &self.config) {
+            return Box::pin(async move { Ok(error.into_response()) });
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+/// Validate every query parameter against the configured level
+///
+/// Malformed query strings pass through so the handler's extractor can
+/// report them.
+fn check_query(
+    request: &Request,
+    config: &InputSanitizationConfig,
+) -> Result<(), InputSanitizationError> {
+    let Ok(axum::extract::Query(params)) =
+        axum::extract::Query::<Vec<(String, String)>>::try_from_uri(request.uri())
+    else {
+        return Ok(());
+    };
+    for (name, value) in &params {
+        Validator::validate_and_sanitize_string(name, "query parameter", None, config)
+            .and_then(|_| Validator::validate_and_sanitize_string(value, name, None, config))
+            .map_err(InputSanitizationError::InvalidUri)?;
     }
+    Ok(())
 }
 
 pub fn create_input_sanitization_middleware() -> InputSanitizationLayer {
@@ -10,7 +10,7 @@ pub mod sanitizers;
 pub mod validators;
 
 // Re-export main types for convenience
-pub use config::InputSanitizationConfig;
+pub use config::{InputSanitizationConfig, SanitizationLevel};
 pub use middleware::create_input_sanitization_middleware;
 pub use sanitizers::Sanitizer;
 pub use validators::Validator;
@@ -27,12 +27,20 @@ impl Sanitizer {
                 .collect();
         }
 
+        // Strict drops the characters it refuses outright
+        let rejected = config.level.rejected_chars();
+        if !rejected.is_empty() {
+            result = result.chars().filter(|c| !rejected.contains(c)).collect();
+        }
+
         // HTML entity encoding for common dangerous characters
-        result = result.replace('<', "&lt;");
-        result = result.replace('>', "&gt;");
-        result = result.replace('&', "&amp;");
-        result = result.replace('"', "&quot;");
-        result = result.replace('\'', "&#x27;");
+        if config.level.encodes_html() {
+            result = result.replace('<', "&lt;");
+            result = result.replace('>', "&gt;");
+            result = result.replace('&', "&amp;");
+            result = result.replace('"', "&quot;");
+            result = result.replace('\'', "&#x27;");
+        }
 
         result
     }
@@ -58,3 +66,37 @@ impl Sanitizer {
             .replace('\0', "") // Remove null bytes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::middleware::input_sanitization::SanitizationLevel;
+
+    fn sanitize(input: &str, level: SanitizationLevel) -> String {
+        Sanitizer::sanitize_string(input, &InputSanitizationConfig::for_level(level))
+    }
+
+    #[test]
+    fn test_strict_strips_markup_quotes_and_separators() {
+        assert_eq!(
+            sanitize("<b>\"hi\"</b> `a'/b\\c", SanitizationLevel::Strict),
+            "bhib abc"
+        );
+    }
+
+    #[test]
+    fn test_standard_encodes_quotes_and_keeps_separators() {
+        assert_eq!(
+            sanitize("say \"hi\" 'a/b\\c'", SanitizationLevel::Standard),
+            "say &quot;hi&quot; &#x27;a/b\\c&#x27;"
+        );
+    }
+
+    #[test]
+    fn test_lenient_keeps_markup() {
+        assert_eq!(
+            sanitize("<b>\"hi\"</b> a/b\0", SanitizationLevel::Lenient),
+            "<b>\"hi\"</b> a/b"
+        );
+    }
+}
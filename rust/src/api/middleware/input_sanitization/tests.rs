@@ -65,6 +65,7 @@ mod sanitizers_tests {
 
 mod validators_tests {
     use super::super::{validators::Validator, config::InputSanitizationConfig};
+    use crate::application::content_policy::ContentPolicy;
 
     #[test]
     fn test_identifier_validation() {
@@ -103,6 +104,18 @@ mod validators_tests {
         assert!(Validator::validate_file_upload("<script>evil.js", Some("application/javascript"), 1024, &config).is_err());
     }
 
+    #[test]
+    fn test_file_upload_content_policy() {
+        let policy = ContentPolicy::parse("*=image/*,.png", "").unwrap();
+        let config = InputSanitizationConfig::default()
+            .with_identifier_regex(r"^[a-zA-Z0-9_.-]+$".to_string())
+            .with_content_policy(policy);
+
+        assert!(Validator::validate_file_upload("cat.png", Some("image/png"), 1024, &config).is_ok());
+        assert!(Validator::validate_file_upload("cat.png", Some("text/html"), 1024, &config).is_err());
+        assert!(Validator::validate_file_upload("cat.svg", Some("image/svg+xml"), 1024, &config).is_err());
+    }
+
     #[test]
     fn test_string_validation_and_sanitization() {
         let config = InputSanitizationConfig::default();
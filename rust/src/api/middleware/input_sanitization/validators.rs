@@ -1,5 +1,6 @@
 use super::config::InputSanitizationConfig;
 use super::sanitizers::Sanitizer;
+use crate::application::metadata_index::ALL_NAMESPACES;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -32,6 +33,11 @@ impl Validator {
             return Err(format!("{} contains invalid content", field_name));
         }
 
+        // Check for characters the level refuses
+        if input.contains(config.level.rejected_chars()) {
+            return Err(format!("{} contains invalid characters", field_name));
+        }
+
         // Sanitize the input
         let sanitized = Sanitizer::sanitize_string(input, config);
 
@@ -95,21 +101,10 @@ impl Validator {
         // Validate filename
         Self::validate_identifier(filename, "filename", config)?;
 
-        // Check file extension (basic)
-        let blocked_extensions = [".exe", ".bat", ".cmd", ".scr", ".pif", ".com"];
-        if let Some(ext) = filename.rsplit('.').next() {
-            if blocked_extensions.contains(&format!(".{}", ext.to_lowercase()).as_str()) {
-                return Err("File type not allowed".to_string());
-            }
-        }
-
-        // Validate content type if provided
-        if let Some(ct) = content_type {
-            let blocked_types = ["application/x-executable", "application/x-msdownload"];
-            if blocked_types.contains(&ct.to_lowercase().as_str()) {
-                return Err("Content type not allowed".to_string());
-            }
-        }
+        // Check extension and content type against the upload policy
+        config
+            .content_policy
+            .check(ALL_NAMESPACES, Some(filename), content_type, None)?;
 
         // Check file size (100MB limit)
         if size > 100 * 1024 * 1024 {
@@ -135,3 +130,61 @@ impl Validator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::middleware::input_sanitization::SanitizationLevel;
+
+    fn validate(input: &str, level: SanitizationLevel) -> Result<String, String> {
+        let config = InputSanitizationConfig::for_level(level);
+        Validator::validate_and_sanitize_string(input, "field", None, &config)
+    }
+
+    fn identifier(input: &str, level: SanitizationLevel) -> Result<(), String> {
+        let config = InputSanitizationConfig::for_level(level);
+        Validator::validate_identifier(input, "field", &config)
+    }
+
+    #[test]
+    fn test_strict_rejects_suspicious_input() {
+        let level = SanitizationLevel::Strict;
+
+        assert_eq!(validate("plain text", level).unwrap(), "plain text");
+        assert!(validate("a/b", level).is_err());
+        assert!(validate("it's", level).is_err());
+        assert!(validate("1 > 0", level).is_err());
+        assert!(validate("%3Cscript", level).is_err());
+        assert!(validate("&#60;", level).is_err());
+        assert!(identifier("report.pdf", level).is_err());
+    }
+
+    #[test]
+    fn test_standard_rejects_attack_patterns() {
+        let level = SanitizationLevel::Standard;
+
+        assert_eq!(validate("a/b", level).unwrap(), "a/b");
+        assert_eq!(validate("%3c", level).unwrap(), "%3c");
+        assert!(validate("<img onerror=x>", level).is_err());
+        assert!(validate("data:text/html", level).is_err());
+        assert!(validate("../etc/passwd", level).is_err());
+        assert!(identifier("report.pdf", level).is_err());
+    }
+
+    #[test]
+    fn test_lenient_rejects_only_scripts_and_traversal() {
+        let level = SanitizationLevel::Lenient;
+
+        assert_eq!(
+            validate("<img onerror=x>", level).unwrap(),
+            "<img onerror=x>"
+        );
+        assert_eq!(
+            validate("it's \"quoted\"", level).unwrap(),
+            "it's \"quoted\""
+        );
+        assert!(validate("<script>alert(1)</script>", level).is_err());
+        assert!(validate("..\\windows", level).is_err());
+        assert!(identifier("report.pdf", level).is_ok());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod access_log;
+pub mod api_version;
 pub mod audit;
 pub mod audit_config;
 pub mod audit_loggers;
@@ -12,13 +14,21 @@ pub mod cors;
 pub mod csrf;
 pub mod error_handling;
 pub mod factory;
+pub mod header_limits;
 pub mod htmx;
+pub mod https_redirect;
 pub mod input_sanitization;
 pub mod metrics;
 pub mod oidc_config;
 pub mod rate_limiting;
+pub mod request_id;
+pub mod request_latency;
+pub mod request_timeout;
+pub mod response_compression;
+pub mod response_format;
 pub mod security_config;
 pub mod security_headers;
 pub mod security_headers_impl;
 pub mod size_limits;
+pub mod storage_class_headers;
 pub mod validation;
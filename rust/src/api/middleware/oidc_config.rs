@@ -1,10 +1,40 @@
+use std::str::FromStr;
+
+use jsonwebtoken::{Algorithm, Validation};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Signature algorithms accepted by default: every asymmetric algorithm with
+/// a verifying key in the JWKS. Shared-secret (HS*) algorithms are never
+/// accepted, since anyone who can read the JWKS could then forge tokens.
+pub const DEFAULT_JWT_ALGORITHMS: &str = "RS256,RS384,RS512,PS256,PS384,PS512,ES256,ES384";
+
+/// Default tolerance for clock skew on `exp` and `nbf`
+pub const DEFAULT_JWT_LEEWAY_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OidcConfig {
     pub enabled: bool,
     pub issuer_url: Option<String>,
     pub audience: Option<String>,
+    /// Key set to verify tokens with, instead of the issuer's discovered one
+    pub jwks_url: Option<String>,
+    /// Accepted signature algorithms
+    pub algorithms: Vec<Algorithm>,
+    /// Clock skew tolerance for `exp` and `nbf`
+    pub leeway_secs: u64,
+}
+
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            issuer_url: None,
+            audience: None,
+            jwks_url: None,
+            algorithms: Self::parse_algorithms(DEFAULT_JWT_ALGORITHMS).unwrap_or_default(),
+            leeway_secs: DEFAULT_JWT_LEEWAY_SECS,
+        }
+    }
 }
 
 impl OidcConfig {
@@ -13,6 +43,103 @@ impl OidcConfig {
             enabled,
             issuer_url,
             audience,
+            ..Self::default()
+        }
+    }
+
+    /// Parse a comma-separated list of asymmetric signature algorithms
+    pub fn parse_algorithms(spec: &str) -> Result<Vec<Algorithm>, String> {
+        let algorithms = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| match Algorithm::from_str(name) {
+                Ok(Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) => Err(format!(
+                    "'{name}' is a shared-secret algorithm; only asymmetric algorithms are supported"
+                )),
+                Ok(algorithm) => Ok(algorithm),
+                Err(_) => Err(format!("unknown algorithm '{name}'")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if algorithms.is_empty() {
+            return Err("at least one algorithm is required".to_string());
+        }
+        Ok(algorithms)
+    }
+
+    /// Whether bearer JWTs are verified at all
+    pub fn verifies_tokens(&self) -> bool {
+        self.enabled && (self.issuer_url.is_some() || self.jwks_url.is_some())
+    }
+
+    /// Validation rules for a token signed with `algorithm`, or `None` if the
+    /// algorithm is not accepted
+    pub fn validation(&self, algorithm: Algorithm) -> Option<Validation> {
+        if !self.algorithms.contains(&algorithm) {
+            return None;
         }
+
+        let mut validation = Validation::new(algorithm);
+        validation.leeway = self.leeway_secs;
+        validation.validate_nbf = true;
+        if let Some(iss) = &self.issuer_url {
+            validation.set_issuer(&[iss]);
+        }
+        if let Some(aud) = &self.audience {
+            validation.set_audience(&[aud]);
+        }
+        Some(validation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_algorithms() {
+        assert_eq!(
+            OidcConfig::parse_algorithms(" RS256, ES256 ").unwrap(),
+            vec![Algorithm::RS256, Algorithm::ES256]
+        );
+        assert!(OidcConfig::parse_algorithms("").is_err());
+        assert!(OidcConfig::parse_algorithms("RS256,HS256").is_err());
+        assert!(OidcConfig::parse_algorithms("none").is_err());
+    }
+
+    #[test]
+    fn test_validation_only_for_accepted_algorithms() {
+        let config = OidcConfig {
+            algorithms: vec![Algorithm::ES256],
+            ..OidcConfig::default()
+        };
+
+        assert!(config.validation(Algorithm::ES256).is_some());
+        assert!(config.validation(Algorithm::RS256).is_none());
+        assert!(config.validation(Algorithm::HS256).is_none());
+    }
+
+    #[test]
+    fn test_validation_applies_leeway_and_nbf() {
+        let config = OidcConfig {
+            leeway_secs: 30,
+            ..OidcConfig::new(true, Some("https://issuer.example".to_string()), None)
+        };
+
+        let validation = config.validation(Algorithm::RS256).unwrap();
+
+        assert_eq!(validation.leeway, 30);
+        assert!(validation.validate_nbf);
+        assert!(validation.iss.is_some());
+    }
+
+    #[test]
+    fn test_jwks_url_enables_verification_without_issuer() {
+        let mut config = OidcConfig::new(true, None, None);
+        assert!(!config.verifies_tokens());
+
+        config.jwks_url = Some("https://keys.example/jwks.json".to_string());
+        assert!(config.verifies_tokens());
     }
 }
@@ -1,4 +1,6 @@
+use async_trait::async_trait;
 use axum::{
+    body::{Body, Bytes, HttpBody},
     extract::Request,
     http::StatusCode,
     middleware::Next,
@@ -6,17 +8,24 @@ use axum::{
 };
 use dashmap::DashMap;
 use futures_util::future::BoxFuture;
+use http_body::{Frame, SizeHint};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::VecDeque,
     net::IpAddr,
-    sync::Arc,
-    task::{Context, Poll},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
     time::{Duration, Instant},
 };
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 // Note: tower_http rate limiting has changed in newer versions
 // For now, we'll implement a simple in-memory rate limiter
 
+use crate::application::ports::{RepositoryError, TenantLimitProvider};
 use crate::domain::authorization::UserContext;
 
 /// Rate limiting configuration
@@ -26,14 +35,22 @@ pub struct RateLimitConfig {
     pub unauthenticated_requests_per_minute: u32,
     /// Maximum requests per window for authenticated users
     pub authenticated_requests_per_minute: u32,
-    /// Maximum concurrent requests per user
+    /// Maximum concurrent requests per user (0 = unlimited)
     pub max_concurrent_per_user: usize,
-    /// Maximum concurrent requests per tenant
+    /// Maximum concurrent requests per tenant (0 = unlimited)
     pub max_concurrent_per_tenant: usize,
-    /// Maximum concurrent requests per IP
+    /// Maximum concurrent requests per IP (0 = unlimited)
     pub max_concurrent_per_ip: usize,
+    /// How long a request over a concurrency limit waits for a slot before
+    /// it is rejected, in milliseconds
+    #[serde(default)]
+    pub concurrency_wait_ms: u64,
     /// Rate limit window duration in seconds
     pub window_seconds: u64,
+    /// Tenant-wide limit as a multiple of the tenant's per-user limit
+    pub tenant_limit_multiplier: u32,
+    /// How long tenant tier lookups are cached, in seconds
+    pub tenant_limit_cache_ttl_seconds: u64,
 }
 
 impl Default for RateLimitConfig {
@@ -44,7 +61,10 @@ impl Default for RateLimitConfig {
             max_concurrent_per_user: 10,
             max_concurrent_per_tenant: 50,
             max_concurrent_per_ip: 25,
+            concurrency_wait_ms: 0,
             window_seconds: 60,
+            tenant_limit_multiplier: 1,
+            tenant_limit_cache_ttl_seconds: 60,
         }
     }
 }
@@ -56,6 +76,47 @@ struct RateLimitResponse {
     retry_after: Option<u64>,
 }
 
+/// Caches tenant limit lookups so the limiter does not hit the database on
+/// every request
+pub struct CachedTenantLimitProvider {
+    inner: Arc<dyn TenantLimitProvider>,
+    cache: moka::future::Cache<String, Option<u32>>,
+}
+
+impl CachedTenantLimitProvider {
+    pub fn new(inner: Arc<dyn TenantLimitProvider>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            cache: moka::future::Cache::builder()
+                .max_capacity(100_000)
+                .time_to_live(ttl)
+                .build(),
+        }
+    }
+}
+
+impl std::fmt::Debug for CachedTenantLimitProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedTenantLimitProvider")
+            .field("cached_tenants", &self.cache.entry_count())
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl TenantLimitProvider for CachedTenantLimitProvider {
+    async fn requests_per_minute(&self, tenant_id: &str) -> Result<Option<u32>, RepositoryError> {
+        if let Some(limit) = self.cache.get(tenant_id).await {
+            return Ok(limit);
+        }
+
+        // Failed lookups are not cached so the next request retries
+        let limit = self.inner.requests_per_minute(tenant_id).await?;
+        self.cache.insert(tenant_id.to_string(), limit).await;
+        Ok(limit)
+    }
+}
+
 /// Thread-safe rate limiter using DashMap
 #[derive(Debug, Clone)]
 pub struct RateLimiter {
@@ -64,6 +125,7 @@ pub struct RateLimiter {
     ip_limits: Arc<DashMap<String, (VecDeque<Instant>, Instant)>>,
     user_limits: Arc<DashMap<String, (VecDeque<Instant>, Instant)>>,
     tenant_limits: Arc<DashMap<String, (VecDeque<Instant>, Instant)>>,
+    tenant_limit_provider: Option<Arc<CachedTenantLimitProvider>>,
 }
 
 impl RateLimiter {
@@ -73,11 +135,58 @@ impl RateLimiter {
             ip_limits: Arc::new(DashMap::new()),
             user_limits: Arc::new(DashMap::new()),
             tenant_limits: Arc::new(DashMap::new()),
+            tenant_limit_provider: None,
+        }
+    }
+
+    /// Look up per-tenant limits (e.g. free vs paid tiers) instead of using
+    /// `authenticated_requests_per_minute` for everyone
+    ///
+    /// Lookups are cached for `tenant_limit_cache_ttl_seconds`.
+    pub fn with_tenant_limit_provider(mut self, provider: Arc<dyn TenantLimitProvider>) -> Self {
+        let ttl = Duration::from_secs(self.config.tenant_limit_cache_ttl_seconds);
+        self.tenant_limit_provider = Some(Arc::new(CachedTenantLimitProvider::new(provider, ttl)));
+        self
+    }
+
+    /// Requests per minute for each user of `tenant_id`
+    ///
+    /// Falls back to `authenticated_requests_per_minute` when the tenant has
+    /// no tier or the lookup fails.
+    async fn user_limit(&self, tenant_id: &str) -> u32 {
+        let default = self.config.authenticated_requests_per_minute;
+        let Some(provider) = &self.tenant_limit_provider else {
+            return default;
+        };
+
+        match provider.requests_per_minute(tenant_id).await {
+            Ok(limit) => limit.unwrap_or(default),
+            Err(e) => {
+                tracing::warn!(
+                    tenant_id,
+                    error = %e,
+                    "Tenant rate limit lookup failed, using default"
+                );
+                default
+            }
         }
     }
 
+    /// Requests per minute shared by all users of `tenant_id`
+    async fn tenant_limit(&self, tenant_id: &str) -> u32 {
+        self.user_limit(tenant_id)
+            .await
+            .saturating_mul(self.config.tenant_limit_multiplier)
+    }
+
     /// Check if a request should be rate limited
-    pub fn check_limit(&self, key: &str, limit_type: LimitType) -> Result<(), RateLimitError> {
+    ///
+    /// Tenant limits come from the tenant limit provider when one is set.
+    pub async fn check_limit(
+        &self,
+        key: &str,
+        limit_type: LimitType,
+    ) -> Result<(), RateLimitError> {
         let (max_requests, map) = match limit_type {
             LimitType::IP => (
                 self.config.unauthenticated_requests_per_minute,
@@ -87,12 +196,31 @@ impl RateLimiter {
                 self.config.authenticated_requests_per_minute,
                 &self.user_limits,
             ),
-            LimitType::Tenant => (
-                self.config.authenticated_requests_per_minute,
-                &self.tenant_limits,
-            ),
+            LimitType::Tenant => (self.tenant_limit(key).await, &self.tenant_limits),
         };
 
+        self.record(key, max_requests, map)
+    }
+
+    /// Check the user and tenant limits of an authenticated request, both
+    /// derived from the user's tenant tier
+    pub async fn check_authenticated(&self, user: &UserContext) -> Result<(), RateLimitError> {
+        let user_limit = self.user_limit(&user.tenant_id).await;
+        let tenant_limit = user_limit.saturating_mul(self.config.tenant_limit_multiplier);
+
+        let user_check = self.record(&user.user_id, user_limit, &self.user_limits);
+        let tenant_check = self.record(&user.tenant_id, tenant_limit, &self.tenant_limits);
+
+        user_check.and(tenant_check)
+    }
+
+    /// Record a request against `key` unless it would exceed `max_requests`
+    fn record(
+        &self,
+        key: &str,
+        max_requests: u32,
+        map: &DashMap<String, (VecDeque<Instant>, Instant)>,
+    ) -> Result<(), RateLimitError> {
         let mut entry = map
             .entry(key.to_string())
             .or_insert_with(|| (VecDeque::new(), Instant::now()));
@@ -153,7 +281,7 @@ pub enum RateLimitError {
 }
 
 /// Rate limit types
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LimitType {
     IP,
     User,
@@ -183,13 +311,10 @@ impl RateLimitMiddleware {
         // Apply rate limiting based on authentication status
         let rate_limit_result = if let Some(user_ctx) = user_context {
             // Authenticated user: check user and tenant limits
-            let user_check = limiter.check_limit(&user_ctx.user_id, LimitType::User);
-            let tenant_check = limiter.check_limit(&user_ctx.tenant_id, LimitType::Tenant);
-
-            user_check.and(tenant_check)
+            limiter.check_authenticated(user_ctx).await
         } else {
             // Unauthenticated: check IP limit
-            limiter.check_limit(&ip_addr, LimitType::IP)
+            limiter.check_limit(&ip_addr, LimitType::IP).await
         };
 
         match rate_limit_result {
@@ -254,7 +379,11 @@ pub struct RateLimitLayer {
 
 impl RateLimitLayer {
     pub fn new(config: RateLimitConfig) -> Self {
-        let limiter = Arc::new(RateLimiter::new(config));
+        Self::with_limiter(RateLimiter::new(config))
+    }
+
+    pub fn with_limiter(limiter: RateLimiter) -> Self {
+        let limiter = Arc::new(limiter);
 
         // Spawn cleanup task
         let cleanup_limiter = Arc::clone(&limiter);
@@ -324,10 +453,7 @@ where
             // Apply rate limiting based on authentication status
             let rate_limit_result = if let Some(user_ctx) = user_context {
                 // Authenticated user: check user and tenant limits
-                let user_check = limiter.check_limit(&user_ctx.user_id, LimitType::User);
-                let tenant_check = limiter.check_limit(&user_ctx.tenant_id, LimitType::Tenant);
-
-                user_check.and(tenant_check)
+                limiter.check_authenticated(&user_ctx).await
             } else {
                 // Unauthenticated: check IP limit
                 let key = if ip_addr == "unknown" {
@@ -336,7 +462,7 @@ where
                 } else {
                     ip_addr
                 };
-                limiter.check_limit(&key, LimitType::IP)
+                limiter.check_limit(&key, LimitType::IP).await
             };
 
             match rate_limit_result {
@@ -388,10 +514,7 @@ pub async fn rate_limit_middleware(request: Request, next: Next) -> Response {
     // Apply rate limiting based on authentication status
     let rate_limit_result = if let Some(user_ctx) = user_context {
         // Authenticated user: check user and tenant limits
-        let user_check = limiter.check_limit(&user_ctx.user_id, LimitType::User);
-        let tenant_check = limiter.check_limit(&user_ctx.tenant_id, LimitType::Tenant);
-
-        user_check.and(tenant_check)
+        limiter.check_authenticated(user_ctx).await
     } else {
         // Unauthenticated: check IP limit
         let key = if ip_addr == "unknown" {
@@ -399,7 +522,7 @@ pub async fn rate_limit_middleware(request: Request, next: Next) -> Response {
         } else {
             ip_addr
         };
-        limiter.check_limit(&key, LimitType::IP)
+        limiter.check_limit(&key, LimitType::IP).await
     };
 
     match rate_limit_result {
@@ -429,18 +552,307 @@ pub fn create_rate_limit_middleware(config: RateLimitConfig) -> RateLimitLayer {
     RateLimitLayer::new(config)
 }
 
-/// Create concurrency limiting layers
-/// Note: This is a placeholder - concurrency limiting not yet implemented
-pub fn create_concurrency_limits(_config: &RateLimitConfig) -> Vec<()> {
-    vec![]
+/// Create rate limiting middleware whose per-user and tenant limits follow
+/// each tenant's tier
+pub fn create_tiered_rate_limit_middleware(
+    config: RateLimitConfig,
+    tenant_limit_provider: Arc<dyn TenantLimitProvider>,
+) -> RateLimitLayer {
+    RateLimitLayer::with_limiter(
+        RateLimiter::new(config).with_tenant_limit_provider(tenant_limit_provider),
+    )
+}
+
+/// Point-in-time concurrency limiter statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct ConcurrencyStats {
+    /// Requests holding their slots right now
+    pub in_flight: u64,
+    /// Requests turned away since startup
+    pub rejected: u64,
+    /// Users, tenants and IPs with a slot table
+    pub tracked_keys: usize,
+}
+
+/// Caps the requests each user, tenant and IP have in flight
+///
+/// Authenticated requests take a slot of their user and of their tenant,
+/// others one of their IP, like the rate limits. A request over a limit
+/// waits up to `concurrency_wait_ms` for a slot and is then rejected.
+/// Slots are held until the response body has been sent or dropped, so
+/// streamed downloads count for as long as they run.
+#[derive(Debug)]
+pub struct ConcurrencyLimiter {
+    config: Arc<RateLimitConfig>,
+    semaphores: DashMap<(LimitType, String), Arc<Semaphore>>,
+    in_flight: Arc<AtomicU64>,
+    rejected: AtomicU64,
+}
+
+/// Slots taken by one request; dropping it frees them
+#[derive(Debug)]
+pub struct ConcurrencyPermits {
+    _permits: Vec<OwnedSemaphorePermit>,
+    in_flight: Arc<AtomicU64>,
+}
+
+impl Drop for ConcurrencyPermits {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl ConcurrencyLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            semaphores: DashMap::new(),
+            in_flight: Arc::new(AtomicU64::new(0)),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    fn max_concurrent(&self, limit_type: LimitType) -> usize {
+        match limit_type {
+            LimitType::IP => self.config.max_concurrent_per_ip,
+            LimitType::User => self.config.max_concurrent_per_user,
+            LimitType::Tenant => self.config.max_concurrent_per_tenant,
+        }
+    }
+
+    /// Take a slot for every key, or none if any limit stays full
+    pub async fn acquire(
+        &self,
+        keys: &[(LimitType, String)],
+    ) -> Result<ConcurrencyPermits, RateLimitError> {
+        let wait = Duration::from_millis(self.config.concurrency_wait_ms);
+        let mut permits = Vec::with_capacity(keys.len());
+        for (limit_type, key) in keys {
+            let max = self.max_concurrent(*limit_type);
+            if max == 0 {
+                continue;
+            }
+            let semaphore = Arc::clone(
+                self.semaphores
+                    .entry((*limit_type, key.clone()))
+                    .or_insert_with(|| Arc::new(Semaphore::new(max)))
+                    .value(),
+            );
+
+            let permit = match Arc::clone(&semaphore).try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) if wait.is_zero() => None,
+                Err(_) => tokio::time::timeout(wait, semaphore.acquire_owned())
+                    .await
+                    .ok()
+                    .and_then(Result::ok),
+            };
+            let Some(permit) = permit else {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                tracing::debug!(?limit_type, %key, max, "Concurrency limit reached");
+                return Err(RateLimitError::LimitExceeded(1));
+            };
+            permits.push(permit);
+        }
+
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        Ok(ConcurrencyPermits {
+            _permits: permits,
+            in_flight: Arc::clone(&self.in_flight),
+        })
+    }
+
+    pub fn stats(&self) -> ConcurrencyStats {
+        ConcurrencyStats {
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            tracked_keys: self.semaphores.len(),
+        }
+    }
+
+    /// Drop the slot tables of keys with no request in flight
+    pub fn cleanup(&self) {
+        // Every held permit owns a reference to its semaphore
+        self.semaphores
+            .retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+    }
+}
+
+/// Concurrency limiting layer
+#[derive(Clone)]
+pub struct ConcurrencyLimitLayer {
+    pub limiter: Arc<ConcurrencyLimiter>,
+}
+
+impl ConcurrencyLimitLayer {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self::with_limiter(Arc::new(ConcurrencyLimiter::new(config)))
+    }
+
+    pub fn with_limiter(limiter: Arc<ConcurrencyLimiter>) -> Self {
+        let cleanup_limiter = Arc::clone(&limiter);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(300));
+            loop {
+                interval.tick().await;
+                cleanup_limiter.cleanup();
+            }
+        });
+
+        Self { limiter }
+    }
+}
+
+impl<S> tower::Layer<S> for ConcurrencyLimitLayer
+where
+    S: tower::Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = ConcurrencyLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConcurrencyLimitService {
+            inner,
+            limiter: Arc::clone(&self.limiter),
+        }
+    }
+}
+
+/// Concurrency limiting service
+#[derive(Clone)]
+pub struct ConcurrencyLimitService<S> {
+    inner: S,
+    limiter: Arc<ConcurrencyLimiter>,
+}
+
+impl<S> tower::Service<Request> for ConcurrencyLimitService<S>
+where
+    S: tower::Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let limiter = Arc::clone(&self.limiter);
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            let keys = match request.extensions().get::<UserContext>() {
+                Some(user) => vec![
+                    (LimitType::Tenant, user.tenant_id.clone()),
+                    (LimitType::User, user.user_id.clone()),
+                ],
+                // Clients without a known address share no limit
+                None => extract_ip_address(&request)
+                    .map(|ip| vec![(LimitType::IP, ip.to_string())])
+                    .unwrap_or_default(),
+            };
+
+            match limiter.acquire(&keys).await {
+                Ok(permits) => {
+                    let response = inner.call(request).await?;
+                    Ok(response.map(|body| {
+                        Body::new(PermittedBody {
+                            inner: body,
+                            permits: Some(permits),
+                        })
+                    }))
+                }
+                Err(RateLimitError::LimitExceeded(retry_after)) => {
+                    let response = RateLimitResponse {
+                        error: "Too many concurrent requests".to_string(),
+                        retry_after: Some(retry_after),
+                    };
+
+                    Ok((
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [("Retry-After", retry_after.to_string())],
+                        axum::Json(response),
+                    )
+                        .into_response())
+                }
+            }
+        })
+    }
+}
+
+/// Response body that holds its request's slots until it ends or is dropped
+///
+/// Keeps the size hint, so responses keep their Content-Length.
+struct PermittedBody {
+    inner: Body,
+    /// Released as soon as the last frame is sent
+    permits: Option<ConcurrencyPermits>,
+}
+
+impl HttpBody for PermittedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        if frame.is_none() {
+            self.permits = None;
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Create concurrency limiting middleware with configuration
+pub fn create_concurrency_limits(config: &RateLimitConfig) -> ConcurrencyLimitLayer {
+    ConcurrencyLimitLayer::new(config.clone())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::ports::MockTenantLimitProvider;
+    use std::collections::HashSet;
+
+    fn user(user_id: &str, tenant_id: &str) -> UserContext {
+        UserContext::new(
+            user_id.to_string(),
+            tenant_id.to_string(),
+            vec!["user".to_string()],
+            HashSet::new(),
+            false,
+            None,
+        )
+    }
 
-    #[test]
-    fn test_rate_limiter_within_limit() {
+    fn tiered_limiter(multiplier: u32) -> RateLimiter {
+        let mut provider = MockTenantLimitProvider::new();
+        provider
+            .expect_requests_per_minute()
+            .returning(|tenant_id| Ok((tenant_id == "paid").then_some(4)));
+
+        RateLimiter::new(RateLimitConfig {
+            authenticated_requests_per_minute: 2,
+            tenant_limit_multiplier: multiplier,
+            ..Default::default()
+        })
+        .with_tenant_limit_provider(Arc::new(provider))
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_within_limit() {
         let config = RateLimitConfig {
             unauthenticated_requests_per_minute: 5,
             ..Default::default()
@@ -451,12 +863,13 @@ mod tests {
         for i in 0..5 {
             assert!(limiter
                 .check_limit(&format!("ip_{}", i), LimitType::IP)
+                .await
                 .is_ok());
         }
     }
 
-    #[test]
-    fn test_rate_limiter_exceeds_limit() {
+    #[tokio::test]
+    async fn test_rate_limiter_exceeds_limit() {
         let config = RateLimitConfig {
             unauthenticated_requests_per_minute: 3,
             ..Default::default()
@@ -465,12 +878,12 @@ mod tests {
 
         // Fill up the limit
         for _i in 0..3 {
-            assert!(limiter.check_limit("test_ip", LimitType::IP).is_ok());
+            assert!(limiter.check_limit("test_ip", LimitType::IP).await.is_ok());
         }
 
         // Next request should be rate limited
         assert!(matches!(
-            limiter.check_limit("test_ip", LimitType::IP),
+            limiter.check_limit("test_ip", LimitType::IP).await,
             Err(RateLimitError::LimitExceeded(_))
         ));
     }
@@ -482,8 +895,8 @@ mod tests {
         assert!(extract_ip_address(&axum::extract::Request::default()).is_none());
     }
 
-    #[test]
-    fn test_rate_limiter_different_types() {
+    #[tokio::test]
+    async fn test_rate_limiter_different_types() {
         let config = RateLimitConfig {
             unauthenticated_requests_per_minute: 3,
             authenticated_requests_per_minute: 5,
@@ -493,19 +906,22 @@ mod tests {
 
         // Test IP limiting: use the same key to fill the limit
         for _ in 0..3 {
-            assert!(limiter.check_limit("ip_test", LimitType::IP).is_ok());
+            assert!(limiter.check_limit("ip_test", LimitType::IP).await.is_ok());
         }
         assert!(matches!(
-            limiter.check_limit("ip_test", LimitType::IP),
+            limiter.check_limit("ip_test", LimitType::IP).await,
             Err(RateLimitError::LimitExceeded(_))
         ));
 
         // Test user limiting (different limit): use same user id
         for _ in 0..5 {
-            assert!(limiter.check_limit("user_test", LimitType::User).is_ok());
+            assert!(limiter
+                .check_limit("user_test", LimitType::User)
+                .await
+                .is_ok());
         }
         assert!(matches!(
-            limiter.check_limit("user_test", LimitType::User),
+            limiter.check_limit("user_test", LimitType::User).await,
             Err(RateLimitError::LimitExceeded(_))
         ));
 
@@ -513,16 +929,17 @@ mod tests {
         for _ in 0..5 {
             assert!(limiter
                 .check_limit("tenant_test", LimitType::Tenant)
+                .await
                 .is_ok());
         }
         assert!(matches!(
-            limiter.check_limit("tenant_test", LimitType::Tenant),
+            limiter.check_limit("tenant_test", LimitType::Tenant).await,
             Err(RateLimitError::LimitExceeded(_))
         ));
     }
 
-    #[test]
-    fn test_rate_limiter_cleanup() {
+    #[tokio::test]
+    async fn test_rate_limiter_cleanup() {
         let config = RateLimitConfig {
             unauthenticated_requests_per_minute: 10,
             window_seconds: 1,
@@ -532,7 +949,9 @@ mod tests {
 
         // Add some entries
         for i in 0..5 {
-            let _ = limiter.check_limit(&format!("ip_{}", i), LimitType::IP);
+            let _ = limiter
+                .check_limit(&format!("ip_{}", i), LimitType::IP)
+                .await;
         }
 
         assert!(!limiter.ip_limits.is_empty());
@@ -555,10 +974,12 @@ mod tests {
         assert_eq!(config.max_concurrent_per_tenant, 50);
         assert_eq!(config.max_concurrent_per_ip, 25);
         assert_eq!(config.window_seconds, 60);
+        assert_eq!(config.tenant_limit_multiplier, 1);
+        assert_eq!(config.tenant_limit_cache_ttl_seconds, 60);
     }
 
-    #[test]
-    fn test_rate_limit_error_retry_after() {
+    #[tokio::test]
+    async fn test_rate_limit_error_retry_after() {
         let config = RateLimitConfig {
             unauthenticated_requests_per_minute: 1,
             window_seconds: 60,
@@ -567,10 +988,10 @@ mod tests {
         let limiter = RateLimiter::new(config);
 
         // Use up the limit
-        assert!(limiter.check_limit("test_ip", LimitType::IP).is_ok());
+        assert!(limiter.check_limit("test_ip", LimitType::IP).await.is_ok());
 
         // Next request should be rate limited
-        match limiter.check_limit("test_ip", LimitType::IP) {
+        match limiter.check_limit("test_ip", LimitType::IP).await {
             Err(RateLimitError::LimitExceeded(retry_after)) => {
                 assert!(retry_after > 0 && retry_after <= 60);
             }
@@ -584,4 +1005,187 @@ mod tests {
         assert!(matches!(LimitType::User, LimitType::User));
         assert!(matches!(LimitType::Tenant, LimitType::Tenant));
     }
+
+    #[tokio::test]
+    async fn test_user_limit_follows_tenant_tier() {
+        let limiter = tiered_limiter(10);
+
+        // Paid tier: 4 per user; untiered tenants keep the default of 2
+        for _ in 0..4 {
+            assert!(limiter
+                .check_authenticated(&user("p", "paid"))
+                .await
+                .is_ok());
+        }
+        assert!(limiter
+            .check_authenticated(&user("p", "paid"))
+            .await
+            .is_err());
+
+        for _ in 0..2 {
+            assert!(limiter
+                .check_authenticated(&user("f", "free"))
+                .await
+                .is_ok());
+        }
+        assert!(limiter
+            .check_authenticated(&user("f", "free"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_limit_uses_configured_multiplier() {
+        let limiter = tiered_limiter(2);
+
+        // Paid tier of 4 per user, doubled for the tenant as a whole
+        for i in 0..8 {
+            let user = user(&format!("user_{}", i / 2), "paid");
+            assert!(limiter.check_authenticated(&user).await.is_ok());
+        }
+        assert!(matches!(
+            limiter.check_authenticated(&user("user_9", "paid")).await,
+            Err(RateLimitError::LimitExceeded(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tenant_limit_lookups_are_cached_and_failures_fall_back() {
+        let mut provider = MockTenantLimitProvider::new();
+        provider
+            .expect_requests_per_minute()
+            .times(1)
+            .returning(|_| Ok(Some(7)));
+        let cached = CachedTenantLimitProvider::new(Arc::new(provider), Duration::from_secs(60));
+
+        assert_eq!(cached.requests_per_minute("t").await.unwrap(), Some(7));
+        assert_eq!(cached.requests_per_minute("t").await.unwrap(), Some(7));
+
+        let mut failing = MockTenantLimitProvider::new();
+        failing
+            .expect_requests_per_minute()
+            .returning(|_| Err(RepositoryError::Database(sqlx::Error::PoolTimedOut)));
+        let limiter = RateLimiter::new(RateLimitConfig {
+            authenticated_requests_per_minute: 1,
+            ..Default::default()
+        })
+        .with_tenant_limit_provider(Arc::new(failing));
+
+        assert!(limiter.check_authenticated(&user("u", "t")).await.is_ok());
+        assert!(limiter.check_authenticated(&user("u", "t")).await.is_err());
+    }
+
+    fn tenant_keys(user_id: &str, tenant_id: &str) -> Vec<(LimitType, String)> {
+        vec![
+            (LimitType::Tenant, tenant_id.to_string()),
+            (LimitType::User, user_id.to_string()),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit_rejects_and_releases_on_drop() {
+        let limiter = ConcurrencyLimiter::new(RateLimitConfig {
+            max_concurrent_per_user: 1,
+            ..Default::default()
+        });
+
+        let permits = limiter.acquire(&tenant_keys("u", "t")).await.unwrap();
+        assert!(limiter.acquire(&tenant_keys("u", "t")).await.is_err());
+        // Other users of the tenant still get a slot
+        let other = limiter.acquire(&tenant_keys("v", "t")).await.unwrap();
+        assert_eq!(limiter.stats().in_flight, 2);
+        assert_eq!(limiter.stats().rejected, 1);
+
+        drop(permits);
+        drop(other);
+        assert_eq!(limiter.stats().in_flight, 0);
+        assert!(limiter.acquire(&tenant_keys("u", "t")).await.is_ok());
+
+        limiter.cleanup();
+        assert_eq!(limiter.stats().tracked_keys, 0);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_concurrency_limit_is_shared_by_its_users() {
+        let limiter = ConcurrencyLimiter::new(RateLimitConfig {
+            max_concurrent_per_tenant: 2,
+            ..Default::default()
+        });
+
+        let _first = limiter.acquire(&tenant_keys("u", "t")).await.unwrap();
+        let _second = limiter.acquire(&tenant_keys("v", "t")).await.unwrap();
+        assert!(limiter.acquire(&tenant_keys("w", "t")).await.is_err());
+        assert!(limiter.acquire(&tenant_keys("w", "other")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit_waits_for_a_slot() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(RateLimitConfig {
+            max_concurrent_per_ip: 1,
+            concurrency_wait_ms: 1_000,
+            ..Default::default()
+        }));
+        let keys = vec![(LimitType::IP, "10.0.0.1".to_string())];
+
+        let permits = limiter.acquire(&keys).await.unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(permits);
+        });
+
+        assert!(limiter.acquire(&keys).await.is_ok());
+        assert_eq!(limiter.stats().rejected, 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_slot_is_held_until_the_body_is_sent() {
+        use tower::{Layer, ServiceExt};
+
+        let limiter = Arc::new(ConcurrencyLimiter::new(RateLimitConfig {
+            max_concurrent_per_ip: 1,
+            ..Default::default()
+        }));
+        let service = ConcurrencyLimitLayer::with_limiter(Arc::clone(&limiter)).layer(
+            tower::service_fn(|_: Request| async {
+                Ok::<_, std::convert::Infallible>(Response::new(Body::from("streamed")))
+            }),
+        );
+        let request = || {
+            Request::builder()
+                .header("x-forwarded-for", "10.0.0.1")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = service.clone().oneshot(request()).await.unwrap();
+        assert_eq!(limiter.stats().in_flight, 1);
+        let rejected = service.clone().oneshot(request()).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(limiter.stats().in_flight, 0);
+
+        // A body dropped unsent frees its slot too
+        drop(service.clone().oneshot(request()).await.unwrap());
+        assert_eq!(limiter.stats().in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_zero_concurrency_limit_is_unlimited() {
+        let limiter = ConcurrencyLimiter::new(RateLimitConfig {
+            max_concurrent_per_ip: 0,
+            ..Default::default()
+        });
+        let keys = vec![(LimitType::IP, "10.0.0.1".to_string())];
+
+        let _held: Vec<_> =
+            futures_util::future::join_all((0..100).map(|_| limiter.acquire(&keys)))
+                .await
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+        assert_eq!(limiter.stats().tracked_keys, 0);
+    }
 }
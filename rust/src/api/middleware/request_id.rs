@@ -0,0 +1,208 @@
+//! Request ID propagation
+//!
+//! Reads the request ID from an incoming header (or generates one), stores
+//! it in request extensions, runs the rest of the stack inside a `tracing`
+//! span carrying the ID, and echoes it back on the response. With
+//! OpenTelemetry export enabled the span continues an inbound W3C trace.
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::infrastructure::telemetry;
+
+/// Default header used to carry the request ID
+pub const DEFAULT_REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request ID that is accepted as-is
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Request ID stored in request extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Request ID configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestIdConfig {
+    /// Header the request ID is read from and echoed in
+    pub header_name: String,
+}
+
+impl Default for RequestIdConfig {
+    fn default() -> Self {
+        Self {
+            header_name: DEFAULT_REQUEST_ID_HEADER.to_string(),
+        }
+    }
+}
+
+impl RequestIdConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the header name used for request IDs
+    pub fn with_header_name(mut self, header_name: impl Into<String>) -> Self {
+        self.header_name = header_name.into();
+        self
+    }
+
+    /// Parsed header name, falling back to the default when invalid
+    pub fn header(&self) -> HeaderName {
+        HeaderName::from_bytes(self.header_name.to_lowercase().as_bytes())
+            .unwrap_or_else(|_| HeaderName::from_static(DEFAULT_REQUEST_ID_HEADER))
+    }
+}
+
+/// Extract a usable request ID from headers or generate a new one
+///
+/// Client-supplied IDs are only trusted when short and made of visible
+/// ASCII, so they are safe to log and echo back.
+pub fn extract_or_generate(headers: &HeaderMap, header: &HeaderName) -> RequestId {
+    headers
+        .get(header)
+        .and_then(|h| h.to_str().ok())
+        .filter(|s| is_valid_request_id(s))
+        .map(|s| RequestId(s.to_string()))
+        .unwrap_or_else(|| RequestId(Uuid::new_v4().to_string()))
+}
+
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Request ID middleware
+pub async fn request_id_middleware(
+    config: &RequestIdConfig,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let header = config.header();
+    let request_id = extract_or_generate(request.headers(), &header);
+    request.extensions_mut().insert(request_id.clone());
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        uri = %request.uri().path(),
+    );
+    // Join the caller's trace when spans are exported
+    telemetry::set_remote_parent(&span, request.headers());
+
+    let mut response = next.run(request).instrument(span).await;
+
+    if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+        response.headers_mut().insert(header, value);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Extension, Router};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn app(config: RequestIdConfig) -> Router {
+        let config = Arc::new(config);
+        Router::new()
+            .route(
+                "/",
+                get(|Extension(id): Extension<RequestId>| async move { id.0 }),
+            )
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                async move { request_id_middleware(&config, req, next).await }
+            }))
+    }
+
+    #[test]
+    fn test_generates_uuid_when_missing() {
+        let header = RequestIdConfig::default().header();
+        let request_id = extract_or_generate(&HeaderMap::new(), &header);
+
+        assert!(Uuid::parse_str(request_id.as_str()).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_oversized_or_unprintable_ids() {
+        let header = RequestIdConfig::default().header();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header.clone(),
+            HeaderValue::from_str(&"a".repeat(MAX_REQUEST_ID_LEN + 1)).unwrap(),
+        );
+        assert!(Uuid::parse_str(extract_or_generate(&headers, &header).as_str()).is_ok());
+
+        headers.insert(header.clone(), HeaderValue::from_static("has space"));
+        assert!(Uuid::parse_str(extract_or_generate(&headers, &header).as_str()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_header_name_falls_back_to_default() {
+        let config = RequestIdConfig::new().with_header_name("not a header");
+
+        assert_eq!(config.header(), DEFAULT_REQUEST_ID_HEADER);
+    }
+
+    #[tokio::test]
+    async fn test_propagates_incoming_request_id() {
+        let response = app(RequestIdConfig::default())
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header("x-request-id", "req-123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["x-request-id"], "req-123");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"req-123");
+    }
+
+    #[tokio::test]
+    async fn test_uses_configured_header_name() {
+        let response = app(RequestIdConfig::new().with_header_name("X-Correlation-Id"))
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header("x-correlation-id", "corr-9")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["x-correlation-id"], "corr-9");
+        assert!(response.headers().get("x-request-id").is_none());
+    }
+}
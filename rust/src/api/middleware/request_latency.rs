@@ -0,0 +1,420 @@
+//! Per-route latency histograms and slow request logs
+//!
+//! The timeout middleware of every route reports how long its handler took,
+//! up to the response head, so a streamed download counts its time to first
+//! byte. Each method and route template gets a histogram. Requests slower
+//! than the threshold of their timeout class are logged with their route,
+//! duration, status and tenant, inside the request ID span. Uploads use the
+//! transfer class and get a higher threshold.
+//!
+//! Slow request logs are capped per second so a slowdown under load does not
+//! flood the log; the next record says how many were skipped.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::StatusCode,
+};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::request_timeout::TimeoutClass;
+use crate::domain::authorization::UserContext;
+
+/// Default slow threshold of metadata reads
+pub const DEFAULT_SLOW_SHORT_MS: u64 = 500;
+
+/// Default slow threshold of routes without a more specific class
+pub const DEFAULT_SLOW_MS: u64 = 1_000;
+
+/// Default slow threshold of uploads and downloads
+pub const DEFAULT_SLOW_TRANSFER_MS: u64 = 60_000;
+
+/// Default cap on slow request log records per second
+pub const DEFAULT_SLOW_LOGS_PER_SEC: u64 = 10;
+
+/// Upper bounds of the histogram buckets, in milliseconds; slower requests
+/// land in a last, unbounded bucket
+const BUCKET_BOUNDS_MS: [u64; 13] = [
+    5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000, 60_000,
+];
+
+/// Slow request thresholds, in milliseconds per timeout class; 0 never logs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlowRequestConfig {
+    pub short_ms: u64,
+    pub default_ms: u64,
+    pub transfer_ms: u64,
+    /// Most slow requests logged per second
+    pub max_logs_per_sec: u64,
+}
+
+impl Default for SlowRequestConfig {
+    fn default() -> Self {
+        Self {
+            short_ms: DEFAULT_SLOW_SHORT_MS,
+            default_ms: DEFAULT_SLOW_MS,
+            transfer_ms: DEFAULT_SLOW_TRANSFER_MS,
+            max_logs_per_sec: DEFAULT_SLOW_LOGS_PER_SEC,
+        }
+    }
+}
+
+impl SlowRequestConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the threshold of metadata reads
+    pub fn with_short_ms(mut self, ms: u64) -> Self {
+        self.short_ms = ms;
+        self
+    }
+
+    /// Set the threshold of routes without a more specific class
+    pub fn with_default_ms(mut self, ms: u64) -> Self {
+        self.default_ms = ms;
+        self
+    }
+
+    /// Set the threshold of uploads and downloads
+    pub fn with_transfer_ms(mut self, ms: u64) -> Self {
+        self.transfer_ms = ms;
+        self
+    }
+
+    /// Cap the slow request log records written per second
+    pub fn with_max_logs_per_sec(mut self, max_logs_per_sec: u64) -> Self {
+        self.max_logs_per_sec = max_logs_per_sec;
+        self
+    }
+
+    /// Threshold of a route class; `None` when slow requests are not logged
+    pub fn threshold(&self, class: TimeoutClass) -> Option<Duration> {
+        let ms = match class {
+            TimeoutClass::Short => self.short_ms,
+            TimeoutClass::Default => self.default_ms,
+            TimeoutClass::Transfer => self.transfer_ms,
+        };
+        (ms > 0).then(|| Duration::from_millis(ms))
+    }
+}
+
+/// What is known of a request before its handler runs
+#[derive(Debug, Clone)]
+pub struct RequestLabels {
+    method: String,
+    /// Route template, e.g. `/v1/objects/{id}`; the path when unmatched
+    route: String,
+    tenant_id: Option<String>,
+}
+
+impl RequestLabels {
+    /// Read the labels of a request inside authentication
+    pub fn of(request: &Request) -> Self {
+        let extensions = request.extensions();
+        Self {
+            method: request.method().to_string(),
+            route: extensions
+                .get::<MatchedPath>()
+                .map(|path| path.as_str().to_string())
+                .unwrap_or_else(|| request.uri().path().to_string()),
+            tenant_id: extensions
+                .get::<UserContext>()
+                .map(|user| user.tenant_id.clone()),
+        }
+    }
+}
+
+/// Latency histogram of one route
+#[derive(Debug)]
+struct Histogram {
+    class: TimeoutClass,
+    /// One more than the bounds, for requests past the last one
+    buckets: [AtomicU64; BUCKET_BOUNDS_MS.len() + 1],
+    total_ms: AtomicU64,
+    max_ms: AtomicU64,
+}
+
+impl Histogram {
+    fn new(class: TimeoutClass) -> Self {
+        Self {
+            class,
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            total_ms: AtomicU64::new(0),
+            max_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, ms: u64) {
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.total_ms.fetch_add(ms, Ordering::Relaxed);
+        self.max_ms.fetch_max(ms, Ordering::Relaxed);
+    }
+}
+
+/// Latencies of one route
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RouteLatency {
+    pub method: String,
+    /// Route template, e.g. `/v1/objects/{id}`
+    pub route: String,
+    /// Timeout class the slow threshold comes from
+    pub class: String,
+    pub requests: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+    /// Requests per bucket, not cumulative
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// Requests that took at most `le_ms`, and more than the previous bucket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LatencyBucket {
+    /// Upper bound; absent for the last bucket
+    pub le_ms: Option<u64>,
+    pub requests: u64,
+}
+
+/// Latencies of every route served since startup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LatencyReport {
+    /// Requests over the slow threshold of their class
+    pub slow_requests: u64,
+    /// Slow requests not logged because of the per-second cap
+    pub slow_logs_skipped: u64,
+    /// Sorted by route, then method
+    pub routes: Vec<RouteLatency>,
+}
+
+/// Records request latencies per route and logs slow requests
+#[derive(Debug)]
+pub struct RequestLatency {
+    config: SlowRequestConfig,
+    routes: DashMap<(String, String), Histogram>,
+    started: Instant,
+    /// Second, since `started`, that `window_logs` counts logs of
+    window: AtomicU64,
+    window_logs: AtomicU64,
+    slow_requests: AtomicU64,
+    /// Skipped since the last slow request was logged
+    skipped: AtomicU64,
+    skipped_total: AtomicU64,
+}
+
+impl RequestLatency {
+    pub fn new(config: SlowRequestConfig) -> Self {
+        Self {
+            config,
+            routes: DashMap::new(),
+            started: Instant::now(),
+            window: AtomicU64::new(0),
+            window_logs: AtomicU64::new(0),
+            slow_requests: AtomicU64::new(0),
+            skipped: AtomicU64::new(0),
+            skipped_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a request's latency, logging it when it was slow
+    pub fn record(
+        &self,
+        class: TimeoutClass,
+        labels: RequestLabels,
+        status: StatusCode,
+        elapsed: Duration,
+    ) {
+        let ms = elapsed.as_millis() as u64;
+        let key = (labels.method, labels.route);
+        match self.routes.get(&key) {
+            Some(histogram) => histogram.record(ms),
+            None => self
+                .routes
+                .entry(key.clone())
+                .or_insert_with(|| Histogram::new(class))
+                .record(ms),
+        }
+
+        if !self
+            .config
+            .threshold(class)
+            .is_some_and(|threshold| elapsed >= threshold)
+        {
+            return;
+        }
+        self.slow_requests.fetch_add(1, Ordering::Relaxed);
+        if !self.may_log() {
+            self.skipped.fetch_add(1, Ordering::Relaxed);
+            self.skipped_total.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        let (method, route) = key;
+        tracing::warn!(
+            %method,
+            route,
+            status = status.as_u16(),
+            duration_ms = ms,
+            tenant_id = labels.tenant_id.as_deref(),
+            skipped = self.skipped.swap(0, Ordering::Relaxed),
+            "Slow request"
+        );
+    }
+
+    /// Whether this second's log budget has room for another record
+    fn may_log(&self) -> bool {
+        let second = self.started.elapsed().as_secs();
+        if self.window.swap(second, Ordering::Relaxed) != second {
+            self.window_logs.store(0, Ordering::Relaxed);
+        }
+        self.window_logs.fetch_add(1, Ordering::Relaxed) < self.config.max_logs_per_sec
+    }
+
+    pub fn report(&self) -> LatencyReport {
+        let mut routes: Vec<RouteLatency> = self
+            .routes
+            .iter()
+            .map(|entry| {
+                let ((method, route), histogram) = entry.pair();
+                let buckets: Vec<LatencyBucket> = histogram
+                    .buckets
+                    .iter()
+                    .enumerate()
+                    .map(|(i, requests)| LatencyBucket {
+                        le_ms: BUCKET_BOUNDS_MS.get(i).copied(),
+                        requests: requests.load(Ordering::Relaxed),
+                    })
+                    .collect();
+                RouteLatency {
+                    method: method.clone(),
+                    route: route.clone(),
+                    class: histogram.class.as_str().to_string(),
+                    requests: buckets.iter().map(|b| b.requests).sum(),
+                    total_ms: histogram.total_ms.load(Ordering::Relaxed),
+                    max_ms: histogram.max_ms.load(Ordering::Relaxed),
+                    buckets,
+                }
+            })
+            .collect();
+        routes.sort_by(|a, b| (&a.route, &a.method).cmp(&(&b.route, &b.method)));
+
+        LatencyReport {
+            slow_requests: self.slow_requests.load(Ordering::Relaxed),
+            slow_logs_skipped: self.skipped_total.load(Ordering::Relaxed),
+            routes,
+        }
+    }
+}
+
+impl Default for RequestLatency {
+    fn default() -> Self {
+        Self::new(SlowRequestConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(route: &str) -> RequestLabels {
+        RequestLabels {
+            method: "GET".to_string(),
+            route: route.to_string(),
+            tenant_id: Some("tenant-a".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_histogram_per_route() {
+        let latency = RequestLatency::default();
+
+        for ms in [3, 7, 7, 120_000] {
+            latency.record(
+                TimeoutClass::Default,
+                labels("/v1/objects/{id}"),
+                StatusCode::OK,
+                Duration::from_millis(ms),
+            );
+        }
+        latency.record(
+            TimeoutClass::Short,
+            labels("/v1/objects"),
+            StatusCode::OK,
+            Duration::from_millis(40),
+        );
+
+        let report = latency.report();
+        assert_eq!(report.routes.len(), 2);
+        let route = &report.routes[1];
+        assert_eq!(route.route, "/v1/objects/{id}");
+        assert_eq!(route.class, "default");
+        assert_eq!(route.requests, 4);
+        assert_eq!(route.max_ms, 120_000);
+        assert_eq!(route.total_ms, 120_017);
+        assert_eq!(route.buckets[0].requests, 1);
+        assert_eq!(route.buckets[1].requests, 2);
+        let last = route.buckets.last().unwrap();
+        assert_eq!((last.le_ms, last.requests), (None, 1));
+    }
+
+    #[test]
+    fn test_slow_threshold_per_class() {
+        let latency = RequestLatency::new(
+            SlowRequestConfig::new()
+                .with_default_ms(100)
+                .with_transfer_ms(0),
+        );
+
+        latency.record(
+            TimeoutClass::Default,
+            labels("/v1/stats"),
+            StatusCode::OK,
+            Duration::from_millis(150),
+        );
+        latency.record(
+            TimeoutClass::Short,
+            labels("/v1/objects"),
+            StatusCode::OK,
+            Duration::from_millis(150),
+        );
+        // Transfers never count as slow with a 0 threshold
+        latency.record(
+            TimeoutClass::Transfer,
+            labels("/v1/objects"),
+            StatusCode::OK,
+            Duration::from_secs(600),
+        );
+
+        assert_eq!(latency.report().slow_requests, 1);
+    }
+
+    #[test]
+    fn test_slow_logs_capped_per_second() {
+        let latency = RequestLatency::new(
+            SlowRequestConfig::new()
+                .with_default_ms(1)
+                .with_max_logs_per_sec(2),
+        );
+
+        for _ in 0..5 {
+            latency.record(
+                TimeoutClass::Default,
+                labels("/v1/stats"),
+                StatusCode::OK,
+                Duration::from_millis(10),
+            );
+        }
+
+        let report = latency.report();
+        assert_eq!(report.slow_requests, 5);
+        assert_eq!(report.slow_logs_skipped, 3);
+    }
+}
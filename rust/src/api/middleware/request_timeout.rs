@@ -0,0 +1,304 @@
+//! Per-route request timeouts
+//!
+//! Routes pick a timeout class: short for metadata reads (list, search,
+//! health), transfer for uploads and downloads, and the default for the
+//! rest. A request still running when its timeout expires is dropped and
+//! answered with `504 Gateway Timeout`.
+//!
+//! The timeout runs until the handler returns its response head. A download
+//! body is streamed after that, so a long transfer is never cut off mid-way;
+//! upload handlers read the whole body first, so their class must allow for
+//! it. An upload dropped by the timeout leaves its object WRITING, which the
+//! stuck upload collector cleans up.
+//!
+//! The middleware also times the handler for the route's latency histogram
+//! and slow request log; see [`super::request_latency`].
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+
+use super::request_latency::{RequestLabels, RequestLatency};
+use crate::api::errors::ApiError;
+
+/// Default timeout of routes without a more specific class
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Default timeout of metadata reads
+pub const DEFAULT_SHORT_TIMEOUT_SECS: u64 = 10;
+
+/// Default timeout of uploads and downloads (until the response head)
+pub const DEFAULT_TRANSFER_TIMEOUT_SECS: u64 = 3600;
+
+/// Timeout class of a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutClass {
+    /// Metadata reads: list, search, health
+    Short,
+    /// Routes without a more specific class
+    Default,
+    /// Uploads and downloads
+    Transfer,
+}
+
+impl TimeoutClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Short => "short",
+            Self::Default => "default",
+            Self::Transfer => "transfer",
+        }
+    }
+}
+
+/// Request timeout configuration, in seconds per class
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestTimeoutConfig {
+    pub default_secs: u64,
+    pub short_secs: u64,
+    pub transfer_secs: u64,
+}
+
+impl Default for RequestTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            default_secs: DEFAULT_TIMEOUT_SECS,
+            short_secs: DEFAULT_SHORT_TIMEOUT_SECS,
+            transfer_secs: DEFAULT_TRANSFER_TIMEOUT_SECS,
+        }
+    }
+}
+
+impl RequestTimeoutConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the timeout of routes without a more specific class
+    pub fn with_default_secs(mut self, secs: u64) -> Self {
+        self.default_secs = secs;
+        self
+    }
+
+    /// Set the timeout of metadata reads
+    pub fn with_short_secs(mut self, secs: u64) -> Self {
+        self.short_secs = secs;
+        self
+    }
+
+    /// Set the timeout of uploads and downloads
+    pub fn with_transfer_secs(mut self, secs: u64) -> Self {
+        self.transfer_secs = secs;
+        self
+    }
+
+    /// Timeout of a route class
+    pub fn timeout(&self, class: TimeoutClass) -> Duration {
+        let secs = match class {
+            TimeoutClass::Short => self.short_secs,
+            TimeoutClass::Default => self.default_secs,
+            TimeoutClass::Transfer => self.transfer_secs,
+        };
+        Duration::from_secs(secs)
+    }
+
+    /// Timeout middleware state of a route of the class
+    pub fn route(&self, class: TimeoutClass) -> RouteTimeout {
+        RouteTimeout {
+            class,
+            timeout: self.timeout(class),
+            latency: None,
+        }
+    }
+}
+
+/// Timeout and latency recording of a route
+#[derive(Debug, Clone)]
+pub struct RouteTimeout {
+    class: TimeoutClass,
+    timeout: Duration,
+    latency: Option<Arc<RequestLatency>>,
+}
+
+impl RouteTimeout {
+    /// Allow `extra` on top of the class timeout, e.g. for long-polls
+    pub fn extended(mut self, extra: Duration) -> Self {
+        self.timeout += extra;
+        self
+    }
+
+    /// Record the route's latencies and log its slow requests
+    pub fn with_latency(mut self, latency: Arc<RequestLatency>) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+}
+
+/// Request timeout middleware; the state is the route's timeout
+pub async fn request_timeout_middleware(
+    State(route): State<RouteTimeout>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let labels = route.latency.as_ref().map(|_| RequestLabels::of(&request));
+    let started = Instant::now();
+
+    let response = match tokio::time::timeout(route.timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(
+                %method,
+                path,
+                timeout_ms = route.timeout.as_millis() as u64,
+                "Request timed out"
+            );
+            ApiError::new(StatusCode::GATEWAY_TIMEOUT, "Request timed out").into_response()
+        }
+    };
+
+    if let (Some(latency), Some(labels)) = (&route.latency, labels) {
+        latency.record(route.class, labels, response.status(), started.elapsed());
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use futures_util::stream;
+    use tower::ServiceExt;
+
+    const TIMEOUT: Duration = Duration::from_millis(50);
+
+    fn app() -> Router {
+        app_with(RouteTimeout {
+            class: TimeoutClass::Default,
+            timeout: TIMEOUT,
+            latency: None,
+        })
+    }
+
+    fn app_with(route: RouteTimeout) -> Router {
+        Router::new()
+            .route("/fast", get(|| async { "data" }))
+            .route("/objects/{id}", get(|| async { "object" }))
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "data"
+                }),
+            )
+            .route(
+                "/stream",
+                get(|| async {
+                    // Three chunks, each slower than the timeout
+                    let chunks = stream::unfold(0, |sent| async move {
+                        if sent == 3 {
+                            return None;
+                        }
+                        tokio::time::sleep(TIMEOUT).await;
+                        Some((Ok::<_, std::io::Error>("chunk"), sent + 1))
+                    });
+                    Body::from_stream(chunks)
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                route,
+                request_timeout_middleware,
+            ))
+    }
+
+    async fn get_path(path: &str) -> Response {
+        app()
+            .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fast_request_passes() {
+        let response = get_path("/fast").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_slow_request_returns_gateway_timeout() {
+        let response = get_path("/slow").await;
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Request timed out");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_body_outlives_timeout() {
+        let response = get_path("/stream").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"chunkchunkchunk");
+    }
+
+    #[tokio::test]
+    async fn test_latency_recorded_per_route_template() {
+        let latency = Arc::new(RequestLatency::default());
+        let app = app_with(RouteTimeout {
+            class: TimeoutClass::Short,
+            timeout: TIMEOUT,
+            latency: Some(Arc::clone(&latency)),
+        });
+
+        for path in ["/objects/a", "/objects/b", "/slow"] {
+            app.clone()
+                .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let report = latency.report();
+        let routes: Vec<_> = report
+            .routes
+            .iter()
+            .map(|r| (r.route.as_str(), r.class.as_str(), r.requests))
+            .collect();
+        assert_eq!(
+            routes,
+            [("/objects/{id}", "short", 2), ("/slow", "short", 1)]
+        );
+    }
+
+    #[test]
+    fn test_timeout_per_class() {
+        let config = RequestTimeoutConfig::new()
+            .with_short_secs(5)
+            .with_transfer_secs(600);
+
+        assert_eq!(config.timeout(TimeoutClass::Short), Duration::from_secs(5));
+        assert_eq!(
+            config.timeout(TimeoutClass::Default),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            config.timeout(TimeoutClass::Transfer),
+            Duration::from_secs(600)
+        );
+    }
+}
@@ -0,0 +1,648 @@
+//! Download response compression
+//!
+//! Negotiates gzip, brotli or zstd from `Accept-Encoding` for object
+//! downloads. Objects whose stored content type is already compressed
+//! (archives, images, audio, video) are passed through untouched, since
+//! compressing them again only costs CPU.
+//!
+//! A compressed response no longer has the stored bytes its strong ETag
+//! names, so `weaken_encoded_etag` marks that ETag weak. `If-None-Match`
+//! compares weakly, so revalidating with it still returns `304`. Objects
+//! uploaded with a `Content-Encoding` and sent as stored keep it strong.
+//!
+//! `RESPONSE_COMPRESSION_MODE` sets whether downloads are compressed by
+//! default; the `X-Download-Compression` request header overrides it for one
+//! download. `measure_compression` counts the bytes compression saved.
+
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use axum::{
+    body::{Body, BodyDataStream, Bytes, HttpBody},
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, Response},
+    BoxError,
+};
+use futures_util::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tower_http::compression::{
+    predicate::{Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+use crate::domain::value_objects::ContentEncoding;
+
+/// Content type recorded for the object at upload time
+///
+/// Downloads are always served as `application/octet-stream`, so handlers
+/// attach the stored type as a response extension for the compression
+/// predicate to inspect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredContentType(pub String);
+
+/// Compressibility of a MIME type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MimeCategory {
+    /// Text formats that typically compress well
+    Text,
+    /// Formats that are already compressed
+    Compressed,
+    /// Other binary formats
+    Binary,
+}
+
+/// Classify a MIME type (parameters such as `charset` are ignored)
+pub fn category_from_mime(mime: &str) -> MimeCategory {
+    let essence = mime
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let Some((kind, subtype)) = essence.split_once('/') else {
+        return MimeCategory::Binary;
+    };
+
+    match (kind, subtype) {
+        ("text", _) => MimeCategory::Text,
+        ("image", "svg+xml") => MimeCategory::Text,
+        ("image" | "audio" | "video", _) => MimeCategory::Compressed,
+        ("font", "woff" | "woff2") => MimeCategory::Compressed,
+        (
+            "application",
+            "json" | "xml" | "javascript" | "x-ndjson" | "yaml" | "x-yaml" | "sql" | "graphql",
+        ) => MimeCategory::Text,
+        ("application", s) if s.ends_with("+json") || s.ends_with("+xml") => MimeCategory::Text,
+        (
+            "application",
+            "zip" | "gzip" | "x-gzip" | "zstd" | "x-bzip2" | "x-xz" | "x-7z-compressed"
+            | "x-rar-compressed" | "vnd.rar" | "java-archive" | "pdf" | "brotli",
+        ) => MimeCategory::Compressed,
+        // Office Open XML and friends are zip containers
+        ("application", s) if s.ends_with("+zip") || s.starts_with("vnd.openxmlformats") => {
+            MimeCategory::Compressed
+        }
+        _ => MimeCategory::Binary,
+    }
+}
+
+/// Request header overriding the compression mode for one download
+pub const COMPRESSION_MODE_HEADER: &str = "x-download-compression";
+
+/// Whether a download stored uncompressed is compressed on the fly
+///
+/// Compression still needs an `Accept-Encoding` the server supports, and
+/// objects stored with a `Content-Encoding` are never compressed again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionMode {
+    /// Compress unless the stored content type is already compressed
+    #[default]
+    Auto,
+    /// Compress whatever the stored content type
+    Force,
+    /// Send the stored bytes
+    Off,
+}
+
+impl FromStr for CompressionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "force" => Ok(Self::Force),
+            "off" => Ok(Self::Off),
+            other => Err(format!(
+                "unknown compression mode '{other}' (expected auto, force or off)"
+            )),
+        }
+    }
+}
+
+impl CompressionMode {
+    /// Mode requested with `X-Download-Compression`, if any
+    pub fn from_request(headers: &HeaderMap) -> Result<Option<Self>, String> {
+        headers
+            .get(COMPRESSION_MODE_HEADER)
+            .map(|value| {
+                value
+                    .to_str()
+                    .map_err(|_| "X-Download-Compression is not valid text".to_string())?
+                    .parse()
+                    .map_err(|e| format!("X-Download-Compression: {e}"))
+            })
+            .transpose()
+    }
+}
+
+/// Length of a response body before compression
+///
+/// Handlers attach it to responses carrying stored bytes so
+/// `measure_compression` can count the bytes saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UncompressedLength(pub u64);
+
+/// Decides from the object's content type and the compression mode
+///
+/// A `CompressionMode` response extension, set from the request header,
+/// takes precedence over the configured default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ObjectCompression {
+    default: CompressionMode,
+}
+
+impl Predicate for ObjectCompression {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        let mode = response
+            .extensions()
+            .get::<CompressionMode>()
+            .copied()
+            .unwrap_or(self.default);
+        match mode {
+            CompressionMode::Force => return true,
+            CompressionMode::Off => return false,
+            CompressionMode::Auto => {}
+        }
+
+        let mime = response
+            .extensions()
+            .get::<StoredContentType>()
+            .map(|content_type| content_type.0.as_str())
+            .or_else(|| {
+                response
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+            });
+
+        mime.is_none_or(|mime| category_from_mime(mime) != MimeCategory::Compressed)
+    }
+}
+
+/// Response compression configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseCompressionConfig {
+    pub gzip: bool,
+    pub br: bool,
+    pub zstd: bool,
+    /// Applies when the request doesn't set `X-Download-Compression`
+    #[serde(default)]
+    pub mode: CompressionMode,
+}
+
+impl Default for ResponseCompressionConfig {
+    fn default() -> Self {
+        Self {
+            gzip: true,
+            br: true,
+            zstd: true,
+            mode: CompressionMode::Auto,
+        }
+    }
+}
+
+impl ResponseCompressionConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Disable all algorithms
+    pub fn disabled() -> Self {
+        Self {
+            gzip: false,
+            br: false,
+            zstd: false,
+            mode: CompressionMode::Auto,
+        }
+    }
+
+    /// Set the default compression mode
+    pub fn with_mode(mut self, mode: CompressionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Parse a comma-separated list of algorithms (`gzip`, `br`, `zstd`),
+    /// or `none` to disable compression
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut config = Self::disabled();
+        for algorithm in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match algorithm.to_ascii_lowercase().as_str() {
+                "none" => {}
+                "gzip" => config.gzip = true,
+                "br" | "brotli" => config.br = true,
+                "zstd" => config.zstd = true,
+                other => {
+                    return Err(format!(
+                        "unknown compression algorithm '{other}' (expected gzip, br, zstd or none)"
+                    ))
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// Whether any algorithm is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.gzip || self.br || self.zstd
+    }
+
+    /// Build the compression layer
+    ///
+    /// With every algorithm disabled the layer passes responses through
+    /// unchanged, so routes keep a single service type either way.
+    pub fn layer(&self) -> CompressionLayer<impl Predicate> {
+        CompressionLayer::new()
+            .gzip(self.gzip)
+            .br(self.br)
+            .zstd(self.zstd)
+            .compress_when(SizeAbove::default().and(ObjectCompression { default: self.mode }))
+    }
+}
+
+/// Mark the ETag of a content-encoded response weak
+///
+/// Responses carrying the stored bytes of an encoded object (a
+/// `ContentEncoding` extension matching `Content-Encoding`) are left alone.
+/// Runs outside the compression layer, e.g. via `axum::middleware::map_response`.
+pub async fn weaken_encoded_etag<B>(mut response: Response<B>) -> Response<B> {
+    let Some(encoding) = response.headers().get(header::CONTENT_ENCODING) else {
+        return response;
+    };
+    let stored = response
+        .extensions()
+        .get::<ContentEncoding>()
+        .is_some_and(|stored| encoding.to_str().is_ok_and(|e| e == stored.to_string()));
+    if stored {
+        return response;
+    }
+    let weak = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"))
+        .and_then(|etag| HeaderValue::from_str(&format!("W/{etag}")).ok());
+    if let Some(weak) = weak {
+        response.headers_mut().insert(header::ETAG, weak);
+    }
+    response
+}
+
+/// Byte counts of compressed downloads
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CompressionStats {
+    /// Downloads sent compressed to completion
+    pub responses: u64,
+    /// Their size before compression
+    pub original_bytes: u64,
+    /// Their size as sent
+    pub sent_bytes: u64,
+    /// `original_bytes` less `sent_bytes`
+    pub bytes_saved: u64,
+}
+
+/// Counts what compressing downloads saved
+#[derive(Debug, Default)]
+pub struct CompressionMeter {
+    responses: AtomicU64,
+    original_bytes: AtomicU64,
+    sent_bytes: AtomicU64,
+}
+
+impl CompressionMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a compressed response that was sent in full
+    pub fn record(&self, original_bytes: u64, sent_bytes: u64) {
+        self.responses.fetch_add(1, Ordering::Relaxed);
+        self.original_bytes
+            .fetch_add(original_bytes, Ordering::Relaxed);
+        self.sent_bytes.fetch_add(sent_bytes, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> CompressionStats {
+        let original_bytes = self.original_bytes.load(Ordering::Relaxed);
+        let sent_bytes = self.sent_bytes.load(Ordering::Relaxed);
+        CompressionStats {
+            responses: self.responses.load(Ordering::Relaxed),
+            original_bytes,
+            sent_bytes,
+            // Tiny bodies can grow when compressed
+            bytes_saved: original_bytes.saturating_sub(sent_bytes),
+        }
+    }
+}
+
+/// Count the bytes sent for responses the compression layer encoded
+///
+/// Needs the `UncompressedLength` extension; responses sent as stored are
+/// skipped. A body is recorded once it has been streamed in full. Runs
+/// outside the compression layer, e.g. via
+/// `axum::middleware::map_response_with_state`.
+pub async fn measure_compression<B>(
+    State(meter): State<Arc<CompressionMeter>>,
+    response: Response<B>,
+) -> Response<Body>
+where
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    let original = response.extensions().get::<UncompressedLength>().copied();
+    let encoding = response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let (Some(UncompressedLength(original)), Some(encoding)) = (original, encoding) else {
+        return response.map(Body::new);
+    };
+
+    response.map(|body| {
+        Body::from_stream(MeteredBody {
+            inner: Body::new(body).into_data_stream(),
+            meter: Some(meter),
+            encoding,
+            original,
+            sent: 0,
+        })
+    })
+}
+
+/// Body stream that records its size once it ends
+struct MeteredBody {
+    inner: BodyDataStream,
+    /// Taken when the body ends or fails
+    meter: Option<Arc<CompressionMeter>>,
+    encoding: String,
+    original: u64,
+    sent: u64,
+}
+
+impl Stream for MeteredBody {
+    type Item = Result<Bytes, axum::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.inner.poll_next_unpin(cx));
+        match &item {
+            Some(Ok(chunk)) => self.sent += chunk.len() as u64,
+            Some(Err(_)) => self.meter = None,
+            None => {
+                if let Some(meter) = self.meter.take() {
+                    meter.record(self.original, self.sent);
+                    tracing::debug!(
+                        encoding = %self.encoding,
+                        original_bytes = self.original,
+                        sent_bytes = self.sent,
+                        "Compressed download sent"
+                    );
+                }
+            }
+        }
+        Poll::Ready(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::get,
+        Extension, Router,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const TEXT: &str = "the quick brown fox jumps over the lazy dog. ";
+
+    fn app(config: ResponseCompressionConfig) -> Router {
+        Router::new()
+            .route(
+                "/text",
+                get(|| async {
+                    (
+                        Extension(StoredContentType("text/csv".into())),
+                        [(header::ETAG, "\"abc\"")],
+                        TEXT.repeat(50),
+                    )
+                }),
+            )
+            .route(
+                "/zip",
+                get(|| async {
+                    (
+                        Extension(StoredContentType("application/zip".into())),
+                        [(header::ETAG, "\"abc\"")],
+                        TEXT.repeat(50),
+                    )
+                }),
+            )
+            .route(
+                "/zip-forced",
+                get(|| async {
+                    (
+                        Extension(StoredContentType("application/zip".into())),
+                        Extension(CompressionMode::Force),
+                        TEXT.repeat(50),
+                    )
+                }),
+            )
+            .route(
+                "/text-off",
+                get(|| async {
+                    (
+                        Extension(StoredContentType("text/csv".into())),
+                        Extension(CompressionMode::Off),
+                        TEXT.repeat(50),
+                    )
+                }),
+            )
+            .route(
+                "/stored-gzip",
+                get(|| async {
+                    (
+                        Extension(ContentEncoding::Gzip),
+                        [
+                            (header::ETAG, "\"abc\""),
+                            (header::CONTENT_ENCODING, "gzip"),
+                        ],
+                        TEXT.repeat(50),
+                    )
+                }),
+            )
+            .layer(config.layer())
+            .layer(middleware::map_response(weaken_encoded_etag))
+    }
+
+    async fn fetch(app: Router, path: &str, accept_encoding: &str) -> Response<Body> {
+        app.oneshot(
+            Request::builder()
+                .uri(path)
+                .header(header::ACCEPT_ENCODING, accept_encoding)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_category_from_mime() {
+        assert_eq!(
+            category_from_mime("text/plain; charset=utf-8"),
+            MimeCategory::Text
+        );
+        assert_eq!(
+            category_from_mime("application/ld+json"),
+            MimeCategory::Text
+        );
+        assert_eq!(category_from_mime("image/svg+xml"), MimeCategory::Text);
+        assert_eq!(category_from_mime("image/png"), MimeCategory::Compressed);
+        assert_eq!(
+            category_from_mime("Application/ZIP"),
+            MimeCategory::Compressed
+        );
+        assert_eq!(
+            category_from_mime(
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            ),
+            MimeCategory::Compressed
+        );
+        assert_eq!(
+            category_from_mime("application/octet-stream"),
+            MimeCategory::Binary
+        );
+        assert_eq!(category_from_mime("garbage"), MimeCategory::Binary);
+    }
+
+    #[test]
+    fn test_parse_algorithms() {
+        let config = ResponseCompressionConfig::parse("zstd, br").unwrap();
+        assert!(config.zstd && config.br && !config.gzip);
+        assert!(!ResponseCompressionConfig::parse("none")
+            .unwrap()
+            .is_enabled());
+        assert!(ResponseCompressionConfig::parse("lz4").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_negotiates_requested_encoding() {
+        let zstd = fetch(app(ResponseCompressionConfig::new()), "/text", "zstd").await;
+        let br = fetch(
+            app(ResponseCompressionConfig::new()),
+            "/text",
+            "gzip;q=0.5, br",
+        )
+        .await;
+
+        assert_eq!(zstd.status(), StatusCode::OK);
+        assert_eq!(zstd.headers()[header::CONTENT_ENCODING], "zstd");
+        assert_eq!(br.headers()[header::CONTENT_ENCODING], "br");
+    }
+
+    #[tokio::test]
+    async fn test_skips_precompressed_and_disabled() {
+        let zip = fetch(app(ResponseCompressionConfig::new()), "/zip", "gzip").await;
+        let disabled = fetch(app(ResponseCompressionConfig::disabled()), "/text", "gzip").await;
+
+        assert!(zip.headers().get(header::CONTENT_ENCODING).is_none());
+        assert!(disabled.headers().get(header::CONTENT_ENCODING).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_mode_overrides_default() {
+        let forced = fetch(app(ResponseCompressionConfig::new()), "/zip-forced", "gzip").await;
+        let off = fetch(app(ResponseCompressionConfig::new()), "/text-off", "gzip").await;
+        let default_off = fetch(
+            app(ResponseCompressionConfig::new().with_mode(CompressionMode::Off)),
+            "/text",
+            "gzip",
+        )
+        .await;
+
+        assert_eq!(forced.headers()[header::CONTENT_ENCODING], "gzip");
+        assert!(off.headers().get(header::CONTENT_ENCODING).is_none());
+        assert!(default_off
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .is_none());
+    }
+
+    #[test]
+    fn test_mode_from_request() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(CompressionMode::from_request(&headers), Ok(None));
+
+        headers.insert(COMPRESSION_MODE_HEADER, HeaderValue::from_static("Force"));
+        assert_eq!(
+            CompressionMode::from_request(&headers),
+            Ok(Some(CompressionMode::Force))
+        );
+
+        headers.insert(COMPRESSION_MODE_HEADER, HeaderValue::from_static("always"));
+        assert!(CompressionMode::from_request(&headers).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_measures_bytes_saved() {
+        let meter = Arc::new(CompressionMeter::new());
+        let app = Router::new()
+            .route(
+                "/text",
+                get(|| async {
+                    (
+                        Extension(StoredContentType("text/csv".into())),
+                        Extension(UncompressedLength(TEXT.len() as u64 * 50)),
+                        TEXT.repeat(50),
+                    )
+                }),
+            )
+            .layer(ResponseCompressionConfig::new().layer())
+            .layer(middleware::map_response_with_state(
+                Arc::clone(&meter),
+                measure_compression,
+            ));
+
+        let response = fetch(app, "/text", "gzip").await;
+        let sent = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        let stats = meter.stats();
+        assert_eq!(stats.responses, 1);
+        assert_eq!(stats.original_bytes, TEXT.len() as u64 * 50);
+        assert_eq!(stats.sent_bytes, sent.len() as u64);
+        assert!(stats.bytes_saved > 0);
+    }
+
+    #[tokio::test]
+    async fn test_compressed_response_has_weak_etag() {
+        let compressed = fetch(app(ResponseCompressionConfig::new()), "/text", "gzip").await;
+        let zip = fetch(app(ResponseCompressionConfig::new()), "/zip", "gzip").await;
+
+        assert_eq!(compressed.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(compressed.headers()[header::ETAG], "W/\"abc\"");
+        assert_eq!(zip.headers()[header::ETAG], "\"abc\"");
+    }
+
+    #[tokio::test]
+    async fn test_stored_encoding_keeps_strong_etag() {
+        let stored = fetch(app(ResponseCompressionConfig::new()), "/stored-gzip", "br").await;
+
+        // Sent as stored, not compressed again
+        assert_eq!(stored.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(stored.headers()[header::ETAG], "\"abc\"");
+    }
+}
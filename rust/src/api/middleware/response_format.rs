@@ -0,0 +1,310 @@
+//! JSON or MessagePack response bodies
+//!
+//! Clients that send `Accept: application/msgpack` get JSON responses
+//! re-encoded as MessagePack; everyone else gets JSON. Error responses are
+//! JSON bodies too, so they follow the same negotiation wherever they were
+//! produced. Only buffered JSON bodies are converted: downloads, event
+//! streams and other content types pass through untouched.
+//!
+//! `RESPONSE_MSGPACK_ENABLED` turns the negotiation off, in which case every
+//! response stays JSON whatever the client accepts.
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Media type of MessagePack responses
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Other names clients use for MessagePack
+const MSGPACK_ALIASES: [&str; 2] = ["application/x-msgpack", "application/vnd.msgpack"];
+
+/// Response encoding configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseFormatConfig {
+    /// Serve MessagePack to clients that prefer it
+    pub msgpack_enabled: bool,
+}
+
+impl Default for ResponseFormatConfig {
+    fn default() -> Self {
+        Self {
+            msgpack_enabled: true,
+        }
+    }
+}
+
+impl ResponseFormatConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable MessagePack responses
+    pub fn with_msgpack_enabled(mut self, msgpack_enabled: bool) -> Self {
+        self.msgpack_enabled = msgpack_enabled;
+        self
+    }
+}
+
+/// Encoding of a response body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    MessagePack,
+}
+
+impl ResponseFormat {
+    /// Format preferred by `Accept`
+    ///
+    /// MessagePack is chosen when the client names it with a quality at
+    /// least that of JSON (including `application/*` and `*/*`); a missing
+    /// or unrelated `Accept` means JSON.
+    pub fn negotiate(headers: &HeaderMap) -> Self {
+        let mut json = None::<f32>;
+        let mut msgpack = None::<f32>;
+        for range in headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+        {
+            let mut params = range.split(';');
+            let media_type = params
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            let slot = match media_type.as_str() {
+                MSGPACK_CONTENT_TYPE => &mut msgpack,
+                t if MSGPACK_ALIASES.contains(&t) => &mut msgpack,
+                "application/json" | "application/*" | "*/*" => &mut json,
+                _ => continue,
+            };
+            *slot = Some(slot.map_or(quality, |q| q.max(quality)));
+        }
+
+        match msgpack {
+            Some(q) if q > 0.0 && q >= json.unwrap_or(0.0) => Self::MessagePack,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// Whether a response carries a JSON body
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|essence| essence.trim().eq_ignore_ascii_case("application/json"))
+}
+
+/// Response encoding middleware
+pub async fn response_format_middleware(
+    config: &ResponseFormatConfig,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !config.msgpack_enabled {
+        return next.run(request).await;
+    }
+
+    let format = ResponseFormat::negotiate(request.headers());
+    let mut response = next.run(request).await;
+    if !is_json(&response) {
+        return response;
+    }
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept"));
+
+    // Streamed JSON has no exact size and is left alone rather than buffered
+    if format == ResponseFormat::Json || response.body().size_hint().exact().is_none() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let encoded = serde_json::from_slice::<Value>(&bytes)
+        .ok()
+        .and_then(|value| rmp_serde::to_vec_named(&value).ok());
+    let Some(encoded) = encoded else {
+        tracing::warn!("JSON response could not be encoded as MessagePack");
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(MSGPACK_CONTENT_TYPE),
+    );
+    Response::from_parts(parts, Body::from(encoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::errors::ApiError;
+    use crate::application::dto::TouchObjectResponse;
+    use crate::domain::value_objects::StorageClass;
+    use axum::{http::StatusCode, middleware, routing::get, Json, Router};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn touched() -> TouchObjectResponse {
+        TouchObjectResponse {
+            id: "0b4e3c6a-8d2f-4c1e-9a7b-5f6e4d3c2b1a".to_string(),
+            storage_class: StorageClass::Hot,
+            promoted: true,
+            last_accessed_at: "2026-10-16T08:00:00Z".to_string(),
+        }
+    }
+
+    fn app(config: ResponseFormatConfig) -> Router {
+        let config = Arc::new(config);
+        Router::new()
+            .route("/touched", get(|| async { Json(touched()) }))
+            .route(
+                "/missing",
+                get(|| async { Err::<(), _>(ApiError::not_found("Object not found")) }),
+            )
+            .route("/text", get(|| async { "plain" }))
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                async move { response_format_middleware(&config, req, next).await }
+            }))
+    }
+
+    async fn get_path(app: Router, path: &str, accept: Option<&str>) -> Response {
+        let mut request = Request::builder().uri(path);
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> Vec<u8> {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    fn negotiate(accept: &str) -> ResponseFormat {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(accept).unwrap());
+        ResponseFormat::negotiate(&headers)
+    }
+
+    #[test]
+    fn test_negotiation() {
+        assert_eq!(
+            ResponseFormat::negotiate(&HeaderMap::new()),
+            ResponseFormat::Json
+        );
+        assert_eq!(negotiate("application/json"), ResponseFormat::Json);
+        assert_eq!(negotiate("text/html, */*"), ResponseFormat::Json);
+        assert_eq!(
+            negotiate("application/msgpack"),
+            ResponseFormat::MessagePack
+        );
+        assert_eq!(
+            negotiate("application/x-msgpack"),
+            ResponseFormat::MessagePack
+        );
+        assert_eq!(
+            negotiate("application/msgpack, application/json"),
+            ResponseFormat::MessagePack
+        );
+        assert_eq!(
+            negotiate("application/json, application/msgpack;q=0.5"),
+            ResponseFormat::Json
+        );
+        assert_eq!(negotiate("application/msgpack;q=0"), ResponseFormat::Json);
+    }
+
+    #[tokio::test]
+    async fn test_json_round_trip() {
+        let response = get_path(app(ResponseFormatConfig::default()), "/touched", None).await;
+
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(response.headers()[header::VARY], "accept");
+        let decoded: TouchObjectResponse = serde_json::from_slice(&body(response).await).unwrap();
+        assert_eq!(decoded, touched());
+    }
+
+    #[tokio::test]
+    async fn test_msgpack_round_trip() {
+        let response = get_path(
+            app(ResponseFormatConfig::default()),
+            "/touched",
+            Some(MSGPACK_CONTENT_TYPE),
+        )
+        .await;
+
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            MSGPACK_CONTENT_TYPE
+        );
+        let decoded: TouchObjectResponse = rmp_serde::from_slice(&body(response).await).unwrap();
+        assert_eq!(decoded, touched());
+    }
+
+    #[tokio::test]
+    async fn test_errors_follow_negotiation() {
+        let response = get_path(
+            app(ResponseFormatConfig::default()),
+            "/missing",
+            Some(MSGPACK_CONTENT_TYPE),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            MSGPACK_CONTENT_TYPE
+        );
+        let decoded: Value = rmp_serde::from_slice(&body(response).await).unwrap();
+        assert_eq!(decoded["error"], "Object not found");
+    }
+
+    #[tokio::test]
+    async fn test_other_bodies_and_disabled_stay_unchanged() {
+        let text = get_path(
+            app(ResponseFormatConfig::default()),
+            "/text",
+            Some(MSGPACK_CONTENT_TYPE),
+        )
+        .await;
+        let disabled = get_path(
+            app(ResponseFormatConfig::new().with_msgpack_enabled(false)),
+            "/touched",
+            Some(MSGPACK_CONTENT_TYPE),
+        )
+        .await;
+
+        assert!(text.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
+        assert_eq!(body(text).await, b"plain");
+        assert_eq!(disabled.headers()[header::CONTENT_TYPE], "application/json");
+    }
+}
@@ -25,6 +25,9 @@ pub struct SizeLimitConfig {
     /// Maximum size of a single form field in bytes (default: 1MB)
     pub max_field_size: u64,
     /// Maximum size of uploaded files in bytes (default: 100MB)
+    ///
+    /// Checked against the declared Content-Length only; the upload use case
+    /// enforces `MAX_OBJECT_SIZE` on the bytes actually received.
     pub max_file_size: u64,
 }
 
@@ -432,12 +435,6 @@ pub fn create_size_limit_middleware_stack() -> Stack<FileUploadLimitLayer, Reque
     Stack::new(file_limits, request_limits)
 }
 
-/// Create concurrency limiting layers
-/// Note: This is a placeholder - concurrency limiting not yet implemented
-pub fn create_concurrency_limits(_config: &SizeLimitConfig) -> Vec<()> {
-    vec![]
-}
-
 /// Utility functions for size validation
 /// Check if a size is within acceptable limits
 pub fn validate_size(size: u64, max_size: u64, context: &str) -> Result<(), String> {
@@ -526,15 +523,6 @@ mod tests {
         assert!(middleware.config.max_request_size > 0);
     }
 
-    #[test]
-    fn test_concurrency_limits_creation() {
-        let config = SizeLimitConfig::default();
-        let limits = create_concurrency_limits(&config);
-
-        // Placeholder test - concurrency limiting not yet implemented
-        assert!(limits.is_empty());
-    }
-
     #[test]
     fn test_size_limit_utilities() {
         // Test the validation function error messages
@@ -0,0 +1,156 @@
+//! Storage class response headers
+//!
+//! Handlers that serve a single object attach its `StorageClass` as a
+//! response extension; this layer turns it into `X-Storage-Class` and,
+//! optionally, `X-Tier-Latency-Hint` so clients can decide whether to
+//! prefetch or show progress for cold retrievals.
+
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::StorageClass;
+
+/// Header carrying the object's storage class (`hot` / `cold`)
+pub const STORAGE_CLASS_HEADER: &str = "x-storage-class";
+
+/// Header carrying the expected retrieval latency (`low` / `high`)
+pub const TIER_LATENCY_HINT_HEADER: &str = "x-tier-latency-hint";
+
+/// Storage class header configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageClassHeadersConfig {
+    /// Send `X-Storage-Class` on single-object responses
+    pub enabled: bool,
+    /// Also send `X-Tier-Latency-Hint` (`low` for hot, `high` for cold)
+    pub latency_hint: bool,
+}
+
+impl Default for StorageClassHeadersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            latency_hint: false,
+        }
+    }
+}
+
+impl StorageClassHeadersConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable the storage class headers
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Enable or disable the latency hint header
+    pub fn with_latency_hint(mut self, latency_hint: bool) -> Self {
+        self.latency_hint = latency_hint;
+        self
+    }
+}
+
+/// Expected retrieval latency of a storage tier
+pub fn latency_hint(storage_class: StorageClass) -> &'static str {
+    match storage_class {
+        StorageClass::Hot => "low",
+        StorageClass::Cold => "high",
+    }
+}
+
+/// Storage class headers middleware
+pub async fn storage_class_headers_middleware(
+    config: &StorageClassHeadersConfig,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    if !config.enabled {
+        return response;
+    }
+
+    let Some(storage_class) = response.extensions().get::<StorageClass>().copied() else {
+        return response;
+    };
+
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&storage_class.to_string()) {
+        headers.insert(STORAGE_CLASS_HEADER, value);
+    }
+    if config.latency_hint {
+        headers.insert(
+            TIER_LATENCY_HINT_HEADER,
+            HeaderValue::from_static(latency_hint(storage_class)),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Extension, Router};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn app(config: StorageClassHeadersConfig) -> Router {
+        let config = Arc::new(config);
+        Router::new()
+            .route(
+                "/hot",
+                get(|| async { (Extension(StorageClass::Hot), "data") }),
+            )
+            .route(
+                "/cold",
+                get(|| async { (Extension(StorageClass::Cold), "data") }),
+            )
+            .route("/plain", get(|| async { "data" }))
+            .layer(middleware::from_fn(move |req, next| {
+                let config = Arc::clone(&config);
+                async move { storage_class_headers_middleware(&config, req, next).await }
+            }))
+    }
+
+    async fn get_path(app: Router, path: &str) -> Response {
+        app.oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_headers_reflect_storage_class() {
+        let config = StorageClassHeadersConfig::new().with_latency_hint(true);
+
+        let hot = get_path(app(config.clone()), "/hot").await;
+        let cold = get_path(app(config), "/cold").await;
+
+        assert_eq!(hot.headers()[STORAGE_CLASS_HEADER], "hot");
+        assert_eq!(hot.headers()[TIER_LATENCY_HINT_HEADER], "low");
+        assert_eq!(cold.headers()[STORAGE_CLASS_HEADER], "cold");
+        assert_eq!(cold.headers()[TIER_LATENCY_HINT_HEADER], "high");
+    }
+
+    #[tokio::test]
+    async fn test_latency_hint_off_by_default() {
+        let response = get_path(app(StorageClassHeadersConfig::default()), "/cold").await;
+
+        assert_eq!(response.headers()[STORAGE_CLASS_HEADER], "cold");
+        assert!(response.headers().get(TIER_LATENCY_HINT_HEADER).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_no_headers_without_storage_class_or_when_disabled() {
+        let plain = get_path(app(StorageClassHeadersConfig::default()), "/plain").await;
+        let disabled = get_path(
+            app(StorageClassHeadersConfig::new().with_enabled(false)),
+            "/hot",
+        )
+        .await;
+
+        assert!(plain.headers().get(STORAGE_CLASS_HEADER).is_none());
+        assert!(disabled.headers().get(STORAGE_CLASS_HEADER).is_none());
+    }
+}
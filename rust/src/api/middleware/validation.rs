@@ -1,46 +1,112 @@
 use axum::http::StatusCode;
 use validator::Validate;
 
+/// Stable `code` of validation error responses
+pub const VALIDATION_ERROR_CODE: &str = "VALIDATION_ERROR";
+
 /// Validation error response
 #[derive(serde::Serialize)]
 pub struct ValidationErrorResponse {
     pub error: String,
-    pub field_errors: Vec<FieldError>,
+    pub code: String,
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationErrorResponse {
+    pub fn new(errors: Vec<FieldError>) -> Self {
+        Self {
+            error: "Validation failed".to_string(),
+            code: VALIDATION_ERROR_CODE.to_string(),
+            errors,
+        }
+    }
 }
 
 /// Field error details
-#[derive(serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct FieldError {
     pub field: String,
     pub message: String,
 }
 
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
 /// Validate a payload and return a proper API error response
 pub fn validate_and_respond<T>(payload: &T) -> Result<(), (StatusCode, ValidationErrorResponse)>
 where
     T: Validate,
 {
     payload.validate().map_err(|validation_errors| {
-        let field_errors = validation_errors
+        let mut field_errors: Vec<FieldError> = validation_errors
             .field_errors()
             .iter()
             .flat_map(|(field, errors)| {
-                errors.iter().map(|error| FieldError {
-                    field: field.to_string(),
-                    message: error
-                        .message
-                        .as_ref()
-                        .map(|cow| cow.to_string())
-                        .unwrap_or_else(|| "Invalid value".to_string()),
+                errors.iter().map(|error| {
+                    FieldError::new(
+                        field.to_string(),
+                        error
+                            .message
+                            .as_ref()
+                            .map(|cow| cow.to_string())
+                            .unwrap_or_else(|| "Invalid value".to_string()),
+                    )
                 })
             })
             .collect();
+        // Field order of the underlying map is unspecified
+        field_errors.sort_by(|a, b| a.field.cmp(&b.field));
 
-        let response = ValidationErrorResponse {
-            error: "Validation failed".to_string(),
-            field_errors,
-        };
-
-        (StatusCode::UNPROCESSABLE_ENTITY, response)
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ValidationErrorResponse::new(field_errors),
+        )
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Validate)]
+    struct Request {
+        #[validate(length(min = 1, message = "must not be empty"))]
+        namespace: String,
+        #[validate(range(min = 1, max = 1000))]
+        limit: i64,
+    }
+
+    #[test]
+    fn test_validate_and_respond_lists_each_invalid_field() {
+        let (status, response) = validate_and_respond(&Request {
+            namespace: String::new(),
+            limit: 0,
+        })
+        .unwrap_err();
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.code, VALIDATION_ERROR_CODE);
+        assert_eq!(
+            response.errors,
+            vec![
+                FieldError::new("limit", "Invalid value"),
+                FieldError::new("namespace", "must not be empty"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_and_respond_accepts_valid_payload() {
+        assert!(validate_and_respond(&Request {
+            namespace: "models".to_string(),
+            limit: 10,
+        })
+        .is_ok());
+    }
+}
@@ -1,8 +1,11 @@
 pub mod errors;
+#[cfg(feature = "graphql")]
+pub mod graphql;
 pub mod handlers;
 pub mod internal;
 pub mod middleware;
 pub mod openapi;
 pub mod router;
+pub mod server;
 
 pub use router::{create_router, create_router_with_middleware};
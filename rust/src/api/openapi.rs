@@ -1,10 +1,26 @@
-use utoipa::OpenApi;
+use utoipa::openapi::RefOr;
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
+use crate::api::handlers::capabilities::{
+    AuthCapabilities, CapabilitiesResponse, CapabilityLimits, CompressionCapabilities,
+    FeatureCapabilities, RateLimitCapabilities, UploadCapabilities,
+};
+use crate::api::handlers::webhooks::WebhookEvent;
+use crate::api::middleware::request_latency::{LatencyBucket, LatencyReport, RouteLatency};
+use crate::api::middleware::response_format::MSGPACK_CONTENT_TYPE;
 use crate::application::dto::{
-    DateRange, DownloadMetadata, ListRequest, ListResponse, ObjectDto, SearchRequest,
-    SearchResponse, SizeRange, SortDirection, SortField, TextSearchRequest, TextSearchResponse,
-    UploadRequest,
+    ArchiveDownloadEntry, ArchiveDownloadManifest, ArchiveFormat, BlobDto,
+    BulkUpdateMetadataReport, BulkUpdateMetadataRequest, BulkUploadEntry, BulkUploadEntryStatus,
+    BulkUploadManifest, DateRange, DedupInfo, DedupStats, DeleteNamespaceReport, DownloadMetadata,
+    KeyPolicyDto, ListBlobsRequest, ListBlobsResponse, ListRequest, ListResponse, LockedObjectDto,
+    NamespaceConfigDto, NamespaceConfigListResponse, ObjectDto, ObjectField, ObjectProjection,
+    ObjectRecordDto, ObjectRetentionRequest, ObjectShareDto, ObjectShareListResponse,
+    ObjectStatusResponse, OperationItem, OperationProgressEvent, OperationState,
+    ProjectedListResponse, PutNamespaceConfigRequest, RegisterObjectLine, RegisterObjectResult,
+    RegisterObjectStatus, ResumableUploadDto, SearchRequest, SearchResponse, ShareObjectRequest,
+    SizeRange, SortDirection, SortField, StatsResponse, TenantDedupStats, TextSearchHit,
+    TextSearchRequest, TextSearchResponse, TouchObjectResponse, UploadRequest, UploadStatus,
 };
 
 /// OpenAPI specification for JustStorage API
@@ -20,42 +36,185 @@ use crate::application::dto::{
         (url = "https://api.juststorage.com", description = "Production server")
     ),
     paths(
-        crate::api::handlers::health::health_handler,
+        crate::api::handlers::health::liveness_handler,
         crate::api::handlers::health::readiness_handler,
+        crate::api::handlers::health::startup_handler,
+        crate::api::handlers::capabilities::capabilities_handler,
         crate::api::handlers::upload::upload_handler,
+        crate::api::handlers::resumable_upload::start_upload_handler,
+        crate::api::handlers::resumable_upload::resume_upload_handler,
+        crate::api::handlers::resumable_upload::upload_offset_handler,
+        crate::api::handlers::bulk_upload::bulk_upload_handler,
         crate::api::handlers::list::list_handler,
         crate::api::handlers::download::download_handler,
         crate::api::handlers::download::download_by_key_handler,
+        crate::api::handlers::download::head_handler,
+        crate::api::handlers::download::head_by_key_handler,
+        crate::api::handlers::download_archive::download_archive_handler,
+        crate::api::handlers::register_objects::register_objects_handler,
         crate::api::handlers::delete::delete_handler,
+        crate::api::handlers::metadata::get_metadata_handler,
+        crate::api::handlers::metadata::update_metadata_handler,
+        crate::api::handlers::retention::update_retention_handler,
+        crate::api::handlers::shares::share_object_handler,
+        crate::api::handlers::shares::list_shares_handler,
+        crate::api::handlers::shares::revoke_share_handler,
+        crate::api::handlers::shares::list_incoming_shares_handler,
+        crate::api::handlers::shares::accept_share_handler,
+        crate::api::handlers::touch::touch_handler,
+        crate::api::handlers::status::object_status_handler,
+        crate::api::handlers::operations::operation_events_handler,
         crate::api::handlers::search::search_handler,
         crate::api::handlers::text_search::text_search_handler,
+        crate::api::handlers::stats::stats_handler,
+        crate::api::handlers::blobs::list_blobs_handler,
+        crate::api::handlers::blobs::get_blob_handler,
+        crate::api::handlers::latency::latency_handler,
+        crate::api::handlers::namespaces::list_namespace_configs_handler,
+        crate::api::handlers::namespaces::get_namespace_config_handler,
+        crate::api::handlers::namespaces::put_namespace_config_handler,
+        crate::api::handlers::namespaces::delete_namespace_config_handler,
+        crate::api::handlers::namespaces::delete_namespace_objects_handler,
+        crate::api::handlers::namespaces::bulk_update_metadata_handler,
+        crate::api::handlers::webhooks::inbound_webhook_handler,
     ),
     components(
         schemas(
             ObjectDto,
+            ObjectRecordDto,
+            DedupInfo,
             UploadRequest,
+            ResumableUploadDto,
+            BulkUploadManifest,
+            BulkUploadEntry,
+            BulkUploadEntryStatus,
+            ArchiveFormat,
+            ArchiveDownloadManifest,
+            ArchiveDownloadEntry,
+            RegisterObjectLine,
+            RegisterObjectResult,
+            RegisterObjectStatus,
             ListRequest,
             ListResponse,
+            ObjectField,
+            ObjectProjection,
+            ProjectedListResponse,
             SearchRequest,
             SearchResponse,
             TextSearchRequest,
             TextSearchResponse,
+            TextSearchHit,
             DownloadMetadata,
+            ObjectStatusResponse,
+            ObjectRetentionRequest,
+            ShareObjectRequest,
+            ObjectShareDto,
+            ObjectShareListResponse,
+            TouchObjectResponse,
+            UploadStatus,
+            OperationProgressEvent,
+            OperationState,
+            OperationItem,
             SortField,
             SortDirection,
             DateRange,
             SizeRange,
+            DedupStats,
+            TenantDedupStats,
+            StatsResponse,
+            BlobDto,
+            ListBlobsRequest,
+            ListBlobsResponse,
+            LatencyReport,
+            RouteLatency,
+            LatencyBucket,
+            NamespaceConfigDto,
+            NamespaceConfigListResponse,
+            PutNamespaceConfigRequest,
+            KeyPolicyDto,
+            DeleteNamespaceReport,
+            BulkUpdateMetadataRequest,
+            BulkUpdateMetadataReport,
+            LockedObjectDto,
+            WebhookEvent,
+            CapabilitiesResponse,
+            CapabilityLimits,
+            UploadCapabilities,
+            CompressionCapabilities,
+            RateLimitCapabilities,
+            AuthCapabilities,
+            FeatureCapabilities,
         )
     ),
+    modifiers(&MessagePackResponses),
     tags(
         (name = "health", description = "Health check endpoints"),
+        (name = "capabilities", description = "Server limits and supported features"),
         (name = "objects", description = "Object storage operations"),
-        (name = "search", description = "Search and filtering operations")
+        (name = "search", description = "Search and filtering operations"),
+        (name = "stats", description = "Storage usage statistics"),
+        (name = "admin", description = "Inspection of the blob layer"),
+        (name = "namespaces", description = "Per-namespace defaults"),
+        (name = "operations", description = "Progress of long-running operations"),
+        (name = "webhooks", description = "Signed callbacks from external systems")
     )
 )]
 pub struct ApiDoc;
 
+/// Documents every JSON response body as available in MessagePack too
+///
+/// Responses are re-encoded by the response format middleware rather than
+/// per handler, so the alternative is added here instead of on each path.
+struct MessagePackResponses;
+
+impl Modify for MessagePackResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+                &mut item.head,
+            ];
+            for operation in operations.into_iter().flatten() {
+                for response in operation.responses.responses.values_mut() {
+                    let RefOr::T(response) = response else {
+                        continue;
+                    };
+                    if let Some(json) = response.content.get("application/json").cloned() {
+                        response
+                            .content
+                            .insert(MSGPACK_CONTENT_TYPE.to_string(), json);
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Create the Swagger UI route
 pub fn swagger_ui() -> SwaggerUi {
     SwaggerUi::new("/swagger-ui/*tail").url("/api-docs/openapi.json", ApiDoc::openapi())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_responses_are_documented_in_msgpack_too() {
+        let openapi = ApiDoc::openapi();
+        let touch = openapi.paths.paths["/v1/objects/{id}/touch"]
+            .post
+            .as_ref()
+            .unwrap();
+        let RefOr::T(ok) = &touch.responses.responses["200"] else {
+            panic!("inline response expected");
+        };
+
+        assert!(ok.content.contains_key("application/json"));
+        assert!(ok.content.contains_key(MSGPACK_CONTENT_TYPE));
+    }
+}
@@ -1,36 +1,86 @@
 use axum::{
     http::StatusCode,
     middleware as axum_middleware,
-    routing::{delete, get, post},
+    routing::{delete, get, head, patch, post},
     Router,
 };
 use sqlx::PgPool;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use crate::api::handlers::{
+    accept_share_handler,
     api_keys::{
         create_api_key_handler, delete_api_key_handler, get_api_key_handler, list_api_keys_handler,
-        update_api_key_handler,
+        rotate_api_key_handler, update_api_key_handler,
     },
-    delete_handler, download_by_key_handler, download_handler, health_handler, list_handler,
-    readiness_handler, search, text_search, upload_handler,
+    body_buffer::BodyBufferConfig,
+    bulk_update_metadata_handler,
+    bulk_upload::BulkUploadState,
+    bulk_upload_handler,
+    capabilities::CapabilitiesResponse,
+    capabilities_handler, delete_handler, delete_namespace_config_handler,
+    delete_namespace_objects_handler, download_archive_handler, download_by_key_handler,
+    download_handler, get_blob_handler, get_metadata_handler, get_namespace_config_handler,
+    head_by_key_handler, head_handler, inbound_webhook_handler, latency_handler,
+    list_blobs_handler, list_handler, list_incoming_shares_handler, list_namespace_configs_handler,
+    list_shares_handler, liveness_handler,
+    namespaces::{BulkUpdateMetadataState, DeleteNamespaceState},
+    object_status_handler, operation_events_handler, put_namespace_config_handler,
+    readiness_handler, register_objects_handler, resume_upload_handler, revoke_share_handler,
+    search, share_object_handler, start_upload_handler, startup_handler, stats_handler,
+    text_search, touch_handler, update_metadata_handler, update_retention_handler, upload_handler,
+    upload_offset_handler,
+    webhooks::WebhookState,
 };
 use crate::api::internal::create_internal_router;
 use crate::api::middleware::{
-    authorization, config::MiddlewareConfig, content_type, factory::MiddlewareFactory, size_limits,
+    access_log,
+    api_version::{self, ApiVersion},
+    authorization,
+    config::MiddlewareConfig,
+    content_type,
+    error_handling::{ErrorDetail, ErrorHandlingConfig},
+    factory::MiddlewareFactory,
+    header_limits::{self, HeaderLimitConfig},
+    https_redirect::{self, HttpsRedirectConfig},
+    input_sanitization::InputSanitizationConfig,
+    oidc_config::OidcConfig,
+    rate_limiting::{ConcurrencyLimitLayer, ConcurrencyLimiter},
+    request_id::{self, RequestIdConfig},
+    request_latency::RequestLatency,
+    request_timeout::{self, RequestTimeoutConfig, TimeoutClass},
+    response_compression::{self, CompressionMeter, ResponseCompressionConfig},
+    response_format::{self, ResponseFormatConfig},
+    security_headers::{SecurityHeadersConfig, SecurityHeadersMiddleware},
+    size_limits,
+    storage_class_headers::{self, StorageClassHeadersConfig},
 };
 use crate::api::openapi::ApiDoc;
+use crate::api::server::ConnectionLimiter;
+use crate::application::access_stats::AccessRecorder;
 use crate::application::gc::GarbageCollector;
-use crate::application::ports::{ApiKeyRepository, AuditRepository, BlobStore};
+use crate::application::operation_progress::OperationProgress;
+use crate::application::ports::{
+    ApiKeyRepository, AuditRepository, BlobStore, TenantLimitProvider,
+};
+use crate::application::scrub::BlobScrubber;
 use crate::application::use_cases::{
-    CreateApiKeyUseCase, DeleteApiKeyUseCase, DeleteObjectUseCase, DownloadObjectUseCase,
-    GetApiKeyUseCase, ListApiKeysUseCase, ListObjectsUseCase, SearchObjectsUseCase,
-    TextSearchObjectsUseCase, UpdateApiKeyUseCase, UploadObjectUseCase,
+    BulkUpdateMetadataUseCase, BulkUploadUseCase, CompactionUseCase, CreateApiKeyUseCase,
+    DeleteApiKeyUseCase, DeleteNamespaceUseCase, DeleteObjectUseCase, DownloadArchiveUseCase,
+    DownloadObjectUseCase, GetApiKeyUseCase, ListApiKeysUseCase, ListBlobsUseCase,
+    ListObjectsUseCase, NamespaceConfigUseCase, ObjectRetentionUseCase, ObjectStatusUseCase,
+    ReconcileRefcountsUseCase, RegisterObjectsUseCase, RotateApiKeyUseCase, SearchObjectsUseCase,
+    ShareObjectUseCase, StatsUseCase, TextSearchObjectsUseCase, TouchObjectUseCase,
+    UpdateApiKeyUseCase, UpdateObjectMetadataUseCase, UploadObjectUseCase, MAX_STATUS_WAIT,
 };
+use crate::application::webhooks::WebhookVerifier;
 use axum::routing::put;
 use utoipa::OpenApi;
 
 use crate::config::Config;
+use crate::infrastructure::persistence::{FilteredBlobRepository, PoolMonitor, RetryPolicy};
+use crate::infrastructure::storage::{CachingBlobStore, ResilientBlobStore};
 
 use std::time::Instant;
 
@@ -38,25 +88,69 @@ use std::time::Instant;
 #[derive(Clone)]
 pub struct AppState {
     pub pool: Arc<PgPool>,
+    /// Shared by the object and blob repositories; counts transient-error retries
+    pub db_retry_policy: RetryPolicy,
+    /// Samples pool usage for readiness; reconnects a dead pool
+    pub pool_monitor: Arc<PoolMonitor>,
     pub upload_use_case: Arc<UploadObjectUseCase>,
+    pub bulk_upload_use_case: Arc<BulkUploadUseCase>,
     pub download_use_case: Arc<DownloadObjectUseCase>,
+    pub download_archive_use_case: Arc<DownloadArchiveUseCase>,
+    /// Bulk register of objects over blobs already in the store (admin only)
+    pub register_objects_use_case: Arc<RegisterObjectsUseCase>,
     pub delete_use_case: Arc<DeleteObjectUseCase>,
+    pub update_metadata_use_case: Arc<UpdateObjectMetadataUseCase>,
+    pub object_retention_use_case: Arc<ObjectRetentionUseCase>,
+    pub share_object_use_case: Arc<ShareObjectUseCase>,
+    pub touch_object_use_case: Arc<TouchObjectUseCase>,
+    pub object_status_use_case: Arc<ObjectStatusUseCase>,
     pub list_use_case: Arc<ListObjectsUseCase>,
     pub search_use_case: Arc<SearchObjectsUseCase>,
     pub text_search_use_case: Arc<TextSearchObjectsUseCase>,
+    pub stats_use_case: Arc<StatsUseCase>,
+    pub list_blobs_use_case: Arc<ListBlobsUseCase>,
+    pub reconcile_refcounts_use_case: Arc<ReconcileRefcountsUseCase>,
+    pub compaction_use_case: Arc<CompactionUseCase>,
+    pub namespace_config_use_case: Arc<NamespaceConfigUseCase>,
+    pub delete_namespace_use_case: Arc<DeleteNamespaceUseCase>,
+    pub bulk_update_metadata_use_case: Arc<BulkUpdateMetadataUseCase>,
     pub create_api_key_use_case: Arc<CreateApiKeyUseCase>,
     pub list_api_keys_use_case: Arc<ListApiKeysUseCase>,
     pub get_api_key_use_case: Arc<GetApiKeyUseCase>,
     pub update_api_key_use_case: Arc<UpdateApiKeyUseCase>,
     pub delete_api_key_use_case: Arc<DeleteApiKeyUseCase>,
+    pub rotate_api_key_use_case: Arc<RotateApiKeyUseCase>,
     pub audit_repo: Arc<dyn AuditRepository>,
     pub blob_store: Arc<dyn BlobStore>,
+    /// Set when the blob read cache is enabled; `blob_store` reads through it
+    pub blob_cache: Option<Arc<CachingBlobStore>>,
+    /// Circuit breakers of the blob store backends when retries are enabled
+    pub blob_store_breakers: Vec<(String, Arc<ResilientBlobStore>)>,
+    /// Bytes saved by compressing downloads
+    pub download_compression: Arc<CompressionMeter>,
+    /// Handler latencies per route; logs slow requests
+    pub request_latency: Arc<RequestLatency>,
+    /// Set when upload dedup lookups go through the content hash filter
+    pub blob_filter: Option<Arc<FilteredBlobRepository>>,
+    /// Requests in flight per user, tenant and IP
+    pub concurrency_limiter: Arc<ConcurrencyLimiter>,
+    /// Connections open per remote IP on the main listener
+    pub connection_limiter: Arc<ConnectionLimiter>,
+    /// Progress of archive imports and namespace deletes, for event streams
+    pub operation_progress: Arc<OperationProgress>,
+    pub tenant_limit_provider: Arc<dyn TenantLimitProvider>,
+    /// Shared secrets for inbound webhooks; without any, the route is not added
+    pub webhook_verifier: Arc<WebhookVerifier>,
     pub gc: Option<Arc<GarbageCollector>>,
+    pub access_recorder: Option<Arc<AccessRecorder>>,
+    pub scrubber: Option<Arc<BlobScrubber>>,
     pub config: Config,
     pub oidc_metadata: Option<openidconnect::core::CoreProviderMetadata>,
     pub jwks_cache: Arc<moka::future::Cache<String, jsonwebtoken::DecodingKey>>,
     pub expected_migration_count: usize,
     pub start_time: Instant,
+    /// Set once graceful shutdown begins; flips the liveness and readiness probes
+    pub shutting_down: Arc<AtomicBool>,
 }
 
 impl AppState {
@@ -79,8 +173,60 @@ pub async fn create_router(
     middleware_config.oidc.enabled = crate::config::parse_bool_env("OIDC_ENABLED", true);
     middleware_config.oidc.issuer_url = state.config.oidc_issuer_url.clone();
     middleware_config.oidc.audience = state.config.oidc_audience.clone();
+    middleware_config.oidc.jwks_url = state.config.jwt_jwks_url.clone();
+    middleware_config.oidc.leeway_secs = state.config.jwt_leeway_secs;
+    // Validated at startup; keep the default asymmetric algorithms otherwise
+    if let Ok(algorithms) = OidcConfig::parse_algorithms(&state.config.jwt_algorithms) {
+        middleware_config.oidc.algorithms = algorithms;
+    }
     middleware_config.size_limits.max_request_size = state.config.max_upload_size_bytes;
     middleware_config.size_limits.max_file_size = state.config.max_upload_size_bytes;
+    middleware_config.header_limits = HeaderLimitConfig::new()
+        .with_max_header_count(state.config.max_request_headers)
+        .with_max_header_bytes(state.config.max_request_header_bytes)
+        .with_max_url_length(state.config.max_url_length);
+    // Validated at startup; keep the standard level otherwise
+    middleware_config.input_sanitization = InputSanitizationConfig::for_level(
+        state.config.sanitization_level.parse().unwrap_or_default(),
+    );
+    middleware_config.rate_limiting.tenant_limit_multiplier =
+        state.config.tenant_rate_limit_multiplier;
+    middleware_config
+        .rate_limiting
+        .tenant_limit_cache_ttl_seconds = state.config.tenant_rate_limit_cache_ttl_secs;
+    middleware_config.https_redirect =
+        HttpsRedirectConfig::new().with_enabled(state.config.enforce_https);
+    middleware_config.request_id =
+        RequestIdConfig::new().with_header_name(state.config.request_id_header.clone());
+    middleware_config.request_timeout = RequestTimeoutConfig::new()
+        .with_default_secs(state.config.request_timeout_secs)
+        .with_short_secs(state.config.request_timeout_short_secs)
+        .with_transfer_secs(state.config.request_timeout_transfer_secs);
+    middleware_config.storage_class_headers = StorageClassHeadersConfig::new()
+        .with_enabled(state.config.storage_class_headers)
+        .with_latency_hint(state.config.tier_latency_hint);
+    // Validated at startup; fall back to no compression rather than failing here
+    middleware_config.response_compression =
+        ResponseCompressionConfig::parse(&state.config.response_compression)
+            .unwrap_or_else(|_| ResponseCompressionConfig::disabled())
+            .with_mode(
+                state
+                    .config
+                    .response_compression_mode
+                    .parse()
+                    .unwrap_or_default(),
+            );
+    middleware_config.response_format =
+        ResponseFormatConfig::new().with_msgpack_enabled(state.config.response_msgpack_enabled);
+    // Validated at startup; fall back to the local-only default allowlist
+    middleware_config.cors = state.config.cors().unwrap_or_default();
+    // Validated at startup; fall back to sanitized errors
+    middleware_config.error_handling = ErrorHandlingConfig::new()
+        .with_detail(ErrorDetail::parse(&state.config.error_detail).unwrap_or_default());
+    // Validated at startup; fall back to serving v1 only
+    middleware_config.api_version = state.config.api_versions().unwrap_or_default();
+    // Validated at startup; fall back to no access log
+    middleware_config.access_log = state.config.access_log().unwrap_or_default();
     create_router_with_middleware(state, api_key_repo, audit_repo, middleware_config).await
 }
 
@@ -92,6 +238,7 @@ pub async fn create_router_with_middleware(
     middleware_config: MiddlewareConfig,
 ) -> Router {
     let middleware_factory = MiddlewareFactory::new(middleware_config);
+    let timeouts = &middleware_factory.config().request_timeout;
 
     // 3. Internal routes (have their own middleware and auth)
     let internal_router = create_internal_router(state.clone()).await;
@@ -107,15 +254,76 @@ pub async fn create_router_with_middleware(
     // 2. Public routes (no auth, no main middleware)
     let mut public_router = Router::new();
     public_router = add_health_routes(public_router, &state);
+    public_router = public_router.layer(axum_middleware::from_fn_with_state(
+        timeouts
+            .route(TimeoutClass::Short)
+            .with_latency(Arc::clone(&state.request_latency)),
+        request_timeout::request_timeout_middleware,
+    ));
     public_router = add_openapi_routes(public_router);
 
     // Merge public routes into main router
     router = router.merge(public_router);
 
+    // Webhooks are signed with tenant secrets instead of using API keys
+    if state.webhook_verifier.is_enabled() {
+        let webhook_router = add_webhook_routes(Router::new(), &state, middleware_factory.config());
+        router = router.merge(apply_webhook_middleware_stack(
+            webhook_router,
+            &middleware_factory,
+            Arc::clone(&audit_repo),
+            Arc::clone(&state.tenant_limit_provider),
+        ));
+    }
+
     // 3. API routes (require main middleware stack including auth)
     let mut api_router = Router::new();
-    api_router = add_object_routes(api_router, &state);
     api_router = add_api_key_routes(api_router, &state);
+    api_router = add_capabilities_routes(api_router, &state, middleware_factory.config());
+    api_router = add_stats_routes(api_router, &state);
+    api_router = add_blob_routes(api_router, &state);
+    api_router = add_namespace_routes(api_router, &state);
+    api_router = add_share_routes(api_router, &state);
+    #[cfg(feature = "graphql")]
+    {
+        api_router = add_graphql_routes(api_router, &state);
+    }
+    // Layers only wrap routes added before them: object routes set their own timeouts
+    api_router = api_router.layer(axum_middleware::from_fn_with_state(
+        timeouts
+            .route(TimeoutClass::Default)
+            .with_latency(Arc::clone(&state.request_latency)),
+        request_timeout::request_timeout_middleware,
+    ));
+    for version in [ApiVersion::V1, ApiVersion::V2] {
+        if middleware_factory.config().api_version.serves(version) {
+            api_router =
+                add_object_routes(api_router, &state, middleware_factory.config(), version);
+        }
+    }
+    api_router = add_operation_routes(api_router, &state);
+
+    // Runs inside authentication, before any handler sees the request
+    api_router = api_router
+        .layer(axum_middleware::from_fn(access_log::record_tenant))
+        .layer(axum_middleware::from_fn(
+            authorization::require_tenant_isolation,
+        ));
+
+    // Storage class headers are derived from handler response extensions
+    let storage_class_headers_config =
+        Arc::new(middleware_factory.config().storage_class_headers.clone());
+    api_router = api_router.layer(axum_middleware::from_fn(move |req, next| {
+        let storage_class_headers_config = Arc::clone(&storage_class_headers_config);
+        async move {
+            storage_class_headers::storage_class_headers_middleware(
+                &storage_class_headers_config,
+                req,
+                next,
+            )
+            .await
+        }
+    }));
 
     // Apply middleware stack only to API routes
     api_router = apply_middleware_stack(
@@ -124,33 +332,102 @@ pub async fn create_router_with_middleware(
         Arc::clone(&api_key_repo),
         audit_repo,
         state.jwks_cache.clone(),
+        Arc::clone(&state.tenant_limit_provider),
+        Arc::clone(&state.concurrency_limiter),
     );
 
     // Merge API router into main router
     router = router.merge(api_router);
 
-    // Apply global middleware (security headers, etc.) to the entire application
+    // Accept-Version picks the routes of a versioned request, so it has to
+    // rewrite the path before the router matches it
+    let api_version_config = Arc::new(middleware_factory.config().api_version.clone());
+    let accept_version = axum_middleware::from_fn(move |req, next| {
+        let api_version_config = Arc::clone(&api_version_config);
+        async move { api_version::accept_version_middleware(&api_version_config, req, next).await }
+    });
+    router = Router::new().fallback_service(router).layer(accept_version);
+
+    // Outside every layer that writes JSON bodies, so errors from any of them
+    // are re-encoded for MessagePack clients too
+    let response_format_config = Arc::new(middleware_factory.config().response_format.clone());
+    router = router.layer(axum_middleware::from_fn(move |req, next| {
+        let response_format_config = Arc::clone(&response_format_config);
+        async move {
+            response_format::response_format_middleware(&response_format_config, req, next).await
+        }
+    }));
+
+    // Apply global middleware (security headers, request ID, etc.) to the entire
+    // application. The request ID layer is outermost so every other layer and
+    // handler logs inside its span.
+    // HSTS is only sent by the HTTPS redirect layer, so proxied deployments
+    // that leave it disabled never advertise it.
+    let request_id_config = Arc::new(middleware_factory.config().request_id.clone());
+    let header_limits_config = Arc::new(middleware_factory.config().header_limits.clone());
+    let https_redirect_config = Arc::new(middleware_factory.config().https_redirect.clone());
+    let security_headers_config = Arc::new(SecurityHeadersConfig {
+        hsts_max_age: None,
+        ..middleware_factory.config().security_headers.clone()
+    });
     router = router
-        .layer(axum_middleware::from_fn(|req, next| async move {
-            crate::api::middleware::security_headers::SecurityHeadersMiddleware::default()
-                .layer(req, next)
-                .await
+        .layer(axum_middleware::from_fn(move |req, next| {
+            let security_headers_config = Arc::clone(&security_headers_config);
+            async move {
+                SecurityHeadersMiddleware::layer_with_config(req, next, security_headers_config)
+                    .await
+            }
         }))
         .layer(axum_middleware::from_fn(
             crate::api::middleware::security_headers::RequestSanitizationMiddleware::layer,
-        ));
+        ))
+        .layer(axum_middleware::from_fn(move |req, next| {
+            let https_redirect_config = Arc::clone(&https_redirect_config);
+            async move {
+                https_redirect::https_redirect_middleware(&https_redirect_config, req, next).await
+            }
+        }))
+        // Oversized request heads are turned away before any other work
+        .layer(axum_middleware::from_fn(move |req, next| {
+            let header_limits_config = Arc::clone(&header_limits_config);
+            async move {
+                header_limits::header_limits_middleware(&header_limits_config, req, next).await
+            }
+        }));
+    // Inside the request ID layer so records carry the request's ID
+    if middleware_factory.config().access_log.enabled {
+        let access_log_config = Arc::new(middleware_factory.config().access_log.clone());
+        router = router.layer(axum_middleware::from_fn(move |req, next| {
+            let access_log_config = Arc::clone(&access_log_config);
+            async move { access_log::access_log_middleware(&access_log_config, req, next).await }
+        }));
+    }
+    router = router.layer(axum_middleware::from_fn(move |req, next| {
+        let request_id_config = Arc::clone(&request_id_config);
+        async move { request_id::request_id_middleware(&request_id_config, req, next).await }
+    }));
 
     router
 }
 
 /// Add health check routes
+///
+/// `/health` is kept as an alias of the liveness probe.
 fn add_health_routes(router: Router, state: &AppState) -> Router {
     router
-        .route("/health", get(health_handler))
+        .route("/health", get(liveness_handler).with_state(state.clone()))
+        .route(
+            "/health/live",
+            get(liveness_handler).with_state(state.clone()),
+        )
         .route(
             "/health/ready",
             get(readiness_handler).with_state(state.clone()),
         )
+        .route(
+            "/health/startup",
+            get(startup_handler).with_state(state.clone()),
+        )
         .route("/favicon.ico", get(|| async { StatusCode::NO_CONTENT }))
 }
 
@@ -162,65 +439,320 @@ fn add_openapi_routes(router: Router) -> Router {
     )
 }
 
-/// Add object management routes
-fn add_object_routes(router: Router, state: &AppState) -> Router {
+/// Add inbound webhook routes
+///
+/// Committing an upload hashes its content, so they get the transfer timeout.
+fn add_webhook_routes(
+    router: Router,
+    state: &AppState,
+    middleware_config: &MiddlewareConfig,
+) -> Router {
+    let webhook_state = WebhookState {
+        verifier: Arc::clone(&state.webhook_verifier),
+        upload_use_case: Arc::clone(&state.upload_use_case),
+    };
+    router.route(
+        "/v1/webhooks/{tenant_id}",
+        post(inbound_webhook_handler)
+            .layer(axum_middleware::from_fn_with_state(
+                middleware_config
+                    .request_timeout
+                    .route(TimeoutClass::Transfer)
+                    .with_latency(Arc::clone(&state.request_latency)),
+                request_timeout::request_timeout_middleware,
+            ))
+            .with_state(webhook_state),
+    )
+}
+
+/// Add object management routes of an API version
+///
+/// Every version shares the use cases; a version whose DTOs diverge gets its
+/// own handlers here. Downloads get response compression; other object
+/// routes return small JSON. Uploads and downloads get the transfer timeout,
+/// metadata reads the short one.
+fn add_object_routes(
+    router: Router,
+    state: &AppState,
+    middleware_config: &MiddlewareConfig,
+    version: ApiVersion,
+) -> Router {
+    let path = |route: &str| format!("{}/objects{route}", version.prefix());
+    let timeouts = &middleware_config.request_timeout;
+    let timeout = |class| {
+        axum_middleware::from_fn_with_state(
+            timeouts
+                .route(class)
+                .with_latency(Arc::clone(&state.request_latency)),
+            request_timeout::request_timeout_middleware,
+        )
+    };
     let upload_state = Arc::clone(&state.upload_use_case);
+    let bulk_upload_state = BulkUploadState {
+        use_case: Arc::clone(&state.bulk_upload_use_case),
+        operations: Arc::clone(&state.operation_progress),
+        body_buffer: Arc::new(
+            BodyBufferConfig::new()
+                .with_memory_limit_bytes(state.config.request_body_memory_limit_bytes)
+                .with_temp_dir(state.config.blob_temp_dir.clone()),
+        ),
+    };
+    let size_limit_config = Arc::new(middleware_config.size_limits.clone());
+    let compression = &middleware_config.response_compression;
     let download_state = Arc::clone(&state.download_use_case);
+    let download_archive_state = Arc::clone(&state.download_archive_use_case);
+    let register_objects_state = Arc::clone(&state.register_objects_use_case);
     let delete_state = Arc::clone(&state.delete_use_case);
+    let update_metadata_state = Arc::clone(&state.update_metadata_use_case);
+    let retention_state = Arc::clone(&state.object_retention_use_case);
+    let share_state = Arc::clone(&state.share_object_use_case);
+    let touch_state = Arc::clone(&state.touch_object_use_case);
+    let object_status_state = Arc::clone(&state.object_status_use_case);
     let list_state = Arc::clone(&state.list_use_case);
     let search_state = Arc::clone(&state.search_use_case);
     let text_search_state = Arc::clone(&state.text_search_use_case);
 
-    router
+    let api_version_config = Arc::new(middleware_config.api_version.clone());
+
+    let objects = Router::new()
         // Object CRUD operations
         .route(
-            "/v1/objects",
+            &path(""),
             post(upload_handler)
                 .layer(axum_middleware::from_fn(
                     authorization::require_object_write,
                 ))
+                .layer(timeout(TimeoutClass::Transfer))
+                .with_state(Arc::clone(&upload_state)),
+        )
+        // Resumable uploads: start, send chunks, read the offset
+        .route(
+            &path("/uploads"),
+            post(start_upload_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(timeout(TimeoutClass::Default))
+                .with_state(Arc::clone(&upload_state)),
+        )
+        .route(
+            &path("/uploads/{id}"),
+            put(resume_upload_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(timeout(TimeoutClass::Transfer))
+                .with_state(Arc::clone(&upload_state)),
+        )
+        .route(
+            &path("/uploads/{id}"),
+            head(upload_offset_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(timeout(TimeoutClass::Short))
                 .with_state(upload_state),
         )
         .route(
-            "/v1/objects",
+            &path("/archive"),
+            post(bulk_upload_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(axum_middleware::from_fn(move |req, next| {
+                    let size_limit_config = Arc::clone(&size_limit_config);
+                    async move {
+                        size_limits::FileUploadLimitMiddleware::layer_with_config(
+                            req,
+                            next,
+                            size_limit_config,
+                        )
+                        .await
+                    }
+                }))
+                .layer(timeout(TimeoutClass::Transfer))
+                .with_state(bulk_upload_state),
+        )
+        .route(
+            &path(""),
             get(list_handler)
                 .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Short))
                 .with_state(list_state),
         )
         .route(
-            "/v1/objects/{id}",
+            &path(":archive"),
+            get(download_archive_handler)
+                .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Transfer))
+                .with_state(download_archive_state),
+        )
+        .route(
+            &path(":register"),
+            post(register_objects_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .layer(timeout(TimeoutClass::Transfer))
+                .with_state(register_objects_state),
+        )
+        .route(
+            &path("/{id}"),
             get(download_handler)
                 .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(compression.layer())
+                .layer(axum_middleware::map_response(
+                    response_compression::weaken_encoded_etag,
+                ))
+                .layer(axum_middleware::map_response_with_state(
+                    Arc::clone(&state.download_compression),
+                    response_compression::measure_compression,
+                ))
+                .layer(timeout(TimeoutClass::Transfer))
                 .with_state(Arc::clone(&download_state)),
         )
+        // Explicit HEAD so existence checks skip the blob store
         .route(
-            "/v1/objects/{id}",
+            &path("/{id}"),
+            head(head_handler)
+                .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Short))
+                .with_state(Arc::clone(&download_state)),
+        )
+        .route(
+            &path("/{id}"),
             delete(delete_handler)
                 .layer(axum_middleware::from_fn(
                     authorization::require_object_delete,
                 ))
+                .layer(timeout(TimeoutClass::Default))
                 .with_state(delete_state),
         )
+        // Reads the record only, never the blob
+        .route(
+            &path("/{id}/metadata"),
+            get(get_metadata_handler)
+                .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Short))
+                .with_state(Arc::clone(&download_state)),
+        )
+        .route(
+            &path("/{id}/metadata"),
+            patch(update_metadata_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(timeout(TimeoutClass::Default))
+                .with_state(update_metadata_state),
+        )
+        // WORM locks need their own permission, not just write access
+        .route(
+            &path("/{id}/retention"),
+            put(update_retention_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_retention,
+                ))
+                .layer(timeout(TimeoutClass::Default))
+                .with_state(retention_state),
+        )
+        // Sharing gives another tenant its own reference to the content
+        .route(
+            &path("/{id}/shares"),
+            post(share_object_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(timeout(TimeoutClass::Default))
+                .with_state(Arc::clone(&share_state)),
+        )
+        .route(
+            &path("/{id}/shares"),
+            get(list_shares_handler)
+                .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Short))
+                .with_state(Arc::clone(&share_state)),
+        )
+        .route(
+            &path("/{id}/shares/{share_id}"),
+            delete(revoke_share_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(timeout(TimeoutClass::Default))
+                .with_state(share_state),
+        )
+        // Promotion copies the whole blob between tiers
+        .route(
+            &path("/{id}/touch"),
+            post(touch_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .layer(timeout(TimeoutClass::Transfer))
+                .with_state(touch_state),
+        )
+        // Long-polls, so the timeout leaves room for the longest wait
+        .route(
+            &path("/{id}/status"),
+            get(object_status_handler)
+                .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(axum_middleware::from_fn_with_state(
+                    timeouts
+                        .route(TimeoutClass::Short)
+                        .extended(MAX_STATUS_WAIT)
+                        .with_latency(Arc::clone(&state.request_latency)),
+                    request_timeout::request_timeout_middleware,
+                ))
+                .with_state(object_status_state),
+        )
         // Object search operations
         .route(
-            "/v1/objects/search",
+            &path("/search"),
             post(search::search_handler)
                 .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Short))
                 .with_state(search_state),
         )
         .route(
-            "/v1/objects/search/text",
+            &path("/search/text"),
             post(text_search::text_search_handler)
                 .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Short))
                 .with_state(text_search_state),
         )
         // Key-based object access
         .route(
-            "/v1/objects/by-key/{namespace}/{tenant_id}/{key}",
+            &path("/by-key/{namespace}/{tenant_id}/{key}"),
             get(download_by_key_handler)
                 .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(compression.layer())
+                .layer(axum_middleware::map_response(
+                    response_compression::weaken_encoded_etag,
+                ))
+                .layer(axum_middleware::map_response_with_state(
+                    Arc::clone(&state.download_compression),
+                    response_compression::measure_compression,
+                ))
+                .layer(timeout(TimeoutClass::Transfer))
+                .with_state(Arc::clone(&download_state)),
+        )
+        .route(
+            &path("/by-key/{namespace}/{tenant_id}/{key}"),
+            head(head_by_key_handler)
+                .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .layer(timeout(TimeoutClass::Short))
                 .with_state(download_state),
         )
+        // Deprecation and Sunset headers once the version is scheduled for removal
+        .layer(axum_middleware::from_fn(move |req, next| {
+            let api_version_config = Arc::clone(&api_version_config);
+            async move {
+                api_version::version_headers_middleware(&api_version_config, version, req, next)
+                    .await
+            }
+        }));
+
+    router.merge(objects)
 }
 
 /// Add API key management routes
@@ -230,6 +762,7 @@ fn add_api_key_routes(router: Router, state: &AppState) -> Router {
     let get_api_key_state = Arc::clone(&state.get_api_key_use_case);
     let update_api_key_state = Arc::clone(&state.update_api_key_use_case);
     let delete_api_key_state = Arc::clone(&state.delete_api_key_use_case);
+    let rotate_api_key_state = Arc::clone(&state.rotate_api_key_use_case);
 
     router
         .route(
@@ -272,6 +805,167 @@ fn add_api_key_routes(router: Router, state: &AppState) -> Router {
                 ))
                 .with_state(delete_api_key_state),
         )
+        .route(
+            "/v1/api-keys/{id}/rotate",
+            post(rotate_api_key_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_api_key_management,
+                ))
+                .with_state(rotate_api_key_state),
+        )
+}
+
+/// Add the capabilities document, open to any authenticated caller
+fn add_capabilities_routes(
+    router: Router,
+    state: &AppState,
+    middleware_config: &MiddlewareConfig,
+) -> Router {
+    let capabilities = CapabilitiesResponse::new(&state.config, middleware_config)
+        .with_max_archive_entries(state.bulk_upload_use_case.max_entries())
+        .with_max_archive_download_objects(state.download_archive_use_case.max_objects())
+        .with_webhooks(state.webhook_verifier.is_enabled());
+
+    router.route(
+        "/v1/capabilities",
+        get(capabilities_handler).with_state(Arc::new(capabilities)),
+    )
+}
+
+/// Add storage statistics routes (admin only)
+fn add_stats_routes(router: Router, state: &AppState) -> Router {
+    let stats_state = Arc::clone(&state.stats_use_case);
+
+    router.route(
+        "/v1/stats",
+        get(stats_handler)
+            .layer(axum_middleware::from_fn(
+                authorization::require_admin_access,
+            ))
+            .with_state(stats_state),
+    )
+}
+
+/// Add blob inspection and latency routes (admin only)
+fn add_blob_routes(router: Router, state: &AppState) -> Router {
+    router
+        .route(
+            "/v1/admin/blobs",
+            get(list_blobs_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .with_state(Arc::clone(&state.list_blobs_use_case)),
+        )
+        .route(
+            "/v1/admin/blobs/{hash}",
+            get(get_blob_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .with_state(Arc::clone(&state.list_blobs_use_case)),
+        )
+        .route(
+            "/v1/admin/latency",
+            get(latency_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .with_state(Arc::clone(&state.request_latency)),
+        )
+}
+
+/// Add namespace configuration and namespace delete routes (admin only)
+fn add_namespace_routes(router: Router, state: &AppState) -> Router {
+    let namespace_config_state = Arc::clone(&state.namespace_config_use_case);
+
+    router
+        .route(
+            "/v1/namespaces",
+            get(list_namespace_configs_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .with_state(Arc::clone(&namespace_config_state)),
+        )
+        .route(
+            "/v1/namespaces/{namespace}",
+            get(get_namespace_config_handler)
+                .put(put_namespace_config_handler)
+                .delete(delete_namespace_config_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .with_state(namespace_config_state),
+        )
+        .route(
+            "/v1/namespaces/{namespace}/objects",
+            delete(delete_namespace_objects_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .with_state(DeleteNamespaceState {
+                    use_case: Arc::clone(&state.delete_namespace_use_case),
+                    operations: Arc::clone(&state.operation_progress),
+                }),
+        )
+        .route(
+            "/v1/namespaces/{namespace}/objects/metadata",
+            patch(bulk_update_metadata_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_admin_access,
+                ))
+                .with_state(BulkUpdateMetadataState {
+                    use_case: Arc::clone(&state.bulk_update_metadata_use_case),
+                    operations: Arc::clone(&state.operation_progress),
+                }),
+        )
+}
+
+/// Add the grantee's side of object sharing: shares offered to a tenant
+/// and their acceptance
+fn add_share_routes(router: Router, state: &AppState) -> Router {
+    router
+        .route(
+            "/v1/shares",
+            get(list_incoming_shares_handler)
+                .layer(axum_middleware::from_fn(authorization::require_object_read))
+                .with_state(Arc::clone(&state.share_object_use_case)),
+        )
+        .route(
+            "/v1/shares/{share_id}/accept",
+            post(accept_share_handler)
+                .layer(axum_middleware::from_fn(
+                    authorization::require_object_write,
+                ))
+                .with_state(Arc::clone(&state.share_object_use_case)),
+        )
+}
+
+/// Add the operation event stream; it lasts as long as the operation, so it
+/// gets no request timeout
+fn add_operation_routes(router: Router, state: &AppState) -> Router {
+    router.route(
+        "/v1/operations/{id}/events",
+        get(operation_events_handler).with_state(Arc::clone(&state.operation_progress)),
+    )
+}
+
+/// Add the read-only GraphQL endpoint; resolvers do their own authorization
+#[cfg(feature = "graphql")]
+fn add_graphql_routes(router: Router, state: &AppState) -> Router {
+    let schema = crate::api::graphql::build_schema(
+        Arc::clone(&state.download_use_case),
+        Arc::clone(&state.list_use_case),
+        Arc::clone(&state.search_use_case),
+        Arc::clone(&state.text_search_use_case),
+        Arc::clone(&state.stats_use_case),
+    );
+
+    router.route(
+        "/graphql",
+        post(crate::api::graphql::graphql_handler).with_state(schema),
+    )
 }
 
 /// Apply the complete middleware stack to the router
@@ -281,22 +975,29 @@ fn apply_middleware_stack(
     api_key_repo: Arc<dyn crate::application::ports::ApiKeyRepository + Send + Sync>,
     audit_repo: Arc<dyn crate::application::ports::AuditRepository + Send + Sync>,
     jwks_cache: Arc<moka::future::Cache<String, jsonwebtoken::DecodingKey>>,
+    tenant_limit_provider: Arc<dyn TenantLimitProvider>,
+    concurrency_limiter: Arc<ConcurrencyLimiter>,
 ) -> Router {
     // Apply middleware in order (innermost/last = runs first):
     // 1. Security headers (outermost - adds headers to response)
     // 2. Metrics
-    // 3. Rate Limiting (before auth to catch brute force)
-    // 4. Audit (runs after auth to have user context)
-    // 5. Auth (runs after validation to allow proper error codes)
-    // 6. Content-type validation (runs before auth)
-    // 7. Size limits (runs before auth)
-    // 8. CORS (innermost - runs first)
+    // 3. Concurrency limits (only requests within their rate take a slot)
+    // 4. Rate Limiting (before auth to catch brute force)
+    // 5. Audit (runs after auth to have user context)
+    // 6. Auth (runs after validation to allow proper error codes)
+    // 7. Content-type validation (runs before auth)
+    // 8. Input sanitization (query parameters at `SANITIZATION_LEVEL`)
+    // 9. Size limits (runs before auth)
+    // 10. Error handling (every /v1 error: request ID added, 5xx bodies made
+    //     generic unless ERROR_DETAIL=full)
+    // 11. CORS (innermost - runs first)
     let audit_layer = middleware_factory.create_audit_layer(audit_repo);
-    let rate_limit_layer = middleware_factory.create_rate_limit_layer();
+    let rate_limit_layer = middleware_factory.create_tiered_rate_limit_layer(tenant_limit_provider);
     let size_limit_config = Arc::new(middleware_factory.config().size_limits.clone());
 
     router
         .layer(middleware_factory.create_metrics_layer())
+        .layer(ConcurrencyLimitLayer::with_limiter(concurrency_limiter))
         .layer(rate_limit_layer)
         .layer(axum::middleware::from_fn(move |req, next| {
             let audit_layer = audit_layer.clone();
@@ -306,6 +1007,7 @@ fn apply_middleware_stack(
         .layer(axum::middleware::from_fn(
             content_type::validate_json_for_objects,
         ))
+        .layer(middleware_factory.create_input_sanitization_layer())
         .layer(axum::middleware::from_fn(move |req, next| {
             let size_limit_config = Arc::clone(&size_limit_config);
             async move {
@@ -317,5 +1019,42 @@ fn apply_middleware_stack(
                 .await
             }
         }))
+        .layer(middleware_factory.create_error_handling_layer())
         .layer(middleware_factory.create_cors_layer())
 }
+
+/// Apply the middleware stack to the webhook routes
+///
+/// Like [`apply_middleware_stack`] without API key authentication and the
+/// layers for browser and JSON clients: requests are verified by their
+/// signature in the handler, rate limited per IP and audited without a user.
+fn apply_webhook_middleware_stack(
+    router: Router,
+    middleware_factory: &MiddlewareFactory,
+    audit_repo: Arc<dyn crate::application::ports::AuditRepository + Send + Sync>,
+    tenant_limit_provider: Arc<dyn TenantLimitProvider>,
+) -> Router {
+    let audit_layer = middleware_factory.create_audit_layer(audit_repo);
+    let rate_limit_layer = middleware_factory.create_tiered_rate_limit_layer(tenant_limit_provider);
+    let size_limit_config = Arc::new(middleware_factory.config().size_limits.clone());
+
+    router
+        .layer(middleware_factory.create_metrics_layer())
+        .layer(rate_limit_layer)
+        .layer(axum::middleware::from_fn(move |req, next| {
+            let audit_layer = audit_layer.clone();
+            async move { audit_layer.layer(req, next).await }
+        }))
+        .layer(axum::middleware::from_fn(move |req, next| {
+            let size_limit_config = Arc::clone(&size_limit_config);
+            async move {
+                size_limits::RequestSizeLimitMiddleware::layer_with_config(
+                    req,
+                    next,
+                    size_limit_config,
+                )
+                .await
+            }
+        }))
+        .layer(middleware_factory.create_error_handling_layer())
+}
@@ -0,0 +1,364 @@
+//! HTTP server with tunable protocol settings
+//!
+//! `axum::serve` accepts connections with hyper's defaults and no way to
+//! change them. This accept loop serves the router with hyper's automatic
+//! protocol detection instead, so every listener speaks HTTP/1.1 and, when
+//! enabled, HTTP/2 over cleartext (h2c with prior knowledge, which is how
+//! proxies such as Fly.io's talk to an `h2_backend`) on the same port.
+//!
+//! Connections can also be capped per remote IP at accept time, before any
+//! bytes are read: one client opening many idle connections would otherwise
+//! hold file descriptors and memory that request-level limits never see.
+
+use std::future::Future;
+use std::io;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tracing::{debug, error};
+
+/// Default maximum number of concurrent HTTP/2 streams per connection
+pub const DEFAULT_HTTP2_MAX_CONCURRENT_STREAMS: u32 = 200;
+
+/// Default time between HTTP/2 keep-alive pings
+pub const DEFAULT_HTTP2_KEEP_ALIVE_INTERVAL_SECS: u64 = 30;
+
+/// Default time to wait for a keep-alive ping to be acknowledged
+pub const DEFAULT_HTTP2_KEEP_ALIVE_TIMEOUT_SECS: u64 = 20;
+
+/// Default time a client has to send the headers of an HTTP/1.1 request
+pub const DEFAULT_HEADER_READ_TIMEOUT_SECS: u64 = 30;
+
+/// Connection-level protocol settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpServerConfig {
+    /// Serve HTTP/2 next to HTTP/1.1; HTTP/1.1 is always served
+    pub http2_enabled: bool,
+    pub http2_max_concurrent_streams: u32,
+    /// Ping idle HTTP/2 connections this often; `None` disables pings
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// Close an HTTP/2 connection whose ping is not acknowledged in time
+    pub http2_keep_alive_timeout: Duration,
+    /// Reuse HTTP/1.1 connections for further requests
+    pub http1_keep_alive: bool,
+    /// Close HTTP/1.1 connections whose request headers take longer
+    pub header_read_timeout: Duration,
+}
+
+impl Default for HttpServerConfig {
+    fn default() -> Self {
+        Self {
+            http2_enabled: true,
+            http2_max_concurrent_streams: DEFAULT_HTTP2_MAX_CONCURRENT_STREAMS,
+            http2_keep_alive_interval: Some(Duration::from_secs(
+                DEFAULT_HTTP2_KEEP_ALIVE_INTERVAL_SECS,
+            )),
+            http2_keep_alive_timeout: Duration::from_secs(DEFAULT_HTTP2_KEEP_ALIVE_TIMEOUT_SECS),
+            http1_keep_alive: true,
+            header_read_timeout: Duration::from_secs(DEFAULT_HEADER_READ_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl HttpServerConfig {
+    /// Create a new config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve HTTP/2 next to HTTP/1.1
+    pub fn with_http2(mut self, enabled: bool) -> Self {
+        self.http2_enabled = enabled;
+        self
+    }
+
+    /// Set the maximum number of concurrent HTTP/2 streams per connection
+    pub fn with_http2_max_concurrent_streams(mut self, max_concurrent_streams: u32) -> Self {
+        self.http2_max_concurrent_streams = max_concurrent_streams;
+        self
+    }
+
+    /// Set the HTTP/2 keep-alive ping interval (`None` disables pings)
+    pub fn with_http2_keep_alive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.http2_keep_alive_interval = interval;
+        self
+    }
+
+    /// Set how long to wait for a keep-alive ping to be acknowledged
+    pub fn with_http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http2_keep_alive_timeout = timeout;
+        self
+    }
+
+    /// Reuse HTTP/1.1 connections for further requests
+    pub fn with_http1_keep_alive(mut self, enabled: bool) -> Self {
+        self.http1_keep_alive = enabled;
+        self
+    }
+
+    /// Set how long a client has to send HTTP/1.1 request headers
+    pub fn with_header_read_timeout(mut self, timeout: Duration) -> Self {
+        self.header_read_timeout = timeout;
+        self
+    }
+
+    /// One-line summary of the effective settings, for the startup log
+    pub fn summary(&self) -> String {
+        let http1 = format!(
+            "HTTP/1.1 (keep-alive {}, header read timeout {}s)",
+            if self.http1_keep_alive { "on" } else { "off" },
+            self.header_read_timeout.as_secs()
+        );
+        if !self.http2_enabled {
+            return format!("{http1}; HTTP/2 disabled");
+        }
+
+        let pings = match self.http2_keep_alive_interval {
+            Some(interval) => format!(
+                "ping every {}s, timeout {}s",
+                interval.as_secs(),
+                self.http2_keep_alive_timeout.as_secs()
+            ),
+            None => "no pings".to_string(),
+        };
+        format!(
+            "{http1}; HTTP/2 ({} concurrent streams, {pings})",
+            self.http2_max_concurrent_streams
+        )
+    }
+
+    fn builder(&self) -> Builder<TokioExecutor> {
+        let mut builder = Builder::new(TokioExecutor::new());
+        builder
+            .http1()
+            .timer(TokioTimer::new())
+            .keep_alive(self.http1_keep_alive)
+            .header_read_timeout(self.header_read_timeout);
+        if !self.http2_enabled {
+            return builder.http1_only();
+        }
+
+        builder
+            .http2()
+            .timer(TokioTimer::new())
+            .max_concurrent_streams(self.http2_max_concurrent_streams)
+            .keep_alive_interval(self.http2_keep_alive_interval)
+            .keep_alive_timeout(self.http2_keep_alive_timeout);
+        builder
+    }
+}
+
+/// Connection counters, for readiness details
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionStats {
+    /// Connections open right now
+    pub open: u64,
+    /// Remote IPs with a connection open
+    pub remote_ips: usize,
+    /// Connections closed on accept for being over the per-IP limit since
+    /// startup
+    pub refused: u64,
+    /// Connections one IP may have open (0 = unlimited)
+    pub max_per_ip: usize,
+}
+
+/// Caps the connections each remote IP has open
+///
+/// The remote IP is the peer address of the TCP connection, so behind a
+/// proxy or load balancer every client shares the proxy's IP; leave the
+/// limit off there and limit at the proxy instead.
+#[derive(Debug)]
+pub struct ConnectionLimiter {
+    max_per_ip: usize,
+    open: DashMap<IpAddr, usize>,
+    open_total: AtomicU64,
+    refused: AtomicU64,
+}
+
+/// A connection's place in its IP's count; dropping it frees the place
+#[derive(Debug)]
+pub struct ConnectionPermit {
+    limiter: Arc<ConnectionLimiter>,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        if let Entry::Occupied(mut entry) = self.limiter.open.entry(self.ip) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+        self.limiter.open_total.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl ConnectionLimiter {
+    /// Limit each IP to `max_per_ip` open connections (0 = unlimited)
+    pub fn new(max_per_ip: usize) -> Self {
+        Self {
+            max_per_ip,
+            open: DashMap::new(),
+            open_total: AtomicU64::new(0),
+            refused: AtomicU64::new(0),
+        }
+    }
+
+    /// Count a new connection from `ip`, or `None` if `ip` is at its limit
+    pub fn try_acquire(self: &Arc<Self>, ip: IpAddr) -> Option<ConnectionPermit> {
+        match self.open.entry(ip) {
+            Entry::Occupied(entry) if self.max_per_ip > 0 && *entry.get() >= self.max_per_ip => {
+                self.refused.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            Entry::Occupied(mut entry) => *entry.get_mut() += 1,
+            Entry::Vacant(entry) => {
+                entry.insert(1);
+            }
+        }
+        self.open_total.fetch_add(1, Ordering::Relaxed);
+        Some(ConnectionPermit {
+            limiter: Arc::clone(self),
+            ip,
+        })
+    }
+
+    pub fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            open: self.open_total.load(Ordering::Relaxed),
+            remote_ips: self.open.len(),
+            refused: self.refused.load(Ordering::Relaxed),
+            max_per_ip: self.max_per_ip,
+        }
+    }
+}
+
+/// Serve `router` on `listener` until `shutdown` completes
+///
+/// Connections from an IP already at the limit of `connections` are closed
+/// as soon as they are accepted. Connections open at shutdown are closed
+/// once their in-flight requests finish.
+pub async fn serve(
+    listener: TcpListener,
+    router: Router,
+    config: &HttpServerConfig,
+    connections: Arc<ConnectionLimiter>,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()> {
+    let builder = config.builder();
+    let graceful = GracefulShutdown::new();
+    tokio::pin!(shutdown);
+
+    loop {
+        let (stream, remote) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // Usually out of file descriptors; give connections time to close
+                    error!("Failed to accept connection: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            () = &mut shutdown => break,
+        };
+
+        // Dropping the stream closes the connection before anything is read
+        let Some(permit) = connections.try_acquire(remote.ip()) else {
+            debug!(remote_ip = %remote.ip(), "Refused connection over the per-IP limit");
+            continue;
+        };
+
+        let service = TowerToHyperService::new(router.clone());
+        let connection = builder
+            .serve_connection_with_upgrades(TokioIo::new(stream), service)
+            .into_owned();
+        let connection = graceful.watch(connection);
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                debug!("Connection closed with error: {}", e);
+            }
+            drop(permit);
+        });
+    }
+
+    drop(listener);
+    graceful.shutdown().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_summary() {
+        assert_eq!(
+            HttpServerConfig::default().summary(),
+            "HTTP/1.1 (keep-alive on, header read timeout 30s); \
+             HTTP/2 (200 concurrent streams, ping every 30s, timeout 20s)"
+        );
+    }
+
+    #[test]
+    fn test_connections_over_the_per_ip_limit_are_refused() {
+        let limiter = Arc::new(ConnectionLimiter::new(2));
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let other: IpAddr = "198.51.100.1".parse().unwrap();
+
+        let first = limiter.try_acquire(client).unwrap();
+        let _second = limiter.try_acquire(client).unwrap();
+        assert!(limiter.try_acquire(client).is_none());
+        let _elsewhere = limiter.try_acquire(other).unwrap();
+
+        // A closed connection makes room for the next one
+        drop(first);
+        let _third = limiter.try_acquire(client).unwrap();
+
+        let stats = limiter.stats();
+        assert_eq!(stats.open, 3);
+        assert_eq!(stats.remote_ips, 2);
+        assert_eq!(stats.refused, 1);
+    }
+
+    #[test]
+    fn test_closed_connections_are_forgotten_and_zero_is_unlimited() {
+        let limiter = Arc::new(ConnectionLimiter::new(0));
+        let client: IpAddr = "2001:db8::1".parse().unwrap();
+
+        let permits: Vec<_> = (0..100)
+            .map(|_| limiter.try_acquire(client).unwrap())
+            .collect();
+        assert_eq!(limiter.stats().open, 100);
+        drop(permits);
+
+        let stats = limiter.stats();
+        assert_eq!(stats.open, 0);
+        assert_eq!(stats.remote_ips, 0);
+        assert_eq!(stats.refused, 0);
+    }
+
+    #[test]
+    fn test_http1_only_summary() {
+        let config = HttpServerConfig::new()
+            .with_http2(false)
+            .with_http1_keep_alive(false);
+
+        assert_eq!(
+            config.summary(),
+            "HTTP/1.1 (keep-alive off, header read timeout 30s); HTTP/2 disabled"
+        );
+    }
+}
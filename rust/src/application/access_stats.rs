@@ -0,0 +1,179 @@
+//! Batched download statistics
+//!
+//! Downloads are counted in memory and written to the object repository in
+//! one batch per flush interval. A popular object therefore costs one row
+//! update per interval instead of one per download, and downloads never wait
+//! on (or contend for) that row.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use time::OffsetDateTime;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+use crate::application::dto::ObjectAccess;
+use crate::application::ports::{ObjectRepository, RepositoryError};
+use crate::domain::value_objects::ObjectId;
+
+/// Default time between flushes of pending access counts
+pub const DEFAULT_ACCESS_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Accumulates object downloads and flushes them in batches
+pub struct AccessRecorder {
+    object_repo: Arc<dyn ObjectRepository>,
+    flush_interval: Duration,
+    pending: DashMap<ObjectId, (u64, OffsetDateTime)>,
+}
+
+impl AccessRecorder {
+    pub fn new(object_repo: Arc<dyn ObjectRepository>) -> Self {
+        Self {
+            object_repo,
+            flush_interval: DEFAULT_ACCESS_FLUSH_INTERVAL,
+            pending: DashMap::new(),
+        }
+    }
+
+    /// Set the time between flushes
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Count a download of `object_id`
+    pub fn record(&self, object_id: ObjectId) {
+        self.add(object_id, 1, OffsetDateTime::now_utc());
+    }
+
+    /// Number of objects with unflushed downloads
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Write pending counts to the repository
+    ///
+    /// Returns the number of objects updated. On failure the counts are kept
+    /// and retried by the next flush.
+    pub async fn flush(&self) -> Result<usize, RepositoryError> {
+        // Collect keys first: removing while iterating would deadlock the shard
+        let ids: Vec<ObjectId> = self.pending.iter().map(|entry| *entry.key()).collect();
+        let accesses: Vec<ObjectAccess> = ids
+            .into_iter()
+            .filter_map(|id| self.pending.remove(&id))
+            .map(|(object_id, (count, last_accessed_at))| ObjectAccess {
+                object_id,
+                count,
+                last_accessed_at,
+            })
+            .collect();
+
+        if accesses.is_empty() {
+            return Ok(0);
+        }
+
+        if let Err(e) = self.object_repo.record_access(&accesses).await {
+            for access in &accesses {
+                self.add(access.object_id, access.count, access.last_accessed_at);
+            }
+            return Err(e);
+        }
+
+        Ok(accesses.len())
+    }
+
+    /// Flush pending counts every interval, forever
+    pub async fn run(self: Arc<Self>) {
+        info!(
+            "Starting access recorder with flush interval: {:?}",
+            self.flush_interval
+        );
+
+        let mut ticks = interval(self.flush_interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticks.tick().await;
+
+            if let Err(e) = self.flush().await {
+                warn!(
+                    pending = self.pending(),
+                    "Failed to flush access counts: {}", e
+                );
+            }
+        }
+    }
+
+    fn add(&self, object_id: ObjectId, count: u64, accessed_at: OffsetDateTime) {
+        let mut entry = self.pending.entry(object_id).or_insert((0, accessed_at));
+        entry.0 += count;
+        entry.1 = entry.1.max(accessed_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockObjectRepository;
+
+    #[tokio::test]
+    async fn test_flush_batches_counts_per_object() {
+        let popular = ObjectId::new();
+        let other = ObjectId::new();
+
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_record_access()
+            .withf(move |accesses| {
+                let count_of =
+                    |id: ObjectId| accesses.iter().find(|a| a.object_id == id).map(|a| a.count);
+                accesses.len() == 2 && count_of(popular) == Some(3) && count_of(other) == Some(1)
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let recorder = AccessRecorder::new(Arc::new(mock_object_repo));
+        recorder.record(popular);
+        recorder.record(other);
+        recorder.record(popular);
+        recorder.record(popular);
+
+        assert_eq!(recorder.flush().await.unwrap(), 2);
+        assert_eq!(recorder.pending(), 0);
+        // Nothing left to write
+        assert_eq!(recorder.flush().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_keeps_counts() {
+        let object_id = ObjectId::new();
+
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut seq = mockall::Sequence::new();
+        mock_object_repo
+            .expect_record_access()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| {
+                Err(RepositoryError::Internal(
+                    "database unavailable".to_string(),
+                ))
+            });
+        mock_object_repo
+            .expect_record_access()
+            .withf(|accesses| accesses.len() == 1 && accesses[0].count == 2)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+
+        let recorder = AccessRecorder::new(Arc::new(mock_object_repo));
+        recorder.record(object_id);
+
+        assert!(recorder.flush().await.is_err());
+        assert_eq!(recorder.pending(), 1);
+
+        recorder.record(object_id);
+        assert_eq!(recorder.flush().await.unwrap(), 1);
+    }
+}
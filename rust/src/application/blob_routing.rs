@@ -0,0 +1,136 @@
+//! Which blob store backend takes the new blobs of a namespace or tenant
+//!
+//! Rules map a namespace, or a tenant as `tenant:<uuid>`, to a backend name;
+//! `*` names the backend for everything unmatched. A tenant rule wins over a
+//! namespace rule. Blobs are content-addressed, so routing only decides where
+//! blobs are written: reads and deletes search every backend.
+
+use std::collections::HashMap;
+
+use crate::application::metadata_index::ALL_NAMESPACES;
+use crate::domain::value_objects::TenantId;
+
+/// Backend of the storage roots configured without a name
+pub const DEFAULT_BLOB_BACKEND: &str = "default";
+
+/// Prefix of tenant rules
+const TENANT_PREFIX: &str = "tenant:";
+
+/// Backend chosen for each namespace and tenant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobRoutes {
+    namespaces: HashMap<String, String>,
+    tenants: HashMap<TenantId, String>,
+    fallback: String,
+}
+
+impl Default for BlobRoutes {
+    fn default() -> Self {
+        Self {
+            namespaces: HashMap::new(),
+            tenants: HashMap::new(),
+            fallback: DEFAULT_BLOB_BACKEND.to_string(),
+        }
+    }
+}
+
+impl BlobRoutes {
+    /// Parse rules like `images=nvme;tenant:<uuid>=archive;*=default`
+    ///
+    /// Every rule must name one of `backends`.
+    pub fn parse(spec: &str, backends: &[&str]) -> Result<Self, String> {
+        let mut routes = Self::default();
+
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (target, backend) = entry.split_once('=').ok_or_else(|| {
+                format!("Invalid blob route '{entry}': expected namespace=backend")
+            })?;
+            let (target, backend) = (target.trim(), backend.trim());
+            if !backends.contains(&backend) {
+                return Err(format!(
+                    "Blob route '{entry}' names unknown backend '{backend}'"
+                ));
+            }
+
+            let duplicate = if target == ALL_NAMESPACES {
+                routes.fallback = backend.to_string();
+                false
+            } else if let Some(tenant_id) = target.strip_prefix(TENANT_PREFIX) {
+                let tenant_id = TenantId::from_string(tenant_id.trim())
+                    .map_err(|e| format!("Invalid blob route '{entry}': {e}"))?;
+                routes
+                    .tenants
+                    .insert(tenant_id, backend.to_string())
+                    .is_some()
+            } else if target.is_empty() {
+                return Err(format!("Invalid blob route '{entry}': empty namespace"));
+            } else {
+                routes
+                    .namespaces
+                    .insert(target.to_lowercase(), backend.to_string())
+                    .is_some()
+            };
+            if duplicate {
+                return Err(format!("'{target}' is routed twice"));
+            }
+        }
+
+        Ok(routes)
+    }
+
+    /// Backend that takes new blobs of `namespace` of `tenant_id`
+    pub fn backend_for(&self, namespace: &str, tenant_id: &TenantId) -> &str {
+        self.tenants
+            .get(tenant_id)
+            .or_else(|| self.namespaces.get(&namespace.to_lowercase()))
+            .unwrap_or(&self.fallback)
+    }
+
+    /// Backend that takes blobs written without a namespace
+    pub fn fallback(&self) -> &str {
+        &self.fallback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    const BACKENDS: &[&str] = &["default", "nvme", "archive"];
+
+    #[test]
+    fn test_tenant_rule_wins_over_namespace_rule() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let other_tenant = TenantId::new(Uuid::new_v4());
+        let routes = BlobRoutes::parse(
+            &format!("images=nvme; tenant:{tenant_id}=archive"),
+            BACKENDS,
+        )
+        .unwrap();
+
+        assert_eq!(routes.backend_for("Images", &other_tenant), "nvme");
+        assert_eq!(routes.backend_for("images", &tenant_id), "archive");
+        assert_eq!(routes.backend_for("docs", &other_tenant), "default");
+    }
+
+    #[test]
+    fn test_wildcard_replaces_default_backend() {
+        let routes = BlobRoutes::parse("*=archive", BACKENDS).unwrap();
+
+        assert_eq!(routes.fallback(), "archive");
+        assert_eq!(
+            routes.backend_for("docs", &TenantId::new(Uuid::new_v4())),
+            "archive"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_specs() {
+        assert!(BlobRoutes::parse("images=s3", BACKENDS).is_err());
+        assert!(BlobRoutes::parse("images", BACKENDS).is_err());
+        assert!(BlobRoutes::parse("=nvme", BACKENDS).is_err());
+        assert!(BlobRoutes::parse("tenant:nope=nvme", BACKENDS).is_err());
+        assert!(BlobRoutes::parse("images=nvme;IMAGES=archive", BACKENDS).is_err());
+    }
+}
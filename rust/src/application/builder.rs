@@ -1,28 +1,64 @@
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use openidconnect::core::CoreProviderMetadata;
 use openidconnect::reqwest::{self, Client as ReqwestClient};
-use openidconnect::{IssuerUrl, JsonWebKey};
+use openidconnect::IssuerUrl;
 use sqlx::postgres::PgPoolOptions;
 use tracing::{error, info, warn};
 
+use crate::api::middleware::rate_limiting::{ConcurrencyLimiter, RateLimitConfig};
+use crate::api::middleware::request_latency::RequestLatency;
+use crate::api::middleware::response_compression::CompressionMeter;
 use crate::api::router::AppState;
-use crate::application::gc::GarbageCollector;
+use crate::api::server::ConnectionLimiter;
+use crate::application::access_stats::AccessRecorder;
+use crate::application::blob_routing::{BlobRoutes, DEFAULT_BLOB_BACKEND};
+use crate::application::content_policy::ContentPolicy;
+use crate::application::errors::GhostObjectPolicy;
+use crate::application::gc::{DeletionRetryPolicy, GarbageCollector, GcConfig};
+use crate::application::metadata_index::MetadataIndexConfig;
+use crate::application::operation_progress::OperationProgress;
 use crate::application::ports::{
-    ApiKeyRepository, AuditRepository, BlobRepository, BlobStore, ObjectRepository,
+    ApiKeyRepository, AuditRepository, BlobInventory, BlobRepository, BlobRouter, BlobStore,
+    IdempotencyRepository, NamespaceConfigRepository, NamespaceDeletionRepository,
+    ObjectRepository, RefcountRepository, StatsRepository, TenantLimitProvider, TextExtractor,
 };
+use crate::application::scrub::{BlobScrubber, ScrubConfig};
+use crate::application::short_hashes::ShortHashes;
+use crate::application::status_watch::StatusWatch;
 use crate::application::use_cases::{
-    CreateApiKeyUseCase, DeleteApiKeyUseCase, DeleteObjectUseCase, DownloadObjectUseCase,
-    GetApiKeyUseCase, ListApiKeysUseCase, ListObjectsUseCase, SearchObjectsUseCase,
-    TextSearchObjectsUseCase, UpdateApiKeyUseCase, UploadObjectUseCase,
+    BulkUpdateMetadataUseCase, BulkUploadUseCase, CompactionUseCase, CreateApiKeyUseCase,
+    DeleteApiKeyUseCase, DeleteNamespaceUseCase, DeleteObjectUseCase, DownloadArchiveUseCase,
+    DownloadObjectUseCase, GetApiKeyUseCase, ListApiKeysUseCase, ListBlobsUseCase,
+    ListObjectsUseCase, NamespaceConfigUseCase, ObjectRetentionUseCase, ObjectStatusUseCase,
+    ReconcileRefcountsUseCase, RegisterObjectsUseCase, RotateApiKeyUseCase, SearchObjectsUseCase,
+    ShareObjectUseCase, StatsUseCase, TextSearchObjectsUseCase, TouchObjectUseCase,
+    UpdateApiKeyUseCase, UpdateObjectMetadataUseCase, UploadObjectUseCase,
 };
+use crate::application::validation::MetadataLimits;
+use crate::application::webhooks::WebhookVerifier;
 use crate::config::Config;
+use crate::domain::value_objects::ApiKeyHashAlgorithm;
+use crate::infrastructure::extraction::{NoopTextExtractor, PlainTextExtractor};
+use crate::infrastructure::jwks;
+#[cfg(feature = "redis")]
+use crate::infrastructure::persistence::RedisObjectRepository;
 use crate::infrastructure::persistence::{
-    PostgresApiKeyRepository, PostgresAuditRepository, PostgresBlobRepository,
-    PostgresObjectRepository,
+    BlobFilterConfig, FilteredBlobRepository, ObjectTableRequired, PoolMonitor, PoolMonitorConfig,
+    PostgresApiKeyRepository, PostgresAuditRepository, PostgresBlobDeletionFailureRepository,
+    PostgresBlobRepository, PostgresIdempotencyRepository, PostgresNamespaceConfigRepository,
+    PostgresNamespaceDeletionRepository, PostgresObjectRepository, PostgresObjectShareRepository,
+    PostgresRefcountRepository, PostgresScrubRepository, PostgresStatsRepository,
+    PostgresTenantLimitProvider, RetryPolicy,
+};
+use crate::infrastructure::scanning::ClamAvScanner;
+use crate::infrastructure::storage::{
+    BlobBackend, BlobBackendRoots, BlobCacheConfig, CachingBlobStore, FsyncPolicy,
+    LocalFilesystemStore, ResilienceConfig, ResilientBlobStore, RoutingBlobStore, ShardLayout,
 };
-use crate::infrastructure::storage::LocalFilesystemStore;
 
 /// Result type for the application builder
 pub type BuildResult = Result<
@@ -40,29 +76,63 @@ pub struct ApplicationBuilder {
     pool: Option<Arc<sqlx::PgPool>>,
     object_repo: Option<Arc<dyn ObjectRepository>>,
     blob_repo: Option<Arc<dyn BlobRepository>>,
+    /// Set when the blob repository is behind the content hash filter
+    blob_filter: Option<Arc<FilteredBlobRepository>>,
     blob_store: Option<Arc<dyn BlobStore>>,
+    blob_cache: Option<Arc<CachingBlobStore>>,
+    /// Set when blobs are routed across several backends
+    blob_router: Option<Arc<dyn BlobRouter>>,
+    /// Circuit breakers of the backends, by backend name
+    blob_store_breakers: Vec<(String, Arc<ResilientBlobStore>)>,
+    blob_inventory: Option<Arc<dyn BlobInventory>>,
     api_key_repo: Option<Arc<dyn ApiKeyRepository>>,
     audit_repo: Option<Arc<dyn AuditRepository>>,
+    stats_repo: Option<Arc<dyn StatsRepository>>,
+    refcount_repo: Option<Arc<dyn RefcountRepository>>,
+    idempotency_repo: Option<Arc<dyn IdempotencyRepository>>,
+    namespace_config_repo: Option<Arc<dyn NamespaceConfigRepository>>,
+    namespace_deletion_repo: Option<Arc<dyn NamespaceDeletionRepository>>,
+    tenant_limit_provider: Option<Arc<dyn TenantLimitProvider>>,
     gc: Option<Arc<GarbageCollector>>,
     oidc_metadata: Option<CoreProviderMetadata>,
     jwks_cache: Arc<moka::future::Cache<String, jsonwebtoken::DecodingKey>>,
+    db_retry_policy: RetryPolicy,
     expected_migration_count: usize,
 }
 
 impl ApplicationBuilder {
     /// Create a new builder with the given configuration
     pub fn new(config: Config) -> Self {
+        // Keys survive two failed refreshes before expiring
+        let jwks_max_stale = Duration::from_secs(config.jwt_jwks_refresh_secs.saturating_mul(3));
+        let db_retry_policy = RetryPolicy::new(
+            config.db_retry_max_attempts,
+            Duration::from_millis(config.db_retry_base_delay_ms),
+            Duration::from_millis(config.db_retry_max_delay_ms),
+        );
         Self {
             config,
             pool: None,
             object_repo: None,
             blob_repo: None,
+            blob_filter: None,
             blob_store: None,
+            blob_cache: None,
+            blob_router: None,
+            blob_store_breakers: Vec::new(),
+            blob_inventory: None,
             api_key_repo: None,
             audit_repo: None,
+            stats_repo: None,
+            refcount_repo: None,
+            idempotency_repo: None,
+            namespace_config_repo: None,
+            namespace_deletion_repo: None,
+            tenant_limit_provider: None,
             gc: None,
             oidc_metadata: None,
-            jwks_cache: Arc::new(moka::future::Cache::new(100)),
+            jwks_cache: jwks::new_cache(jwks_max_stale),
+            db_retry_policy,
             expected_migration_count: 0,
         }
     }
@@ -126,41 +196,300 @@ impl ApplicationBuilder {
     pub async fn with_infrastructure(mut self) -> Result<Self, Box<dyn std::error::Error>> {
         let pool = self.pool.as_ref().ok_or("Database pool not initialized")?;
 
-        let object_repo = Arc::new(PostgresObjectRepository::new(
+        let metadata_index = match &self.config.text_search_metadata_keys {
+            Some(spec) => MetadataIndexConfig::parse(spec)
+                .map_err(|e| format!("Invalid TEXT_SEARCH_METADATA_KEYS: {}", e))?,
+            None => MetadataIndexConfig::default(),
+        };
+        // Everything but object metadata stays in Postgres with either store
+        let objects_in_postgres = self.config.metadata_store != "redis";
+        let object_repo: Arc<dyn ObjectRepository> = if objects_in_postgres {
+            Arc::new(
+                PostgresObjectRepository::with_metadata_index(
+                    Arc::clone(pool).as_ref().clone(),
+                    metadata_index,
+                )
+                .with_text_search_config(self.config.text_search_config.clone())
+                .with_retry_policy(self.db_retry_policy.clone())
+                .with_key_uniqueness(self.config.key_uniqueness()?),
+            )
+        } else {
+            self.redis_object_repo(metadata_index).await?
+        };
+        let blob_repo = Arc::new(
+            PostgresBlobRepository::new(Arc::clone(pool).as_ref().clone())
+                .with_retry_policy(self.db_retry_policy.clone()),
+        );
+        // Lets uploads skip dedup lookups for content nobody stores yet
+        let blob_repo: Arc<dyn BlobRepository> = if self.config.blob_filter_enabled {
+            let config = BlobFilterConfig {
+                expected_blobs: self.config.blob_filter_expected_blobs,
+                false_positive_rate: self.config.blob_filter_false_positive_rate,
+                max_bytes: self.config.blob_filter_max_bytes,
+            };
+            match FilteredBlobRepository::new(blob_repo.clone(), config) {
+                Ok(filtered) => {
+                    let filtered = Arc::new(filtered);
+                    let populating = Arc::clone(&filtered);
+                    tokio::spawn(async move {
+                        match populating.populate().await {
+                            Ok(hashes) => info!("Blob filter loaded {} content hashes", hashes),
+                            Err(e) => warn!(
+                                "Failed to load blob filter; dedup lookups keep querying: {}",
+                                e
+                            ),
+                        }
+                    });
+                    self.blob_filter = Some(Arc::clone(&filtered));
+                    filtered
+                }
+                Err(e) => {
+                    warn!(
+                        "Blob filter disabled; dedup lookups query the database: {}",
+                        e
+                    );
+                    blob_repo
+                }
+            }
+        } else {
+            blob_repo
+        };
+        let audit_repo = Arc::new(PostgresAuditRepository::new(
             Arc::clone(pool).as_ref().clone(),
         ));
-        let blob_repo = Arc::new(PostgresBlobRepository::new(
+        // These scan the objects table, which is empty when objects live elsewhere
+        let stats_repo: Arc<dyn StatsRepository> = if objects_in_postgres {
+            Arc::new(PostgresStatsRepository::new(
+                Arc::clone(pool).as_ref().clone(),
+            ))
+        } else {
+            Arc::new(ObjectTableRequired::new(self.config.metadata_store.clone()))
+        };
+        let refcount_repo: Arc<dyn RefcountRepository> = if objects_in_postgres {
+            Arc::new(PostgresRefcountRepository::new(
+                Arc::clone(pool).as_ref().clone(),
+            ))
+        } else {
+            Arc::new(ObjectTableRequired::new(self.config.metadata_store.clone()))
+        };
+        let idempotency_repo = Arc::new(PostgresIdempotencyRepository::new(
             Arc::clone(pool).as_ref().clone(),
         ));
-        let audit_repo = Arc::new(PostgresAuditRepository::new(
+        let namespace_config_repo = Arc::new(PostgresNamespaceConfigRepository::new(
+            Arc::clone(pool).as_ref().clone(),
+        ));
+        let namespace_deletion_repo: Arc<dyn NamespaceDeletionRepository> = if objects_in_postgres {
+            Arc::new(PostgresNamespaceDeletionRepository::new(
+                Arc::clone(pool).as_ref().clone(),
+            ))
+        } else {
+            Arc::new(ObjectTableRequired::new(self.config.metadata_store.clone()))
+        };
+        let tenant_limit_provider = Arc::new(PostgresTenantLimitProvider::new(
             Arc::clone(pool).as_ref().clone(),
         ));
 
-        let blob_store = Arc::new(LocalFilesystemStore::new(
+        let local_store = |hot_root: PathBuf, cold_root: PathBuf| {
+            LocalFilesystemStore::with_full_config(
+                hot_root,
+                cold_root,
+                true,
+                true,
+                self.config.concurrent_cache_threshold,
+                self.config.adaptive_buffering_enabled,
+            )
+            // Validated at startup; fall back to fsync rather than failing here
+            .with_fsync_policy(FsyncPolicy::parse(&self.config.blob_fsync).unwrap_or_default())
+            .with_shard_layout(ShardLayout::new(
+                self.config.storage_shard_depth,
+                self.config.storage_shard_width,
+            ))
+            .with_flat_layout_migration(self.config.storage_layout_migration)
+        };
+        // Reads move flat-layout blobs on access; this moves the rest
+        let migrate_flat_layout = |name: String, store: Arc<LocalFilesystemStore>| {
+            if !store.flat_layout_migration_pending() {
+                return;
+            }
+            tokio::spawn(async move {
+                match store.migrate_flat_layout().await {
+                    Ok(report) => info!(
+                        "Blob backend {} left the flat layout ({} blobs moved)",
+                        name, report.moved
+                    ),
+                    Err(e) => error!(
+                        "Flat layout migration of blob backend {} failed: {}",
+                        name, e
+                    ),
+                }
+            });
+        };
+        let mut default_store = local_store(
             self.config.hot_storage_root.clone(),
             self.config.cold_storage_root.clone(),
-        ));
+        );
+        // Extra backends stage under their own roots
+        if let Some(temp_dir) = &self.config.blob_temp_dir {
+            default_store = default_store.with_temp_dir(temp_dir.clone());
+        }
+        let default_store = Arc::new(default_store);
 
         // Initialize storage directories
-        blob_store
+        default_store
             .init()
             .await
             .map_err(|e| format!("Failed to initialize blob store: {}", e))?;
+        migrate_flat_layout(DEFAULT_BLOB_BACKEND.to_string(), Arc::clone(&default_store));
+
+        // Retries and a circuit breaker per backend; inventory listings go direct
+        let resilience = self
+            .config
+            .blob_store_resilience_enabled
+            .then(|| ResilienceConfig {
+                max_attempts: self.config.blob_store_retry_max_attempts,
+                base_delay: Duration::from_millis(self.config.blob_store_retry_base_delay_ms),
+                max_delay: Duration::from_millis(self.config.blob_store_retry_max_delay_ms),
+                attempt_timeout: Duration::from_secs(self.config.blob_store_attempt_timeout_secs),
+                failure_threshold: self.config.blob_store_breaker_failure_threshold,
+                open_duration: Duration::from_secs(self.config.blob_store_breaker_open_secs),
+            });
+        let mut breakers = Vec::new();
+        let mut resilient = |name: &str, store: Arc<dyn BlobStore>| -> Arc<dyn BlobStore> {
+            match &resilience {
+                Some(config) => {
+                    let store = Arc::new(ResilientBlobStore::new(store, config.clone()));
+                    breakers.push((name.to_string(), Arc::clone(&store)));
+                    store
+                }
+                None => store,
+            }
+        };
+
+        let (blob_store, blob_inventory): (Arc<dyn BlobStore>, Arc<dyn BlobInventory>) =
+            match &self.config.blob_backends {
+                Some(spec) => {
+                    let mut backends = vec![BlobBackend {
+                        name: DEFAULT_BLOB_BACKEND.to_string(),
+                        store: resilient(
+                            DEFAULT_BLOB_BACKEND,
+                            Arc::clone(&default_store) as Arc<dyn BlobStore>,
+                        ),
+                        inventory: default_store,
+                    }];
+                    for roots in BlobBackendRoots::parse_list(spec)
+                        .map_err(|e| format!("Invalid BLOB_BACKENDS: {}", e))?
+                    {
+                        let store = Arc::new(local_store(roots.hot_root, roots.cold_root));
+                        store.init().await.map_err(|e| {
+                            format!("Failed to initialize blob backend {}: {}", roots.name, e)
+                        })?;
+                        migrate_flat_layout(roots.name.clone(), Arc::clone(&store));
+                        backends.push(BlobBackend {
+                            store: resilient(&roots.name, Arc::clone(&store) as Arc<dyn BlobStore>),
+                            name: roots.name,
+                            inventory: store,
+                        });
+                    }
+                    let names: Vec<&str> = backends.iter().map(|b| b.name.as_str()).collect();
+                    let routes =
+                        BlobRoutes::parse(self.config.blob_routes.as_deref().unwrap_or(""), &names)
+                            .map_err(|e| format!("Invalid BLOB_ROUTES: {}", e))?;
+                    info!("Routing blobs across {} backends", backends.len());
+                    let routing = Arc::new(RoutingBlobStore::new(backends, routes)?);
+                    self.blob_router = Some(Arc::clone(&routing) as Arc<dyn BlobRouter>);
+                    (
+                        Arc::clone(&routing) as Arc<dyn BlobStore>,
+                        routing as Arc<dyn BlobInventory>,
+                    )
+                }
+                None => (
+                    resilient(
+                        DEFAULT_BLOB_BACKEND,
+                        Arc::clone(&default_store) as Arc<dyn BlobStore>,
+                    ),
+                    default_store as Arc<dyn BlobInventory>,
+                ),
+            };
+        if !breakers.is_empty() {
+            info!(
+                "Blob store retries enabled ({} attempts, breaker opens after {} failures)",
+                self.config.blob_store_retry_max_attempts,
+                self.config.blob_store_breaker_failure_threshold
+            );
+        }
+        self.blob_store_breakers = breakers;
 
         self.object_repo = Some(object_repo);
         self.blob_repo = Some(blob_repo);
         self.audit_repo = Some(audit_repo);
-        self.blob_store = Some(blob_store);
+        self.stats_repo = Some(stats_repo);
+        self.refcount_repo = Some(refcount_repo);
+        self.idempotency_repo = Some(idempotency_repo);
+        self.namespace_config_repo = Some(namespace_config_repo);
+        self.namespace_deletion_repo = Some(namespace_deletion_repo);
+        self.tenant_limit_provider = Some(tenant_limit_provider);
+        self.blob_inventory = Some(blob_inventory);
+        if self.config.blob_cache_enabled {
+            let blob_cache = Arc::new(CachingBlobStore::new(
+                blob_store,
+                BlobCacheConfig {
+                    max_blob_bytes: self.config.blob_cache_max_blob_bytes,
+                    capacity_bytes: self.config.blob_cache_capacity_bytes,
+                    ttl: Duration::from_secs(self.config.blob_cache_ttl_secs),
+                },
+            ));
+            info!(
+                "Blob read cache enabled ({} bytes, blobs up to {} bytes)",
+                self.config.blob_cache_capacity_bytes, self.config.blob_cache_max_blob_bytes
+            );
+            self.blob_store = Some(Arc::clone(&blob_cache) as Arc<dyn BlobStore>);
+            self.blob_cache = Some(blob_cache);
+        } else {
+            self.blob_store = Some(blob_store);
+        }
 
         Ok(self)
     }
 
+    /// Connect the Redis object metadata store (METADATA_STORE=redis)
+    #[cfg(feature = "redis")]
+    async fn redis_object_repo(
+        &self,
+        metadata_index: MetadataIndexConfig,
+    ) -> Result<Arc<dyn ObjectRepository>, Box<dyn std::error::Error>> {
+        let url = self
+            .config
+            .redis_url
+            .as_deref()
+            .ok_or("REDIS_URL is required when METADATA_STORE=redis")?;
+        let repo = RedisObjectRepository::connect(url, self.config.redis_key_prefix.clone())
+            .await
+            .map_err(|e| format!("Failed to connect to Redis: {}", e))?;
+        info!(
+            "Object metadata stored in Redis (key prefix {})",
+            self.config.redis_key_prefix
+        );
+        Ok(Arc::new(repo.with_metadata_index(metadata_index)))
+    }
+
+    #[cfg(not(feature = "redis"))]
+    async fn redis_object_repo(
+        &self,
+        _metadata_index: MetadataIndexConfig,
+    ) -> Result<Arc<dyn ObjectRepository>, Box<dyn std::error::Error>> {
+        Err("METADATA_STORE=redis requires a build with the redis feature".into())
+    }
+
     /// Set up API key repository
     pub async fn with_api_keys(mut self) -> Result<Self, Box<dyn std::error::Error>> {
         let pool = self.pool.as_ref().ok_or("Database pool not initialized")?;
-        let api_key_repo = Arc::new(PostgresApiKeyRepository::new(
-            Arc::clone(pool).as_ref().clone(),
-        ));
+        // Validated at startup; fall back to Argon2 rather than failing here
+        let hash_algorithm =
+            ApiKeyHashAlgorithm::parse(&self.config.api_key_hash).unwrap_or_default();
+        let api_key_repo = Arc::new(
+            PostgresApiKeyRepository::new(Arc::clone(pool).as_ref().clone())
+                .with_hash_algorithm(hash_algorithm),
+        );
         self.api_key_repo = Some(api_key_repo);
         Ok(self)
     }
@@ -173,78 +502,60 @@ impl ApplicationBuilder {
         Ok(self)
     }
 
-    /// Set up OIDC metadata
+    /// Set up OIDC metadata and the JWT verification key set
+    ///
+    /// Keys come from `JWT_JWKS_URL` when set, otherwise from the issuer's
+    /// discovered `jwks_uri`. Startup fails if the initial fetch fails.
     pub async fn with_oidc(mut self) -> Result<Self, Box<dyn std::error::Error>> {
+        // Configure HTTP client with SSRF protection (no redirects)
+        let http_client = ReqwestClient::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(std::time::Duration::from_secs(10))
+            .build()?;
+        let mut jwks_url = self.config.jwt_jwks_url.clone();
+
         if let Some(issuer_url_str) = &self.config.oidc_issuer_url {
             info!("Initializing OIDC for issuer: {}", issuer_url_str);
 
             let issuer_url = IssuerUrl::new(issuer_url_str.clone())?;
-
-            // Configure HTTP client with SSRF protection (no redirects)
-            let http_client = ReqwestClient::builder()
-                .redirect(reqwest::redirect::Policy::none())
-                .timeout(std::time::Duration::from_secs(10))
-                .build()?;
-
             let provider_metadata =
                 CoreProviderMetadata::discover_async(issuer_url.clone(), &http_client).await?;
-            self.oidc_metadata = Some(provider_metadata.clone());
+            jwks_url.get_or_insert_with(|| provider_metadata.jwks_uri().url().to_string());
+            self.oidc_metadata = Some(provider_metadata);
+        } else {
+            warn!("OIDC issuer URL not configured, SSO will be disabled");
+        }
 
+        if let Some(jwks_url) = jwks_url {
             // Initial JWKS fetch and cache population
-            Self::refresh_jwks(&http_client, &provider_metadata, &self.jwks_cache).await?;
-
-            // Spawn background refresh task (every hour)
+            let key_count = jwks::refresh(&http_client, &jwks_url, &self.jwks_cache).await?;
+            info!(
+                "Loaded {} JWT verification keys from {}",
+                key_count, jwks_url
+            );
+
+            // Periodic refresh picks up rotated keys. Failures leave the cached
+            // keys to expire, after which tokens are rejected (fail closed).
             let jwks_cache = Arc::clone(&self.jwks_cache);
-            let http_client_clone = http_client.clone();
-            let provider_metadata_clone = provider_metadata.clone();
+            let refresh_interval = Duration::from_secs(self.config.jwt_jwks_refresh_secs);
 
             tokio::spawn(async move {
-                let mut interval = tokio::time::interval(Duration::from_secs(3600));
+                let mut interval = tokio::time::interval(refresh_interval);
+                // The first tick completes immediately; the keys were just loaded
+                interval.tick().await;
                 loop {
                     interval.tick().await;
-                    match Self::refresh_jwks(
-                        &http_client_clone,
-                        &provider_metadata_clone,
-                        &jwks_cache,
-                    )
-                    .await
-                    {
-                        Ok(_) => info!("JWKS cache refreshed successfully"),
+                    match jwks::refresh(&http_client, &jwks_url, &jwks_cache).await {
+                        Ok(key_count) => info!("JWKS cache refreshed ({} keys)", key_count),
                         Err(e) => error!("Failed to refresh JWKS cache: {}", e),
                     }
                 }
             });
-        } else {
-            warn!("OIDC issuer URL not configured, SSO will be disabled");
         }
 
         Ok(self)
     }
 
-    /// Internal helper to refresh JWKS cache
-    async fn refresh_jwks(
-        http_client: &ReqwestClient,
-        provider_metadata: &CoreProviderMetadata,
-        jwks_cache: &Arc<moka::future::Cache<String, jsonwebtoken::DecodingKey>>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let jwks_url = provider_metadata.jwks_uri().url();
-        let jwks_response = http_client.get(jwks_url.as_str()).send().await?;
-        let jwks_body = jwks_response.bytes().await?;
-        let jwks: openidconnect::core::CoreJsonWebKeySet = serde_json::from_slice(&jwks_body)?;
-
-        for jwk in jwks.keys() {
-            if let Some(kid) = jwk.key_id() {
-                let jwk_json: serde_json::Value = serde_json::to_value(jwk)?;
-                if let (Some(n), Some(e)) = (jwk_json["n"].as_str(), jwk_json["e"].as_str()) {
-                    if let Ok(decoding_key) = jsonwebtoken::DecodingKey::from_rsa_components(n, e) {
-                        jwks_cache.insert(kid.to_string(), decoding_key).await;
-                    }
-                }
-            }
-        }
-        Ok(())
-    }
-
     /// Build application state with all use cases
     pub fn build(self) -> BuildResult {
         let pool = self.pool.ok_or("Database pool not initialized")?;
@@ -253,22 +564,137 @@ impl ApplicationBuilder {
             .ok_or("Object repository not initialized")?;
         let blob_repo = self.blob_repo.ok_or("Blob repository not initialized")?;
         let blob_store = self.blob_store.ok_or("Blob store not initialized")?;
+        let blob_inventory = self
+            .blob_inventory
+            .ok_or("Blob inventory not initialized")?;
         let api_key_repo = self
             .api_key_repo
             .ok_or("API key repository not initialized")?;
         let audit_repo = self.audit_repo.ok_or("Audit repository not initialized")?;
+        let stats_repo = self.stats_repo.ok_or("Stats repository not initialized")?;
+        let refcount_repo = self
+            .refcount_repo
+            .ok_or("Refcount repository not initialized")?;
+        let idempotency_repo = self
+            .idempotency_repo
+            .ok_or("Idempotency repository not initialized")?;
+        let namespace_config_repo = self
+            .namespace_config_repo
+            .ok_or("Namespace config repository not initialized")?;
+        let namespace_deletion_repo = self
+            .namespace_deletion_repo
+            .ok_or("Namespace deletion repository not initialized")?;
+        let tenant_limit_provider = self
+            .tenant_limit_provider
+            .ok_or("Tenant limit provider not initialized")?;
+
+        let text_extractor: Arc<dyn TextExtractor> = match self.config.text_extractor.as_str() {
+            "plain_text" => Arc::new(PlainTextExtractor),
+            _ => Arc::new(NoopTextExtractor),
+        };
+
+        let content_policy = ContentPolicy::parse(
+            self.config.upload_allowed_types.as_deref().unwrap_or(""),
+            self.config.upload_blocked_types.as_deref().unwrap_or(""),
+        )
+        .map_err(|e| format!("Invalid upload content policy: {}", e))?
+        .with_required_type(self.config.require_content_type);
+        let webhook_verifier = Arc::new(
+            WebhookVerifier::parse(
+                self.config.webhook_secrets.as_deref().unwrap_or(""),
+                self.config.webhook_tolerance_secs,
+            )
+            .map_err(|e| format!("Invalid WEBHOOK_SECRETS: {}", e))?,
+        );
 
         // Initialize use cases (application layer)
-        let upload_use_case = Arc::new(UploadObjectUseCase::with_max_upload_size_bytes(
+        let status_watch = Arc::new(StatusWatch::new());
+        let mut upload_use_case = UploadObjectUseCase::with_max_upload_size_bytes(
             Arc::clone(&object_repo),
             Arc::clone(&blob_repo),
             Arc::clone(&blob_store),
             self.config.max_upload_size_bytes,
-        ));
+        )
+        .with_max_object_size_bytes(self.config.max_object_size_bytes)
+        .with_text_extractor(text_extractor, self.config.text_extraction_max_bytes)
+        .with_content_policy(content_policy)
+        .with_namespace_configs(Arc::clone(&namespace_config_repo))
+        .with_status_watch(Arc::clone(&status_watch))
+        .with_short_hash_len(self.config.content_hash_short_len);
+        if let Some(blob_router) = &self.blob_router {
+            upload_use_case = upload_use_case.with_blob_router(Arc::clone(blob_router));
+        }
+        // Without a scanner uploads commit without being read back
+        if self.config.content_scanner == "clamav" {
+            let scanner = ClamAvScanner::new(
+                self.config.clamav_addr.clone(),
+                Duration::from_secs(self.config.content_scan_timeout_secs),
+            );
+            upload_use_case = upload_use_case
+                .with_content_scanner(Arc::new(scanner), self.config.content_scan_fail_open);
+        }
+        if self.config.upload_idempotency_ttl_hours > 0 {
+            upload_use_case = upload_use_case.with_idempotency(
+                Arc::clone(&idempotency_repo),
+                self.config.upload_idempotency_ttl_hours * 3600,
+            );
 
-        let download_use_case = Arc::new(DownloadObjectUseCase::new(
+            // Expired keys no longer match; purge them periodically
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(3600));
+                loop {
+                    interval.tick().await;
+                    match idempotency_repo.delete_expired().await {
+                        Ok(deleted) if deleted > 0 => {
+                            info!("Deleted {} expired upload idempotency keys", deleted)
+                        }
+                        Ok(_) => {}
+                        Err(e) => error!("Failed to delete expired idempotency keys: {}", e),
+                    }
+                }
+            });
+        }
+        let upload_use_case = Arc::new(upload_use_case);
+
+        let bulk_upload_use_case = Arc::new(BulkUploadUseCase::new(Arc::clone(&upload_use_case)));
+
+        let ghost_object_policy = if self.config.ghost_objects_return_gone {
+            GhostObjectPolicy::Gone
+        } else {
+            GhostObjectPolicy::InternalError
+        };
+        let access_recorder = self.config.access_tracking_enabled.then(|| {
+            Arc::new(
+                AccessRecorder::new(Arc::clone(&object_repo)).with_flush_interval(
+                    Duration::from_secs(self.config.access_flush_interval_secs),
+                ),
+            )
+        });
+        let mut download_use_case =
+            DownloadObjectUseCase::new(Arc::clone(&object_repo), Arc::clone(&blob_store))
+                .with_audit_repo(Arc::clone(&audit_repo))
+                .with_ghost_object_policy(ghost_object_policy)
+                .with_short_hashes(ShortHashes::new(
+                    Arc::clone(&blob_repo),
+                    self.config.content_hash_short_len,
+                ))
+                .with_read_verification(
+                    self.config.verify_on_read.parse().unwrap_or_default(),
+                    self.config.verify_on_read_sample_rate,
+                );
+        if let Some(access_recorder) = &access_recorder {
+            download_use_case = download_use_case.with_access_recorder(Arc::clone(access_recorder));
+        }
+        let download_use_case = Arc::new(download_use_case);
+        let download_archive_use_case = Arc::new(DownloadArchiveUseCase::new(
+            Arc::clone(&object_repo),
+            Arc::clone(&blob_store),
+        ));
+        let register_objects_use_case = Arc::new(RegisterObjectsUseCase::new(
             Arc::clone(&object_repo),
+            Arc::clone(&blob_repo),
             Arc::clone(&blob_store),
+            Arc::clone(&upload_use_case),
         ));
 
         let delete_use_case = Arc::new(DeleteObjectUseCase::new(
@@ -277,38 +703,181 @@ impl ApplicationBuilder {
             Arc::clone(&blob_store),
         ));
 
-        let list_use_case = Arc::new(ListObjectsUseCase::new(Arc::clone(&object_repo)));
+        let update_metadata_use_case = Arc::new(
+            UpdateObjectMetadataUseCase::new(Arc::clone(&object_repo)).with_limits(
+                MetadataLimits {
+                    max_bytes: self.config.metadata_max_bytes,
+                    max_tags: self.config.metadata_max_tags,
+                    max_tag_key_chars: self.config.metadata_max_tag_key_chars,
+                    max_string_chars: self.config.metadata_max_string_chars,
+                },
+            ),
+        );
+        let object_retention_use_case =
+            Arc::new(ObjectRetentionUseCase::new(Arc::clone(&object_repo)));
+        let share_object_use_case = Arc::new(ShareObjectUseCase::new(
+            Arc::clone(&object_repo),
+            Arc::clone(&blob_repo),
+            Arc::new(PostgresObjectShareRepository::new(pool.as_ref().clone())),
+            Arc::clone(&upload_use_case),
+            Arc::clone(&delete_use_case),
+        ));
+        let touch_object_use_case = Arc::new(TouchObjectUseCase::new(
+            Arc::clone(&object_repo),
+            Arc::clone(&blob_repo),
+            Arc::clone(&blob_store),
+        ));
+        let object_status_use_case = Arc::new(ObjectStatusUseCase::new(
+            Arc::clone(&object_repo),
+            status_watch,
+        ));
+
+        let list_use_case = Arc::new(
+            ListObjectsUseCase::new(Arc::clone(&object_repo))
+                .with_count_limit(self.config.list_count_limit),
+        );
         let search_use_case = Arc::new(SearchObjectsUseCase::new(Arc::clone(&object_repo)));
-        let text_search_use_case =
-            Arc::new(TextSearchObjectsUseCase::new(Arc::clone(&object_repo)));
+        let text_search_use_case = Arc::new(
+            TextSearchObjectsUseCase::new(Arc::clone(&object_repo))
+                .with_min_rank(self.config.text_search_min_rank),
+        );
+        let stats_use_case = Arc::new(StatsUseCase::with_cache_ttl(
+            stats_repo,
+            Duration::from_secs(self.config.stats_cache_ttl_secs),
+        ));
+        let list_blobs_use_case = Arc::new(
+            ListBlobsUseCase::new(Arc::clone(&blob_repo))
+                .with_short_hash_len(self.config.content_hash_short_len),
+        );
+        let reconcile_refcounts_use_case = Arc::new(
+            ReconcileRefcountsUseCase::new(refcount_repo)
+                .with_batch_size(self.config.reconcile_batch_size)
+                .with_parallelism(self.config.reconcile_parallelism),
+        );
+        // Uploads older than the stuck-upload age are abandoned, so their
+        // files are fair game
+        let compaction_use_case = Arc::new(CompactionUseCase::new(
+            blob_inventory,
+            Arc::clone(&blob_repo),
+            self.config.gc_stuck_upload_age_hours,
+        ));
+
+        let namespace_config_use_case =
+            Arc::new(NamespaceConfigUseCase::new(namespace_config_repo));
+        let delete_namespace_use_case = Arc::new(
+            DeleteNamespaceUseCase::new(namespace_deletion_repo)
+                .with_batch_size(self.config.namespace_delete_batch_size)
+                .with_max_batches(self.config.namespace_delete_max_batches),
+        );
+        let bulk_update_metadata_use_case = Arc::new(
+            BulkUpdateMetadataUseCase::new(
+                Arc::clone(&object_repo),
+                Arc::clone(&update_metadata_use_case),
+            )
+            .with_batch_size(self.config.bulk_metadata_batch_size)
+            .with_max_batches(self.config.bulk_metadata_max_batches),
+        );
 
-        let create_api_key_use_case = Arc::new(CreateApiKeyUseCase::new(Arc::clone(&api_key_repo)));
+        let api_key_hash =
+            ApiKeyHashAlgorithm::parse(&self.config.api_key_hash).unwrap_or_default();
+        let create_api_key_use_case = Arc::new(
+            CreateApiKeyUseCase::new(Arc::clone(&api_key_repo)).with_hash_algorithm(api_key_hash),
+        );
         let list_api_keys_use_case = Arc::new(ListApiKeysUseCase::new(Arc::clone(&api_key_repo)));
         let get_api_key_use_case = Arc::new(GetApiKeyUseCase::new(Arc::clone(&api_key_repo)));
         let update_api_key_use_case = Arc::new(UpdateApiKeyUseCase::new(Arc::clone(&api_key_repo)));
         let delete_api_key_use_case = Arc::new(DeleteApiKeyUseCase::new(Arc::clone(&api_key_repo)));
+        let rotate_api_key_use_case = Arc::new(
+            RotateApiKeyUseCase::new(Arc::clone(&api_key_repo)).with_hash_algorithm(api_key_hash),
+        );
+
+        let pool_monitor = Arc::new(PoolMonitor::new(
+            Arc::clone(&pool),
+            self.config.db_max_connections,
+            PoolMonitorConfig {
+                interval: Duration::from_secs(self.config.db_pool_monitor_interval_secs),
+                probe_timeout: Duration::from_secs(self.config.db_acquire_timeout_secs),
+                saturation_percent: self.config.db_pool_saturation_percent,
+                dead_after_failures: self.config.db_pool_dead_after_failures,
+            },
+        ));
+
+        // Scrub reads bypass the cache: a cached copy says nothing about the disk
+        let scrubber = self.config.scrub_enabled.then(|| {
+            let blob_store = self.blob_cache.as_ref().map_or_else(
+                || Arc::clone(&blob_store),
+                |cache| Arc::clone(cache.inner()),
+            );
+            Arc::new(BlobScrubber::new(
+                Arc::new(PostgresScrubRepository::new(pool.as_ref().clone())),
+                blob_store,
+                ScrubConfig::new(
+                    Duration::from_secs(self.config.scrub_interval_days * 24 * 3600),
+                    self.config.scrub_max_bytes_per_sec,
+                ),
+            ))
+        });
 
         let app_state = AppState {
             pool: Arc::clone(&pool),
+            db_retry_policy: self.db_retry_policy,
+            pool_monitor,
             upload_use_case,
+            bulk_upload_use_case,
             download_use_case,
+            download_archive_use_case,
+            register_objects_use_case,
             delete_use_case,
+            update_metadata_use_case,
+            object_retention_use_case,
+            share_object_use_case,
+            touch_object_use_case,
+            object_status_use_case,
             list_use_case,
             search_use_case,
             text_search_use_case,
+            stats_use_case,
+            list_blobs_use_case,
+            reconcile_refcounts_use_case,
+            compaction_use_case,
+            namespace_config_use_case,
+            delete_namespace_use_case,
+            bulk_update_metadata_use_case,
             create_api_key_use_case,
             list_api_keys_use_case,
             get_api_key_use_case,
             update_api_key_use_case,
             delete_api_key_use_case,
+            rotate_api_key_use_case,
             audit_repo: Arc::clone(&audit_repo),
             blob_store: Arc::clone(&blob_store),
+            blob_cache: self.blob_cache,
+            blob_store_breakers: self.blob_store_breakers,
+            download_compression: Arc::new(CompressionMeter::new()),
+            request_latency: Arc::new(RequestLatency::new(self.config.slow_requests())),
+            blob_filter: self.blob_filter,
+            concurrency_limiter: Arc::new(ConcurrencyLimiter::new(RateLimitConfig {
+                max_concurrent_per_user: self.config.max_concurrent_per_user,
+                max_concurrent_per_tenant: self.config.max_concurrent_per_tenant,
+                max_concurrent_per_ip: self.config.max_concurrent_per_ip,
+                concurrency_wait_ms: self.config.concurrency_wait_ms,
+                ..RateLimitConfig::default()
+            })),
+            connection_limiter: Arc::new(ConnectionLimiter::new(
+                self.config.max_connections_per_ip,
+            )),
+            operation_progress: Arc::new(OperationProgress::new()),
+            tenant_limit_provider,
+            webhook_verifier,
             gc: self.gc,
+            access_recorder,
+            scrubber,
             config: self.config.clone(),
             oidc_metadata: self.oidc_metadata,
             jwks_cache: self.jwks_cache,
             expected_migration_count: self.expected_migration_count,
             start_time: Instant::now(),
+            shutting_down: Arc::new(AtomicBool::new(false)),
         };
 
         Ok((app_state, api_key_repo, audit_repo))
@@ -326,14 +895,46 @@ impl ApplicationBuilder {
             .ok_or("Blob store not initialized")?;
         let object_repo = self.object_repo.clone();
 
-        let gc = GarbageCollector::with_object_repo(
+        let gc_config = GcConfig::new(
+            Duration::from_secs(self.config.gc_interval_secs),
+            self.config.gc_batch_size,
+            self.config.gc_stuck_upload_age_hours,
+        )
+        .with_max_concurrent_deletions(self.config.gc_max_concurrent_deletions)
+        .with_dry_run(self.config.gc_dry_run)
+        .with_orphaned_blobs(self.config.gc_orphaned_blobs_enabled)
+        .with_stuck_uploads(self.config.gc_stuck_uploads_enabled)
+        .with_write_recovery(self.config.gc_write_recovery_enabled)
+        .with_write_recovery_age_minutes(self.config.gc_write_recovery_age_minutes)
+        .with_deletion_retries(DeletionRetryPolicy::new(
+            self.config.gc_delete_max_attempts,
+            Duration::from_secs(self.config.gc_delete_retry_base_secs),
+            Duration::from_secs(self.config.gc_delete_retry_max_secs),
+        ));
+
+        let mut gc = GarbageCollector::with_config(
             Arc::clone(blob_repo),
             Arc::clone(blob_store),
             object_repo,
-            Duration::from_secs(self.config.gc_interval_secs),
-            self.config.gc_batch_size,
-            24, // 24 hours
+            gc_config,
         );
+        if let Some(pool) = &self.pool {
+            gc = gc.with_deletion_failures(Arc::new(PostgresBlobDeletionFailureRepository::new(
+                pool.as_ref().clone(),
+            )));
+        }
+        // The blob repository checks locks against the objects table, which
+        // is empty when objects live elsewhere
+        if self.config.metadata_store == "redis" {
+            if let Some(object_repo) = &self.object_repo {
+                gc = gc.with_lock_check(Arc::clone(object_repo));
+            }
+        }
+        if self.config.gc_audit_enabled {
+            if let Some(audit_repo) = &self.audit_repo {
+                gc = gc.with_audit_repo(Arc::clone(audit_repo));
+            }
+        }
 
         Ok(Arc::new(gc))
     }
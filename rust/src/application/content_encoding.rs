@@ -0,0 +1,97 @@
+//! Decoding content stored with a `Content-Encoding`
+//!
+//! Objects are stored as uploaded, so their hash and size always describe
+//! the encoded bytes. Readers that need the content itself (downloads for
+//! clients that don't accept the encoding, text extraction) decode it here.
+
+use async_compression::tokio::bufread::{GzipDecoder, ZstdDecoder};
+use std::io::Cursor;
+use tokio::io::{AsyncReadExt, BufReader};
+
+use crate::application::ports::BlobReader;
+use crate::domain::value_objects::ContentEncoding;
+
+/// Wrap a reader of encoded content in a decoder for `encoding`
+///
+/// Decoding errors, e.g. content that isn't actually gzip, surface as I/O
+/// errors while reading.
+pub fn decode(reader: BlobReader, encoding: ContentEncoding) -> BlobReader {
+    let reader = BufReader::new(reader);
+    match encoding {
+        ContentEncoding::Gzip => {
+            let mut decoder = GzipDecoder::new(reader);
+            // Concatenated gzip members decode as one stream, like `gunzip`
+            decoder.multiple_members(true);
+            Box::pin(decoder)
+        }
+        ContentEncoding::Zstd => {
+            let mut decoder = ZstdDecoder::new(reader);
+            decoder.multiple_members(true);
+            Box::pin(decoder)
+        }
+    }
+}
+
+/// Decode up to `limit` bytes from the start of encoded content
+///
+/// `prefix` is usually cut off mid-stream; whatever decoded before the cut
+/// is returned, so content types can be sniffed from the first bytes.
+pub async fn decode_prefix(prefix: &[u8], encoding: ContentEncoding, limit: usize) -> Vec<u8> {
+    let reader = decode(Box::pin(Cursor::new(prefix.to_vec())), encoding);
+    let mut reader = reader.take(limit as u64);
+    let mut decoded = Vec::with_capacity(limit);
+    let mut chunk = [0u8; 512];
+    // Stops at the cut, which reads as an unexpected end of stream
+    while let Ok(n @ 1..) = reader.read(&mut chunk).await {
+        decoded.extend_from_slice(&chunk[..n]);
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_compression::tokio::bufread::{GzipEncoder, ZstdEncoder};
+
+    const TEXT: &[u8] = b"the quick brown fox jumps over the lazy dog";
+
+    async fn read_all(mut reader: BlobReader) -> std::io::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).await?;
+        Ok(content)
+    }
+
+    #[tokio::test]
+    async fn test_decode_round_trip() {
+        let gzip = read_all(Box::pin(GzipEncoder::new(TEXT))).await.unwrap();
+        let zstd = read_all(Box::pin(ZstdEncoder::new(TEXT))).await.unwrap();
+
+        for (encoded, encoding) in [(gzip, ContentEncoding::Gzip), (zstd, ContentEncoding::Zstd)] {
+            assert_ne!(encoded, TEXT);
+            let decoded = read_all(decode(Box::pin(Cursor::new(encoded)), encoding))
+                .await
+                .unwrap();
+            assert_eq!(decoded, TEXT);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_prefix_of_truncated_content() {
+        // Poorly compressible, so half the stream holds more than the limit
+        let content: Vec<u8> = (0u32..4096)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        let encoded = read_all(Box::pin(GzipEncoder::new(Cursor::new(content.clone()))))
+            .await
+            .unwrap();
+
+        let decoded = decode_prefix(&encoded[..encoded.len() / 2], ContentEncoding::Gzip, 64).await;
+        assert_eq!(decoded, &content[..64]);
+    }
+
+    #[tokio::test]
+    async fn test_decode_rejects_content_in_another_encoding() {
+        let reader = decode(Box::pin(TEXT), ContentEncoding::Gzip);
+        assert!(read_all(reader).await.is_err());
+    }
+}
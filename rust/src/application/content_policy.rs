@@ -0,0 +1,390 @@
+//! Content-type allow and block lists for uploads
+//!
+//! Entries are MIME types (`image/png`, or `image/*` for a whole top-level
+//! type) or file extensions (`.exe`), matched case-insensitively. An upload is
+//! checked against its declared content type, the type sniffed from its first
+//! bytes and the extension of its key, so an executable renamed to `.jpg` and
+//! declared as `image/jpeg` is still caught by its magic bytes.
+//!
+//! A policy can also require every upload to have a type: declared, or
+//! sniffed when no `Content-Type` is sent.
+
+use std::collections::HashMap;
+
+use crate::application::metadata_index::ALL_NAMESPACES;
+
+/// Executables blocked by the request validator unless configured otherwise
+pub const DEFAULT_BLOCKED_UPLOAD_TYPES: &str =
+    "*=application/x-executable,application/x-msdownload,.exe,.bat,.cmd,.scr,.pif,.com";
+
+/// Bytes read from the start of an upload to sniff its type
+pub const SNIFF_PREFIX_BYTES: usize = 512;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ContentRule {
+    /// Full MIME type, or `type/*`
+    Type(String),
+    /// Extension without the leading dot
+    Extension(String),
+}
+
+impl ContentRule {
+    fn parse(entry: &str) -> Result<Self, String> {
+        let entry = entry.to_ascii_lowercase();
+        if let Some(ext) = entry.strip_prefix('.') {
+            if ext.is_empty() || ext.contains(['.', '/']) {
+                return Err(format!("Invalid extension '.{ext}'"));
+            }
+            return Ok(Self::Extension(ext.to_string()));
+        }
+
+        match entry.split_once('/') {
+            Some((top, sub)) if !top.is_empty() && !sub.is_empty() && top != "*" => {
+                Ok(Self::Type(entry))
+            }
+            _ => Err(format!(
+                "Invalid content type '{entry}': expected type/subtype, type/* or .ext"
+            )),
+        }
+    }
+
+    fn matches_type(&self, mime: &str) -> bool {
+        match self {
+            Self::Type(rule) => match rule.strip_suffix("/*") {
+                Some(top) => mime.split('/').next() == Some(top),
+                None => rule == mime,
+            },
+            Self::Extension(_) => false,
+        }
+    }
+
+    fn matches_extension(&self, ext: &str) -> bool {
+        matches!(self, Self::Extension(rule) if rule == ext)
+    }
+}
+
+/// Which content types may be uploaded, per namespace
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentPolicy {
+    allowed: HashMap<String, Vec<ContentRule>>,
+    blocked: HashMap<String, Vec<ContentRule>>,
+    /// Reject uploads with neither a declared nor a sniffed type
+    require_type: bool,
+}
+
+impl ContentPolicy {
+    /// Parse allow and block specifications like
+    /// `images=image/*,.png,.jpg;*=application/pdf`
+    ///
+    /// Each `;`-separated entry maps a namespace (or `*` for all namespaces)
+    /// to a comma-separated list of MIME types and extensions. A namespace's
+    /// allowlist replaces the `*` allowlist; blocklists accumulate.
+    pub fn parse(allowed: &str, blocked: &str) -> Result<Self, String> {
+        Ok(Self {
+            allowed: parse_rules(allowed)?,
+            blocked: parse_rules(blocked)?,
+            require_type: false,
+        })
+    }
+
+    /// Reject uploads whose type is neither declared nor recognised from
+    /// their first bytes
+    pub fn with_required_type(mut self, require_type: bool) -> Self {
+        self.require_type = require_type;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.blocked.is_empty() && !self.require_type
+    }
+
+    /// Check an upload's declared type, sniffed type and key extension
+    ///
+    /// The blocklist wins over the allowlist. An allowlist with MIME types
+    /// requires every known type of the upload to match; one with
+    /// extensions requires the key's extension to match. Content whose type
+    /// cannot be determined is rejected by a MIME allowlist, and by any
+    /// policy requiring a type.
+    pub fn check(
+        &self,
+        namespace: &str,
+        key: Option<&str>,
+        declared_type: Option<&str>,
+        sniffed_type: Option<&str>,
+    ) -> Result<(), String> {
+        let extension = key.and_then(extension);
+        let mut types: Vec<String> = declared_type
+            .and_then(essence)
+            .into_iter()
+            .chain(sniffed_type.map(str::to_string))
+            .collect();
+        types.dedup();
+
+        let namespace = namespace.to_lowercase();
+        let blocked = [namespace.as_str(), ALL_NAMESPACES]
+            .iter()
+            .filter_map(|ns| self.blocked.get(*ns))
+            .flatten();
+        for rule in blocked {
+            if let Some(mime) = types.iter().find(|mime| rule.matches_type(mime)) {
+                return Err(format!("Content type '{mime}' is not allowed"));
+            }
+            if let Some(ext) = extension
+                .as_deref()
+                .filter(|ext| rule.matches_extension(ext))
+            {
+                return Err(format!("File extension '.{ext}' is not allowed"));
+            }
+        }
+
+        if self.require_type && types.is_empty() {
+            return Err("Content type is required: send a Content-Type header".to_string());
+        }
+
+        let Some(allowed) = self
+            .allowed
+            .get(&namespace)
+            .or_else(|| self.allowed.get(ALL_NAMESPACES))
+        else {
+            return Ok(());
+        };
+
+        if allowed.iter().any(|r| matches!(r, ContentRule::Type(_))) {
+            if types.is_empty() {
+                return Err("Content type could not be determined".to_string());
+            }
+            if let Some(mime) = types
+                .iter()
+                .find(|mime| !allowed.iter().any(|r| r.matches_type(mime)))
+            {
+                return Err(format!("Content type '{mime}' is not allowed"));
+            }
+        }
+
+        if allowed
+            .iter()
+            .any(|r| matches!(r, ContentRule::Extension(_)))
+        {
+            match extension {
+                Some(ext) if allowed.iter().any(|r| r.matches_extension(&ext)) => {}
+                Some(ext) => return Err(format!("File extension '.{ext}' is not allowed")),
+                None => return Err("File extension is required".to_string()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_rules(spec: &str) -> Result<HashMap<String, Vec<ContentRule>>, String> {
+    let mut rules_by_namespace: HashMap<String, Vec<ContentRule>> = HashMap::new();
+
+    for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let (namespace, rules) = entry.split_once('=').ok_or_else(|| {
+            format!("Invalid content policy entry '{entry}': expected namespace=type1,type2")
+        })?;
+
+        let namespace = namespace.trim().to_lowercase();
+        if namespace.is_empty() {
+            return Err(format!(
+                "Invalid content policy entry '{entry}': empty namespace"
+            ));
+        }
+
+        let rules = rules
+            .split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(ContentRule::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if rules.is_empty() {
+            return Err(format!("Invalid content policy entry '{entry}': no types"));
+        }
+
+        rules_by_namespace
+            .entry(namespace)
+            .or_default()
+            .extend(rules);
+    }
+
+    Ok(rules_by_namespace)
+}
+
+/// MIME type without parameters such as "; charset=utf-8"
+fn essence(content_type: &str) -> Option<String> {
+    let mime = content_type.split(';').next()?.trim();
+    (!mime.is_empty()).then(|| mime.to_ascii_lowercase())
+}
+
+/// Lowercased extension of the last path segment of a key
+fn extension(key: &str) -> Option<String> {
+    let name = key.rsplit('/').next()?;
+    let (stem, ext) = name.rsplit_once('.')?;
+    (!stem.is_empty() && !ext.is_empty()).then(|| ext.to_ascii_lowercase())
+}
+
+/// Identify content from its magic bytes
+///
+/// Only formats worth enforcing policy on are recognised: executables and
+/// scripts, plus common formats they are disguised as.
+pub fn sniff_content_type(prefix: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"MZ", "application/x-msdownload"),
+        (b"\x7fELF", "application/x-executable"),
+        (b"\xfe\xed\xfa\xce", "application/x-mach-binary"),
+        (b"\xfe\xed\xfa\xcf", "application/x-mach-binary"),
+        (b"\xce\xfa\xed\xfe", "application/x-mach-binary"),
+        (b"\xcf\xfa\xed\xfe", "application/x-mach-binary"),
+        (b"#!", "text/x-shellscript"),
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+    ];
+
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| prefix.starts_with(magic))
+        .map(|(_, mime)| *mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rejects_malformed_entries() {
+        assert!(ContentPolicy::parse("images", "").is_err());
+        assert!(ContentPolicy::parse("=image/png", "").is_err());
+        assert!(ContentPolicy::parse("images=", "").is_err());
+        assert!(ContentPolicy::parse("", "*=png").is_err());
+        assert!(ContentPolicy::parse("", "*=*/*").is_err());
+        assert!(ContentPolicy::parse("", "*=.").is_err());
+        assert!(ContentPolicy::parse("", "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_blocklist_checks_declared_sniffed_and_extension() {
+        let policy = ContentPolicy::parse("", "*=application/x-msdownload,.exe").unwrap();
+
+        assert!(policy.check("docs", Some("setup.exe"), None, None).is_err());
+        assert!(policy
+            .check("docs", None, Some("application/x-msdownload"), None)
+            .is_err());
+        // A renamed executable is caught by its magic bytes
+        assert!(policy
+            .check(
+                "docs",
+                Some("cat.jpg"),
+                Some("image/jpeg"),
+                sniff_content_type(b"MZ\x90\x00")
+            )
+            .is_err());
+        assert!(policy
+            .check("docs", Some("cat.jpg"), Some("image/jpeg"), None)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_allowlist_per_namespace() {
+        let policy = ContentPolicy::parse("images=image/*;*=application/pdf", "").unwrap();
+
+        assert!(policy
+            .check("images", None, Some("image/png; q=1"), Some("image/png"))
+            .is_ok());
+        assert!(policy
+            .check("images", None, Some("application/pdf"), None)
+            .is_err());
+        assert!(policy
+            .check("docs", None, Some("application/pdf"), None)
+            .is_ok());
+        // Declared type alone does not get a disguised upload through
+        assert!(policy
+            .check(
+                "images",
+                None,
+                Some("image/jpeg"),
+                Some("text/x-shellscript")
+            )
+            .is_err());
+        assert!(policy.check("images", None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_allowlist_of_extensions() {
+        let policy = ContentPolicy::parse("*=.csv,.parquet", "").unwrap();
+
+        assert!(policy
+            .check("data", Some("2024/events.CSV"), None, None)
+            .is_ok());
+        assert!(policy
+            .check("data", Some("events.json"), None, None)
+            .is_err());
+        assert!(policy.check("data", Some("events"), None, None).is_err());
+    }
+
+    #[test]
+    fn test_required_type_declared_or_sniffed() {
+        let strict = ContentPolicy::parse("", "")
+            .unwrap()
+            .with_required_type(true);
+        let lenient = ContentPolicy::parse("", "").unwrap();
+
+        assert!(!strict.is_empty());
+        assert!(strict
+            .check(
+                "docs",
+                Some("a.bin"),
+                Some("application/octet-stream"),
+                None
+            )
+            .is_ok());
+        // Detected content satisfies the requirement without a header
+        assert!(strict
+            .check("docs", None, None, sniff_content_type(b"%PDF-1.7"))
+            .is_ok());
+        assert!(strict
+            .check(
+                "docs",
+                Some("notes.txt"),
+                None,
+                sniff_content_type(b"hello")
+            )
+            .is_err());
+        assert!(strict.check("docs", None, Some(" ; q=1"), None).is_err());
+        assert!(lenient.check("docs", None, None, None).is_ok());
+    }
+
+    #[test]
+    fn test_blocklist_wins_over_allowlist() {
+        let policy = ContentPolicy::parse("*=application/*", "*=application/x-msdownload").unwrap();
+
+        assert!(policy
+            .check("docs", None, Some("application/pdf"), None)
+            .is_ok());
+        assert!(policy
+            .check("docs", None, Some("application/x-msdownload"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sniff_content_type() {
+        assert_eq!(
+            sniff_content_type(b"\x7fELF\x02\x01"),
+            Some("application/x-executable")
+        );
+        assert_eq!(
+            sniff_content_type(b"\xcf\xfa\xed\xfe\x07"),
+            Some("application/x-mach-binary")
+        );
+        assert_eq!(
+            sniff_content_type(b"#!/bin/sh\n"),
+            Some("text/x-shellscript")
+        );
+        assert_eq!(sniff_content_type(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff_content_type(b"hello"), None);
+        assert_eq!(sniff_content_type(b""), None);
+    }
+}
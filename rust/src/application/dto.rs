@@ -3,9 +3,13 @@ use time::format_description::well_known::Rfc3339;
 use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::application::ports::ObjectShare;
 use crate::domain::{
-    entities::Object,
-    value_objects::{ApiKeyPermissions, ObjectId, ObjectMetadata, ObjectStatus, StorageClass},
+    entities::{Blob, KeyPolicy, NamespaceConfig, Object},
+    value_objects::{
+        ApiKeyPermissions, ContentEncoding, ContentHash, ObjectId, ObjectMetadata, ObjectStatus,
+        StorageClass,
+    },
 };
 
 /// DTO for object metadata responses
@@ -18,11 +22,26 @@ pub struct ObjectDto {
     pub status: ObjectStatus,
     pub storage_class: StorageClass,
     pub content_hash: Option<String>,
+    /// Shortest unique prefix of `content_hash`, accepted in its place;
+    /// set on single-object responses when short hashes are on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_hash: Option<String>,
     pub size_bytes: Option<u64>,
     pub content_type: Option<String>,
+    /// Compression of the stored content; `content_hash` and `size_bytes`
+    /// describe the stored bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<ContentEncoding>,
     pub metadata: ObjectMetadata,
     pub created_at: String,
     pub updated_at: String,
+    /// Number of recorded downloads (0 when access tracking is disabled)
+    pub download_count: u64,
+    pub last_accessed_at: Option<String>,
+    /// Until when the object can't be overwritten or deleted (RFC 3339)
+    pub retention_until: Option<String>,
+    /// Locked until the hold is released
+    pub legal_hold: bool,
 }
 
 impl From<Object> for ObjectDto {
@@ -35,15 +54,206 @@ impl From<Object> for ObjectDto {
             status: obj.status(),
             storage_class: obj.storage_class(),
             content_hash: obj.content_hash().map(|h| h.to_string()),
+            short_hash: None,
             size_bytes: obj.size_bytes(),
             content_type: obj.content_type().map(|c| c.to_string()),
+            content_encoding: obj.content_encoding(),
             metadata: obj.metadata().clone(),
             created_at: obj.created_at().format(&Rfc3339).unwrap_or_default(),
             updated_at: obj.updated_at().format(&Rfc3339).unwrap_or_default(),
+            download_count: obj.download_count(),
+            last_accessed_at: obj
+                .last_accessed_at()
+                .and_then(|at| at.format(&Rfc3339).ok()),
+            retention_until: obj
+                .retention_until()
+                .and_then(|at| at.format(&Rfc3339).ok()),
+            legal_hold: obj.legal_hold(),
         }
     }
 }
 
+/// An object's full metadata record, as returned without its content
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ObjectRecordDto {
+    #[serde(flatten)]
+    pub object: ObjectDto,
+    /// Sharing of the object's content; only reported to admins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup: Option<DedupInfo>,
+}
+
+/// How many objects share a blob through content deduplication
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DedupInfo {
+    /// Objects referencing the blob, this one included
+    pub ref_count: u64,
+    /// Whether other objects reference the same blob
+    pub shared: bool,
+}
+
+impl DedupInfo {
+    pub fn from_ref_count(ref_count: u64) -> Self {
+        Self {
+            ref_count,
+            shared: ref_count > 1,
+        }
+    }
+}
+
+/// A field of [`ObjectDto`] that a listing can be projected onto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectField {
+    Id,
+    Namespace,
+    TenantId,
+    Key,
+    Status,
+    StorageClass,
+    ContentHash,
+    SizeBytes,
+    ContentType,
+    Metadata,
+    CreatedAt,
+    UpdatedAt,
+    DownloadCount,
+    LastAccessedAt,
+}
+
+impl ObjectField {
+    pub const ALL: [ObjectField; 14] = [
+        Self::Id,
+        Self::Namespace,
+        Self::TenantId,
+        Self::Key,
+        Self::Status,
+        Self::StorageClass,
+        Self::ContentHash,
+        Self::SizeBytes,
+        Self::ContentType,
+        Self::Metadata,
+        Self::CreatedAt,
+        Self::UpdatedAt,
+        Self::DownloadCount,
+        Self::LastAccessedAt,
+    ];
+
+    /// Name of the field in [`ObjectDto`]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Namespace => "namespace",
+            Self::TenantId => "tenant_id",
+            Self::Key => "key",
+            Self::Status => "status",
+            Self::StorageClass => "storage_class",
+            Self::ContentHash => "content_hash",
+            Self::SizeBytes => "size_bytes",
+            Self::ContentType => "content_type",
+            Self::Metadata => "metadata",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+            Self::DownloadCount => "download_count",
+            Self::LastAccessedAt => "last_accessed_at",
+        }
+    }
+
+    /// Parse a comma-separated field list such as `id,key,size,content_type`
+    ///
+    /// `size` is accepted for `size_bytes`. Repeated fields are kept once;
+    /// an empty list or an unknown name is an error.
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, String> {
+        let mut fields = Vec::new();
+        for name in list.split(',').map(str::trim) {
+            let field = Self::ALL
+                .into_iter()
+                .find(|field| field.as_str() == name)
+                .or((name == "size").then_some(Self::SizeBytes))
+                .ok_or_else(|| {
+                    let names: Vec<&str> = Self::ALL.iter().map(|f| f.as_str()).collect();
+                    format!(
+                        "Unknown field '{}' (expected one of: {})",
+                        name,
+                        names.join(", ")
+                    )
+                })?;
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        Ok(fields)
+    }
+}
+
+/// An object reduced to the fields a listing asked for
+///
+/// Fields that were not requested are omitted from the JSON; requested
+/// fields without a value are `null`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct ObjectProjection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ObjectStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_class: Option<StorageClass>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ObjectMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_accessed_at: Option<Option<String>>,
+}
+
+impl ObjectProjection {
+    /// Project a full object DTO onto `fields`
+    pub fn new(dto: &ObjectDto, fields: &[ObjectField]) -> Self {
+        let mut projection = Self::default();
+        for field in fields {
+            match field {
+                ObjectField::Id => projection.id = Some(dto.id.clone()),
+                ObjectField::Namespace => projection.namespace = Some(dto.namespace.clone()),
+                ObjectField::TenantId => projection.tenant_id = Some(dto.tenant_id.clone()),
+                ObjectField::Key => projection.key = Some(dto.key.clone()),
+                ObjectField::Status => projection.status = Some(dto.status),
+                ObjectField::StorageClass => projection.storage_class = Some(dto.storage_class),
+                ObjectField::ContentHash => {
+                    projection.content_hash = Some(dto.content_hash.clone())
+                }
+                ObjectField::SizeBytes => projection.size_bytes = Some(dto.size_bytes),
+                ObjectField::ContentType => {
+                    projection.content_type = Some(dto.content_type.clone())
+                }
+                ObjectField::Metadata => projection.metadata = Some(dto.metadata.clone()),
+                ObjectField::CreatedAt => projection.created_at = Some(dto.created_at.clone()),
+                ObjectField::UpdatedAt => projection.updated_at = Some(dto.updated_at.clone()),
+                ObjectField::DownloadCount => projection.download_count = Some(dto.download_count),
+                ObjectField::LastAccessedAt => {
+                    projection.last_accessed_at = Some(dto.last_accessed_at.clone())
+                }
+            }
+        }
+        projection
+    }
+}
+
 /// DTO for upload request
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UploadRequest {
@@ -54,6 +264,291 @@ pub struct UploadRequest {
     #[validate(length(max = 255))]
     pub key: Option<String>,
     pub storage_class: Option<StorageClass>,
+    /// MIME type of the uploaded content, used to pick a text extractor
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Compression the content was uploaded with, kept as sent
+    #[serde(default)]
+    pub content_encoding: Option<ContentEncoding>,
+    /// SHA-256 the client expects the content to have; a mismatch aborts the upload
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub expected_hash: Option<ContentHash>,
+    /// Client-chosen key that makes retries of this upload return the first result
+    #[serde(default)]
+    #[validate(length(max = 255))]
+    pub idempotency_key: Option<String>,
+}
+
+/// Optimistic concurrency condition for an upload to a key
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum UploadPrecondition {
+    /// Unconditional upload
+    #[default]
+    None,
+    /// `If-Match`: overwrite the object under the key only while its content
+    /// hash (ETag) still matches; `None` is `If-Match: *` (any existing object)
+    IfMatch(Option<ContentHash>),
+    /// `If-None-Match: *`: create only if no object exists under the key
+    IfNoneMatch,
+}
+
+/// Response of starting or continuing a resumable upload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ResumableUploadDto {
+    pub upload_id: String,
+    /// Bytes received so far; the next chunk starts here
+    pub offset: u64,
+}
+
+/// Chunk of a resumable upload, from its `Content-Range`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadChunk {
+    /// First and last byte (inclusive); `None` sends no bytes (`bytes */total`)
+    pub range: Option<(u64, u64)>,
+    /// Size of the whole object if known; the upload commits once it is reached
+    pub total_size: Option<u64>,
+    /// SHA-256 the whole object must have, checked before the commit
+    pub expected_hash: Option<ContentHash>,
+}
+
+/// Result of receiving a chunk of a resumable upload
+#[derive(Debug, Clone)]
+pub enum ResumeOutcome {
+    /// More bytes are expected
+    Incomplete(ResumableUploadDto),
+    /// The last chunk arrived and the object was committed
+    Committed(ObjectDto),
+}
+
+/// Archive formats of bulk uploads and archive downloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    Tar,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Archive format for a request `Content-Type`
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.split(';').next()?.trim() {
+            "application/x-tar" | "application/tar" => Some(Self::Tar),
+            "application/zip" | "application/x-zip-compressed" => Some(Self::Zip),
+            _ => None,
+        }
+    }
+
+    /// `Content-Type` of an archive in this format
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Tar => "application/x-tar",
+            Self::Zip => "application/zip",
+        }
+    }
+
+    /// File extension, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Tar => "tar",
+            Self::Zip => "zip",
+        }
+    }
+}
+
+/// DTO for a bulk upload: every archive entry becomes an object in `namespace`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BulkUploadRequest {
+    pub namespace: String,
+    pub tenant_id: String,
+    pub storage_class: Option<StorageClass>,
+}
+
+/// Outcome of a single archive entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkUploadEntryStatus {
+    /// Stored as a new object
+    Created,
+    /// Not a regular file (symlink, device, ...)
+    Skipped,
+    /// Path rejected (absolute, traversal, too long)
+    Rejected,
+    /// Upload of the entry failed
+    Failed,
+}
+
+impl BulkUploadEntryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Skipped => "skipped",
+            Self::Rejected => "rejected",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Manifest line for a single archive entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BulkUploadEntry {
+    /// Path of the entry inside the archive
+    pub path: String,
+    pub status: BulkUploadEntryStatus,
+    pub object_id: Option<String>,
+    pub key: Option<String>,
+    pub content_hash: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl BulkUploadEntry {
+    pub fn created(path: String, object: &ObjectDto) -> Self {
+        Self {
+            path,
+            status: BulkUploadEntryStatus::Created,
+            object_id: Some(object.id.clone()),
+            key: object.key.clone(),
+            content_hash: object.content_hash.clone(),
+            size_bytes: object.size_bytes,
+            error: None,
+        }
+    }
+
+    pub fn unsuccessful(path: String, status: BulkUploadEntryStatus, error: String) -> Self {
+        Self {
+            path,
+            status,
+            object_id: None,
+            key: None,
+            content_hash: None,
+            size_bytes: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Result of a bulk upload
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BulkUploadManifest {
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub entries: Vec<BulkUploadEntry>,
+    /// Set when the archive could not be read to the end; entries listed
+    /// before the error were processed
+    pub error: Option<String>,
+}
+
+/// One NDJSON line of a bulk register: an object over a blob that is already
+/// in the blob store
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RegisterObjectLine {
+    pub namespace: String,
+    #[validate(length(min = 1, max = 255))]
+    pub key: String,
+    /// SHA-256 of the blob, as stored under the blob store's hash layout
+    pub content_hash: String,
+    #[serde(alias = "size")]
+    pub size_bytes: u64,
+    pub content_type: Option<String>,
+    /// Storage class the blob is stored in (default: hot)
+    pub storage_class: Option<StorageClass>,
+}
+
+/// Outcome of a single bulk register line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RegisterObjectStatus {
+    /// Registered as a new committed object
+    Created,
+    /// Not valid JSON, an invalid namespace, key or hash, or refused by
+    /// the namespace's key policy or the content policy
+    Rejected,
+    /// Blob missing from the store, key taken, or registration failed
+    Failed,
+}
+
+/// Result line for a single bulk register line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RegisterObjectResult {
+    /// Line number in the request body, from 1
+    pub line: u64,
+    pub status: RegisterObjectStatus,
+    pub object_id: Option<String>,
+    pub key: Option<String>,
+    pub error: Option<String>,
+}
+
+impl RegisterObjectResult {
+    pub fn created(line: u64, object: &ObjectDto) -> Self {
+        Self {
+            line,
+            status: RegisterObjectStatus::Created,
+            object_id: Some(object.id.clone()),
+            key: object.key.clone(),
+            error: None,
+        }
+    }
+
+    pub fn unsuccessful(
+        line: u64,
+        status: RegisterObjectStatus,
+        key: Option<String>,
+        error: String,
+    ) -> Self {
+        Self {
+            line,
+            status,
+            object_id: None,
+            key,
+            error: Some(error),
+        }
+    }
+}
+
+/// DTO for downloading the committed objects of a namespace as one archive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveDownloadRequest {
+    pub namespace: String,
+    pub tenant_id: String,
+    /// Keep only keys starting with this prefix
+    pub prefix: Option<String>,
+}
+
+/// Manifest line for a single object of a downloaded archive
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveDownloadEntry {
+    /// Path of the object's entry inside the archive; absent when its
+    /// content could not be added
+    pub path: Option<String>,
+    pub object: ObjectDto,
+    /// Why the content is missing from the archive
+    pub error: Option<String>,
+}
+
+/// Manifest written as the last entry of a downloaded archive
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveDownloadManifest {
+    pub namespace: String,
+    pub tenant_id: String,
+    pub prefix: Option<String>,
+    /// Objects whose content is in the archive
+    pub added: usize,
+    /// Objects listed without content, with the reason in their entry
+    pub failed: usize,
+    pub entries: Vec<ArchiveDownloadEntry>,
+}
+
+impl BulkUploadManifest {
+    pub fn push(&mut self, entry: BulkUploadEntry) {
+        match entry.status {
+            BulkUploadEntryStatus::Created => self.created += 1,
+            BulkUploadEntryStatus::Skipped => self.skipped += 1,
+            BulkUploadEntryStatus::Rejected | BulkUploadEntryStatus::Failed => self.failed += 1,
+        }
+        self.entries.push(entry);
+    }
 }
 
 /// DTO for list request
@@ -63,28 +558,92 @@ pub struct ListRequest {
     pub namespace: String,
     #[validate(length(min = 1, max = 100))]
     pub tenant_id: String,
-    #[validate(range(min = 1, max = 1000))]
+    /// Clamped like every listing (default: 100, max: 1000)
     pub limit: Option<i64>,
-    #[validate(range(min = 0))]
     pub offset: Option<i64>,
+    pub sort_by: Option<SortField>,
+    pub sort_direction: Option<SortDirection>,
+    /// `next_cursor` of the previous page; replaces `offset`
+    #[validate(length(max = 2048))]
+    pub cursor: Option<String>,
+    /// Keep only keys starting with this prefix, e.g. `photos/2024/`
+    #[validate(length(max = 1024))]
+    pub prefix: Option<String>,
+    /// Fold keys containing this after the prefix into common prefixes
+    #[validate(length(min = 1, max = 16))]
+    pub delimiter: Option<String>,
+    /// JSON document the metadata must contain, e.g. `{"tags": {"author": "jane"}}`
+    pub metadata_filters: Option<serde_json::Value>,
+    /// Dotted metadata paths that must exist, e.g. `tags.author`
+    pub metadata_has_keys: Option<Vec<String>>,
 }
 
-/// Sorting options for search results
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+/// Sorting options for list and search results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SortField {
+    #[default]
     CreatedAt,
     UpdatedAt,
     SizeBytes,
     Key,
     ContentType,
+    DownloadCount,
+    LastAccessedAt,
+}
+
+impl SortField {
+    /// Names accepted by the `sort` parameter of listings
+    pub const SORT_NAMES: [(&'static str, Self); 5] = [
+        ("created_at", Self::CreatedAt),
+        ("updated_at", Self::UpdatedAt),
+        ("size", Self::SizeBytes),
+        ("key", Self::Key),
+        ("access_count", Self::DownloadCount),
+    ];
+
+    /// Parse a `sort` parameter: a field name, optionally followed by
+    /// `:asc` or `:desc` (default: desc), e.g. `size:asc`
+    ///
+    /// Unknown fields and directions are an error.
+    pub fn parse_sort(sort: &str) -> Result<(Self, SortDirection), String> {
+        let (name, direction) = match sort.trim().split_once(':') {
+            Some((name, direction)) => (name, Some(direction)),
+            None => (sort.trim(), None),
+        };
+        let field = Self::SORT_NAMES
+            .into_iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, field)| field)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::SORT_NAMES.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "Unknown sort field '{}' (expected one of: {})",
+                    name,
+                    names.join(", ")
+                )
+            })?;
+        let direction = match direction {
+            None => SortDirection::default(),
+            Some("asc") => SortDirection::Asc,
+            Some("desc") => SortDirection::Desc,
+            Some(other) => {
+                return Err(format!(
+                    "Unknown sort direction '{}' (expected asc or desc)",
+                    other
+                ))
+            }
+        };
+        Ok((field, direction))
+    }
 }
 
 /// Sort direction
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SortDirection {
     Asc,
+    #[default]
     Desc,
 }
 
@@ -112,10 +671,8 @@ pub struct SearchRequest {
     #[validate(length(min = 1, max = 100))]
     pub tenant_id: String,
 
-    // Pagination
-    #[validate(range(min = 1, max = 1000))]
+    // Pagination, clamped like every listing (default: 100, max: 1000)
     pub limit: Option<i64>,
-    #[validate(range(min = 0))]
     pub offset: Option<i64>,
 
     // Sorting
@@ -125,6 +682,9 @@ pub struct SearchRequest {
     // Basic filters
     #[validate(length(max = 255))]
     pub key_contains: Option<String>,
+    /// Keep only keys starting with this prefix, matched literally
+    #[validate(length(max = 1024))]
+    pub key_prefix: Option<String>,
     #[validate(length(max = 255))]
     pub content_type: Option<String>,
     pub storage_class: Option<crate::domain::value_objects::StorageClass>,
@@ -134,8 +694,11 @@ pub struct SearchRequest {
     pub created_at_range: Option<DateRange>,
     pub updated_at_range: Option<DateRange>,
 
-    // Metadata filters (JSON path queries)
+    // Metadata filters
+    /// JSON document the metadata must contain, e.g. `{"tags": {"author": "jane"}}`
     pub metadata_filters: Option<serde_json::Value>,
+    /// Dotted metadata paths that must exist, e.g. `tags.author`
+    pub metadata_has_keys: Option<Vec<String>>,
 }
 
 /// Text search request (full-text search)
@@ -146,10 +709,8 @@ pub struct TextSearchRequest {
     #[validate(length(min = 1, max = 100))]
     pub tenant_id: String,
 
-    // Pagination
-    #[validate(range(min = 1, max = 1000))]
+    // Pagination, clamped like every listing (default: 100, max: 1000)
     pub limit: Option<i64>,
-    #[validate(range(min = 0))]
     pub offset: Option<i64>,
 
     // Full-text search query
@@ -159,15 +720,54 @@ pub struct TextSearchRequest {
     // Search in specific fields
     pub search_in_metadata: Option<bool>, // default: true
     pub search_in_key: Option<bool>,      // default: true
+    /// Also match custom metadata keys indexed for the namespace (default: true)
+    pub search_in_custom_metadata: Option<bool>,
+    /// Also match text extracted from object content on upload (default: true)
+    pub search_in_content: Option<bool>,
+    /// Treat each query word as a prefix, for search-as-you-type (default: false)
+    pub prefix: Option<bool>,
+    /// Drop matches ranked below this threshold (default: server setting)
+    #[validate(range(min = 0.0))]
+    pub min_rank: Option<f32>,
 }
 
 /// DTO for list response
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ListResponse {
     pub objects: Vec<ObjectDto>,
+    /// Objects matching the listing, across all pages
     pub total: usize,
+    /// False when counting stopped early and `total` is a lower bound
+    pub total_exact: bool,
     pub limit: i64,
     pub offset: i64,
+    /// Whether a page follows this one
+    pub has_more: bool,
+    /// Pass as `cursor` to get the next page; set when `has_more`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Key prefixes up to the delimiter that group further objects, like
+    /// folders; only listed when a delimiter is given
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub common_prefixes: Vec<String>,
+}
+
+/// DTO for a list response projected onto requested fields
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProjectedListResponse {
+    pub objects: Vec<ObjectProjection>,
+    /// Objects matching the listing, across all pages
+    pub total: usize,
+    /// False when counting stopped early and `total` is a lower bound
+    pub total_exact: bool,
+    pub limit: i64,
+    pub offset: i64,
+    /// Whether a page follows this one
+    pub has_more: bool,
+    /// Key prefixes up to the delimiter that group further objects, like
+    /// folders; only listed when a delimiter is given
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub common_prefixes: Vec<String>,
 }
 
 /// DTO for search response
@@ -182,19 +782,603 @@ pub struct SearchResponse {
 /// DTO for text search response
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TextSearchResponse {
-    pub objects: Vec<ObjectDto>,
+    /// Matches ordered by descending rank
+    pub objects: Vec<TextSearchHit>,
+    /// Total number of matches across all pages
     pub total: usize,
     pub limit: i64,
     pub offset: i64,
     pub query: String,
 }
 
+/// A ranked text search match
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TextSearchHit {
+    #[serde(flatten)]
+    pub object: ObjectDto,
+    /// Relevance; key matches rank above vector-only matches
+    pub rank: f32,
+    /// Matching excerpt of the extracted content, with matched words wrapped
+    /// in `<mark>`/`</mark>`. The surrounding text is not HTML-escaped.
+    pub highlight: Option<String>,
+}
+
+impl From<TextSearchMatch> for TextSearchHit {
+    fn from(m: TextSearchMatch) -> Self {
+        Self {
+            object: ObjectDto::from(m.object),
+            rank: m.rank,
+            highlight: m.highlight,
+        }
+    }
+}
+
+/// A text search match as read from the repository
+#[derive(Debug, Clone)]
+pub struct TextSearchMatch {
+    pub object: Object,
+    pub rank: f32,
+    pub highlight: Option<String>,
+}
+
+/// A committed object together with the reference count of its blob
+///
+/// The count is `None` when the blob has no entry (e.g. a ghost object).
+#[derive(Debug, Clone)]
+pub struct ObjectRecord {
+    pub object: Object,
+    pub blob_ref_count: Option<u64>,
+}
+
+/// One page of text search matches plus the total match count
+#[derive(Debug, Clone, Default)]
+pub struct TextSearchPage {
+    pub matches: Vec<TextSearchMatch>,
+    pub total: u64,
+}
+
 /// DTO for download response metadata
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DownloadMetadata {
     pub object_id: ObjectId,
+    pub key: Option<String>,
     pub size_bytes: u64,
     pub content_hash: String,
+    pub content_type: Option<String>,
+    pub content_encoding: Option<ContentEncoding>,
+    pub storage_class: StorageClass,
+    pub updated_at: time::OffsetDateTime,
+}
+
+/// Header-level view of a committed object, read without opening its blob
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectHead {
+    pub object_id: ObjectId,
+    pub tenant_id: String,
+    pub size_bytes: u64,
+    pub content_hash: String,
+    pub content_type: Option<String>,
+    pub content_encoding: Option<ContentEncoding>,
+    pub storage_class: StorageClass,
+    pub updated_at: time::OffsetDateTime,
+}
+
+impl ObjectHead {
+    /// Header view of an in-memory object; `None` unless it is committed
+    pub fn from_committed(object: &Object) -> Option<Self> {
+        if !object.is_readable() {
+            return None;
+        }
+        Some(Self {
+            object_id: *object.id(),
+            tenant_id: object.tenant_id().to_string(),
+            size_bytes: object.size_bytes()?,
+            content_hash: object.content_hash()?.to_string(),
+            content_type: object.content_type().map(str::to_string),
+            content_encoding: object.content_encoding(),
+            storage_class: object.storage_class(),
+            updated_at: object.updated_at(),
+        })
+    }
+}
+
+/// Status of an object as reported while waiting for its upload
+///
+/// The object's lifecycle status, or `FAILED` when its upload failed while
+/// the caller waited. A failed upload's reservation stays `WRITING` until
+/// the stuck upload collector removes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UploadStatus {
+    Writing,
+    Committed,
+    Failed,
+    Deleting,
+    Deleted,
+}
+
+impl From<ObjectStatus> for UploadStatus {
+    fn from(status: ObjectStatus) -> Self {
+        match status {
+            ObjectStatus::Writing => Self::Writing,
+            ObjectStatus::Committed => Self::Committed,
+            ObjectStatus::Deleting => Self::Deleting,
+            ObjectStatus::Deleted => Self::Deleted,
+        }
+    }
+}
+
+/// Request of `PUT /v1/objects/{id}/retention`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ObjectRetentionRequest {
+    /// Keep the object until this time (RFC 3339); only ever extended
+    pub retention_until: Option<String>,
+    /// Place (`true`) or release (`false`) a legal hold
+    pub legal_hold: Option<bool>,
+}
+
+/// Request of `POST /v1/objects/{id}/shares`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShareObjectRequest {
+    /// Tenant the object is shared with
+    pub grantee_tenant_id: String,
+    /// Namespace of the grantee's object
+    pub namespace: String,
+    /// Key of the grantee's object; none for an object reachable by ID only
+    pub key: Option<String>,
+}
+
+/// DTO for a grant sharing an object with another tenant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ObjectShareDto {
+    pub id: String,
+    /// The shared object
+    pub object_id: String,
+    pub owner_tenant_id: String,
+    pub grantee_tenant_id: String,
+    /// The grantee's object, readable with the grantee's `tenant_id`; set
+    /// once the grantee accepts the grant
+    pub grantee_object_id: Option<String>,
+    pub grantee_namespace: String,
+    pub grantee_key: Option<String>,
+    pub content_hash: String,
+    pub created_at: String,
+    /// When the grantee accepted the grant; none while it is pending
+    pub accepted_at: Option<String>,
+}
+
+impl From<ObjectShare> for ObjectShareDto {
+    fn from(share: ObjectShare) -> Self {
+        Self {
+            id: share.id.to_string(),
+            object_id: share.object_id.to_string(),
+            owner_tenant_id: share.owner_tenant_id.to_string(),
+            grantee_tenant_id: share.grantee_tenant_id.to_string(),
+            grantee_object_id: share.grantee_object_id.map(|id| id.to_string()),
+            grantee_namespace: share.grantee_namespace.to_string(),
+            grantee_key: share.grantee_key,
+            content_hash: share.content_hash.as_hex().to_string(),
+            created_at: share.created_at.format(&Rfc3339).unwrap_or_default(),
+            accepted_at: share
+                .accepted_at
+                .map(|dt| dt.format(&Rfc3339).unwrap_or_default()),
+        }
+    }
+}
+
+/// Response of `GET /v1/objects/{id}/shares` and `GET /v1/shares`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ObjectShareListResponse {
+    pub shares: Vec<ObjectShareDto>,
+}
+
+/// Response of `POST /v1/objects/{id}/touch`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TouchObjectResponse {
+    pub id: String,
+    /// Storage class of the object after the touch
+    pub storage_class: StorageClass,
+    /// Moved from cold to hot storage by this request
+    pub promoted: bool,
+    pub last_accessed_at: String,
+}
+
+/// Response of `GET /v1/objects/{id}/status`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ObjectStatusResponse {
+    pub id: String,
+    pub status: UploadStatus,
+}
+
+/// Lifecycle of a long-running operation followed over
+/// `GET /v1/operations/{id}/events`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OperationState {
+    /// Followed, but not started yet
+    Pending,
+    Running,
+    Completed,
+    /// Stopped before all the work was done; run it again to continue
+    Partial,
+    Failed,
+}
+
+impl OperationState {
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Partial | Self::Failed)
+    }
+}
+
+/// Outcome of one item of an operation (an archive entry, a delete batch)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OperationItem {
+    pub name: String,
+    pub status: String,
+    pub detail: Option<String>,
+}
+
+/// `progress` event of an operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OperationProgressEvent {
+    pub id: String,
+    /// `bulk_upload` or `namespace_delete`; unset while pending
+    pub kind: Option<String>,
+    pub state: OperationState,
+    /// Work done so far, in the operation's unit (archive bytes, objects)
+    pub done: u64,
+    /// Total work, when known up front
+    pub total: Option<u64>,
+    /// 0-100, when the total is known
+    pub percent: Option<f64>,
+    pub items: usize,
+    /// Why the operation failed or stopped short
+    pub error: Option<String>,
+}
+
+/// Downloads of one object accumulated since the last flush
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectAccess {
+    pub object_id: ObjectId,
+    pub count: u64,
+    pub last_accessed_at: time::OffsetDateTime,
+}
+
+/// Logical vs physical storage usage for deduplication statistics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DedupStats {
+    /// Number of committed objects
+    pub object_count: i64,
+    /// Sum of committed object sizes (what clients think they store)
+    pub logical_bytes: i64,
+    /// Number of distinct blobs backing those objects
+    pub blob_count: i64,
+    /// Sum of distinct blob sizes (what is actually on disk)
+    pub physical_bytes: i64,
+    /// Bytes saved by deduplication
+    pub bytes_saved: i64,
+    /// logical_bytes / physical_bytes (1.0 when nothing is stored)
+    pub dedup_ratio: f64,
+}
+
+impl DedupStats {
+    pub fn new(
+        object_count: i64,
+        logical_bytes: i64,
+        blob_count: i64,
+        physical_bytes: i64,
+    ) -> Self {
+        let dedup_ratio = if physical_bytes > 0 {
+            logical_bytes as f64 / physical_bytes as f64
+        } else {
+            1.0
+        };
+
+        Self {
+            object_count,
+            logical_bytes,
+            blob_count,
+            physical_bytes,
+            bytes_saved: (logical_bytes - physical_bytes).max(0),
+            dedup_ratio,
+        }
+    }
+}
+
+/// Deduplication statistics for a single tenant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TenantDedupStats {
+    pub tenant_id: String,
+    pub stats: DedupStats,
+}
+
+/// DTO for the storage statistics response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StatsResponse {
+    pub global: DedupStats,
+    pub tenants: Vec<TenantDedupStats>,
+    /// RFC3339 timestamp of when the statistics were computed
+    pub generated_at: String,
+}
+
+/// DTO for a blob entry and its reference count
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BlobDto {
+    pub content_hash: String,
+    /// Shortest unique prefix of `content_hash`, accepted in its place;
+    /// set by blob lookups when short hashes are on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_hash: Option<String>,
+    pub size_bytes: u64,
+    pub storage_class: StorageClass,
+    /// Objects referencing the blob; 0 makes it a GC candidate
+    pub ref_count: i32,
+    pub created_at: String,
+}
+
+impl From<Blob> for BlobDto {
+    fn from(blob: Blob) -> Self {
+        Self {
+            content_hash: blob.content_hash().to_string(),
+            short_hash: None,
+            size_bytes: blob.size_bytes(),
+            storage_class: blob.storage_class(),
+            ref_count: blob.ref_count(),
+            created_at: blob.created_at().format(&Rfc3339).unwrap_or_default(),
+        }
+    }
+}
+
+/// DTO for a blob listing request
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ListBlobsRequest {
+    /// Results per page (default: 100, max: 1000)
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Only blobs without references, i.e. GC candidates
+    #[serde(default)]
+    pub orphaned: bool,
+}
+
+/// DTO for a page of blobs, in content hash order
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListBlobsResponse {
+    pub blobs: Vec<BlobDto>,
+    pub limit: i64,
+    pub has_more: bool,
+    /// Pass as `cursor` to get the next page; set when `has_more`
+    pub next_cursor: Option<String>,
+}
+
+/// A blob whose stored reference count disagrees with its committed objects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RefcountDiscrepancy {
+    pub content_hash: String,
+    pub stored_ref_count: i64,
+    pub actual_ref_count: i64,
+    /// The stored count was corrected to the actual count
+    pub fixed: bool,
+}
+
+/// DTO for a refcount reconciliation run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReconcileRefcountsReport {
+    /// Discrepancies were only reported, not fixed
+    pub dry_run: bool,
+    /// Whether the scan reached the last blob (false when stopped early; the
+    /// next run resumes from the checkpoint)
+    pub completed: bool,
+    pub batches: u64,
+    pub scanned: u64,
+    pub discrepancies: u64,
+    pub fixed: u64,
+    /// First discrepancies found, capped to keep the report small; `fixed`
+    /// marks the blobs whose count this run corrected
+    pub sample: Vec<RefcountDiscrepancy>,
+}
+
+/// DTO for a file found by blob compaction
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CompactionCandidate {
+    pub path: String,
+    pub storage_class: StorageClass,
+    pub size_bytes: u64,
+    /// Removed by this run
+    pub removed: bool,
+}
+
+/// DTO for a blob compaction run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct CompactionReport {
+    /// Files were only reported, not removed
+    pub dry_run: bool,
+    /// Files older than the grace window that were checked
+    pub scanned: u64,
+    /// Blob files the database has no record of
+    pub orphaned_blobs: u64,
+    /// Leftover temp files from abandoned uploads
+    pub stale_temp_files: u64,
+    pub removed: u64,
+    /// Bytes freed (or, on a dry run, that would be freed)
+    pub bytes_reclaimed: u64,
+    pub errors: u64,
+    /// First files found, capped to keep the report small
+    pub sample: Vec<CompactionCandidate>,
+}
+
+/// DTO for a namespace's configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NamespaceConfigDto {
+    pub namespace: String,
+    /// Storage class for uploads that do not name one
+    pub default_storage_class: StorageClass,
+    /// Auto-tiering: objects not downloaded for this many days belong in cold storage
+    pub cold_after_days: Option<u32>,
+    /// Constraints on keys uploaded to the namespace
+    pub key_policy: Option<KeyPolicyDto>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<NamespaceConfig> for NamespaceConfigDto {
+    fn from(config: NamespaceConfig) -> Self {
+        Self {
+            namespace: config.namespace().to_string(),
+            default_storage_class: config.default_storage_class(),
+            cold_after_days: config.tiering().map(|t| t.cold_after_days()),
+            key_policy: config.key_policy().map(KeyPolicyDto::from),
+            created_at: config.created_at().format(&Rfc3339).unwrap_or_default(),
+            updated_at: config.updated_at().format(&Rfc3339).unwrap_or_default(),
+        }
+    }
+}
+
+/// DTO for the namespace configuration list
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NamespaceConfigListResponse {
+    pub namespaces: Vec<NamespaceConfigDto>,
+}
+
+/// DTO for creating or replacing a namespace's configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PutNamespaceConfigRequest {
+    pub default_storage_class: StorageClass,
+    /// 1 to 36500; omit to disable auto-tiering for the namespace
+    #[serde(default)]
+    pub cold_after_days: Option<u32>,
+    /// Omit to accept any valid key
+    #[serde(default)]
+    pub key_policy: Option<KeyPolicyDto>,
+}
+
+/// DTO for a namespace's key policy; every constraint is optional
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct KeyPolicyDto {
+    /// Maximum key length in characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u32>,
+    /// Allowed characters and `x-y` ranges, e.g. `a-z0-9._/-`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_chars: Option<String>,
+    /// Prefix every key must start with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_prefix: Option<String>,
+}
+
+impl From<&KeyPolicy> for KeyPolicyDto {
+    fn from(policy: &KeyPolicy) -> Self {
+        Self {
+            max_length: policy.max_length(),
+            allowed_chars: policy.allowed_chars().map(str::to_string),
+            required_prefix: policy.required_prefix().map(str::to_string),
+        }
+    }
+}
+
+/// An object whose retention or legal hold blocks deleting its namespace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LockedObjectDto {
+    pub id: String,
+    pub key: Option<String>,
+    /// RFC 3339
+    pub retention_until: Option<String>,
+    pub legal_hold: bool,
+}
+
+/// DTO for a namespace delete run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct DeleteNamespaceReport {
+    pub namespace: String,
+    pub tenant_id: String,
+    /// Objects were only counted, not deleted
+    pub dry_run: bool,
+    /// No deletable object is left (false when the run stopped after its
+    /// batch budget; run it again to continue)
+    pub completed: bool,
+    pub batches: u64,
+    pub deleted_objects: u64,
+    pub deleted_bytes: u64,
+    /// Blobs left without references, reclaimed by the next GC runs
+    pub released_blobs: u64,
+    /// Committed objects still in the namespace after the run
+    pub remaining_objects: u64,
+    pub remaining_bytes: u64,
+    /// Locked objects that block the delete, capped to keep the report small
+    pub blockers: Vec<LockedObjectDto>,
+}
+
+/// Request of `PATCH /v1/namespaces/{namespace}/objects/metadata`
+///
+/// The filters are those of search; every committed object of the tenant's
+/// namespace matching all of them is patched.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct BulkUpdateMetadataRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub tenant_id: String,
+    /// JSON Merge Patch (RFC 7386) applied to each object's metadata, e.g.
+    /// `{"tags": {"archived": "true"}}`
+    pub patch: serde_json::Value,
+
+    /// Keep only keys starting with this prefix, matched literally
+    #[validate(length(max = 1024))]
+    pub key_prefix: Option<String>,
+    #[validate(length(max = 255))]
+    pub key_contains: Option<String>,
+    #[validate(length(max = 255))]
+    pub content_type: Option<String>,
+    pub storage_class: Option<StorageClass>,
+    /// JSON document the metadata must contain
+    pub metadata_filters: Option<serde_json::Value>,
+    /// Dotted metadata paths that must exist
+    pub metadata_has_keys: Option<Vec<String>>,
+}
+
+impl BulkUpdateMetadataRequest {
+    /// Search for one page of the objects to patch, oldest first; pages are
+    /// continued by cursor
+    pub fn search(&self, namespace: &str, limit: i64) -> SearchRequest {
+        SearchRequest {
+            namespace: namespace.to_string(),
+            tenant_id: self.tenant_id.clone(),
+            limit: Some(limit),
+            offset: None,
+            sort_by: Some(SortField::CreatedAt),
+            sort_direction: Some(SortDirection::Asc),
+            key_contains: self.key_contains.clone(),
+            key_prefix: self.key_prefix.clone(),
+            content_type: self.content_type.clone(),
+            storage_class: self.storage_class,
+            size_range: None,
+            created_at_range: None,
+            updated_at_range: None,
+            metadata_filters: self.metadata_filters.clone(),
+            metadata_has_keys: self.metadata_has_keys.clone(),
+        }
+    }
+}
+
+/// DTO for a bulk metadata update run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct BulkUpdateMetadataReport {
+    pub namespace: String,
+    pub tenant_id: String,
+    /// Every matching object was processed (false when the run stopped after
+    /// its batch budget; run it again to continue)
+    pub completed: bool,
+    pub batches: u64,
+    /// Objects matching the filters that this run came to
+    pub matched: u64,
+    pub updated: u64,
+    /// Objects the patch was already applied to
+    pub unchanged: u64,
+    /// Objects under retention or legal hold, left as they are
+    pub skipped_locked: u64,
+    /// Objects deleted meanwhile, kept changing concurrently, or whose
+    /// patched metadata would exceed the limits
+    pub failed: u64,
 }
 
 /// DTO for API key creation request
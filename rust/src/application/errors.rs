@@ -6,6 +6,7 @@
 use thiserror::Error;
 
 use crate::application::ports::{ApiKeyRepositoryError, RepositoryError, StorageError};
+use crate::domain::error_types::ValidationError;
 use crate::domain::errors::DomainError;
 
 /// Common error type for object-related use cases
@@ -23,6 +24,24 @@ pub enum ObjectUseCaseError {
 
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
+
+    #[error("Validation error: {0}")]
+    Validation(#[from] ValidationError),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Object not found: {0}")]
+    NotFound(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Content rejected by scan: {0}")]
+    ContentRejected(String),
+
+    #[error("Content scan failed: {0}")]
+    ScanFailed(String),
 }
 
 /// Common error type for API key-related use cases
@@ -51,6 +70,16 @@ pub enum TextSearchUseCaseError {
     InvalidRequest(String),
 }
 
+/// How a ghost object (committed row whose blob is missing) is reported to clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GhostObjectPolicy {
+    /// Report the object as permanently gone (410)
+    #[default]
+    Gone,
+    /// Report the object as a server-side failure (500)
+    InternalError,
+}
+
 /// Common error type for download use cases
 #[derive(Debug, Error)]
 pub enum DownloadUseCaseError {
@@ -65,6 +94,19 @@ pub enum DownloadUseCaseError {
 
     #[error("Object not readable (status: {0})")]
     NotReadable(String),
+
+    #[error("GHOST_OBJECT: blob {content_hash} for object {object_id} is missing")]
+    GhostObject {
+        object_id: String,
+        content_hash: String,
+        policy: GhostObjectPolicy,
+    },
+
+    #[error("Object {object_id} is being restored from cold storage")]
+    Restoring {
+        object_id: String,
+        retry_after_secs: u64,
+    },
 }
 
 /// Common error type for delete use cases
@@ -81,6 +123,9 @@ pub enum DeleteUseCaseError {
 
     #[error("Not found: {0}")]
     NotFound(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
 
 #[cfg(test)]
@@ -223,6 +268,18 @@ mod tests {
             assert!(download_err.to_string().contains("Storage error"));
         }
 
+        #[test]
+        fn test_download_use_case_error_ghost_object() {
+            let download_err = DownloadUseCaseError::GhostObject {
+                object_id: "obj-1".to_string(),
+                content_hash: "abc".to_string(),
+                policy: GhostObjectPolicy::default(),
+            };
+
+            assert!(download_err.to_string().starts_with("GHOST_OBJECT"));
+            assert!(download_err.to_string().contains("obj-1"));
+        }
+
         #[test]
         fn test_download_use_case_error_not_found() {
             let download_err = DownloadUseCaseError::NotFound("test object".to_string());
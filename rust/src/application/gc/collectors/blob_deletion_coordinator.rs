@@ -1,7 +1,12 @@
+use futures_util::{stream, StreamExt};
 use std::sync::Arc;
-use tracing::{debug, warn};
+use time::OffsetDateTime;
+use tracing::{debug, error, warn};
 
-use crate::application::ports::{BlobRepository, BlobStore};
+use crate::application::gc::config::{DeletionRetryPolicy, DEFAULT_MAX_CONCURRENT_DELETIONS};
+use crate::application::ports::{
+    BlobDeletionFailure, BlobDeletionFailureRepository, BlobRepository, BlobStore, StorageError,
+};
 use crate::domain::value_objects::{ContentHash, StorageClass};
 
 /// Result of a blob deletion operation
@@ -20,6 +25,9 @@ pub struct DetailedBlobDeletionResult {
     pub file_deleted: bool,
     pub db_entry_deleted: bool,
     pub errors: Vec<String>,
+    /// The failure recorded when the file could not be deleted and the blob
+    /// was kept for a retry (or dead-lettered)
+    pub deferred: Option<BlobDeletionFailure>,
 }
 
 /// Coordinator for deleting blobs from both storage and database
@@ -27,6 +35,8 @@ pub struct DetailedBlobDeletionResult {
 pub struct BlobDeletionCoordinator {
     blob_repo: Arc<dyn BlobRepository>,
     blob_store: Arc<dyn BlobStore>,
+    max_concurrent: usize,
+    retries: Option<(Arc<dyn BlobDeletionFailureRepository>, DeletionRetryPolicy)>,
 }
 
 impl BlobDeletionCoordinator {
@@ -34,9 +44,32 @@ impl BlobDeletionCoordinator {
         Self {
             blob_repo,
             blob_store,
+            max_concurrent: DEFAULT_MAX_CONCURRENT_DELETIONS,
+            retries: None,
         }
     }
 
+    /// Limit how many blobs `delete_blobs` deletes at the same time
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent.max(1);
+        self
+    }
+
+    /// Keep the database entry of a blob whose file could not be deleted
+    ///
+    /// The failure is recorded in `failures` and the blob retried by later
+    /// cycles according to `policy`. A file that is already gone counts as
+    /// deleted. Without this (or with retries disabled in `policy`) the entry
+    /// is deleted regardless and the file left behind.
+    pub fn with_retries(
+        mut self,
+        failures: Arc<dyn BlobDeletionFailureRepository>,
+        policy: DeletionRetryPolicy,
+    ) -> Self {
+        self.retries = policy.enabled().then_some((failures, policy));
+        self
+    }
+
     /// Delete a single blob from both storage and database
     pub async fn delete_blob(
         &self,
@@ -46,13 +79,36 @@ impl BlobDeletionCoordinator {
         let mut errors = Vec::new();
 
         // Delete physical file
-        let file_result = self.blob_store.delete(&content_hash, storage_class).await;
+        let file_result = match self.blob_store.delete(&content_hash, storage_class).await {
+            Err(StorageError::NotFound(_)) if self.retries.is_some() => {
+                debug!("File of blob {} was already gone", content_hash);
+                Ok(())
+            }
+            result => result,
+        };
         let file_deleted = file_result.is_ok();
 
-        if let Err(e) = file_result {
-            let error_msg = format!("File deletion failed: {}", e);
-            debug!("{} for blob {}", error_msg, content_hash);
-            errors.push(error_msg);
+        match (file_result, &self.retries) {
+            (Err(e), Some((failures, policy))) => {
+                return Self::defer(failures.as_ref(), policy, content_hash, storage_class, e)
+                    .await;
+            }
+            (Err(e), None) => {
+                let error_msg = format!("File deletion failed: {}", e);
+                debug!("{} for blob {}", error_msg, content_hash);
+                errors.push(error_msg);
+            }
+            // An earlier failure no longer holds the blob back, even if its
+            // row outlives this cycle
+            (Ok(()), Some((failures, _))) => {
+                if let Err(e) = failures.clear(&content_hash).await {
+                    warn!(
+                        "Clearing the failed deletion of blob {} failed: {}",
+                        content_hash, e
+                    );
+                }
+            }
+            (Ok(()), None) => {}
         }
 
         // Delete database entry
@@ -73,32 +129,91 @@ impl BlobDeletionCoordinator {
             file_deleted,
             db_entry_deleted,
             errors,
+            deferred: None,
+        }
+    }
+
+    /// Record a failed file deletion and keep the blob for a retry, or
+    /// dead-letter it once its attempts are used up
+    async fn defer(
+        failures: &dyn BlobDeletionFailureRepository,
+        policy: &DeletionRetryPolicy,
+        content_hash: ContentHash,
+        storage_class: StorageClass,
+        cause: StorageError,
+    ) -> DetailedBlobDeletionResult {
+        let mut errors = vec![format!("File deletion failed: {}", cause)];
+
+        let previous_attempts = match failures.find(&content_hash).await {
+            Ok(previous) => previous.map_or(0, |failure| failure.attempts),
+            Err(e) => {
+                warn!(
+                    "Loading failed deletion of blob {} failed: {}",
+                    content_hash, e
+                );
+                0
+            }
+        };
+        let attempts = previous_attempts + 1;
+        let now = OffsetDateTime::now_utc();
+        let dead_lettered = attempts >= policy.max_attempts;
+
+        let failure = BlobDeletionFailure {
+            content_hash: content_hash.clone(),
+            storage_class,
+            attempts,
+            last_error: cause.to_string(),
+            next_attempt_at: (!dead_lettered).then(|| now + policy.backoff(attempts)),
+            dead_lettered_at: dead_lettered.then_some(now),
+        };
+
+        if dead_lettered {
+            error!(
+                content_hash = %content_hash,
+                storage_class = %storage_class,
+                attempts,
+                error = %cause,
+                "Giving up on deleting blob file; blob is dead-lettered until an operator removes it"
+            );
+        } else {
+            warn!(
+                content_hash = %content_hash,
+                attempts,
+                error = %cause,
+                retry_in_secs = policy.backoff(attempts).as_secs(),
+                "Blob file could not be deleted; keeping the blob for a retry"
+            );
+        }
+
+        if let Err(e) = failures.record(&failure).await {
+            let error_msg = format!("Recording the failed deletion failed: {}", e);
+            warn!("{} for blob {}", error_msg, content_hash);
+            errors.push(error_msg);
+        }
+
+        DetailedBlobDeletionResult {
+            content_hash,
+            success: false,
+            file_deleted: false,
+            db_entry_deleted: false,
+            errors,
+            deferred: Some(failure),
         }
     }
 
     /// Delete multiple blobs concurrently
+    ///
+    /// At most `max_concurrent` deletions are in flight at once, and a new one
+    /// starts as soon as any finishes. Results come back in completion order.
     pub async fn delete_blobs(
         &self,
         blobs: Vec<(ContentHash, StorageClass)>,
     ) -> Result<Vec<DetailedBlobDeletionResult>, super::errors::BatchProcessingError> {
-        use super::batch_processor::{BatchConfig, BatchProcessor};
-
-        let config = BatchConfig::default();
-
-        let processor = {
-            let coordinator = self.clone();
-            move |(content_hash, storage_class): (ContentHash, StorageClass)| {
-                let coordinator = coordinator.clone();
-                async move { coordinator.delete_blob(content_hash, storage_class).await }
-            }
-        };
-
-        let batch_results = BatchProcessor::process_concurrent(blobs, &config, processor).await;
-
-        let results: Vec<DetailedBlobDeletionResult> = batch_results
-            .into_iter()
-            .map(|batch_result| batch_result.result)
-            .collect();
+        let results = stream::iter(blobs)
+            .map(|(content_hash, storage_class)| self.delete_blob(content_hash, storage_class))
+            .buffer_unordered(self.max_concurrent)
+            .collect()
+            .await;
 
         Ok(results)
     }
@@ -107,11 +222,14 @@ impl BlobDeletionCoordinator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::gc::collectors::test_utils::{self, InMemoryDeletionFailures};
     use crate::application::ports::{BlobRepository, BlobStore, RepositoryError, StorageError};
-    use crate::domain::value_objects::{ContentHash, StorageClass};
+    use crate::domain::value_objects::{ContentHash, ContentHashPrefix, StorageClass, TenantId};
     use async_trait::async_trait;
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Mutex;
+    use std::time::Duration;
 
     struct MockBlobRepository {
         deleted_hashes: Mutex<Vec<String>>,
@@ -138,6 +256,15 @@ mod tests {
             unimplemented!()
         }
 
+        async fn reference_existing(
+            &self,
+            _content_hash: &ContentHash,
+            _storage_class: StorageClass,
+            _tenant_id: &TenantId,
+        ) -> Result<Option<Blob>, RepositoryError> {
+            unimplemented!()
+        }
+
         async fn increment_ref(&self, _content_hash: &ContentHash) -> Result<(), RepositoryError> {
             unimplemented!()
         }
@@ -163,11 +290,54 @@ mod tests {
                 .push(content_hash.to_string());
             Ok(())
         }
+
+        async fn move_storage_class(
+            &self,
+            _content_hash: &ContentHash,
+            _from: StorageClass,
+            _to: StorageClass,
+        ) -> Result<bool, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_known(
+            &self,
+            _content_hashes: &[ContentHash],
+        ) -> Result<Vec<ContentHash>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn list_hashes(
+            &self,
+            _after: Option<ContentHash>,
+            _limit: i64,
+        ) -> Result<Vec<ContentHash>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn list(
+            &self,
+            _after: Option<ContentHash>,
+            _orphaned_only: bool,
+            _limit: i64,
+        ) -> Result<Vec<Blob>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_by_prefix(
+            &self,
+            _prefix: &ContentHashPrefix,
+            _limit: i64,
+        ) -> Result<Vec<Blob>, RepositoryError> {
+            unimplemented!()
+        }
     }
 
     struct MockBlobStore {
         deleted_files: Mutex<Vec<String>>,
         should_fail: bool,
+        in_flight: AtomicUsize,
+        peak_in_flight: AtomicUsize,
     }
 
     impl MockBlobStore {
@@ -175,6 +345,8 @@ mod tests {
             Self {
                 deleted_files: Mutex::new(Vec::new()),
                 should_fail,
+                in_flight: AtomicUsize::new(0),
+                peak_in_flight: AtomicUsize::new(0),
             }
         }
     }
@@ -202,6 +374,10 @@ mod tests {
             content_hash: &ContentHash,
             _storage_class: StorageClass,
         ) -> Result<(), StorageError> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
             if self.should_fail {
                 return Err(StorageError::NotFound(content_hash.to_string()));
             }
@@ -223,6 +399,54 @@ mod tests {
         async fn get_total_size(&self, _storage_class: StorageClass) -> Result<u64, StorageError> {
             Ok(0)
         }
+
+        async fn append_staged(
+            &self,
+            _upload_id: uuid::Uuid,
+            _offset: u64,
+            _reader: crate::application::ports::BlobReader,
+            _storage_class: StorageClass,
+        ) -> Result<u64, StorageError> {
+            unimplemented!()
+        }
+
+        async fn staged_len(
+            &self,
+            _upload_id: uuid::Uuid,
+            _storage_class: StorageClass,
+        ) -> Result<Option<u64>, StorageError> {
+            unimplemented!()
+        }
+
+        async fn read_staged(
+            &self,
+            _upload_id: uuid::Uuid,
+            _storage_class: StorageClass,
+        ) -> Result<crate::application::ports::BlobReader, StorageError> {
+            unimplemented!()
+        }
+
+        async fn discard_staged(
+            &self,
+            _upload_id: uuid::Uuid,
+            _storage_class: StorageClass,
+        ) -> Result<(), StorageError> {
+            unimplemented!()
+        }
+
+        async fn initiate_restore(
+            &self,
+            _content_hash: &ContentHash,
+        ) -> Result<crate::application::ports::RestoreJob, StorageError> {
+            unimplemented!()
+        }
+
+        async fn restore_status(
+            &self,
+            _content_hash: &ContentHash,
+        ) -> Result<crate::application::ports::RestoreStatus, StorageError> {
+            unimplemented!()
+        }
     }
 
     #[tokio::test]
@@ -287,4 +511,163 @@ mod tests {
         assert_eq!(store.deleted_files.lock().unwrap().len(), 1);
         assert_eq!(repo.deleted_hashes.lock().unwrap().len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_delete_blobs_bounds_concurrency() {
+        let repo = Arc::new(MockBlobRepository::new(false));
+        let store = Arc::new(MockBlobStore::new(false));
+        let coordinator =
+            BlobDeletionCoordinator::new(repo.clone(), store.clone()).with_max_concurrent(3);
+
+        let blobs: Vec<_> = (0..10)
+            .map(|i| {
+                (
+                    ContentHash::from_hex(format!("{:064x}", i)).unwrap(),
+                    StorageClass::Hot,
+                )
+            })
+            .collect();
+
+        let results = coordinator.delete_blobs(blobs).await.unwrap();
+
+        assert_eq!(results.len(), 10);
+        assert!(results.iter().all(|r| r.success));
+        assert_eq!(repo.deleted_hashes.lock().unwrap().len(), 10);
+        assert_eq!(store.peak_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_delete_blobs_file_failure_still_deletes_entries() {
+        let repo = Arc::new(MockBlobRepository::new(false));
+        let store = Arc::new(MockBlobStore::new(true)); // fail file deletion
+        let coordinator = BlobDeletionCoordinator::new(repo.clone(), store.clone());
+
+        let blobs: Vec<_> = ["d", "e"]
+            .iter()
+            .map(|c| {
+                (
+                    ContentHash::from_hex(c.repeat(64)).unwrap(),
+                    StorageClass::Cold,
+                )
+            })
+            .collect();
+
+        let results = coordinator.delete_blobs(blobs).await.unwrap();
+
+        assert!(results
+            .iter()
+            .all(|r| !r.file_deleted && r.db_entry_deleted));
+        assert_eq!(repo.deleted_hashes.lock().unwrap().len(), 2);
+    }
+
+    fn retrying(
+        repo: Arc<MockBlobRepository>,
+        store: Arc<dyn BlobStore>,
+        failures: Arc<InMemoryDeletionFailures>,
+        max_attempts: u32,
+    ) -> BlobDeletionCoordinator {
+        let policy = DeletionRetryPolicy::new(
+            max_attempts,
+            Duration::from_secs(60),
+            Duration::from_secs(3600),
+        );
+        BlobDeletionCoordinator::new(repo, store).with_retries(failures, policy)
+    }
+
+    #[tokio::test]
+    async fn test_intermittent_file_failure_is_retried_until_deleted() {
+        let repo = Arc::new(MockBlobRepository::new(false));
+        let store = Arc::new(test_utils::MockBlobStore::flaky(2));
+        let failures = Arc::new(InMemoryDeletionFailures::default());
+        let coordinator = retrying(repo.clone(), store.clone(), failures.clone(), 5);
+        let content_hash = ContentHash::from_hex("f".repeat(64)).unwrap();
+
+        for attempt in 1..=2 {
+            let result = coordinator
+                .delete_blob(content_hash.clone(), StorageClass::Hot)
+                .await;
+
+            assert!(!result.file_deleted);
+            assert!(!result.db_entry_deleted); // kept so the file is not leaked
+            let failure = result.deferred.unwrap();
+            assert_eq!(failure.attempts, attempt);
+            assert!(failure.next_attempt_at.is_some());
+            assert!(failure.dead_lettered_at.is_none());
+        }
+        assert!(repo.deleted_hashes.lock().unwrap().is_empty());
+        assert_eq!(failures.get(&content_hash).unwrap().attempts, 2);
+
+        let result = coordinator
+            .delete_blob(content_hash.clone(), StorageClass::Hot)
+            .await;
+
+        assert!(result.file_deleted && result.db_entry_deleted);
+        assert!(result.deferred.is_none());
+        assert!(failures.get(&content_hash).is_none());
+        assert_eq!(store.deleted_files.lock().unwrap().len(), 1);
+        assert_eq!(repo.deleted_hashes.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_failures_are_dead_lettered_after_max_attempts() {
+        let repo = Arc::new(MockBlobRepository::new(false));
+        let store = Arc::new(test_utils::MockBlobStore::flaky(usize::MAX));
+        let failures = Arc::new(InMemoryDeletionFailures::default());
+        let coordinator = retrying(repo.clone(), store, failures.clone(), 3);
+        let content_hash = ContentHash::from_hex("1".repeat(64)).unwrap();
+
+        let mut results = Vec::new();
+        for _ in 0..3 {
+            results.push(
+                coordinator
+                    .delete_blob(content_hash.clone(), StorageClass::Cold)
+                    .await,
+            );
+        }
+
+        assert!(results[1]
+            .deferred
+            .as_ref()
+            .unwrap()
+            .dead_lettered_at
+            .is_none());
+        let failure = results[2].deferred.as_ref().unwrap();
+        assert_eq!(failure.attempts, 3);
+        assert!(failure.dead_lettered_at.is_some());
+        assert!(failure.next_attempt_at.is_none());
+        assert!(failure.last_error.contains("backend timed out"));
+        assert_eq!(failures.get(&content_hash).unwrap(), *failure);
+        assert!(repo.deleted_hashes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retries_count_missing_file_as_deleted() {
+        let repo = Arc::new(MockBlobRepository::new(false));
+        let store = Arc::new(MockBlobStore::new(true)); // file already gone
+        let failures = Arc::new(InMemoryDeletionFailures::default());
+        let coordinator = retrying(repo.clone(), store, failures.clone(), 5);
+
+        let result = coordinator
+            .delete_blob(
+                ContentHash::from_hex("2".repeat(64)).unwrap(),
+                StorageClass::Hot,
+            )
+            .await;
+
+        assert!(result.file_deleted && result.db_entry_deleted);
+        assert!(failures.failures.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_retry_backoff_doubles_up_to_max() {
+        let policy =
+            DeletionRetryPolicy::new(10, Duration::from_secs(60), Duration::from_secs(300));
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(60));
+        assert_eq!(policy.backoff(2), Duration::from_secs(120));
+        assert_eq!(policy.backoff(3), Duration::from_secs(240));
+        assert_eq!(policy.backoff(4), Duration::from_secs(300));
+        assert_eq!(policy.backoff(40), Duration::from_secs(300));
+        assert!(!DeletionRetryPolicy::new(0, Duration::ZERO, Duration::ZERO).enabled());
+    }
 }
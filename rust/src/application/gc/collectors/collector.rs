@@ -1,3 +1,7 @@
+use serde::Serialize;
+
+use crate::domain::value_objects::StorageClass;
+
 /// Common trait for garbage collection operations.
 ///
 /// This trait defines the interface that all garbage collectors must implement.
@@ -8,7 +12,7 @@
 ///
 /// ```rust,ignore
 /// use async_trait::async_trait;
-/// use crate::application::gc::collectors::Collector;
+/// use crate::application::gc::collectors::{CollectionReport, Collector};
 ///
 /// struct MyCollector;
 ///
@@ -18,9 +22,9 @@
 ///         "my_collector"
 ///     }
 ///
-///     async fn collect(&self) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+///     async fn collect(&self) -> Result<CollectionReport, Box<dyn std::error::Error + Send + Sync>> {
 ///         // Perform collection logic here
-///         Ok(42)
+///         Ok(CollectionReport::with_items(42))
 ///     }
 /// }
 /// ```
@@ -46,8 +50,9 @@ pub trait Collector {
     ///
     /// # Returns
     ///
-    /// Returns the number of items that were successfully cleaned up during
-    /// this collection cycle.
+    /// Returns a report of the items that were successfully cleaned up during
+    /// this collection cycle. Collectors in dry-run mode delete nothing and
+    /// report the candidates they would have removed instead.
     ///
     /// # Errors
     ///
@@ -58,10 +63,67 @@ pub trait Collector {
     /// # Examples
     ///
     /// ```rust,ignore
-    /// let cleaned_count = collector.collect().await?;
-    /// println!("Cleaned up {} items", cleaned_count);
+    /// let report = collector.collect().await?;
+    /// println!("Cleaned up {} items ({} bytes)", report.items, report.bytes);
     /// ```
-    async fn collect(&self) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+    async fn collect(&self) -> Result<CollectionReport, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// What a collection cycle removed (or, in dry-run mode, would remove).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CollectionReport {
+    /// The number of items cleaned up.
+    pub items: usize,
+    /// Blob store bytes reclaimed by the cleanup.
+    pub bytes: u64,
+    /// Items stored in the hot tier.
+    pub hot_items: usize,
+    /// Items stored in the cold tier.
+    pub cold_items: usize,
+    /// Items removed from the database whose blob store file could not be
+    /// deleted (left behind for a later orphan scan).
+    pub files_left: usize,
+    /// Items that could not be fully removed, with the reason.
+    pub failures: Vec<String>,
+    /// Items kept because their blob store file could not be deleted yet;
+    /// a later cycle retries them.
+    pub deferred: usize,
+    /// Items whose deletion failed too often to retry, left for an operator.
+    pub dead_lettered: Vec<DeadLetteredBlob>,
+}
+
+/// A blob whose file could not be deleted after every retry.
+///
+/// Its database row is kept and the garbage collector leaves it alone until
+/// an operator removes the file and clears the recorded failure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeadLetteredBlob {
+    pub content_hash: String,
+    pub storage_class: StorageClass,
+    /// Failed deletion attempts.
+    pub attempts: u32,
+    /// The last deletion error.
+    pub error: String,
+}
+
+impl CollectionReport {
+    /// Creates a report for items without a known size or storage class.
+    pub fn with_items(items: usize) -> Self {
+        Self {
+            items,
+            ..Self::default()
+        }
+    }
+
+    /// Records one cleaned up item.
+    pub fn record(&mut self, storage_class: StorageClass, bytes: u64) {
+        self.items += 1;
+        self.bytes += bytes;
+        match storage_class {
+            StorageClass::Hot => self.hot_items += 1,
+            StorageClass::Cold => self.cold_items += 1,
+        }
+    }
 }
 
 /// Result of a single collection operation.
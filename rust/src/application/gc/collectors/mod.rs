@@ -9,7 +9,7 @@ pub mod test_utils;
 
 pub use batch_processor::{BatchConfig, BatchItemResult, BatchProcessor};
 pub use blob_deletion_coordinator::{BlobDeletionCoordinator, BlobDeletionResult};
-pub use collector::{CollectionResult, Collector};
+pub use collector::{CollectionReport, CollectionResult, Collector, DeadLetteredBlob};
 pub use errors::{BatchProcessingError, BlobDeletionAttempt, BlobDeletionError, GcError, GcResult};
 pub use orphaned_blob_collector::OrphanedBlobCollector;
 pub use stuck_upload_collector::StuckUploadCollector;
@@ -1,11 +1,16 @@
 use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{debug, info};
 
 use super::{
-    blob_deletion_coordinator::BlobDeletionCoordinator, collector::Collector, errors::GcResult,
+    blob_deletion_coordinator::BlobDeletionCoordinator,
+    collector::{CollectionReport, Collector, DeadLetteredBlob},
+    errors::GcResult,
 };
-use crate::application::ports::BlobRepository;
+use crate::application::gc::config::DeletionRetryPolicy;
+use crate::application::ports::{BlobDeletionFailureRepository, BlobRepository, ObjectRepository};
+use crate::domain::entities::Blob;
 
 /// Collector for orphaned blobs (blobs with reference count = 0).
 ///
@@ -33,8 +38,8 @@ use crate::application::ports::BlobRepository;
 ///     100, // batch size
 /// );
 ///
-/// let cleaned_count = collector.collect().await?;
-/// println!("Cleaned up {} orphaned blobs", cleaned_count);
+/// let report = collector.collect().await?;
+/// println!("Cleaned up {} orphaned blobs", report.items);
 /// ```
 pub struct OrphanedBlobCollector {
     /// Repository for querying blob metadata.
@@ -43,6 +48,11 @@ pub struct OrphanedBlobCollector {
     deletion_coordinator: BlobDeletionCoordinator,
     /// Maximum number of blobs to process in a single collection cycle.
     batch_size: i64,
+    /// Report candidates without deleting them.
+    dry_run: bool,
+    /// Objects asked which candidates are still locked, if the blob
+    /// repository cannot tell.
+    lock_check: Option<Arc<dyn ObjectRepository>>,
 }
 
 #[async_trait]
@@ -51,7 +61,7 @@ impl Collector for OrphanedBlobCollector {
         "orphaned_blob_collector"
     }
 
-    async fn collect(&self) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    async fn collect(&self) -> Result<CollectionReport, Box<dyn std::error::Error + Send + Sync>> {
         self.collect_internal()
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
@@ -87,22 +97,70 @@ impl OrphanedBlobCollector {
             blob_repo,
             deletion_coordinator,
             batch_size,
+            dry_run: false,
+            lock_check: None,
         }
     }
 
+    /// Sets how many blobs are deleted concurrently (10 by default).
+    ///
+    /// Each deletion removes a file from the blob store and a row from the
+    /// database, so this bounds the load a cycle puts on both.
+    pub fn with_max_concurrent_deletions(mut self, max_concurrent_deletions: usize) -> Self {
+        self.deletion_coordinator = self
+            .deletion_coordinator
+            .with_max_concurrent(max_concurrent_deletions);
+        self
+    }
+
+    /// Retries blobs whose file could not be deleted instead of dropping
+    /// their database entry.
+    ///
+    /// Failures are recorded in `failures`; the blob is skipped by orphan
+    /// scans until its backoff has passed, and dead-lettered after
+    /// `policy.max_attempts` failures.
+    pub fn with_deletion_retries(
+        mut self,
+        failures: Arc<dyn BlobDeletionFailureRepository>,
+        policy: DeletionRetryPolicy,
+    ) -> Self {
+        self.deletion_coordinator = self.deletion_coordinator.with_retries(failures, policy);
+        self
+    }
+
+    /// Keeps blobs that an object under retention or legal hold points at.
+    ///
+    /// The Postgres blob repository checks this itself, against the objects
+    /// table; objects kept in another store have to be asked.
+    pub fn with_lock_check(mut self, object_repo: Arc<dyn ObjectRepository>) -> Self {
+        self.lock_check = Some(object_repo);
+        self
+    }
+
+    /// Enables or disables dry-run mode.
+    ///
+    /// In dry-run mode orphaned blobs are logged and counted but left in place.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     /// Collect and delete orphaned blobs.
     ///
     /// This method performs a complete collection cycle:
     /// 1. Queries the database for blobs with reference count = 0
     /// 2. Processes the blobs in concurrent batches for efficiency
     /// 3. Deletes each blob from both the physical store and database
-    /// 4. Returns the count of successfully deleted blobs
+    /// 4. Returns a report of the successfully deleted blobs
     ///
     /// # Returns
     ///
-    /// The number of orphaned blobs that were successfully deleted.
+    /// The number, size and storage classes of the orphaned blobs that were
+    /// successfully deleted.
     /// Note that partial failures (e.g., file deletion fails but DB entry succeeds)
     /// are logged but still count as successful deletions from the database perspective.
+    /// With deletion retries such blobs are kept instead, and counted as
+    /// deferred or dead-lettered.
     ///
     /// # Errors
     ///
@@ -112,26 +170,52 @@ impl OrphanedBlobCollector {
     /// # Examples
     ///
     /// ```rust,ignore
-    /// let deleted_count = collector.collect().await?;
-    /// if deleted_count > 0 {
-    ///     println!("Reclaimed space by deleting {} orphaned blobs", deleted_count);
+    /// let report = collector.collect().await?;
+    /// if report.items > 0 {
+    ///     println!("Reclaimed {} bytes from {} orphaned blobs", report.bytes, report.items);
     /// }
     /// ```
-    async fn collect_internal(&self) -> GcResult<usize> {
+    async fn collect_internal(&self) -> GcResult<CollectionReport> {
         let orphaned_blobs = self
             .blob_repo
             .find_orphaned(self.batch_size)
             .await
             .map_err(|e| super::errors::GcError::QueryError { source: e.into() })?;
+        let orphaned_blobs = self.without_locked(orphaned_blobs).await?;
 
         let blob_count = orphaned_blobs.len();
 
         if blob_count == 0 {
-            return Ok(0);
+            return Ok(CollectionReport::default());
+        }
+
+        if self.dry_run {
+            let mut report = CollectionReport::default();
+            for blob in &orphaned_blobs {
+                info!(
+                    content_hash = %blob.content_hash(),
+                    storage_class = %blob.storage_class(),
+                    size_bytes = blob.size_bytes(),
+                    "Dry run: would delete orphaned blob"
+                );
+                report.record(blob.storage_class(), blob.size_bytes());
+            }
+            return Ok(report);
         }
 
         debug!("Found {} orphaned blobs to delete", blob_count);
 
+        // Remember sizes so the report can account for reclaimed bytes
+        let sizes: HashMap<_, _> = orphaned_blobs
+            .iter()
+            .map(|blob| {
+                (
+                    blob.content_hash().clone(),
+                    (blob.storage_class(), blob.size_bytes()),
+                )
+            })
+            .collect();
+
         // Convert blobs to deletion tuples
         let blob_info: Vec<_> = orphaned_blobs
             .into_iter()
@@ -146,13 +230,69 @@ impl OrphanedBlobCollector {
             .map_err(|e| super::errors::GcError::DeletionError { source: e.into() })?;
 
         // Count successful deletions (based on DB deletion success)
-        let total_deleted = deletion_results
-            .iter()
-            .filter(|result| result.db_entry_deleted)
-            .count();
+        let mut report = CollectionReport::default();
+        for result in &deletion_results {
+            if !result.errors.is_empty() {
+                report.failures.push(format!(
+                    "{}: {}",
+                    result.content_hash,
+                    result.errors.join("; ")
+                ));
+            }
+            if let Some(failure) = &result.deferred {
+                if failure.dead_lettered_at.is_some() {
+                    report.dead_lettered.push(DeadLetteredBlob {
+                        content_hash: failure.content_hash.to_string(),
+                        storage_class: failure.storage_class,
+                        attempts: failure.attempts,
+                        error: failure.last_error.clone(),
+                    });
+                } else {
+                    report.deferred += 1;
+                }
+            }
+            if !result.db_entry_deleted {
+                continue;
+            }
+            if let Some(&(storage_class, size_bytes)) = sizes.get(&result.content_hash) {
+                report.record(storage_class, size_bytes);
+            }
+            if !result.file_deleted {
+                report.files_left += 1;
+            }
+        }
 
-        info!("Cleaned up {} orphaned blobs", total_deleted);
-        Ok(total_deleted)
+        info!(
+            "Cleaned up {} orphaned blobs ({} bytes)",
+            report.items, report.bytes
+        );
+        Ok(report)
+    }
+
+    /// Drop the blobs a locked object still points at.
+    async fn without_locked(&self, blobs: Vec<Blob>) -> GcResult<Vec<Blob>> {
+        let Some(object_repo) = &self.lock_check else {
+            return Ok(blobs);
+        };
+        if blobs.is_empty() {
+            return Ok(blobs);
+        }
+
+        let hashes: Vec<_> = blobs.iter().map(|b| b.content_hash().clone()).collect();
+        let locked: HashSet<_> = object_repo
+            .find_locked_hashes(&hashes)
+            .await
+            .map_err(|e| super::errors::GcError::QueryError { source: e.into() })?
+            .into_iter()
+            .collect();
+        if !locked.is_empty() {
+            debug!("Keeping {} orphaned blobs of locked objects", locked.len());
+        }
+
+        Ok(blobs
+            .into_iter()
+            .filter(|blob| !locked.contains(blob.content_hash()))
+            .collect())
     }
 }
 
@@ -162,6 +302,7 @@ mod tests {
     use crate::application::gc::collectors::test_utils::{
         create_test_blob, MockBlobRepository, MockBlobStore,
     };
+    use crate::application::ports::MockObjectRepository;
     use std::sync::Arc;
 
     #[tokio::test]
@@ -172,7 +313,7 @@ mod tests {
         let collector = OrphanedBlobCollector::new(mock_repo, mock_store, 100);
 
         let result = collector.collect().await.unwrap();
-        assert_eq!(result, 0);
+        assert_eq!(result.items, 0);
     }
 
     #[tokio::test]
@@ -185,10 +326,54 @@ mod tests {
         let collector = OrphanedBlobCollector::new(mock_repo.clone(), mock_store.clone(), 100);
 
         let result = collector.collect().await.unwrap();
-        assert_eq!(result, 1);
+        assert_eq!(result.items, 1);
+        assert_eq!(result.hot_items, 1);
 
         // Verify deletions occurred
         assert_eq!(mock_repo.deleted_hashes.lock().unwrap().len(), 1);
         assert_eq!(mock_store.deleted_files.lock().unwrap().len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_without_deleting() {
+        let blob = create_test_blob(&"b".repeat(64), 0);
+        let size_bytes = blob.size_bytes();
+
+        let mock_repo = Arc::new(MockBlobRepository::new(vec![blob]));
+        let mock_store = Arc::new(MockBlobStore::new());
+
+        let collector = OrphanedBlobCollector::new(mock_repo.clone(), mock_store.clone(), 100)
+            .with_dry_run(true);
+
+        let result = collector.collect().await.unwrap();
+        assert_eq!(result.items, 1);
+        assert_eq!(result.bytes, size_bytes);
+
+        assert!(mock_repo.deleted_hashes.lock().unwrap().is_empty());
+        assert!(mock_store.deleted_files.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lock_check_keeps_blobs_of_locked_objects() {
+        let locked = create_test_blob(&"c".repeat(64), 0);
+        let free = create_test_blob(&"d".repeat(64), 0);
+        let locked_hash = locked.content_hash().clone();
+
+        let mock_repo = Arc::new(MockBlobRepository::new(vec![locked, free]));
+        let mock_store = Arc::new(MockBlobStore::new());
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_locked_hashes()
+            .times(1)
+            .returning(move |_| Ok(vec![locked_hash.clone()]));
+
+        let collector = OrphanedBlobCollector::new(mock_repo.clone(), mock_store.clone(), 100)
+            .with_lock_check(Arc::new(mock_object_repo));
+
+        let result = collector.collect().await.unwrap();
+        assert_eq!(result.items, 1);
+        let deleted = mock_repo.deleted_hashes.lock().unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0], "d".repeat(64));
+    }
 }
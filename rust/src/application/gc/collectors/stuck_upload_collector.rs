@@ -2,13 +2,20 @@ use async_trait::async_trait;
 use std::sync::Arc;
 use tracing::info;
 
-use super::{collector::Collector, errors::GcResult};
+use super::{
+    collector::{CollectionReport, Collector},
+    errors::GcResult,
+};
 use crate::application::ports::ObjectRepository;
 
+/// Upper bound on the stuck uploads listed by one dry-run cycle
+const DRY_RUN_SCAN_LIMIT: i64 = 1000;
+
 /// Collector for stuck uploads (objects in WRITING state that are too old)
 pub struct StuckUploadCollector {
     object_repo: Arc<dyn ObjectRepository>,
     stuck_upload_age_hours: i64,
+    dry_run: bool,
 }
 
 #[async_trait]
@@ -17,7 +24,7 @@ impl Collector for StuckUploadCollector {
         "stuck_upload_collector"
     }
 
-    async fn collect(&self) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    async fn collect(&self) -> Result<CollectionReport, Box<dyn std::error::Error + Send + Sync>> {
         self.collect_internal()
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
@@ -29,11 +36,35 @@ impl StuckUploadCollector {
         Self {
             object_repo,
             stuck_upload_age_hours,
+            dry_run: false,
         }
     }
 
+    /// Log and count stuck uploads instead of deleting them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     /// Collect and cleanup stuck uploads (internal implementation)
-    async fn collect_internal(&self) -> GcResult<usize> {
+    ///
+    /// Stuck uploads never reached the blob store, so the report carries a
+    /// count only.
+    async fn collect_internal(&self) -> GcResult<CollectionReport> {
+        if self.dry_run {
+            let stuck = self
+                .object_repo
+                .find_stuck_writing_objects(self.stuck_upload_age_hours, DRY_RUN_SCAN_LIMIT)
+                .await
+                .map_err(|e| super::errors::GcError::QueryError { source: e.into() })?;
+
+            for object_id in &stuck {
+                info!(%object_id, "Dry run: would delete stuck WRITING object");
+            }
+
+            return Ok(CollectionReport::with_items(stuck.len()));
+        }
+
         let count = self
             .object_repo
             .cleanup_stuck_uploads(self.stuck_upload_age_hours)
@@ -44,7 +75,7 @@ impl StuckUploadCollector {
             info!("Cleaned up {} stuck WRITING objects", count);
         }
 
-        Ok(count)
+        Ok(CollectionReport::with_items(count))
     }
 }
 
@@ -61,7 +92,7 @@ mod tests {
         let collector = StuckUploadCollector::new(mock_repo.clone(), 24);
 
         let result = collector.collect().await.unwrap();
-        assert_eq!(result, 5);
+        assert_eq!(result.items, 5);
 
         let calls = mock_repo.cleanup_calls.lock().unwrap();
         assert_eq!(calls.len(), 1);
@@ -74,7 +105,7 @@ mod tests {
         let collector = StuckUploadCollector::new(mock_repo, 24);
 
         let result = collector.collect().await.unwrap();
-        assert_eq!(result, 0);
+        assert_eq!(result.items, 0);
     }
 
     #[tokio::test]
@@ -87,4 +118,14 @@ mod tests {
         let result = collector.collect().await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_dry_run_counts_without_cleanup() {
+        let mock_repo = Arc::new(MockObjectRepository::success(3));
+        let collector = StuckUploadCollector::new(mock_repo.clone(), 24).with_dry_run(true);
+
+        let result = collector.collect().await.unwrap();
+        assert_eq!(result.items, 3);
+        assert!(mock_repo.cleanup_calls.lock().unwrap().is_empty());
+    }
 }
@@ -1,11 +1,14 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 
 use crate::application::ports::{
-    BlobRepository, BlobStore, ObjectRepository, RepositoryError, StorageError,
+    BlobDeletionFailure, BlobDeletionFailureRepository, BlobRepository, BlobStore,
+    ObjectRepository, RepositoryError, StorageError,
 };
 use crate::domain::entities::Blob;
-use crate::domain::value_objects::{ContentHash, StorageClass};
+use crate::domain::value_objects::{ContentHash, ContentHashPrefix, StorageClass, TenantId};
 
 /// Mock blob repository for testing
 pub struct MockBlobRepository {
@@ -55,6 +58,15 @@ impl BlobRepository for MockBlobRepository {
         unimplemented!("Not needed for GC collector tests")
     }
 
+    async fn reference_existing(
+        &self,
+        _content_hash: &ContentHash,
+        _storage_class: StorageClass,
+        _tenant_id: &TenantId,
+    ) -> Result<Option<Blob>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
     async fn increment_ref(&self, _content_hash: &ContentHash) -> Result<(), RepositoryError> {
         unimplemented!("Not needed for GC collector tests")
     }
@@ -88,12 +100,55 @@ impl BlobRepository for MockBlobRepository {
             .push(content_hash.to_string());
         Ok(())
     }
+
+    async fn move_storage_class(
+        &self,
+        _content_hash: &ContentHash,
+        _from: StorageClass,
+        _to: StorageClass,
+    ) -> Result<bool, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn find_known(
+        &self,
+        _content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn list_hashes(
+        &self,
+        _after: Option<ContentHash>,
+        _limit: i64,
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn list(
+        &self,
+        _after: Option<ContentHash>,
+        _orphaned_only: bool,
+        _limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn find_by_prefix(
+        &self,
+        _prefix: &ContentHashPrefix,
+        _limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
 }
 
 /// Mock blob store for testing
 pub struct MockBlobStore {
     pub deleted_files: Mutex<Vec<String>>,
     pub should_fail_delete: bool,
+    /// Deletions that fail with a transient error before they succeed
+    pub transient_failures: AtomicUsize,
 }
 
 impl MockBlobStore {
@@ -101,13 +156,22 @@ impl MockBlobStore {
         Self {
             deleted_files: Mutex::new(Vec::new()),
             should_fail_delete: false,
+            transient_failures: AtomicUsize::new(0),
         }
     }
 
     pub fn failing() -> Self {
         Self {
-            deleted_files: Mutex::new(Vec::new()),
             should_fail_delete: true,
+            ..Self::new()
+        }
+    }
+
+    /// A store whose first `failures` deletions fail transiently
+    pub fn flaky(failures: usize) -> Self {
+        Self {
+            transient_failures: AtomicUsize::new(failures),
+            ..Self::new()
         }
     }
 }
@@ -144,6 +208,13 @@ impl BlobStore for MockBlobStore {
         if self.should_fail_delete {
             return Err(StorageError::NotFound("blob not found".to_string()));
         }
+        if self
+            .transient_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(StorageError::Transient("backend timed out".to_string()));
+        }
         self.deleted_files
             .lock()
             .unwrap()
@@ -162,6 +233,96 @@ impl BlobStore for MockBlobStore {
     async fn get_total_size(&self, _storage_class: StorageClass) -> Result<u64, StorageError> {
         Ok(0)
     }
+
+    async fn append_staged(
+        &self,
+        _upload_id: uuid::Uuid,
+        _offset: u64,
+        _reader: crate::application::ports::BlobReader,
+        _storage_class: StorageClass,
+    ) -> Result<u64, StorageError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn staged_len(
+        &self,
+        _upload_id: uuid::Uuid,
+        _storage_class: StorageClass,
+    ) -> Result<Option<u64>, StorageError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn read_staged(
+        &self,
+        _upload_id: uuid::Uuid,
+        _storage_class: StorageClass,
+    ) -> Result<crate::application::ports::BlobReader, StorageError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn discard_staged(
+        &self,
+        _upload_id: uuid::Uuid,
+        _storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn initiate_restore(
+        &self,
+        _content_hash: &ContentHash,
+    ) -> Result<crate::application::ports::RestoreJob, StorageError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn restore_status(
+        &self,
+        _content_hash: &ContentHash,
+    ) -> Result<crate::application::ports::RestoreStatus, StorageError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+}
+
+/// Failed blob deletions kept in memory
+#[derive(Default)]
+pub struct InMemoryDeletionFailures {
+    pub failures: Mutex<HashMap<String, BlobDeletionFailure>>,
+}
+
+impl InMemoryDeletionFailures {
+    pub fn get(&self, content_hash: &ContentHash) -> Option<BlobDeletionFailure> {
+        self.failures
+            .lock()
+            .unwrap()
+            .get(&content_hash.to_string())
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl BlobDeletionFailureRepository for InMemoryDeletionFailures {
+    async fn find(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<Option<BlobDeletionFailure>, RepositoryError> {
+        Ok(self.get(content_hash))
+    }
+
+    async fn record(&self, failure: &BlobDeletionFailure) -> Result<(), RepositoryError> {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert(failure.content_hash.to_string(), failure.clone());
+        Ok(())
+    }
+
+    async fn clear(&self, content_hash: &ContentHash) -> Result<(), RepositoryError> {
+        self.failures
+            .lock()
+            .unwrap()
+            .remove(&content_hash.to_string());
+        Ok(())
+    }
 }
 
 /// Mock object repository for testing
@@ -201,6 +362,44 @@ impl ObjectRepository for MockObjectRepository {
         unimplemented!("Not needed for GC collector tests")
     }
 
+    async fn replace_content_if_match(
+        &self,
+        _object: &crate::domain::entities::Object,
+        _expected: &ContentHash,
+    ) -> Result<bool, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn update_metadata_if_match(
+        &self,
+        _object: &crate::domain::entities::Object,
+        _expected: &crate::domain::value_objects::ObjectMetadata,
+    ) -> Result<bool, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn update_retention(
+        &self,
+        _object: &crate::domain::entities::Object,
+    ) -> Result<bool, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn mark_deleting_if_unlocked(
+        &self,
+        _object: &crate::domain::entities::Object,
+    ) -> Result<bool, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn set_extracted_text(
+        &self,
+        _id: &crate::domain::value_objects::ObjectId,
+        _text: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
     async fn find_by_id(
         &self,
         _id: &crate::domain::value_objects::ObjectId,
@@ -208,6 +407,49 @@ impl ObjectRepository for MockObjectRepository {
         unimplemented!("Not needed for GC collector tests")
     }
 
+    async fn find_writing(
+        &self,
+        _id: &crate::domain::value_objects::ObjectId,
+    ) -> Result<Option<crate::domain::entities::Object>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn head(
+        &self,
+        _id: &crate::domain::value_objects::ObjectId,
+    ) -> Result<Option<crate::application::dto::ObjectHead>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn find_record(
+        &self,
+        _id: &crate::domain::value_objects::ObjectId,
+    ) -> Result<Option<crate::application::dto::ObjectRecord>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn status(
+        &self,
+        _id: &crate::domain::value_objects::ObjectId,
+    ) -> Result<
+        Option<(
+            crate::domain::value_objects::TenantId,
+            crate::domain::value_objects::ObjectStatus,
+        )>,
+        RepositoryError,
+    > {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn head_by_key(
+        &self,
+        _namespace: &crate::domain::value_objects::Namespace,
+        _tenant_id: &crate::domain::value_objects::TenantId,
+        _key: &str,
+    ) -> Result<Option<crate::application::dto::ObjectHead>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
     async fn find_by_key(
         &self,
         _namespace: &crate::domain::value_objects::Namespace,
@@ -221,15 +463,79 @@ impl ObjectRepository for MockObjectRepository {
         &self,
         _namespace: &crate::domain::value_objects::Namespace,
         _tenant_id: &crate::domain::value_objects::TenantId,
+        _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+        _metadata: &crate::application::metadata_query::MetadataQuery,
+        _sort_by: crate::application::dto::SortField,
+        _sort_direction: crate::application::dto::SortDirection,
+        _after: Option<crate::application::list_cursor::ListCursor>,
         _limit: i64,
         _offset: i64,
     ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
         unimplemented!("Not needed for GC collector tests")
     }
 
+    async fn list_projected(
+        &self,
+        _namespace: &crate::domain::value_objects::Namespace,
+        _tenant_id: &crate::domain::value_objects::TenantId,
+        _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+        _metadata: &crate::application::metadata_query::MetadataQuery,
+        _sort_by: crate::application::dto::SortField,
+        _sort_direction: crate::application::dto::SortDirection,
+        _fields: &[crate::application::dto::ObjectField],
+        _limit: i64,
+        _offset: i64,
+    ) -> Result<Vec<crate::application::dto::ObjectProjection>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    fn stream(
+        &self,
+        _namespace: &crate::domain::value_objects::Namespace,
+        _tenant_id: &crate::domain::value_objects::TenantId,
+        _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+        _metadata: &crate::application::metadata_query::MetadataQuery,
+        _sort_by: crate::application::dto::SortField,
+        _sort_direction: crate::application::dto::SortDirection,
+    ) -> crate::application::ports::ObjectStream {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn common_prefixes(
+        &self,
+        _namespace: &crate::domain::value_objects::Namespace,
+        _tenant_id: &crate::domain::value_objects::TenantId,
+        _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+        _metadata: &crate::application::metadata_query::MetadataQuery,
+        _max: i64,
+    ) -> Result<Vec<String>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn count(
+        &self,
+        _namespace: &crate::domain::value_objects::Namespace,
+        _tenant_id: &crate::domain::value_objects::TenantId,
+        _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+        _metadata: &crate::application::metadata_query::MetadataQuery,
+        _max: i64,
+    ) -> Result<i64, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn namespaces(
+        &self,
+        _tenant_id: &crate::domain::value_objects::TenantId,
+    ) -> Result<Vec<crate::domain::value_objects::Namespace>, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
     async fn search(
         &self,
         _request: &crate::application::dto::SearchRequest,
+        _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+        _metadata: &crate::application::metadata_query::MetadataQuery,
+        _after: Option<crate::application::list_cursor::ListCursor>,
     ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
         unimplemented!("Not needed for GC collector tests")
     }
@@ -237,7 +543,23 @@ impl ObjectRepository for MockObjectRepository {
     async fn text_search(
         &self,
         _request: &crate::application::dto::TextSearchRequest,
-    ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
+    ) -> Result<crate::application::dto::TextSearchPage, RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn record_access(
+        &self,
+        _accesses: &[crate::application::dto::ObjectAccess],
+    ) -> Result<(), RepositoryError> {
+        unimplemented!("Not needed for GC collector tests")
+    }
+
+    async fn move_storage_class(
+        &self,
+        _content_hash: &ContentHash,
+        _from: StorageClass,
+        _to: StorageClass,
+    ) -> Result<u64, RepositoryError> {
         unimplemented!("Not needed for GC collector tests")
     }
 
@@ -251,8 +573,28 @@ impl ObjectRepository for MockObjectRepository {
     async fn find_stuck_writing_objects(
         &self,
         _age_hours: i64,
-        _limit: i64,
+        limit: i64,
     ) -> Result<Vec<crate::domain::value_objects::ObjectId>, RepositoryError> {
+        match *self.cleanup_result.lock().unwrap() {
+            Ok(count) => Ok((0..count.min(limit as usize))
+                .map(|_| crate::domain::value_objects::ObjectId::new())
+                .collect()),
+            Err(_) => Err(RepositoryError::Database(sqlx::Error::RowNotFound)),
+        }
+    }
+
+    async fn find_stuck_staged_objects(
+        &self,
+        _age_minutes: i64,
+        _limit: i64,
+    ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    async fn find_locked_hashes(
+        &self,
+        _content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
         unimplemented!("Not needed for GC collector tests")
     }
 }
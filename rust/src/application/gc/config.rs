@@ -1,5 +1,56 @@
 use std::time::Duration;
 
+/// Default number of orphaned blobs deleted at the same time
+pub const DEFAULT_MAX_CONCURRENT_DELETIONS: usize = 10;
+
+/// How blobs whose file could not be deleted are retried
+///
+/// Such a blob keeps its database row and is retried by later cycles, waiting
+/// `base_backoff` after the first failure and twice as long after each
+/// further one (up to `max_backoff`). After `max_attempts` failures it is
+/// dead-lettered for an operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeletionRetryPolicy {
+    /// Failed attempts before a blob is dead-lettered; 0 disables retries
+    /// (the row is deleted even when the file is not)
+    pub max_attempts: u32,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for DeletionRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_backoff: Duration::from_secs(300), // one default GC interval
+            max_backoff: Duration::from_secs(6 * 3600),
+        }
+    }
+}
+
+impl DeletionRetryPolicy {
+    pub fn new(max_attempts: u32, base_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_backoff,
+            max_backoff,
+        }
+    }
+
+    /// Whether failed deletions are retried at all
+    pub fn enabled(&self) -> bool {
+        self.max_attempts > 0
+    }
+
+    /// Wait before retrying a blob that has failed `attempts` times
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let doublings = attempts.saturating_sub(1).min(31);
+        self.base_backoff
+            .saturating_mul(1 << doublings)
+            .min(self.max_backoff)
+    }
+}
+
 /// Configuration for garbage collection operations
 #[derive(Debug, Clone)]
 pub struct GcConfig {
@@ -7,10 +58,27 @@ pub struct GcConfig {
     pub interval: Duration,
     /// Number of items to process in each batch
     pub batch_size: i64,
-    /// Age threshold for considering uploads "stuck" (in hours)
+    /// Number of blob deletions (storage and database) in flight at once
+    pub max_concurrent_deletions: usize,
+    /// Retries of blobs whose file could not be deleted
+    pub deletion_retries: DeletionRetryPolicy,
+    /// Writing-state timeout: uploads still WRITING after this many hours
+    /// are considered "stuck"
     pub stuck_upload_age_hours: i64,
     /// How often to run stuck upload cleanup (relative to main interval)
     pub stuck_upload_cleanup_multiplier: u32,
+    /// Log and count candidates without deleting anything
+    pub dry_run: bool,
+    /// Run the orphaned blob collector
+    pub orphaned_blobs_enabled: bool,
+    /// Run the stuck upload collector (also requires an object repository)
+    pub stuck_uploads_enabled: bool,
+    /// Commit or roll back interrupted uploads at startup (also requires an
+    /// object repository)
+    pub write_recovery_enabled: bool,
+    /// Staged uploads created more than this many minutes ago are recovered;
+    /// kept below the stuck upload age so recovery gets to them first
+    pub write_recovery_age_minutes: i64,
 }
 
 impl Default for GcConfig {
@@ -18,8 +86,15 @@ impl Default for GcConfig {
         Self {
             interval: Duration::from_secs(300), // 5 minutes
             batch_size: 100,
+            max_concurrent_deletions: DEFAULT_MAX_CONCURRENT_DELETIONS,
+            deletion_retries: DeletionRetryPolicy::default(),
             stuck_upload_age_hours: 1,
             stuck_upload_cleanup_multiplier: 10, // Run stuck upload cleanup 10x less frequently
+            dry_run: false,
+            orphaned_blobs_enabled: true,
+            stuck_uploads_enabled: true,
+            write_recovery_enabled: true,
+            write_recovery_age_minutes: 15,
         }
     }
 }
@@ -30,10 +105,58 @@ impl GcConfig {
             interval,
             batch_size,
             stuck_upload_age_hours,
-            stuck_upload_cleanup_multiplier: 10,
+            ..Self::default()
         }
     }
 
+    /// Set the number of items processed per collection cycle
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set how many blob deletions run concurrently (at least one)
+    pub fn with_max_concurrent_deletions(mut self, max_concurrent_deletions: usize) -> Self {
+        self.max_concurrent_deletions = max_concurrent_deletions.max(1);
+        self
+    }
+
+    /// Set how blobs whose file could not be deleted are retried
+    pub fn with_deletion_retries(mut self, deletion_retries: DeletionRetryPolicy) -> Self {
+        self.deletion_retries = deletion_retries;
+        self
+    }
+
+    /// Enable or disable dry-run mode
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Enable or disable the orphaned blob collector
+    pub fn with_orphaned_blobs(mut self, enabled: bool) -> Self {
+        self.orphaned_blobs_enabled = enabled;
+        self
+    }
+
+    /// Enable or disable the stuck upload collector
+    pub fn with_stuck_uploads(mut self, enabled: bool) -> Self {
+        self.stuck_uploads_enabled = enabled;
+        self
+    }
+
+    /// Enable or disable startup recovery of interrupted uploads
+    pub fn with_write_recovery(mut self, enabled: bool) -> Self {
+        self.write_recovery_enabled = enabled;
+        self
+    }
+
+    /// Set the age in minutes after which staged uploads are recovered
+    pub fn with_write_recovery_age_minutes(mut self, age_minutes: i64) -> Self {
+        self.write_recovery_age_minutes = age_minutes;
+        self
+    }
+
     /// Calculate the stuck upload cleanup interval
     pub fn stuck_upload_cleanup_interval(&self) -> Duration {
         self.interval * self.stuck_upload_cleanup_multiplier
@@ -1,10 +1,12 @@
 pub mod collectors;
 pub mod config;
+pub mod recovery;
 pub mod results;
 pub mod scheduler;
 pub mod worker;
 
-pub use config::GcConfig;
+pub use config::{DeletionRetryPolicy, GcConfig, DEFAULT_MAX_CONCURRENT_DELETIONS};
+pub use recovery::{RecoveryReport, WriteRecovery};
 pub use results::{GcResult, GcStatistics};
 pub use scheduler::{ConditionalTaskRunner, PeriodicTaskRunner, TaskScheduler};
 pub use worker::GarbageCollector;
@@ -0,0 +1,308 @@
+//! Recovery of uploads interrupted between writing the blob and committing
+//!
+//! Once an upload's blob is written and referenced, its content hash is
+//! staged on the still-WRITING object row. If the process dies before the
+//! commit, the row stays WRITING. Recovery looks at staged rows older than its
+//! own age threshold, kept below the stuck upload age so they are recovered
+//! before the stuck upload collector deletes them: when the blob for the
+//! staged hash exists in the store the object is committed, otherwise it is
+//! rolled back (row deleted, blob reference released). Rows that never got as
+//! far as staging have no blob and are left to the stuck upload collector.
+
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::application::ports::{BlobRepository, BlobStore, ObjectRepository};
+use crate::domain::entities::Object;
+
+/// Upper bound on the interrupted uploads handled by one recovery pass
+pub const RECOVERY_SCAN_LIMIT: i64 = 1000;
+
+/// Outcome of a recovery pass
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Objects committed from a blob found in storage
+    pub committed: usize,
+    /// Objects deleted because their blob never landed
+    pub rolled_back: usize,
+    /// Whether this was a dry run (counts describe what would have happened)
+    pub dry_run: bool,
+    /// Objects (or the scan) that could not be recovered
+    pub errors: Vec<String>,
+}
+
+impl RecoveryReport {
+    /// Returns true if any object was (or in a dry run would be) recovered
+    pub fn has_recoveries(&self) -> bool {
+        self.committed > 0 || self.rolled_back > 0
+    }
+
+    /// Returns a summary of the recovery pass as a formatted string
+    pub fn summary(&self) -> String {
+        let prefix = if self.dry_run {
+            "Write recovery dry run"
+        } else {
+            "Write recovery"
+        };
+        format!(
+            "{}: {} committed, {} rolled back, {} errors",
+            prefix,
+            self.committed,
+            self.rolled_back,
+            self.errors.len()
+        )
+    }
+}
+
+/// Commits or rolls back uploads left in WRITING state by a crash
+pub struct WriteRecovery {
+    object_repo: Arc<dyn ObjectRepository>,
+    blob_repo: Arc<dyn BlobRepository>,
+    blob_store: Arc<dyn BlobStore>,
+    age_minutes: i64,
+    dry_run: bool,
+}
+
+impl WriteRecovery {
+    pub fn new(
+        object_repo: Arc<dyn ObjectRepository>,
+        blob_repo: Arc<dyn BlobRepository>,
+        blob_store: Arc<dyn BlobStore>,
+        age_minutes: i64,
+    ) -> Self {
+        Self {
+            object_repo,
+            blob_repo,
+            blob_store,
+            age_minutes,
+            dry_run: false,
+        }
+    }
+
+    /// Log the outcome for each object without changing anything
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Recover staged uploads created more than the recovery age ago
+    ///
+    /// Failures are collected per object; one bad object does not stop the
+    /// pass.
+    pub async fn run(&self) -> RecoveryReport {
+        let mut report = RecoveryReport {
+            dry_run: self.dry_run,
+            ..RecoveryReport::default()
+        };
+
+        let staged = match self
+            .object_repo
+            .find_stuck_staged_objects(self.age_minutes, RECOVERY_SCAN_LIMIT)
+            .await
+        {
+            Ok(staged) => staged,
+            Err(e) => {
+                report
+                    .errors
+                    .push(format!("Failed to scan for interrupted uploads: {}", e));
+                return report;
+            }
+        };
+
+        for object in staged {
+            let object_id = *object.id();
+            if let Err(e) = self.recover(object, &mut report).await {
+                warn!(%object_id, "Failed to recover interrupted upload: {}", e);
+                report.errors.push(format!("{}: {}", object_id, e));
+            }
+        }
+
+        report
+    }
+
+    async fn recover(&self, mut object: Object, report: &mut RecoveryReport) -> Result<(), String> {
+        let Some((content_hash, size_bytes)) = object
+            .staged_content()
+            .map(|(hash, size)| (hash.clone(), size))
+        else {
+            return Ok(());
+        };
+        let object_id = *object.id();
+
+        let landed = self
+            .blob_store
+            .exists(&content_hash, object.storage_class())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if landed {
+            if self.dry_run {
+                info!(%object_id, %content_hash, "Dry run: would commit interrupted upload");
+            } else {
+                object
+                    .commit(&content_hash, size_bytes)
+                    .map_err(|e| e.to_string())?;
+                self.object_repo
+                    .save(&object)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                info!(%object_id, %content_hash, "Committed interrupted upload from its blob");
+            }
+            report.committed += 1;
+        } else {
+            if self.dry_run {
+                info!(%object_id, %content_hash, "Dry run: would roll back interrupted upload");
+            } else {
+                self.object_repo
+                    .delete(&object_id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                // The staged row held a reference on the blob
+                self.blob_repo
+                    .decrement_ref(&content_hash)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                warn!(%object_id, %content_hash, "Rolled back interrupted upload: blob missing from storage");
+            }
+            report.rolled_back += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{
+        MockBlobRepository, MockBlobStore, MockObjectRepository, RepositoryError,
+    };
+    use crate::domain::value_objects::{
+        ContentHash, Namespace, ObjectStatus, StorageClass, TenantId,
+    };
+    use std::str::FromStr;
+
+    fn staged_object() -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("models").unwrap(),
+            TenantId::new(uuid::Uuid::new_v4()),
+            Some("weights.bin".to_string()),
+            StorageClass::Hot,
+        );
+        object
+            .stage_content(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 42)
+            .unwrap();
+        object
+    }
+
+    fn object_repo_with(staged: Vec<Object>) -> MockObjectRepository {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_stuck_staged_objects()
+            .withf(|age_minutes, _| *age_minutes == 15)
+            .times(1)
+            .return_once(move |_, _| Ok(staged));
+        mock_object_repo
+    }
+
+    fn blob_store_with(exists: bool) -> MockBlobStore {
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_exists()
+            .returning(move |_, _| Ok(exists));
+        mock_blob_store
+    }
+
+    #[tokio::test]
+    async fn test_commits_upload_whose_blob_landed() {
+        let mut mock_object_repo = object_repo_with(vec![staged_object()]);
+        mock_object_repo
+            .expect_save()
+            .withf(|object| {
+                object.status() == ObjectStatus::Committed && object.size_bytes() == Some(42)
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_decrement_ref().never();
+
+        let recovery = WriteRecovery::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(blob_store_with(true)),
+            15,
+        );
+
+        let report = recovery.run().await;
+        assert_eq!(report.committed, 1);
+        assert_eq!(report.rolled_back, 0);
+        assert!(report.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rolls_back_upload_without_blob() {
+        let object = staged_object();
+        let object_id = *object.id();
+        let mut mock_object_repo = object_repo_with(vec![object]);
+        mock_object_repo
+            .expect_delete()
+            .withf(move |id| *id == object_id)
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(0));
+
+        let recovery = WriteRecovery::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(blob_store_with(false)),
+            15,
+        );
+
+        let report = recovery.run().await;
+        assert_eq!(report.committed, 0);
+        assert_eq!(report.rolled_back, 1);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_changes_nothing() {
+        let mut mock_object_repo = object_repo_with(vec![staged_object(), staged_object()]);
+        mock_object_repo.expect_save().never();
+        mock_object_repo.expect_delete().never();
+
+        let recovery = WriteRecovery::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(blob_store_with(true)),
+            15,
+        )
+        .with_dry_run(true);
+
+        let report = recovery.run().await;
+        assert!(report.dry_run);
+        assert_eq!(report.committed, 2);
+    }
+
+    #[tokio::test]
+    async fn test_scan_failure_is_reported() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_stuck_staged_objects()
+            .returning(|_, _| Err(RepositoryError::Internal("down".to_string())));
+
+        let recovery = WriteRecovery::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+            15,
+        );
+
+        let report = recovery.run().await;
+        assert!(!report.has_recoveries());
+        assert_eq!(report.errors.len(), 1);
+    }
+}
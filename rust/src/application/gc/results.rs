@@ -1,3 +1,5 @@
+use crate::application::gc::collectors::DeadLetteredBlob;
+
 /// Result types and utilities for garbage collection operations
 ///
 /// This module contains all the result types returned by GC operations,
@@ -13,6 +15,31 @@ pub struct GcResult {
     pub orphaned_blobs_deleted: usize,
     /// Number of stuck uploads that were successfully cleaned up.
     pub stuck_uploads_deleted: usize,
+    /// Blob store bytes reclaimed by deleting orphaned blobs.
+    pub bytes_reclaimed: u64,
+    /// Number of deleted orphaned blobs from the hot tier.
+    pub hot_blobs_deleted: usize,
+    /// Number of deleted orphaned blobs from the cold tier.
+    pub cold_blobs_deleted: usize,
+    /// Number of deleted orphaned blobs whose database entry was removed but
+    /// whose blob store file could not be deleted.
+    pub orphaned_blob_files_left: usize,
+    /// Orphaned blobs that could not be fully deleted, with the reason.
+    ///
+    /// Unlike `errors`, these do not stop a collector; the blobs are retried
+    /// or picked up by a later cycle.
+    pub blob_deletion_failures: Vec<String>,
+    /// Number of orphaned blobs kept because their blob store file could not
+    /// be deleted; a later cycle retries them.
+    pub blob_deletions_deferred: usize,
+    /// Orphaned blobs whose file deletion failed too often to retry, left
+    /// for an operator.
+    pub dead_lettered_blobs: Vec<DeadLetteredBlob>,
+    /// Whether this was a dry run.
+    ///
+    /// In a dry run nothing is deleted and every count above describes the
+    /// candidates that would have been removed.
+    pub dry_run: bool,
     /// Any errors that occurred during the collection process.
     ///
     /// Each error represents a failure in one of the collectors. The collection
@@ -81,20 +108,35 @@ impl GcResult {
         self.total_deleted += other.total_deleted;
         self.orphaned_blobs_deleted += other.orphaned_blobs_deleted;
         self.stuck_uploads_deleted += other.stuck_uploads_deleted;
+        self.bytes_reclaimed += other.bytes_reclaimed;
+        self.hot_blobs_deleted += other.hot_blobs_deleted;
+        self.cold_blobs_deleted += other.cold_blobs_deleted;
+        self.orphaned_blob_files_left += other.orphaned_blob_files_left;
+        self.blob_deletion_failures
+            .extend(other.blob_deletion_failures);
+        self.blob_deletions_deferred += other.blob_deletions_deferred;
+        self.dead_lettered_blobs.extend(other.dead_lettered_blobs);
+        self.dry_run |= other.dry_run;
         self.errors.extend(other.errors);
     }
 
     /// Returns a summary of the collection results as a formatted string
     pub fn summary(&self) -> String {
+        let prefix = if self.dry_run { "GC dry run" } else { "GC" };
+        let deleted = if self.dry_run {
+            "would be deleted"
+        } else {
+            "deleted"
+        };
         if self.errors.is_empty() {
             format!(
-                "GC completed successfully: {} total deleted ({} orphaned blobs, {} stuck uploads)",
-                self.total_deleted, self.orphaned_blobs_deleted, self.stuck_uploads_deleted
+                "{} completed successfully: {} total {} ({} orphaned blobs, {} stuck uploads, {} bytes)",
+                prefix, self.total_deleted, deleted, self.orphaned_blobs_deleted, self.stuck_uploads_deleted, self.bytes_reclaimed
             )
         } else {
             format!(
-                "GC completed with {} errors: {} total deleted ({} orphaned blobs, {} stuck uploads)",
-                self.errors.len(), self.total_deleted, self.orphaned_blobs_deleted, self.stuck_uploads_deleted
+                "{} completed with {} errors: {} total {} ({} orphaned blobs, {} stuck uploads, {} bytes)",
+                prefix, self.errors.len(), self.total_deleted, deleted, self.orphaned_blobs_deleted, self.stuck_uploads_deleted, self.bytes_reclaimed
             )
         }
     }
@@ -102,9 +144,24 @@ impl GcResult {
     /// Returns detailed information about the collection results
     pub fn details(&self) -> String {
         let mut details = vec![
+            format!("Dry run: {}", self.dry_run),
             format!("Total items deleted: {}", self.total_deleted),
-            format!("Orphaned blobs deleted: {}", self.orphaned_blobs_deleted),
+            format!(
+                "Orphaned blobs deleted: {} ({} hot, {} cold)",
+                self.orphaned_blobs_deleted, self.hot_blobs_deleted, self.cold_blobs_deleted
+            ),
+            format!(
+                "Orphaned blob files left: {}",
+                self.orphaned_blob_files_left
+            ),
+            format!("Bytes reclaimed: {}", self.bytes_reclaimed),
             format!("Stuck uploads cleaned: {}", self.stuck_uploads_deleted),
+            format!(
+                "Blob deletion failures: {}",
+                self.blob_deletion_failures.len()
+            ),
+            format!("Blob deletions deferred: {}", self.blob_deletions_deferred),
+            format!("Dead-lettered blobs: {}", self.dead_lettered_blobs.len()),
             format!("Errors encountered: {}", self.errors.len()),
         ];
 
@@ -130,6 +187,18 @@ pub struct GcStatistics {
     pub total_orphaned_blobs_deleted: usize,
     /// Total stuck uploads cleaned
     pub total_stuck_uploads_cleaned: usize,
+    /// Total blob store bytes reclaimed
+    pub total_bytes_reclaimed: u64,
+    /// Total orphaned blobs deleted from the hot tier
+    pub total_hot_blobs_deleted: usize,
+    /// Total orphaned blobs deleted from the cold tier
+    pub total_cold_blobs_deleted: usize,
+    /// Total orphaned blobs whose blob store file could not be deleted
+    pub total_orphaned_blob_files_left: usize,
+    /// Total orphaned blobs dead-lettered after failing every deletion retry
+    pub total_dead_lettered_blobs: usize,
+    /// Cycles that ran in dry-run mode (their counts are included above)
+    pub dry_run_cycles: usize,
     /// Total errors encountered
     pub total_errors: usize,
     /// Average items deleted per cycle
@@ -143,6 +212,14 @@ impl GcStatistics {
         self.total_items_deleted += result.total_deleted;
         self.total_orphaned_blobs_deleted += result.orphaned_blobs_deleted;
         self.total_stuck_uploads_cleaned += result.stuck_uploads_deleted;
+        self.total_bytes_reclaimed += result.bytes_reclaimed;
+        self.total_hot_blobs_deleted += result.hot_blobs_deleted;
+        self.total_cold_blobs_deleted += result.cold_blobs_deleted;
+        self.total_orphaned_blob_files_left += result.orphaned_blob_files_left;
+        self.total_dead_lettered_blobs += result.dead_lettered_blobs.len();
+        if result.dry_run {
+            self.dry_run_cycles += 1;
+        }
         self.total_errors += result.errors.len();
 
         if self.cycles_completed > 0 {
@@ -155,15 +232,21 @@ impl GcStatistics {
     pub fn summary(&self) -> String {
         format!(
             "GC Statistics:\n\
-             Cycles completed: {}\n\
+             Cycles completed: {} ({} dry run)\n\
              Total items deleted: {}\n\
-             Orphaned blobs: {}\n\
+             Orphaned blobs: {} ({} hot, {} cold, {} files left)\n\
+             Bytes reclaimed: {}\n\
              Stuck uploads: {}\n\
              Total errors: {}\n\
              Average deletions/cycle: {:.2}",
             self.cycles_completed,
+            self.dry_run_cycles,
             self.total_items_deleted,
             self.total_orphaned_blobs_deleted,
+            self.total_hot_blobs_deleted,
+            self.total_cold_blobs_deleted,
+            self.total_orphaned_blob_files_left,
+            self.total_bytes_reclaimed,
             self.total_stuck_uploads_cleaned,
             self.total_errors,
             self.average_deletions_per_cycle
@@ -206,6 +289,7 @@ mod tests {
             orphaned_blobs_deleted: 3,
             stuck_uploads_deleted: 2,
             errors: vec!["error1".to_string()],
+            ..Default::default()
         };
 
         let result2 = GcResult {
@@ -213,6 +297,7 @@ mod tests {
             orphaned_blobs_deleted: 2,
             stuck_uploads_deleted: 1,
             errors: vec!["error2".to_string()],
+            ..Default::default()
         };
 
         result1.merge(result2);
@@ -230,6 +315,7 @@ mod tests {
             orphaned_blobs_deleted: 7,
             stuck_uploads_deleted: 3,
             errors: vec![],
+            ..Default::default()
         };
 
         let summary = result.summary();
@@ -247,6 +333,7 @@ mod tests {
             orphaned_blobs_deleted: 3,
             stuck_uploads_deleted: 2,
             errors: vec!["error".to_string()],
+            ..Default::default()
         };
 
         stats.update(&result);
@@ -258,4 +345,23 @@ mod tests {
         assert_eq!(stats.total_errors, 1);
         assert_eq!(stats.average_deletions_per_cycle, 5.0);
     }
+
+    #[test]
+    fn test_gc_statistics_accumulate_files_left() {
+        let mut stats = GcStatistics::default();
+
+        let result = GcResult {
+            total_deleted: 4,
+            orphaned_blobs_deleted: 4,
+            orphaned_blob_files_left: 1,
+            ..Default::default()
+        };
+
+        stats.update(&result);
+        stats.update(&result);
+
+        assert_eq!(stats.total_orphaned_blobs_deleted, 8);
+        assert_eq!(stats.total_orphaned_blob_files_left, 2);
+        assert!(stats.summary().contains("2 files left"));
+    }
 }
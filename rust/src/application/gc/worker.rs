@@ -1,15 +1,21 @@
+use ::time::OffsetDateTime;
+use serde_json::json;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::time;
 use tracing::{error, info};
 
+use crate::api::middleware::audit::{AuditEventType, AuditLogEntry};
 use crate::application::gc::collectors::{
     errors::GcResult as CollectorResult, Collector, OrphanedBlobCollector, StuckUploadCollector,
 };
 use crate::application::gc::config::GcConfig;
+use crate::application::gc::recovery::{RecoveryReport, WriteRecovery};
 use crate::application::gc::results::{GcResult, GcStatistics};
 use crate::application::gc::scheduler::TaskScheduler;
-use crate::application::ports::{BlobRepository, BlobStore, ObjectRepository};
+use crate::application::ports::{
+    AuditRepository, BlobDeletionFailureRepository, BlobRepository, BlobStore, ObjectRepository,
+};
 
 /// Garbage collector for orphaned blobs and stuck uploads.
 ///
@@ -20,6 +26,7 @@ use crate::application::ports::{BlobRepository, BlobStore, ObjectRepository};
 /// The collector supports:
 /// - **Orphaned blob cleanup**: Removes blobs with zero references
 /// - **Stuck upload cleanup**: Removes incomplete uploads that have been stuck too long
+/// - **Write recovery**: Commits or rolls back uploads interrupted after their blob was written
 /// - **Extensible architecture**: New collectors can be easily added
 /// - **Periodic execution**: Can run continuously with configurable intervals
 /// - **Conditional execution**: Some collectors only run periodically for efficiency
@@ -69,10 +76,19 @@ pub struct GarbageCollector {
     config: GcConfig,
     /// Optional scheduler for stuck upload cleanup (runs less frequently).
     stuck_upload_scheduler: Option<TaskScheduler>,
+    /// Optional recovery of interrupted uploads, run once at startup.
+    write_recovery: Option<WriteRecovery>,
     /// Statistics for garbage collection cycles.
     stats: Mutex<GcStatistics>,
     /// Last execution time.
     last_run: Mutex<Option<Instant>>,
+    /// Optional durable record of collection cycles.
+    audit_repo: Option<Arc<dyn AuditRepository>>,
+    /// Kept to rebuild the orphaned blob collector with deletion retries.
+    blob_repo: Arc<dyn BlobRepository>,
+    blob_store: Arc<dyn BlobStore>,
+    deletion_failures: Option<Arc<dyn BlobDeletionFailureRepository>>,
+    lock_check: Option<Arc<dyn ObjectRepository>>,
 }
 
 impl GarbageCollector {
@@ -106,18 +122,13 @@ impl GarbageCollector {
         interval: Duration,
         batch_size: i64,
     ) -> Self {
-        let orphaned_collector =
-            OrphanedBlobCollector::new(Arc::clone(&blob_repo), Arc::clone(&blob_store), batch_size);
-
-        let collectors: Vec<Box<dyn Collector + Send + Sync>> = vec![Box::new(orphaned_collector)];
-
-        Self {
-            collectors,
-            config: GcConfig::new(interval, batch_size, 1),
-            stuck_upload_scheduler: None, // No stuck upload collector
-            stats: Mutex::new(GcStatistics::default()),
-            last_run: Mutex::new(None),
-        }
+        // No object repository, so no stuck upload collector
+        Self::with_config(
+            blob_repo,
+            blob_store,
+            None,
+            GcConfig::new(interval, batch_size, 1),
+        )
     }
 
     pub fn with_object_repo(
@@ -128,34 +139,18 @@ impl GarbageCollector {
         batch_size: i64,
         stuck_upload_age_hours: i64,
     ) -> Self {
-        let config = GcConfig::new(interval, batch_size, stuck_upload_age_hours);
-
-        let mut collectors: Vec<Box<dyn Collector + Send + Sync>> = Vec::new();
-
-        // Add orphaned blob collector
-        let orphaned_collector =
-            OrphanedBlobCollector::new(Arc::clone(&blob_repo), Arc::clone(&blob_store), batch_size);
-        collectors.push(Box::new(orphaned_collector));
-
-        // Add stuck upload collector if object repo is provided
-        let stuck_upload_scheduler = if let Some(obj_repo) = object_repo {
-            let stuck_upload_collector =
-                StuckUploadCollector::new(obj_repo, stuck_upload_age_hours);
-            collectors.push(Box::new(stuck_upload_collector));
-            Some(TaskScheduler::new(config.stuck_upload_cleanup_interval()))
-        } else {
-            None
-        };
-
-        Self {
-            collectors,
-            config,
-            stuck_upload_scheduler,
-            stats: Mutex::new(GcStatistics::default()),
-            last_run: Mutex::new(None),
-        }
+        Self::with_config(
+            blob_repo,
+            blob_store,
+            object_repo,
+            GcConfig::new(interval, batch_size, stuck_upload_age_hours),
+        )
     }
 
+    /// Creates a garbage collector from a full configuration.
+    ///
+    /// Collectors disabled in `config` are not registered at all, and in
+    /// dry-run mode the registered ones only log and count their candidates.
     pub fn with_config(
         blob_repo: Arc<dyn BlobRepository>,
         blob_store: Arc<dyn BlobStore>,
@@ -165,17 +160,31 @@ impl GarbageCollector {
         let mut collectors: Vec<Box<dyn Collector + Send + Sync>> = Vec::new();
 
         // Add orphaned blob collector
-        let orphaned_collector = OrphanedBlobCollector::new(
-            Arc::clone(&blob_repo),
-            Arc::clone(&blob_store),
-            config.batch_size,
-        );
-        collectors.push(Box::new(orphaned_collector));
+        if config.orphaned_blobs_enabled {
+            let orphaned_collector =
+                Self::orphaned_blob_collector(&blob_repo, &blob_store, &config);
+            collectors.push(Box::new(orphaned_collector));
+        }
 
-        // Add stuck upload collector if object repo is provided
+        let write_recovery = object_repo
+            .as_ref()
+            .filter(|_| config.write_recovery_enabled)
+            .map(|obj_repo| {
+                WriteRecovery::new(
+                    Arc::clone(obj_repo),
+                    Arc::clone(&blob_repo),
+                    Arc::clone(&blob_store),
+                    config.write_recovery_age_minutes,
+                )
+                .with_dry_run(config.dry_run)
+            });
+
+        // Add stuck upload collector if enabled and object repo is provided
+        let object_repo = object_repo.filter(|_| config.stuck_uploads_enabled);
         let stuck_upload_scheduler = if let Some(obj_repo) = object_repo {
             let stuck_upload_collector =
-                StuckUploadCollector::new(obj_repo, config.stuck_upload_age_hours);
+                StuckUploadCollector::new(obj_repo, config.stuck_upload_age_hours)
+                    .with_dry_run(config.dry_run);
             collectors.push(Box::new(stuck_upload_collector));
             Some(TaskScheduler::new(config.stuck_upload_cleanup_interval()))
         } else {
@@ -186,17 +195,95 @@ impl GarbageCollector {
             collectors,
             config,
             stuck_upload_scheduler,
+            write_recovery,
             stats: Mutex::new(GcStatistics::default()),
             last_run: Mutex::new(None),
+            audit_repo: None,
+            blob_repo,
+            blob_store,
+            deletion_failures: None,
+            lock_check: None,
+        }
+    }
+
+    fn orphaned_blob_collector(
+        blob_repo: &Arc<dyn BlobRepository>,
+        blob_store: &Arc<dyn BlobStore>,
+        config: &GcConfig,
+    ) -> OrphanedBlobCollector {
+        OrphanedBlobCollector::new(
+            Arc::clone(blob_repo),
+            Arc::clone(blob_store),
+            config.batch_size,
+        )
+        .with_max_concurrent_deletions(config.max_concurrent_deletions)
+        .with_dry_run(config.dry_run)
+    }
+
+    /// Retries orphaned blobs whose file could not be deleted.
+    ///
+    /// Their failures are recorded in `failures` and the blobs kept until a
+    /// later cycle deletes the file, following the configured
+    /// `deletion_retries` policy. Blobs that fail every retry are
+    /// dead-lettered: logged, recorded in the audit log and left for an
+    /// operator. Without this the database entry is deleted regardless and
+    /// the file left behind.
+    pub fn with_deletion_failures(
+        mut self,
+        failures: Arc<dyn BlobDeletionFailureRepository>,
+    ) -> Self {
+        self.deletion_failures = Some(failures);
+        self.rebuild_orphaned_blob_collector();
+        self
+    }
+
+    /// Keeps orphaned blobs that an object under retention or legal hold
+    /// still points at, asking `object_repo`.
+    ///
+    /// Needed when objects live outside the blob repository's database,
+    /// which then cannot check this itself.
+    pub fn with_lock_check(mut self, object_repo: Arc<dyn ObjectRepository>) -> Self {
+        self.lock_check = Some(object_repo);
+        self.rebuild_orphaned_blob_collector();
+        self
+    }
+
+    fn rebuild_orphaned_blob_collector(&mut self) {
+        for collector in &mut self.collectors {
+            if collector.name() == "orphaned_blob_collector" {
+                let mut orphaned_collector =
+                    Self::orphaned_blob_collector(&self.blob_repo, &self.blob_store, &self.config);
+                if let Some(failures) = &self.deletion_failures {
+                    orphaned_collector = orphaned_collector
+                        .with_deletion_retries(Arc::clone(failures), self.config.deletion_retries);
+                }
+                if let Some(object_repo) = &self.lock_check {
+                    orphaned_collector =
+                        orphaned_collector.with_lock_check(Arc::clone(object_repo));
+                }
+                *collector = Box::new(orphaned_collector);
+            }
         }
     }
 
+    /// Records each collection cycle in the audit log.
+    ///
+    /// Cycles that found nothing to collect and hit no errors are not
+    /// recorded. Without an audit repository cycles are only logged.
+    pub fn with_audit_repo(mut self, audit_repo: Arc<dyn AuditRepository>) -> Self {
+        self.audit_repo = Some(audit_repo);
+        self
+    }
+
     /// Run garbage collection loop
     pub async fn run(self: Arc<Self>) {
         info!(
             "Starting garbage collector with interval: {:?}",
             self.config.interval
         );
+        if self.config.dry_run {
+            info!("Garbage collector is in dry-run mode: candidates are logged, not deleted");
+        }
 
         let mut interval = time::interval(self.config.interval);
 
@@ -216,12 +303,7 @@ impl GarbageCollector {
                     }
 
                     if result.has_deletions() {
-                        info!(
-                            "Garbage collection completed: {} total deleted ({} orphaned blobs, {} stuck uploads)",
-                            result.total_deleted,
-                            result.orphaned_blobs_deleted,
-                            result.stuck_uploads_deleted
-                        );
+                        info!("{}", result.summary());
                     }
 
                     if !result.is_success() {
@@ -237,6 +319,18 @@ impl GarbageCollector {
         }
     }
 
+    /// Commits or rolls back uploads interrupted by a crash.
+    ///
+    /// Meant to run once at startup, before the collection loop, so stuck
+    /// uploads that can be recovered are not deleted first. Returns `None`
+    /// when recovery is disabled or no object repository is configured.
+    pub async fn recover_interrupted_writes(&self) -> Option<RecoveryReport> {
+        match &self.write_recovery {
+            Some(recovery) => Some(recovery.run().await),
+            None => None,
+        }
+    }
+
     /// Returns statistics about the garbage collector.
     pub fn stats(&self) -> GcStatistics {
         self.stats.lock().map(|s| s.clone()).unwrap_or_default()
@@ -261,6 +355,7 @@ impl GarbageCollector {
     /// # Returns
     ///
     /// A `GcResult` containing counts of deleted items and any errors that occurred.
+    /// In dry-run mode the counts describe candidates and nothing is deleted.
     /// The operation succeeds even if individual collectors fail - errors are collected
     /// and returned in the result.
     ///
@@ -284,7 +379,11 @@ impl GarbageCollector {
     ///
     /// This method is safe to call concurrently from multiple tasks.
     pub async fn collect_once(&self) -> CollectorResult<GcResult> {
-        let mut result = GcResult::default();
+        let mut result = GcResult {
+            dry_run: self.config.dry_run,
+            ..GcResult::default()
+        };
+        let mut reports = serde_json::Map::new();
 
         for collector in &self.collectors {
             let collector_name = collector.name();
@@ -296,12 +395,28 @@ impl GarbageCollector {
 
             if should_run {
                 match collector.collect().await {
-                    Ok(count) => match collector_name {
-                        "orphaned_blob_collector" => result.orphaned_blobs_deleted = count,
-                        "stuck_upload_collector" => result.stuck_uploads_deleted = count,
-                        _ => result.total_deleted += count,
-                    },
+                    Ok(report) => {
+                        reports.insert(collector_name.to_string(), json!(report));
+                        match collector_name {
+                            "orphaned_blob_collector" => {
+                                result.orphaned_blobs_deleted = report.items;
+                                result.bytes_reclaimed = report.bytes;
+                                result.hot_blobs_deleted = report.hot_items;
+                                result.cold_blobs_deleted = report.cold_items;
+                                result.orphaned_blob_files_left = report.files_left;
+                                result.blob_deletion_failures = report.failures;
+                                result.blob_deletions_deferred = report.deferred;
+                                result.dead_lettered_blobs = report.dead_lettered;
+                            }
+                            "stuck_upload_collector" => result.stuck_uploads_deleted = report.items,
+                            _ => result.total_deleted += report.items,
+                        }
+                    }
                     Err(e) => {
+                        reports.insert(
+                            collector_name.to_string(),
+                            json!({ "error": e.to_string() }),
+                        );
                         result
                             .errors
                             .push(format!("{} collection failed: {}", collector_name, e));
@@ -311,9 +426,87 @@ impl GarbageCollector {
         }
 
         result.total_deleted = result.orphaned_blobs_deleted + result.stuck_uploads_deleted;
+        self.record_cycle(&result, reports).await;
         Ok(result)
     }
 
+    /// Store a cycle's results in the audit log, if one is configured
+    async fn record_cycle(
+        &self,
+        result: &GcResult,
+        reports: serde_json::Map<String, serde_json::Value>,
+    ) {
+        let Some(audit_repo) = &self.audit_repo else {
+            return;
+        };
+        if !result.has_deletions()
+            && result.errors.is_empty()
+            && result.blob_deletion_failures.is_empty()
+        {
+            return;
+        }
+
+        for blob in &result.dead_lettered_blobs {
+            let entry = AuditLogEntry {
+                timestamp: OffsetDateTime::now_utc(),
+                event_type: AuditEventType::GarbageCollection,
+                user_id: None,
+                tenant_id: None,
+                api_key_id: None,
+                ip_address: None,
+                user_agent: None,
+                method: "GC".to_string(),
+                path: "garbage_collector/dead_letter".to_string(),
+                query: None,
+                status_code: None,
+                response_time_ms: None,
+                error_message: Some(blob.error.clone()),
+                additional_data: Some(json!({
+                    "content_hash": blob.content_hash,
+                    "storage_class": blob.storage_class,
+                    "attempts": blob.attempts,
+                })),
+            };
+            if let Err(e) = audit_repo.store(entry).await {
+                error!(
+                    "Failed to store audit log for dead-lettered blob {}: {}",
+                    blob.content_hash, e
+                );
+            }
+        }
+
+        let entry = AuditLogEntry {
+            timestamp: OffsetDateTime::now_utc(),
+            event_type: AuditEventType::GarbageCollection,
+            user_id: None,
+            tenant_id: None,
+            api_key_id: None,
+            ip_address: None,
+            user_agent: None,
+            method: "GC".to_string(),
+            path: "garbage_collector".to_string(),
+            query: None,
+            status_code: None,
+            response_time_ms: None,
+            error_message: (!result.errors.is_empty()).then(|| result.errors.join("; ")),
+            additional_data: Some(json!({
+                "dry_run": result.dry_run,
+                "total_deleted": result.total_deleted,
+                "orphaned_blobs_deleted": result.orphaned_blobs_deleted,
+                "stuck_uploads_deleted": result.stuck_uploads_deleted,
+                "bytes_reclaimed": result.bytes_reclaimed,
+                "blob_deletion_failures": result.blob_deletion_failures,
+                "blob_deletions_deferred": result.blob_deletions_deferred,
+                "dead_lettered_blobs": result.dead_lettered_blobs.len(),
+                "collectors": reports,
+            })),
+        };
+
+        if let Err(e) = audit_repo.store(entry).await {
+            error!("Failed to store audit log for GC cycle: {}", e);
+        }
+    }
+
     /// Check if stuck upload cleanup should run
     fn should_run_stuck_upload_cleanup(&self) -> bool {
         self.stuck_upload_scheduler
@@ -328,10 +521,11 @@ mod tests {
     use super::*;
     use crate::application::gc::config::GcConfig;
     use crate::application::ports::{
-        BlobRepository, BlobStore, ObjectRepository, RepositoryError, StorageError,
+        AuditQueryFilter, AuditRepositoryError, BlobRepository, BlobStore, ObjectRepository,
+        RepositoryError, StorageError,
     };
     use crate::domain::entities::Blob;
-    use crate::domain::value_objects::{ContentHash, StorageClass};
+    use crate::domain::value_objects::{ContentHash, ContentHashPrefix, StorageClass, TenantId};
     use async_trait::async_trait;
     struct MockBlobRepository {
         blobs: std::sync::Mutex<Vec<Blob>>,
@@ -356,6 +550,15 @@ mod tests {
             unimplemented!("Not needed for GC worker tests")
         }
 
+        async fn reference_existing(
+            &self,
+            _content_hash: &ContentHash,
+            _storage_class: StorageClass,
+            _tenant_id: &TenantId,
+        ) -> Result<Option<Blob>, RepositoryError> {
+            unimplemented!("Not needed for GC worker tests")
+        }
+
         async fn increment_ref(&self, _content_hash: &ContentHash) -> Result<(), RepositoryError> {
             unimplemented!("Not needed for GC worker tests")
         }
@@ -380,6 +583,47 @@ mod tests {
             blobs.retain(|b| b.content_hash() != content_hash);
             Ok(())
         }
+
+        async fn move_storage_class(
+            &self,
+            _content_hash: &ContentHash,
+            _from: StorageClass,
+            _to: StorageClass,
+        ) -> Result<bool, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_known(
+            &self,
+            _content_hashes: &[ContentHash],
+        ) -> Result<Vec<ContentHash>, RepositoryError> {
+            unimplemented!("Not needed for GC worker tests")
+        }
+
+        async fn list_hashes(
+            &self,
+            _after: Option<ContentHash>,
+            _limit: i64,
+        ) -> Result<Vec<ContentHash>, RepositoryError> {
+            unimplemented!("Not needed for GC worker tests")
+        }
+
+        async fn list(
+            &self,
+            _after: Option<ContentHash>,
+            _orphaned_only: bool,
+            _limit: i64,
+        ) -> Result<Vec<Blob>, RepositoryError> {
+            unimplemented!("Not needed for GC worker tests")
+        }
+
+        async fn find_by_prefix(
+            &self,
+            _prefix: &ContentHashPrefix,
+            _limit: i64,
+        ) -> Result<Vec<Blob>, RepositoryError> {
+            unimplemented!("Not needed for GC worker tests")
+        }
     }
 
     struct MockBlobStore;
@@ -421,6 +665,54 @@ mod tests {
         async fn get_total_size(&self, _storage_class: StorageClass) -> Result<u64, StorageError> {
             Ok(0)
         }
+
+        async fn append_staged(
+            &self,
+            _upload_id: uuid::Uuid,
+            _offset: u64,
+            _reader: crate::application::ports::BlobReader,
+            _storage_class: StorageClass,
+        ) -> Result<u64, StorageError> {
+            unimplemented!()
+        }
+
+        async fn staged_len(
+            &self,
+            _upload_id: uuid::Uuid,
+            _storage_class: StorageClass,
+        ) -> Result<Option<u64>, StorageError> {
+            unimplemented!()
+        }
+
+        async fn read_staged(
+            &self,
+            _upload_id: uuid::Uuid,
+            _storage_class: StorageClass,
+        ) -> Result<crate::application::ports::BlobReader, StorageError> {
+            unimplemented!()
+        }
+
+        async fn discard_staged(
+            &self,
+            _upload_id: uuid::Uuid,
+            _storage_class: StorageClass,
+        ) -> Result<(), StorageError> {
+            unimplemented!()
+        }
+
+        async fn initiate_restore(
+            &self,
+            _content_hash: &ContentHash,
+        ) -> Result<crate::application::ports::RestoreJob, StorageError> {
+            unimplemented!()
+        }
+
+        async fn restore_status(
+            &self,
+            _content_hash: &ContentHash,
+        ) -> Result<crate::application::ports::RestoreStatus, StorageError> {
+            unimplemented!()
+        }
     }
 
     struct MockObjectRepository;
@@ -438,6 +730,13 @@ mod tests {
             unimplemented!()
         }
 
+        async fn find_writing(
+            &self,
+            _id: &crate::domain::value_objects::ObjectId,
+        ) -> Result<Option<crate::domain::entities::Object>, RepositoryError> {
+            unimplemented!()
+        }
+
         async fn save(
             &self,
             _object: &crate::domain::entities::Object,
@@ -445,6 +744,80 @@ mod tests {
             unimplemented!()
         }
 
+        async fn head(
+            &self,
+            _id: &crate::domain::value_objects::ObjectId,
+        ) -> Result<Option<crate::application::dto::ObjectHead>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_record(
+            &self,
+            _id: &crate::domain::value_objects::ObjectId,
+        ) -> Result<Option<crate::application::dto::ObjectRecord>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn status(
+            &self,
+            _id: &crate::domain::value_objects::ObjectId,
+        ) -> Result<
+            Option<(
+                crate::domain::value_objects::TenantId,
+                crate::domain::value_objects::ObjectStatus,
+            )>,
+            RepositoryError,
+        > {
+            unimplemented!()
+        }
+
+        async fn head_by_key(
+            &self,
+            _namespace: &crate::domain::value_objects::Namespace,
+            _tenant_id: &crate::domain::value_objects::TenantId,
+            _key: &str,
+        ) -> Result<Option<crate::application::dto::ObjectHead>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn replace_content_if_match(
+            &self,
+            _object: &crate::domain::entities::Object,
+            _expected: &crate::domain::value_objects::ContentHash,
+        ) -> Result<bool, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn update_metadata_if_match(
+            &self,
+            _object: &crate::domain::entities::Object,
+            _expected: &crate::domain::value_objects::ObjectMetadata,
+        ) -> Result<bool, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn update_retention(
+            &self,
+            _object: &crate::domain::entities::Object,
+        ) -> Result<bool, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn mark_deleting_if_unlocked(
+            &self,
+            _object: &crate::domain::entities::Object,
+        ) -> Result<bool, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn set_extracted_text(
+            &self,
+            _id: &crate::domain::value_objects::ObjectId,
+            _text: Option<String>,
+        ) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
         async fn delete(
             &self,
             _id: &crate::domain::value_objects::ObjectId,
@@ -465,15 +838,79 @@ mod tests {
             &self,
             _namespace: &crate::domain::value_objects::Namespace,
             _tenant_id: &crate::domain::value_objects::TenantId,
+            _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+            _metadata: &crate::application::metadata_query::MetadataQuery,
+            _sort_by: crate::application::dto::SortField,
+            _sort_direction: crate::application::dto::SortDirection,
+            _after: Option<crate::application::list_cursor::ListCursor>,
             _limit: i64,
             _offset: i64,
         ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
             unimplemented!()
         }
 
+        async fn list_projected(
+            &self,
+            _namespace: &crate::domain::value_objects::Namespace,
+            _tenant_id: &crate::domain::value_objects::TenantId,
+            _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+            _metadata: &crate::application::metadata_query::MetadataQuery,
+            _sort_by: crate::application::dto::SortField,
+            _sort_direction: crate::application::dto::SortDirection,
+            _fields: &[crate::application::dto::ObjectField],
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<crate::application::dto::ObjectProjection>, RepositoryError> {
+            unimplemented!()
+        }
+
+        fn stream(
+            &self,
+            _namespace: &crate::domain::value_objects::Namespace,
+            _tenant_id: &crate::domain::value_objects::TenantId,
+            _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+            _metadata: &crate::application::metadata_query::MetadataQuery,
+            _sort_by: crate::application::dto::SortField,
+            _sort_direction: crate::application::dto::SortDirection,
+        ) -> crate::application::ports::ObjectStream {
+            unimplemented!()
+        }
+
+        async fn common_prefixes(
+            &self,
+            _namespace: &crate::domain::value_objects::Namespace,
+            _tenant_id: &crate::domain::value_objects::TenantId,
+            _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+            _metadata: &crate::application::metadata_query::MetadataQuery,
+            _max: i64,
+        ) -> Result<Vec<String>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn count(
+            &self,
+            _namespace: &crate::domain::value_objects::Namespace,
+            _tenant_id: &crate::domain::value_objects::TenantId,
+            _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+            _metadata: &crate::application::metadata_query::MetadataQuery,
+            _max: i64,
+        ) -> Result<i64, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn namespaces(
+            &self,
+            _tenant_id: &crate::domain::value_objects::TenantId,
+        ) -> Result<Vec<crate::domain::value_objects::Namespace>, RepositoryError> {
+            unimplemented!()
+        }
+
         async fn search(
             &self,
             _request: &crate::application::dto::SearchRequest,
+            _keys: &crate::application::key_prefix_query::KeyPrefixQuery,
+            _metadata: &crate::application::metadata_query::MetadataQuery,
+            _after: Option<crate::application::list_cursor::ListCursor>,
         ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
             unimplemented!()
         }
@@ -481,7 +918,23 @@ mod tests {
         async fn text_search(
             &self,
             _request: &crate::application::dto::TextSearchRequest,
-        ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
+        ) -> Result<crate::application::dto::TextSearchPage, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn record_access(
+            &self,
+            _accesses: &[crate::application::dto::ObjectAccess],
+        ) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn move_storage_class(
+            &self,
+            _content_hash: &ContentHash,
+            _from: StorageClass,
+            _to: StorageClass,
+        ) -> Result<u64, RepositoryError> {
             unimplemented!()
         }
 
@@ -492,6 +945,21 @@ mod tests {
         ) -> Result<Vec<crate::domain::value_objects::ObjectId>, RepositoryError> {
             unimplemented!()
         }
+
+        async fn find_stuck_staged_objects(
+            &self,
+            _age_minutes: i64,
+            _limit: i64,
+        ) -> Result<Vec<crate::domain::entities::Object>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_locked_hashes(
+            &self,
+            _content_hashes: &[ContentHash],
+        ) -> Result<Vec<ContentHash>, RepositoryError> {
+            unimplemented!()
+        }
     }
 
     #[tokio::test]
@@ -526,6 +994,151 @@ mod tests {
         assert!(result.is_success());
     }
 
+    #[tokio::test]
+    async fn test_gc_concurrent_deletions_keep_storage_failures_counted() {
+        use crate::application::gc::collectors::test_utils::{
+            create_test_blob, MockBlobRepository, MockBlobStore,
+        };
+
+        let blobs = ["1", "2", "3", "4", "5"]
+            .iter()
+            .map(|c| create_test_blob(&c.repeat(64), 0))
+            .collect();
+        let repo = Arc::new(MockBlobRepository::new(blobs));
+        let store = Arc::new(MockBlobStore::failing());
+
+        let config =
+            GcConfig::new(Duration::from_secs(60), 100, 1).with_max_concurrent_deletions(2);
+        let gc = GarbageCollector::with_config(repo.clone(), store, None, config);
+
+        let result = gc.collect_once().await.unwrap();
+        assert_eq!(result.orphaned_blobs_deleted, 5);
+        assert_eq!(result.orphaned_blob_files_left, 5);
+        assert_eq!(repo.deleted_hashes.lock().unwrap().len(), 5);
+
+        gc.stats.lock().unwrap().update(&result);
+        assert_eq!(gc.stats().total_orphaned_blob_files_left, 5);
+    }
+
+    /// Audit repository that keeps stored entries in memory
+    #[derive(Default)]
+    struct RecordingAuditRepository {
+        entries: Mutex<Vec<AuditLogEntry>>,
+    }
+
+    #[async_trait]
+    impl AuditRepository for RecordingAuditRepository {
+        async fn store(&self, entry: AuditLogEntry) -> Result<(), AuditRepositoryError> {
+            self.entries.lock().unwrap().push(entry);
+            Ok(())
+        }
+
+        async fn query(
+            &self,
+            _filter: AuditQueryFilter,
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<AuditLogEntry>, AuditRepositoryError> {
+            Ok(self.entries.lock().unwrap().clone())
+        }
+
+        async fn count(&self, _filter: AuditQueryFilter) -> Result<i64, AuditRepositoryError> {
+            Ok(self.entries.lock().unwrap().len() as i64)
+        }
+
+        async fn cleanup_old_logs(
+            &self,
+            _retention_days: i32,
+        ) -> Result<i64, AuditRepositoryError> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gc_cycles_are_recorded_in_audit_log() {
+        use crate::application::gc::collectors::test_utils::{
+            create_test_blob, MockBlobRepository, MockBlobStore,
+        };
+
+        let blob = create_test_blob(&"e".repeat(64), 0);
+        let repo = Arc::new(MockBlobRepository::new(vec![blob]));
+        let store = Arc::new(MockBlobStore::failing());
+        let audit_repo = Arc::new(RecordingAuditRepository::default());
+
+        let gc = GarbageCollector::new(repo, store, Duration::from_secs(60), 100)
+            .with_audit_repo(audit_repo.clone());
+        gc.collect_once().await.unwrap();
+
+        let entries = audit_repo.entries.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].event_type.to_string(), "garbage_collection");
+        let data = entries[0].additional_data.as_ref().unwrap();
+        assert_eq!(data["orphaned_blobs_deleted"], 1);
+        let orphaned = &data["collectors"]["orphaned_blob_collector"];
+        assert_eq!(orphaned["files_left"], 1);
+        assert_eq!(orphaned["failures"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gc_dead_letters_blobs_whose_files_keep_failing() {
+        use crate::application::gc::collectors::test_utils::{
+            create_test_blob, InMemoryDeletionFailures, MockBlobRepository, MockBlobStore,
+        };
+        use crate::application::gc::DeletionRetryPolicy;
+
+        let blob = create_test_blob(&"7".repeat(64), 0);
+        let repo = Arc::new(MockBlobRepository::new(vec![blob]));
+        let store = Arc::new(MockBlobStore::flaky(usize::MAX));
+        let failures = Arc::new(InMemoryDeletionFailures::default());
+        let audit_repo = Arc::new(RecordingAuditRepository::default());
+
+        let config = GcConfig::new(Duration::from_secs(60), 100, 1).with_deletion_retries(
+            DeletionRetryPolicy::new(2, Duration::from_secs(60), Duration::from_secs(60)),
+        );
+        let gc = GarbageCollector::with_config(repo.clone(), store, None, config)
+            .with_deletion_failures(failures)
+            .with_audit_repo(audit_repo.clone());
+
+        let first = gc.collect_once().await.unwrap();
+        assert_eq!(first.orphaned_blobs_deleted, 0);
+        assert_eq!(first.blob_deletions_deferred, 1);
+        assert!(first.dead_lettered_blobs.is_empty());
+
+        let second = gc.collect_once().await.unwrap();
+        assert_eq!(second.blob_deletions_deferred, 0);
+        assert_eq!(second.dead_lettered_blobs.len(), 1);
+        assert_eq!(second.dead_lettered_blobs[0].attempts, 2);
+        assert!(repo.deleted_hashes.lock().unwrap().is_empty());
+
+        let entries = audit_repo.entries.lock().unwrap();
+        let dead_letters: Vec<_> = entries
+            .iter()
+            .filter(|e| e.path == "garbage_collector/dead_letter")
+            .collect();
+        assert_eq!(dead_letters.len(), 1);
+        let data = dead_letters[0].additional_data.as_ref().unwrap();
+        assert_eq!(data["content_hash"], "7".repeat(64));
+        assert_eq!(data["attempts"], 2);
+        assert!(dead_letters[0]
+            .error_message
+            .as_ref()
+            .unwrap()
+            .contains("backend timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_gc_idle_cycles_are_not_audited() {
+        let repo = Arc::new(MockBlobRepository::new(vec![]));
+        let store = Arc::new(MockBlobStore);
+        let audit_repo = Arc::new(RecordingAuditRepository::default());
+
+        let gc = GarbageCollector::new(repo, store, Duration::from_secs(60), 100)
+            .with_audit_repo(audit_repo.clone());
+        gc.collect_once().await.unwrap();
+
+        assert!(audit_repo.entries.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_gc_with_config() {
         let repo = Arc::new(MockBlobRepository::new(vec![]));
@@ -548,6 +1161,7 @@ mod tests {
             orphaned_blobs_deleted: 3,
             stuck_uploads_deleted: 2,
             errors: vec![],
+            ..Default::default()
         };
 
         assert!(result.is_success());
@@ -562,6 +1176,7 @@ mod tests {
             orphaned_blobs_deleted: 2,
             stuck_uploads_deleted: 0,
             errors: vec!["Test error".to_string()],
+            ..Default::default()
         };
 
         assert!(!result.is_success());
@@ -583,4 +1198,56 @@ mod tests {
         // Immediately after should not run
         assert!(!gc.should_run_stuck_upload_cleanup());
     }
+
+    #[tokio::test]
+    async fn test_gc_dry_run_deletes_nothing() {
+        use crate::application::gc::collectors::test_utils::{
+            create_test_blob, MockBlobRepository, MockBlobStore,
+        };
+
+        let blob = create_test_blob(&"e".repeat(64), 0);
+        let size_bytes = blob.size_bytes();
+
+        let repo = Arc::new(MockBlobRepository::new(vec![blob]));
+        let store = Arc::new(MockBlobStore::new());
+
+        let config = GcConfig::new(Duration::from_secs(60), 100, 1).with_dry_run(true);
+        let gc = GarbageCollector::with_config(repo.clone(), store.clone(), None, config);
+
+        let result = gc.collect_once().await.unwrap();
+        assert!(result.dry_run);
+        assert_eq!(result.orphaned_blobs_deleted, 1);
+        assert_eq!(result.hot_blobs_deleted, 1);
+        assert_eq!(result.bytes_reclaimed, size_bytes);
+        assert!(repo.deleted_hashes.lock().unwrap().is_empty());
+        assert!(store.deleted_files.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gc_collectors_can_be_disabled() {
+        use crate::application::gc::collectors::test_utils::{
+            create_test_blob, MockBlobRepository, MockBlobStore,
+            MockObjectRepository as StuckUploadRepository,
+        };
+
+        let blob = create_test_blob(&"f".repeat(64), 0);
+        let repo = Arc::new(MockBlobRepository::new(vec![blob]));
+        let store = Arc::new(MockBlobStore::new());
+        let object_repo = Arc::new(StuckUploadRepository::success(2));
+
+        // Only stuck upload cleanup
+        let config = GcConfig::new(Duration::from_secs(60), 100, 1).with_orphaned_blobs(false);
+        let gc = GarbageCollector::with_config(
+            repo.clone(),
+            store,
+            Some(object_repo.clone() as Arc<dyn ObjectRepository>),
+            config,
+        );
+
+        let result = gc.collect_once().await.unwrap();
+        assert_eq!(result.orphaned_blobs_deleted, 0);
+        assert_eq!(result.stuck_uploads_deleted, 2);
+        assert!(repo.deleted_hashes.lock().unwrap().is_empty());
+        assert_eq!(object_repo.cleanup_calls.lock().unwrap().len(), 1);
+    }
 }
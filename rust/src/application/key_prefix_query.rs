@@ -0,0 +1,145 @@
+//! Hierarchical key listing, in the style of S3's `ListObjectsV2`
+//!
+//! Keys are commonly organized like paths (`photos/2024/beach.jpg`). A
+//! listing can be narrowed to keys starting with a `prefix`, and a
+//! `delimiter` folds every key that has one after the prefix into a
+//! "common prefix" ending at that delimiter (`photos/2024/`), so each level
+//! of the hierarchy is listed like a folder.
+//!
+//! Prefixes are matched with `LIKE`, escaping `%`, `_` and `\` so they match
+//! literally. Objects without a key never match a prefix or a delimiter.
+
+/// Longest prefix accepted, matching the longest object key
+const MAX_PREFIX_CHARS: usize = 1024;
+
+/// Longest delimiter accepted
+const MAX_DELIMITER_CHARS: usize = 16;
+
+/// Most common prefixes returned by one listing
+pub const MAX_COMMON_PREFIXES: i64 = 1000;
+
+/// Validated key prefix and delimiter
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyPrefixQuery {
+    prefix: String,
+    delimiter: Option<String>,
+}
+
+impl KeyPrefixQuery {
+    /// Validate a key prefix and delimiter; an empty prefix matches every key
+    pub fn new(prefix: Option<String>, delimiter: Option<String>) -> Result<Self, String> {
+        let prefix = prefix.unwrap_or_default();
+        if prefix.chars().count() > MAX_PREFIX_CHARS {
+            return Err(format!(
+                "Key prefix must be at most {MAX_PREFIX_CHARS} characters"
+            ));
+        }
+        if prefix.contains('\0') {
+            return Err("Key prefix must not contain NUL characters".to_string());
+        }
+
+        let delimiter = match delimiter {
+            None => None,
+            Some(delimiter) if delimiter.is_empty() => {
+                return Err("Delimiter must not be empty".to_string())
+            }
+            Some(delimiter)
+                if delimiter.chars().count() > MAX_DELIMITER_CHARS || delimiter.contains('\0') =>
+            {
+                return Err(format!(
+                    "Delimiter must be at most {MAX_DELIMITER_CHARS} characters without NUL"
+                ))
+            }
+            Some(delimiter) => Some(delimiter),
+        };
+
+        Ok(Self { prefix, delimiter })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_empty() && self.delimiter.is_none()
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn delimiter(&self) -> Option<&str> {
+        self.delimiter.as_deref()
+    }
+
+    /// `LIKE` pattern matching keys that start with the prefix, using `\`
+    /// as the escape character
+    pub fn like_pattern(&self) -> String {
+        let mut pattern = String::with_capacity(self.prefix.len() + 1);
+        for c in self.prefix.chars() {
+            if matches!(c, '%' | '_' | '\\') {
+                pattern.push('\\');
+            }
+            pattern.push(c);
+        }
+        pattern.push('%');
+        pattern
+    }
+
+    /// 1-based character position in a key where the part after the prefix
+    /// starts, as taken by SQL `substr`
+    pub fn remainder_start(&self) -> i32 {
+        self.prefix.chars().count() as i32 + 1
+    }
+
+    /// Common prefix `key` is folded into, or `None` when it is listed as
+    /// an object
+    pub fn common_prefix<'k>(&self, key: &'k str) -> Option<&'k str> {
+        let delimiter = self.delimiter.as_deref()?;
+        let rest = key.strip_prefix(self.prefix.as_str())?;
+        rest.find(delimiter)
+            .map(|at| &key[..self.prefix.len() + at + delimiter.len()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(prefix: &str, delimiter: Option<&str>) -> KeyPrefixQuery {
+        KeyPrefixQuery::new(Some(prefix.to_string()), delimiter.map(str::to_string)).unwrap()
+    }
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(query("photos/", None).like_pattern(), "photos/%");
+        assert_eq!(
+            query("100%_done\\", None).like_pattern(),
+            "100\\%\\_done\\\\%"
+        );
+        assert_eq!(query("", None).like_pattern(), "%");
+    }
+
+    #[test]
+    fn test_common_prefix_stops_at_first_delimiter() {
+        let listing = query("photos/", Some("/"));
+
+        assert_eq!(
+            listing.common_prefix("photos/2024/beach.jpg"),
+            Some("photos/2024/")
+        );
+        assert_eq!(listing.common_prefix("photos/cover.jpg"), None);
+        assert_eq!(listing.common_prefix("videos/2024/clip.mp4"), None);
+        assert_eq!(query("photos/", None).common_prefix("photos/2024/a"), None);
+    }
+
+    #[test]
+    fn test_remainder_start_counts_characters() {
+        assert_eq!(query("", None).remainder_start(), 1);
+        assert_eq!(query("fotos/ü/", None).remainder_start(), 9);
+    }
+
+    #[test]
+    fn test_rejects_invalid_delimiters() {
+        assert!(KeyPrefixQuery::new(None, Some(String::new())).is_err());
+        assert!(KeyPrefixQuery::new(None, Some("/".repeat(17))).is_err());
+        assert!(KeyPrefixQuery::new(Some("a\0".to_string()), None).is_err());
+        assert!(KeyPrefixQuery::new(None, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Cursor pagination of object listings
+//!
+//! An offset page is found by skipping every object before it, so deep pages
+//! get slower and objects created or deleted between requests shift others
+//! across page boundaries. A cursor instead names the last object of a page
+//! by its sort value and ID, and the next page starts right after it; the ID
+//! breaks ties between equal sort values.
+//!
+//! Cursors are opaque to clients: URL-safe base64 of a small JSON document
+//! that also records the order it was issued for, so it cannot be replayed
+//! against another one.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::application::dto::{SortDirection, SortField};
+use crate::domain::entities::Object;
+use crate::domain::value_objects::ObjectId;
+
+/// Longest cursor accepted, well above any cursor this module issues
+const MAX_CURSOR_CHARS: usize = 2048;
+
+/// Sort value of the object a cursor points at
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CursorValue {
+    Time(#[serde(with = "time::serde::rfc3339")] OffsetDateTime),
+    Int(i64),
+    Text(String),
+}
+
+/// Position after the last object of a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListCursor {
+    pub sort_by: SortField,
+    pub sort_direction: SortDirection,
+    /// Sort value of the last object; `None` when it has none (no key)
+    pub value: Option<CursorValue>,
+    pub id: ObjectId,
+}
+
+impl ListCursor {
+    /// Whether listings in this order can be paged by cursor: the orders
+    /// offered by the `sort` parameter
+    pub fn supports(sort_by: SortField) -> bool {
+        matches!(
+            sort_by,
+            SortField::CreatedAt
+                | SortField::UpdatedAt
+                | SortField::SizeBytes
+                | SortField::Key
+                | SortField::DownloadCount
+        )
+    }
+
+    /// Cursor for the page after `object` in the given order
+    pub fn after(object: &Object, sort_by: SortField, sort_direction: SortDirection) -> Self {
+        Self {
+            sort_by,
+            sort_direction,
+            value: Self::sort_value(object, sort_by),
+            id: *object.id(),
+        }
+    }
+
+    /// Value `object` is sorted by
+    pub fn sort_value(object: &Object, sort_by: SortField) -> Option<CursorValue> {
+        match sort_by {
+            SortField::CreatedAt => Some(CursorValue::Time(object.created_at())),
+            SortField::UpdatedAt => Some(CursorValue::Time(object.updated_at())),
+            SortField::SizeBytes => object
+                .size_bytes()
+                .map(|size| CursorValue::Int(size as i64)),
+            SortField::Key => object.key().map(|key| CursorValue::Text(key.to_string())),
+            SortField::ContentType => object
+                .content_type()
+                .map(|content_type| CursorValue::Text(content_type.to_string())),
+            SortField::DownloadCount => Some(CursorValue::Int(object.download_count() as i64)),
+            SortField::LastAccessedAt => object.last_accessed_at().map(CursorValue::Time),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        // Serializing plain fields cannot fail
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, String> {
+        let invalid = || "Invalid cursor; use next_cursor from a previous page".to_string();
+        if cursor.len() > MAX_CURSOR_CHARS {
+            return Err(invalid());
+        }
+        let json = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let cursor: Self = serde_json::from_slice(&json).map_err(|_| invalid())?;
+
+        let expected = match cursor.sort_by {
+            SortField::CreatedAt | SortField::UpdatedAt | SortField::LastAccessedAt => "time",
+            SortField::SizeBytes | SortField::DownloadCount => "int",
+            SortField::Key | SortField::ContentType => "text",
+        };
+        let kind = match cursor.value {
+            None => expected,
+            Some(CursorValue::Time(_)) => "time",
+            Some(CursorValue::Int(_)) => "int",
+            Some(CursorValue::Text(_)) => "text",
+        };
+        if kind != expected {
+            return Err(invalid());
+        }
+        Ok(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Namespace, StorageClass, TenantId};
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn object(key: Option<&str>) -> Object {
+        Object::new(
+            Namespace::from_str("test").unwrap(),
+            TenantId::new(Uuid::new_v4()),
+            key.map(str::to_string),
+            StorageClass::Hot,
+        )
+    }
+
+    #[test]
+    fn test_cursor_round_trips() {
+        for sort_by in [
+            SortField::CreatedAt,
+            SortField::Key,
+            SortField::DownloadCount,
+        ] {
+            let cursor = ListCursor::after(&object(Some("a/b.txt")), sort_by, SortDirection::Asc);
+
+            assert_eq!(ListCursor::decode(&cursor.encode()), Ok(cursor));
+        }
+    }
+
+    #[test]
+    fn test_cursor_without_sort_value_round_trips() {
+        let cursor = ListCursor::after(&object(None), SortField::Key, SortDirection::Desc);
+
+        assert_eq!(cursor.value, None);
+        assert_eq!(ListCursor::decode(&cursor.encode()), Ok(cursor));
+    }
+
+    #[test]
+    fn test_malformed_cursors_are_rejected() {
+        assert!(ListCursor::decode("not a cursor").is_err());
+        assert!(ListCursor::decode(&URL_SAFE_NO_PAD.encode(b"{}")).is_err());
+
+        // A key value on a time order
+        let mut cursor = ListCursor::after(&object(None), SortField::Key, SortDirection::Asc);
+        cursor.sort_by = SortField::CreatedAt;
+        cursor.value = Some(CursorValue::Text("a".to_string()));
+        assert!(ListCursor::decode(&cursor.encode()).is_err());
+    }
+}
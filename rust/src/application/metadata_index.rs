@@ -0,0 +1,187 @@
+//! Full-text indexing of custom metadata fields
+//!
+//! Custom metadata lives in free-form tags, so only keys an operator
+//! designates per namespace are folded into the search vector. This keeps
+//! arbitrary (possibly sensitive) tag values out of text search unless
+//! explicitly opted in.
+
+use std::collections::HashMap;
+
+use crate::domain::value_objects::ObjectMetadata;
+
+/// Namespace entry that applies to every namespace
+pub const ALL_NAMESPACES: &str = "*";
+
+/// Which custom metadata keys are full-text indexed, per namespace
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataIndexConfig {
+    keys_by_namespace: HashMap<String, Vec<String>>,
+}
+
+impl MetadataIndexConfig {
+    /// Parse a specification like `models=author,license;docs=title;*=project`
+    ///
+    /// Each `;`-separated entry maps a namespace (or `*` for all
+    /// namespaces) to a comma-separated list of tag keys.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut keys_by_namespace: HashMap<String, Vec<String>> = HashMap::new();
+
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (namespace, keys) = entry.split_once('=').ok_or_else(|| {
+                format!("Invalid metadata index entry '{entry}': expected namespace=key1,key2")
+            })?;
+
+            let namespace = namespace.trim().to_lowercase();
+            if namespace.is_empty() {
+                return Err(format!(
+                    "Invalid metadata index entry '{entry}': empty namespace"
+                ));
+            }
+
+            let keys: Vec<String> = keys
+                .split(',')
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(str::to_string)
+                .collect();
+            if keys.is_empty() {
+                return Err(format!("Invalid metadata index entry '{entry}': no keys"));
+            }
+
+            keys_by_namespace.entry(namespace).or_default().extend(keys);
+        }
+
+        Ok(Self { keys_by_namespace })
+    }
+
+    /// Designate `keys` as indexed for `namespace`
+    pub fn with_keys(mut self, namespace: &str, keys: &[&str]) -> Self {
+        self.keys_by_namespace
+            .entry(namespace.to_lowercase())
+            .or_default()
+            .extend(keys.iter().map(|k| k.to_string()));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys_by_namespace.is_empty()
+    }
+
+    /// Keys indexed for a namespace, including those configured for all namespaces
+    pub fn indexed_keys(&self, namespace: &str) -> Vec<&str> {
+        let mut keys: Vec<&str> = [namespace, ALL_NAMESPACES]
+            .iter()
+            .filter_map(|ns| self.keys_by_namespace.get(*ns))
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Text to feed into the search vector for an object's custom metadata
+    ///
+    /// String, number and boolean values are included; arrays contribute
+    /// their scalar elements and nested objects are skipped.
+    pub fn searchable_text(&self, namespace: &str, metadata: &ObjectMetadata) -> String {
+        let mut parts = Vec::new();
+
+        for key in self.indexed_keys(namespace) {
+            match metadata.tags.get(key) {
+                Some(serde_json::Value::Array(items)) => {
+                    parts.extend(items.iter().filter_map(scalar_text));
+                }
+                Some(value) => parts.extend(scalar_text(value)),
+                None => {}
+            }
+        }
+
+        parts.join(" ")
+    }
+}
+
+fn scalar_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata_with_tags(tags: serde_json::Value) -> ObjectMetadata {
+        let mut metadata = ObjectMetadata::default();
+        if let serde_json::Value::Object(map) = tags {
+            metadata.tags = map.into_iter().collect();
+        }
+        metadata
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let config = MetadataIndexConfig::parse("models=author, license ; docs=title").unwrap();
+
+        assert_eq!(config.indexed_keys("models"), vec!["author", "license"]);
+        assert_eq!(config.indexed_keys("docs"), vec!["title"]);
+        assert!(config.indexed_keys("other").is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_entries() {
+        assert!(MetadataIndexConfig::parse("models").is_err());
+        assert!(MetadataIndexConfig::parse("=author").is_err());
+        assert!(MetadataIndexConfig::parse("models=").is_err());
+    }
+
+    #[test]
+    fn test_parse_empty_spec() {
+        assert!(MetadataIndexConfig::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_applies_to_all_namespaces() {
+        let config = MetadataIndexConfig::parse("*=project;models=author").unwrap();
+
+        assert_eq!(config.indexed_keys("models"), vec!["author", "project"]);
+        assert_eq!(config.indexed_keys("docs"), vec!["project"]);
+    }
+
+    #[test]
+    fn test_searchable_text_includes_only_indexed_keys() {
+        let config = MetadataIndexConfig::default().with_keys("models", &["author"]);
+        let metadata = metadata_with_tags(json!({
+            "author": "zephyrine",
+            "secret": "hunter2",
+        }));
+
+        let text = config.searchable_text("models", &metadata);
+
+        assert!(text.contains("zephyrine"));
+        assert!(!text.contains("hunter2"));
+        assert_eq!(config.searchable_text("docs", &metadata), "");
+    }
+
+    #[test]
+    fn test_searchable_text_flattens_scalars_and_arrays() {
+        let config =
+            MetadataIndexConfig::default().with_keys("models", &["labels", "epochs", "nested"]);
+        let metadata = metadata_with_tags(json!({
+            "labels": ["vision", "beta", {"skip": true}],
+            "epochs": 12,
+            "nested": {"ignored": "value"},
+        }));
+
+        let text = config.searchable_text("models", &metadata);
+
+        assert!(text.contains("vision"));
+        assert!(text.contains("beta"));
+        assert!(text.contains("12"));
+        assert!(!text.contains("ignored"));
+    }
+}
@@ -0,0 +1,334 @@
+//! Structured queries inside object metadata
+//!
+//! Filters address the stored metadata document (see `ObjectMetadata`), so
+//! custom tags are reached as `tags.<name>`. Two operators are supported:
+//!
+//! - containment: the metadata contains a JSON document (`metadata @> ...`),
+//!   e.g. `{"tags": {"author": "jane"}}`
+//! - key existence: a dotted path is present (`metadata @? '$."tags"."author"'`)
+//!
+//! Both are served by the `jsonb_path_ops` GIN index on `objects.metadata`.
+//! Path segments are limited to ASCII letters, digits, `_` and `-`, and
+//! documents and paths are bound as parameters, never spliced into SQL.
+
+use serde_json::{Map, Value};
+
+/// Query parameter prefix for containment filters (`metadata.tags.author=jane`)
+pub const CONTAINS_PARAM_PREFIX: &str = "metadata.";
+
+/// Query parameter naming a path that must exist (`metadata_has=tags.author`)
+pub const HAS_KEY_PARAM: &str = "metadata_has";
+
+/// Deepest path or document nesting accepted
+const MAX_DEPTH: usize = 8;
+
+/// Longest path segment accepted
+const MAX_SEGMENT_LEN: usize = 64;
+
+/// Most key existence paths per query
+const MAX_KEY_PATHS: usize = 16;
+
+/// Largest containment document accepted, serialized
+const MAX_DOCUMENT_BYTES: usize = 4096;
+
+/// Validated metadata filters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetadataQuery {
+    contains: Option<Value>,
+    key_paths: Vec<Vec<String>>,
+}
+
+impl MetadataQuery {
+    /// Validate a containment document and dotted key existence paths
+    ///
+    /// The document must be a JSON object; an empty one filters nothing.
+    pub fn new(contains: Option<Value>, has_keys: &[String]) -> Result<Self, String> {
+        let contains = match contains {
+            None | Some(Value::Null) => None,
+            Some(Value::Object(map)) if map.is_empty() => None,
+            Some(document @ Value::Object(_)) => {
+                check_document(&document, 1)?;
+                if document.to_string().len() > MAX_DOCUMENT_BYTES {
+                    return Err(format!(
+                        "Metadata filter must be at most {MAX_DOCUMENT_BYTES} bytes"
+                    ));
+                }
+                Some(document)
+            }
+            Some(_) => return Err("Metadata filter must be a JSON object".to_string()),
+        };
+
+        if has_keys.len() > MAX_KEY_PATHS {
+            return Err(format!(
+                "At most {MAX_KEY_PATHS} metadata key paths are allowed"
+            ));
+        }
+        let key_paths = has_keys
+            .iter()
+            .map(|path| parse_path(path))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            contains,
+            key_paths,
+        })
+    }
+
+    /// Build from query parameters: `metadata.<path>=<value>` requires the
+    /// string value at `path`, `metadata_has=<path>` requires `path` to exist
+    ///
+    /// Other parameters are ignored.
+    pub fn from_params<'a>(
+        params: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, String> {
+        let mut document = Map::new();
+        let mut has_keys = Vec::new();
+
+        for (name, value) in params {
+            if name == HAS_KEY_PARAM {
+                has_keys.push(value.to_string());
+            } else if let Some(path) = name.strip_prefix(CONTAINS_PARAM_PREFIX) {
+                insert_at_path(&mut document, &parse_path(path)?, value)?;
+            }
+        }
+
+        Self::new(Some(Value::Object(document)), &has_keys)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contains.is_none() && self.key_paths.is_empty()
+    }
+
+    /// Document the metadata must contain
+    pub fn contains(&self) -> Option<&Value> {
+        self.contains.as_ref()
+    }
+
+    /// Key existence paths as dotted strings, as accepted by [`Self::new`]
+    pub fn has_keys(&self) -> Vec<String> {
+        self.key_paths.iter().map(|path| path.join(".")).collect()
+    }
+
+    /// Key existence paths as SQL/JSON path expressions
+    pub fn jsonpaths(&self) -> Vec<String> {
+        self.key_paths
+            .iter()
+            .map(|path| {
+                let segments: String = path.iter().map(|s| format!(".\"{s}\"")).collect();
+                format!("${segments}")
+            })
+            .collect()
+    }
+
+    /// Whether a metadata document passes the filters, for stores that
+    /// filter in memory
+    ///
+    /// Follows the semantics of the Postgres operators: containment is
+    /// recursive and array elements may match in any order, and arrays along
+    /// a key path are searched element by element.
+    pub fn matches(&self, metadata: &Value) -> bool {
+        self.contains
+            .as_ref()
+            .is_none_or(|document| contains(metadata, document))
+            && self.key_paths.iter().all(|path| has_path(metadata, path))
+    }
+}
+
+/// `haystack @> needle`
+fn contains(haystack: &Value, needle: &Value) -> bool {
+    match (haystack, needle) {
+        (Value::Object(haystack), Value::Object(needle)) => needle.iter().all(|(key, value)| {
+            haystack
+                .get(key)
+                .is_some_and(|found| contains(found, value))
+        }),
+        (Value::Array(haystack), Value::Array(needle)) => needle
+            .iter()
+            .all(|value| haystack.iter().any(|found| contains(found, value))),
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => haystack == needle,
+    }
+}
+
+/// Whether `path` leads to a value in `value`, looking into arrays
+fn has_path(value: &Value, path: &[String]) -> bool {
+    let Some((segment, rest)) = path.split_first() else {
+        return true;
+    };
+    match value {
+        Value::Object(map) => map.get(segment).is_some_and(|child| has_path(child, rest)),
+        Value::Array(items) => items.iter().any(|item| has_path(item, path)),
+        _ => false,
+    }
+}
+
+/// Split a dotted path, rejecting empty, overlong or unsafe segments
+fn parse_path(path: &str) -> Result<Vec<String>, String> {
+    let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+    if segments.len() > MAX_DEPTH {
+        return Err(format!(
+            "Metadata path '{path}' is deeper than {MAX_DEPTH} levels"
+        ));
+    }
+    if let Some(segment) = segments.iter().find(|s| !is_valid_segment(s)) {
+        return Err(format!(
+            "Invalid metadata path '{path}': segment '{segment}' must be 1-{MAX_SEGMENT_LEN} letters, digits, '_' or '-'"
+        ));
+    }
+    Ok(segments)
+}
+
+fn is_valid_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment.len() <= MAX_SEGMENT_LEN
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Reject documents nested deeper than `MAX_DEPTH`
+fn check_document(value: &Value, depth: usize) -> Result<(), String> {
+    let children: Vec<&Value> = match value {
+        Value::Object(map) => map.values().collect(),
+        Value::Array(items) => items.iter().collect(),
+        _ => return Ok(()),
+    };
+    if depth > MAX_DEPTH {
+        return Err(format!(
+            "Metadata filter is nested deeper than {MAX_DEPTH} levels"
+        ));
+    }
+    children
+        .into_iter()
+        .try_for_each(|child| check_document(child, depth + 1))
+}
+
+/// Set `value` at `path` in `document`, creating intermediate objects
+fn insert_at_path(
+    document: &mut Map<String, Value>,
+    path: &[String],
+    value: &str,
+) -> Result<(), String> {
+    let conflict = || {
+        format!(
+            "Metadata filter '{}' conflicts with another filter",
+            path.join(".")
+        )
+    };
+    let (last, parents) = path.split_last().ok_or_else(conflict)?;
+
+    let mut current = document;
+    for segment in parents {
+        current = match current
+            .entry(segment.clone())
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            Value::Object(map) => map,
+            _ => return Err(conflict()),
+        };
+    }
+    if current.contains_key(last) {
+        return Err(conflict());
+    }
+    current.insert(last.clone(), Value::String(value.to_string()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_params_nests_paths() {
+        let query = MetadataQuery::from_params([
+            ("metadata.tags.author", "jane"),
+            ("metadata.tags.license", "mit"),
+            ("metadata.kind", "model"),
+            ("metadata_has", "model.quantization"),
+            ("namespace", "models"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            query.contains(),
+            Some(&json!({"tags": {"author": "jane", "license": "mit"}, "kind": "model"}))
+        );
+        assert_eq!(query.jsonpaths(), vec![r#"$."model"."quantization""#]);
+    }
+
+    #[test]
+    fn test_from_params_without_filters_is_empty() {
+        let query = MetadataQuery::from_params([("namespace", "models")]).unwrap();
+
+        assert!(query.is_empty());
+    }
+
+    #[test]
+    fn test_matches_in_memory() {
+        let metadata = json!({
+            "kind": "model",
+            "tags": {"author": "jane", "langs": ["en", "de"], "stars": 5},
+            "model": null
+        });
+        let query = |contains: serde_json::Value, has_keys: &[&str]| {
+            let has_keys: Vec<String> = has_keys.iter().map(|k| k.to_string()).collect();
+            MetadataQuery::new(Some(contains), &has_keys).unwrap()
+        };
+
+        assert!(MetadataQuery::default().matches(&metadata));
+        assert!(query(json!({"tags": {"author": "jane"}}), &["kind"]).matches(&metadata));
+        assert!(query(json!({"tags": {"langs": ["de"], "stars": 5.0}}), &[]).matches(&metadata));
+        assert!(query(json!({}), &["model"]).matches(&metadata));
+        assert!(!query(json!({"tags": {"author": "joe"}}), &[]).matches(&metadata));
+        assert!(!query(json!({}), &["tags.license"]).matches(&metadata));
+    }
+
+    #[test]
+    fn test_rejects_unsafe_paths() {
+        for path in [
+            "tags.",
+            "tags..author",
+            "tags.author'",
+            "tags.a\"b",
+            "$.tags",
+            "a b",
+        ] {
+            assert!(
+                MetadataQuery::new(None, &[path.to_string()]).is_err(),
+                "{path} should be rejected"
+            );
+        }
+        assert!(MetadataQuery::from_params([("metadata.tags.x') OR 1=1 --", "jane")]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_conflicting_params() {
+        let result = MetadataQuery::from_params([
+            ("metadata.tags", "jane"),
+            ("metadata.tags.author", "jane"),
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_object_and_deep_documents() {
+        assert!(MetadataQuery::new(Some(json!(["author"])), &[]).is_err());
+
+        let mut deep = json!("jane");
+        for _ in 0..MAX_DEPTH {
+            deep = json!({ "a": deep });
+        }
+        assert!(MetadataQuery::new(Some(deep.clone()), &[]).is_ok());
+        assert!(MetadataQuery::new(Some(json!({ "a": deep })), &[]).is_err());
+    }
+
+    #[test]
+    fn test_empty_document_filters_nothing() {
+        let query = MetadataQuery::new(Some(json!({})), &[]).unwrap();
+
+        assert!(query.is_empty());
+    }
+}
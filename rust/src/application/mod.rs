@@ -1,7 +1,22 @@
+pub mod access_stats;
+pub mod blob_routing;
 pub mod builder;
+pub mod content_encoding;
+pub mod content_policy;
 pub mod dto;
 pub mod errors;
 pub mod gc;
+pub mod key_prefix_query;
+pub mod list_cursor;
+pub mod metadata_index;
+pub mod metadata_query;
+pub mod operation_progress;
+pub mod pagination;
 pub mod ports;
+pub mod read_verification;
+pub mod scrub;
+pub mod short_hashes;
+pub mod status_watch;
 pub mod use_cases;
 pub mod validation;
+pub mod webhooks;
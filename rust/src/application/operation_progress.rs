@@ -0,0 +1,390 @@
+//! Progress of long-running operations
+//!
+//! A client names an operation by sending an operation ID with the request
+//! that starts it (an archive import, a namespace delete) and follows it
+//! from another connection. The operation reports its progress and the
+//! outcome of each item as it goes, and followers are woken on every change
+//! instead of polling.
+//!
+//! Like [`StatusWatch`](crate::application::status_watch::StatusWatch),
+//! progress is in-process: an operation can only be followed on the
+//! instance that runs it.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::application::dto::{OperationItem, OperationProgressEvent, OperationState};
+use crate::domain::value_objects::TenantId;
+
+/// How long a finished operation stays around for late followers
+const FINISHED_RETENTION: Duration = Duration::from_secs(60);
+
+/// Current state of one operation
+#[derive(Debug, Clone)]
+pub struct OperationSnapshot {
+    pub kind: Option<&'static str>,
+    /// Tenant the operation works on; only it and admins may follow it
+    pub owner: Option<TenantId>,
+    pub state: OperationState,
+    pub done: u64,
+    pub total: Option<u64>,
+    pub items: Vec<OperationItem>,
+    pub error: Option<String>,
+}
+
+impl Default for OperationSnapshot {
+    fn default() -> Self {
+        Self {
+            kind: None,
+            owner: None,
+            state: OperationState::Pending,
+            done: 0,
+            total: None,
+            items: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl OperationSnapshot {
+    /// `progress` event for the snapshot of operation `id`
+    pub fn event(&self, id: &Uuid) -> OperationProgressEvent {
+        let percent = match self.total {
+            _ if self.state == OperationState::Completed => Some(100.0),
+            Some(total) if total > 0 => Some((self.done.min(total) as f64 / total as f64) * 100.0),
+            _ => None,
+        };
+
+        OperationProgressEvent {
+            id: id.to_string(),
+            kind: self.kind.map(str::to_string),
+            state: self.state,
+            done: self.done,
+            total: self.total,
+            percent,
+            items: self.items.len(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+type OperationSender = Arc<watch::Sender<OperationSnapshot>>;
+
+/// Running and recently finished operations, keyed by operation ID
+#[derive(Default)]
+pub struct OperationProgress {
+    senders: DashMap<Uuid, OperationSender>,
+}
+
+impl OperationProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follow operation `id`, which may not have started yet
+    pub fn subscribe(self: &Arc<Self>, id: Uuid) -> OperationSubscription {
+        // Subscribe while holding the entry, so a departing follower cannot
+        // remove the channel in between
+        let entry = self
+            .senders
+            .entry(id)
+            .or_insert_with(|| Arc::new(watch::channel(OperationSnapshot::default()).0));
+        let sender = Arc::clone(entry.value());
+        let receiver = sender.subscribe();
+        drop(entry);
+
+        OperationSubscription {
+            progress: Arc::clone(self),
+            id,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Start reporting operation `id` of `kind` on behalf of `owner`
+    ///
+    /// Fails when the ID belongs to an operation that already started.
+    pub fn start(
+        self: &Arc<Self>,
+        id: Uuid,
+        kind: &'static str,
+        owner: TenantId,
+    ) -> Result<OperationReporter, String> {
+        let entry = self
+            .senders
+            .entry(id)
+            .or_insert_with(|| Arc::new(watch::channel(OperationSnapshot::default()).0));
+        let sender = Arc::clone(entry.value());
+        let started = sender.send_if_modified(|snapshot| {
+            if snapshot.state != OperationState::Pending {
+                return false;
+            }
+            snapshot.kind = Some(kind);
+            snapshot.owner = Some(owner);
+            snapshot.state = OperationState::Running;
+            true
+        });
+        drop(entry);
+
+        if !started {
+            return Err(format!("Operation {id} is already in use"));
+        }
+
+        Ok(OperationReporter {
+            registration: Some((Arc::clone(self), id)),
+            sender,
+        })
+    }
+
+    /// Number of operations being followed or reported
+    pub fn tracked(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Forget a finished operation once late followers had their chance
+    fn retire(self: &Arc<Self>, id: Uuid, sender: OperationSender) {
+        let progress = Arc::clone(self);
+        let remove = move || {
+            progress
+                .senders
+                .remove_if(&id, |_, current| Arc::ptr_eq(current, &sender));
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    tokio::time::sleep(FINISHED_RETENTION).await;
+                    remove();
+                });
+            }
+            Err(_) => remove(),
+        }
+    }
+}
+
+/// Reports the progress of one operation to its followers
+///
+/// Dropping a reporter that neither finished nor failed marks the operation
+/// failed, so followers are not left waiting on an aborted request.
+pub struct OperationReporter {
+    registration: Option<(Arc<OperationProgress>, Uuid)>,
+    sender: OperationSender,
+}
+
+impl OperationReporter {
+    /// A reporter nobody follows, for operations started without an ID
+    pub fn detached() -> Self {
+        Self {
+            registration: None,
+            sender: Arc::new(watch::channel(OperationSnapshot::default()).0),
+        }
+    }
+
+    /// Set the total work, in the unit of [`Self::advance`]
+    pub fn set_total(&self, total: u64) {
+        self.sender
+            .send_modify(|snapshot| snapshot.total = Some(total));
+    }
+
+    /// Count `amount` more work as done
+    pub fn advance(&self, amount: u64) {
+        if amount > 0 {
+            self.sender
+                .send_modify(|snapshot| snapshot.done = snapshot.done.saturating_add(amount));
+        }
+    }
+
+    /// Report the outcome of one item
+    pub fn item(&self, item: OperationItem) {
+        // Nobody can follow a detached reporter; don't keep its items
+        if self.registration.is_some() {
+            self.sender
+                .send_modify(|snapshot| snapshot.items.push(item));
+        }
+    }
+
+    /// The operation succeeded
+    pub fn finish(self) {
+        self.end(OperationState::Completed, None);
+    }
+
+    /// The operation stopped before all the work was done, for `reason`
+    pub fn stop_partial(self, reason: String) {
+        self.end(OperationState::Partial, Some(reason));
+    }
+
+    /// The operation failed with `error`
+    pub fn fail(self, error: String) {
+        self.end(OperationState::Failed, Some(error));
+    }
+
+    fn end(&self, state: OperationState, error: Option<String>) {
+        self.sender.send_if_modified(|snapshot| {
+            if snapshot.state.is_finished() {
+                return false;
+            }
+            snapshot.state = state;
+            snapshot.error = error;
+            true
+        });
+    }
+}
+
+impl Drop for OperationReporter {
+    fn drop(&mut self) {
+        self.end(
+            OperationState::Failed,
+            Some("Operation ended unexpectedly".to_string()),
+        );
+        if let Some((progress, id)) = self.registration.take() {
+            progress.retire(id, Arc::clone(&self.sender));
+        }
+    }
+}
+
+/// A follower of one operation
+pub struct OperationSubscription {
+    progress: Arc<OperationProgress>,
+    id: Uuid,
+    sender: OperationSender,
+    receiver: watch::Receiver<OperationSnapshot>,
+}
+
+impl OperationSubscription {
+    /// The latest state, marking it seen
+    pub fn current(&mut self) -> watch::Ref<'_, OperationSnapshot> {
+        self.receiver.borrow_and_update()
+    }
+
+    /// Wait for a state newer than the last [`Self::current`]
+    pub async fn changed(&mut self) {
+        // The subscription holds a sender, so the channel never closes
+        let _ = self.receiver.changed().await;
+    }
+}
+
+impl Drop for OperationSubscription {
+    fn drop(&mut self) {
+        // The last follower of an operation that never started removes it;
+        // started operations are retired by their reporter
+        self.progress.senders.remove_if(&self.id, |_, sender| {
+            Arc::ptr_eq(sender, &self.sender)
+                && sender.receiver_count() <= 1
+                && sender.borrow().state == OperationState::Pending
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner() -> TenantId {
+        TenantId::from_string(&Uuid::new_v4().to_string()).unwrap()
+    }
+
+    fn item(name: &str) -> OperationItem {
+        OperationItem {
+            name: name.to_string(),
+            status: "created".to_string(),
+            detail: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follower_sees_progress_and_items() {
+        let progress = Arc::new(OperationProgress::new());
+        let id = Uuid::new_v4();
+        let mut subscription = progress.subscribe(id);
+        assert_eq!(subscription.current().state, OperationState::Pending);
+
+        let reporter = progress.start(id, "bulk_upload", owner()).unwrap();
+        reporter.set_total(200);
+        reporter.advance(50);
+        reporter.item(item("a.txt"));
+
+        subscription.changed().await;
+        let event = subscription.current().event(&id);
+        assert_eq!(event.state, OperationState::Running);
+        assert_eq!(event.kind.as_deref(), Some("bulk_upload"));
+        assert_eq!(event.percent, Some(25.0));
+        assert_eq!(event.items, 1);
+
+        reporter.finish();
+        subscription.changed().await;
+        let event = subscription.current().event(&id);
+        assert_eq!(event.state, OperationState::Completed);
+        assert_eq!(event.percent, Some(100.0));
+    }
+
+    #[tokio::test]
+    async fn test_partial_operation_finishes_with_reason() {
+        let progress = Arc::new(OperationProgress::new());
+        let id = Uuid::new_v4();
+        let mut subscription = progress.subscribe(id);
+
+        let reporter = progress.start(id, "namespace_delete", owner()).unwrap();
+        reporter.set_total(200);
+        reporter.advance(50);
+        reporter.stop_partial("Batch budget exhausted".to_string());
+
+        let event = subscription.current().event(&id);
+        assert_eq!(event.state, OperationState::Partial);
+        assert!(event.state.is_finished());
+        assert_eq!(event.percent, Some(25.0));
+        assert_eq!(event.error.as_deref(), Some("Batch budget exhausted"));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_reporter_fails_operation() {
+        let progress = Arc::new(OperationProgress::new());
+        let id = Uuid::new_v4();
+        let mut subscription = progress.subscribe(id);
+
+        drop(progress.start(id, "namespace_delete", owner()).unwrap());
+
+        let snapshot = subscription.current();
+        assert_eq!(snapshot.state, OperationState::Failed);
+        assert!(snapshot.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_operation_id_cannot_be_reused() {
+        let progress = Arc::new(OperationProgress::new());
+        let id = Uuid::new_v4();
+
+        let _reporter = progress.start(id, "bulk_upload", owner()).unwrap();
+
+        assert!(progress.start(id, "bulk_upload", owner()).is_err());
+    }
+
+    #[test]
+    fn test_last_follower_of_pending_operation_removes_it() {
+        let progress = Arc::new(OperationProgress::new());
+        let id = Uuid::new_v4();
+
+        let first = progress.subscribe(id);
+        let second = progress.subscribe(id);
+        assert_eq!(progress.tracked(), 1);
+
+        drop(first);
+        assert_eq!(progress.tracked(), 1);
+        drop(second);
+        assert_eq!(progress.tracked(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_follower_leaving_keeps_running_operation() {
+        let progress = Arc::new(OperationProgress::new());
+        let id = Uuid::new_v4();
+        let subscription = progress.subscribe(id);
+        let _reporter = progress.start(id, "bulk_upload", owner()).unwrap();
+
+        drop(subscription);
+
+        assert_eq!(progress.tracked(), 1);
+    }
+}
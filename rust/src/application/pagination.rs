@@ -0,0 +1,102 @@
+//! Limit and offset handling shared by every paged listing
+//!
+//! Object listings, searches, text searches, API key listings and audit log
+//! queries all resolve their page here, so out-of-range values behave the
+//! same everywhere: a missing limit takes the listing's default, a limit
+//! outside `1..=max` is clamped into it, and a missing or negative offset
+//! starts from the first result.
+
+/// Default and largest page size of a kind of listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageLimits {
+    pub default: i64,
+    pub max: i64,
+}
+
+impl PageLimits {
+    /// Object listings, searches and text searches
+    pub const OBJECTS: Self = Self {
+        default: 100,
+        max: 1000,
+    };
+
+    /// API keys of a tenant
+    pub const API_KEYS: Self = Self {
+        default: 50,
+        max: 100,
+    };
+
+    /// Audit log entries
+    pub const AUDIT_LOGS: Self = Self {
+        default: 100,
+        max: 1000,
+    };
+
+    /// Requested page size, clamped to `1..=max`
+    pub fn limit(self, requested: Option<i64>) -> i64 {
+        requested.unwrap_or(self.default).clamp(1, self.max)
+    }
+}
+
+/// A resolved page of a listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Pagination {
+    /// Resolve a requested limit and offset against a listing's limits
+    pub fn new(limit: Option<i64>, offset: Option<i64>, limits: PageLimits) -> Self {
+        Self {
+            limit: limits.limit(limit),
+            offset: offset.unwrap_or(0).max(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_values_take_defaults() {
+        assert_eq!(
+            Pagination::new(None, None, PageLimits::OBJECTS),
+            Pagination {
+                limit: 100,
+                offset: 0
+            }
+        );
+        assert_eq!(Pagination::new(None, None, PageLimits::API_KEYS).limit, 50);
+    }
+
+    #[test]
+    fn test_out_of_range_limits_are_clamped() {
+        for limits in [
+            PageLimits::OBJECTS,
+            PageLimits::API_KEYS,
+            PageLimits::AUDIT_LOGS,
+        ] {
+            assert_eq!(limits.limit(Some(0)), 1);
+            assert_eq!(limits.limit(Some(-5)), 1);
+            assert_eq!(limits.limit(Some(limits.max + 1)), limits.max);
+            assert_eq!(limits.limit(Some(i64::MAX)), limits.max);
+            assert_eq!(limits.limit(Some(7)), 7);
+        }
+    }
+
+    #[test]
+    fn test_negative_offsets_start_at_zero() {
+        for offset in [-1, i64::MIN] {
+            assert_eq!(
+                Pagination::new(Some(10), Some(offset), PageLimits::OBJECTS).offset,
+                0
+            );
+        }
+        assert_eq!(
+            Pagination::new(Some(10), Some(30), PageLimits::OBJECTS).offset,
+            30
+        );
+    }
+}
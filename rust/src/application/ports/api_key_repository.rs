@@ -15,6 +15,8 @@ pub enum ApiKeyRepositoryError {
     Serialization(#[from] serde_json::Error),
     #[error("Validation error: {0}")]
     Validation(String),
+    #[error("Internal error: {0}")]
+    Internal(String),
 }
 
 /// API key repository interface
@@ -26,7 +28,11 @@ pub trait ApiKeyRepository: Send + Sync {
     /// Find API key by ID
     async fn find_by_id(&self, id: &ApiKeyId) -> Result<Option<ApiKey>, ApiKeyRepositoryError>;
 
-    /// Find API key by key value (for authentication)
+    /// Find an active API key by its plaintext secret (for authentication)
+    ///
+    /// The record is found by the key's prefix and the secret checked
+    /// against the stored salted hash in constant time; only the hash is
+    /// ever stored.
     async fn find_by_key(&self, key: &str) -> Result<Option<ApiKey>, ApiKeyRepositoryError>;
 
     /// List API keys for a tenant
@@ -43,6 +49,10 @@ pub trait ApiKeyRepository: Send + Sync {
     /// Update an API key
     async fn update(&self, api_key: &ApiKey) -> Result<(), ApiKeyRepositoryError>;
 
+    /// Store an API key's new secret hash; the previous secret stops
+    /// validating at once
+    async fn update_secret(&self, api_key: &ApiKey) -> Result<(), ApiKeyRepositoryError>;
+
     /// Delete an API key
     async fn delete(&self, id: &ApiKeyId) -> Result<(), ApiKeyRepositoryError>;
 
@@ -65,6 +75,7 @@ mockall::mock! {
         async fn list_by_tenant(&self, tenant_id: &str, limit: i64, offset: i64) -> Result<Vec<ApiKey>, ApiKeyRepositoryError>;
         async fn count_by_tenant(&self, tenant_id: &str) -> Result<i64, ApiKeyRepositoryError>;
         async fn update(&self, api_key: &ApiKey) -> Result<(), ApiKeyRepositoryError>;
+        async fn update_secret(&self, api_key: &ApiKey) -> Result<(), ApiKeyRepositoryError>;
         async fn delete(&self, id: &ApiKeyId) -> Result<(), ApiKeyRepositoryError>;
         async fn mark_used(&self, id: &ApiKeyId) -> Result<(), ApiKeyRepositoryError>;
         async fn cleanup_expired(&self) -> Result<i64, ApiKeyRepositoryError>;
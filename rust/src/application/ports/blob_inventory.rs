@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::domain::value_objects::{ContentHash, StorageClass};
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::StorageError;
+
+/// What a file under a storage root holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoredFileKind {
+    /// Content-addressable blob named by its hash
+    Blob(ContentHash),
+    /// Upload staging file
+    Temp,
+}
+
+/// A file found under a storage root
+#[derive(Debug, Clone)]
+pub struct StoredFile {
+    pub path: PathBuf,
+    pub storage_class: StorageClass,
+    pub kind: StoredFileKind,
+    pub size_bytes: u64,
+    /// Last modification; writes in progress keep this recent
+    pub modified: SystemTime,
+}
+
+/// Port for enumerating the files a blob store keeps on disk
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait BlobInventory: Send + Sync {
+    /// Blob and temp files last modified before `modified_before`
+    ///
+    /// Files with unexpected names are never listed, so they are never removed.
+    async fn list_files(
+        &self,
+        storage_class: StorageClass,
+        modified_before: SystemTime,
+    ) -> Result<Vec<StoredFile>, StorageError>;
+
+    /// Remove a file returned by `list_files`
+    async fn remove_file(&self, file: &StoredFile) -> Result<(), StorageError>;
+}
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 
 use crate::domain::entities::Blob;
-use crate::domain::value_objects::{ContentHash, StorageClass};
+use crate::domain::value_objects::{ContentHash, ContentHashPrefix, StorageClass, TenantId};
 #[cfg(test)]
 use mockall::{automock, predicate::*};
 
@@ -19,6 +19,17 @@ pub trait BlobRepository: Send + Sync {
         size_bytes: u64,
     ) -> Result<Blob, RepositoryError>;
 
+    /// Take a reference on a live `storage_class` blob that `tenant_id`
+    /// already stores in a committed object
+    ///
+    /// Returns `None`, changing nothing, when there is no such blob.
+    async fn reference_existing(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+        tenant_id: &TenantId,
+    ) -> Result<Option<Blob>, RepositoryError>;
+
     /// Increment reference count
     async fn increment_ref(&self, content_hash: &ContentHash) -> Result<(), RepositoryError>;
 
@@ -26,8 +37,54 @@ pub trait BlobRepository: Send + Sync {
     async fn decrement_ref(&self, content_hash: &ContentHash) -> Result<i32, RepositoryError>;
 
     /// Find blobs with zero references for GC
+    ///
+    /// Blobs whose file deletion failed are skipped while they back off and
+    /// once they are dead-lettered.
     async fn find_orphaned(&self, limit: i64) -> Result<Vec<Blob>, RepositoryError>;
 
     /// Delete blob entry (hard delete)
     async fn delete(&self, content_hash: &ContentHash) -> Result<(), RepositoryError>;
+
+    /// Move a blob entry from the `from` storage class to `to`
+    ///
+    /// Returns false, changing nothing, when the blob is not in `from`, e.g.
+    /// because a concurrent move got there first.
+    async fn move_storage_class(
+        &self,
+        content_hash: &ContentHash,
+        from: StorageClass,
+        to: StorageClass,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Hashes among `content_hashes` the database still needs: those with a
+    /// blob entry or staged on an object that is still being written
+    async fn find_known(
+        &self,
+        content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError>;
+
+    /// Content hashes of all blob entries in hash order, `limit` at a time,
+    /// starting after `after`
+    async fn list_hashes(
+        &self,
+        after: Option<ContentHash>,
+        limit: i64,
+    ) -> Result<Vec<ContentHash>, RepositoryError>;
+
+    /// Blob entries in hash order, `limit` at a time, starting after `after`;
+    /// only those without references when `orphaned_only`
+    async fn list(
+        &self,
+        after: Option<ContentHash>,
+        orphaned_only: bool,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError>;
+
+    /// Blob entries whose hash starts with `prefix`, in hash order, at most
+    /// `limit`; a full hash finds only its own entry
+    async fn find_by_prefix(
+        &self,
+        prefix: &ContentHashPrefix,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError>;
 }
@@ -2,10 +2,12 @@ use async_trait::async_trait;
 #[cfg(test)]
 use mockall::{automock, predicate::*};
 use std::pin::Pin;
+use std::sync::Arc;
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWrite};
+use uuid::Uuid;
 
-use crate::domain::value_objects::{ContentHash, StorageClass};
+use crate::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
 
 #[derive(Debug, Error)]
 pub enum StorageError {
@@ -18,6 +20,23 @@ pub enum StorageError {
     #[error("Hash mismatch: expected {expected}, got {actual}")]
     HashMismatch { expected: String, actual: String },
 
+    /// The cold blob must be restored before it can be read
+    #[error("Blob is not restored from cold storage: {0}")]
+    Restoring(String),
+
+    /// A staged upload was appended to at an offset other than its length
+    #[error("Staged upload has {staged} bytes, not {offset}")]
+    OffsetMismatch { staged: u64, offset: u64 },
+
+    /// A remote backend timed out or failed with a server error; the same
+    /// call may succeed when retried
+    #[error("Transient storage error: {0}")]
+    Transient(String),
+
+    /// The backend failed repeatedly and is not called until the hint passes
+    #[error("Blob store unavailable; retry in {retry_after_secs}s")]
+    Unavailable { retry_after_secs: u64 },
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -28,6 +47,23 @@ pub type BlobReader = Pin<Box<dyn AsyncRead + Send>>;
 /// Type alias for async writer
 pub type BlobWriter = Pin<Box<dyn AsyncWrite + Send>>;
 
+/// State of a cold blob restore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreStatus {
+    /// The blob can be read
+    Restored,
+    /// The blob is being restored; reads should be retried after the hint
+    InProgress { retry_after_secs: u64 },
+}
+
+/// Handle of a cold blob restore
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreJob {
+    /// Backend identifier of the restore request
+    pub id: String,
+    pub status: RestoreStatus,
+}
+
 /// Port for physical blob storage operations
 #[cfg_attr(test, automock)]
 #[async_trait]
@@ -63,4 +99,65 @@ pub trait BlobStore: Send + Sync {
 
     /// Get total size of storage for a given class
     async fn get_total_size(&self, storage_class: StorageClass) -> Result<u64, StorageError>;
+
+    /// Append to the staged upload `upload_id` and return its new length
+    ///
+    /// `offset` must be the staged length, otherwise
+    /// `StorageError::OffsetMismatch`; offset 0 creates the staged upload.
+    /// Bytes received before `reader` fails stay staged, so an interrupted
+    /// transfer can resume where it stopped.
+    async fn append_staged(
+        &self,
+        upload_id: Uuid,
+        offset: u64,
+        reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<u64, StorageError>;
+
+    /// Length of the staged upload, if it exists
+    async fn staged_len(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<Option<u64>, StorageError>;
+
+    /// Read a staged upload back from the start
+    async fn read_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError>;
+
+    /// Remove a staged upload; a missing one is not an error
+    async fn discard_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError>;
+
+    /// Start restoring a cold blob so it can be read
+    ///
+    /// Backends with retrieval latency answer `read` with
+    /// `StorageError::Restoring` until a restore completes. Starting a
+    /// restore that is already under way returns the existing job.
+    async fn initiate_restore(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreJob, StorageError>;
+
+    /// Check whether a cold blob can be read yet
+    async fn restore_status(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreStatus, StorageError>;
+}
+
+/// Port for choosing the blob store that new blobs of a namespace are written to
+///
+/// The stores it returns find blobs of every backend on read, so content
+/// shared across namespaces stays readable wherever it was first written.
+#[cfg_attr(test, automock)]
+pub trait BlobRouter: Send + Sync {
+    /// Store for the blobs written for `namespace` of `tenant_id`
+    fn store_for(&self, namespace: &Namespace, tenant_id: &TenantId) -> Arc<dyn BlobStore>;
 }
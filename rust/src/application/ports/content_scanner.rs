@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+use thiserror::Error;
+
+use crate::application::ports::BlobReader;
+
+#[derive(Debug, Error)]
+pub enum ScanError {
+    #[error("Scanner unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Scan failed: {0}")]
+    Failed(String),
+}
+
+/// Outcome of scanning an upload's content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    /// The content must not be stored, e.g. the name of the signature found
+    Rejected(String),
+}
+
+/// What is known about the content being scanned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanSubject {
+    pub namespace: String,
+    pub tenant_id: String,
+    pub key: Option<String>,
+    pub content_type: Option<String>,
+    pub content_hash: String,
+    pub size_bytes: u64,
+}
+
+/// Port for scanning uploaded content (virus scanning, content moderation)
+///
+/// Runs once the blob is written and before the object commits; a rejected
+/// upload never becomes readable. The content is handed over as a stream,
+/// exactly as stored (still encoded if uploaded with a `Content-Encoding`).
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ContentScanner: Send + Sync {
+    async fn scan(
+        &self,
+        subject: &ScanSubject,
+        content: BlobReader,
+    ) -> Result<ScanVerdict, ScanError>;
+}
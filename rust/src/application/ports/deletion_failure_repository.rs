@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use time::OffsetDateTime;
+
+use crate::domain::value_objects::{ContentHash, StorageClass};
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// An orphaned blob whose blob store file could not be deleted
+///
+/// The blob keeps its database row until the file is gone, so the garbage
+/// collector can retry it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobDeletionFailure {
+    pub content_hash: ContentHash,
+    pub storage_class: StorageClass,
+    /// Failed deletion attempts so far
+    pub attempts: u32,
+    pub last_error: String,
+    /// When the blob may be retried; `None` once dead-lettered
+    pub next_attempt_at: Option<OffsetDateTime>,
+    /// Set once retries are exhausted; the blob then waits for an operator
+    pub dead_lettered_at: Option<OffsetDateTime>,
+}
+
+/// Port for the garbage collector's failed blob deletions
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait BlobDeletionFailureRepository: Send + Sync {
+    /// The recorded failure of a blob, if its deletion failed before
+    async fn find(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<Option<BlobDeletionFailure>, RepositoryError>;
+
+    /// Record (or replace) the failure of a blob
+    ///
+    /// Orphan scans skip the blob until `next_attempt_at`, and for good once
+    /// it is dead-lettered. The record goes away with the blob's row.
+    async fn record(&self, failure: &BlobDeletionFailure) -> Result<(), RepositoryError>;
+
+    /// Forget the failure of a blob whose file was deleted after all
+    async fn clear(&self, content_hash: &ContentHash) -> Result<(), RepositoryError>;
+}
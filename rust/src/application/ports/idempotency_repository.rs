@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+
+use crate::domain::value_objects::{ContentHash, ObjectId, TenantId};
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// An upload recorded under an idempotency key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    pub namespace: String,
+    pub object_key: Option<String>,
+    /// Object the upload created; `None` while the upload is in progress
+    pub object_id: Option<ObjectId>,
+    pub content_hash: Option<ContentHash>,
+}
+
+/// Port for upload idempotency keys, scoped per tenant
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    /// The unexpired record under `key`, if any
+    async fn find(
+        &self,
+        tenant_id: &TenantId,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>, RepositoryError>;
+
+    /// Claim `key` for an upload in progress, leased for `lease_secs`
+    ///
+    /// Returns false when an unexpired record already holds the key. An
+    /// expired record is taken over, including the lapsed lease of an
+    /// upload that never completed or released it.
+    async fn reserve(
+        &self,
+        tenant_id: &TenantId,
+        key: &str,
+        record: &IdempotencyRecord,
+        lease_secs: i64,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Record the object a reserved upload created, remembering it for
+    /// `ttl_secs`
+    async fn complete(
+        &self,
+        tenant_id: &TenantId,
+        key: &str,
+        object_id: &ObjectId,
+        content_hash: &ContentHash,
+        ttl_secs: i64,
+    ) -> Result<(), RepositoryError>;
+
+    /// Drop the reservation of a failed upload so it can be retried
+    async fn release(&self, tenant_id: &TenantId, key: &str) -> Result<(), RepositoryError>;
+
+    /// Delete expired records, returning how many were removed
+    async fn delete_expired(&self) -> Result<u64, RepositoryError>;
+}
@@ -1,20 +1,72 @@
 mod api_key_repository;
 mod audit_repository;
+mod blob_inventory;
 mod blob_repository;
 mod blob_store;
+mod content_scanner;
+mod deletion_failure_repository;
+mod idempotency_repository;
+mod namespace_config_repository;
+mod namespace_deletion_repository;
 mod object_repository;
+mod object_share_repository;
+mod refcount_repository;
+mod scrub_repository;
+mod stats_repository;
+mod tenant_limit_provider;
+mod text_extractor;
 
 pub use api_key_repository::{ApiKeyRepository, ApiKeyRepositoryError};
 pub use audit_repository::{AuditQueryFilter, AuditRepository, AuditRepositoryError};
+pub use blob_inventory::{BlobInventory, StoredFile, StoredFileKind};
 pub use blob_repository::BlobRepository;
-pub use blob_store::{BlobReader, BlobStore, BlobWriter, StorageError};
-pub use object_repository::{ObjectRepository, RepositoryError};
+pub use blob_store::{
+    BlobReader, BlobRouter, BlobStore, BlobWriter, RestoreJob, RestoreStatus, StorageError,
+};
+pub use content_scanner::{ContentScanner, ScanError, ScanSubject, ScanVerdict};
+pub use deletion_failure_repository::{BlobDeletionFailure, BlobDeletionFailureRepository};
+pub use idempotency_repository::{IdempotencyRecord, IdempotencyRepository};
+pub use namespace_config_repository::NamespaceConfigRepository;
+pub use namespace_deletion_repository::{
+    LockedObject, NamespaceDeleteBatch, NamespaceDeletionRepository, NamespaceUsage,
+};
+pub use object_repository::{ObjectRepository, ObjectStream, RepositoryError};
+pub use object_share_repository::{ObjectShare, ObjectShareRepository};
+pub use refcount_repository::{RefcountEntry, RefcountRepository};
+pub use scrub_repository::{ScrubCheckpoint, ScrubFinding, ScrubRepository};
+pub use stats_repository::StatsRepository;
+pub use tenant_limit_provider::TenantLimitProvider;
+pub use text_extractor::{ExtractionError, TextExtractor};
 
 #[cfg(test)]
 pub use api_key_repository::MockApiKeyRepository;
 #[cfg(test)]
+pub use blob_inventory::MockBlobInventory;
+#[cfg(test)]
 pub use blob_repository::MockBlobRepository;
 #[cfg(test)]
-pub use blob_store::MockBlobStore;
+pub use blob_store::{MockBlobRouter, MockBlobStore};
+#[cfg(test)]
+pub use content_scanner::MockContentScanner;
+#[cfg(test)]
+pub use deletion_failure_repository::MockBlobDeletionFailureRepository;
+#[cfg(test)]
+pub use idempotency_repository::MockIdempotencyRepository;
+#[cfg(test)]
+pub use namespace_config_repository::MockNamespaceConfigRepository;
+#[cfg(test)]
+pub use namespace_deletion_repository::MockNamespaceDeletionRepository;
 #[cfg(test)]
 pub use object_repository::MockObjectRepository;
+#[cfg(test)]
+pub use object_share_repository::MockObjectShareRepository;
+#[cfg(test)]
+pub use refcount_repository::MockRefcountRepository;
+#[cfg(test)]
+pub use scrub_repository::MockScrubRepository;
+#[cfg(test)]
+pub use stats_repository::MockStatsRepository;
+#[cfg(test)]
+pub use tenant_limit_provider::MockTenantLimitProvider;
+#[cfg(test)]
+pub use text_extractor::MockTextExtractor;
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::NamespaceConfig;
+use crate::domain::value_objects::Namespace;
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// Port for per-namespace configuration
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait NamespaceConfigRepository: Send + Sync {
+    /// Configuration of one namespace, if any was set
+    async fn find(&self, namespace: &Namespace)
+        -> Result<Option<NamespaceConfig>, RepositoryError>;
+
+    /// All configured namespaces, ordered by name
+    async fn list(&self) -> Result<Vec<NamespaceConfig>, RepositoryError>;
+
+    /// Create or replace a namespace's configuration, returning it as stored
+    async fn upsert(&self, config: &NamespaceConfig) -> Result<NamespaceConfig, RepositoryError>;
+
+    /// Remove a namespace's configuration; false if there was none
+    async fn delete(&self, namespace: &Namespace) -> Result<bool, RepositoryError>;
+}
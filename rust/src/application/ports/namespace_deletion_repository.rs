@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use time::OffsetDateTime;
+
+use crate::domain::value_objects::{Namespace, ObjectId, TenantId};
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// A committed object whose WORM lock keeps its namespace from being deleted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedObject {
+    pub id: ObjectId,
+    pub key: Option<String>,
+    pub retention_until: Option<OffsetDateTime>,
+    pub legal_hold: bool,
+}
+
+/// Committed objects of a namespace and their total size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NamespaceUsage {
+    pub objects: u64,
+    pub bytes: u64,
+}
+
+/// What deleting one batch of a namespace's objects did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NamespaceDeleteBatch {
+    pub objects: u64,
+    pub bytes: u64,
+    /// Blobs left without references, for the garbage collector to reclaim
+    pub released_blobs: u64,
+}
+
+/// Port for deleting every object of a tenant's namespace
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait NamespaceDeletionRepository: Send + Sync {
+    /// Count the committed objects of the namespace
+    async fn usage(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+    ) -> Result<NamespaceUsage, RepositoryError>;
+
+    /// Up to `limit` committed objects of the namespace under retention or
+    /// legal hold, in ID order
+    async fn find_locked(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        limit: i64,
+    ) -> Result<Vec<LockedObject>, RepositoryError>;
+
+    /// Mark up to `limit` unlocked committed objects of the namespace deleted
+    /// and release their blob references, in one short transaction
+    ///
+    /// Objects already taken by a concurrent batch are skipped.
+    async fn delete_batch(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        limit: i64,
+    ) -> Result<NamespaceDeleteBatch, RepositoryError>;
+}
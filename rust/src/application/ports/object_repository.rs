@@ -1,9 +1,18 @@
 use async_trait::async_trait;
+use futures_util::stream::BoxStream;
 use thiserror::Error;
 
-use crate::application::dto::{SearchRequest, TextSearchRequest};
+use crate::application::dto::{
+    ObjectAccess, ObjectField, ObjectHead, ObjectProjection, ObjectRecord, SearchRequest,
+    SortDirection, SortField, TextSearchPage, TextSearchRequest,
+};
+use crate::application::key_prefix_query::KeyPrefixQuery;
+use crate::application::list_cursor::ListCursor;
+use crate::application::metadata_query::MetadataQuery;
 use crate::domain::entities::Object;
-use crate::domain::value_objects::{Namespace, ObjectId, TenantId};
+use crate::domain::value_objects::{
+    ContentHash, Namespace, ObjectId, ObjectMetadata, ObjectStatus, StorageClass, TenantId,
+};
 #[cfg(test)]
 use mockall::{automock, predicate::*};
 
@@ -23,8 +32,29 @@ pub enum RepositoryError {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// Failure of a metadata store other than Postgres
+    #[error("Metadata store error: {0}")]
+    Store(String),
+}
+
+impl RepositoryError {
+    /// Whether a write lost to another object holding the same key
+    ///
+    /// Postgres reports it through its unique index, other stores as a
+    /// constraint violation.
+    pub fn is_unique_violation(&self) -> bool {
+        match self {
+            Self::Database(sqlx::Error::Database(e)) => e.is_unique_violation(),
+            Self::ConstraintViolation(_) => true,
+            _ => false,
+        }
+    }
 }
 
+/// Stream of objects, read from the store as it is polled
+pub type ObjectStream = BoxStream<'static, Result<Object, RepositoryError>>;
+
 /// Port for object persistence operations
 #[cfg_attr(test, automock)]
 #[async_trait]
@@ -35,6 +65,70 @@ pub trait ObjectRepository: Send + Sync {
     /// Find object by ID (only COMMITTED objects)
     async fn find_by_id(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError>;
 
+    /// Find an object still being uploaded (only WRITING objects)
+    async fn find_writing(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError>;
+
+    /// Load the header fields of a committed object by ID, skipping the
+    /// metadata document
+    async fn head(&self, id: &ObjectId) -> Result<Option<ObjectHead>, RepositoryError>;
+
+    /// Load a committed object with its blob's reference count, for a
+    /// metadata view that doesn't open the blob
+    async fn find_record(&self, id: &ObjectId) -> Result<Option<ObjectRecord>, RepositoryError>;
+
+    /// Load the owning tenant and lifecycle status of an object in any state
+    async fn status(
+        &self,
+        id: &ObjectId,
+    ) -> Result<Option<(TenantId, ObjectStatus)>, RepositoryError>;
+
+    /// Load the header fields of a committed object by key
+    async fn head_by_key(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        key: &str,
+    ) -> Result<Option<ObjectHead>, RepositoryError>;
+
+    /// Persist replaced content of a committed object if its stored content
+    /// hash still equals `expected`
+    ///
+    /// Returns false when the object changed concurrently and was left alone.
+    async fn replace_content_if_match(
+        &self,
+        object: &Object,
+        expected: &ContentHash,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Persist the metadata of a committed object if its stored metadata still
+    /// equals `expected`
+    ///
+    /// Returns false when the metadata changed concurrently and was left alone.
+    async fn update_metadata_if_match(
+        &self,
+        object: &Object,
+        expected: &ObjectMetadata,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Persist the retention and legal hold of a committed object
+    ///
+    /// A retention earlier than the stored one leaves the stored one in place.
+    /// Returns false when the object is no longer committed.
+    async fn update_retention(&self, object: &Object) -> Result<bool, RepositoryError>;
+
+    /// Persist a committed object's move to DELETING if it is not locked
+    ///
+    /// Returns false when the object was locked, or stopped being committed,
+    /// concurrently and was left alone.
+    async fn mark_deleting_if_unlocked(&self, object: &Object) -> Result<bool, RepositoryError>;
+
+    /// Store (or clear) the text extracted from an object's content for search
+    async fn set_extracted_text(
+        &self,
+        id: &ObjectId,
+        text: Option<String>,
+    ) -> Result<(), RepositoryError>;
+
     /// Find object by key (namespace + tenant + key)
     async fn find_by_key(
         &self,
@@ -43,23 +137,120 @@ pub trait ObjectRepository: Send + Sync {
         key: &str,
     ) -> Result<Option<Object>, RepositoryError>;
 
-    /// List objects with pagination
+    /// List objects matching `keys` and `metadata` with pagination, in the
+    /// given order, with ties broken by ID
+    ///
+    /// With `after`, the page starts after the object the cursor points at
+    /// (the cursor is in the same order). Keys folded into a common prefix by
+    /// a delimiter are not listed.
+    #[allow(clippy::too_many_arguments)]
     async fn list(
         &self,
         namespace: &Namespace,
         tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        after: Option<ListCursor>,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<Object>, RepositoryError>;
 
-    /// Advanced search with filters
-    async fn search(&self, request: &SearchRequest) -> Result<Vec<Object>, RepositoryError>;
+    /// Page through the same objects as `list`, loading only `fields`
+    #[allow(clippy::too_many_arguments)]
+    async fn list_projected(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        fields: &[ObjectField],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ObjectProjection>, RepositoryError>;
 
-    /// Full-text search across metadata and keys
+    /// Stream every object `list` pages through, in the same order
+    ///
+    /// Rows are fetched as the stream is polled instead of loaded up front,
+    /// so memory stays flat however large the namespace. A failed read ends
+    /// the stream with an error; it is not retried.
+    fn stream(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+    ) -> ObjectStream;
+
+    /// Count the objects `list` pages through, stopping at `max`
+    ///
+    /// Bounding the count keeps it cheap for tenants with huge namespaces.
+    async fn count(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        max: i64,
+    ) -> Result<i64, RepositoryError>;
+
+    /// Namespaces holding committed objects of a tenant, in name order
+    async fn namespaces(&self, tenant_id: &TenantId) -> Result<Vec<Namespace>, RepositoryError>;
+
+    /// Distinct common prefixes of the keys `list` folds away with a
+    /// delimiter, in key order, at most `max`
+    ///
+    /// Empty when `keys` has no delimiter.
+    async fn common_prefixes(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        max: i64,
+    ) -> Result<Vec<String>, RepositoryError>;
+
+    /// Advanced search with filters; `keys` and `metadata` are validated from
+    /// the request
+    ///
+    /// With `after`, only objects past that cursor are returned, ties on the
+    /// sort value ordered by ID; the cursor must be for the request's order.
+    async fn search(
+        &self,
+        request: &SearchRequest,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        after: Option<ListCursor>,
+    ) -> Result<Vec<Object>, RepositoryError>;
+
+    /// Full-text search across metadata and keys, best matches first
     async fn text_search(
         &self,
         request: &TextSearchRequest,
-    ) -> Result<Vec<Object>, RepositoryError>;
+    ) -> Result<TextSearchPage, RepositoryError>;
+
+    /// Add batched download counts and advance last-accessed times
+    ///
+    /// Applied in one statement per batch; does not touch `updated_at`.
+    async fn record_access(&self, accesses: &[ObjectAccess]) -> Result<(), RepositoryError>;
+
+    /// Point every object stored as `content_hash` in the `from` storage
+    /// class at `to`, after its blob moved there
+    ///
+    /// Objects in any state are moved, so uploads still being committed
+    /// follow the blob. Returns how many objects changed; does not touch
+    /// `updated_at`.
+    async fn move_storage_class(
+        &self,
+        content_hash: &ContentHash,
+        from: StorageClass,
+        to: StorageClass,
+    ) -> Result<u64, RepositoryError>;
 
     /// Delete object (hard delete from DB)
     async fn delete(&self, id: &ObjectId) -> Result<(), RepositoryError>;
@@ -72,6 +263,23 @@ pub trait ObjectRepository: Send + Sync {
         limit: i64,
     ) -> Result<Vec<ObjectId>, RepositoryError>;
 
+    /// Find WRITING objects older than the given number of minutes that have
+    /// staged content (see `Object::stage_content`), oldest first
+    async fn find_stuck_staged_objects(
+        &self,
+        age_minutes: i64,
+        limit: i64,
+    ) -> Result<Vec<Object>, RepositoryError>;
+
     /// Clean up stuck WRITING objects (orphaned uploads)
     async fn cleanup_stuck_uploads(&self, age_hours: i64) -> Result<usize, RepositoryError>;
+
+    /// Which of `content_hashes` an object under retention or legal hold
+    /// still points at
+    ///
+    /// Garbage collection keeps these blobs even without references.
+    async fn find_locked_hashes(
+        &self,
+        content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError>;
 }
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::value_objects::{ContentHash, Namespace, ObjectId, TenantId};
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// A grant sharing one tenant's object with another tenant
+///
+/// The owner offers the grant; once the grantee accepts it, the grantee
+/// holds its own object, `grantee_object_id`, referencing the same blob as
+/// the shared object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectShare {
+    pub id: Uuid,
+    /// The shared object
+    pub object_id: ObjectId,
+    pub owner_tenant_id: TenantId,
+    pub grantee_tenant_id: TenantId,
+    /// The grantee's object, created when the grant is accepted
+    pub grantee_object_id: Option<ObjectId>,
+    pub grantee_namespace: Namespace,
+    pub grantee_key: Option<String>,
+    /// Content the grant offers
+    pub content_hash: ContentHash,
+    pub created_at: OffsetDateTime,
+    pub accepted_at: Option<OffsetDateTime>,
+}
+
+/// Port for object share grants
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ObjectShareRepository: Send + Sync {
+    /// Record a grant
+    async fn create(&self, share: &ObjectShare) -> Result<(), RepositoryError>;
+
+    /// One grant, if it exists
+    async fn find(&self, id: &Uuid) -> Result<Option<ObjectShare>, RepositoryError>;
+
+    /// Grants of an object, oldest first
+    async fn list_for_object(
+        &self,
+        object_id: &ObjectId,
+    ) -> Result<Vec<ObjectShare>, RepositoryError>;
+
+    /// Grants offered to a tenant, oldest first
+    async fn list_for_grantee(
+        &self,
+        grantee_tenant_id: &TenantId,
+    ) -> Result<Vec<ObjectShare>, RepositoryError>;
+
+    /// Mark a pending grant accepted with the grantee's object; false if
+    /// it is gone or was accepted already
+    async fn accept(
+        &self,
+        id: &Uuid,
+        grantee_object_id: &ObjectId,
+        accepted_at: OffsetDateTime,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Remove a grant; false if there was none
+    async fn delete(&self, id: &Uuid) -> Result<bool, RepositoryError>;
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+
+use crate::domain::value_objects::ContentHash;
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// Stored vs actual reference count for one blob
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefcountEntry {
+    pub content_hash: ContentHash,
+    /// `ref_count` column on the blob row
+    pub stored_ref_count: i64,
+    /// Committed objects and staged uploads that reference the blob
+    pub actual_ref_count: i64,
+}
+
+impl RefcountEntry {
+    pub fn is_consistent(&self) -> bool {
+        self.stored_ref_count == self.actual_ref_count
+    }
+}
+
+/// Port for blob reference count reconciliation
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RefcountRepository: Send + Sync {
+    /// Scan up to `limit` blobs ordered by content hash, starting after `after`
+    async fn scan_batch(
+        &self,
+        after: Option<ContentHash>,
+        limit: i64,
+    ) -> Result<Vec<RefcountEntry>, RepositoryError>;
+
+    /// Set a blob's reference count if it still equals `expected`
+    ///
+    /// Returns false when the count changed concurrently and was left alone.
+    async fn set_ref_count(
+        &self,
+        content_hash: &ContentHash,
+        expected: i64,
+        ref_count: i64,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Load the cursor a previous run of `task` stopped at
+    async fn load_checkpoint(&self, task: &str) -> Result<Option<ContentHash>, RepositoryError>;
+
+    /// Persist the cursor for `task`; `None` clears it once a run completes
+    async fn save_checkpoint(
+        &self,
+        task: &str,
+        cursor: Option<ContentHash>,
+    ) -> Result<(), RepositoryError>;
+}
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::domain::entities::Blob;
+use crate::domain::value_objects::{ContentHash, StorageClass};
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// Where the blob scrubber stands
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScrubCheckpoint {
+    /// Last blob verified by the pass in progress; `None` between passes
+    pub cursor: Option<ContentHash>,
+    /// When the last pass over every blob finished
+    pub last_pass_completed_at: Option<OffsetDateTime>,
+}
+
+/// A blob the scrubber could not verify, kept until it verifies again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScrubFinding {
+    pub content_hash: ContentHash,
+    pub storage_class: StorageClass,
+    /// Why verification failed: a hash mismatch or a missing or unreadable file
+    pub error: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub detected_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub last_seen_at: OffsetDateTime,
+}
+
+/// Port for the blob integrity scrubber's progress and findings
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ScrubRepository: Send + Sync {
+    /// Load up to `limit` blobs ordered by content hash, starting after `after`
+    async fn scan_blobs(
+        &self,
+        after: Option<ContentHash>,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError>;
+
+    async fn load_checkpoint(&self) -> Result<ScrubCheckpoint, RepositoryError>;
+
+    async fn save_checkpoint(&self, checkpoint: &ScrubCheckpoint) -> Result<(), RepositoryError>;
+
+    /// Record (or refresh) a finding for a blob that failed verification
+    async fn flag(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+        error: &str,
+    ) -> Result<(), RepositoryError>;
+
+    /// Drop the finding of a blob that verified again, e.g. after an operator
+    /// restored it
+    async fn clear(&self, content_hash: &ContentHash) -> Result<(), RepositoryError>;
+
+    /// Findings awaiting operator action, most recently seen first
+    async fn findings(&self, limit: i64) -> Result<Vec<ScrubFinding>, RepositoryError>;
+}
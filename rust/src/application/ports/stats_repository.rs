@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+
+use crate::application::dto::{DedupStats, TenantDedupStats};
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// Port for storage usage aggregation
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StatsRepository: Send + Sync {
+    /// Logical object bytes vs physical blob bytes across all tenants
+    async fn global_usage(&self) -> Result<DedupStats, RepositoryError>;
+
+    /// Logical object bytes vs physical blob bytes for each tenant
+    async fn usage_by_tenant(&self) -> Result<Vec<TenantDedupStats>, RepositoryError>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+use super::RepositoryError;
+
+/// Port for per-tenant rate limit lookups (e.g. free vs paid tiers)
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TenantLimitProvider: Send + Sync {
+    /// Requests per minute allowed for a tenant's users, or `None` when the
+    /// tenant has no tier and the configured default applies
+    async fn requests_per_minute(&self, tenant_id: &str) -> Result<Option<u32>, RepositoryError>;
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExtractionError {
+    #[error("Unsupported encoding: {0}")]
+    UnsupportedEncoding(String),
+
+    #[error("Extraction failed: {0}")]
+    Failed(String),
+}
+
+/// Port for server-side text extraction from uploaded content
+///
+/// Extracted text is stored next to the object and matched by text search;
+/// it never affects the stored blob.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TextExtractor: Send + Sync {
+    /// Whether content of this MIME type (without parameters) is handled
+    fn supports(&self, content_type: &str) -> bool;
+
+    /// Extract text from content; `None` when there is nothing to index
+    async fn extract(
+        &self,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<Option<String>, ExtractionError>;
+}
@@ -0,0 +1,240 @@
+//! Checksum verification of downloads
+//!
+//! Blobs are addressed by the SHA-256 of their content, so a blob that rots
+//! on disk no longer matches its own name. Re-hashing a blob as it streams
+//! to the client catches that at the cost of the hashing CPU, so it is done
+//! for every download, a random sample of them, or none.
+//!
+//! The response headers are sent before the last byte is hashed, so a
+//! mismatch cannot become an error status: the body is aborted instead,
+//! and the client sees a truncated download rather than corrupt content.
+
+use std::io;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use rand::RngExt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::application::ports::BlobReader;
+use crate::domain::value_objects::{ContentHash, ObjectId};
+
+/// Default share of downloads verified in `sampled` mode
+pub const DEFAULT_VERIFY_ON_READ_SAMPLE_RATE: f64 = 0.01;
+
+/// Which downloads are verified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerifyOnRead {
+    Always,
+    /// A random share of downloads, set by the sample rate
+    Sampled,
+    #[default]
+    Never,
+}
+
+impl FromStr for VerifyOnRead {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "always" => Ok(Self::Always),
+            "sampled" => Ok(Self::Sampled),
+            "never" => Ok(Self::Never),
+            other => Err(format!(
+                "unknown verification mode '{other}' (expected always, sampled or never)"
+            )),
+        }
+    }
+}
+
+/// Verification counters since startup
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReadVerificationStats {
+    pub mode: VerifyOnRead,
+    pub sample_rate: f64,
+    /// Downloads read to the end and found intact
+    pub verified: u64,
+    /// Downloads whose content did not match its hash
+    pub mismatches: u64,
+}
+
+/// Decides which downloads to verify and counts the outcomes
+#[derive(Debug, Default)]
+pub struct ReadVerifier {
+    mode: VerifyOnRead,
+    sample_rate: f64,
+    verified: AtomicU64,
+    mismatches: AtomicU64,
+}
+
+impl ReadVerifier {
+    pub fn new(mode: VerifyOnRead, sample_rate: f64) -> Self {
+        Self {
+            mode,
+            sample_rate: sample_rate.clamp(0.0, 1.0),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the next download is verified
+    pub fn should_verify(&self) -> bool {
+        match self.mode {
+            VerifyOnRead::Always => true,
+            VerifyOnRead::Sampled => rand::rng().random::<f64>() < self.sample_rate,
+            VerifyOnRead::Never => false,
+        }
+    }
+
+    /// Wrap the reader of `object_id`'s blob so that reading past its end
+    /// fails unless the content hashed to `expected`
+    pub fn verify(
+        self: &Arc<Self>,
+        reader: BlobReader,
+        expected: ContentHash,
+        object_id: ObjectId,
+    ) -> BlobReader {
+        Box::pin(VerifyingReader {
+            inner: reader,
+            hasher: Sha256::new(),
+            expected: Some(expected),
+            object_id,
+            verifier: Arc::clone(self),
+        })
+    }
+
+    pub fn stats(&self) -> ReadVerificationStats {
+        ReadVerificationStats {
+            mode: self.mode,
+            sample_rate: self.sample_rate,
+            verified: self.verified.load(Ordering::Relaxed),
+            mismatches: self.mismatches.load(Ordering::Relaxed),
+        }
+    }
+
+    fn check(
+        &self,
+        hasher: Sha256,
+        expected: &ContentHash,
+        object_id: &ObjectId,
+    ) -> io::Result<()> {
+        let actual = hex::encode(hasher.finalize());
+        if actual == expected.as_hex() {
+            self.verified.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        self.mismatches.fetch_add(1, Ordering::Relaxed);
+        tracing::error!(
+            object_id = %object_id,
+            content_hash = %expected,
+            actual_hash = %actual,
+            "Blob corruption detected: content does not match its hash"
+        );
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Blob {expected} for object {object_id} is corrupt"),
+        ))
+    }
+}
+
+/// Hashes a blob as it is read and checks the hash at the end
+struct VerifyingReader {
+    inner: BlobReader,
+    hasher: Sha256,
+    /// Taken once the end is reached and checked
+    expected: Option<ContentHash>,
+    object_id: ObjectId,
+    verifier: Arc<ReadVerifier>,
+}
+
+impl AsyncRead for VerifyingReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let had_room = buf.remaining() > 0;
+        let before = buf.filled().len();
+        ready!(this.inner.as_mut().poll_read(cx, buf))?;
+
+        let read = &buf.filled()[before..];
+        if !read.is_empty() {
+            this.hasher.update(read);
+            return Poll::Ready(Ok(()));
+        }
+
+        // An empty read into a buffer with room is the end of the blob
+        if had_room {
+            if let Some(expected) = this.expected.take() {
+                let hasher = std::mem::take(&mut this.hasher);
+                return Poll::Ready(this.verifier.check(hasher, &expected, &this.object_id));
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tokio::io::AsyncReadExt;
+
+    fn hash_of(content: &[u8]) -> ContentHash {
+        ContentHash::from_hex(hex::encode(Sha256::digest(content))).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_intact_blob_is_verified() {
+        let verifier = Arc::new(ReadVerifier::new(VerifyOnRead::Always, 0.0));
+        let mut reader = verifier.verify(
+            Box::pin(Cursor::new(b"test data".to_vec())),
+            hash_of(b"test data"),
+            ObjectId::new(),
+        );
+
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).await.unwrap();
+
+        assert_eq!(content, b"test data");
+        let stats = verifier.stats();
+        assert_eq!((stats.verified, stats.mismatches), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_blob_fails_at_the_end() {
+        let verifier = Arc::new(ReadVerifier::new(VerifyOnRead::Always, 0.0));
+        let mut reader = verifier.verify(
+            Box::pin(Cursor::new(b"test dat4".to_vec())),
+            hash_of(b"test data"),
+            ObjectId::new(),
+        );
+
+        let error = reader.read_to_end(&mut Vec::new()).await.unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let stats = verifier.stats();
+        assert_eq!((stats.verified, stats.mismatches), (0, 1));
+    }
+
+    #[test]
+    fn test_modes_select_downloads() {
+        assert!(ReadVerifier::new(VerifyOnRead::Always, 0.0).should_verify());
+        assert!(!ReadVerifier::new(VerifyOnRead::Never, 1.0).should_verify());
+        assert!(ReadVerifier::new(VerifyOnRead::Sampled, 1.0).should_verify());
+        assert!(!ReadVerifier::new(VerifyOnRead::Sampled, 0.0).should_verify());
+    }
+
+    #[test]
+    fn test_mode_parsing() {
+        assert_eq!("Sampled".parse(), Ok(VerifyOnRead::Sampled));
+        assert!("sometimes".parse::<VerifyOnRead>().is_err());
+    }
+}
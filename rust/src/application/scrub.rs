@@ -0,0 +1,495 @@
+//! Background integrity scrubbing of stored blobs
+//!
+//! Verification on read only covers blobs that are downloaded; a blob nobody
+//! reads can rot unnoticed until the one time it is needed. The scrubber
+//! walks every blob in content-hash order and re-hashes it, throttled to a
+//! byte rate so it never competes with client traffic for disk or network.
+//!
+//! Progress is checkpointed after each batch, so a restart resumes the pass
+//! where it stopped, and a new pass starts once the previous one is older
+//! than the configured interval. Blobs that fail verification are recorded
+//! as findings for an operator to act on; the scrubber never deletes or
+//! repairs anything itself. Cold blobs that must be restored before they can
+//! be read are skipped rather than restored.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use time::OffsetDateTime;
+use tokio::io::AsyncReadExt;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+use crate::application::ports::{
+    BlobStore, RepositoryError, ScrubCheckpoint, ScrubFinding, ScrubRepository, StorageError,
+};
+use crate::domain::entities::Blob;
+
+/// Default time between the starts of two passes over every blob
+pub const DEFAULT_SCRUB_INTERVAL_DAYS: u64 = 30;
+/// Default read rate limit of the scrubber
+pub const DEFAULT_SCRUB_MAX_BYTES_PER_SEC: u64 = 8 * 1024 * 1024;
+/// Default number of blobs verified between checkpoints
+pub const DEFAULT_SCRUB_BATCH_SIZE: i64 = 100;
+/// Default time between checks whether a pass is due
+pub const DEFAULT_SCRUB_POLL_INTERVAL: Duration = Duration::from_secs(3600);
+
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Configuration of the blob scrubber
+#[derive(Debug, Clone)]
+pub struct ScrubConfig {
+    /// Time between the starts of two passes over every blob
+    pub interval: Duration,
+    /// Bytes read per second, at most
+    pub max_bytes_per_sec: u64,
+    /// Blobs verified between checkpoints
+    pub batch_size: i64,
+    /// How often to check whether a pass is due
+    pub poll_interval: Duration,
+}
+
+impl Default for ScrubConfig {
+    fn default() -> Self {
+        Self::new(
+            Duration::from_secs(DEFAULT_SCRUB_INTERVAL_DAYS * 24 * 3600),
+            DEFAULT_SCRUB_MAX_BYTES_PER_SEC,
+        )
+    }
+}
+
+impl ScrubConfig {
+    pub fn new(interval: Duration, max_bytes_per_sec: u64) -> Self {
+        Self {
+            interval,
+            max_bytes_per_sec: max_bytes_per_sec.max(1),
+            batch_size: DEFAULT_SCRUB_BATCH_SIZE,
+            poll_interval: DEFAULT_SCRUB_POLL_INTERVAL,
+        }
+    }
+
+    /// Set the number of blobs verified between checkpoints
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set how often to check whether a pass is due
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+/// Why a pass stopped before covering every blob
+///
+/// The checkpoint of the last finished batch is kept, so the next attempt
+/// resumes from there.
+#[derive(Debug, Error)]
+pub enum ScrubError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Outcome of one complete pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScrubPass {
+    pub blobs_scrubbed: u64,
+    pub bytes_scrubbed: u64,
+    pub corruptions: u64,
+    pub skipped: u64,
+}
+
+/// Scrubber counters since startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScrubStats {
+    /// Blobs read to the end and found intact
+    pub blobs_scrubbed: u64,
+    pub bytes_scrubbed: u64,
+    /// Blobs that were missing, unreadable or did not match their hash
+    pub corruptions: u64,
+    /// Unreferenced blobs awaiting GC and cold blobs not restored
+    pub skipped: u64,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_pass_completed_at: Option<OffsetDateTime>,
+}
+
+/// Re-hashes every stored blob on a schedule and flags the ones that fail
+pub struct BlobScrubber {
+    scrub_repo: Arc<dyn ScrubRepository>,
+    blob_store: Arc<dyn BlobStore>,
+    config: ScrubConfig,
+    blobs_scrubbed: AtomicU64,
+    bytes_scrubbed: AtomicU64,
+    corruptions: AtomicU64,
+    skipped: AtomicU64,
+    last_pass_completed_at: Mutex<Option<OffsetDateTime>>,
+}
+
+impl BlobScrubber {
+    /// `blob_store` should read from the backend itself, not through a cache
+    pub fn new(
+        scrub_repo: Arc<dyn ScrubRepository>,
+        blob_store: Arc<dyn BlobStore>,
+        config: ScrubConfig,
+    ) -> Self {
+        Self {
+            scrub_repo,
+            blob_store,
+            config,
+            blobs_scrubbed: AtomicU64::new(0),
+            bytes_scrubbed: AtomicU64::new(0),
+            corruptions: AtomicU64::new(0),
+            skipped: AtomicU64::new(0),
+            last_pass_completed_at: Mutex::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &ScrubConfig {
+        &self.config
+    }
+
+    pub fn stats(&self) -> ScrubStats {
+        ScrubStats {
+            blobs_scrubbed: self.blobs_scrubbed.load(Ordering::Relaxed),
+            bytes_scrubbed: self.bytes_scrubbed.load(Ordering::Relaxed),
+            corruptions: self.corruptions.load(Ordering::Relaxed),
+            skipped: self.skipped.load(Ordering::Relaxed),
+            last_pass_completed_at: *self.last_pass_completed_at.lock().unwrap(),
+        }
+    }
+
+    /// Blobs awaiting operator action, most recently seen first
+    pub async fn findings(&self, limit: i64) -> Result<Vec<ScrubFinding>, RepositoryError> {
+        self.scrub_repo.findings(limit).await
+    }
+
+    /// Resume the pass in progress, or start one if the last is older than
+    /// the interval
+    ///
+    /// Returns `None` when no pass was due.
+    pub async fn scrub_if_due(&self) -> Result<Option<ScrubPass>, ScrubError> {
+        let mut checkpoint = self.scrub_repo.load_checkpoint().await?;
+        *self.last_pass_completed_at.lock().unwrap() = checkpoint.last_pass_completed_at;
+
+        if checkpoint.cursor.is_none() && !self.is_due(&checkpoint) {
+            return Ok(None);
+        }
+
+        let mut pass = ScrubPass::default();
+        let mut throttle = Throttle::new(self.config.max_bytes_per_sec);
+        loop {
+            let blobs = self
+                .scrub_repo
+                .scan_blobs(checkpoint.cursor.clone(), self.config.batch_size)
+                .await?;
+            let Some(last) = blobs.last() else {
+                break;
+            };
+            let last_hash = last.content_hash().clone();
+
+            for blob in &blobs {
+                self.scrub_blob(blob, &mut throttle, &mut pass).await?;
+            }
+
+            checkpoint.cursor = Some(last_hash);
+            self.scrub_repo.save_checkpoint(&checkpoint).await?;
+
+            if (blobs.len() as i64) < self.config.batch_size {
+                break;
+            }
+        }
+
+        let completed_at = OffsetDateTime::now_utc();
+        self.scrub_repo
+            .save_checkpoint(&ScrubCheckpoint {
+                cursor: None,
+                last_pass_completed_at: Some(completed_at),
+            })
+            .await?;
+        *self.last_pass_completed_at.lock().unwrap() = Some(completed_at);
+
+        Ok(Some(pass))
+    }
+
+    /// Check for a due pass every poll interval, forever
+    pub async fn run(self: Arc<Self>) {
+        info!(
+            "Starting blob scrubber: a pass every {:?}, at most {} bytes/s",
+            self.config.interval, self.config.max_bytes_per_sec
+        );
+
+        let mut ticks = interval(self.config.poll_interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticks.tick().await;
+
+            match self.scrub_if_due().await {
+                Ok(Some(pass)) => info!(
+                    "Blob scrub pass complete: {} blobs ({} bytes) verified, {} corrupt, {} skipped",
+                    pass.blobs_scrubbed, pass.bytes_scrubbed, pass.corruptions, pass.skipped
+                ),
+                Ok(None) => {}
+                Err(e) => warn!("Blob scrub interrupted, resuming from checkpoint: {}", e),
+            }
+        }
+    }
+
+    fn is_due(&self, checkpoint: &ScrubCheckpoint) -> bool {
+        checkpoint
+            .last_pass_completed_at
+            .is_none_or(|completed_at| {
+                OffsetDateTime::now_utc() - completed_at >= self.config.interval
+            })
+    }
+
+    async fn scrub_blob(
+        &self,
+        blob: &Blob,
+        throttle: &mut Throttle,
+        pass: &mut ScrubPass,
+    ) -> Result<(), ScrubError> {
+        // Unreferenced blobs are about to be collected
+        if blob.ref_count() == 0 {
+            pass.skipped += 1;
+            self.skipped.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        match self.verify(blob, throttle).await {
+            Ok(bytes) => {
+                pass.blobs_scrubbed += 1;
+                pass.bytes_scrubbed += bytes;
+                self.blobs_scrubbed.fetch_add(1, Ordering::Relaxed);
+                self.bytes_scrubbed.fetch_add(bytes, Ordering::Relaxed);
+                self.scrub_repo.clear(blob.content_hash()).await?;
+            }
+            Err(StorageError::Restoring(_)) => {
+                pass.skipped += 1;
+                self.skipped.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(
+                e @ (StorageError::HashMismatch { .. }
+                | StorageError::NotFound(_)
+                | StorageError::Io(_)),
+            ) => {
+                error!(
+                    content_hash = %blob.content_hash(),
+                    storage_class = %blob.storage_class(),
+                    "Blob failed integrity scrub: {}", e
+                );
+                pass.corruptions += 1;
+                self.corruptions.fetch_add(1, Ordering::Relaxed);
+                self.scrub_repo
+                    .flag(blob.content_hash(), blob.storage_class(), &e.to_string())
+                    .await?;
+            }
+            // The backend is struggling; stop instead of flagging everything
+            Err(e) => return Err(e.into()),
+        }
+
+        Ok(())
+    }
+
+    /// Read the blob to the end and compare its hash; returns its length
+    async fn verify(&self, blob: &Blob, throttle: &mut Throttle) -> Result<u64, StorageError> {
+        let expected = blob.content_hash();
+        let mut reader = self.blob_store.read(expected, blob.storage_class()).await?;
+
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0u8; READ_CHUNK_BYTES];
+        let mut bytes = 0u64;
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            bytes += n as u64;
+            throttle.consume(n as u64).await;
+        }
+
+        let actual = hex::encode(hasher.finalize());
+        if actual != expected.as_hex() {
+            return Err(StorageError::HashMismatch {
+                expected: expected.as_hex().to_string(),
+                actual,
+            });
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// Paces reads to a byte rate averaged over the pass
+struct Throttle {
+    max_bytes_per_sec: u64,
+    started: Instant,
+    bytes: u64,
+}
+
+impl Throttle {
+    fn new(max_bytes_per_sec: u64) -> Self {
+        Self {
+            max_bytes_per_sec,
+            started: Instant::now(),
+            bytes: 0,
+        }
+    }
+
+    /// Sleep until `bytes` more are within the rate
+    async fn consume(&mut self, bytes: u64) {
+        self.bytes += bytes;
+        let allowed_at = Duration::from_secs_f64(self.bytes as f64 / self.max_bytes_per_sec as f64);
+        let elapsed = self.started.elapsed();
+        if allowed_at > elapsed {
+            tokio::time::sleep(allowed_at - elapsed).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{MockBlobStore, MockScrubRepository};
+    use crate::domain::value_objects::{ContentHash, StorageClass};
+    use std::io::Cursor;
+
+    fn hash_of(content: &[u8]) -> ContentHash {
+        ContentHash::from_hex(hex::encode(Sha256::digest(content))).unwrap()
+    }
+
+    fn blob(hash: ContentHash) -> Blob {
+        Blob::reconstruct(hash, StorageClass::Hot, 9, 1, OffsetDateTime::now_utc())
+    }
+
+    fn scrubber(scrub_repo: MockScrubRepository, blob_store: MockBlobStore) -> BlobScrubber {
+        BlobScrubber::new(
+            Arc::new(scrub_repo),
+            Arc::new(blob_store),
+            ScrubConfig::new(Duration::from_secs(3600), u64::MAX),
+        )
+    }
+
+    /// A repository holding `blobs`, with no pass ever completed
+    fn repo_with(blobs: Vec<Blob>) -> MockScrubRepository {
+        let mut scrub_repo = MockScrubRepository::new();
+        scrub_repo
+            .expect_load_checkpoint()
+            .returning(|| Ok(ScrubCheckpoint::default()));
+        scrub_repo
+            .expect_scan_blobs()
+            .withf(|after, _| after.is_none())
+            .times(1)
+            .returning(move |_, _| Ok(blobs.clone()));
+        scrub_repo
+    }
+
+    #[tokio::test]
+    async fn test_intact_blob_is_cleared() {
+        let hash = hash_of(b"test data");
+        let mut scrub_repo = repo_with(vec![blob(hash.clone())]);
+        let expected = hash.clone();
+        scrub_repo
+            .expect_clear()
+            .withf(move |h| *h == expected)
+            .times(1)
+            .returning(|_| Ok(()));
+        scrub_repo.expect_flag().never();
+        scrub_repo
+            .expect_save_checkpoint()
+            .times(2)
+            .returning(|_| Ok(()));
+        let mut blob_store = MockBlobStore::new();
+        blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"test data".to_vec()))));
+
+        let scrubber = scrubber(scrub_repo, blob_store);
+        let pass = scrubber.scrub_if_due().await.unwrap().unwrap();
+
+        assert_eq!((pass.blobs_scrubbed, pass.bytes_scrubbed), (1, 9));
+        assert_eq!(pass.corruptions, 0);
+        assert!(scrubber.stats().last_pass_completed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_and_missing_blobs_are_flagged_not_deleted() {
+        let corrupt = hash_of(b"test data");
+        let missing = hash_of(b"other data");
+        let mut scrub_repo = repo_with(vec![blob(corrupt.clone()), blob(missing.clone())]);
+        scrub_repo
+            .expect_flag()
+            .withf(move |h, _, error| {
+                (*h == corrupt && error.contains("mismatch"))
+                    || (*h == missing && error.contains("not found"))
+            })
+            .times(2)
+            .returning(|_, _, _| Ok(()));
+        scrub_repo.expect_clear().never();
+        scrub_repo
+            .expect_save_checkpoint()
+            .times(2)
+            .returning(|_| Ok(()));
+        let mut blob_store = MockBlobStore::new();
+        let corrupt_read = hash_of(b"test data");
+        blob_store.expect_read().returning(move |h, _| {
+            if *h == corrupt_read {
+                Ok(Box::pin(Cursor::new(b"test dat4".to_vec())))
+            } else {
+                Err(StorageError::NotFound(h.to_string()))
+            }
+        });
+        blob_store.expect_delete().never();
+
+        let scrubber = scrubber(scrub_repo, blob_store);
+        let pass = scrubber.scrub_if_due().await.unwrap().unwrap();
+
+        assert_eq!(pass.corruptions, 2);
+        assert_eq!(scrubber.stats().corruptions, 2);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_store_keeps_checkpoint() {
+        let mut scrub_repo = repo_with(vec![blob(hash_of(b"test data"))]);
+        scrub_repo.expect_flag().never();
+        scrub_repo.expect_save_checkpoint().never();
+        let mut blob_store = MockBlobStore::new();
+        blob_store.expect_read().returning(|_, _| {
+            Err(StorageError::Unavailable {
+                retry_after_secs: 30,
+            })
+        });
+
+        let scrubber = scrubber(scrub_repo, blob_store);
+
+        assert!(matches!(
+            scrubber.scrub_if_due().await,
+            Err(ScrubError::Storage(StorageError::Unavailable { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recent_pass_is_not_repeated() {
+        let mut scrub_repo = MockScrubRepository::new();
+        scrub_repo.expect_load_checkpoint().returning(|| {
+            Ok(ScrubCheckpoint {
+                cursor: None,
+                last_pass_completed_at: Some(OffsetDateTime::now_utc()),
+            })
+        });
+        scrub_repo.expect_scan_blobs().never();
+
+        let scrubber = scrubber(scrub_repo, MockBlobStore::new());
+
+        assert_eq!(scrubber.scrub_if_due().await.unwrap(), None);
+    }
+}
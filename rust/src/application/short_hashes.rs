@@ -0,0 +1,181 @@
+//! Short content hashes, like git short hashes
+//!
+//! With `CONTENT_HASH_SHORT_LEN` set, a content hash can be referred to by
+//! any prefix of it that matches a single blob, and responses report the
+//! shortest such prefix next to the full hash. Full hashes are accepted
+//! either way. Resolving or reporting a short hash costs a blob lookup, so
+//! it is opt-in.
+
+use std::sync::Arc;
+
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::{BlobRepository, RepositoryError};
+use crate::domain::entities::Blob;
+use crate::domain::value_objects::{ContentHash, ContentHashPrefix};
+
+/// Resolves and reports short content hashes against the blob entries
+#[derive(Clone)]
+pub struct ShortHashes {
+    blob_repo: Arc<dyn BlobRepository>,
+    /// Shortest reported short hash; 0 accepts and reports only full hashes
+    min_len: usize,
+}
+
+impl ShortHashes {
+    pub fn new(blob_repo: Arc<dyn BlobRepository>, min_len: usize) -> Self {
+        Self { blob_repo, min_len }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.min_len > 0
+    }
+
+    /// Full hash a reference stands for; `None` when a prefix matches no
+    /// blob
+    ///
+    /// A full hash is returned as is, without a lookup.
+    pub async fn resolve(
+        &self,
+        reference: &ContentHashPrefix,
+    ) -> Result<Option<ContentHash>, ObjectUseCaseError> {
+        if reference.is_full() {
+            return Ok(Some(ContentHash::from_hex(reference.to_string())?));
+        }
+        Ok(self
+            .find(reference)
+            .await?
+            .map(|blob| blob.content_hash().clone()))
+    }
+
+    /// Blob entry a full hash, or a unique prefix of one, refers to
+    ///
+    /// A prefix is rejected while short hashes are off, and when it matches
+    /// several blobs.
+    pub async fn find(
+        &self,
+        reference: &ContentHashPrefix,
+    ) -> Result<Option<Blob>, ObjectUseCaseError> {
+        if !reference.is_full() && !self.is_enabled() {
+            return Err(ObjectUseCaseError::InvalidRequest(
+                "Short content hashes are disabled; send all 64 hex characters".to_string(),
+            ));
+        }
+
+        // Two rows are enough to tell a unique prefix from an ambiguous one
+        let mut blobs = self.blob_repo.find_by_prefix(reference, 2).await?;
+        if blobs.len() > 1 {
+            return Err(ObjectUseCaseError::InvalidRequest(format!(
+                "Ambiguous content hash prefix '{reference}': it matches several blobs"
+            )));
+        }
+        Ok(blobs.pop())
+    }
+
+    /// Short hash to report next to `hash`; `None` while short hashes are
+    /// off or without a hash
+    pub async fn short_hash(
+        &self,
+        hash: Option<&ContentHash>,
+    ) -> Result<Option<String>, RepositoryError> {
+        match hash {
+            Some(hash) if self.is_enabled() => Ok(Some(self.shortest_unique_prefix(hash).await?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Shortest prefix of `hash`, of at least `min_len` characters, that
+    /// matches no other blob
+    ///
+    /// Usually settled by the first query; each collision lengthens the
+    /// prefix past the part both hashes share.
+    async fn shortest_unique_prefix(&self, hash: &ContentHash) -> Result<String, RepositoryError> {
+        let mut len = self.min_len;
+        while len < 64 {
+            let others: Vec<_> = self
+                .blob_repo
+                .find_by_prefix(&hash.short_prefix(len), 2)
+                .await?
+                .into_iter()
+                .filter(|blob| blob.content_hash() != hash)
+                .collect();
+            let Some(other) = others.first() else {
+                break;
+            };
+            let shared = hash
+                .as_hex()
+                .bytes()
+                .zip(other.content_hash().as_hex().bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            len = (shared + 1).max(len + 1);
+        }
+        Ok(hash.short(len).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockBlobRepository;
+    use crate::domain::value_objects::StorageClass;
+    use std::str::FromStr;
+    use time::OffsetDateTime;
+
+    fn blob(hex: &str) -> Blob {
+        Blob::reconstruct(
+            ContentHash::from_str(hex).unwrap(),
+            StorageClass::Hot,
+            42,
+            1,
+            OffsetDateTime::now_utc(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resolve_full_hash_without_lookup() {
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_find_by_prefix().never();
+        let short_hashes = ShortHashes::new(Arc::new(mock_blob_repo), 0);
+        let hex = "ab".repeat(32);
+
+        let hash = short_hashes
+            .resolve(&ContentHashPrefix::from_str(&hex).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(hash.map(|hash| hash.to_string()), Some(hex));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_prefix_only_when_enabled() {
+        let hex = "abcd".to_string() + &"0".repeat(60);
+        let stored = blob(&hex);
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_find_by_prefix()
+            .returning(move |_, _| Ok(vec![stored.clone()]));
+        let blob_repo: Arc<dyn BlobRepository> = Arc::new(mock_blob_repo);
+        let prefix = ContentHashPrefix::from_str("abcd").unwrap();
+
+        let disabled = ShortHashes::new(Arc::clone(&blob_repo), 0);
+        assert!(matches!(
+            disabled.resolve(&prefix).await,
+            Err(ObjectUseCaseError::InvalidRequest(_))
+        ));
+        assert_eq!(
+            disabled
+                .short_hash(Some(&ContentHash::from_str(&hex).unwrap()))
+                .await
+                .unwrap(),
+            None
+        );
+
+        let enabled = ShortHashes::new(blob_repo, 4);
+        let hash = enabled.resolve(&prefix).await.unwrap().unwrap();
+        assert_eq!(hash.to_string(), hex);
+        assert_eq!(
+            enabled.short_hash(Some(&hash)).await.unwrap().as_deref(),
+            Some("abcd")
+        );
+    }
+}
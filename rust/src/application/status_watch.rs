@@ -0,0 +1,171 @@
+//! Notifications of finished uploads
+//!
+//! Clients waiting for an upload to finish subscribe to its object ID, and
+//! the upload publishes its outcome when it commits or fails. A waiter
+//! therefore wakes as soon as the status changes instead of polling the
+//! database.
+//!
+//! Notifications are in-process: a waiter is only woken by uploads handled
+//! by the same instance, and otherwise sees the outcome when its wait ends.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use tokio::sync::watch;
+
+use crate::application::dto::UploadStatus;
+use crate::domain::value_objects::ObjectId;
+
+type StatusSender = Arc<watch::Sender<Option<UploadStatus>>>;
+
+/// Wakes waiters on the outcome of uploads, keyed by object ID
+#[derive(Default)]
+pub struct StatusWatch {
+    senders: DashMap<ObjectId, StatusSender>,
+}
+
+impl StatusWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start waiting for the outcome of the upload of `object_id`
+    ///
+    /// Subscribe before reading the stored status, so an outcome published
+    /// in between is not missed.
+    pub fn subscribe(self: &Arc<Self>, object_id: ObjectId) -> StatusSubscription {
+        // Subscribe while holding the entry, so a departing waiter cannot
+        // remove the channel in between
+        let entry = self
+            .senders
+            .entry(object_id)
+            .or_insert_with(|| Arc::new(watch::channel(None).0));
+        let sender = Arc::clone(entry.value());
+        let receiver = sender.subscribe();
+        drop(entry);
+
+        StatusSubscription {
+            watch: Arc::clone(self),
+            object_id,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Wake everyone waiting for the upload of `object_id` with its outcome
+    pub fn publish(&self, object_id: &ObjectId, status: UploadStatus) {
+        if let Some((_, sender)) = self.senders.remove(object_id) {
+            sender.send_replace(Some(status));
+        }
+    }
+
+    /// Number of objects with waiters
+    pub fn watched(&self) -> usize {
+        self.senders.len()
+    }
+}
+
+/// A waiter for the outcome of one upload
+pub struct StatusSubscription {
+    watch: Arc<StatusWatch>,
+    object_id: ObjectId,
+    sender: StatusSender,
+    receiver: watch::Receiver<Option<UploadStatus>>,
+}
+
+impl StatusSubscription {
+    /// Wait up to `timeout` for the upload's outcome
+    ///
+    /// Returns `None` when nothing was published in time.
+    pub async fn wait(&mut self, timeout: Duration) -> Option<UploadStatus> {
+        match tokio::time::timeout(timeout, self.receiver.wait_for(Option::is_some)).await {
+            Ok(Ok(status)) => *status,
+            _ => None,
+        }
+    }
+}
+
+impl Drop for StatusSubscription {
+    fn drop(&mut self) {
+        // The last waiter removes the channel, unless a publish already did
+        // and a new one took its place
+        self.watch.senders.remove_if(&self.object_id, |_, sender| {
+            Arc::ptr_eq(sender, &self.sender) && sender.receiver_count() <= 1
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_wakes_waiters() {
+        let watch = Arc::new(StatusWatch::new());
+        let object_id = ObjectId::new();
+        let mut first = watch.subscribe(object_id);
+        let mut second = watch.subscribe(object_id);
+
+        let publisher = Arc::clone(&watch);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            publisher.publish(&object_id, UploadStatus::Committed);
+        });
+
+        assert_eq!(
+            first.wait(Duration::from_secs(5)).await,
+            Some(UploadStatus::Committed)
+        );
+        assert_eq!(
+            second.wait(Duration::from_secs(5)).await,
+            Some(UploadStatus::Committed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out_without_publish() {
+        let watch = Arc::new(StatusWatch::new());
+        let mut subscription = watch.subscribe(ObjectId::new());
+
+        assert_eq!(subscription.wait(Duration::from_millis(10)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_publish_for_other_object_is_ignored() {
+        let watch = Arc::new(StatusWatch::new());
+        let mut subscription = watch.subscribe(ObjectId::new());
+
+        watch.publish(&ObjectId::new(), UploadStatus::Failed);
+
+        assert_eq!(subscription.wait(Duration::from_millis(10)).await, None);
+    }
+
+    #[test]
+    fn test_last_waiter_removes_channel() {
+        let watch = Arc::new(StatusWatch::new());
+        let object_id = ObjectId::new();
+
+        let first = watch.subscribe(object_id);
+        let second = watch.subscribe(object_id);
+        assert_eq!(watch.watched(), 1);
+
+        drop(first);
+        assert_eq!(watch.watched(), 1);
+        drop(second);
+        assert_eq!(watch.watched(), 0);
+    }
+
+    #[test]
+    fn test_stale_waiter_keeps_new_channel() {
+        let watch = Arc::new(StatusWatch::new());
+        let object_id = ObjectId::new();
+
+        let stale = watch.subscribe(object_id);
+        watch.publish(&object_id, UploadStatus::Failed);
+        let _current = watch.subscribe(object_id);
+
+        drop(stale);
+        assert_eq!(watch.watched(), 1);
+    }
+}
@@ -2,21 +2,32 @@ use std::sync::Arc;
 
 use crate::application::{
     dto::{ApiKeyDto, ApiKeyListResponse, CreateApiKeyRequest, UpdateApiKeyRequest},
+    pagination::{PageLimits, Pagination},
     ports::{ApiKeyRepository, ApiKeyRepositoryError},
 };
 use crate::domain::{
     entities::ApiKey,
-    value_objects::{ApiKeyId, ApiKeyPermissions},
+    value_objects::{ApiKeyHashAlgorithm, ApiKeyId, ApiKeyPermissions},
 };
 
 /// Use case for creating API keys
 pub struct CreateApiKeyUseCase {
     repository: Arc<dyn ApiKeyRepository>,
+    hash_algorithm: ApiKeyHashAlgorithm,
 }
 
 impl CreateApiKeyUseCase {
     pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            hash_algorithm: ApiKeyHashAlgorithm::default(),
+        }
+    }
+
+    /// Hash new secrets with `hash_algorithm` instead of Argon2
+    pub fn with_hash_algorithm(mut self, hash_algorithm: ApiKeyHashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
     }
 
     pub async fn execute(
@@ -34,6 +45,7 @@ impl CreateApiKeyUseCase {
             request.description,
             permissions,
             request.expires_at,
+            self.hash_algorithm,
         );
 
         self.repository.create(api_key.clone()).await?;
@@ -59,12 +71,11 @@ impl ListApiKeysUseCase {
         limit: Option<i64>,
         offset: Option<i64>,
     ) -> Result<ApiKeyListResponse, ApiKeyUseCaseError> {
-        let limit = limit.unwrap_or(50).clamp(1, 100);
-        let offset = offset.unwrap_or(0).max(0);
+        let page = Pagination::new(limit, offset, PageLimits::API_KEYS);
 
         let api_keys = self
             .repository
-            .list_by_tenant(&tenant_id, limit, offset)
+            .list_by_tenant(&tenant_id, page.limit, page.offset)
             .await?;
         let total = self.repository.count_by_tenant(&tenant_id).await?;
 
@@ -199,6 +210,59 @@ impl DeleteApiKeyUseCase {
     }
 }
 
+/// Use case for issuing a new secret for an existing API key
+pub struct RotateApiKeyUseCase {
+    repository: Arc<dyn ApiKeyRepository>,
+    hash_algorithm: ApiKeyHashAlgorithm,
+}
+
+impl RotateApiKeyUseCase {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self {
+            repository,
+            hash_algorithm: ApiKeyHashAlgorithm::default(),
+        }
+    }
+
+    /// Hash new secrets with `hash_algorithm` instead of Argon2
+    pub fn with_hash_algorithm(mut self, hash_algorithm: ApiKeyHashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// Replace the key's secret, keeping its ID and permissions
+    ///
+    /// The new secret is returned in `key` this once; the old one stops
+    /// validating as soon as this returns.
+    pub async fn execute(
+        &self,
+        tenant_id: &str,
+        api_key_id: &str,
+    ) -> Result<ApiKeyDto, ApiKeyUseCaseError> {
+        let id = api_key_id
+            .parse::<ApiKeyId>()
+            .map_err(|_| ApiKeyUseCaseError::InvalidId(api_key_id.to_string()))?;
+
+        let mut api_key = self
+            .repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| ApiKeyUseCaseError::NotFound(api_key_id.to_string()))?;
+
+        // Check tenant ownership
+        if api_key.tenant_id() != tenant_id {
+            return Err(ApiKeyUseCaseError::NotFound(api_key_id.to_string()));
+        }
+
+        let plain_key = api_key.rotate_secret(self.hash_algorithm);
+        self.repository.update_secret(&api_key).await?;
+
+        let mut dto = ApiKeyDto::from(api_key);
+        dto.key = Some(plain_key);
+        Ok(dto)
+    }
+}
+
 /// Use case errors
 #[derive(Debug, thiserror::Error)]
 pub enum ApiKeyUseCaseError {
@@ -231,6 +295,7 @@ mod tests {
             async fn list_by_tenant(&self, tenant_id: &str, limit: i64, offset: i64) -> Result<Vec<ApiKey>, ApiKeyRepositoryError>;
             async fn count_by_tenant(&self, tenant_id: &str) -> Result<i64, ApiKeyRepositoryError>;
             async fn update(&self, api_key: &ApiKey) -> Result<(), ApiKeyRepositoryError>;
+            async fn update_secret(&self, api_key: &ApiKey) -> Result<(), ApiKeyRepositoryError>;
             async fn delete(&self, id: &ApiKeyId) -> Result<(), ApiKeyRepositoryError>;
             async fn mark_used(&self, id: &ApiKeyId) -> Result<(), ApiKeyRepositoryError>;
             async fn cleanup_expired(&self) -> Result<i64, ApiKeyRepositoryError>;
@@ -327,6 +392,7 @@ mod tests {
                     Some("Description 1".to_string()),
                     ApiKeyPermissions::read_only(),
                     None,
+                    ApiKeyHashAlgorithm::Sha256,
                 ).0,
                 ApiKey::new(
                     "tenant-123".to_string(),
@@ -334,6 +400,7 @@ mod tests {
                     None,
                     ApiKeyPermissions::full_access(),
                     None,
+                    ApiKeyHashAlgorithm::Sha256,
                 ).0,
             ];
 
@@ -368,6 +435,7 @@ mod tests {
                 None,
                 ApiKeyPermissions::read_only(),
                 None,
+                ApiKeyHashAlgorithm::Sha256,
             ).0];
 
             let mut mock_repo = MockApiKeyRepositoryImpl::new();
@@ -391,6 +459,34 @@ mod tests {
             assert!(result.is_ok());
         }
 
+        #[tokio::test]
+        async fn test_list_api_keys_clamps_out_of_range_page() {
+            let mut mock_repo = MockApiKeyRepositoryImpl::new();
+            mock_repo
+                .expect_count_by_tenant()
+                .times(2)
+                .returning(|_| Ok(0));
+            mock_repo
+                .expect_list_by_tenant()
+                .with(eq("tenant-123"), eq(100), eq(0))
+                .times(1)
+                .returning(|_, _, _| Ok(vec![]));
+            mock_repo
+                .expect_list_by_tenant()
+                .with(eq("tenant-123"), eq(1), eq(0))
+                .times(1)
+                .returning(|_, _, _| Ok(vec![]));
+
+            let use_case = ListApiKeysUseCase::new(Arc::new(mock_repo));
+
+            for (limit, offset) in [(500, -3), (0, -1)] {
+                let result = use_case
+                    .execute("tenant-123".to_string(), Some(limit), Some(offset))
+                    .await;
+                assert!(result.is_ok());
+            }
+        }
+
         #[tokio::test]
         async fn test_list_api_keys_empty() {
             let mut mock_repo = MockApiKeyRepositoryImpl::new();
@@ -425,6 +521,7 @@ mod tests {
                 Some("Test description".to_string()),
                 ApiKeyPermissions::read_only(),
                 None,
+                ApiKeyHashAlgorithm::Sha256,
             ).0;
             let api_key_id = *api_key.id();
 
@@ -482,6 +579,7 @@ mod tests {
                 Some("Original description".to_string()),
                 ApiKeyPermissions::read_only(),
                 None,
+                ApiKeyHashAlgorithm::Sha256,
             ).0;
             let api_key_id = *api_key.id();
 
@@ -558,6 +656,7 @@ mod tests {
                 None,
                 ApiKeyPermissions::read_only(),
                 None,
+                ApiKeyHashAlgorithm::Sha256,
             ).0;
             let api_key_id = *api_key.id();
 
@@ -591,6 +690,7 @@ mod tests {
                 None,
                 ApiKeyPermissions::read_only(),
                 None,
+                ApiKeyHashAlgorithm::Sha256,
             ).0;
             let api_key_id = *api_key.id();
 
@@ -620,4 +720,76 @@ mod tests {
             ));
         }
     }
+
+    mod rotate_api_key_tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_rotate_api_key_issues_new_secret() {
+            let (api_key, old_key) = ApiKey::new(
+                "tenant-123".to_string(),
+                "Test Key".to_string(),
+                None,
+                ApiKeyPermissions::read_only(),
+                None,
+                ApiKeyHashAlgorithm::Sha256,
+            );
+            let api_key_id = *api_key.id();
+
+            let mut mock_repo = MockApiKeyRepositoryImpl::new();
+            mock_repo
+                .expect_find_by_id()
+                .with(eq(api_key_id))
+                .times(1)
+                .returning(move |_| Ok(Some(api_key.clone())));
+            // The stored hash must reject the old secret
+            mock_repo
+                .expect_update_secret()
+                .withf(move |stored| {
+                    *stored.id() == api_key_id && !stored.verify_secret(&old_key)
+                })
+                .times(1)
+                .returning(|_| Ok(()));
+
+            let use_case = RotateApiKeyUseCase::new(Arc::new(mock_repo))
+                .with_hash_algorithm(ApiKeyHashAlgorithm::Sha256);
+
+            let dto = use_case
+                .execute("tenant-123", &api_key_id.to_string())
+                .await
+                .unwrap();
+
+            assert_eq!(dto.id, api_key_id.to_string());
+            assert_eq!(dto.permissions, ApiKeyPermissions::read_only());
+            assert!(dto.key.is_some());
+        }
+
+        #[tokio::test]
+        async fn test_rotate_api_key_of_other_tenant_not_found() {
+            let api_key = ApiKey::new(
+                "tenant-123".to_string(),
+                "Test Key".to_string(),
+                None,
+                ApiKeyPermissions::read_only(),
+                None,
+                ApiKeyHashAlgorithm::Sha256,
+            )
+            .0;
+            let api_key_id = *api_key.id();
+
+            let mut mock_repo = MockApiKeyRepositoryImpl::new();
+            mock_repo
+                .expect_find_by_id()
+                .returning(move |_| Ok(Some(api_key.clone())));
+            mock_repo.expect_update_secret().never();
+
+            let use_case = RotateApiKeyUseCase::new(Arc::new(mock_repo));
+
+            let result = use_case
+                .execute("tenant-456", &api_key_id.to_string())
+                .await;
+
+            assert!(matches!(result, Err(ApiKeyUseCaseError::NotFound(_))));
+        }
+    }
 }
@@ -0,0 +1,374 @@
+use std::sync::Arc;
+
+use crate::application::dto::{
+    BulkUpdateMetadataReport, BulkUpdateMetadataRequest, OperationItem, SortDirection, SortField,
+};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::key_prefix_query::KeyPrefixQuery;
+use crate::application::list_cursor::ListCursor;
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::operation_progress::OperationReporter;
+use crate::application::pagination::PageLimits;
+use crate::application::ports::ObjectRepository;
+use crate::application::use_cases::UpdateObjectMetadataUseCase;
+use crate::application::validation::validate_namespace_and_tenant;
+use crate::domain::errors::DomainError;
+
+/// Default number of objects patched per batch
+pub const DEFAULT_BULK_METADATA_BATCH_SIZE: usize = 500;
+/// Default number of batches of updates one run makes before returning
+pub const DEFAULT_BULK_METADATA_MAX_BATCHES: u64 = 20;
+
+/// Use case: Apply one metadata patch to every object matching a search
+/// filter (admin only)
+///
+/// Each object is patched like `PATCH /v1/objects/{id}/metadata`: only the
+/// metadata record changes, never the blob or content hash, and objects
+/// under retention or legal hold are skipped. Matching objects are fetched
+/// and patched a page at a time, oldest first; each page continues from a
+/// cursor on the last object of the one before rather than an offset, so a
+/// patch that makes objects stop matching the filter cannot make the run
+/// skip others.
+///
+/// A run stops once it has written its batch budget. Patching is
+/// idempotent, so running it again passes over the objects already patched
+/// as unchanged and continues with the rest.
+pub struct BulkUpdateMetadataUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+    update: Arc<UpdateObjectMetadataUseCase>,
+    batch_size: usize,
+    max_batches: u64,
+}
+
+impl BulkUpdateMetadataUseCase {
+    pub fn new(
+        object_repo: Arc<dyn ObjectRepository>,
+        update: Arc<UpdateObjectMetadataUseCase>,
+    ) -> Self {
+        Self {
+            object_repo,
+            update,
+            batch_size: DEFAULT_BULK_METADATA_BATCH_SIZE,
+            max_batches: DEFAULT_BULK_METADATA_MAX_BATCHES,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Stop each run after updating `max_batches` batches of objects
+    pub fn with_max_batches(mut self, max_batches: u64) -> Self {
+        self.max_batches = max_batches.max(1);
+        self
+    }
+
+    /// Patch the matching objects of `namespace`, reporting processed
+    /// objects and each batch to `progress`; finishing the operation is left
+    /// to the caller
+    #[tracing::instrument(
+        name = "BulkUpdateMetadataUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(namespace = %namespace, tenant_id = %request.tenant_id)
+    )]
+    pub async fn execute_reporting(
+        &self,
+        namespace: String,
+        request: &BulkUpdateMetadataRequest,
+        progress: &OperationReporter,
+    ) -> Result<BulkUpdateMetadataReport, ObjectUseCaseError> {
+        // 1. Validate the target, the filters and the patch
+        let (namespace, tenant_id) = validate_namespace_and_tenant(&namespace, &request.tenant_id)?;
+        let keys = KeyPrefixQuery::new(request.key_prefix.clone(), None)
+            .map_err(ObjectUseCaseError::InvalidRequest)?;
+        let metadata = MetadataQuery::new(
+            request.metadata_filters.clone(),
+            request.metadata_has_keys.as_deref().unwrap_or_default(),
+        )
+        .map_err(ObjectUseCaseError::InvalidRequest)?;
+        self.update.validate_patch(&request.patch)?;
+
+        // 2. Patch the matching objects a page at a time until the write
+        //    budget is spent
+        let mut report = BulkUpdateMetadataReport {
+            namespace: namespace.to_string(),
+            tenant_id: tenant_id.to_string(),
+            ..Default::default()
+        };
+        let search = request.search(
+            namespace.as_str(),
+            (self.batch_size as i64).min(PageLimits::OBJECTS.max),
+        );
+        let budget = self.batch_size as u64 * self.max_batches;
+        let mut after = None;
+        while report.updated < budget {
+            let page = self
+                .object_repo
+                .search(&search, &keys, &metadata, after.clone())
+                .await?;
+            let Some(last) = page.last() else {
+                report.completed = true;
+                break;
+            };
+            after = Some(ListCursor::after(
+                last,
+                SortField::CreatedAt,
+                SortDirection::Asc,
+            ));
+            report.matched += page.len() as u64;
+            report.batches += 1;
+            let before = report.clone();
+            for object in &page {
+                let object_id = object.id();
+                match self
+                    .update
+                    .apply(object_id, &tenant_id, &request.patch)
+                    .await
+                {
+                    Ok((_, true)) => report.updated += 1,
+                    Ok((_, false)) => report.unchanged += 1,
+                    Err(ObjectUseCaseError::Domain(DomainError::ObjectLocked(_))) => {
+                        report.skipped_locked += 1
+                    }
+                    Err(
+                        e @ (ObjectUseCaseError::NotFound(_)
+                        | ObjectUseCaseError::Conflict(_)
+                        | ObjectUseCaseError::Validation(_)
+                        | ObjectUseCaseError::Domain(_)),
+                    ) => {
+                        tracing::warn!(%object_id, error = %e, "Bulk metadata update skipped object");
+                        report.failed += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            progress.advance(page.len() as u64);
+            progress.item(OperationItem {
+                name: format!("batch {}", report.batches),
+                status: "patched".to_string(),
+                detail: Some(format!(
+                    "{} updated, {} unchanged, {} locked, {} failed",
+                    report.updated - before.updated,
+                    report.unchanged - before.unchanged,
+                    report.skipped_locked - before.skipped_locked,
+                    report.failed - before.failed,
+                )),
+            });
+            if (page.len() as i64) < search.limit.unwrap_or_default() {
+                report.completed = true;
+                break;
+            }
+        }
+
+        tracing::info!(
+            %namespace,
+            %tenant_id,
+            completed = report.completed,
+            matched = report.matched,
+            updated = report.updated,
+            unchanged = report.unchanged,
+            skipped_locked = report.skipped_locked,
+            failed = report.failed,
+            "Bulk metadata update finished"
+        );
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockObjectRepository;
+    use crate::domain::entities::Object;
+    use crate::domain::value_objects::{ContentHash, Namespace, ObjectId, StorageClass, TenantId};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn committed_object(tenant_id: &TenantId) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("logs").unwrap(),
+            tenant_id.clone(),
+            Some(format!("2026/{}.log", Uuid::new_v4())),
+            StorageClass::Hot,
+        );
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 42)
+            .unwrap();
+        object
+    }
+
+    fn request(tenant_id: &TenantId) -> BulkUpdateMetadataRequest {
+        BulkUpdateMetadataRequest {
+            tenant_id: tenant_id.to_string(),
+            patch: serde_json::json!({ "tags": { "archived": "true" } }),
+            key_prefix: Some("2026/".to_string()),
+            key_contains: None,
+            content_type: None,
+            storage_class: None,
+            metadata_filters: None,
+            metadata_has_keys: None,
+        }
+    }
+
+    /// Repository serving `objects`, in order, to search by cursor and by ID
+    fn repo(objects: Vec<Object>) -> MockObjectRepository {
+        let by_id: HashMap<ObjectId, Object> = objects
+            .iter()
+            .map(|object| (*object.id(), object.clone()))
+            .collect();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_search()
+            .withf(|request, _, _, _| request.key_prefix.as_deref() == Some("2026/"))
+            .returning(move |request, _, _, after| {
+                let start = after.map_or(0, |after| {
+                    objects
+                        .iter()
+                        .position(|object| *object.id() == after.id)
+                        .unwrap()
+                        + 1
+                });
+                Ok(objects
+                    .iter()
+                    .skip(start)
+                    .take(request.limit.unwrap() as usize)
+                    .cloned()
+                    .collect())
+            });
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(by_id.get(id).cloned()));
+        mock_object_repo
+    }
+
+    fn use_case(mock_object_repo: MockObjectRepository) -> BulkUpdateMetadataUseCase {
+        let object_repo: Arc<dyn ObjectRepository> = Arc::new(mock_object_repo);
+        BulkUpdateMetadataUseCase::new(
+            Arc::clone(&object_repo),
+            Arc::new(UpdateObjectMetadataUseCase::new(object_repo)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_patches_matching_objects_and_skips_locked() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let mut locked = committed_object(&tenant_id);
+        locked.set_legal_hold(true).unwrap();
+        let objects = vec![
+            committed_object(&tenant_id),
+            locked,
+            committed_object(&tenant_id),
+        ];
+        let mut mock_object_repo = repo(objects);
+        mock_object_repo
+            .expect_update_metadata_if_match()
+            .withf(|object, _| {
+                object.metadata().tags.get("archived") == Some(&serde_json::json!("true"))
+                    && object.content_hash().is_some()
+            })
+            .times(2)
+            .returning(|_, _| Ok(true));
+
+        let report = use_case(mock_object_repo)
+            .execute_reporting(
+                "logs".to_string(),
+                &request(&tenant_id),
+                &OperationReporter::detached(),
+            )
+            .await
+            .unwrap();
+
+        assert!(report.completed);
+        assert_eq!(report.matched, 3);
+        assert_eq!(report.updated, 2);
+        assert_eq!(report.skipped_locked, 1);
+        assert_eq!(report.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stops_after_budget_and_resumes_past_unchanged() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let mut patched = committed_object(&tenant_id);
+        let mut metadata = patched.metadata().clone();
+        metadata
+            .tags
+            .insert("archived".to_string(), serde_json::json!("true"));
+        patched.set_metadata(metadata);
+        let objects = vec![
+            patched,
+            committed_object(&tenant_id),
+            committed_object(&tenant_id),
+        ];
+        let mut mock_object_repo = repo(objects);
+        mock_object_repo
+            .expect_update_metadata_if_match()
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let report = use_case(mock_object_repo)
+            .with_batch_size(1)
+            .with_max_batches(1)
+            .execute_reporting(
+                "logs".to_string(),
+                &request(&tenant_id),
+                &OperationReporter::detached(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!report.completed);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.batches, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pages_through_matches_by_cursor() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let objects = (0..3).map(|_| committed_object(&tenant_id)).collect();
+        let mut mock_object_repo = repo(objects);
+        mock_object_repo
+            .expect_update_metadata_if_match()
+            .times(3)
+            .returning(|_, _| Ok(true));
+
+        let report = use_case(mock_object_repo)
+            .with_batch_size(1)
+            .execute_reporting(
+                "logs".to_string(),
+                &request(&tenant_id),
+                &OperationReporter::detached(),
+            )
+            .await
+            .unwrap();
+
+        assert!(report.completed);
+        assert_eq!(report.matched, 3);
+        assert_eq!(report.updated, 3);
+        assert_eq!(report.batches, 3);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_patch_is_rejected_before_searching() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_search().never();
+        let tenant_id = TenantId::new(Uuid::new_v4());
+
+        let result = use_case(mock_object_repo)
+            .execute_reporting(
+                "logs".to_string(),
+                &BulkUpdateMetadataRequest {
+                    patch: serde_json::json!(["not", "an", "object"]),
+                    ..request(&tenant_id)
+                },
+                &OperationReporter::detached(),
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+}
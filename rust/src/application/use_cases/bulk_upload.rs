@@ -0,0 +1,582 @@
+use std::io;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures_util::StreamExt;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek};
+use tokio::sync::mpsc;
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use tokio_util::io::StreamReader;
+
+use crate::application::dto::{
+    ArchiveFormat, BulkUploadEntry, BulkUploadEntryStatus, BulkUploadManifest, BulkUploadRequest,
+    ObjectDto, OperationItem, UploadRequest,
+};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::operation_progress::OperationReporter;
+use crate::application::ports::BlobReader;
+use crate::application::use_cases::UploadObjectUseCase;
+use crate::application::validation::{archive_entry_key, validate_namespace_and_tenant};
+
+/// Default cap on entries processed from a single archive
+pub const DEFAULT_MAX_ARCHIVE_ENTRIES: usize = 10_000;
+
+/// Read size when feeding an entry into the blob store
+const ENTRY_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Use case: Unpack a tar or zip archive into objects
+///
+/// Each regular file becomes an object keyed by its path inside the
+/// archive. The archive is parsed as it streams in, or for a buffered zip
+/// archive from its central directory, and each entry is written through the
+/// regular upload path, so identical entries share a blob by content hash.
+pub struct BulkUploadUseCase {
+    upload_use_case: Arc<UploadObjectUseCase>,
+    max_entries: usize,
+}
+
+impl BulkUploadUseCase {
+    pub fn new(upload_use_case: Arc<UploadObjectUseCase>) -> Self {
+        Self {
+            upload_use_case,
+            max_entries: DEFAULT_MAX_ARCHIVE_ENTRIES,
+        }
+    }
+
+    /// Limit the number of entries processed per archive
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Most entries processed per archive
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Unpack `reader` and return a manifest with the outcome of every entry
+    ///
+    /// Per-entry failures are reported in the manifest; the total size of
+    /// extracted content is capped at the upload size limit so a small
+    /// compressed archive cannot expand without bound.
+    pub async fn execute<R>(
+        &self,
+        request: BulkUploadRequest,
+        format: ArchiveFormat,
+        reader: R,
+    ) -> Result<BulkUploadManifest, ObjectUseCaseError>
+    where
+        R: AsyncBufRead + Unpin + Send,
+    {
+        self.execute_reporting(request, format, reader, &OperationReporter::detached())
+            .await
+    }
+
+    /// [`Self::execute`], reporting each entry's outcome to `progress`
+    ///
+    /// Finishing the operation is left to the caller, which also counts the
+    /// archive bytes read.
+    pub async fn execute_reporting<R>(
+        &self,
+        request: BulkUploadRequest,
+        format: ArchiveFormat,
+        reader: R,
+        progress: &OperationReporter,
+    ) -> Result<BulkUploadManifest, ObjectUseCaseError>
+    where
+        R: AsyncBufRead + Unpin + Send,
+    {
+        validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
+
+        let mut budget = self.upload_use_case.max_upload_size_bytes();
+        let mut manifest = Manifest {
+            manifest: BulkUploadManifest::default(),
+            progress,
+        };
+        let result = match format {
+            ArchiveFormat::Tar => {
+                self.unpack_tar(&request, reader, &mut budget, &mut manifest)
+                    .await
+            }
+            ArchiveFormat::Zip => {
+                self.unpack_zip(&request, reader, &mut budget, &mut manifest)
+                    .await
+            }
+        };
+        manifest.finish(result)
+    }
+
+    /// Unpack a buffered zip archive, reporting each entry's outcome to
+    /// `progress`
+    ///
+    /// Entries are listed from the central directory at the end of the
+    /// archive rather than from the local headers as it streams in, so
+    /// entries whose sizes only follow their data (stored entries written by
+    /// streaming archivers) can be read too. Finishing the operation is left
+    /// to the caller.
+    pub async fn execute_zip_reporting<R>(
+        &self,
+        request: BulkUploadRequest,
+        reader: R,
+        progress: &OperationReporter,
+    ) -> Result<BulkUploadManifest, ObjectUseCaseError>
+    where
+        R: AsyncBufRead + AsyncSeek + Unpin + Send,
+    {
+        validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
+
+        let mut budget = self.upload_use_case.max_upload_size_bytes();
+        let mut manifest = Manifest {
+            manifest: BulkUploadManifest::default(),
+            progress,
+        };
+        let result = self
+            .unpack_indexed_zip(&request, reader, &mut budget, &mut manifest)
+            .await;
+        manifest.finish(result)
+    }
+
+    /// Largest archive accepted, which is also the cap on extracted content
+    pub fn max_archive_size_bytes(&self) -> u64 {
+        self.upload_use_case.max_upload_size_bytes()
+    }
+
+    async fn unpack_tar<R>(
+        &self,
+        request: &BulkUploadRequest,
+        reader: R,
+        budget: &mut u64,
+        manifest: &mut Manifest<'_>,
+    ) -> Result<(), String>
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        let mut archive = tokio_tar::Archive::new(reader);
+        let mut entries = archive.entries().map_err(|e| e.to_string())?;
+
+        while let Some(entry) = entries.next().await {
+            let mut entry = entry.map_err(|e| e.to_string())?;
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_dir() {
+                continue;
+            }
+            self.check_entry_limit(manifest)?;
+
+            let path = entry
+                .path()
+                .map(|path| path.to_string_lossy().into_owned())
+                .map_err(|e| e.to_string())?;
+            if !entry_type.is_file() {
+                manifest.push(BulkUploadEntry::unsuccessful(
+                    path,
+                    BulkUploadEntryStatus::Skipped,
+                    "Not a regular file".to_string(),
+                ));
+                continue;
+            }
+
+            // Unread entry data is skipped when advancing to the next entry
+            let outcome = self.store_entry(request, path, &mut entry, budget).await;
+            manifest.push(outcome);
+        }
+
+        Ok(())
+    }
+
+    async fn unpack_zip<R>(
+        &self,
+        request: &BulkUploadRequest,
+        reader: R,
+        budget: &mut u64,
+        manifest: &mut Manifest<'_>,
+    ) -> Result<(), String>
+    where
+        R: AsyncBufRead + Unpin + Send,
+    {
+        let mut zip = async_zip::base::read::stream::ZipFileReader::with_tokio(reader);
+
+        while let Some(mut reading) = zip.next_with_entry().await.map_err(|e| e.to_string())? {
+            let entry = reading.reader().entry();
+            let path = entry
+                .filename()
+                .as_str()
+                .map(str::to_string)
+                .map_err(|e| e.to_string())?;
+            let is_dir = entry.dir().map_err(|e| e.to_string())?;
+
+            if !is_dir {
+                self.check_entry_limit(manifest)?;
+                let mut entry_reader = reading.reader_mut().compat();
+                let outcome = self
+                    .store_entry(request, path, &mut entry_reader, budget)
+                    .await;
+                manifest.push(outcome);
+            }
+
+            // Reads past whatever the upload left unconsumed
+            zip = reading.skip().await.map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    async fn unpack_indexed_zip<R>(
+        &self,
+        request: &BulkUploadRequest,
+        reader: R,
+        budget: &mut u64,
+        manifest: &mut Manifest<'_>,
+    ) -> Result<(), String>
+    where
+        R: AsyncBufRead + AsyncSeek + Unpin + Send,
+    {
+        let mut zip = async_zip::tokio::read::seek::ZipFileReader::with_tokio(reader)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        for index in 0..zip.file().entries().len() {
+            let entry = &zip.file().entries()[index];
+            let path = entry
+                .filename()
+                .as_str()
+                .map(str::to_string)
+                .map_err(|e| e.to_string())?;
+            if entry.dir().map_err(|e| e.to_string())? {
+                continue;
+            }
+            self.check_entry_limit(manifest)?;
+
+            let mut entry_reader = zip
+                .reader_without_entry(index)
+                .await
+                .map_err(|e| e.to_string())?
+                .compat();
+            let outcome = self
+                .store_entry(request, path, &mut entry_reader, budget)
+                .await;
+            manifest.push(outcome);
+        }
+
+        Ok(())
+    }
+
+    fn check_entry_limit(&self, manifest: &Manifest<'_>) -> Result<(), String> {
+        if manifest.manifest.entries.len() >= self.max_entries {
+            return Err(format!(
+                "Archive exceeds {} entries; remaining entries were not processed",
+                self.max_entries
+            ));
+        }
+        Ok(())
+    }
+
+    /// Upload one entry and describe the outcome
+    async fn store_entry<E>(
+        &self,
+        request: &BulkUploadRequest,
+        path: String,
+        entry: &mut E,
+        budget: &mut u64,
+    ) -> BulkUploadEntry
+    where
+        E: AsyncRead + Unpin,
+    {
+        let key = match archive_entry_key(&path) {
+            Ok(key) => key,
+            Err(e) => {
+                return BulkUploadEntry::unsuccessful(
+                    path,
+                    BulkUploadEntryStatus::Rejected,
+                    e.to_string(),
+                )
+            }
+        };
+
+        let upload = UploadRequest {
+            namespace: request.namespace.clone(),
+            tenant_id: request.tenant_id.clone(),
+            key: Some(key),
+            storage_class: request.storage_class,
+            content_type: None,
+            content_encoding: None,
+            expected_hash: None,
+            idempotency_key: None,
+        };
+
+        match self.upload_entry(upload, entry, budget).await {
+            Ok(object) => BulkUploadEntry::created(path, &object),
+            Err(e) => {
+                BulkUploadEntry::unsuccessful(path, BulkUploadEntryStatus::Failed, e.to_string())
+            }
+        }
+    }
+
+    /// Stream an archive entry into the upload use case
+    ///
+    /// The entry borrows the archive, so it is fed through a channel rather
+    /// than handed over as a reader. Read errors and an exhausted size budget
+    /// are forwarded as stream errors, so a corrupt entry fails its upload
+    /// instead of being committed truncated.
+    async fn upload_entry<E>(
+        &self,
+        request: UploadRequest,
+        entry: &mut E,
+        budget: &mut u64,
+    ) -> Result<ObjectDto, ObjectUseCaseError>
+    where
+        E: AsyncRead + Unpin,
+    {
+        let (tx, rx) = mpsc::channel::<io::Result<Bytes>>(4);
+        let chunks = futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        });
+        let reader: BlobReader = Box::pin(StreamReader::new(chunks));
+
+        let feed = async move {
+            let mut buf = vec![0u8; ENTRY_CHUNK_BYTES];
+            loop {
+                let chunk = match entry.read(&mut buf).await {
+                    Ok(0) => break,
+                    Ok(n) if n as u64 > *budget => Err(io::Error::other(
+                        "Archive content exceeds the upload size limit",
+                    )),
+                    Ok(n) => {
+                        *budget -= n as u64;
+                        Ok(Bytes::copy_from_slice(&buf[..n]))
+                    }
+                    Err(e) => Err(e),
+                };
+                let failed = chunk.is_err();
+                // The upload hung up (it failed); stop reading
+                if tx.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        };
+
+        let ((), uploaded) = tokio::join!(feed, self.upload_use_case.execute(request, reader));
+        uploaded
+    }
+}
+
+/// The manifest being built, with every entry also reported as it finishes
+struct Manifest<'a> {
+    manifest: BulkUploadManifest,
+    progress: &'a OperationReporter,
+}
+
+impl Manifest<'_> {
+    fn push(&mut self, entry: BulkUploadEntry) {
+        self.progress.item(OperationItem {
+            name: entry.path.clone(),
+            status: entry.status.as_str().to_string(),
+            detail: entry.error.clone().or_else(|| entry.object_id.clone()),
+        });
+        self.manifest.push(entry);
+    }
+
+    /// The manifest, or the archive's error if not a single entry was read
+    fn finish(self, result: Result<(), String>) -> Result<BulkUploadManifest, ObjectUseCaseError> {
+        let mut manifest = self.manifest;
+        if let Err(error) = result {
+            if manifest.entries.is_empty() {
+                return Err(ObjectUseCaseError::InvalidRequest(format!(
+                    "Invalid archive: {error}"
+                )));
+            }
+            manifest.error = Some(error);
+        }
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::operation_progress::OperationProgress;
+    use crate::application::ports::{MockBlobRepository, MockBlobStore, MockObjectRepository};
+    use crate::domain::entities::Blob;
+    use crate::domain::value_objects::{ContentHash, StorageClass, TenantId};
+    use std::io::Cursor;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    async fn tar_archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tokio_tar::Builder::new(Vec::new());
+        for (path, data) in files {
+            let mut header = tokio_tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_entry_type(tokio_tar::EntryType::Regular);
+            // set_path refuses `..`; write the raw name like a hostile archiver
+            let name = &mut header.as_old_mut().name;
+            name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_cksum();
+            builder.append(&header, data.as_bytes()).await.unwrap();
+        }
+        builder.into_inner().await.unwrap()
+    }
+
+    fn use_case(uploads: usize, budget: u64) -> BulkUploadUseCase {
+        let content_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+
+        mock_object_repo
+            .expect_save()
+            .times(uploads * 3)
+            .returning(|_| Ok(()));
+        let written = content_hash.clone();
+        mock_blob_store
+            .expect_write()
+            .times(uploads)
+            .returning(move |_, _| Ok((written.clone(), 4)));
+        mock_blob_repo
+            .expect_get_or_create()
+            .times(uploads)
+            .returning(move |hash, _, _| Ok(Blob::new(hash.clone(), StorageClass::Hot, 4)));
+
+        let upload_use_case = UploadObjectUseCase::with_max_upload_size_bytes(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+            budget,
+        );
+        BulkUploadUseCase::new(Arc::new(upload_use_case))
+    }
+
+    fn request() -> BulkUploadRequest {
+        BulkUploadRequest {
+            namespace: "imports".to_string(),
+            tenant_id: "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_string(),
+            storage_class: Some(StorageClass::Hot),
+        }
+    }
+
+    #[test]
+    fn test_archive_entry_key_normalizes_and_rejects_traversal() {
+        assert_eq!(archive_entry_key("./docs//a.txt").unwrap(), "docs/a.txt");
+        assert!(archive_entry_key("../../../etc/passwd").is_err());
+        assert!(archive_entry_key("docs/../../secret").is_err());
+        assert!(archive_entry_key("/etc/passwd").is_err());
+        assert!(archive_entry_key("C:/Windows/win.ini").is_err());
+        assert!(archive_entry_key("..\\..\\boot.ini").is_err());
+        assert!(archive_entry_key("./").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tar_entries_become_objects_and_traversal_is_rejected() {
+        // Arrange: two files, one hostile path
+        let archive = tar_archive(&[
+            ("docs/a.txt", "aaaa"),
+            ("../../../etc/passwd", "root"),
+            ("docs/b.txt", "aaaa"),
+        ])
+        .await;
+        let use_case = use_case(2, 1024);
+
+        // Act
+        let manifest = use_case
+            .execute(request(), ArchiveFormat::Tar, Cursor::new(archive))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(manifest.created, 2);
+        assert_eq!(manifest.failed, 1);
+        assert_eq!(manifest.entries[0].key.as_deref(), Some("docs/a.txt"));
+        assert_eq!(manifest.entries[1].status, BulkUploadEntryStatus::Rejected);
+        // Identical content dedups to one content hash
+        assert_eq!(
+            manifest.entries[0].content_hash,
+            manifest.entries[2].content_hash
+        );
+        assert!(manifest.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_entry_limit_stops_processing() {
+        let archive = tar_archive(&[("a.txt", "aaaa"), ("b.txt", "aaaa")]).await;
+        let use_case = use_case(1, 1024).with_max_entries(1);
+
+        let manifest = use_case
+            .execute(request(), ArchiveFormat::Tar, Cursor::new(archive))
+            .await
+            .unwrap();
+
+        assert_eq!(manifest.created, 1);
+        assert!(manifest.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_entries_are_reported_to_followers() {
+        let archive = tar_archive(&[("a.txt", "aaaa"), ("../b.txt", "bbbb")]).await;
+        let use_case = use_case(1, 1024);
+        let operations = Arc::new(OperationProgress::new());
+        let id = Uuid::new_v4();
+        let mut subscription = operations.subscribe(id);
+        let owner = TenantId::from_string(&request().tenant_id).unwrap();
+        let progress = operations.start(id, "bulk_upload", owner).unwrap();
+
+        use_case
+            .execute_reporting(
+                request(),
+                ArchiveFormat::Tar,
+                Cursor::new(archive),
+                &progress,
+            )
+            .await
+            .unwrap();
+
+        let snapshot = subscription.current();
+        let statuses: Vec<_> = snapshot.items.iter().map(|i| i.status.as_str()).collect();
+        assert_eq!(statuses, ["created", "rejected"]);
+        assert_eq!(snapshot.items[0].name, "a.txt");
+    }
+
+    #[tokio::test]
+    async fn test_buffered_zip_entries_are_read_from_central_directory() {
+        let mut writer = async_zip::tokio::write::ZipFileWriter::with_tokio(Vec::new());
+        for (path, data) in [("docs/", ""), ("docs/a.txt", "aaaa"), ("b.txt", "aaaa")] {
+            let entry = async_zip::ZipEntryBuilder::new(
+                path.to_string().into(),
+                async_zip::Compression::Stored,
+            );
+            writer
+                .write_entry_whole(entry, data.as_bytes())
+                .await
+                .unwrap();
+        }
+        let archive = writer.close().await.unwrap().into_inner();
+        let use_case = use_case(2, 1024);
+
+        let manifest = use_case
+            .execute_zip_reporting(
+                request(),
+                Cursor::new(archive),
+                &OperationReporter::detached(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(manifest.created, 2);
+        assert_eq!(manifest.entries[0].key.as_deref(), Some("docs/a.txt"));
+        assert!(manifest.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_archive_rejected() {
+        let use_case = use_case(0, 1024);
+
+        let result = use_case
+            .execute(
+                request(),
+                ArchiveFormat::Zip,
+                Cursor::new(b"not a zip archive".to_vec()),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+}
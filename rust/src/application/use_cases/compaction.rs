@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::application::dto::{CompactionCandidate, CompactionReport};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::{BlobInventory, BlobRepository, StoredFile, StoredFileKind};
+use crate::domain::value_objects::{ContentHash, StorageClass};
+
+/// Default number of blob files checked against the database per query
+pub const DEFAULT_COMPACTION_BATCH_SIZE: usize = 1000;
+
+/// Default maximum files included in a report
+pub const DEFAULT_COMPACTION_SAMPLE_LIMIT: usize = 100;
+
+/// Use case: Remove files in blob storage that the database does not know about
+///
+/// Reference-count GC only sees blobs that have a row. Blob files written
+/// without ever getting one (a crash between the rename and the insert, a
+/// restore from backup) and temp files of abandoned uploads are found here
+/// instead, by scanning the storage roots.
+///
+/// Uploads in progress are protected three ways: files modified within the
+/// grace window are not scanned, a hash staged on a WRITING object counts as
+/// known (interrupted-write recovery still needs it), and orphans are checked
+/// again right before removal.
+pub struct CompactionUseCase {
+    blob_inventory: Arc<dyn BlobInventory>,
+    blob_repo: Arc<dyn BlobRepository>,
+    grace: Duration,
+    batch_size: usize,
+    sample_limit: usize,
+    running: tokio::sync::Mutex<()>,
+}
+
+impl CompactionUseCase {
+    pub fn new(
+        blob_inventory: Arc<dyn BlobInventory>,
+        blob_repo: Arc<dyn BlobRepository>,
+        grace_hours: i64,
+    ) -> Self {
+        Self {
+            blob_inventory,
+            blob_repo,
+            grace: Duration::from_secs(grace_hours.max(1) as u64 * 3600),
+            batch_size: DEFAULT_COMPACTION_BATCH_SIZE,
+            sample_limit: DEFAULT_COMPACTION_SAMPLE_LIMIT,
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Include up to `sample_limit` files in each report
+    pub fn with_sample_limit(mut self, sample_limit: usize) -> Self {
+        self.sample_limit = sample_limit;
+        self
+    }
+
+    /// Scan both storage classes, removing unknown files unless `dry_run`
+    pub async fn execute(&self, dry_run: bool) -> Result<CompactionReport, ObjectUseCaseError> {
+        let _guard = self.running.try_lock().map_err(|_| {
+            ObjectUseCaseError::InvalidRequest("Blob compaction is already running".to_string())
+        })?;
+
+        let mut report = CompactionReport {
+            dry_run,
+            ..Default::default()
+        };
+        let modified_before = SystemTime::now() - self.grace;
+
+        for storage_class in [StorageClass::Hot, StorageClass::Cold] {
+            let files = self
+                .blob_inventory
+                .list_files(storage_class, modified_before)
+                .await?;
+            report.scanned += files.len() as u64;
+
+            let (blobs, temps): (Vec<StoredFile>, Vec<StoredFile>) = files
+                .into_iter()
+                .partition(|f| matches!(f.kind, StoredFileKind::Blob(_)));
+
+            report.stale_temp_files += temps.len() as u64;
+            self.remove(temps, dry_run, &mut report).await;
+
+            for batch in blobs.chunks(self.batch_size) {
+                let orphans = self.unknown(batch.to_vec()).await?;
+                report.orphaned_blobs += orphans.len() as u64;
+
+                // A concurrent upload may have claimed the content since the
+                // first check
+                let orphans = if dry_run {
+                    orphans
+                } else {
+                    self.unknown(orphans).await?
+                };
+                self.remove(orphans, dry_run, &mut report).await;
+            }
+        }
+
+        tracing::info!(
+            dry_run,
+            scanned = report.scanned,
+            orphaned_blobs = report.orphaned_blobs,
+            stale_temp_files = report.stale_temp_files,
+            removed = report.removed,
+            bytes_reclaimed = report.bytes_reclaimed,
+            errors = report.errors,
+            "Blob compaction finished"
+        );
+
+        Ok(report)
+    }
+
+    /// Blob files whose hash the database does not know
+    async fn unknown(&self, files: Vec<StoredFile>) -> Result<Vec<StoredFile>, ObjectUseCaseError> {
+        if files.is_empty() {
+            return Ok(files);
+        }
+
+        let hashes: Vec<ContentHash> = files.iter().filter_map(blob_hash).cloned().collect();
+        let known: HashSet<ContentHash> = self
+            .blob_repo
+            .find_known(&hashes)
+            .await?
+            .into_iter()
+            .collect();
+
+        Ok(files
+            .into_iter()
+            .filter(|f| blob_hash(f).is_some_and(|hash| !known.contains(hash)))
+            .collect())
+    }
+
+    async fn remove(&self, files: Vec<StoredFile>, dry_run: bool, report: &mut CompactionReport) {
+        for file in files {
+            let removed = if dry_run {
+                report.bytes_reclaimed += file.size_bytes;
+                false
+            } else {
+                match self.blob_inventory.remove_file(&file).await {
+                    Ok(()) => {
+                        report.removed += 1;
+                        report.bytes_reclaimed += file.size_bytes;
+                        true
+                    }
+                    Err(e) => {
+                        tracing::warn!(path = %file.path.display(), "Failed to remove unreferenced file: {}", e);
+                        report.errors += 1;
+                        false
+                    }
+                }
+            };
+
+            if report.sample.len() < self.sample_limit {
+                report.sample.push(CompactionCandidate {
+                    path: file.path.display().to_string(),
+                    storage_class: file.storage_class,
+                    size_bytes: file.size_bytes,
+                    removed,
+                });
+            }
+        }
+    }
+}
+
+fn blob_hash(file: &StoredFile) -> Option<&ContentHash> {
+    match &file.kind {
+        StoredFileKind::Blob(hash) => Some(hash),
+        StoredFileKind::Temp => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{MockBlobInventory, MockBlobRepository};
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    fn hash(c: char) -> ContentHash {
+        ContentHash::from_str(&c.to_string().repeat(64)).unwrap()
+    }
+
+    fn blob_file(c: char) -> StoredFile {
+        StoredFile {
+            path: PathBuf::from(format!("/data/hot/sha256/{}", c.to_string().repeat(64))),
+            storage_class: StorageClass::Hot,
+            kind: StoredFileKind::Blob(hash(c)),
+            size_bytes: 10,
+            modified: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    fn temp_file() -> StoredFile {
+        StoredFile {
+            path: PathBuf::from("/data/hot/temp/upload"),
+            storage_class: StorageClass::Hot,
+            kind: StoredFileKind::Temp,
+            size_bytes: 5,
+            modified: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    fn inventory_with(files: Vec<StoredFile>) -> MockBlobInventory {
+        let mut mock_inventory = MockBlobInventory::new();
+        mock_inventory
+            .expect_list_files()
+            .returning(move |class, _| {
+                Ok(files
+                    .iter()
+                    .filter(|f| f.storage_class == class)
+                    .cloned()
+                    .collect())
+            });
+        mock_inventory
+    }
+
+    #[tokio::test]
+    async fn test_removes_unknown_blobs_and_stale_temp_files() {
+        let mut mock_inventory = inventory_with(vec![blob_file('a'), blob_file('b'), temp_file()]);
+        mock_inventory
+            .expect_remove_file()
+            .withf(|f| f.kind != StoredFileKind::Blob(hash('a')))
+            .times(2)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        // 'a' has a blob row (or is staged on a WRITING object)
+        mock_blob_repo.expect_find_known().returning(|hashes| {
+            Ok(hashes
+                .iter()
+                .filter(|h| **h == hash('a'))
+                .cloned()
+                .collect())
+        });
+
+        let use_case =
+            CompactionUseCase::new(Arc::new(mock_inventory), Arc::new(mock_blob_repo), 24);
+
+        let report = use_case.execute(false).await.unwrap();
+
+        assert_eq!(report.scanned, 3);
+        assert_eq!(report.orphaned_blobs, 1);
+        assert_eq!(report.stale_temp_files, 1);
+        assert_eq!(report.removed, 2);
+        assert_eq!(report.bytes_reclaimed, 15);
+        assert_eq!(report.errors, 0);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_blob_claimed_before_removal() {
+        let mut mock_inventory = inventory_with(vec![blob_file('b')]);
+        mock_inventory.expect_remove_file().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut seq = mockall::Sequence::new();
+        mock_blob_repo
+            .expect_find_known()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(vec![]));
+        // An upload deduplicated onto the file between the two checks
+        mock_blob_repo
+            .expect_find_known()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(vec![hash('b')]));
+
+        let use_case =
+            CompactionUseCase::new(Arc::new(mock_inventory), Arc::new(mock_blob_repo), 24);
+
+        let report = use_case.execute(false).await.unwrap();
+
+        assert_eq!(report.orphaned_blobs, 1);
+        assert_eq!(report.removed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_removes_nothing() {
+        let mut mock_inventory = inventory_with(vec![blob_file('b'), temp_file()]);
+        mock_inventory.expect_remove_file().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_find_known()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let use_case =
+            CompactionUseCase::new(Arc::new(mock_inventory), Arc::new(mock_blob_repo), 24);
+
+        let report = use_case.execute(true).await.unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(report.removed, 0);
+        assert_eq!(report.bytes_reclaimed, 15);
+        assert_eq!(report.sample.len(), 2);
+        assert!(report.sample.iter().all(|c| !c.removed));
+    }
+
+    #[tokio::test]
+    async fn test_scans_only_past_grace_window() {
+        let mut mock_inventory = MockBlobInventory::new();
+        mock_inventory
+            .expect_list_files()
+            .withf(|_, modified_before| {
+                let age = SystemTime::now().duration_since(*modified_before).unwrap();
+                age >= Duration::from_secs(6 * 3600) && age < Duration::from_secs(6 * 3600 + 60)
+            })
+            .times(2)
+            .returning(|_, _| Ok(vec![]));
+
+        let use_case = CompactionUseCase::new(
+            Arc::new(mock_inventory),
+            Arc::new(MockBlobRepository::new()),
+            6,
+        );
+
+        let report = use_case.execute(false).await.unwrap();
+        assert_eq!(report.scanned, 0);
+    }
+}
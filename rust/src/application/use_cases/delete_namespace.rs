@@ -0,0 +1,368 @@
+use std::sync::Arc;
+
+use time::format_description::well_known::Rfc3339;
+
+use crate::application::dto::{DeleteNamespaceReport, LockedObjectDto, OperationItem};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::operation_progress::OperationReporter;
+use crate::application::ports::{LockedObject, NamespaceDeletionRepository};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Namespace, TenantId};
+
+/// Default number of objects deleted per batch
+pub const DEFAULT_NAMESPACE_DELETE_BATCH_SIZE: i64 = 500;
+/// Default number of batches one run deletes before returning
+pub const DEFAULT_NAMESPACE_DELETE_MAX_BATCHES: u64 = 20;
+
+/// Maximum locked objects listed in a report or error
+const MAX_LISTED_BLOCKERS: i64 = 20;
+
+/// Use case: Delete every object of a tenant's namespace (admin only)
+///
+/// Objects are marked deleted in short batches that also release their blob
+/// references, so the garbage collector reclaims the storage. A run stops
+/// after its batch budget and reports what is left; running it again
+/// continues where it stopped. Any object under retention or legal hold
+/// blocks the delete.
+pub struct DeleteNamespaceUseCase {
+    repository: Arc<dyn NamespaceDeletionRepository>,
+    batch_size: i64,
+    max_batches: u64,
+}
+
+impl DeleteNamespaceUseCase {
+    pub fn new(repository: Arc<dyn NamespaceDeletionRepository>) -> Self {
+        Self {
+            repository,
+            batch_size: DEFAULT_NAMESPACE_DELETE_BATCH_SIZE,
+            max_batches: DEFAULT_NAMESPACE_DELETE_MAX_BATCHES,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Stop each run after `max_batches` batches
+    pub fn with_max_batches(mut self, max_batches: u64) -> Self {
+        self.max_batches = max_batches.max(1);
+        self
+    }
+
+    /// Count the namespace's objects and, unless `dry_run`, delete them
+    pub async fn execute(
+        &self,
+        namespace: String,
+        tenant_id: &str,
+        dry_run: bool,
+    ) -> Result<DeleteNamespaceReport, ObjectUseCaseError> {
+        self.execute_reporting(
+            namespace,
+            tenant_id,
+            dry_run,
+            &OperationReporter::detached(),
+        )
+        .await
+    }
+
+    /// [`Self::execute`], reporting deleted objects and each batch to
+    /// `progress`; finishing the operation is left to the caller
+    #[tracing::instrument(
+        name = "DeleteNamespaceUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(namespace = %namespace, tenant_id = %tenant_id, dry_run)
+    )]
+    pub async fn execute_reporting(
+        &self,
+        namespace: String,
+        tenant_id: &str,
+        dry_run: bool,
+        progress: &OperationReporter,
+    ) -> Result<DeleteNamespaceReport, ObjectUseCaseError> {
+        let namespace = Namespace::new(namespace)?;
+        let tenant_id = TenantId::from_string(tenant_id)?;
+
+        let usage = self.repository.usage(&namespace, &tenant_id).await?;
+        let blockers = self
+            .repository
+            .find_locked(&namespace, &tenant_id, MAX_LISTED_BLOCKERS)
+            .await?;
+
+        let mut report = DeleteNamespaceReport {
+            namespace: namespace.to_string(),
+            tenant_id: tenant_id.to_string(),
+            dry_run,
+            completed: usage.objects == 0,
+            remaining_objects: usage.objects,
+            remaining_bytes: usage.bytes,
+            blockers: blockers.iter().map(Self::blocker_dto).collect(),
+            ..Default::default()
+        };
+        if dry_run {
+            return Ok(report);
+        }
+        if !blockers.is_empty() {
+            return Err(DomainError::ObjectLocked(Self::describe(&namespace, &blockers)).into());
+        }
+        progress.set_total(usage.objects);
+
+        while report.batches < self.max_batches {
+            let batch = self
+                .repository
+                .delete_batch(&namespace, &tenant_id, self.batch_size)
+                .await?;
+            report.batches += 1;
+            report.deleted_objects += batch.objects;
+            report.deleted_bytes += batch.bytes;
+            report.released_blobs += batch.released_blobs;
+            progress.advance(batch.objects);
+            progress.item(OperationItem {
+                name: format!("batch {}", report.batches),
+                status: "deleted".to_string(),
+                detail: Some(format!("{} objects, {} bytes", batch.objects, batch.bytes)),
+            });
+
+            tracing::info!(
+                %namespace,
+                %tenant_id,
+                deleted = report.deleted_objects,
+                of = usage.objects,
+                "Namespace delete progress"
+            );
+
+            if (batch.objects as i64) < self.batch_size {
+                break;
+            }
+        }
+
+        let remaining = self.repository.usage(&namespace, &tenant_id).await?;
+        report.completed = remaining.objects == 0;
+        report.remaining_objects = remaining.objects;
+        report.remaining_bytes = remaining.bytes;
+        if !report.completed {
+            // Objects locked while the run was in progress are left in place
+            report.blockers = self
+                .repository
+                .find_locked(&namespace, &tenant_id, MAX_LISTED_BLOCKERS)
+                .await?
+                .iter()
+                .map(Self::blocker_dto)
+                .collect();
+        }
+
+        tracing::info!(
+            %namespace,
+            %tenant_id,
+            completed = report.completed,
+            batches = report.batches,
+            deleted = report.deleted_objects,
+            released_blobs = report.released_blobs,
+            remaining = report.remaining_objects,
+            "Namespace delete finished"
+        );
+
+        Ok(report)
+    }
+
+    fn blocker_dto(object: &LockedObject) -> LockedObjectDto {
+        LockedObjectDto {
+            id: object.id.to_string(),
+            key: object.key.clone(),
+            retention_until: object
+                .retention_until
+                .and_then(|at| at.format(&Rfc3339).ok()),
+            legal_hold: object.legal_hold,
+        }
+    }
+
+    /// List the blockers, e.g. `namespace logs has 2 locked objects: <id>
+    /// (legal hold), <id> (retained until 2030-01-01T00:00:00Z)`
+    fn describe(namespace: &Namespace, blockers: &[LockedObject]) -> String {
+        let listed = blockers
+            .iter()
+            .map(|object| {
+                let lock = match object.retention_until {
+                    _ if object.legal_hold => "legal hold".to_string(),
+                    Some(until) => format!(
+                        "retained until {}",
+                        until.format(&Rfc3339).unwrap_or_default()
+                    ),
+                    None => "locked".to_string(),
+                };
+                format!("{} ({lock})", object.id)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let more = if blockers.len() as i64 >= MAX_LISTED_BLOCKERS {
+            " and possibly more"
+        } else {
+            ""
+        };
+
+        format!(
+            "namespace {namespace} has {} locked objects{more}: {listed}",
+            blockers.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{
+        MockNamespaceDeletionRepository, NamespaceDeleteBatch, NamespaceUsage,
+    };
+    use crate::domain::value_objects::ObjectId;
+    use uuid::Uuid;
+
+    fn tenant() -> String {
+        Uuid::new_v4().to_string()
+    }
+
+    fn usage(objects: u64) -> NamespaceUsage {
+        NamespaceUsage {
+            objects,
+            bytes: objects * 10,
+        }
+    }
+
+    fn batch(objects: u64) -> NamespaceDeleteBatch {
+        NamespaceDeleteBatch {
+            objects,
+            bytes: objects * 10,
+            released_blobs: objects / 2,
+        }
+    }
+
+    fn held_object() -> LockedObject {
+        LockedObject {
+            id: ObjectId::new(),
+            key: Some("ledger.csv".to_string()),
+            retention_until: None,
+            legal_hold: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_counts_without_deleting() {
+        // Arrange
+        let mut repo = MockNamespaceDeletionRepository::new();
+        repo.expect_usage().times(1).returning(|_, _| Ok(usage(7)));
+        repo.expect_find_locked()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![held_object()]));
+        repo.expect_delete_batch().never();
+        let use_case = DeleteNamespaceUseCase::new(Arc::new(repo));
+
+        // Act
+        let report = use_case
+            .execute("logs".to_string(), &tenant(), true)
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(report.dry_run);
+        assert!(!report.completed);
+        assert_eq!(report.remaining_objects, 7);
+        assert_eq!(report.remaining_bytes, 70);
+        assert_eq!(report.deleted_objects, 0);
+        assert_eq!(report.blockers.len(), 1);
+        assert!(report.blockers[0].legal_hold);
+    }
+
+    #[tokio::test]
+    async fn test_locked_objects_block_delete() {
+        // Arrange
+        let blocker = held_object();
+        let blocker_id = blocker.id;
+        let mut repo = MockNamespaceDeletionRepository::new();
+        repo.expect_usage().returning(|_, _| Ok(usage(3)));
+        repo.expect_find_locked()
+            .returning(move |_, _, _| Ok(vec![blocker.clone()]));
+        repo.expect_delete_batch().never();
+        let use_case = DeleteNamespaceUseCase::new(Arc::new(repo));
+
+        // Act
+        let result = use_case.execute("logs".to_string(), &tenant(), false).await;
+
+        // Assert
+        match result {
+            Err(ObjectUseCaseError::Domain(DomainError::ObjectLocked(msg))) => {
+                assert!(msg.contains(&blocker_id.to_string()));
+                assert!(msg.contains("legal hold"));
+            }
+            other => panic!("expected ObjectLocked, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deletes_in_batches_until_empty() {
+        // Arrange: 5 objects in batches of 2
+        let mut repo = MockNamespaceDeletionRepository::new();
+        let mut usages = vec![usage(5), usage(0)].into_iter();
+        repo.expect_usage()
+            .times(2)
+            .returning(move |_, _| Ok(usages.next().unwrap()));
+        repo.expect_find_locked().returning(|_, _, _| Ok(vec![]));
+        let mut batches = vec![batch(2), batch(2), batch(1)].into_iter();
+        repo.expect_delete_batch()
+            .withf(|_, _, limit| *limit == 2)
+            .times(3)
+            .returning(move |_, _, _| Ok(batches.next().unwrap()));
+        let use_case = DeleteNamespaceUseCase::new(Arc::new(repo)).with_batch_size(2);
+
+        // Act
+        let report = use_case
+            .execute("logs".to_string(), &tenant(), false)
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(report.completed);
+        assert_eq!(report.batches, 3);
+        assert_eq!(report.deleted_objects, 5);
+        assert_eq!(report.deleted_bytes, 50);
+        assert_eq!(report.released_blobs, 2);
+        assert_eq!(report.remaining_objects, 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_after_batch_budget() {
+        // Arrange
+        let mut repo = MockNamespaceDeletionRepository::new();
+        let mut usages = vec![usage(10), usage(6)].into_iter();
+        repo.expect_usage()
+            .returning(move |_, _| Ok(usages.next().unwrap()));
+        repo.expect_find_locked().returning(|_, _, _| Ok(vec![]));
+        repo.expect_delete_batch()
+            .times(2)
+            .returning(|_, _, _| Ok(batch(2)));
+        let use_case = DeleteNamespaceUseCase::new(Arc::new(repo))
+            .with_batch_size(2)
+            .with_max_batches(2);
+
+        // Act
+        let report = use_case
+            .execute("logs".to_string(), &tenant(), false)
+            .await
+            .unwrap();
+
+        // Assert: the next run continues with the 6 left
+        assert!(!report.completed);
+        assert_eq!(report.batches, 2);
+        assert_eq!(report.deleted_objects, 4);
+        assert_eq!(report.remaining_objects, 6);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_tenant_is_rejected() {
+        let use_case =
+            DeleteNamespaceUseCase::new(Arc::new(MockNamespaceDeletionRepository::new()));
+
+        let result = use_case.execute("logs".to_string(), "nope", true).await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::Domain(_))));
+    }
+}
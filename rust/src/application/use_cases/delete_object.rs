@@ -25,6 +25,12 @@ impl DeleteObjectUseCase {
     }
 
     /// Execute delete workflow
+    #[tracing::instrument(
+        name = "DeleteObjectUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id)
+    )]
     pub async fn execute(&self, object_id: &ObjectId) -> Result<(), DeleteUseCaseError> {
         // 1. Find object
         let mut object = match self.object_repo.find_by_id(object_id).await {
@@ -37,9 +43,14 @@ impl DeleteObjectUseCase {
             Err(e) => return Err(DeleteUseCaseError::Repository(e)),
         };
 
-        // 2. Mark for deletion (domain validation)
+        // 2. Mark for deletion (domain validation); the store checks the lock
+        // again, so one set since the object was loaded is not overridden
         object.mark_for_deletion()?;
-        self.object_repo.save(&object).await?;
+        if !self.object_repo.mark_deleting_if_unlocked(&object).await? {
+            return Err(DeleteUseCaseError::Conflict(format!(
+                "Object {object_id} was locked or changed while being deleted"
+            )));
+        }
 
         // 3. Decrement blob ref count
         if let Some(content_hash) = object.content_hash() {
@@ -69,6 +80,7 @@ mod tests {
     use super::*;
     use crate::application::ports::{MockBlobRepository, MockBlobStore, MockObjectRepository};
     use crate::domain::entities::Object;
+    use crate::domain::errors::DomainError;
     use crate::domain::value_objects::{ContentHash, Namespace, ObjectId, StorageClass, TenantId};
     use std::str::FromStr;
     use std::sync::Arc;
@@ -102,9 +114,13 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(object.clone())));
 
+        mock_object_repo
+            .expect_mark_deleting_if_unlocked()
+            .times(1)
+            .returning(|_| Ok(true));
         mock_object_repo
             .expect_save()
-            .times(2)
+            .times(1)
             .returning(|_| Ok(()));
 
         mock_blob_repo
@@ -182,9 +198,13 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(object.clone())));
 
+        mock_object_repo
+            .expect_mark_deleting_if_unlocked()
+            .times(1)
+            .returning(|_| Ok(true));
         mock_object_repo
             .expect_save()
-            .times(2)
+            .times(1)
             .returning(|_| Ok(()));
 
         mock_blob_repo
@@ -204,4 +224,116 @@ mod tests {
         // Assert
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_delete_object_within_retention_is_refused() {
+        // Arrange
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        let mut object = create_test_object();
+        object
+            .extend_retention(time::OffsetDateTime::now_utc() + time::Duration::days(1))
+            .unwrap();
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo.expect_mark_deleting_if_unlocked().never();
+        mock_object_repo.expect_save().never();
+        mock_blob_repo.expect_decrement_ref().never();
+
+        let use_case = DeleteObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        // Act
+        let result = use_case.execute(&object_id).await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(DeleteUseCaseError::Domain(DomainError::ObjectLocked(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_object_after_retention_succeeds() {
+        // Arrange
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        let mut object = create_test_object();
+        object
+            .extend_retention(time::OffsetDateTime::now_utc() - time::Duration::days(1))
+            .unwrap();
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo
+            .expect_mark_deleting_if_unlocked()
+            .times(1)
+            .returning(|_| Ok(true));
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(1));
+
+        let use_case = DeleteObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        // Act
+        let result = use_case.execute(&object_id).await;
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_object_locked_concurrently_is_refused() {
+        // Arrange
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        let object = create_test_object();
+        let object_id = *object.id();
+
+        // Loaded unlocked, but a hold is placed before the guarded update
+        mock_object_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo
+            .expect_mark_deleting_if_unlocked()
+            .times(1)
+            .returning(|_| Ok(false));
+        mock_object_repo.expect_save().never();
+        mock_blob_repo.expect_decrement_ref().never();
+
+        let use_case = DeleteObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        // Act
+        let result = use_case.execute(&object_id).await;
+
+        // Assert
+        assert!(matches!(result, Err(DeleteUseCaseError::Conflict(_))));
+    }
 }
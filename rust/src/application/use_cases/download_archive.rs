@@ -0,0 +1,523 @@
+use std::collections::HashSet;
+use std::io;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures_util::stream::{self, Take};
+use futures_util::{Stream, StreamExt};
+use time::OffsetDateTime;
+use tokio::io::DuplexStream;
+use tokio::sync::oneshot;
+use tokio_util::compat::FuturesAsyncWriteCompatExt;
+use tokio_util::io::ReaderStream;
+
+use crate::application::dto::{
+    ArchiveDownloadEntry, ArchiveDownloadManifest, ArchiveDownloadRequest, ArchiveFormat,
+    ObjectDto, SortDirection, SortField,
+};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::key_prefix_query::KeyPrefixQuery;
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::ports::{BlobReader, BlobStore, ObjectRepository, ObjectStream};
+use crate::application::validation::{archive_entry_key, validate_namespace_and_tenant};
+use crate::domain::entities::Object;
+
+/// Default cap on objects in a single downloaded archive
+pub const DEFAULT_MAX_ARCHIVE_DOWNLOAD_OBJECTS: usize = 1_000;
+
+/// Name of the manifest entry written after the objects
+pub const ARCHIVE_MANIFEST_NAME: &str = "_manifest.json";
+
+/// Bytes buffered between the archive writer and the response
+const ARCHIVE_PIPE_BYTES: usize = 64 * 1024;
+
+/// Use case: Download the objects of a namespace as a tar or zip archive
+///
+/// Objects are read one blob at a time and written as archive entries while
+/// the client reads, so memory stays bounded however large the archive.
+pub struct DownloadArchiveUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+    blob_store: Arc<dyn BlobStore>,
+    max_objects: usize,
+}
+
+impl DownloadArchiveUseCase {
+    pub fn new(object_repo: Arc<dyn ObjectRepository>, blob_store: Arc<dyn BlobStore>) -> Self {
+        Self {
+            object_repo,
+            blob_store,
+            max_objects: DEFAULT_MAX_ARCHIVE_DOWNLOAD_OBJECTS,
+        }
+    }
+
+    /// Limit the number of objects a single archive may hold
+    pub fn with_max_objects(mut self, max_objects: usize) -> Self {
+        self.max_objects = max_objects;
+        self
+    }
+
+    /// Most objects in a single archive
+    pub fn max_objects(&self) -> usize {
+        self.max_objects
+    }
+
+    /// Stream the committed objects of the request, in key order, as an
+    /// archive followed by a [`ARCHIVE_MANIFEST_NAME`] entry
+    ///
+    /// Entries are named after the object keys (the object ID for objects
+    /// without one) and hold the stored bytes. Objects whose blob is missing
+    /// or cannot be read yet, and objects whose entry path is taken (by the
+    /// manifest, or by an earlier key normalizing to the same path, like
+    /// `a//b` and `a/b`), are listed in the manifest with the error
+    /// instead of aborting the archive. Requests matching more than
+    /// `max_objects` objects are rejected before anything is sent; a failure
+    /// once streaming has started ends the body with an error, so clients
+    /// must treat a truncated archive as failed.
+    pub async fn execute(
+        &self,
+        request: ArchiveDownloadRequest,
+        format: ArchiveFormat,
+    ) -> Result<impl Stream<Item = io::Result<Bytes>> + Send + 'static, ObjectUseCaseError> {
+        let (namespace, tenant_id) =
+            validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
+        let keys = KeyPrefixQuery::new(request.prefix.clone(), None)
+            .map_err(ObjectUseCaseError::InvalidRequest)?;
+        let metadata = MetadataQuery::default();
+
+        let matching = self
+            .object_repo
+            .count(
+                &namespace,
+                &tenant_id,
+                &keys,
+                &metadata,
+                self.max_objects as i64 + 1,
+            )
+            .await?;
+        if matching > self.max_objects as i64 {
+            return Err(ObjectUseCaseError::InvalidRequest(format!(
+                "More than {} objects match; narrow the prefix",
+                self.max_objects
+            )));
+        }
+
+        let objects = self.object_repo.stream(
+            &namespace,
+            &tenant_id,
+            &keys,
+            &metadata,
+            SortField::Key,
+            SortDirection::Asc,
+        );
+        let writer = ArchiveWriter {
+            blob_store: Arc::clone(&self.blob_store),
+            // Objects committed after the count are left out
+            max_objects: self.max_objects,
+            paths: HashSet::new(),
+            manifest: ArchiveDownloadManifest {
+                namespace: request.namespace,
+                tenant_id: request.tenant_id,
+                prefix: request.prefix,
+                added: 0,
+                failed: 0,
+                entries: Vec::new(),
+            },
+        };
+
+        let (reader, pipe) = tokio::io::duplex(ARCHIVE_PIPE_BYTES);
+        let (done_tx, done_rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result = writer.write(format, objects, pipe).await;
+            if let Err(e) = &result {
+                tracing::warn!("Archive download failed: {}", e);
+            }
+            let _ = done_tx.send(result);
+        });
+
+        // A failed archive ends the body with the error instead of cleanly
+        let failure = stream::once(done_rx).filter_map(|done| async move {
+            match done {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(Err(e)),
+                Err(_) => Some(Err(io::Error::other("Archive writer stopped"))),
+            }
+        });
+        Ok(ReaderStream::new(reader).chain(failure))
+    }
+}
+
+/// Content of an object ready to be written as an archive entry
+struct ArchiveEntry {
+    path: String,
+    size_bytes: u64,
+    modified_at: OffsetDateTime,
+    reader: BlobReader,
+}
+
+/// Writes the archive on its own task, feeding the response through a pipe
+struct ArchiveWriter {
+    blob_store: Arc<dyn BlobStore>,
+    max_objects: usize,
+    /// Entry paths written so far
+    paths: HashSet<String>,
+    manifest: ArchiveDownloadManifest,
+}
+
+impl ArchiveWriter {
+    async fn write(
+        self,
+        format: ArchiveFormat,
+        objects: ObjectStream,
+        pipe: DuplexStream,
+    ) -> io::Result<()> {
+        let objects = objects.take(self.max_objects);
+        match format {
+            ArchiveFormat::Tar => self.write_tar(objects, pipe).await,
+            ArchiveFormat::Zip => self.write_zip(objects, pipe).await,
+        }
+    }
+
+    async fn write_tar(
+        mut self,
+        mut objects: Take<ObjectStream>,
+        pipe: DuplexStream,
+    ) -> io::Result<()> {
+        let mut builder = tokio_tar::Builder::new(pipe);
+        while let Some(object) = objects.next().await {
+            let Some(entry) = self.open(object.map_err(io::Error::other)?).await else {
+                continue;
+            };
+            let mut header = tar_header(entry.size_bytes, entry.modified_at);
+            builder
+                .append_data(&mut header, &entry.path, entry.reader)
+                .await?;
+        }
+
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+        let mut header = tar_header(manifest.len() as u64, OffsetDateTime::now_utc());
+        builder
+            .append_data(&mut header, ARCHIVE_MANIFEST_NAME, manifest.as_slice())
+            .await?;
+        builder.into_inner().await?;
+        Ok(())
+    }
+
+    async fn write_zip(
+        mut self,
+        mut objects: Take<ObjectStream>,
+        pipe: DuplexStream,
+    ) -> io::Result<()> {
+        let mut zip = async_zip::tokio::write::ZipFileWriter::with_tokio(pipe);
+        while let Some(object) = objects.next().await {
+            let Some(mut entry) = self.open(object.map_err(io::Error::other)?).await else {
+                continue;
+            };
+            // Sizes and checksum follow the data, so nothing is buffered
+            let mut writer = zip
+                .write_entry_stream(zip_entry(entry.path))
+                .await
+                .map_err(io::Error::other)?
+                .compat_write();
+            tokio::io::copy(&mut entry.reader, &mut writer).await?;
+            writer
+                .into_inner()
+                .close()
+                .await
+                .map_err(io::Error::other)?;
+        }
+
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+        zip.write_entry_whole(zip_entry(ARCHIVE_MANIFEST_NAME.to_string()), &manifest)
+            .await
+            .map_err(io::Error::other)?;
+        zip.close().await.map_err(io::Error::other)?;
+        Ok(())
+    }
+
+    /// Open an object's content, or list it in the manifest with the reason
+    /// it cannot be added
+    async fn open(&mut self, object: Object) -> Option<ArchiveEntry> {
+        let path = match object.key() {
+            Some(key) => archive_entry_key(key).map_err(|e| e.to_string()),
+            None => Ok(object.id().to_string()),
+        }
+        .and_then(|path| {
+            // Extracting would overwrite one entry with another
+            if path == ARCHIVE_MANIFEST_NAME {
+                Err(format!("Entry path '{path}' is reserved for the manifest"))
+            } else if self.paths.contains(&path) {
+                Err(format!(
+                    "Entry path '{path}' is already taken by another object"
+                ))
+            } else {
+                Ok(path)
+            }
+        });
+        let content = match (object.content_hash(), object.size_bytes()) {
+            (Some(hash), Some(size_bytes)) => Ok((hash, size_bytes)),
+            _ => Err("Object has no content".to_string()),
+        };
+
+        let opened = match (path, content) {
+            (Ok(path), Ok((hash, size_bytes))) => {
+                match self.blob_store.read(hash, object.storage_class()).await {
+                    Ok(reader) => Ok(ArchiveEntry {
+                        path,
+                        size_bytes,
+                        modified_at: object.updated_at(),
+                        reader,
+                    }),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => Err(e),
+        };
+
+        match opened {
+            Ok(entry) => {
+                self.paths.insert(entry.path.clone());
+                self.manifest.added += 1;
+                self.manifest.entries.push(ArchiveDownloadEntry {
+                    path: Some(entry.path.clone()),
+                    object: ObjectDto::from(object),
+                    error: None,
+                });
+                Some(entry)
+            }
+            Err(error) => {
+                tracing::warn!(
+                    object_id = %object.id(),
+                    error = %error,
+                    "Object left out of archive download"
+                );
+                self.manifest.failed += 1;
+                self.manifest.entries.push(ArchiveDownloadEntry {
+                    path: None,
+                    object: ObjectDto::from(object),
+                    error: Some(error),
+                });
+                None
+            }
+        }
+    }
+}
+
+fn tar_header(size_bytes: u64, modified_at: OffsetDateTime) -> tokio_tar::Header {
+    let mut header = tokio_tar::Header::new_gnu();
+    header.set_size(size_bytes);
+    header.set_mode(0o644);
+    header.set_mtime(modified_at.unix_timestamp().max(0) as u64);
+    header.set_entry_type(tokio_tar::EntryType::Regular);
+    header
+}
+
+fn zip_entry(path: String) -> async_zip::ZipEntryBuilder {
+    async_zip::ZipEntryBuilder::new(path.into(), async_zip::Compression::Stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{MockBlobStore, MockObjectRepository, StorageError};
+    use crate::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
+    use std::io::Cursor;
+    use std::str::FromStr;
+    use tokio::io::AsyncReadExt;
+    use tokio_util::compat::FuturesAsyncReadCompatExt;
+    use uuid::Uuid;
+
+    fn object(tenant_id: &TenantId, key: &str, hash: char, content: &str) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("photos").unwrap(),
+            tenant_id.clone(),
+            Some(key.to_string()),
+            StorageClass::Hot,
+        );
+        object
+            .commit(
+                &ContentHash::from_str(&hash.to_string().repeat(64)).unwrap(),
+                content.len() as u64,
+            )
+            .unwrap();
+        object
+    }
+
+    /// Objects `a/1.txt` (stored) and `a/2.txt` (blob missing)
+    fn use_case(tenant_id: &TenantId, max_objects: usize) -> DownloadArchiveUseCase {
+        let objects = vec![
+            object(tenant_id, "a/1.txt", 'a', "hello"),
+            object(tenant_id, "a/2.txt", 'b', "lost"),
+        ];
+        let count = objects.len() as i64;
+
+        let mut object_repo = MockObjectRepository::new();
+        object_repo
+            .expect_count()
+            .returning(move |_, _, _, _, max| Ok(count.min(max)));
+        object_repo
+            .expect_stream()
+            .returning(move |_, _, _, _, _, _| {
+                stream::iter(objects.clone().into_iter().map(Ok)).boxed()
+            });
+
+        let mut blob_store = MockBlobStore::new();
+        blob_store.expect_read().returning(|hash, _| {
+            if hash.as_hex().starts_with('a') {
+                Ok(Box::pin(Cursor::new(b"hello".to_vec())) as BlobReader)
+            } else {
+                Err(StorageError::NotFound(hash.to_string()))
+            }
+        });
+
+        DownloadArchiveUseCase::new(Arc::new(object_repo), Arc::new(blob_store))
+            .with_max_objects(max_objects)
+    }
+
+    fn request(tenant_id: &TenantId) -> ArchiveDownloadRequest {
+        ArchiveDownloadRequest {
+            namespace: "photos".to_string(),
+            tenant_id: tenant_id.to_string(),
+            prefix: Some("a/".to_string()),
+        }
+    }
+
+    async fn collect(
+        archive: impl Stream<Item = io::Result<Bytes>> + Send + 'static,
+    ) -> io::Result<Vec<u8>> {
+        let chunks: Vec<_> = archive.collect().await;
+        let mut bytes = Vec::new();
+        for chunk in chunks {
+            bytes.extend_from_slice(&chunk?);
+        }
+        Ok(bytes)
+    }
+
+    #[tokio::test]
+    async fn test_tar_archive_holds_objects_and_manifest() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let archive = use_case(&tenant_id, 10)
+            .execute(request(&tenant_id), ArchiveFormat::Tar)
+            .await
+            .unwrap();
+        let bytes = collect(archive).await.unwrap();
+
+        let mut entries = Vec::new();
+        let mut tar = tokio_tar::Archive::new(bytes.as_slice());
+        let mut tar_entries = tar.entries().unwrap();
+        while let Some(entry) = tar_entries.next().await {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut content = String::new();
+            entry.read_to_string(&mut content).await.unwrap();
+            entries.push((path, content));
+        }
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], ("a/1.txt".to_string(), "hello".to_string()));
+        assert_eq!(entries[1].0, ARCHIVE_MANIFEST_NAME);
+        let manifest: ArchiveDownloadManifest = serde_json::from_str(&entries[1].1).unwrap();
+        assert_eq!(manifest.added, 1);
+        assert_eq!(manifest.failed, 1);
+        assert_eq!(manifest.entries[0].path.as_deref(), Some("a/1.txt"));
+        assert_eq!(manifest.entries[1].object.key.as_deref(), Some("a/2.txt"));
+        assert!(manifest.entries[1].path.is_none());
+        assert!(manifest.entries[1]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_zip_archive_holds_objects_and_manifest() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let archive = use_case(&tenant_id, 10)
+            .execute(request(&tenant_id), ArchiveFormat::Zip)
+            .await
+            .unwrap();
+        let bytes = collect(archive).await.unwrap();
+
+        let zip = async_zip::base::read::mem::ZipFileReader::new(bytes)
+            .await
+            .unwrap();
+        let names: Vec<_> = zip
+            .file()
+            .entries()
+            .iter()
+            .map(|entry| entry.filename().as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, ["a/1.txt", ARCHIVE_MANIFEST_NAME]);
+
+        let mut content = String::new();
+        zip.reader_with_entry(0)
+            .await
+            .unwrap()
+            .compat()
+            .read_to_string(&mut content)
+            .await
+            .unwrap();
+        assert_eq!(content, "hello");
+    }
+
+    #[tokio::test]
+    async fn test_colliding_entry_paths_are_left_out() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let mut blob_store = MockBlobStore::new();
+        blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"hello".to_vec())) as BlobReader));
+        let mut writer = ArchiveWriter {
+            blob_store: Arc::new(blob_store),
+            max_objects: 10,
+            paths: HashSet::new(),
+            manifest: ArchiveDownloadManifest {
+                namespace: "photos".to_string(),
+                tenant_id: tenant_id.to_string(),
+                prefix: None,
+                added: 0,
+                failed: 0,
+                entries: Vec::new(),
+            },
+        };
+
+        assert!(writer
+            .open(object(&tenant_id, "a//b", 'a', "hello"))
+            .await
+            .is_some());
+        assert!(writer
+            .open(object(&tenant_id, "a/b", 'a', "hello"))
+            .await
+            .is_none());
+        assert!(writer
+            .open(object(&tenant_id, ARCHIVE_MANIFEST_NAME, 'a', "hello"))
+            .await
+            .is_none());
+
+        assert_eq!(writer.manifest.added, 1);
+        assert_eq!(writer.manifest.failed, 2);
+        assert!(writer.manifest.entries[1]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("already taken"));
+        assert!(writer.manifest.entries[2]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("reserved"));
+    }
+
+    #[tokio::test]
+    async fn test_too_many_objects_rejected_before_streaming() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+
+        let result = use_case(&tenant_id, 1)
+            .execute(request(&tenant_id), ArchiveFormat::Tar)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::InvalidRequest(message)) if message.contains("More than 1")
+        ));
+    }
+}
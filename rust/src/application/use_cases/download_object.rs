@@ -1,14 +1,33 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use crate::application::dto::DownloadMetadata;
-use crate::application::errors::DownloadUseCaseError;
-use crate::application::ports::{BlobReader, BlobStore, ObjectRepository};
-use crate::domain::value_objects::ObjectId;
+use serde_json::json;
+use time::OffsetDateTime;
+
+use crate::api::middleware::audit::{AuditEventType, AuditLogEntry};
+use crate::application::access_stats::AccessRecorder;
+use crate::application::dto::{
+    DedupInfo, DownloadMetadata, ObjectDto, ObjectHead, ObjectRecordDto,
+};
+use crate::application::errors::{DownloadUseCaseError, GhostObjectPolicy};
+use crate::application::ports::{
+    AuditRepository, BlobReader, BlobStore, ObjectRepository, RestoreStatus, StorageError,
+};
+use crate::application::read_verification::{ReadVerificationStats, ReadVerifier, VerifyOnRead};
+use crate::application::short_hashes::ShortHashes;
+use crate::domain::entities::Object;
+use crate::domain::value_objects::{ContentHash, ObjectId};
 
 /// Use case: Download an object
 pub struct DownloadObjectUseCase {
     object_repo: Arc<dyn ObjectRepository>,
     blob_store: Arc<dyn BlobStore>,
+    audit_repo: Option<Arc<dyn AuditRepository>>,
+    access_recorder: Option<Arc<AccessRecorder>>,
+    ghost_object_policy: GhostObjectPolicy,
+    ghost_objects_detected: AtomicU64,
+    read_verifier: Arc<ReadVerifier>,
+    short_hashes: Option<ShortHashes>,
 }
 
 impl DownloadObjectUseCase {
@@ -16,10 +35,80 @@ impl DownloadObjectUseCase {
         Self {
             object_repo,
             blob_store,
+            audit_repo: None,
+            access_recorder: None,
+            ghost_object_policy: GhostObjectPolicy::default(),
+            ghost_objects_detected: AtomicU64::new(0),
+            read_verifier: Arc::new(ReadVerifier::default()),
+            short_hashes: None,
+        }
+    }
+
+    /// Record ghost object detections in the audit log for later reconciliation
+    pub fn with_audit_repo(mut self, audit_repo: Arc<dyn AuditRepository>) -> Self {
+        self.audit_repo = Some(audit_repo);
+        self
+    }
+
+    /// Count downloads for the object's access statistics
+    pub fn with_access_recorder(mut self, access_recorder: Arc<AccessRecorder>) -> Self {
+        self.access_recorder = Some(access_recorder);
+        self
+    }
+
+    /// Set how ghost objects are reported to clients
+    pub fn with_ghost_object_policy(mut self, policy: GhostObjectPolicy) -> Self {
+        self.ghost_object_policy = policy;
+        self
+    }
+
+    /// Re-hash the blob of every download, or of a `sample_rate` share of
+    /// them when `Sampled`, and fail those that no longer match their hash
+    pub fn with_read_verification(mut self, mode: VerifyOnRead, sample_rate: f64) -> Self {
+        self.read_verifier = Arc::new(ReadVerifier::new(mode, sample_rate));
+        self
+    }
+
+    /// Report short hashes on object metadata while `short_hashes` has
+    /// them on
+    pub fn with_short_hashes(mut self, short_hashes: ShortHashes) -> Self {
+        self.short_hashes = Some(short_hashes);
+        self
+    }
+
+    /// Downloads verified and corrupt blobs found since startup
+    pub fn read_verification_stats(&self) -> ReadVerificationStats {
+        self.read_verifier.stats()
+    }
+
+    /// Number of ghost objects detected since startup
+    pub fn ghost_objects_detected(&self) -> u64 {
+        self.ghost_objects_detected.load(Ordering::Relaxed)
+    }
+
+    /// Check whether a committed object has lost its blob
+    ///
+    /// Returns `Ok(false)` for objects that are missing or not committed,
+    /// since those are not ghosts.
+    pub async fn is_ghost(&self, object_id: &ObjectId) -> Result<bool, DownloadUseCaseError> {
+        let object = match self.object_repo.find_by_id(object_id).await? {
+            Some(obj) => obj,
+            None => return Ok(false),
+        };
+
+        match object.content_hash() {
+            Some(hash) => Ok(!self.blob_store.exists(hash, object.storage_class()).await?),
+            None => Ok(false),
         }
     }
 
     /// Execute download by ID
+    #[tracing::instrument(
+        name = "DownloadObjectUseCase::execute_by_id",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id)
+    )]
     pub async fn execute_by_id(
         &self,
         object_id: &ObjectId,
@@ -51,17 +140,61 @@ impl DownloadObjectUseCase {
             .size_bytes()
             .ok_or_else(|| DownloadUseCaseError::NotReadable("No size".to_string()))?;
 
-        // 4. Open blob for reading
-        let reader = self
+        // 4. Make sure the blob still exists (it may have been lost out-of-band)
+        if !self
+            .blob_store
+            .exists(content_hash, object.storage_class())
+            .await?
+        {
+            return Err(self.report_ghost(&object, content_hash).await);
+        }
+
+        // 5. Open blob for reading; a cold blob may need restoring first
+        let reader = match self
             .blob_store
             .read(content_hash, object.storage_class())
-            .await?;
+            .await
+        {
+            Err(StorageError::Restoring(_)) => {
+                // Make sure a restore is under way before asking the client to retry
+                match self.blob_store.initiate_restore(content_hash).await?.status {
+                    RestoreStatus::Restored => {
+                        self.blob_store
+                            .read(content_hash, object.storage_class())
+                            .await?
+                    }
+                    RestoreStatus::InProgress { retry_after_secs } => {
+                        return Err(DownloadUseCaseError::Restoring {
+                            object_id: object.id().to_string(),
+                            retry_after_secs,
+                        })
+                    }
+                }
+            }
+            result => result?,
+        };
+        let reader = if self.read_verifier.should_verify() {
+            self.read_verifier
+                .verify(reader, content_hash.clone(), *object.id())
+        } else {
+            reader
+        };
+
+        // 6. Count the download (flushed in batches)
+        if let Some(access_recorder) = &self.access_recorder {
+            access_recorder.record(*object.id());
+        }
 
-        // 5. Return metadata + stream
+        // 7. Return metadata + stream
         let metadata = DownloadMetadata {
             object_id: *object.id(),
+            key: object.key().map(str::to_string),
             size_bytes,
             content_hash: content_hash.to_string(),
+            content_type: object.content_type().map(str::to_string),
+            content_encoding: object.content_encoding(),
+            storage_class: object.storage_class(),
+            updated_at: object.updated_at(),
         };
 
         Ok((metadata, reader))
@@ -109,21 +242,210 @@ impl DownloadObjectUseCase {
         // Reuse by_id logic
         self.execute_by_id(object.id()).await
     }
+
+    /// Look up the headers of a committed object owned by `tenant_id`
+    ///
+    /// Only the metadata record is read; the blob store is never touched.
+    pub async fn head_by_id(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &str,
+    ) -> Result<ObjectHead, DownloadUseCaseError> {
+        self.object_repo
+            .head(object_id)
+            .await?
+            .filter(|head| head.tenant_id == tenant_id)
+            .ok_or_else(|| DownloadUseCaseError::NotFound(object_id.to_string()))
+    }
+
+    /// Look up a committed object owned by `tenant_id`, metadata included
+    ///
+    /// Like `head_by_id`, the blob store is never touched.
+    pub async fn metadata_by_id(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &str,
+    ) -> Result<ObjectDto, DownloadUseCaseError> {
+        let object = self
+            .object_repo
+            .find_by_id(object_id)
+            .await?
+            .filter(|object| object.tenant_id().to_string() == tenant_id)
+            .ok_or_else(|| DownloadUseCaseError::NotFound(object_id.to_string()))?;
+
+        self.dto(object).await
+    }
+
+    /// Look up the full metadata record of a committed object owned by
+    /// `tenant_id`
+    ///
+    /// With `include_dedup`, also reports how many objects share its blob.
+    /// Like `head_by_id`, the blob store is never touched.
+    pub async fn record_by_id(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &str,
+        include_dedup: bool,
+    ) -> Result<ObjectRecordDto, DownloadUseCaseError> {
+        let record = self
+            .object_repo
+            .find_record(object_id)
+            .await?
+            .filter(|record| record.object.tenant_id().to_string() == tenant_id)
+            .ok_or_else(|| DownloadUseCaseError::NotFound(object_id.to_string()))?;
+
+        Ok(ObjectRecordDto {
+            dedup: record
+                .blob_ref_count
+                .filter(|_| include_dedup)
+                .map(DedupInfo::from_ref_count),
+            object: self.dto(record.object).await?,
+        })
+    }
+
+    /// Metadata of `object`, with its short hash when short hashes are on
+    async fn dto(&self, object: Object) -> Result<ObjectDto, DownloadUseCaseError> {
+        let short_hash = match &self.short_hashes {
+            Some(short_hashes) => short_hashes.short_hash(object.content_hash()).await?,
+            None => None,
+        };
+        Ok(ObjectDto {
+            short_hash,
+            ..ObjectDto::from(object)
+        })
+    }
+
+    /// Look up the headers of a committed object by key (namespace + tenant + key)
+    pub async fn head_by_key(
+        &self,
+        namespace: &str,
+        tenant_id: &str,
+        key: &str,
+    ) -> Result<ObjectHead, DownloadUseCaseError> {
+        use crate::domain::value_objects::{Namespace, TenantId};
+
+        let namespace = Namespace::new(namespace.to_string())
+            .map_err(|e| DownloadUseCaseError::NotFound(e.to_string()))?;
+
+        let tenant_id = TenantId::from_string(tenant_id)
+            .map_err(|e| DownloadUseCaseError::NotFound(e.to_string()))?;
+
+        self.object_repo
+            .head_by_key(&namespace, &tenant_id, key)
+            .await?
+            .ok_or_else(|| {
+                DownloadUseCaseError::NotFound(format!("{}/{}/{}", namespace, tenant_id, key))
+            })
+    }
+
+    /// Log, count and audit a ghost object, returning the error to surface
+    async fn report_ghost(
+        &self,
+        object: &Object,
+        content_hash: &ContentHash,
+    ) -> DownloadUseCaseError {
+        self.ghost_objects_detected.fetch_add(1, Ordering::Relaxed);
+
+        tracing::warn!(
+            object_id = %object.id(),
+            content_hash = %content_hash,
+            storage_class = %object.storage_class(),
+            "Ghost object detected: blob missing for committed object"
+        );
+
+        let error = DownloadUseCaseError::GhostObject {
+            object_id: object.id().to_string(),
+            content_hash: content_hash.to_string(),
+            policy: self.ghost_object_policy,
+        };
+
+        if let Some(audit_repo) = &self.audit_repo {
+            let status_code = match self.ghost_object_policy {
+                GhostObjectPolicy::Gone => 410,
+                GhostObjectPolicy::InternalError => 500,
+            };
+            let entry = AuditLogEntry {
+                timestamp: OffsetDateTime::now_utc(),
+                event_type: AuditEventType::GhostObjectDetected,
+                user_id: None,
+                tenant_id: Some(object.tenant_id().to_string()),
+                api_key_id: None,
+                ip_address: None,
+                user_agent: None,
+                method: "GET".to_string(),
+                path: format!("/v1/objects/{}", object.id()),
+                query: None,
+                status_code: Some(status_code),
+                response_time_ms: None,
+                error_message: Some(error.to_string()),
+                additional_data: Some(json!({
+                    "object_id": object.id().to_string(),
+                    "namespace": object.namespace().to_string(),
+                    "key": object.key(),
+                    "content_hash": content_hash.to_string(),
+                    "storage_class": object.storage_class().to_string(),
+                })),
+            };
+
+            if let Err(e) = audit_repo.store(entry).await {
+                tracing::error!("Failed to store audit log for ghost object: {}", e);
+            }
+        }
+
+        error
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::application::ports::{MockBlobStore, MockObjectRepository};
+    use crate::application::dto::ObjectRecord;
+    use crate::application::ports::{
+        AuditQueryFilter, AuditRepositoryError, MockBlobStore, MockObjectRepository, RestoreJob,
+    };
     use crate::domain::entities::Object;
     use crate::domain::value_objects::{
         ContentHash, Namespace, ObjectId, ObjectStatus, StorageClass, TenantId,
     };
     use std::io::Cursor;
     use std::str::FromStr;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use uuid::Uuid;
 
+    /// Audit repository that keeps stored entries in memory
+    #[derive(Default)]
+    struct RecordingAuditRepository {
+        entries: Mutex<Vec<AuditLogEntry>>,
+    }
+
+    #[async_trait::async_trait]
+    impl AuditRepository for RecordingAuditRepository {
+        async fn store(&self, entry: AuditLogEntry) -> Result<(), AuditRepositoryError> {
+            self.entries.lock().unwrap().push(entry);
+            Ok(())
+        }
+
+        async fn query(
+            &self,
+            _filter: AuditQueryFilter,
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<AuditLogEntry>, AuditRepositoryError> {
+            Ok(self.entries.lock().unwrap().clone())
+        }
+
+        async fn count(&self, _filter: AuditQueryFilter) -> Result<i64, AuditRepositoryError> {
+            Ok(self.entries.lock().unwrap().len() as i64)
+        }
+
+        async fn cleanup_old_logs(
+            &self,
+            _retention_days: i32,
+        ) -> Result<i64, AuditRepositoryError> {
+            Ok(0)
+        }
+    }
+
     fn create_test_object(status: ObjectStatus) -> Object {
         let mut object = Object::new(
             Namespace::from_str("test").unwrap(),
@@ -158,6 +480,11 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(object.clone())));
 
+        mock_blob_store
+            .expect_exists()
+            .times(1)
+            .returning(|_, _| Ok(true));
+
         mock_blob_store.expect_read().times(1).returning(|_, _| {
             let reader = Box::pin(Cursor::new("test data"));
             Ok(reader)
@@ -173,6 +500,66 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_download_records_access() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_blob_store.expect_exists().returning(|_, _| Ok(true));
+        mock_blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new("test data"))));
+
+        let mut stats_repo = MockObjectRepository::new();
+        stats_repo
+            .expect_record_access()
+            .withf(move |accesses| {
+                accesses.len() == 1 && accesses[0].object_id == object_id && accesses[0].count == 2
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        let recorder = Arc::new(AccessRecorder::new(Arc::new(stats_repo)));
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store))
+                .with_access_recorder(Arc::clone(&recorder));
+
+        use_case.execute_by_id(&object_id).await.unwrap();
+        use_case.execute_by_id(&object_id).await.unwrap();
+
+        assert_eq!(recorder.flush().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_blob_fails_verified_download() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_blob_store.expect_exists().returning(|_, _| Ok(true));
+        // Content that does not hash to the stored "aaaa..." hash
+        mock_blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new("test data"))));
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store))
+                .with_read_verification(VerifyOnRead::Always, 0.0);
+
+        let (_, mut reader) = use_case.execute_by_id(&object_id).await.unwrap();
+        let result = tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut Vec::new()).await;
+
+        assert!(result.is_err());
+        assert_eq!(use_case.read_verification_stats().mismatches, 1);
+    }
+
     #[tokio::test]
     async fn test_download_by_id_not_found() {
         // Arrange
@@ -219,4 +606,302 @@ mod tests {
         // Assert
         assert!(matches!(result, Err(DownloadUseCaseError::NotReadable(_))));
     }
+
+    #[tokio::test]
+    async fn test_download_by_id_ghost_object() {
+        // Arrange: committed object whose blob was deleted out-of-band
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .withf(move |id| id == &object_id)
+            .times(1)
+            .returning(move |_| Ok(Some(object.clone())));
+
+        mock_blob_store
+            .expect_exists()
+            .times(1)
+            .returning(|_, _| Ok(false));
+        mock_blob_store.expect_read().never();
+
+        let audit_repo = Arc::new(RecordingAuditRepository::default());
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store))
+                .with_audit_repo(audit_repo.clone());
+
+        // Act
+        let result = use_case.execute_by_id(&object_id).await;
+
+        // Assert
+        match result {
+            Err(DownloadUseCaseError::GhostObject {
+                object_id: id,
+                policy,
+                ..
+            }) => {
+                assert_eq!(id, object_id.to_string());
+                assert_eq!(policy, GhostObjectPolicy::Gone);
+            }
+            _ => panic!("expected GhostObject error"),
+        }
+        assert_eq!(use_case.ghost_objects_detected(), 1);
+
+        let entries = audit_repo.entries.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(
+            entries[0].event_type,
+            AuditEventType::GhostObjectDetected
+        ));
+        assert_eq!(entries[0].status_code, Some(410));
+    }
+
+    #[tokio::test]
+    async fn test_download_ghost_object_uses_configured_policy() {
+        // Arrange
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_blob_store.expect_exists().returning(|_, _| Ok(false));
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store))
+                .with_ghost_object_policy(GhostObjectPolicy::InternalError);
+
+        // Act
+        let result = use_case.execute_by_id(&object_id).await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(DownloadUseCaseError::GhostObject {
+                policy: GhostObjectPolicy::InternalError,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_unrestored_cold_blob_starts_restore() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_blob_store.expect_exists().returning(|_, _| Ok(true));
+        mock_blob_store
+            .expect_read()
+            .times(1)
+            .returning(|hash, _| Err(StorageError::Restoring(hash.to_string())));
+        mock_blob_store
+            .expect_initiate_restore()
+            .times(1)
+            .returning(|hash| {
+                Ok(RestoreJob {
+                    id: hash.to_string(),
+                    status: RestoreStatus::InProgress {
+                        retry_after_secs: 300,
+                    },
+                })
+            });
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store));
+
+        let result = use_case.execute_by_id(&object_id).await;
+
+        match result {
+            Err(DownloadUseCaseError::Restoring {
+                object_id: id,
+                retry_after_secs,
+            }) => {
+                assert_eq!(id, object_id.to_string());
+                assert_eq!(retry_after_secs, 300);
+            }
+            _ => panic!("expected Restoring error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_reads_again_once_restored() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_blob_store.expect_exists().returning(|_, _| Ok(true));
+        let reads = AtomicU64::new(0);
+        mock_blob_store
+            .expect_read()
+            .times(2)
+            .returning(move |hash, _| {
+                if reads.fetch_add(1, Ordering::Relaxed) == 0 {
+                    Err(StorageError::Restoring(hash.to_string()))
+                } else {
+                    Ok(Box::pin(Cursor::new("test data")))
+                }
+            });
+        mock_blob_store.expect_initiate_restore().returning(|hash| {
+            Ok(RestoreJob {
+                id: hash.to_string(),
+                status: RestoreStatus::Restored,
+            })
+        });
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store));
+
+        assert!(use_case.execute_by_id(&object_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_is_ghost() {
+        // Arrange
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_blob_store.expect_exists().returning(|_, _| Ok(false));
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(mock_blob_store));
+
+        // Act & Assert
+        assert!(use_case.is_ghost(&object_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_head_by_id_reads_metadata_only() {
+        // Arrange: the blob store has no expectations, so any call panics
+        let mut mock_object_repo = MockObjectRepository::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+        let tenant_id = object.tenant_id().to_string();
+        let head = ObjectHead::from_committed(&object).unwrap();
+
+        mock_object_repo
+            .expect_head()
+            .withf(move |id| id == &object_id)
+            .times(1)
+            .returning(move |_| Ok(Some(head.clone())));
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(MockBlobStore::new()));
+
+        // Act
+        let head = use_case.head_by_id(&object_id, &tenant_id).await.unwrap();
+
+        // Assert
+        assert_eq!(head.size_bytes, 123);
+        assert_eq!(head.content_hash, "a".repeat(64));
+        assert_eq!(head.storage_class, StorageClass::Hot);
+    }
+
+    #[tokio::test]
+    async fn test_head_by_id_hides_other_tenants_objects() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+        let head = ObjectHead::from_committed(&object).unwrap();
+
+        mock_object_repo
+            .expect_head()
+            .returning(move |_| Ok(Some(head.clone())));
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(MockBlobStore::new()));
+
+        let result = use_case
+            .head_by_id(&object_id, &Uuid::new_v4().to_string())
+            .await;
+
+        assert!(matches!(result, Err(DownloadUseCaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_metadata_by_id_hides_other_tenants_objects() {
+        // Arrange: the blob store has no expectations, so any call panics
+        let mut mock_object_repo = MockObjectRepository::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+        let tenant_id = object.tenant_id().to_string();
+
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(MockBlobStore::new()));
+
+        // Act
+        let own = use_case.metadata_by_id(&object_id, &tenant_id).await;
+        let other = use_case
+            .metadata_by_id(&object_id, &Uuid::new_v4().to_string())
+            .await;
+
+        // Assert
+        assert_eq!(own.unwrap().id, object_id.to_string());
+        assert!(matches!(other, Err(DownloadUseCaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_record_by_id_reports_dedup_only_when_asked() {
+        // Arrange: the blob store has no expectations, so any call panics
+        let mut mock_object_repo = MockObjectRepository::new();
+        let object = create_test_object(ObjectStatus::Committed);
+        let object_id = *object.id();
+        let tenant_id = object.tenant_id().to_string();
+
+        mock_object_repo.expect_find_record().returning(move |_| {
+            Ok(Some(ObjectRecord {
+                object: object.clone(),
+                blob_ref_count: Some(3),
+            }))
+        });
+
+        let use_case =
+            DownloadObjectUseCase::new(Arc::new(mock_object_repo), Arc::new(MockBlobStore::new()));
+
+        // Act
+        let admin = use_case
+            .record_by_id(&object_id, &tenant_id, true)
+            .await
+            .unwrap();
+        let user = use_case
+            .record_by_id(&object_id, &tenant_id, false)
+            .await
+            .unwrap();
+        let other = use_case
+            .record_by_id(&object_id, &Uuid::new_v4().to_string(), true)
+            .await;
+
+        // Assert
+        assert_eq!(admin.object.id, object_id.to_string());
+        assert_eq!(
+            admin.dedup,
+            Some(DedupInfo {
+                ref_count: 3,
+                shared: true
+            })
+        );
+        assert_eq!(user.dedup, None);
+        assert!(matches!(other, Err(DownloadUseCaseError::NotFound(_))));
+    }
 }
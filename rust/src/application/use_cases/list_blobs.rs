@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use crate::application::dto::{BlobDto, ListBlobsRequest, ListBlobsResponse};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::BlobRepository;
+use crate::application::short_hashes::ShortHashes;
+use crate::domain::value_objects::{ContentHash, ContentHashPrefix};
+
+/// Default number of blobs per page
+const DEFAULT_BLOB_LIST_LIMIT: i64 = 100;
+
+/// Most blobs returned per page
+const MAX_BLOB_LIST_LIMIT: i64 = 1000;
+
+/// Use case: List blob entries and their reference counts
+///
+/// For debugging deduplication and GC: `orphaned` lists the blobs without
+/// references, which the next GC runs will collect unless an object under
+/// retention still points at them.
+pub struct ListBlobsUseCase {
+    blob_repo: Arc<dyn BlobRepository>,
+    short_hashes: ShortHashes,
+}
+
+impl ListBlobsUseCase {
+    pub fn new(blob_repo: Arc<dyn BlobRepository>) -> Self {
+        Self {
+            short_hashes: ShortHashes::new(Arc::clone(&blob_repo), 0),
+            blob_repo,
+        }
+    }
+
+    /// Look blobs up by hash prefix and report short hashes of at least
+    /// `len` characters; 0 turns short hashes off
+    pub fn with_short_hash_len(mut self, len: usize) -> Self {
+        self.short_hashes = ShortHashes::new(Arc::clone(&self.blob_repo), len);
+        self
+    }
+
+    /// Find one blob by its content hash, or by a unique prefix of it when
+    /// short hashes are on
+    ///
+    /// A prefix matching several blobs is rejected as ambiguous. The blob
+    /// comes with its shortest unique prefix, so clients can keep using it.
+    pub async fn get(&self, reference: &str) -> Result<BlobDto, ObjectUseCaseError> {
+        let prefix = ContentHashPrefix::from_hex(reference).map_err(|e| {
+            ObjectUseCaseError::InvalidRequest(format!("Invalid content hash: {e}"))
+        })?;
+        let blob = self
+            .short_hashes
+            .find(&prefix)
+            .await?
+            .ok_or_else(|| ObjectUseCaseError::NotFound(format!("blob {prefix}")))?;
+
+        Ok(BlobDto {
+            short_hash: self
+                .short_hashes
+                .short_hash(Some(blob.content_hash()))
+                .await?,
+            ..BlobDto::from(blob)
+        })
+    }
+
+    /// Execute, one page at a time in content hash order
+    ///
+    /// The cursor is the hash of the last blob of the previous page.
+    pub async fn execute(
+        &self,
+        request: ListBlobsRequest,
+    ) -> Result<ListBlobsResponse, ObjectUseCaseError> {
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_BLOB_LIST_LIMIT)
+            .clamp(1, MAX_BLOB_LIST_LIMIT);
+        let after = request
+            .cursor
+            .map(ContentHash::from_hex)
+            .transpose()
+            .map_err(|_| ObjectUseCaseError::InvalidRequest("Invalid cursor".to_string()))?;
+
+        // One extra row to tell whether another page exists
+        let mut blobs = self
+            .blob_repo
+            .list(after, request.orphaned, limit + 1)
+            .await?;
+        let has_more = blobs.len() as i64 > limit;
+        blobs.truncate(limit as usize);
+
+        let next_cursor = blobs
+            .last()
+            .filter(|_| has_more)
+            .map(|last| last.content_hash().to_string());
+
+        Ok(ListBlobsResponse {
+            blobs: blobs.into_iter().map(BlobDto::from).collect(),
+            limit,
+            has_more,
+            next_cursor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockBlobRepository;
+    use crate::domain::entities::Blob;
+    use crate::domain::value_objects::StorageClass;
+    use time::OffsetDateTime;
+
+    fn hash(digit: char) -> ContentHash {
+        ContentHash::from_hex(digit.to_string().repeat(64)).unwrap()
+    }
+
+    fn blob(digit: char) -> Blob {
+        Blob::reconstruct(
+            hash(digit),
+            StorageClass::Hot,
+            42,
+            0,
+            OffsetDateTime::now_utc(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_list_blobs_pages_by_hash() {
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_list()
+            .withf(|after, orphaned_only, limit| {
+                *after == Some(hash('a')) && *orphaned_only && *limit == 3
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(vec![blob('b'), blob('c'), blob('d')]));
+        let use_case = ListBlobsUseCase::new(Arc::new(mock_blob_repo));
+
+        let response = use_case
+            .execute(ListBlobsRequest {
+                limit: Some(2),
+                cursor: Some(hash('a').to_string()),
+                orphaned: true,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.blobs.len(), 2);
+        assert!(response.has_more);
+        assert_eq!(response.next_cursor, Some(hash('c').to_string()));
+        assert_eq!(response.blobs[0].ref_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_blobs_rejects_invalid_cursor() {
+        let use_case = ListBlobsUseCase::new(Arc::new(MockBlobRepository::new()));
+
+        let result = use_case
+            .execute(ListBlobsRequest {
+                cursor: Some("not-a-hash".to_string()),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_by_prefix_reports_unique_short_hash() {
+        // Two blobs sharing their first 9 characters
+        let target = ContentHash::from_hex("abcdef012".to_string() + &"1".repeat(55)).unwrap();
+        let neighbour = ContentHash::from_hex("abcdef012".to_string() + &"2".repeat(55)).unwrap();
+        let blobs = vec![
+            Blob::reconstruct(
+                target.clone(),
+                StorageClass::Hot,
+                42,
+                1,
+                OffsetDateTime::now_utc(),
+            ),
+            Blob::reconstruct(
+                neighbour,
+                StorageClass::Hot,
+                42,
+                1,
+                OffsetDateTime::now_utc(),
+            ),
+        ];
+
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_find_by_prefix()
+            .returning(move |prefix, limit| {
+                Ok(blobs
+                    .iter()
+                    .filter(|blob| blob.content_hash().matches(prefix))
+                    .take(limit as usize)
+                    .cloned()
+                    .collect())
+            });
+        let use_case = ListBlobsUseCase::new(Arc::new(mock_blob_repo)).with_short_hash_len(7);
+
+        let blob = use_case.get("abcdef0121").await.unwrap();
+        assert_eq!(blob.content_hash, target.to_string());
+        assert_eq!(blob.short_hash.as_deref(), Some("abcdef0121"));
+
+        let ambiguous = use_case.get("abcdef01").await;
+        assert!(matches!(
+            ambiguous,
+            Err(ObjectUseCaseError::InvalidRequest(_))
+        ));
+
+        let missing = use_case.get("ffff").await;
+        assert!(matches!(missing, Err(ObjectUseCaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_needs_full_hash_when_short_hashes_off() {
+        let use_case = ListBlobsUseCase::new(Arc::new(MockBlobRepository::new()));
+
+        let result = use_case.get("abcdef01").await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+}
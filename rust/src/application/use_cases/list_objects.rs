@@ -1,45 +1,274 @@
+use futures_util::{Stream, StreamExt};
 use std::sync::Arc;
 
-use crate::application::dto::{ListRequest, ListResponse, ObjectDto};
+use crate::application::dto::{
+    ListRequest, ListResponse, ObjectDto, ObjectField, ProjectedListResponse, SortDirection,
+    SortField,
+};
 use crate::application::errors::ObjectUseCaseError;
+use crate::application::key_prefix_query::{KeyPrefixQuery, MAX_COMMON_PREFIXES};
+use crate::application::list_cursor::ListCursor;
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::pagination::{PageLimits, Pagination};
 use crate::application::ports::ObjectRepository;
 use crate::application::validation::validate_namespace_and_tenant;
+use crate::domain::value_objects::{Namespace, TenantId};
+
+/// Default number of objects counted before `total` is reported as a lower bound
+pub const DEFAULT_LIST_COUNT_LIMIT: i64 = 10_000;
 
 /// Use case: List objects
 pub struct ListObjectsUseCase {
     object_repo: Arc<dyn ObjectRepository>,
+    count_limit: i64,
 }
 
 impl ListObjectsUseCase {
     pub fn new(object_repo: Arc<dyn ObjectRepository>) -> Self {
-        Self { object_repo }
+        Self {
+            object_repo,
+            count_limit: DEFAULT_LIST_COUNT_LIMIT,
+        }
     }
 
-    /// Execute list with pagination
-    pub async fn execute(&self, request: ListRequest) -> Result<ListResponse, ObjectUseCaseError> {
-        // 1. Parse and validate
-        let (namespace, tenant_id) =
-            validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
+    /// Stop counting at `count_limit` objects; larger totals are reported
+    /// as inexact
+    pub fn with_count_limit(mut self, count_limit: i64) -> Self {
+        self.count_limit = count_limit;
+        self
+    }
 
-        let limit = request.limit.unwrap_or(100).min(1000); // Cap at 1000
-        let offset = request.offset.unwrap_or(0);
+    /// Execute list with pagination (newest first unless a sort is requested)
+    ///
+    /// Pages follow each other by offset, or by the cursor each page returns
+    /// for the next one.
+    pub async fn execute(&self, request: ListRequest) -> Result<ListResponse, ObjectUseCaseError> {
+        let listing = Listing::parse(request)?;
 
-        // 2. Query repository
-        let objects = self
+        // Query repository, one extra row to tell whether another page exists
+        let mut objects = self
             .object_repo
-            .list(&namespace, &tenant_id, limit, offset)
+            .list(
+                &listing.namespace,
+                &listing.tenant_id,
+                &listing.keys,
+                &listing.metadata,
+                listing.sort_by,
+                listing.sort_direction,
+                listing.after.clone(),
+                listing.limit + 1,
+                listing.offset,
+            )
             .await?;
+        let has_more = objects.len() as i64 > listing.limit;
+        objects.truncate(listing.limit as usize);
 
-        // 3. Convert to DTOs
-        let dtos: Vec<ObjectDto> = objects.into_iter().map(ObjectDto::from).collect();
-
-        let total = dtos.len();
+        let next_cursor = objects
+            .last()
+            .filter(|_| has_more && ListCursor::supports(listing.sort_by))
+            .map(|last| ListCursor::after(last, listing.sort_by, listing.sort_direction).encode());
+        let (total, total_exact) = self.total(&listing, objects.len(), has_more).await?;
+        let common_prefixes = self.common_prefixes(&listing).await?;
 
         Ok(ListResponse {
-            objects: dtos,
+            objects: objects.into_iter().map(ObjectDto::from).collect(),
+            total,
+            total_exact,
+            limit: listing.limit,
+            offset: listing.offset,
+            has_more,
+            next_cursor,
+            common_prefixes,
+        })
+    }
+
+    /// Execute list like [`Self::execute`], loading only `fields` of each object
+    pub async fn execute_projected(
+        &self,
+        request: ListRequest,
+        fields: &[ObjectField],
+    ) -> Result<ProjectedListResponse, ObjectUseCaseError> {
+        if fields.is_empty() {
+            return Err(ObjectUseCaseError::InvalidRequest(
+                "At least one field must be requested".to_string(),
+            ));
+        }
+        let listing = Listing::parse(request)?;
+        if listing.after.is_some() {
+            return Err(ObjectUseCaseError::InvalidRequest(
+                "cursor cannot be combined with fields; page projected listings by offset"
+                    .to_string(),
+            ));
+        }
+
+        let mut objects = self
+            .object_repo
+            .list_projected(
+                &listing.namespace,
+                &listing.tenant_id,
+                &listing.keys,
+                &listing.metadata,
+                listing.sort_by,
+                listing.sort_direction,
+                fields,
+                listing.limit + 1,
+                listing.offset,
+            )
+            .await?;
+        let has_more = objects.len() as i64 > listing.limit;
+        objects.truncate(listing.limit as usize);
+
+        let (total, total_exact) = self.total(&listing, objects.len(), has_more).await?;
+        let common_prefixes = self.common_prefixes(&listing).await?;
+
+        Ok(ProjectedListResponse {
+            objects,
             total,
-            limit,
-            offset,
+            total_exact,
+            limit: listing.limit,
+            offset: listing.offset,
+            has_more,
+            common_prefixes,
+        })
+    }
+
+    /// Stream every object of the listing in order, ignoring `limit`,
+    /// `offset` and `cursor`
+    ///
+    /// Invalid requests fail before anything is read; a failed read ends
+    /// the stream with its error. Common prefixes are not streamed.
+    pub fn stream(
+        &self,
+        request: ListRequest,
+    ) -> Result<impl Stream<Item = Result<ObjectDto, ObjectUseCaseError>> + Send, ObjectUseCaseError>
+    {
+        let listing = Listing::parse(request)?;
+
+        let objects = self.object_repo.stream(
+            &listing.namespace,
+            &listing.tenant_id,
+            &listing.keys,
+            &listing.metadata,
+            listing.sort_by,
+            listing.sort_direction,
+        );
+        Ok(objects.map(|object| {
+            object
+                .map(ObjectDto::from)
+                .map_err(ObjectUseCaseError::Repository)
+        }))
+    }
+
+    /// Common prefixes of a delimited listing; the same for every page
+    async fn common_prefixes(&self, listing: &Listing) -> Result<Vec<String>, ObjectUseCaseError> {
+        if listing.keys.delimiter().is_none() {
+            return Ok(Vec::new());
+        }
+
+        Ok(self
+            .object_repo
+            .common_prefixes(
+                &listing.namespace,
+                &listing.tenant_id,
+                &listing.keys,
+                &listing.metadata,
+                MAX_COMMON_PREFIXES,
+            )
+            .await?)
+    }
+
+    /// Total objects of a listing and whether it is exact; a first page that
+    /// is also the last needs no query
+    async fn total(
+        &self,
+        listing: &Listing,
+        page_len: usize,
+        has_more: bool,
+    ) -> Result<(usize, bool), ObjectUseCaseError> {
+        let seen = listing.offset + page_len as i64;
+        if listing.offset == 0 && !has_more {
+            return Ok((seen as usize, true));
+        }
+
+        let counted = self
+            .object_repo
+            .count(
+                &listing.namespace,
+                &listing.tenant_id,
+                &listing.keys,
+                &listing.metadata,
+                self.count_limit,
+            )
+            .await?;
+        // Past the count limit the page itself is a better lower bound
+        Ok((counted.max(seen) as usize, counted < self.count_limit))
+    }
+}
+
+/// A validated list request
+struct Listing {
+    namespace: Namespace,
+    tenant_id: TenantId,
+    keys: KeyPrefixQuery,
+    metadata: MetadataQuery,
+    sort_by: SortField,
+    sort_direction: SortDirection,
+    /// Start after this object instead of at `offset`
+    after: Option<ListCursor>,
+    limit: i64,
+    offset: i64,
+}
+
+impl Listing {
+    fn parse(request: ListRequest) -> Result<Self, ObjectUseCaseError> {
+        let (namespace, tenant_id) =
+            validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
+
+        let keys = KeyPrefixQuery::new(request.prefix, request.delimiter)
+            .map_err(ObjectUseCaseError::InvalidRequest)?;
+        let metadata = MetadataQuery::new(
+            request.metadata_filters,
+            request.metadata_has_keys.as_deref().unwrap_or_default(),
+        )
+        .map_err(ObjectUseCaseError::InvalidRequest)?;
+
+        let sort_by = request.sort_by.unwrap_or_default();
+        let sort_direction = request.sort_direction.unwrap_or_default();
+        let page = Pagination::new(request.limit, request.offset, PageLimits::OBJECTS);
+        let after = request
+            .cursor
+            .as_deref()
+            .map(ListCursor::decode)
+            .transpose()
+            .map_err(ObjectUseCaseError::InvalidRequest)?;
+        if let Some(after) = &after {
+            if after.sort_by != sort_by || after.sort_direction != sort_direction {
+                return Err(ObjectUseCaseError::InvalidRequest(
+                    "Cursor was issued for a different sort order".to_string(),
+                ));
+            }
+            if !ListCursor::supports(sort_by) {
+                return Err(ObjectUseCaseError::InvalidRequest(
+                    "Listings in this order cannot be paged by cursor".to_string(),
+                ));
+            }
+            if page.offset != 0 {
+                return Err(ObjectUseCaseError::InvalidRequest(
+                    "cursor and offset cannot be combined".to_string(),
+                ));
+            }
+        }
+
+        Ok(Self {
+            namespace,
+            tenant_id,
+            keys,
+            metadata,
+            sort_by,
+            sort_direction,
+            after,
+            limit: page.limit,
+            offset: page.offset,
         })
     }
 }
@@ -47,9 +276,10 @@ impl ListObjectsUseCase {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::dto::ObjectProjection;
     use crate::application::ports::MockObjectRepository;
     use crate::domain::entities::Object;
-    use crate::domain::value_objects::{Namespace, StorageClass, TenantId};
+    use crate::domain::value_objects::StorageClass;
     use std::str::FromStr;
     use std::sync::Arc;
     use uuid::Uuid;
@@ -72,13 +302,20 @@ mod tests {
             tenant_id: Uuid::new_v4().to_string(),
             limit: Some(10),
             offset: Some(0),
+            sort_by: None,
+            sort_direction: None,
+            cursor: None,
+            prefix: None,
+            delimiter: None,
+            metadata_filters: None,
+            metadata_has_keys: None,
         };
 
         let objects = vec![create_test_object(), create_test_object()];
         mock_object_repo
             .expect_list()
             .times(1)
-            .returning(move |_, _, _, _| Ok(objects.clone()));
+            .returning(move |_, _, _, _, _, _, _, _, _| Ok(objects.clone()));
 
         let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
 
@@ -101,12 +338,19 @@ mod tests {
             tenant_id: Uuid::new_v4().to_string(),
             limit: Some(10),
             offset: Some(0),
+            sort_by: None,
+            sort_direction: None,
+            cursor: None,
+            prefix: None,
+            delimiter: None,
+            metadata_filters: None,
+            metadata_has_keys: None,
         };
 
         mock_object_repo
             .expect_list()
             .times(1)
-            .returning(|_, _, _, _| Ok(vec![]));
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![]));
 
         let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
 
@@ -119,4 +363,342 @@ mod tests {
         assert_eq!(response.objects.len(), 0);
         assert_eq!(response.total, 0);
     }
+
+    #[tokio::test]
+    async fn test_list_objects_with_delimiter_returns_common_prefixes() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_list()
+            .withf(|_, _, keys, _, _, _, _, _, _| {
+                keys.prefix() == "photos/" && keys.delimiter() == Some("/")
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![create_test_object()]));
+        mock_object_repo
+            .expect_common_prefixes()
+            .withf(|_, _, _, _, max| *max == MAX_COMMON_PREFIXES)
+            .times(1)
+            .returning(|_, _, _, _, _| {
+                Ok(vec!["photos/2023/".to_string(), "photos/2024/".to_string()])
+            });
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        let response = use_case
+            .execute(ListRequest {
+                namespace: "test".to_string(),
+                tenant_id: Uuid::new_v4().to_string(),
+                limit: Some(10),
+                offset: Some(0),
+                sort_by: None,
+                sort_direction: None,
+                cursor: None,
+                prefix: Some("photos/".to_string()),
+                delimiter: Some("/".to_string()),
+                metadata_filters: None,
+                metadata_has_keys: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.objects.len(), 1);
+        assert_eq!(
+            response.common_prefixes,
+            vec!["photos/2023/", "photos/2024/"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_rejects_empty_delimiter() {
+        let use_case = ListObjectsUseCase::new(Arc::new(MockObjectRepository::new()));
+
+        let result = use_case
+            .execute(ListRequest {
+                namespace: "test".to_string(),
+                tenant_id: Uuid::new_v4().to_string(),
+                limit: Some(10),
+                offset: Some(0),
+                sort_by: None,
+                sort_direction: None,
+                cursor: None,
+                prefix: None,
+                delimiter: Some(String::new()),
+                metadata_filters: None,
+                metadata_has_keys: None,
+            })
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_sorted_by_download_count() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let request = ListRequest {
+            namespace: "test".to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            limit: None,
+            offset: None,
+            sort_by: Some(SortField::DownloadCount),
+            sort_direction: None,
+            cursor: None,
+            prefix: None,
+            delimiter: None,
+            metadata_filters: None,
+            metadata_has_keys: None,
+        };
+
+        mock_object_repo
+            .expect_list()
+            .withf(|_, _, _, _, sort_by, sort_direction, _, _, _| {
+                *sort_by == SortField::DownloadCount && *sort_direction == SortDirection::Desc
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![]));
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        assert!(use_case.execute(request).await.is_ok());
+    }
+
+    fn page_request(limit: i64, offset: i64) -> ListRequest {
+        ListRequest {
+            namespace: "test".to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            limit: Some(limit),
+            offset: Some(offset),
+            sort_by: None,
+            sort_direction: None,
+            cursor: None,
+            prefix: None,
+            delimiter: None,
+            metadata_filters: None,
+            metadata_has_keys: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_reports_more_pages() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_list()
+            .withf(|_, _, _, _, _, _, _, limit, offset| *limit == 3 && *offset == 0)
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![create_test_object(); 3]));
+        mock_object_repo
+            .expect_count()
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(7));
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        let response = use_case.execute(page_request(2, 0)).await.unwrap();
+
+        assert_eq!(response.objects.len(), 2);
+        assert!(response.has_more);
+        assert_eq!(response.total, 7);
+        assert!(response.total_exact);
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_clamps_out_of_range_page() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        // One extra row is fetched to tell whether more pages follow
+        mock_object_repo
+            .expect_list()
+            .withf(|_, _, _, _, _, _, _, limit, offset| *limit == 2 && *offset == 0)
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![]));
+        mock_object_repo
+            .expect_list()
+            .withf(|_, _, _, _, _, _, _, limit, offset| *limit == 1001 && *offset == 0)
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![]));
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        let response = use_case.execute(page_request(0, -5)).await.unwrap();
+        assert_eq!((response.limit, response.offset), (1, 0));
+        let response = use_case.execute(page_request(5000, 0)).await.unwrap();
+        assert_eq!((response.limit, response.offset), (1000, 0));
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_total_is_lower_bound_past_count_limit() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_list()
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![create_test_object(); 3]));
+        mock_object_repo
+            .expect_count()
+            .withf(|_, _, _, _, max| *max == 5)
+            .times(1)
+            .returning(|_, _, _, _, max| Ok(max));
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo)).with_count_limit(5);
+
+        let response = use_case.execute(page_request(2, 10)).await.unwrap();
+
+        assert!(response.has_more);
+        assert!(!response.total_exact);
+        assert_eq!(response.total, 12);
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_next_page_follows_cursor() {
+        let objects = vec![create_test_object(); 3];
+        let last = objects[1].clone();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_list()
+            .withf(|_, _, _, _, _, _, after, _, _| after.is_none())
+            .times(1)
+            .returning(move |_, _, _, _, _, _, _, _, _| Ok(objects.clone()));
+        mock_object_repo
+            .expect_list()
+            .withf(move |_, _, _, _, _, _, after, _, offset| {
+                after.as_ref().is_some_and(|after| after.id == *last.id()) && *offset == 0
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(vec![create_test_object()]));
+        mock_object_repo
+            .expect_count()
+            .returning(|_, _, _, _, _| Ok(3));
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        let first = use_case.execute(page_request(2, 0)).await.unwrap();
+        let cursor = first.next_cursor.expect("a cursor for the next page");
+        let second = use_case
+            .execute(ListRequest {
+                cursor: Some(cursor),
+                ..page_request(2, 0)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(second.objects.len(), 1);
+        assert!(!second.has_more);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_rejects_cursor_for_other_sort() {
+        let cursor =
+            ListCursor::after(&create_test_object(), SortField::Key, SortDirection::Asc).encode();
+        let use_case = ListObjectsUseCase::new(Arc::new(MockObjectRepository::new()));
+
+        let result = use_case
+            .execute(ListRequest {
+                cursor: Some(cursor),
+                ..page_request(2, 0)
+            })
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_sort_parameter_parsing() {
+        assert_eq!(
+            SortField::parse_sort("size:asc"),
+            Ok((SortField::SizeBytes, SortDirection::Asc))
+        );
+        assert_eq!(
+            SortField::parse_sort("access_count"),
+            Ok((SortField::DownloadCount, SortDirection::Desc))
+        );
+        assert!(SortField::parse_sort("password").is_err());
+        assert!(SortField::parse_sort("key:sideways").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_projected_loads_only_requested_fields() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_list_projected()
+            .withf(|_, _, _, _, _, _, fields, limit, _| {
+                fields == [ObjectField::Key, ObjectField::SizeBytes] && *limit == 3
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _, fields, _, _| {
+                let dto = ObjectDto::from(create_test_object());
+                Ok(vec![ObjectProjection::new(&dto, fields); 2])
+            });
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        let response = use_case
+            .execute_projected(
+                page_request(2, 0),
+                &[ObjectField::Key, ObjectField::SizeBytes],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.objects.len(), 2);
+        assert!(!response.has_more);
+        assert_eq!(response.total, 2);
+        let json = serde_json::to_value(&response.objects[0]).unwrap();
+        assert_eq!(json, serde_json::json!({"key": "key", "size_bytes": null}));
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_projected_requires_fields() {
+        let use_case = ListObjectsUseCase::new(Arc::new(MockObjectRepository::new()));
+
+        let result = use_case.execute_projected(page_request(2, 0), &[]).await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_object_field_list_parsing() {
+        assert_eq!(
+            ObjectField::parse_list("id, key,size,content_type,key").unwrap(),
+            vec![
+                ObjectField::Id,
+                ObjectField::Key,
+                ObjectField::SizeBytes,
+                ObjectField::ContentType
+            ]
+        );
+        assert!(ObjectField::parse_list("id,password").is_err());
+        assert!(ObjectField::parse_list("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stream_objects_reads_whole_listing() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_stream()
+            .withf(|_, _, _, _, sort_by, _| *sort_by == SortField::CreatedAt)
+            .times(1)
+            .returning(|_, _, _, _, _, _| {
+                Box::pin(futures_util::stream::iter(
+                    vec![create_test_object(); 3].into_iter().map(Ok),
+                ))
+            });
+
+        let use_case = ListObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        // The page size does not cap a stream
+        let objects: Vec<_> = use_case.stream(page_request(2, 0)).unwrap().collect().await;
+
+        assert_eq!(objects.len(), 3);
+        assert!(objects.iter().all(Result::is_ok));
+    }
+
+    #[tokio::test]
+    async fn test_stream_objects_rejects_invalid_request_up_front() {
+        let use_case = ListObjectsUseCase::new(Arc::new(MockObjectRepository::new()));
+
+        let result = use_case.stream(ListRequest {
+            namespace: "bad namespace!".to_string(),
+            ..page_request(2, 0)
+        });
+
+        assert!(result.is_err());
+    }
 }
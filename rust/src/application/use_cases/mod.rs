@@ -1,18 +1,55 @@
 mod api_keys;
+mod bulk_update_metadata;
+mod bulk_upload;
+mod compaction;
+mod delete_namespace;
 mod delete_object;
+mod download_archive;
 mod download_object;
+mod list_blobs;
 mod list_objects;
+mod namespace_configs;
+mod object_retention;
+mod object_status;
+mod reconcile_refcounts;
+mod register_objects;
 mod search_objects;
+mod share_object;
+mod stats;
 mod text_search_objects;
+mod touch_object;
+mod update_object_metadata;
+mod upload_guard;
 mod upload_object;
 
 pub use api_keys::{
     ApiKeyUseCaseError, CreateApiKeyUseCase, DeleteApiKeyUseCase, GetApiKeyUseCase,
-    ListApiKeysUseCase, UpdateApiKeyUseCase,
+    ListApiKeysUseCase, RotateApiKeyUseCase, UpdateApiKeyUseCase,
+};
+pub use bulk_update_metadata::{
+    BulkUpdateMetadataUseCase, DEFAULT_BULK_METADATA_BATCH_SIZE,
+    DEFAULT_BULK_METADATA_MAX_BATCHES,
+};
+pub use bulk_upload::{BulkUploadUseCase, DEFAULT_MAX_ARCHIVE_ENTRIES};
+pub use compaction::CompactionUseCase;
+pub use delete_namespace::{
+    DeleteNamespaceUseCase, DEFAULT_NAMESPACE_DELETE_BATCH_SIZE,
+    DEFAULT_NAMESPACE_DELETE_MAX_BATCHES,
 };
 pub use delete_object::DeleteObjectUseCase;
+pub use download_archive::{DownloadArchiveUseCase, DEFAULT_MAX_ARCHIVE_DOWNLOAD_OBJECTS};
 pub use download_object::DownloadObjectUseCase;
-pub use list_objects::ListObjectsUseCase;
+pub use list_blobs::ListBlobsUseCase;
+pub use list_objects::{ListObjectsUseCase, DEFAULT_LIST_COUNT_LIMIT};
+pub use namespace_configs::NamespaceConfigUseCase;
+pub use object_retention::ObjectRetentionUseCase;
+pub use object_status::{ObjectStatusUseCase, MAX_STATUS_WAIT};
+pub use reconcile_refcounts::{ReconcileProgress, ReconcileRefcountsUseCase};
+pub use register_objects::{RegisterObjectsUseCase, MAX_REGISTER_LINE_BYTES};
 pub use search_objects::SearchObjectsUseCase;
+pub use share_object::ShareObjectUseCase;
+pub use stats::StatsUseCase;
 pub use text_search_objects::TextSearchObjectsUseCase;
+pub use touch_object::TouchObjectUseCase;
+pub use update_object_metadata::UpdateObjectMetadataUseCase;
 pub use upload_object::UploadObjectUseCase;
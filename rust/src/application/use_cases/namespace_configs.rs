@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use crate::application::dto::{
+    NamespaceConfigDto, NamespaceConfigListResponse, PutNamespaceConfigRequest,
+};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::NamespaceConfigRepository;
+use crate::domain::entities::{KeyPolicy, NamespaceConfig, TieringPolicy};
+use crate::domain::value_objects::Namespace;
+
+/// Use case: Manage per-namespace defaults (admin only)
+pub struct NamespaceConfigUseCase {
+    repository: Arc<dyn NamespaceConfigRepository>,
+}
+
+impl NamespaceConfigUseCase {
+    pub fn new(repository: Arc<dyn NamespaceConfigRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// All configured namespaces
+    pub async fn list(&self) -> Result<NamespaceConfigListResponse, ObjectUseCaseError> {
+        let configs = self.repository.list().await?;
+
+        Ok(NamespaceConfigListResponse {
+            namespaces: configs.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Configuration of one namespace
+    pub async fn get(&self, namespace: String) -> Result<NamespaceConfigDto, ObjectUseCaseError> {
+        let namespace = Namespace::new(namespace)?;
+
+        self.repository
+            .find(&namespace)
+            .await?
+            .map(Into::into)
+            .ok_or_else(|| {
+                ObjectUseCaseError::NotFound(format!("No configuration for namespace {namespace}"))
+            })
+    }
+
+    /// Create or replace the configuration of a namespace
+    pub async fn put(
+        &self,
+        namespace: String,
+        request: PutNamespaceConfigRequest,
+    ) -> Result<NamespaceConfigDto, ObjectUseCaseError> {
+        let namespace = Namespace::new(namespace)?;
+        let tiering = request
+            .cold_after_days
+            .map(TieringPolicy::new)
+            .transpose()?;
+        let key_policy = request
+            .key_policy
+            .map(|policy| {
+                KeyPolicy::new(
+                    policy.max_length,
+                    policy.allowed_chars,
+                    policy.required_prefix,
+                )
+            })
+            .transpose()?;
+
+        let config = NamespaceConfig::new(
+            namespace,
+            request.default_storage_class,
+            tiering,
+            key_policy,
+        );
+        let stored = self.repository.upsert(&config).await?;
+
+        Ok(stored.into())
+    }
+
+    /// Remove the configuration of a namespace; uploads fall back to the global default
+    pub async fn delete(&self, namespace: String) -> Result<(), ObjectUseCaseError> {
+        let namespace = Namespace::new(namespace)?;
+
+        if !self.repository.delete(&namespace).await? {
+            return Err(ObjectUseCaseError::NotFound(format!(
+                "No configuration for namespace {namespace}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dto::KeyPolicyDto;
+    use crate::application::ports::MockNamespaceConfigRepository;
+    use crate::domain::value_objects::StorageClass;
+
+    #[tokio::test]
+    async fn test_put_stores_default_class_and_tiering() {
+        // Arrange
+        let mut mock_repo = MockNamespaceConfigRepository::new();
+        mock_repo
+            .expect_upsert()
+            .withf(|config| {
+                config.namespace().as_str() == "logs"
+                    && config.default_storage_class() == StorageClass::Cold
+                    && config.tiering().map(|t| t.cold_after_days()) == Some(7)
+            })
+            .times(1)
+            .returning(|config| Ok(config.clone()));
+
+        let use_case = NamespaceConfigUseCase::new(Arc::new(mock_repo));
+
+        // Act
+        let result = use_case
+            .put(
+                "logs".to_string(),
+                PutNamespaceConfigRequest {
+                    default_storage_class: StorageClass::Cold,
+                    cold_after_days: Some(7),
+                    key_policy: Some(KeyPolicyDto {
+                        required_prefix: Some("app-".to_string()),
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(result.namespace, "logs");
+        assert_eq!(result.default_storage_class, StorageClass::Cold);
+        assert_eq!(result.cold_after_days, Some(7));
+        assert_eq!(
+            result.key_policy.and_then(|p| p.required_prefix),
+            Some("app-".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_rejects_invalid_input() {
+        // Arrange: the repository must not be reached
+        let use_case = NamespaceConfigUseCase::new(Arc::new(MockNamespaceConfigRepository::new()));
+        let request = PutNamespaceConfigRequest {
+            default_storage_class: StorageClass::Hot,
+            cold_after_days: None,
+            key_policy: None,
+        };
+
+        // Act
+        let bad_name = use_case.put("bad name!".to_string(), request.clone()).await;
+        let zero_days = use_case
+            .put(
+                "logs".to_string(),
+                PutNamespaceConfigRequest {
+                    cold_after_days: Some(0),
+                    ..request.clone()
+                },
+            )
+            .await;
+        let bad_charset = use_case
+            .put(
+                "logs".to_string(),
+                PutNamespaceConfigRequest {
+                    key_policy: Some(KeyPolicyDto {
+                        allowed_chars: Some("z-a".to_string()),
+                        ..Default::default()
+                    }),
+                    ..request
+                },
+            )
+            .await;
+
+        // Assert
+        assert!(matches!(bad_name, Err(ObjectUseCaseError::Domain(_))));
+        assert!(matches!(zero_days, Err(ObjectUseCaseError::Domain(_))));
+        assert!(matches!(bad_charset, Err(ObjectUseCaseError::Domain(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_and_delete_missing_namespace() {
+        // Arrange
+        let mut mock_repo = MockNamespaceConfigRepository::new();
+        mock_repo.expect_find().returning(|_| Ok(None));
+        mock_repo.expect_delete().returning(|_| Ok(false));
+
+        let use_case = NamespaceConfigUseCase::new(Arc::new(mock_repo));
+
+        // Act
+        let get = use_case.get("logs".to_string()).await;
+        let delete = use_case.delete("logs".to_string()).await;
+
+        // Assert
+        assert!(matches!(get, Err(ObjectUseCaseError::NotFound(_))));
+        assert!(matches!(delete, Err(ObjectUseCaseError::NotFound(_))));
+    }
+}
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::application::dto::{ObjectDto, ObjectRetentionRequest};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::ObjectRepository;
+use crate::domain::value_objects::{ObjectId, TenantId};
+
+/// Use case: Lock an object against overwrite and deletion (WORM)
+///
+/// Retention keeps the object until a date and can only be extended; a legal
+/// hold keeps it until the hold is released.
+pub struct ObjectRetentionUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+}
+
+impl ObjectRetentionUseCase {
+    pub fn new(object_repo: Arc<dyn ObjectRepository>) -> Self {
+        Self { object_repo }
+    }
+
+    /// Set or extend the retention of a committed object owned by `tenant_id`
+    /// and place or release its legal hold
+    #[tracing::instrument(
+        name = "ObjectRetentionUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id)
+    )]
+    pub async fn execute(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &TenantId,
+        request: &ObjectRetentionRequest,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        // 1. Parse the request
+        let retention_until = request
+            .retention_until
+            .as_deref()
+            .map(|at| {
+                OffsetDateTime::parse(at, &Rfc3339).map_err(|e| {
+                    ObjectUseCaseError::InvalidRequest(format!("Invalid retention_until: {e}"))
+                })
+            })
+            .transpose()?;
+        if retention_until.is_none() && request.legal_hold.is_none() {
+            return Err(ObjectUseCaseError::InvalidRequest(
+                "Set retention_until, legal_hold or both".to_string(),
+            ));
+        }
+
+        // 2. Load the object (other tenants' objects are not found)
+        let mut object = self
+            .object_repo
+            .find_by_id(object_id)
+            .await?
+            .filter(|object| object.tenant_id() == tenant_id)
+            .ok_or_else(|| ObjectUseCaseError::NotFound(object_id.to_string()))?;
+
+        // 3. Apply the lock; shortening the retention is refused
+        if let Some(until) = retention_until {
+            object.extend_retention(until)?;
+        }
+        if let Some(legal_hold) = request.legal_hold {
+            object.set_legal_hold(legal_hold)?;
+        }
+
+        // 4. Persist, unless the object was deleted meanwhile
+        if !self.object_repo.update_retention(&object).await? {
+            return Err(ObjectUseCaseError::NotFound(object_id.to_string()));
+        }
+
+        Ok(ObjectDto::from(object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockObjectRepository;
+    use crate::domain::entities::Object;
+    use crate::domain::errors::DomainError;
+    use crate::domain::value_objects::{ContentHash, Namespace, StorageClass};
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn committed_object(tenant_id: &TenantId) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("test").unwrap(),
+            tenant_id.clone(),
+            Some("ledger.csv".to_string()),
+            StorageClass::Hot,
+        );
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 42)
+            .unwrap();
+        object
+    }
+
+    fn retention(until: OffsetDateTime) -> ObjectRetentionRequest {
+        ObjectRetentionRequest {
+            retention_until: Some(until.format(&Rfc3339).unwrap()),
+            legal_hold: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sets_retention_and_legal_hold() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id);
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo
+            .expect_update_retention()
+            .withf(|object| object.is_locked() && object.legal_hold())
+            .times(1)
+            .returning(|_| Ok(true));
+        let use_case = ObjectRetentionUseCase::new(Arc::new(mock_object_repo));
+
+        let request = ObjectRetentionRequest {
+            legal_hold: Some(true),
+            ..retention(OffsetDateTime::now_utc() + time::Duration::days(7))
+        };
+        let object = use_case
+            .execute(&object_id, &tenant_id, &request)
+            .await
+            .unwrap();
+
+        assert!(object.legal_hold);
+        assert!(object.retention_until.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_refuses_to_shorten_retention() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let until = OffsetDateTime::now_utc() + time::Duration::days(30);
+        let mut object = committed_object(&tenant_id);
+        object.extend_retention(until).unwrap();
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo.expect_update_retention().never();
+        let use_case = ObjectRetentionUseCase::new(Arc::new(mock_object_repo));
+
+        let result = use_case
+            .execute(
+                &object_id,
+                &tenant_id,
+                &retention(until - time::Duration::days(1)),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(DomainError::RetentionShortened(
+                _
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_date() {
+        let use_case = ObjectRetentionUseCase::new(Arc::new(MockObjectRepository::new()));
+        let request = ObjectRetentionRequest {
+            retention_until: Some("next tuesday".to_string()),
+            legal_hold: None,
+        };
+
+        let result = use_case
+            .execute(&ObjectId::new(), &TenantId::new(Uuid::new_v4()), &request)
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_other_tenants_object_is_not_found() {
+        let object = committed_object(&TenantId::new(Uuid::new_v4()));
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        let use_case = ObjectRetentionUseCase::new(Arc::new(mock_object_repo));
+
+        let result = use_case
+            .execute(
+                &object_id,
+                &TenantId::new(Uuid::new_v4()),
+                &ObjectRetentionRequest {
+                    legal_hold: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+}
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::dto::{ObjectStatusResponse, UploadStatus};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::ObjectRepository;
+use crate::application::status_watch::StatusWatch;
+use crate::domain::value_objects::{ObjectId, ObjectStatus, TenantId};
+
+/// Longest a status request waits for an upload to finish
+pub const MAX_STATUS_WAIT: Duration = Duration::from_secs(60);
+
+/// Use case: Report an object's status, optionally waiting for its upload
+pub struct ObjectStatusUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+    status_watch: Arc<StatusWatch>,
+}
+
+impl ObjectStatusUseCase {
+    pub fn new(object_repo: Arc<dyn ObjectRepository>, status_watch: Arc<StatusWatch>) -> Self {
+        Self {
+            object_repo,
+            status_watch,
+        }
+    }
+
+    /// Status of an object owned by `tenant_id`, in any state
+    ///
+    /// While the object is `WRITING`, waits up to `wait` (capped at
+    /// [`MAX_STATUS_WAIT`]) for its upload to commit or fail and returns as
+    /// soon as it does. A zero `wait` reads the status once.
+    #[tracing::instrument(
+        name = "ObjectStatusUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id)
+    )]
+    pub async fn execute(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &TenantId,
+        wait: Duration,
+    ) -> Result<ObjectStatusResponse, ObjectUseCaseError> {
+        let wait = wait.min(MAX_STATUS_WAIT);
+
+        // 1. Subscribe before reading, so an upload finishing in between
+        // still wakes us
+        let mut subscription = (!wait.is_zero()).then(|| self.status_watch.subscribe(*object_id));
+
+        // 2. Read the stored status
+        let status = self.load(object_id, tenant_id).await?;
+        let subscription = match subscription.as_mut() {
+            Some(subscription) if status == ObjectStatus::Writing => subscription,
+            _ => return Ok(Self::response(object_id, status.into())),
+        };
+
+        // 3. Wait for the upload's outcome; without one (the wait ran out, or
+        // another instance handled the upload) report what is stored now
+        let status = match subscription.wait(wait).await {
+            Some(status) => status,
+            None => self.load(object_id, tenant_id).await?.into(),
+        };
+
+        Ok(Self::response(object_id, status))
+    }
+
+    /// Stored status of the object; other tenants' objects are not found
+    async fn load(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &TenantId,
+    ) -> Result<ObjectStatus, ObjectUseCaseError> {
+        self.object_repo
+            .status(object_id)
+            .await?
+            .filter(|(owner, _)| owner == tenant_id)
+            .map(|(_, status)| status)
+            .ok_or_else(|| ObjectUseCaseError::NotFound(object_id.to_string()))
+    }
+
+    fn response(object_id: &ObjectId, status: UploadStatus) -> ObjectStatusResponse {
+        ObjectStatusResponse {
+            id: object_id.to_string(),
+            status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockObjectRepository;
+    use uuid::Uuid;
+
+    fn repo_with_status(tenant_id: &TenantId, statuses: Vec<ObjectStatus>) -> MockObjectRepository {
+        let tenant_id = tenant_id.clone();
+        let mut statuses = statuses.into_iter();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_status()
+            .returning(move |_| Ok(statuses.next().map(|status| (tenant_id.clone(), status))));
+        mock_object_repo
+    }
+
+    #[tokio::test]
+    async fn test_without_wait_reads_status_once() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let mut mock_object_repo = MockObjectRepository::new();
+        let owner = tenant_id.clone();
+        mock_object_repo
+            .expect_status()
+            .times(1)
+            .returning(move |_| Ok(Some((owner.clone(), ObjectStatus::Writing))));
+        let use_case =
+            ObjectStatusUseCase::new(Arc::new(mock_object_repo), Arc::new(StatusWatch::new()));
+
+        let response = use_case
+            .execute(&ObjectId::new(), &tenant_id, Duration::ZERO)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, UploadStatus::Writing);
+    }
+
+    #[tokio::test]
+    async fn test_wait_returns_when_upload_commits() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object_id = ObjectId::new();
+        let status_watch = Arc::new(StatusWatch::new());
+        let use_case = ObjectStatusUseCase::new(
+            Arc::new(repo_with_status(&tenant_id, vec![ObjectStatus::Writing])),
+            Arc::clone(&status_watch),
+        );
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            status_watch.publish(&object_id, UploadStatus::Committed);
+        });
+
+        let response = use_case
+            .execute(&object_id, &tenant_id, Duration::from_secs(30))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, UploadStatus::Committed);
+    }
+
+    #[tokio::test]
+    async fn test_wait_rereads_status_when_it_runs_out() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let use_case = ObjectStatusUseCase::new(
+            Arc::new(repo_with_status(
+                &tenant_id,
+                vec![ObjectStatus::Writing, ObjectStatus::Committed],
+            )),
+            Arc::new(StatusWatch::new()),
+        );
+
+        let response = use_case
+            .execute(&ObjectId::new(), &tenant_id, Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, UploadStatus::Committed);
+    }
+
+    #[tokio::test]
+    async fn test_other_tenants_object_is_not_found() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let use_case = ObjectStatusUseCase::new(
+            Arc::new(repo_with_status(&owner, vec![ObjectStatus::Committed])),
+            Arc::new(StatusWatch::new()),
+        );
+
+        let result = use_case
+            .execute(
+                &ObjectId::new(),
+                &TenantId::new(Uuid::new_v4()),
+                Duration::from_secs(5),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+}
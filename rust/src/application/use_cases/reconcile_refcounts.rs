@@ -0,0 +1,518 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::application::dto::{ReconcileRefcountsReport, RefcountDiscrepancy};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::{RefcountEntry, RefcountRepository};
+
+/// Checkpoint key for runs that apply fixes
+pub const RECONCILE_REFCOUNTS_TASK: &str = "reconcile_refcounts";
+/// Checkpoint key for dry runs, kept apart so a report never skips blobs a fix run needs
+pub const RECONCILE_REFCOUNTS_DRY_RUN_TASK: &str = "reconcile_refcounts:dry_run";
+
+/// Default number of blobs scanned per batch
+pub const DEFAULT_RECONCILE_BATCH_SIZE: i64 = 1000;
+/// Default number of concurrent fixes within a batch
+pub const DEFAULT_RECONCILE_PARALLELISM: usize = 4;
+
+/// Default maximum discrepancies included in a report
+pub const DEFAULT_REPORT_SAMPLE_LIMIT: usize = 100;
+
+/// Live counters for the current (or last) reconciliation run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReconcileProgress {
+    pub scanned: u64,
+    pub discrepancies: u64,
+    pub fixed: u64,
+}
+
+/// Use case: Reconcile blob reference counts with committed objects
+///
+/// Blobs are scanned in content-hash order in fixed-size batches. After each
+/// batch the last hash is checkpointed, so an interrupted run resumes where it
+/// stopped instead of rescanning millions of blobs. Fixes are applied with
+/// bounded parallelism and only when the stored count has not changed since
+/// the scan.
+pub struct ReconcileRefcountsUseCase {
+    refcount_repo: Arc<dyn RefcountRepository>,
+    batch_size: i64,
+    parallelism: usize,
+    max_batches: Option<u64>,
+    sample_limit: usize,
+    running: tokio::sync::Mutex<()>,
+    scanned: AtomicU64,
+    discrepancies: AtomicU64,
+    fixed: AtomicU64,
+}
+
+impl ReconcileRefcountsUseCase {
+    pub fn new(refcount_repo: Arc<dyn RefcountRepository>) -> Self {
+        Self {
+            refcount_repo,
+            batch_size: DEFAULT_RECONCILE_BATCH_SIZE,
+            parallelism: DEFAULT_RECONCILE_PARALLELISM,
+            max_batches: None,
+            sample_limit: DEFAULT_REPORT_SAMPLE_LIMIT,
+            running: tokio::sync::Mutex::new(()),
+            scanned: AtomicU64::new(0),
+            discrepancies: AtomicU64::new(0),
+            fixed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Stop each run after `max_batches` batches, leaving a checkpoint
+    pub fn with_max_batches(mut self, max_batches: u64) -> Self {
+        self.max_batches = Some(max_batches);
+        self
+    }
+
+    /// Include up to `sample_limit` discrepancies in each report
+    pub fn with_sample_limit(mut self, sample_limit: usize) -> Self {
+        self.sample_limit = sample_limit;
+        self
+    }
+
+    /// Counters for the current (or last) run
+    pub fn progress(&self) -> ReconcileProgress {
+        ReconcileProgress {
+            scanned: self.scanned.load(Ordering::Relaxed),
+            discrepancies: self.discrepancies.load(Ordering::Relaxed),
+            fixed: self.fixed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Execute a reconciliation run, resuming from the last checkpoint
+    pub async fn execute(
+        &self,
+        dry_run: bool,
+    ) -> Result<ReconcileRefcountsReport, ObjectUseCaseError> {
+        let _guard = self.running.try_lock().map_err(|_| {
+            ObjectUseCaseError::InvalidRequest(
+                "Refcount reconciliation is already running".to_string(),
+            )
+        })?;
+
+        self.scanned.store(0, Ordering::Relaxed);
+        self.discrepancies.store(0, Ordering::Relaxed);
+        self.fixed.store(0, Ordering::Relaxed);
+
+        let task = if dry_run {
+            RECONCILE_REFCOUNTS_DRY_RUN_TASK
+        } else {
+            RECONCILE_REFCOUNTS_TASK
+        };
+
+        let mut report = ReconcileRefcountsReport {
+            dry_run,
+            ..Default::default()
+        };
+        let mut cursor = self.refcount_repo.load_checkpoint(task).await?;
+
+        loop {
+            if self.max_batches.is_some_and(|max| report.batches >= max) {
+                break;
+            }
+
+            let batch = self
+                .refcount_repo
+                .scan_batch(cursor.clone(), self.batch_size)
+                .await?;
+            let Some(last) = batch.last() else {
+                report.completed = true;
+                break;
+            };
+            let next_cursor = last.content_hash.clone();
+            let is_last_batch = (batch.len() as i64) < self.batch_size;
+
+            self.process_batch(batch, dry_run, &mut report).await?;
+            report.batches += 1;
+
+            if is_last_batch {
+                report.completed = true;
+                break;
+            }
+
+            cursor = Some(next_cursor);
+            self.refcount_repo
+                .save_checkpoint(task, cursor.clone())
+                .await?;
+        }
+
+        if report.completed {
+            self.refcount_repo.save_checkpoint(task, None).await?;
+        }
+
+        tracing::info!(
+            dry_run,
+            completed = report.completed,
+            batches = report.batches,
+            scanned = report.scanned,
+            discrepancies = report.discrepancies,
+            fixed = report.fixed,
+            "Refcount reconciliation finished"
+        );
+
+        Ok(report)
+    }
+
+    async fn process_batch(
+        &self,
+        batch: Vec<RefcountEntry>,
+        dry_run: bool,
+        report: &mut ReconcileRefcountsReport,
+    ) -> Result<(), ObjectUseCaseError> {
+        let scanned = batch.len() as u64;
+        let discrepancies: Vec<RefcountEntry> =
+            batch.into_iter().filter(|e| !e.is_consistent()).collect();
+
+        report.scanned += scanned;
+        report.discrepancies += discrepancies.len() as u64;
+        self.scanned.fetch_add(scanned, Ordering::Relaxed);
+        self.discrepancies
+            .fetch_add(discrepancies.len() as u64, Ordering::Relaxed);
+
+        for entry in &discrepancies {
+            tracing::warn!(
+                content_hash = %entry.content_hash,
+                stored = entry.stored_ref_count,
+                actual = entry.actual_ref_count,
+                dry_run,
+                "Blob reference count mismatch"
+            );
+        }
+
+        if dry_run {
+            for entry in &discrepancies {
+                Self::record_sample(report, self.sample_limit, entry, false);
+            }
+            return Ok(());
+        }
+
+        let results: Vec<_> = stream::iter(discrepancies)
+            .map(|entry| async move {
+                let result = self
+                    .refcount_repo
+                    .set_ref_count(
+                        &entry.content_hash,
+                        entry.stored_ref_count,
+                        entry.actual_ref_count,
+                    )
+                    .await;
+                (entry, result)
+            })
+            .buffer_unordered(self.parallelism)
+            .collect()
+            .await;
+
+        for (entry, result) in results {
+            let fixed = result?;
+            if fixed {
+                tracing::info!(
+                    content_hash = %entry.content_hash,
+                    from = entry.stored_ref_count,
+                    to = entry.actual_ref_count,
+                    "Blob reference count corrected"
+                );
+                report.fixed += 1;
+                self.fixed.fetch_add(1, Ordering::Relaxed);
+            }
+            Self::record_sample(report, self.sample_limit, &entry, fixed);
+        }
+
+        Ok(())
+    }
+
+    fn record_sample(
+        report: &mut ReconcileRefcountsReport,
+        sample_limit: usize,
+        entry: &RefcountEntry,
+        fixed: bool,
+    ) {
+        if report.sample.len() < sample_limit {
+            report.sample.push(RefcountDiscrepancy {
+                content_hash: entry.content_hash.to_string(),
+                stored_ref_count: entry.stored_ref_count,
+                actual_ref_count: entry.actual_ref_count,
+                fixed,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::RepositoryError;
+    use crate::domain::value_objects::ContentHash;
+    use async_trait::async_trait;
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::Mutex;
+
+    /// In-memory refcount store with real checkpoint semantics
+    #[derive(Default)]
+    struct InMemoryRefcountRepository {
+        // content hash -> (stored, actual)
+        blobs: Mutex<BTreeMap<String, (i64, i64)>>,
+        checkpoints: Mutex<HashMap<String, ContentHash>>,
+        scans: Mutex<Vec<Option<ContentHash>>>,
+    }
+
+    impl InMemoryRefcountRepository {
+        fn with_blobs(blobs: &[(char, i64, i64)]) -> Self {
+            let repo = Self::default();
+            {
+                let mut map = repo.blobs.lock().unwrap();
+                for (c, stored, actual) in blobs {
+                    map.insert(c.to_string().repeat(64), (*stored, *actual));
+                }
+            }
+            repo
+        }
+
+        fn stored(&self, c: char) -> i64 {
+            self.blobs.lock().unwrap()[&c.to_string().repeat(64)].0
+        }
+    }
+
+    #[async_trait]
+    impl RefcountRepository for InMemoryRefcountRepository {
+        async fn scan_batch(
+            &self,
+            after: Option<ContentHash>,
+            limit: i64,
+        ) -> Result<Vec<RefcountEntry>, RepositoryError> {
+            self.scans.lock().unwrap().push(after.clone());
+            let blobs = self.blobs.lock().unwrap();
+            Ok(blobs
+                .iter()
+                .filter(|(hash, _)| after.as_ref().is_none_or(|a| hash.as_str() > a.as_hex()))
+                .take(limit as usize)
+                .map(|(hash, (stored, actual))| RefcountEntry {
+                    content_hash: ContentHash::from_hex(hash.clone()).unwrap(),
+                    stored_ref_count: *stored,
+                    actual_ref_count: *actual,
+                })
+                .collect())
+        }
+
+        async fn set_ref_count(
+            &self,
+            content_hash: &ContentHash,
+            expected: i64,
+            ref_count: i64,
+        ) -> Result<bool, RepositoryError> {
+            let mut blobs = self.blobs.lock().unwrap();
+            match blobs.get_mut(content_hash.as_hex()) {
+                Some(entry) if entry.0 == expected => {
+                    entry.0 = ref_count;
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn load_checkpoint(
+            &self,
+            task: &str,
+        ) -> Result<Option<ContentHash>, RepositoryError> {
+            Ok(self.checkpoints.lock().unwrap().get(task).cloned())
+        }
+
+        async fn save_checkpoint(
+            &self,
+            task: &str,
+            cursor: Option<ContentHash>,
+        ) -> Result<(), RepositoryError> {
+            let mut checkpoints = self.checkpoints.lock().unwrap();
+            match cursor {
+                Some(cursor) => checkpoints.insert(task.to_string(), cursor),
+                None => checkpoints.remove(task),
+            };
+            Ok(())
+        }
+    }
+
+    fn blobs() -> Vec<(char, i64, i64)> {
+        vec![
+            ('a', 1, 1),
+            ('b', 3, 1), // over-counted
+            ('c', 2, 2),
+            ('d', 0, 2), // under-counted
+            ('e', 1, 1),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_fixes_discrepancies_in_batches() {
+        // Arrange
+        let repo = Arc::new(InMemoryRefcountRepository::with_blobs(&blobs()));
+        let use_case = ReconcileRefcountsUseCase::new(repo.clone())
+            .with_batch_size(2)
+            .with_parallelism(2);
+
+        // Act
+        let report = use_case.execute(false).await.unwrap();
+
+        // Assert
+        assert!(report.completed);
+        assert_eq!(report.batches, 3);
+        assert_eq!(report.scanned, 5);
+        assert_eq!(report.discrepancies, 2);
+        assert_eq!(report.fixed, 2);
+        assert_eq!(repo.stored('b'), 1);
+        assert_eq!(repo.stored('d'), 2);
+        assert_eq!(report.sample.len(), 2);
+        assert!(report.sample.iter().all(|d| d.fixed));
+        assert_eq!(
+            use_case.progress(),
+            ReconcileProgress {
+                scanned: 5,
+                discrepancies: 2,
+                fixed: 2
+            }
+        );
+        assert!(repo
+            .load_checkpoint(RECONCILE_REFCOUNTS_TASK)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_dry_run_reports_without_fixing() {
+        // Arrange
+        let repo = Arc::new(InMemoryRefcountRepository::with_blobs(&blobs()));
+        let use_case = ReconcileRefcountsUseCase::new(repo.clone()).with_batch_size(10);
+
+        // Act
+        let report = use_case.execute(true).await.unwrap();
+
+        // Assert
+        assert!(report.dry_run);
+        assert_eq!(report.discrepancies, 2);
+        assert_eq!(report.fixed, 0);
+        assert_eq!(report.sample.len(), 2);
+        assert_eq!(report.sample[0].stored_ref_count, 3);
+        assert_eq!(report.sample[0].actual_ref_count, 1);
+        assert!(report.sample.iter().all(|d| !d.fixed));
+        assert_eq!(repo.stored('b'), 3);
+        assert_eq!(repo.stored('d'), 0);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_resumes_from_checkpoint() {
+        // Arrange: each run stops after one batch of two blobs
+        let repo = Arc::new(InMemoryRefcountRepository::with_blobs(&blobs()));
+        let use_case = ReconcileRefcountsUseCase::new(repo.clone())
+            .with_batch_size(2)
+            .with_max_batches(1);
+
+        // Act: first run covers a, b
+        let first = use_case.execute(false).await.unwrap();
+
+        // Assert
+        assert!(!first.completed);
+        assert_eq!(first.scanned, 2);
+        assert_eq!(first.fixed, 1);
+        assert_eq!(
+            repo.load_checkpoint(RECONCILE_REFCOUNTS_TASK)
+                .await
+                .unwrap()
+                .unwrap()
+                .as_hex(),
+            "b".repeat(64)
+        );
+
+        // Act: second run resumes after b and covers c, d
+        let second = use_case.execute(false).await.unwrap();
+
+        // Assert
+        assert!(!second.completed);
+        assert_eq!(second.scanned, 2);
+        assert_eq!(second.fixed, 1);
+        assert_eq!(repo.stored('d'), 2);
+        let scans = repo.scans.lock().unwrap().clone();
+        assert_eq!(scans[0], None);
+        assert_eq!(scans[1].as_ref().unwrap().as_hex(), "b".repeat(64));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_does_not_move_fix_checkpoint() {
+        // Arrange
+        let repo = Arc::new(InMemoryRefcountRepository::with_blobs(&blobs()));
+        let use_case = ReconcileRefcountsUseCase::new(repo.clone())
+            .with_batch_size(2)
+            .with_max_batches(1);
+
+        // Act
+        use_case.execute(true).await.unwrap();
+
+        // Assert
+        assert!(repo
+            .load_checkpoint(RECONCILE_REFCOUNTS_DRY_RUN_TASK)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repo
+            .load_checkpoint(RECONCILE_REFCOUNTS_TASK)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_skips_concurrently_changed_counts() {
+        // Arrange: the stored count moves between scan and fix
+        let mut mock_repo = MockRefcountRepository::new();
+        mock_repo.expect_load_checkpoint().returning(|_| Ok(None));
+        mock_repo.expect_scan_batch().returning(|_, _| {
+            Ok(vec![RefcountEntry {
+                content_hash: ContentHash::from_hex("f".repeat(64)).unwrap(),
+                stored_ref_count: 5,
+                actual_ref_count: 1,
+            }])
+        });
+        mock_repo
+            .expect_set_ref_count()
+            .times(1)
+            .returning(|_, _, _| Ok(false));
+        mock_repo.expect_save_checkpoint().returning(|_, _| Ok(()));
+
+        let use_case = ReconcileRefcountsUseCase::new(Arc::new(mock_repo));
+
+        // Act
+        let report = use_case.execute(false).await.unwrap();
+
+        // Assert
+        assert_eq!(report.discrepancies, 1);
+        assert_eq!(report.fixed, 0);
+        assert_eq!(report.sample.len(), 1);
+        assert!(!report.sample[0].fixed);
+    }
+
+    #[tokio::test]
+    async fn test_sample_limit_caps_report() {
+        // Arrange
+        let repo = Arc::new(InMemoryRefcountRepository::with_blobs(&blobs()));
+        let use_case = ReconcileRefcountsUseCase::new(repo.clone()).with_sample_limit(1);
+
+        // Act
+        let report = use_case.execute(false).await.unwrap();
+
+        // Assert: both are fixed, only one is listed
+        assert_eq!(report.fixed, 2);
+        assert_eq!(report.sample.len(), 1);
+    }
+}
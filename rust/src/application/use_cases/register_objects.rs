@@ -0,0 +1,521 @@
+use std::sync::Arc;
+
+use futures_util::{stream, Stream};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+use validator::Validate;
+
+use super::upload_object::{reservation_error, UploadObjectUseCase};
+use crate::application::dto::{
+    ObjectDto, RegisterObjectLine, RegisterObjectResult, RegisterObjectStatus,
+};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::{BlobRepository, BlobStore, ObjectRepository};
+use crate::domain::entities::Object;
+use crate::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
+
+/// Longest line accepted in a bulk register body, in bytes
+pub const MAX_REGISTER_LINE_BYTES: usize = 64 * 1024;
+
+/// Use case: Register objects over blobs already in the blob store
+///
+/// For migrations that copy blobs into the store out of band: each NDJSON
+/// line describes one object, which is created committed and takes a
+/// reference on its blob by content hash. Lines are read and registered one
+/// at a time, so an import of any length streams through in bounded memory,
+/// and a bad line fails on its own without aborting the import. Objects are
+/// admitted like uploads of the same content, by the upload use case's
+/// namespace key policies and content policy.
+pub struct RegisterObjectsUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+    blob_repo: Arc<dyn BlobRepository>,
+    blob_store: Arc<dyn BlobStore>,
+    upload: Arc<UploadObjectUseCase>,
+}
+
+impl RegisterObjectsUseCase {
+    pub fn new(
+        object_repo: Arc<dyn ObjectRepository>,
+        blob_repo: Arc<dyn BlobRepository>,
+        blob_store: Arc<dyn BlobStore>,
+        upload: Arc<UploadObjectUseCase>,
+    ) -> Self {
+        Self {
+            object_repo,
+            blob_repo,
+            blob_store,
+            upload,
+        }
+    }
+
+    /// Register the objects described by the NDJSON `lines` for `tenant_id`,
+    /// yielding one result per non-blank line as it is processed
+    ///
+    /// A line that is not valid JSON, names an invalid namespace, key or
+    /// hash, or is refused by the namespace's key policy or the content
+    /// policy is `rejected`; one whose blob is missing from the store, whose
+    /// key is taken or whose size or storage class disagrees with the blob
+    /// already recorded under its hash is `failed`, and leaves nothing
+    /// behind. A line longer than [`MAX_REGISTER_LINE_BYTES`] or a body that
+    /// cannot be read ends the stream with a final `failed` result.
+    pub fn execute<R>(
+        &self,
+        tenant_id: &str,
+        lines: R,
+    ) -> Result<impl Stream<Item = RegisterObjectResult> + Send + 'static, ObjectUseCaseError>
+    where
+        R: AsyncBufRead + Unpin + Send + 'static,
+    {
+        let tenant_id = TenantId::from_string(tenant_id)
+            .map_err(|e| ObjectUseCaseError::InvalidRequest(e.to_string()))?;
+        let registrar = Registrar {
+            object_repo: Arc::clone(&self.object_repo),
+            blob_repo: Arc::clone(&self.blob_repo),
+            blob_store: Arc::clone(&self.blob_store),
+            upload: Arc::clone(&self.upload),
+            tenant_id,
+        };
+        let lines = NdjsonLines {
+            reader: lines,
+            number: 0,
+            done: false,
+        };
+
+        Ok(stream::unfold(
+            (registrar, lines),
+            |(registrar, mut lines)| async move {
+                let result = loop {
+                    match lines.next().await? {
+                        (_, Ok(line)) if line.iter().all(u8::is_ascii_whitespace) => continue,
+                        (number, Ok(line)) => break registrar.register(number, &line).await,
+                        (number, Err(e)) => {
+                            break RegisterObjectResult::unsuccessful(
+                                number,
+                                RegisterObjectStatus::Failed,
+                                None,
+                                e,
+                            )
+                        }
+                    }
+                };
+                Some((result, (registrar, lines)))
+            },
+        ))
+    }
+}
+
+/// Lines of an NDJSON body, numbered from 1
+struct NdjsonLines<R> {
+    reader: R,
+    number: u64,
+    done: bool,
+}
+
+impl<R: AsyncBufRead + Unpin> NdjsonLines<R> {
+    /// Next line without its line ending; `None` at the end of the body or
+    /// after an overlong or unreadable line
+    async fn next(&mut self) -> Option<(u64, Result<Vec<u8>, String>)> {
+        if self.done {
+            return None;
+        }
+
+        let mut line = Vec::new();
+        let read = (&mut self.reader)
+            .take(MAX_REGISTER_LINE_BYTES as u64 + 1)
+            .read_until(b'\n', &mut line)
+            .await;
+        self.number += 1;
+
+        match read {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) if line.last() == Some(&b'\n') => {
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                Some((self.number, Ok(line)))
+            }
+            Ok(_) if line.len() > MAX_REGISTER_LINE_BYTES => {
+                self.done = true;
+                Some((
+                    self.number,
+                    Err(format!(
+                        "Line longer than {MAX_REGISTER_LINE_BYTES} bytes; import stopped"
+                    )),
+                ))
+            }
+            // Last line, without a trailing newline
+            Ok(_) => Some((self.number, Ok(line))),
+            Err(e) => {
+                self.done = true;
+                Some((
+                    self.number,
+                    Err(format!("Failed to read request body: {e}; import stopped")),
+                ))
+            }
+        }
+    }
+}
+
+/// A validated register line
+struct Registration {
+    namespace: Namespace,
+    key: String,
+    content_hash: ContentHash,
+    size_bytes: u64,
+    content_type: Option<String>,
+    storage_class: StorageClass,
+}
+
+/// Registers lines for one tenant; owns its ports so the result stream can
+/// outlive the request handler
+struct Registrar {
+    object_repo: Arc<dyn ObjectRepository>,
+    blob_repo: Arc<dyn BlobRepository>,
+    blob_store: Arc<dyn BlobStore>,
+    upload: Arc<UploadObjectUseCase>,
+    tenant_id: TenantId,
+}
+
+impl Registrar {
+    async fn register(&self, number: u64, line: &[u8]) -> RegisterObjectResult {
+        let registration = match parse(line) {
+            Ok(registration) => registration,
+            Err(e) => {
+                return RegisterObjectResult::unsuccessful(
+                    number,
+                    RegisterObjectStatus::Rejected,
+                    None,
+                    e.to_string(),
+                )
+            }
+        };
+
+        let key = registration.key.clone();
+        match self.create(registration).await {
+            Ok(object) => RegisterObjectResult::created(number, &object),
+            Err(e) => {
+                // Policy refusals, as opposed to failures of the registration
+                let status = match e {
+                    ObjectUseCaseError::InvalidRequest(_) | ObjectUseCaseError::Domain(_) => {
+                        RegisterObjectStatus::Rejected
+                    }
+                    _ => RegisterObjectStatus::Failed,
+                };
+                RegisterObjectResult::unsuccessful(number, status, Some(key), e.to_string())
+            }
+        }
+    }
+
+    async fn create(&self, registration: Registration) -> Result<ObjectDto, ObjectUseCaseError> {
+        let Registration {
+            namespace,
+            key,
+            content_hash,
+            size_bytes,
+            content_type,
+            storage_class,
+        } = registration;
+
+        if !self.blob_store.exists(&content_hash, storage_class).await? {
+            return Err(ObjectUseCaseError::NotFound(format!(
+                "blob {content_hash} in {storage_class} storage"
+            )));
+        }
+
+        // Reserve the key first, so a taken key leaves the blob's
+        // reference count alone
+        let mut object = Object::new(namespace, self.tenant_id.clone(), Some(key), storage_class);
+        if let Some(content_type) = content_type {
+            object.set_content_type(content_type);
+        }
+        self.upload
+            .check_stored_content(&object, &content_hash)
+            .await?;
+        self.object_repo
+            .save(&object)
+            .await
+            .map_err(|e| reservation_error(&object, e))?;
+
+        let blob = match self
+            .blob_repo
+            .get_or_create(&content_hash, storage_class, size_bytes)
+            .await
+        {
+            Ok(blob) => blob,
+            Err(e) => {
+                self.roll_back(&object, None).await;
+                return Err(e.into());
+            }
+        };
+        if blob.size_bytes() != size_bytes || blob.storage_class() != storage_class {
+            self.roll_back(&object, Some(&content_hash)).await;
+            return Err(ObjectUseCaseError::Conflict(format!(
+                "blob {content_hash} is recorded as {} bytes in {} storage",
+                blob.size_bytes(),
+                blob.storage_class()
+            )));
+        }
+
+        object.commit(&content_hash, size_bytes)?;
+        if let Err(e) = self.object_repo.save(&object).await {
+            self.roll_back(&object, Some(&content_hash)).await;
+            return Err(e.into());
+        }
+
+        Ok(ObjectDto::from(object))
+    }
+
+    /// Undo a failed registration: drop the blob reference it took, if any,
+    /// and its reserved object
+    async fn roll_back(&self, object: &Object, content_hash: Option<&ContentHash>) {
+        if let Some(content_hash) = content_hash {
+            if let Err(e) = self.blob_repo.decrement_ref(content_hash).await {
+                tracing::warn!(%content_hash, "Failed to release blob reference: {}", e);
+            }
+        }
+        if let Err(e) = self.object_repo.delete(object.id()).await {
+            tracing::warn!(object_id = %object.id(), "Failed to remove reserved object: {}", e);
+        }
+    }
+}
+
+/// Parse and validate a register line
+fn parse(line: &[u8]) -> Result<Registration, ObjectUseCaseError> {
+    let invalid = ObjectUseCaseError::InvalidRequest;
+
+    let line: RegisterObjectLine =
+        serde_json::from_slice(line).map_err(|e| invalid(format!("Invalid JSON: {e}")))?;
+    line.validate().map_err(|e| invalid(e.to_string()))?;
+    let namespace = Namespace::new(line.namespace).map_err(|e| invalid(e.to_string()))?;
+    let content_hash =
+        ContentHash::from_hex(line.content_hash).map_err(|e| invalid(e.to_string()))?;
+
+    Ok(Registration {
+        namespace,
+        key: line.key,
+        content_hash,
+        size_bytes: line.size_bytes,
+        content_type: line.content_type,
+        storage_class: line.storage_class.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::content_policy::ContentPolicy;
+    use crate::application::ports::{
+        MockBlobRepository, MockBlobStore, MockNamespaceConfigRepository, MockObjectRepository,
+    };
+    use crate::domain::entities::{Blob, KeyPolicy, NamespaceConfig};
+    use futures_util::StreamExt;
+    use std::io::Cursor;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn hash() -> ContentHash {
+        ContentHash::from_str(&"a".repeat(64)).unwrap()
+    }
+
+    fn line(key: &str, size: u64) -> String {
+        format!(
+            r#"{{"namespace":"photos","key":"{key}","content_hash":"{}","size":{size},"content_type":"image/png"}}"#,
+            hash()
+        )
+    }
+
+    fn blob_store(exists: bool) -> MockBlobStore {
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_exists()
+            .returning(move |_, _| Ok(exists));
+        mock_blob_store
+    }
+
+    async fn register(
+        object_repo: MockObjectRepository,
+        blob_repo: MockBlobRepository,
+        blob_store: MockBlobStore,
+        body: String,
+    ) -> Vec<RegisterObjectResult> {
+        register_with_upload(object_repo, blob_repo, blob_store, body, |upload| upload).await
+    }
+
+    /// Register with lines admitted by the upload use case `configure`
+    /// returns
+    async fn register_with_upload(
+        object_repo: MockObjectRepository,
+        blob_repo: MockBlobRepository,
+        blob_store: MockBlobStore,
+        body: String,
+        configure: impl FnOnce(UploadObjectUseCase) -> UploadObjectUseCase,
+    ) -> Vec<RegisterObjectResult> {
+        let object_repo: Arc<dyn ObjectRepository> = Arc::new(object_repo);
+        let blob_repo: Arc<dyn BlobRepository> = Arc::new(blob_repo);
+        let blob_store: Arc<dyn BlobStore> = Arc::new(blob_store);
+        let upload = configure(UploadObjectUseCase::new(
+            Arc::clone(&object_repo),
+            Arc::clone(&blob_repo),
+            Arc::clone(&blob_store),
+        ));
+        let use_case =
+            RegisterObjectsUseCase::new(object_repo, blob_repo, blob_store, Arc::new(upload));
+        use_case
+            .execute(&Uuid::new_v4().to_string(), Cursor::new(body.into_bytes()))
+            .unwrap()
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_register_creates_objects_and_rejects_bad_lines() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_save()
+            .times(2)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_get_or_create()
+            .times(1)
+            .returning(|hash, class, size| Ok(Blob::new(hash.clone(), class, size)));
+
+        let body = format!("{}\n\n{{\"namespace\":\"photos\"}}\n", line("a.png", 42));
+        let results = register(mock_object_repo, mock_blob_repo, blob_store(true), body).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].line, 1);
+        assert_eq!(results[0].status, RegisterObjectStatus::Created);
+        assert_eq!(results[0].key.as_deref(), Some("a.png"));
+        assert!(results[0].object_id.is_some());
+        assert_eq!(results[1].line, 3);
+        assert_eq!(results[1].status, RegisterObjectStatus::Rejected);
+    }
+
+    #[tokio::test]
+    async fn test_register_fails_line_without_blob() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_get_or_create().never();
+
+        let results = register(
+            mock_object_repo,
+            mock_blob_repo,
+            blob_store(false),
+            line("a.png", 42),
+        )
+        .await;
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, RegisterObjectStatus::Failed);
+        assert!(results[0].error.as_ref().unwrap().contains("blob"));
+    }
+
+    #[tokio::test]
+    async fn test_register_rolls_back_size_mismatch() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_object_repo
+            .expect_delete()
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_get_or_create()
+            .returning(|hash, class, _| Ok(Blob::new(hash.clone(), class, 7)));
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(1));
+
+        let results = register(
+            mock_object_repo,
+            mock_blob_repo,
+            blob_store(true),
+            line("a.png", 42),
+        )
+        .await;
+
+        assert_eq!(results[0].status, RegisterObjectStatus::Failed);
+        assert_eq!(results[0].key.as_deref(), Some("a.png"));
+    }
+
+    #[tokio::test]
+    async fn test_register_stops_at_overlong_line() {
+        let body = format!(
+            "{}\n{}\n",
+            "x".repeat(MAX_REGISTER_LINE_BYTES + 1),
+            line("a.png", 42)
+        );
+        let results = register(
+            MockObjectRepository::new(),
+            MockBlobRepository::new(),
+            MockBlobStore::new(),
+            body,
+        )
+        .await;
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, RegisterObjectStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_key_outside_namespace_policy() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_get_or_create().never();
+        let mut mock_namespace_configs = MockNamespaceConfigRepository::new();
+        mock_namespace_configs.expect_find().returning(|namespace| {
+            let policy = KeyPolicy::new(None, None, Some("albums/".to_string())).unwrap();
+            Ok(Some(NamespaceConfig::new(
+                namespace.clone(),
+                StorageClass::Hot,
+                None,
+                Some(policy),
+            )))
+        });
+
+        let results = register_with_upload(
+            mock_object_repo,
+            mock_blob_repo,
+            blob_store(true),
+            line("a.png", 42),
+            |upload| upload.with_namespace_configs(Arc::new(mock_namespace_configs)),
+        )
+        .await;
+
+        assert_eq!(results[0].status, RegisterObjectStatus::Rejected);
+        assert_eq!(results[0].key.as_deref(), Some("a.png"));
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_blocked_content() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_get_or_create().never();
+        let mut mock_blob_store = blob_store(true);
+        mock_blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"\x89PNG\r\n\x1a\n".to_vec()))));
+
+        let results = register_with_upload(
+            mock_object_repo,
+            mock_blob_repo,
+            mock_blob_store,
+            line("a.png", 42),
+            |upload| upload.with_content_policy(ContentPolicy::parse("", "photos=.png").unwrap()),
+        )
+        .await;
+
+        assert_eq!(results[0].status, RegisterObjectStatus::Rejected);
+    }
+}
@@ -2,6 +2,9 @@ use std::sync::Arc;
 
 use crate::application::dto::{ObjectDto, SearchRequest, SearchResponse};
 use crate::application::errors::ObjectUseCaseError;
+use crate::application::key_prefix_query::KeyPrefixQuery;
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::pagination::{PageLimits, Pagination};
 use crate::application::ports::ObjectRepository;
 use crate::application::validation::validate_namespace_and_tenant;
 
@@ -24,23 +27,34 @@ impl SearchObjectsUseCase {
         let (_namespace, _tenant_id) =
             validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
 
-        // Note: We don't validate the search request here as it's optional filters
+        // Note: We don't validate the search request here as it's optional filters,
+        // except key prefix and metadata filters, which must be safe to send
+        // to the database
+        let keys = KeyPrefixQuery::new(request.key_prefix.clone(), None)
+            .map_err(ObjectUseCaseError::InvalidRequest)?;
+        let metadata = MetadataQuery::new(
+            request.metadata_filters.clone(),
+            request.metadata_has_keys.as_deref().unwrap_or_default(),
+        )
+        .map_err(ObjectUseCaseError::InvalidRequest)?;
 
         // 2. Query repository with search filters
-        let objects = self.object_repo.search(&request).await?;
+        let objects = self
+            .object_repo
+            .search(&request, &keys, &metadata, None)
+            .await?;
 
         // 3. Convert to DTOs
         let dtos: Vec<ObjectDto> = objects.into_iter().map(ObjectDto::from).collect();
 
         let total = dtos.len();
-        let limit = request.limit.unwrap_or(100).min(1000);
-        let offset = request.offset.unwrap_or(0);
+        let page = Pagination::new(request.limit, request.offset, PageLimits::OBJECTS);
 
         Ok(SearchResponse {
             objects: dtos,
             total,
-            limit,
-            offset,
+            limit: page.limit,
+            offset: page.offset,
         })
     }
 }
@@ -76,19 +90,21 @@ mod tests {
             sort_by: None,
             sort_direction: None,
             key_contains: None,
+            key_prefix: None,
             content_type: None,
             storage_class: None,
             size_range: None,
             created_at_range: None,
             updated_at_range: None,
             metadata_filters: None,
+            metadata_has_keys: None,
         };
 
         let objects = vec![create_test_object(), create_test_object()];
         mock_object_repo
             .expect_search()
             .times(1)
-            .returning(move |_| Ok(objects.clone()));
+            .returning(move |_, _, _, _| Ok(objects.clone()));
 
         let use_case = SearchObjectsUseCase::new(Arc::new(mock_object_repo));
 
@@ -101,4 +117,66 @@ mod tests {
         assert_eq!(response.objects.len(), 2);
         assert_eq!(response.total, 2);
     }
+
+    #[tokio::test]
+    async fn test_search_objects_clamps_out_of_range_page() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_search()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+        let request = SearchRequest {
+            namespace: "test".to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            limit: Some(5000),
+            offset: Some(-1),
+            sort_by: None,
+            sort_direction: None,
+            key_contains: None,
+            key_prefix: None,
+            content_type: None,
+            storage_class: None,
+            size_range: None,
+            created_at_range: None,
+            updated_at_range: None,
+            metadata_filters: None,
+            metadata_has_keys: None,
+        };
+
+        let use_case = SearchObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        let response = use_case.execute(request).await.unwrap();
+
+        assert_eq!(response.limit, 1000);
+        assert_eq!(response.offset, 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_objects_rejects_invalid_metadata_path() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_search().never();
+        let request = SearchRequest {
+            namespace: "test".to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            limit: None,
+            offset: None,
+            sort_by: None,
+            sort_direction: None,
+            key_contains: None,
+            key_prefix: None,
+            content_type: None,
+            storage_class: None,
+            size_range: None,
+            created_at_range: None,
+            updated_at_range: None,
+            metadata_filters: None,
+            metadata_has_keys: Some(vec!["tags.author') OR true --".to_string()]),
+        };
+
+        let use_case = SearchObjectsUseCase::new(Arc::new(mock_object_repo));
+
+        let result = use_case.execute(request).await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
 }
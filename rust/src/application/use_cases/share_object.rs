@@ -0,0 +1,902 @@
+use std::sync::Arc;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::dto::{ObjectShareDto, ShareObjectRequest};
+use crate::application::errors::{DeleteUseCaseError, ObjectUseCaseError};
+use crate::application::ports::{
+    BlobRepository, ObjectRepository, ObjectShare, ObjectShareRepository,
+};
+use crate::application::use_cases::{DeleteObjectUseCase, UploadObjectUseCase};
+use crate::application::validation::validate_namespace_and_tenant;
+use crate::domain::entities::Object;
+use crate::domain::value_objects::{ContentHash, ObjectId, TenantId};
+
+/// Use case: Share an object with another tenant, and revoke the share
+///
+/// The owner offers a grant naming the grantee's namespace and key; nothing
+/// reaches the grantee until it accepts the grant with its own credentials.
+/// Accepting gives the grantee an object of its own referencing the shared
+/// object's blob. The blob's reference count goes up with it, so GC keeps
+/// the blob until the owner's object and every grantee's object are gone.
+/// The grantee reads its object under its own tenant like any other; the
+/// owner's object stays out of its reach.
+///
+/// Revoking deletes the grantee's object, if the grant was accepted, and
+/// drops the grant. A grantee
+/// object whose content was since replaced belongs to the grantee alone and
+/// is left in place.
+pub struct ShareObjectUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+    blob_repo: Arc<dyn BlobRepository>,
+    share_repo: Arc<dyn ObjectShareRepository>,
+    upload: Arc<UploadObjectUseCase>,
+    delete: Arc<DeleteObjectUseCase>,
+}
+
+impl ShareObjectUseCase {
+    pub fn new(
+        object_repo: Arc<dyn ObjectRepository>,
+        blob_repo: Arc<dyn BlobRepository>,
+        share_repo: Arc<dyn ObjectShareRepository>,
+        upload: Arc<UploadObjectUseCase>,
+        delete: Arc<DeleteObjectUseCase>,
+    ) -> Self {
+        Self {
+            object_repo,
+            blob_repo,
+            share_repo,
+            upload,
+            delete,
+        }
+    }
+
+    /// Offer a committed object owned by `owner_tenant_id` to the tenant
+    /// named in `request`
+    ///
+    /// Nothing is created for the grantee until it accepts the grant.
+    #[tracing::instrument(
+        name = "ShareObjectUseCase::share",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id)
+    )]
+    pub async fn share(
+        &self,
+        object_id: &ObjectId,
+        owner_tenant_id: &TenantId,
+        request: &ShareObjectRequest,
+    ) -> Result<ObjectShareDto, ObjectUseCaseError> {
+        // 1. Validate the grantee
+        let (namespace, grantee_tenant_id) =
+            validate_namespace_and_tenant(&request.namespace, &request.grantee_tenant_id)?;
+        if grantee_tenant_id == *owner_tenant_id {
+            return Err(ObjectUseCaseError::InvalidRequest(
+                "An object cannot be shared with its own tenant".to_string(),
+            ));
+        }
+        if request.key.as_deref().is_some_and(str::is_empty) {
+            return Err(ObjectUseCaseError::InvalidRequest(
+                "key cannot be empty".to_string(),
+            ));
+        }
+
+        // 2. Load the object (other tenants' objects are not found)
+        let object = self
+            .object_repo
+            .find_by_id(object_id)
+            .await?
+            .filter(|object| object.tenant_id() == owner_tenant_id)
+            .ok_or_else(|| ObjectUseCaseError::NotFound(object_id.to_string()))?;
+        let Some(content_hash) = object.content_hash().cloned() else {
+            return Err(ObjectUseCaseError::NotFound(object_id.to_string()));
+        };
+
+        // 3. Record the pending grant
+        let share = ObjectShare {
+            id: Uuid::new_v4(),
+            object_id: *object_id,
+            owner_tenant_id: owner_tenant_id.clone(),
+            grantee_tenant_id,
+            grantee_object_id: None,
+            grantee_namespace: namespace,
+            grantee_key: request.key.clone(),
+            content_hash,
+            created_at: OffsetDateTime::now_utc(),
+            accepted_at: None,
+        };
+        self.share_repo.create(&share).await?;
+
+        tracing::info!(
+            %object_id,
+            share_id = %share.id,
+            owner_tenant_id = %share.owner_tenant_id,
+            grantee_tenant_id = %share.grantee_tenant_id,
+            "Object share offered"
+        );
+
+        Ok(ObjectShareDto::from(share))
+    }
+
+    /// Accept a grant offered to `grantee_tenant_id`, creating its object
+    ///
+    /// The object is checked against the grantee namespace's key policy and
+    /// the content policy like an upload of the content. Fails if the
+    /// owner's object no longer holds the offered content.
+    #[tracing::instrument(
+        name = "ShareObjectUseCase::accept",
+        level = "debug",
+        skip_all,
+        fields(share_id = %share_id)
+    )]
+    pub async fn accept(
+        &self,
+        share_id: &Uuid,
+        grantee_tenant_id: &TenantId,
+    ) -> Result<ObjectShareDto, ObjectUseCaseError> {
+        // 1. Load the grant (grants offered to other tenants are not found)
+        let mut share = self
+            .share_repo
+            .find(share_id)
+            .await?
+            .filter(|share| share.grantee_tenant_id == *grantee_tenant_id)
+            .ok_or_else(|| ObjectUseCaseError::NotFound(format!("share {share_id}")))?;
+        if share.accepted_at.is_some() {
+            return Err(ObjectUseCaseError::Conflict(
+                "The share was already accepted".to_string(),
+            ));
+        }
+
+        // 2. Load the shared object, still holding the offered content
+        let object = self
+            .object_repo
+            .find_by_id(&share.object_id)
+            .await?
+            .filter(|object| {
+                object.tenant_id() == &share.owner_tenant_id
+                    && object.content_hash() == Some(&share.content_hash)
+            })
+            .ok_or_else(|| ObjectUseCaseError::NotFound(share.object_id.to_string()))?;
+        let Some(size_bytes) = object.size_bytes() else {
+            return Err(ObjectUseCaseError::NotFound(share.object_id.to_string()));
+        };
+
+        // 3. The grantee's object: same content, type and metadata, admitted
+        //    by the grantee namespace's policies
+        let mut shared = Object::new(
+            share.grantee_namespace.clone(),
+            grantee_tenant_id.clone(),
+            share.grantee_key.clone(),
+            object.storage_class(),
+        );
+        if let Some(content_type) = object.content_type() {
+            shared.set_content_type(content_type.to_string());
+        }
+        shared.set_content_encoding(object.content_encoding());
+        shared.set_metadata(object.metadata().clone());
+        self.upload
+            .check_stored_content(&shared, &share.content_hash)
+            .await?;
+        shared.commit(&share.content_hash, size_bytes)?;
+
+        // 4. Take the blob reference first, so the blob is never unreferenced
+        //    while the grantee's object exists. Only a live blob the owner
+        //    still stores qualifies: an orphan may be mid-collection.
+        self.blob_repo
+            .reference_existing(
+                &share.content_hash,
+                object.storage_class(),
+                &share.owner_tenant_id,
+            )
+            .await?
+            .ok_or_else(|| ObjectUseCaseError::NotFound(share.object_id.to_string()))?;
+        if let Err(e) = self.object_repo.save(&shared).await {
+            self.release(&share.content_hash).await;
+            if e.is_unique_violation() {
+                return Err(ObjectUseCaseError::Conflict(
+                    "An object already exists for this key in the grantee's namespace".to_string(),
+                ));
+            }
+            return Err(e.into());
+        }
+
+        // 5. Mark the grant accepted; a lost race removes the object again
+        let accepted_at = OffsetDateTime::now_utc();
+        let accepted = self
+            .share_repo
+            .accept(share_id, shared.id(), accepted_at)
+            .await;
+        if !matches!(accepted, Ok(true)) {
+            if let Err(undo) = self.delete.execute(shared.id()).await {
+                tracing::error!(
+                    grantee_object_id = %shared.id(),
+                    error = %undo,
+                    "Failed to remove shared object after its grant could not be accepted"
+                );
+            }
+            return Err(match accepted {
+                Err(e) => e.into(),
+                _ => ObjectUseCaseError::Conflict(
+                    "The share was already accepted or revoked".to_string(),
+                ),
+            });
+        }
+        share.grantee_object_id = Some(*shared.id());
+        share.accepted_at = Some(accepted_at);
+
+        tracing::info!(
+            object_id = %share.object_id,
+            %share_id,
+            grantee_tenant_id = %share.grantee_tenant_id,
+            grantee_object_id = %shared.id(),
+            "Object share accepted"
+        );
+
+        Ok(ObjectShareDto::from(share))
+    }
+
+    /// Grants offered to `grantee_tenant_id`, pending and accepted, oldest first
+    pub async fn list_incoming(
+        &self,
+        grantee_tenant_id: &TenantId,
+    ) -> Result<Vec<ObjectShareDto>, ObjectUseCaseError> {
+        let shares = self.share_repo.list_for_grantee(grantee_tenant_id).await?;
+        Ok(shares.into_iter().map(ObjectShareDto::from).collect())
+    }
+
+    /// Grants of an object owned by `owner_tenant_id`, oldest first
+    pub async fn list(
+        &self,
+        object_id: &ObjectId,
+        owner_tenant_id: &TenantId,
+    ) -> Result<Vec<ObjectShareDto>, ObjectUseCaseError> {
+        // Grants outlive the shared object, so they are matched on their
+        // owner rather than on the object
+        let shares = self.share_repo.list_for_object(object_id).await?;
+        Ok(shares
+            .into_iter()
+            .filter(|share| share.owner_tenant_id == *owner_tenant_id)
+            .map(ObjectShareDto::from)
+            .collect())
+    }
+
+    /// Revoke a grant of an object owned by `owner_tenant_id`, deleting the
+    /// grantee's object
+    #[tracing::instrument(
+        name = "ShareObjectUseCase::revoke",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id, share_id = %share_id)
+    )]
+    pub async fn revoke(
+        &self,
+        object_id: &ObjectId,
+        share_id: &Uuid,
+        owner_tenant_id: &TenantId,
+    ) -> Result<(), ObjectUseCaseError> {
+        // 1. Load the grant (other tenants' grants are not found)
+        let share = self
+            .share_repo
+            .find(share_id)
+            .await?
+            .filter(|share| {
+                share.object_id == *object_id && share.owner_tenant_id == *owner_tenant_id
+            })
+            .ok_or_else(|| ObjectUseCaseError::NotFound(format!("share {share_id}")))?;
+
+        // 2. Delete the grantee's object, if the grant was accepted, while it
+        //    still holds the shared content
+        let shared = match &share.grantee_object_id {
+            Some(grantee_object_id) => self
+                .object_repo
+                .find_by_id(grantee_object_id)
+                .await?
+                .filter(|object| {
+                    object.tenant_id() == &share.grantee_tenant_id
+                        && object.content_hash() == Some(&share.content_hash)
+                }),
+            None => None,
+        };
+        if let Some(shared) = shared {
+            match self.delete.execute(shared.id()).await {
+                // Deleted by the grantee meanwhile
+                Ok(()) | Err(DeleteUseCaseError::NotFound(_)) => {}
+                Err(DeleteUseCaseError::Domain(e)) => return Err(e.into()),
+                Err(DeleteUseCaseError::Conflict(msg)) => {
+                    return Err(ObjectUseCaseError::Conflict(msg))
+                }
+                Err(DeleteUseCaseError::Repository(e)) => return Err(e.into()),
+                Err(DeleteUseCaseError::Storage(e)) => return Err(e.into()),
+            }
+        }
+
+        // 3. Drop the grant
+        self.share_repo.delete(share_id).await?;
+
+        tracing::info!(
+            %object_id,
+            %share_id,
+            grantee_tenant_id = %share.grantee_tenant_id,
+            "Object share revoked"
+        );
+
+        Ok(())
+    }
+
+    /// Give back a blob reference taken for an object that was not created
+    async fn release(&self, content_hash: &ContentHash) {
+        if let Err(e) = self.blob_repo.decrement_ref(content_hash).await {
+            tracing::error!(
+                %content_hash,
+                error = %e,
+                "Failed to release blob reference of an unshared object; reconcile refcounts to repair"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{
+        MockBlobRepository, MockBlobStore, MockNamespaceConfigRepository, MockObjectRepository,
+        MockObjectShareRepository, RepositoryError,
+    };
+    use crate::domain::entities::{Blob, KeyPolicy, NamespaceConfig};
+    use crate::domain::errors::DomainError;
+    use crate::domain::value_objects::{Namespace, ObjectStatus, StorageClass};
+    use std::str::FromStr;
+
+    fn hash(c: char) -> ContentHash {
+        ContentHash::from_str(&c.to_string().repeat(64)).unwrap()
+    }
+
+    fn committed_object(
+        tenant_id: &TenantId,
+        namespace: &str,
+        content_hash: &ContentHash,
+    ) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str(namespace).unwrap(),
+            tenant_id.clone(),
+            Some("census-2020.parquet".to_string()),
+            StorageClass::Hot,
+        );
+        object.set_content_type("application/vnd.apache.parquet".to_string());
+        object.commit(content_hash, 42).unwrap();
+        object
+    }
+
+    fn request(grantee_tenant_id: &TenantId) -> ShareObjectRequest {
+        ShareObjectRequest {
+            grantee_tenant_id: grantee_tenant_id.to_string(),
+            namespace: "datasets".to_string(),
+            key: Some("public/census-2020.parquet".to_string()),
+        }
+    }
+
+    fn grant(source: &Object, shared: &Object) -> ObjectShare {
+        ObjectShare {
+            id: Uuid::new_v4(),
+            object_id: *source.id(),
+            owner_tenant_id: source.tenant_id().clone(),
+            grantee_tenant_id: shared.tenant_id().clone(),
+            grantee_object_id: Some(*shared.id()),
+            grantee_namespace: shared.namespace().clone(),
+            grantee_key: shared.key().map(str::to_string),
+            content_hash: hash('a'),
+            created_at: OffsetDateTime::now_utc(),
+            accepted_at: Some(OffsetDateTime::now_utc()),
+        }
+    }
+
+    fn pending_grant(source: &Object, grantee_tenant_id: &TenantId) -> ObjectShare {
+        ObjectShare {
+            id: Uuid::new_v4(),
+            object_id: *source.id(),
+            owner_tenant_id: source.tenant_id().clone(),
+            grantee_tenant_id: grantee_tenant_id.clone(),
+            grantee_object_id: None,
+            grantee_namespace: Namespace::from_str("datasets").unwrap(),
+            grantee_key: Some("public/census-2020.parquet".to_string()),
+            content_hash: hash('a'),
+            created_at: OffsetDateTime::now_utc(),
+            accepted_at: None,
+        }
+    }
+
+    fn use_case(
+        mock_object_repo: MockObjectRepository,
+        mock_blob_repo: MockBlobRepository,
+        mock_blob_store: MockBlobStore,
+        mock_share_repo: MockObjectShareRepository,
+    ) -> ShareObjectUseCase {
+        use_case_with_upload(
+            mock_object_repo,
+            mock_blob_repo,
+            mock_blob_store,
+            mock_share_repo,
+            |upload| upload,
+        )
+    }
+
+    /// A use case whose grantee objects are admitted by the upload use case
+    /// `configure` returns
+    fn use_case_with_upload(
+        mock_object_repo: MockObjectRepository,
+        mock_blob_repo: MockBlobRepository,
+        mock_blob_store: MockBlobStore,
+        mock_share_repo: MockObjectShareRepository,
+        configure: impl FnOnce(UploadObjectUseCase) -> UploadObjectUseCase,
+    ) -> ShareObjectUseCase {
+        let object_repo: Arc<dyn ObjectRepository> = Arc::new(mock_object_repo);
+        let blob_repo: Arc<dyn BlobRepository> = Arc::new(mock_blob_repo);
+        let upload = configure(UploadObjectUseCase::new(
+            Arc::clone(&object_repo),
+            Arc::clone(&blob_repo),
+            Arc::new(MockBlobStore::new()),
+        ));
+        ShareObjectUseCase::new(
+            Arc::clone(&object_repo),
+            Arc::clone(&blob_repo),
+            Arc::new(mock_share_repo),
+            Arc::new(upload),
+            Arc::new(DeleteObjectUseCase::new(
+                object_repo,
+                blob_repo,
+                Arc::new(mock_blob_store),
+            )),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_share_offers_grant_without_creating_object() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let object_id = *source.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_reference_existing().never();
+        mock_blob_repo.expect_increment_ref().never();
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_create()
+            .withf(move |share| {
+                share.object_id == object_id
+                    && share.grantee_object_id.is_none()
+                    && share.accepted_at.is_none()
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let share = use_case(
+            mock_object_repo,
+            mock_blob_repo,
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .share(&object_id, &owner, &request(&grantee))
+        .await
+        .unwrap();
+
+        assert_eq!(share.object_id, object_id.to_string());
+        assert_eq!(share.owner_tenant_id, owner.to_string());
+        assert_eq!(share.grantee_tenant_id, grantee.to_string());
+        assert_eq!(share.grantee_namespace, "datasets");
+        assert_eq!(share.grantee_object_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_accept_references_blob_from_grantee_namespace() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let share = pending_grant(&source, &grantee);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo
+            .expect_accept()
+            .withf(move |id, _, _| *id == share_id)
+            .times(1)
+            .returning(|_, _, _| Ok(true));
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+        let expected_grantee = grantee.clone();
+        mock_object_repo
+            .expect_save()
+            .withf(move |object| {
+                object.tenant_id() == &expected_grantee
+                    && object.namespace().as_str() == "datasets"
+                    && object.status() == ObjectStatus::Committed
+                    && object.content_hash() == Some(&hash('a'))
+                    && object.content_type() == Some("application/vnd.apache.parquet")
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        // The reference is only taken while the owner still stores the blob
+        let expected_owner = owner.clone();
+        mock_blob_repo
+            .expect_reference_existing()
+            .withf(move |content_hash, _, tenant_id| {
+                content_hash == &hash('a') && tenant_id == &expected_owner
+            })
+            .times(1)
+            .returning(|content_hash, _, _| {
+                Ok(Some(Blob::new(content_hash.clone(), StorageClass::Hot, 42)))
+            });
+        mock_blob_repo.expect_increment_ref().never();
+
+        let share = use_case(
+            mock_object_repo,
+            mock_blob_repo,
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .accept(&share_id, &grantee)
+        .await
+        .unwrap();
+
+        assert!(share.grantee_object_id.is_some());
+        assert!(share.accepted_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_accept_fails_once_blob_is_unreferenced() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let share = pending_grant(&source, &grantee);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo.expect_accept().never();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        // The owner deleted the object and GC is collecting the blob
+        mock_blob_repo
+            .expect_reference_existing()
+            .times(1)
+            .returning(|_, _, _| Ok(None));
+
+        let result = use_case(
+            mock_object_repo,
+            mock_blob_repo,
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .accept(&share_id, &grantee)
+        .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_accept_checks_grantee_key_policy() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let share = pending_grant(&source, &grantee);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_reference_existing().never();
+        let mut mock_namespace_configs = MockNamespaceConfigRepository::new();
+        mock_namespace_configs.expect_find().returning(|namespace| {
+            let policy = KeyPolicy::new(None, None, Some("reports/".to_string())).unwrap();
+            Ok(Some(NamespaceConfig::new(
+                namespace.clone(),
+                StorageClass::Hot,
+                None,
+                Some(policy),
+            )))
+        });
+
+        let result = use_case_with_upload(
+            mock_object_repo,
+            mock_blob_repo,
+            MockBlobStore::new(),
+            mock_share_repo,
+            |upload| upload.with_namespace_configs(Arc::new(mock_namespace_configs)),
+        )
+        .accept(&share_id, &grantee)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(DomainError::ValidationError { ref field, .. }))
+                if field == "key"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_only_grantee_accepts() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let share = pending_grant(&source, &grantee);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo.expect_accept().never();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_find_by_id().never();
+
+        let result = use_case(
+            mock_object_repo,
+            MockBlobRepository::new(),
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .accept(&share_id, &owner)
+        .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_other_tenants_object_cannot_be_shared() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let caller = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let object_id = *source.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo.expect_create().never();
+
+        let result = use_case(
+            mock_object_repo,
+            MockBlobRepository::new(),
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .share(&object_id, &caller, &request(&grantee))
+        .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_share_with_own_tenant_is_rejected() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_find_by_id().never();
+
+        let result = use_case(
+            mock_object_repo,
+            MockBlobRepository::new(),
+            MockBlobStore::new(),
+            MockObjectShareRepository::new(),
+        )
+        .share(&ObjectId::new(), &owner, &request(&owner))
+        .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_taken_key_releases_blob_reference() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let share = pending_grant(&source, &grantee);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo.expect_accept().never();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+        mock_object_repo.expect_save().times(1).returning(|_| {
+            Err(RepositoryError::ConstraintViolation(
+                "unique_key_per_tenant_ns".to_string(),
+            ))
+        });
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_reference_existing()
+            .times(1)
+            .returning(|content_hash, _, _| {
+                Ok(Some(Blob::new(content_hash.clone(), StorageClass::Hot, 42)))
+            });
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(1));
+
+        let result = use_case(
+            mock_object_repo,
+            mock_blob_repo,
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .accept(&share_id, &grantee)
+        .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_deletes_grantee_object_but_keeps_blob_still_referenced() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let shared = committed_object(&grantee, "datasets", &hash('a'));
+        let share = grant(&source, &shared);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo
+            .expect_delete()
+            .withf(move |id| *id == share_id)
+            .times(1)
+            .returning(|_| Ok(true));
+        let shared_id = *shared.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .withf(move |id| *id == shared_id)
+            .returning(move |_| Ok(Some(shared.clone())));
+        mock_object_repo
+            .expect_mark_deleting_if_unlocked()
+            .times(1)
+            .returning(|_| Ok(true));
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        // The owner's object still references the blob
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(1));
+        mock_blob_repo.expect_delete().never();
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store.expect_delete().never();
+
+        use_case(
+            mock_object_repo,
+            mock_blob_repo,
+            mock_blob_store,
+            mock_share_repo,
+        )
+        .revoke(source.id(), &share_id, &owner)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_revoke_leaves_grantee_object_with_replaced_content() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let replaced = committed_object(&grantee, "datasets", &hash('b'));
+        let share = grant(&source, &replaced);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo
+            .expect_delete()
+            .times(1)
+            .returning(|_| Ok(true));
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(replaced.clone())));
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo.expect_decrement_ref().never();
+
+        use_case(
+            mock_object_repo,
+            mock_blob_repo,
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .revoke(source.id(), &share_id, &owner)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_revoke_pending_grant_only_drops_it() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let share = pending_grant(&source, &grantee);
+        let share_id = share.id;
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo
+            .expect_delete()
+            .times(1)
+            .returning(|_| Ok(true));
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_find_by_id().never();
+
+        use_case(
+            mock_object_repo,
+            MockBlobRepository::new(),
+            MockBlobStore::new(),
+            mock_share_repo,
+        )
+        .revoke(source.id(), &share_id, &owner)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_grants_of_other_owners_are_not_found() {
+        let owner = TenantId::new(Uuid::new_v4());
+        let grantee = TenantId::new(Uuid::new_v4());
+        let source = committed_object(&owner, "public", &hash('a'));
+        let shared = committed_object(&grantee, "datasets", &hash('a'));
+        let share = grant(&source, &shared);
+        let share_id = share.id;
+        let listed = share.clone();
+        let mut mock_share_repo = MockObjectShareRepository::new();
+        mock_share_repo
+            .expect_find()
+            .returning(move |_| Ok(Some(share.clone())));
+        mock_share_repo
+            .expect_list_for_object()
+            .returning(move |_| Ok(vec![listed.clone()]));
+        mock_share_repo.expect_delete().never();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_find_by_id().never();
+        let use_case = use_case(
+            mock_object_repo,
+            MockBlobRepository::new(),
+            MockBlobStore::new(),
+            mock_share_repo,
+        );
+
+        // The grantee cannot revoke or see the owner's grants
+        let revoked = use_case.revoke(source.id(), &share_id, &grantee).await;
+        let listed = use_case.list(source.id(), &grantee).await.unwrap();
+
+        assert!(matches!(revoked, Err(ObjectUseCaseError::NotFound(_))));
+        assert!(listed.is_empty());
+        assert_eq!(use_case.list(source.id(), &owner).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::application::dto::StatsResponse;
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::StatsRepository;
+
+/// Default time-to-live for cached statistics
+pub const DEFAULT_STATS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Use case: Compute deduplication statistics
+///
+/// The aggregation scans all committed objects, so results are cached
+/// for a short TTL to keep repeated dashboard polling cheap.
+pub struct StatsUseCase {
+    stats_repo: Arc<dyn StatsRepository>,
+    cache: moka::future::Cache<(), StatsResponse>,
+}
+
+impl StatsUseCase {
+    pub fn new(stats_repo: Arc<dyn StatsRepository>) -> Self {
+        Self::with_cache_ttl(stats_repo, DEFAULT_STATS_CACHE_TTL)
+    }
+
+    pub fn with_cache_ttl(stats_repo: Arc<dyn StatsRepository>, ttl: Duration) -> Self {
+        Self {
+            stats_repo,
+            cache: moka::future::Cache::builder()
+                .max_capacity(1)
+                .time_to_live(ttl)
+                .build(),
+        }
+    }
+
+    /// Execute, serving cached statistics when still fresh
+    pub async fn execute(&self) -> Result<StatsResponse, ObjectUseCaseError> {
+        if let Some(cached) = self.cache.get(&()).await {
+            return Ok(cached);
+        }
+
+        let response = self.compute().await?;
+        self.cache.insert((), response.clone()).await;
+
+        Ok(response)
+    }
+
+    async fn compute(&self) -> Result<StatsResponse, ObjectUseCaseError> {
+        let global = self.stats_repo.global_usage().await?;
+        let tenants = self.stats_repo.usage_by_tenant().await?;
+
+        Ok(StatsResponse {
+            global,
+            tenants,
+            generated_at: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dto::{DedupStats, TenantDedupStats};
+    use crate::application::ports::{MockStatsRepository, RepositoryError};
+
+    #[tokio::test]
+    async fn test_stats_happy_path() {
+        // Arrange
+        let mut mock_stats_repo = MockStatsRepository::new();
+        mock_stats_repo
+            .expect_global_usage()
+            .times(1)
+            .returning(|| Ok(DedupStats::new(4, 400, 2, 200)));
+        mock_stats_repo
+            .expect_usage_by_tenant()
+            .times(1)
+            .returning(|| {
+                Ok(vec![TenantDedupStats {
+                    tenant_id: "tenant-a".to_string(),
+                    stats: DedupStats::new(4, 400, 2, 200),
+                }])
+            });
+
+        let use_case = StatsUseCase::new(Arc::new(mock_stats_repo));
+
+        // Act
+        let result = use_case.execute().await.unwrap();
+
+        // Assert
+        assert_eq!(result.global.bytes_saved, 200);
+        assert_eq!(result.global.dedup_ratio, 2.0);
+        assert_eq!(result.tenants.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stats_are_cached() {
+        // Arrange: repository must only be hit once
+        let mut mock_stats_repo = MockStatsRepository::new();
+        mock_stats_repo
+            .expect_global_usage()
+            .times(1)
+            .returning(|| Ok(DedupStats::new(1, 10, 1, 10)));
+        mock_stats_repo
+            .expect_usage_by_tenant()
+            .times(1)
+            .returning(|| Ok(vec![]));
+
+        let use_case = StatsUseCase::new(Arc::new(mock_stats_repo));
+
+        // Act
+        let first = use_case.execute().await.unwrap();
+        let second = use_case.execute().await.unwrap();
+
+        // Assert
+        assert_eq!(first.generated_at, second.generated_at);
+    }
+
+    #[tokio::test]
+    async fn test_stats_repository_error() {
+        // Arrange
+        let mut mock_stats_repo = MockStatsRepository::new();
+        mock_stats_repo
+            .expect_global_usage()
+            .returning(|| Err(RepositoryError::Internal("boom".to_string())));
+
+        let use_case = StatsUseCase::new(Arc::new(mock_stats_repo));
+
+        // Act
+        let result = use_case.execute().await;
+
+        // Assert
+        assert!(matches!(result, Err(ObjectUseCaseError::Repository(_))));
+    }
+
+    #[test]
+    fn test_dedup_ratio_with_empty_storage() {
+        let stats = DedupStats::new(0, 0, 0, 0);
+
+        assert_eq!(stats.dedup_ratio, 1.0);
+        assert_eq!(stats.bytes_saved, 0);
+    }
+}
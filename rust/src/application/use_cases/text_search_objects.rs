@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
-use crate::application::dto::{ObjectDto, TextSearchRequest, TextSearchResponse};
+use crate::application::dto::{TextSearchHit, TextSearchRequest, TextSearchResponse};
 use crate::application::errors::TextSearchUseCaseError;
+use crate::application::pagination::{PageLimits, Pagination};
 use crate::application::ports::ObjectRepository;
 use crate::application::validation::{
     validate_namespace_and_tenant_for_text_search, validate_search_query,
@@ -10,38 +11,49 @@ use crate::application::validation::{
 /// Use case: Full-text search across object metadata and keys
 pub struct TextSearchObjectsUseCase {
     object_repo: Arc<dyn ObjectRepository>,
+    min_rank: f32,
 }
 
 impl TextSearchObjectsUseCase {
     pub fn new(object_repo: Arc<dyn ObjectRepository>) -> Self {
-        Self { object_repo }
+        Self {
+            object_repo,
+            min_rank: 0.0,
+        }
+    }
+
+    /// Rank threshold applied when a request does not set `min_rank`
+    pub fn with_min_rank(mut self, min_rank: f32) -> Self {
+        self.min_rank = min_rank;
+        self
     }
 
     /// Execute full-text search
     pub async fn execute(
         &self,
-        request: TextSearchRequest,
+        mut request: TextSearchRequest,
     ) -> Result<TextSearchResponse, TextSearchUseCaseError> {
         // 1. Parse and validate
         let (_namespace, _tenant_id) =
             validate_namespace_and_tenant_for_text_search(&request.namespace, &request.tenant_id)?;
         validate_search_query(&request.query)?;
 
+        request.min_rank = Some(request.min_rank.unwrap_or(self.min_rank));
+
         // 2. Query repository with text search
-        let objects = self.object_repo.text_search(&request).await?;
+        let page = self.object_repo.text_search(&request).await?;
 
         // 3. Convert to DTOs
-        let dtos: Vec<ObjectDto> = objects.into_iter().map(ObjectDto::from).collect();
+        let hits: Vec<TextSearchHit> = page.matches.into_iter().map(TextSearchHit::from).collect();
 
-        let total = dtos.len();
-        let limit = request.limit.unwrap_or(100).min(1000);
-        let offset = request.offset.unwrap_or(0);
+        let total = page.total as usize;
+        let pagination = Pagination::new(request.limit, request.offset, PageLimits::OBJECTS);
 
         Ok(TextSearchResponse {
-            objects: dtos,
+            objects: hits,
             total,
-            limit,
-            offset,
+            limit: pagination.limit,
+            offset: pagination.offset,
             query: request.query,
         })
     }
@@ -50,6 +62,7 @@ impl TextSearchObjectsUseCase {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::dto::{TextSearchMatch, TextSearchPage};
     use crate::application::ports::MockObjectRepository;
     use crate::domain::entities::Object;
     use crate::domain::value_objects::{Namespace, StorageClass, TenantId};
@@ -78,13 +91,31 @@ mod tests {
             query: "llama".to_string(),
             search_in_metadata: Some(true),
             search_in_key: Some(true),
+            search_in_custom_metadata: Some(true),
+            search_in_content: Some(true),
+            prefix: None,
+            min_rank: None,
         };
 
-        let objects = vec![create_test_object(), create_test_object()];
+        let page = TextSearchPage {
+            matches: vec![
+                TextSearchMatch {
+                    object: create_test_object(),
+                    rank: 1.5,
+                    highlight: Some("a <mark>llama</mark> model".to_string()),
+                },
+                TextSearchMatch {
+                    object: create_test_object(),
+                    rank: 0.1,
+                    highlight: None,
+                },
+            ],
+            total: 12,
+        };
         mock_object_repo
             .expect_text_search()
             .times(1)
-            .returning(move |_| Ok(objects.clone()));
+            .returning(move |_| Ok(page.clone()));
 
         let use_case = TextSearchObjectsUseCase::new(Arc::new(mock_object_repo));
 
@@ -95,10 +126,50 @@ mod tests {
         assert!(result.is_ok());
         let response = result.unwrap();
         assert_eq!(response.objects.len(), 2);
-        assert_eq!(response.total, 2);
+        assert_eq!(response.total, 12);
+        assert_eq!(response.objects[0].rank, 1.5);
+        assert_eq!(
+            response.objects[0].highlight.as_deref(),
+            Some("a <mark>llama</mark> model")
+        );
         assert_eq!(response.query, "llama");
     }
 
+    #[tokio::test]
+    async fn test_text_search_applies_default_min_rank() {
+        // Arrange
+        let mut mock_object_repo = MockObjectRepository::new();
+        let request = TextSearchRequest {
+            namespace: "test".to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            limit: None,
+            offset: None,
+            query: "llama".to_string(),
+            search_in_metadata: None,
+            search_in_key: None,
+            search_in_custom_metadata: None,
+            search_in_content: None,
+            prefix: None,
+            min_rank: None,
+        };
+
+        mock_object_repo
+            .expect_text_search()
+            .withf(|request| request.min_rank == Some(0.25))
+            .times(1)
+            .returning(|_| Ok(TextSearchPage::default()));
+
+        let use_case =
+            TextSearchObjectsUseCase::new(Arc::new(mock_object_repo)).with_min_rank(0.25);
+
+        // Act
+        let response = use_case.execute(request).await.unwrap();
+
+        // Assert
+        assert!(response.objects.is_empty());
+        assert_eq!(response.total, 0);
+    }
+
     #[tokio::test]
     async fn test_text_search_empty_query() {
         // Arrange
@@ -111,6 +182,10 @@ mod tests {
             query: "".to_string(),
             search_in_metadata: Some(true),
             search_in_key: Some(true),
+            search_in_custom_metadata: Some(true),
+            search_in_content: Some(true),
+            prefix: None,
+            min_rank: None,
         };
 
         let use_case = TextSearchObjectsUseCase::new(Arc::new(mock_object_repo));
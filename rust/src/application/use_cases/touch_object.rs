@@ -0,0 +1,323 @@
+use std::sync::Arc;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+
+use crate::application::dto::{ObjectAccess, TouchObjectResponse};
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::{BlobRepository, BlobStore, ObjectRepository, StorageError};
+use crate::domain::entities::Object;
+use crate::domain::value_objects::{ContentHash, ObjectId, StorageClass, TenantId};
+
+/// Use case: Mark an object as accessed and optionally bring it back to hot
+/// storage
+///
+/// Touching advances `last_accessed_at` without counting a download, which
+/// resets the idle time that moves objects to cold storage. Promotion copies
+/// the blob from cold to hot storage and moves the blob and every object
+/// sharing it, since objects with the same content share one blob.
+pub struct TouchObjectUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+    blob_repo: Arc<dyn BlobRepository>,
+    blob_store: Arc<dyn BlobStore>,
+}
+
+impl TouchObjectUseCase {
+    pub fn new(
+        object_repo: Arc<dyn ObjectRepository>,
+        blob_repo: Arc<dyn BlobRepository>,
+        blob_store: Arc<dyn BlobStore>,
+    ) -> Self {
+        Self {
+            object_repo,
+            blob_repo,
+            blob_store,
+        }
+    }
+
+    /// Touch a committed object owned by `tenant_id`, promoting its blob to
+    /// hot storage if `promote` is set and it is cold
+    #[tracing::instrument(
+        name = "TouchObjectUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id, promote)
+    )]
+    pub async fn execute(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &TenantId,
+        promote: bool,
+    ) -> Result<TouchObjectResponse, ObjectUseCaseError> {
+        // 1. Load the object (other tenants' objects are not found)
+        let object = self.find(object_id, tenant_id).await?;
+        let content_hash = object
+            .content_hash()
+            .cloned()
+            .ok_or_else(|| ObjectUseCaseError::NotFound(object_id.to_string()))?;
+
+        // 2. Advance the last access without counting a download
+        let now = OffsetDateTime::now_utc();
+        self.object_repo
+            .record_access(&[ObjectAccess {
+                object_id: *object_id,
+                count: 0,
+                last_accessed_at: now,
+            }])
+            .await?;
+
+        // 3. Bring the blob back to hot storage
+        let mut storage_class = object.storage_class();
+        let mut promoted = false;
+        if promote && storage_class == StorageClass::Cold {
+            promoted = self.promote(&content_hash).await?;
+            storage_class = if promoted {
+                StorageClass::Hot
+            } else {
+                // Moved by someone else meanwhile; report where it is now
+                self.find(object_id, tenant_id).await?.storage_class()
+            };
+        }
+
+        Ok(TouchObjectResponse {
+            id: object_id.to_string(),
+            storage_class,
+            promoted,
+            last_accessed_at: now.format(&Rfc3339).unwrap_or_default(),
+        })
+    }
+
+    async fn find(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &TenantId,
+    ) -> Result<Object, ObjectUseCaseError> {
+        self.object_repo
+            .find_by_id(object_id)
+            .await?
+            .filter(|object| object.tenant_id() == tenant_id && object.is_readable())
+            .ok_or_else(|| ObjectUseCaseError::NotFound(object_id.to_string()))
+    }
+
+    /// Copy a cold blob to hot storage and move its records
+    ///
+    /// The blob row only moves if it is still cold, so a concurrent promotion
+    /// or lifecycle move wins cleanly: the loser leaves the records alone and
+    /// returns `false`. The hot copy is written before any record points at
+    /// it, and the cold copy is removed only after every record moved.
+    async fn promote(&self, content_hash: &ContentHash) -> Result<bool, ObjectUseCaseError> {
+        // 1. Copy the content, checking it arrived intact
+        let reader = self
+            .blob_store
+            .read(content_hash, StorageClass::Cold)
+            .await
+            .map_err(|e| match e {
+                e @ StorageError::Restoring(_) => ObjectUseCaseError::Conflict(e.to_string()),
+                e => e.into(),
+            })?;
+        let (written, size_bytes) = self.blob_store.write(reader, StorageClass::Hot).await?;
+        if &written != content_hash {
+            if let Err(e) = self.blob_store.delete(&written, StorageClass::Hot).await {
+                warn!(content_hash = %written, error = %e, "Failed to remove corrupt hot copy");
+            }
+            return Err(StorageError::HashMismatch {
+                expected: content_hash.to_string(),
+                actual: written.to_string(),
+            }
+            .into());
+        }
+
+        // 2. Move the blob, unless it is no longer cold
+        if !self
+            .blob_repo
+            .move_storage_class(content_hash, StorageClass::Cold, StorageClass::Hot)
+            .await?
+        {
+            info!(content_hash = %content_hash, "Blob left cold storage concurrently");
+            return Ok(false);
+        }
+
+        // 3. Move every object sharing the blob
+        let objects = self
+            .object_repo
+            .move_storage_class(content_hash, StorageClass::Cold, StorageClass::Hot)
+            .await?;
+
+        // 4. Drop the cold copy; a leftover is found by the orphan scan
+        if let Err(e) = self
+            .blob_store
+            .delete(content_hash, StorageClass::Cold)
+            .await
+        {
+            warn!(content_hash = %content_hash, error = %e, "Failed to remove cold copy");
+        }
+
+        info!(
+            content_hash = %content_hash,
+            size_bytes,
+            objects,
+            "Promoted blob to hot storage"
+        );
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{MockBlobRepository, MockBlobStore, MockObjectRepository};
+    use crate::domain::value_objects::Namespace;
+    use std::io::Cursor;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn hash() -> ContentHash {
+        ContentHash::from_str(&"a".repeat(64)).unwrap()
+    }
+
+    fn committed_object(tenant_id: &TenantId, storage_class: StorageClass) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("test").unwrap(),
+            tenant_id.clone(),
+            Some("report.pdf".to_string()),
+            storage_class,
+        );
+        object.commit(&hash(), 42).unwrap();
+        object
+    }
+
+    fn object_repo(object: Object) -> MockObjectRepository {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo
+            .expect_record_access()
+            .withf(|accesses| accesses.len() == 1 && accesses[0].count == 0)
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_object_repo
+    }
+
+    #[tokio::test]
+    async fn test_touch_records_access_without_moving() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id, StorageClass::Cold);
+        let object_id = *object.id();
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store.expect_read().never();
+        let use_case = TouchObjectUseCase::new(
+            Arc::new(object_repo(object)),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(mock_blob_store),
+        );
+
+        let response = use_case
+            .execute(&object_id, &tenant_id, false)
+            .await
+            .unwrap();
+
+        assert_eq!(response.storage_class, StorageClass::Cold);
+        assert!(!response.promoted);
+    }
+
+    #[tokio::test]
+    async fn test_promote_copies_blob_and_moves_records() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id, StorageClass::Cold);
+        let object_id = *object.id();
+        let mut mock_object_repo = object_repo(object);
+        mock_object_repo
+            .expect_move_storage_class()
+            .withf(|_, from, to| *from == StorageClass::Cold && *to == StorageClass::Hot)
+            .times(1)
+            .returning(|_, _, _| Ok(2));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_move_storage_class()
+            .times(1)
+            .returning(|_, _, _| Ok(true));
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_read()
+            .withf(|_, class| *class == StorageClass::Cold)
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"content".to_vec()))));
+        mock_blob_store
+            .expect_write()
+            .withf(|_, class| *class == StorageClass::Hot)
+            .returning(|_, _| Ok((hash(), 7)));
+        mock_blob_store
+            .expect_delete()
+            .withf(|_, class| *class == StorageClass::Cold)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let use_case = TouchObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        );
+
+        let response = use_case
+            .execute(&object_id, &tenant_id, true)
+            .await
+            .unwrap();
+
+        assert_eq!(response.storage_class, StorageClass::Hot);
+        assert!(response.promoted);
+    }
+
+    #[tokio::test]
+    async fn test_promote_loses_to_concurrent_move() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id, StorageClass::Cold);
+        let object_id = *object.id();
+        let mut mock_object_repo = object_repo(object);
+        mock_object_repo.expect_move_storage_class().never();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_move_storage_class()
+            .returning(|_, _, _| Ok(false));
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"content".to_vec()))));
+        mock_blob_store
+            .expect_write()
+            .returning(|_, _| Ok((hash(), 7)));
+        mock_blob_store.expect_delete().never();
+        let use_case = TouchObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        );
+
+        let response = use_case
+            .execute(&object_id, &tenant_id, true)
+            .await
+            .unwrap();
+
+        assert!(!response.promoted);
+    }
+
+    #[tokio::test]
+    async fn test_other_tenants_object_is_not_found() {
+        let object = committed_object(&TenantId::new(Uuid::new_v4()), StorageClass::Hot);
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo.expect_record_access().never();
+        let use_case = TouchObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        let result = use_case
+            .execute(&object_id, &TenantId::new(Uuid::new_v4()), true)
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+}
@@ -0,0 +1,393 @@
+use std::sync::Arc;
+
+use crate::application::dto::ObjectDto;
+use crate::application::errors::ObjectUseCaseError;
+use crate::application::ports::ObjectRepository;
+use crate::application::validation::{validate_metadata, validate_metadata_patch, MetadataLimits};
+use crate::domain::entities::Object;
+use crate::domain::value_objects::{ObjectId, TenantId};
+
+/// Attempts before a patch racing other writers gives up with a conflict
+const MAX_PATCH_ATTEMPTS: usize = 5;
+
+/// Use case: Update object metadata with a JSON Merge Patch (RFC 7386)
+///
+/// Only the metadata record changes; the blob and content hash are untouched.
+pub struct UpdateObjectMetadataUseCase {
+    object_repo: Arc<dyn ObjectRepository>,
+    limits: MetadataLimits,
+}
+
+impl UpdateObjectMetadataUseCase {
+    pub fn new(object_repo: Arc<dyn ObjectRepository>) -> Self {
+        Self {
+            object_repo,
+            limits: MetadataLimits::default(),
+        }
+    }
+
+    /// Bound the size of patches and of the metadata they produce
+    pub fn with_limits(mut self, limits: MetadataLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Apply `patch` to the metadata of a committed object owned by `tenant_id`
+    /// and return the updated object
+    ///
+    /// Concurrent patches are serialized by comparing the stored metadata on
+    /// write and re-applying the patch to the fresh metadata on conflict.
+    #[tracing::instrument(
+        name = "UpdateObjectMetadataUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(object_id = %object_id)
+    )]
+    pub async fn execute(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &TenantId,
+        patch: &serde_json::Value,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        // 1. Validate the patch shape and size
+        self.validate_patch(patch)?;
+
+        let (object, _) = self.apply(object_id, tenant_id, patch).await?;
+        Ok(ObjectDto::from(object))
+    }
+
+    /// Check a patch before applying it to any object
+    pub(crate) fn validate_patch(
+        &self,
+        patch: &serde_json::Value,
+    ) -> Result<(), ObjectUseCaseError> {
+        validate_metadata_patch(patch, &self.limits)
+    }
+
+    /// Apply a validated `patch` and return the object, and whether its
+    /// metadata changed
+    pub(crate) async fn apply(
+        &self,
+        object_id: &ObjectId,
+        tenant_id: &TenantId,
+        patch: &serde_json::Value,
+    ) -> Result<(Object, bool), ObjectUseCaseError> {
+        for _ in 0..MAX_PATCH_ATTEMPTS {
+            // 2. Load the current object (other tenants' objects are not found)
+            let mut object = self
+                .object_repo
+                .find_by_id(object_id)
+                .await?
+                .filter(|object| object.tenant_id() == tenant_id)
+                .ok_or_else(|| ObjectUseCaseError::NotFound(object_id.to_string()))?;
+            object.ensure_unlocked()?;
+
+            // 3. Merge and validate the result
+            let current = object.metadata().clone();
+            let merged = current.merge_patch(patch)?;
+            validate_metadata(&merged, &self.limits)?;
+
+            if merged == current {
+                return Ok((object, false));
+            }
+
+            // 4. Compare-and-swap the metadata record
+            object.set_metadata(merged);
+            if self
+                .object_repo
+                .update_metadata_if_match(&object, &current)
+                .await?
+            {
+                return Ok((object, true));
+            }
+        }
+
+        Err(ObjectUseCaseError::Conflict(format!(
+            "Metadata of object {object_id} is being modified concurrently"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockObjectRepository;
+    use crate::domain::entities::Object;
+    use crate::domain::error_types::ValidationError;
+    use crate::domain::value_objects::{ContentHash, Namespace, StorageClass};
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn committed_object(tenant_id: &TenantId) -> Object {
+        let mut object = Object::new(
+            Namespace::from_str("test").unwrap(),
+            tenant_id.clone(),
+            Some("report.pdf".to_string()),
+            StorageClass::Hot,
+        );
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 42)
+            .unwrap();
+        object
+    }
+
+    #[tokio::test]
+    async fn test_patch_merges_and_keeps_content() {
+        // Arrange
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id);
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        let content_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+        mock_object_repo
+            .expect_update_metadata_if_match()
+            .withf(move |object, expected| {
+                expected.description.is_none()
+                    && object.metadata().description.as_deref() == Some("Q3 report")
+                    && object.content_hash() == Some(&content_hash)
+            })
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let use_case = UpdateObjectMetadataUseCase::new(Arc::new(mock_object_repo));
+
+        // Act
+        let dto = use_case
+            .execute(
+                &object_id,
+                &tenant_id,
+                &serde_json::json!({ "description": "Q3 report" }),
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(dto.metadata.description.as_deref(), Some("Q3 report"));
+        assert_eq!(dto.content_hash, Some("a".repeat(64)));
+    }
+
+    #[tokio::test]
+    async fn test_patch_retries_after_concurrent_update() {
+        // Arrange: first swap loses, second wins
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id);
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .times(2)
+            .returning(move |_| Ok(Some(object.clone())));
+        let mut attempts = 0;
+        mock_object_repo
+            .expect_update_metadata_if_match()
+            .times(2)
+            .returning(move |_, _| {
+                attempts += 1;
+                Ok(attempts > 1)
+            });
+
+        let use_case = UpdateObjectMetadataUseCase::new(Arc::new(mock_object_repo));
+
+        // Act
+        let result = use_case
+            .execute(
+                &object_id,
+                &tenant_id,
+                &serde_json::json!({ "tags": { "team": "finance" } }),
+            )
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_patch_gives_up_under_sustained_contention() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id);
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo
+            .expect_update_metadata_if_match()
+            .times(MAX_PATCH_ATTEMPTS)
+            .returning(|_, _| Ok(false));
+
+        let use_case = UpdateObjectMetadataUseCase::new(Arc::new(mock_object_repo));
+
+        let result = use_case
+            .execute(
+                &object_id,
+                &tenant_id,
+                &serde_json::json!({ "description": "x" }),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_patch_rejects_unknown_and_oversized_fields() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id);
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+
+        let use_case = UpdateObjectMetadataUseCase::new(Arc::new(mock_object_repo));
+
+        let unknown = use_case
+            .execute(
+                &object_id,
+                &tenant_id,
+                &serde_json::json!({ "colour": "red" }),
+            )
+            .await;
+        let oversized = use_case
+            .execute(
+                &object_id,
+                &tenant_id,
+                &serde_json::json!({ "description": "x".repeat(5000) }),
+            )
+            .await;
+        let not_an_object = use_case
+            .execute(&object_id, &tenant_id, &serde_json::json!(["description"]))
+            .await;
+
+        assert!(matches!(unknown, Err(ObjectUseCaseError::Domain(_))));
+        assert!(matches!(
+            oversized,
+            Err(ObjectUseCaseError::Validation(
+                ValidationError::TooLong { .. }
+            ))
+        ));
+        assert!(matches!(
+            not_an_object,
+            Err(ObjectUseCaseError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_patch_hides_other_tenants_objects() {
+        let object = committed_object(&TenantId::new(Uuid::new_v4()));
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+
+        let use_case = UpdateObjectMetadataUseCase::new(Arc::new(mock_object_repo));
+
+        let result = use_case
+            .execute(
+                &object_id,
+                &TenantId::new(Uuid::new_v4()),
+                &serde_json::json!({ "description": "x" }),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_patch_of_locked_object_is_refused() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let mut object = committed_object(&tenant_id);
+        object.set_legal_hold(true).unwrap();
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo.expect_update_metadata_if_match().never();
+
+        let use_case = UpdateObjectMetadataUseCase::new(Arc::new(mock_object_repo));
+
+        let result = use_case
+            .execute(
+                &object_id,
+                &tenant_id,
+                &serde_json::json!({ "description": "x" }),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(
+                crate::domain::errors::DomainError::ObjectLocked(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_patch_at_configured_limits_accepted_and_past_them_rejected() {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let object = committed_object(&tenant_id);
+        let object_id = *object.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(object.clone())));
+        mock_object_repo
+            .expect_update_metadata_if_match()
+            .times(3)
+            .returning(|_, _| Ok(true));
+
+        let use_case = UpdateObjectMetadataUseCase::new(Arc::new(mock_object_repo)).with_limits(
+            MetadataLimits {
+                max_bytes: 1024,
+                max_tags: 2,
+                max_tag_key_chars: 8,
+                max_string_chars: 16,
+            },
+        );
+
+        let at_limit = [
+            serde_json::json!({ "tags": { "a": 1, "b": 2 } }),
+            serde_json::json!({ "tags": { ("k".repeat(8)): 1 } }),
+            serde_json::json!({ "description": "x".repeat(16) }),
+        ];
+        for metadata in at_limit {
+            let result = use_case.execute(&object_id, &tenant_id, &metadata).await;
+            assert!(result.is_ok(), "{metadata}");
+        }
+
+        let past_limit = [
+            (
+                serde_json::json!({ "tags": { "a": 1, "b": 2, "c": 3 } }),
+                "tags",
+                2,
+            ),
+            (
+                serde_json::json!({ "tags": { ("k".repeat(9)): 1 } }),
+                "tags.kkkkkkkkk",
+                8,
+            ),
+            (
+                serde_json::json!({ "description": "x".repeat(17) }),
+                "description",
+                16,
+            ),
+            (
+                serde_json::json!({ "description": "x".repeat(2000) }),
+                "metadata",
+                1024,
+            ),
+        ];
+        for (metadata, expected_field, expected_max) in past_limit {
+            match use_case.execute(&object_id, &tenant_id, &metadata).await {
+                Err(ObjectUseCaseError::Validation(ValidationError::TooLong { field, max })) => {
+                    assert_eq!((field.as_str(), max), (expected_field, expected_max));
+                }
+                other => panic!("expected TooLong for {expected_field}, got {other:?}"),
+            }
+        }
+    }
+}
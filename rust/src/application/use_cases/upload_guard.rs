@@ -0,0 +1,145 @@
+use sha2::{Digest, Sha256};
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::application::ports::{BlobReader, StorageError};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::ContentHash;
+
+/// Upload body reader that enforces the size limit and the expected hash
+///
+/// Bytes are counted (and hashed, when the client supplied a hash) as the
+/// blob store pulls them, so a violation fails the store's write before the
+/// blob is committed. Violations surface as I/O errors wrapping a
+/// `DomainError`; see [`violation`].
+pub(crate) struct UploadGuard {
+    inner: BlobReader,
+    max_bytes: u64,
+    bytes_read: u64,
+    expected: Option<(ContentHash, Sha256)>,
+}
+
+impl UploadGuard {
+    pub(crate) fn new(inner: BlobReader, max_bytes: u64, expected: Option<ContentHash>) -> Self {
+        Self {
+            inner,
+            max_bytes,
+            bytes_read: 0,
+            expected: expected.map(|hash| (hash, Sha256::new())),
+        }
+    }
+}
+
+impl AsyncRead for UploadGuard {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let start = buf.filled().len();
+        let may_end = buf.remaining() > 0;
+        ready!(this.inner.as_mut().poll_read(cx, buf))?;
+        let chunk = &buf.filled()[start..];
+
+        this.bytes_read += chunk.len() as u64;
+        if this.bytes_read > this.max_bytes {
+            return Poll::Ready(Err(io::Error::other(DomainError::SizeExceedsMaximum {
+                size: this.bytes_read,
+                max: this.max_bytes,
+            })));
+        }
+
+        if !chunk.is_empty() {
+            if let Some((_, hasher)) = &mut this.expected {
+                hasher.update(chunk);
+            }
+        } else if may_end {
+            // End of body: check the digest once
+            if let Some((expected, hasher)) = this.expected.take() {
+                let actual = hex::encode(hasher.finalize());
+                if actual != expected.as_hex() {
+                    return Poll::Ready(Err(io::Error::other(DomainError::ContentHashMismatch {
+                        expected: expected.to_string(),
+                        actual,
+                    })));
+                }
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The guard violation behind a failed blob write, if any
+pub(crate) fn violation(error: &StorageError) -> Option<DomainError> {
+    match error {
+        StorageError::Io(e) => e
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<DomainError>())
+            .cloned(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tokio::io::AsyncReadExt;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    fn guard(content: &'static [u8], max_bytes: u64, expected: Option<&str>) -> UploadGuard {
+        UploadGuard::new(
+            Box::pin(Cursor::new(content)),
+            max_bytes,
+            expected.map(|hex| ContentHash::from_hex(hex.to_string()).unwrap()),
+        )
+    }
+
+    async fn read_all(mut guard: UploadGuard) -> Result<Vec<u8>, StorageError> {
+        let mut content = Vec::new();
+        guard.read_to_end(&mut content).await?;
+        Ok(content)
+    }
+
+    #[tokio::test]
+    async fn test_passes_content_through() {
+        let content = read_all(guard(b"hello", 5, Some(HELLO_SHA256)))
+            .await
+            .unwrap();
+
+        assert_eq!(content, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_content_over_limit() {
+        let err = read_all(guard(b"hello", 4, None)).await.unwrap_err();
+
+        assert!(matches!(
+            violation(&err),
+            Some(DomainError::SizeExceedsMaximum { size: 5, max: 4 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_hash_mismatch_at_end() {
+        let err = read_all(guard(b"hellO", 5, Some(HELLO_SHA256)))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            violation(&err),
+            Some(DomainError::ContentHashMismatch { expected, .. }) if expected == HELLO_SHA256
+        ));
+    }
+
+    #[test]
+    fn test_other_storage_errors_are_not_violations() {
+        assert!(violation(&StorageError::Internal("disk full".to_string())).is_none());
+        assert!(violation(&StorageError::Io(io::Error::other("reset"))).is_none());
+    }
+}
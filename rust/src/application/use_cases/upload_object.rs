@@ -1,17 +1,66 @@
+use dashmap::DashSet;
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
 use std::sync::Arc;
+use tokio::io::AsyncReadExt;
 
-use crate::application::dto::{ObjectDto, UploadRequest};
+use crate::application::content_encoding;
+use crate::application::content_policy::{sniff_content_type, ContentPolicy, SNIFF_PREFIX_BYTES};
+use crate::application::dto::{
+    ObjectDto, ResumableUploadDto, ResumeOutcome, UploadChunk, UploadPrecondition, UploadRequest,
+    UploadStatus,
+};
 use crate::application::errors::ObjectUseCaseError;
-use crate::application::ports::{BlobReader, BlobRepository, BlobStore, ObjectRepository};
+use crate::application::ports::{
+    BlobReader, BlobRepository, BlobRouter, BlobStore, ContentScanner, IdempotencyRecord,
+    IdempotencyRepository, NamespaceConfigRepository, ObjectRepository, RepositoryError,
+    ScanSubject, ScanVerdict, StorageError, TextExtractor,
+};
+use crate::application::short_hashes::ShortHashes;
+use crate::application::status_watch::StatusWatch;
+use crate::application::use_cases::upload_guard::{self, UploadGuard};
 use crate::application::validation::validate_namespace_and_tenant;
-use crate::domain::entities::Object;
+use crate::domain::entities::{NamespaceConfig, Object};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{
+    ContentEncoding, ContentHash, ContentHashPrefix, Namespace, ObjectId, StorageClass, TenantId,
+};
+
+/// Default cap on content read back for text extraction (1 MiB)
+pub const DEFAULT_TEXT_EXTRACTION_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Default time an idempotency key is remembered (24 hours)
+pub const DEFAULT_IDEMPOTENCY_TTL_SECS: i64 = 24 * 3600;
+
+/// How long an upload in progress holds its idempotency key (15 minutes)
+///
+/// A retry after the lease lapses takes the key over, so a crashed upload
+/// does not block its key until the full TTL.
+pub const IDEMPOTENCY_LEASE_SECS: i64 = 15 * 60;
+
+/// Longest idempotency key accepted
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
 
 /// Use case: Upload an object
 pub struct UploadObjectUseCase {
     object_repo: Arc<dyn ObjectRepository>,
     blob_repo: Arc<dyn BlobRepository>,
     blob_store: Arc<dyn BlobStore>,
+    blob_router: Option<Arc<dyn BlobRouter>>,
     max_upload_size_bytes: u64,
+    max_object_size_bytes: u64,
+    text_extractor: Option<Arc<dyn TextExtractor>>,
+    text_extraction_max_bytes: u64,
+    content_policy: ContentPolicy,
+    content_scanner: Option<Arc<dyn ContentScanner>>,
+    scan_fail_open: bool,
+    idempotency_repo: Option<Arc<dyn IdempotencyRepository>>,
+    idempotency_ttl_secs: i64,
+    namespace_configs: Option<Arc<dyn NamespaceConfigRepository>>,
+    status_watch: Option<Arc<StatusWatch>>,
+    short_hashes: ShortHashes,
+    /// Resumable uploads with a chunk being received
+    receiving: DashSet<ObjectId>,
 }
 
 impl UploadObjectUseCase {
@@ -22,9 +71,22 @@ impl UploadObjectUseCase {
     ) -> Self {
         Self {
             object_repo,
+            short_hashes: ShortHashes::new(Arc::clone(&blob_repo), 0),
             blob_repo,
             blob_store,
+            blob_router: None,
             max_upload_size_bytes: 10 * 1024 * 1024 * 1024,
+            max_object_size_bytes: 10 * 1024 * 1024 * 1024,
+            text_extractor: None,
+            text_extraction_max_bytes: DEFAULT_TEXT_EXTRACTION_MAX_BYTES,
+            content_policy: ContentPolicy::default(),
+            content_scanner: None,
+            scan_fail_open: false,
+            idempotency_repo: None,
+            idempotency_ttl_secs: DEFAULT_IDEMPOTENCY_TTL_SECS,
+            namespace_configs: None,
+            status_watch: None,
+            receiving: DashSet::new(),
         }
     }
 
@@ -36,12 +98,126 @@ impl UploadObjectUseCase {
     ) -> Self {
         Self {
             object_repo,
+            short_hashes: ShortHashes::new(Arc::clone(&blob_repo), 0),
             blob_repo,
             blob_store,
+            blob_router: None,
             max_upload_size_bytes,
+            max_object_size_bytes: max_upload_size_bytes,
+            text_extractor: None,
+            text_extraction_max_bytes: DEFAULT_TEXT_EXTRACTION_MAX_BYTES,
+            content_policy: ContentPolicy::default(),
+            content_scanner: None,
+            scan_fail_open: false,
+            idempotency_repo: None,
+            idempotency_ttl_secs: DEFAULT_IDEMPOTENCY_TTL_SECS,
+            namespace_configs: None,
+            status_watch: None,
+            receiving: DashSet::new(),
         }
     }
 
+    /// Reject objects whose content exceeds `max_bytes` (defaults to the upload limit)
+    ///
+    /// Counted while the content streams, so bodies without a Content-Length
+    /// and bulk archive entries are capped too.
+    pub fn with_max_object_size_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_object_size_bytes = max_bytes;
+        self
+    }
+
+    /// Extract searchable text from uploads of supported content types
+    ///
+    /// Content larger than `max_bytes` is stored without extracted text.
+    pub fn with_text_extractor(
+        mut self,
+        text_extractor: Arc<dyn TextExtractor>,
+        max_bytes: u64,
+    ) -> Self {
+        self.text_extractor = Some(text_extractor);
+        self.text_extraction_max_bytes = max_bytes;
+        self
+    }
+
+    /// Reject uploads whose declared type, sniffed type or key extension
+    /// the policy disallows for their namespace
+    pub fn with_content_policy(mut self, content_policy: ContentPolicy) -> Self {
+        self.content_policy = content_policy;
+        self
+    }
+
+    /// Scan content before it commits, rejecting what the scanner flags
+    ///
+    /// With `fail_open`, uploads commit unscanned while the scanner is
+    /// failing; otherwise they fail.
+    pub fn with_content_scanner(
+        mut self,
+        content_scanner: Arc<dyn ContentScanner>,
+        fail_open: bool,
+    ) -> Self {
+        self.content_scanner = Some(content_scanner);
+        self.scan_fail_open = fail_open;
+        self
+    }
+
+    /// Honour `request.idempotency_key`, remembering each key for `ttl_secs`
+    ///
+    /// Without a repository the key is ignored.
+    pub fn with_idempotency(
+        mut self,
+        idempotency_repo: Arc<dyn IdempotencyRepository>,
+        ttl_secs: i64,
+    ) -> Self {
+        self.idempotency_repo = Some(idempotency_repo);
+        self.idempotency_ttl_secs = ttl_secs;
+        self
+    }
+
+    /// Store uploads that name no storage class in their namespace's default class
+    pub fn with_namespace_configs(
+        mut self,
+        namespace_configs: Arc<dyn NamespaceConfigRepository>,
+    ) -> Self {
+        self.namespace_configs = Some(namespace_configs);
+        self
+    }
+
+    /// Wake clients waiting on `status_watch` when an upload commits or fails
+    pub fn with_status_watch(mut self, status_watch: Arc<StatusWatch>) -> Self {
+        self.status_watch = Some(status_watch);
+        self
+    }
+
+    /// Write each object's blobs to the store `blob_router` picks for its
+    /// namespace and tenant instead of `blob_store`
+    pub fn with_blob_router(mut self, blob_router: Arc<dyn BlobRouter>) -> Self {
+        self.blob_router = Some(blob_router);
+        self
+    }
+
+    /// Accept a unique prefix of a stored content hash as `X-Content-Hash`
+    /// and report short hashes of at least `len` characters; 0 turns short
+    /// hashes off
+    pub fn with_short_hash_len(mut self, len: usize) -> Self {
+        self.short_hashes = ShortHashes::new(Arc::clone(&self.blob_repo), len);
+        self
+    }
+
+    /// Full content hash an expected hash refers to
+    ///
+    /// A short hash must name stored content: a prefix can't be checked
+    /// against content the service hasn't seen.
+    pub async fn resolve_hash(
+        &self,
+        reference: &ContentHashPrefix,
+    ) -> Result<ContentHash, ObjectUseCaseError> {
+        self.short_hashes.resolve(reference).await?.ok_or_else(|| {
+            ObjectUseCaseError::InvalidRequest(format!(
+                "Content hash prefix '{reference}' matches no stored content; send the full hash"
+            ))
+        })
+    }
+
     pub fn max_upload_size_bytes(&self) -> u64 {
         self.max_upload_size_bytes
     }
@@ -51,33 +227,1026 @@ impl UploadObjectUseCase {
         &self,
         request: UploadRequest,
         reader: BlobReader,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        self.execute_with_precondition(request, reader, UploadPrecondition::None)
+            .await
+    }
+
+    /// Execute upload workflow guarded by an optimistic concurrency precondition
+    ///
+    /// `IfNoneMatch` creates the object only when the key is free. `IfMatch`
+    /// overwrites the committed object under the key, and only if its content
+    /// hash is still the expected one when the new content is committed.
+    ///
+    /// The body is streamed to the blob store once; objects over the size
+    /// limit or not matching `request.expected_hash` fail before the blob is
+    /// committed.
+    ///
+    /// With `request.idempotency_key` set, a repeat of an earlier upload
+    /// with the same key and content returns the earlier object instead of
+    /// creating another; see [`Self::with_idempotency`].
+    #[tracing::instrument(
+        name = "UploadObjectUseCase::execute",
+        level = "debug",
+        skip_all,
+        fields(namespace = %request.namespace, tenant_id = %request.tenant_id)
+    )]
+    pub async fn execute_with_precondition(
+        &self,
+        request: UploadRequest,
+        reader: BlobReader,
+        precondition: UploadPrecondition,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        match (&self.idempotency_repo, request.idempotency_key.clone()) {
+            (Some(idempotency_repo), Some(idempotency_key)) => {
+                self.upload_idempotent(
+                    idempotency_repo.as_ref(),
+                    idempotency_key,
+                    request,
+                    reader,
+                    precondition,
+                )
+                .await
+            }
+            _ => self.upload(request, reader, precondition).await,
+        }
+    }
+
+    /// Upload once per idempotency key
+    ///
+    /// The key is reserved before anything is stored, so a concurrent retry
+    /// sees the upload in progress instead of starting a second one. A failed
+    /// upload releases the key for the next retry; one that crashes first
+    /// holds it until [`IDEMPOTENCY_LEASE_SECS`] lapse.
+    async fn upload_idempotent(
+        &self,
+        idempotency_repo: &dyn IdempotencyRepository,
+        idempotency_key: String,
+        request: UploadRequest,
+        reader: BlobReader,
+        precondition: UploadPrecondition,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        if idempotency_key.is_empty()
+            || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN
+            || !idempotency_key.chars().all(|c| c.is_ascii_graphic())
+        {
+            return Err(ObjectUseCaseError::InvalidRequest(format!(
+                "Idempotency key must be 1-{MAX_IDEMPOTENCY_KEY_LEN} visible ASCII characters"
+            )));
+        }
+        let (_, tenant_id) = validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
+
+        let record = IdempotencyRecord {
+            namespace: request.namespace.clone(),
+            object_key: request.key.clone(),
+            object_id: None,
+            content_hash: None,
+        };
+        if !idempotency_repo
+            .reserve(
+                &tenant_id,
+                &idempotency_key,
+                &record,
+                IDEMPOTENCY_LEASE_SECS.min(self.idempotency_ttl_secs),
+            )
+            .await?
+        {
+            let existing = idempotency_repo.find(&tenant_id, &idempotency_key).await?;
+            return self
+                .replay(existing, &record, request.expected_hash, reader)
+                .await;
+        }
+
+        let result = self.upload(request, reader, precondition).await;
+
+        let completed = match &result {
+            Ok(dto) => {
+                let object_id = dto.id.parse::<ObjectId>();
+                let content_hash = dto.content_hash.as_deref().map(str::parse::<ContentHash>);
+                match (object_id, content_hash) {
+                    (Ok(object_id), Some(Ok(content_hash))) => {
+                        idempotency_repo
+                            .complete(
+                                &tenant_id,
+                                &idempotency_key,
+                                &object_id,
+                                &content_hash,
+                                self.idempotency_ttl_secs,
+                            )
+                            .await
+                    }
+                    _ => idempotency_repo.release(&tenant_id, &idempotency_key).await,
+                }
+            }
+            Err(_) => idempotency_repo.release(&tenant_id, &idempotency_key).await,
+        };
+        // The upload's own outcome stands; a stale reservation expires
+        if let Err(e) = completed {
+            tracing::warn!(%tenant_id, idempotency_key = %idempotency_key, "Failed to record idempotency key: {}", e);
+        }
+
+        result
+    }
+
+    /// Answer a repeated upload from the record of the first one
+    async fn replay(
+        &self,
+        existing: Option<IdempotencyRecord>,
+        request: &IdempotencyRecord,
+        expected_hash: Option<ContentHash>,
+        reader: BlobReader,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        // Released or expired since the reservation failed
+        let existing = existing.ok_or_else(|| {
+            ObjectUseCaseError::Conflict("Idempotency key is in use, retry the upload".to_string())
+        })?;
+
+        if existing.namespace != request.namespace || existing.object_key != request.object_key {
+            return Err(ObjectUseCaseError::Conflict(
+                "Idempotency key was used for a different upload".to_string(),
+            ));
+        }
+        let (Some(object_id), Some(original_hash)) = (existing.object_id, existing.content_hash)
+        else {
+            return Err(ObjectUseCaseError::Conflict(
+                "An upload with this idempotency key is still in progress".to_string(),
+            ));
+        };
+
+        let payload_hash = match expected_hash {
+            Some(expected_hash) => expected_hash,
+            None => self.hash_payload(reader).await?,
+        };
+        if payload_hash != original_hash {
+            return Err(ObjectUseCaseError::Conflict(
+                "Idempotency key was used with a different payload".to_string(),
+            ));
+        }
+
+        let object = self
+            .object_repo
+            .find_by_id(&object_id)
+            .await?
+            .ok_or_else(|| {
+                ObjectUseCaseError::Conflict(
+                    "The object uploaded with this idempotency key no longer exists".to_string(),
+                )
+            })?;
+        tracing::debug!(%object_id, "Replayed upload for idempotency key");
+
+        Ok(self.dto(object).await)
+    }
+
+    /// Hash a repeated upload's content without storing it
+    async fn hash_payload(&self, reader: BlobReader) -> Result<ContentHash, ObjectUseCaseError> {
+        let mut reader = UploadGuard::new(reader, self.max_object_size_bytes, None);
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = reader.read(&mut buf).await.map_err(|e| {
+                let e = StorageError::from(e);
+                upload_guard::violation(&e)
+                    .map_or(ObjectUseCaseError::Storage(e), ObjectUseCaseError::Domain)
+            })?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+
+        Ok(ContentHash::from_hex(hex::encode(hasher.finalize()))?)
+    }
+
+    /// The namespace's configuration, if one is set
+    async fn namespace_config(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Option<NamespaceConfig>, ObjectUseCaseError> {
+        match &self.namespace_configs {
+            Some(namespace_configs) => Ok(namespace_configs.find(namespace).await?),
+            None => Ok(None),
+        }
+    }
+
+    async fn upload(
+        &self,
+        request: UploadRequest,
+        reader: BlobReader,
+        precondition: UploadPrecondition,
     ) -> Result<ObjectDto, ObjectUseCaseError> {
         // 1. Parse and validate request
         let (namespace, tenant_id) =
             validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
 
-        let storage_class = request.storage_class.unwrap_or_default();
+        let config = self.namespace_config(&namespace).await?;
+        if let (Some(key), Some(policy)) =
+            (&request.key, config.as_ref().and_then(|c| c.key_policy()))
+        {
+            policy.check(key)?;
+        }
+
+        let reader = self.check_content_policy(&request, reader).await?;
 
-        // 2. Create domain entity in WRITING state
+        let storage_class = match request.storage_class {
+            Some(storage_class) => storage_class,
+            None => config
+                .map(|config| config.default_storage_class())
+                .unwrap_or_default(),
+        };
+        let reader: BlobReader = Box::pin(UploadGuard::new(
+            reader,
+            self.max_object_size_bytes,
+            request.expected_hash.clone(),
+        ));
+
+        if precondition != UploadPrecondition::None && request.key.is_none() {
+            return Err(ObjectUseCaseError::InvalidRequest(
+                "Conditional uploads require a key".to_string(),
+            ));
+        }
+
+        // 2. Check the precondition against the current object under the key
+        let existing = match &request.key {
+            Some(key) if precondition != UploadPrecondition::None => {
+                self.object_repo
+                    .find_by_key(&namespace, &tenant_id, key)
+                    .await?
+            }
+            _ => None,
+        };
+
+        match (&precondition, existing) {
+            (UploadPrecondition::None, _) => {}
+            (UploadPrecondition::IfNoneMatch, None) => {}
+            (UploadPrecondition::IfNoneMatch, Some(_)) => {
+                return Err(ObjectUseCaseError::PreconditionFailed(
+                    "An object already exists for this key".to_string(),
+                ));
+            }
+            (UploadPrecondition::IfMatch(_), None) => {
+                return Err(ObjectUseCaseError::PreconditionFailed(
+                    "No object exists for this key".to_string(),
+                ));
+            }
+            (UploadPrecondition::IfMatch(expected), Some(existing)) => {
+                let current = existing.content_hash().cloned().ok_or_else(|| {
+                    ObjectUseCaseError::PreconditionFailed(
+                        "Object has no committed content".to_string(),
+                    )
+                })?;
+                if expected
+                    .as_ref()
+                    .is_some_and(|expected| *expected != current)
+                {
+                    return Err(ObjectUseCaseError::PreconditionFailed(
+                        "Object content hash does not match If-Match".to_string(),
+                    ));
+                }
+                return self
+                    .replace(
+                        existing,
+                        current,
+                        reader,
+                        request.content_type,
+                        request.content_encoding,
+                    )
+                    .await;
+            }
+        }
+
+        // 3. Create domain entity in WRITING state
         let mut object = Object::new(namespace, tenant_id, request.key, storage_class);
+        if let Some(content_type) = request.content_type {
+            object.set_content_type(content_type);
+        }
+        object.set_content_encoding(request.content_encoding);
+
+        // 4. Reserve in DB (status=WRITING); the per-key unique index makes a
+        // concurrent create-if-absent lose here
+        match self.object_repo.save(&object).await {
+            Err(e)
+                if precondition == UploadPrecondition::IfNoneMatch && e.is_unique_violation() =>
+            {
+                return Err(ObjectUseCaseError::PreconditionFailed(
+                    "An object already exists for this key".to_string(),
+                ));
+            }
+            result => result.map_err(|e| reservation_error(&object, e))?,
+        }
+        let pending = PendingUpload {
+            status_watch: self.status_watch.as_deref(),
+            object_id: *object.id(),
+            committed: false,
+        };
+
+        // 5. Reuse content the tenant already stores under the claimed hash,
+        // without reading the body
+        let existing = match &request.expected_hash {
+            Some(expected_hash) => self.reference_existing_blob(&object, expected_hash).await?,
+            None => None,
+        };
+        let (content_hash, size_bytes) = match existing {
+            Some(existing) => existing,
+            None => {
+                // 6. Write blob to storage (computes and verifies the hash
+                // during write) and get or create its entry with ref counting
+                let (content_hash, size_bytes) = self.write_blob(&object, reader).await?;
+                self.blob_repo
+                    .get_or_create(&content_hash, storage_class, size_bytes)
+                    .await?;
+                (content_hash, size_bytes)
+            }
+        };
+
+        // 7-10. Scan, stage, commit and index the content
+        self.finish(object, content_hash, size_bytes, pending).await
+    }
+
+    /// Commit a reserved object to content whose blob is written and referenced
+    async fn finish(
+        &self,
+        mut object: Object,
+        content_hash: ContentHash,
+        size_bytes: u64,
+        pending: PendingUpload<'_>,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        // 1. Scan the written content; rejected content is never staged
+        Object::check_size(size_bytes, self.max_object_size_bytes)?;
+        self.scan_content(&object, &content_hash, size_bytes)
+            .await?;
+
+        // 2. Stage the content on the WRITING row: from here an interrupted
+        // upload is recovered from the blob instead of being discarded
+        object.stage_content(&content_hash, size_bytes)?;
+        self.object_repo.save(&object).await?;
 
-        // 3. Reserve in DB (status=WRITING)
+        // 3. Commit: update object state to COMMITTED
+        object.commit(&content_hash, size_bytes)?;
         self.object_repo.save(&object).await?;
+        pending.committed();
+
+        // 4. Index extracted text (best effort; never fails the upload)
+        if let Some(text) = self
+            .extract_text(
+                object.content_type(),
+                object.content_encoding(),
+                &content_hash,
+                size_bytes,
+                object.storage_class(),
+            )
+            .await
+        {
+            self.store_extracted_text(object.id(), Some(text)).await;
+        }
+
+        Ok(self.dto(object).await)
+    }
+
+    /// Reserve an object whose content arrives in chunks; see [`Self::resume`]
+    ///
+    /// Preconditions and idempotency keys do not apply; `expected_hash` is
+    /// sent with the chunks instead.
+    #[tracing::instrument(
+        name = "UploadObjectUseCase::start_resumable",
+        level = "debug",
+        skip_all,
+        fields(namespace = %request.namespace, tenant_id = %request.tenant_id)
+    )]
+    pub async fn start_resumable(
+        &self,
+        request: UploadRequest,
+    ) -> Result<ResumableUploadDto, ObjectUseCaseError> {
+        let (namespace, tenant_id) =
+            validate_namespace_and_tenant(&request.namespace, &request.tenant_id)?;
+
+        let config = self.namespace_config(&namespace).await?;
+        if let (Some(key), Some(policy)) =
+            (&request.key, config.as_ref().and_then(|c| c.key_policy()))
+        {
+            policy.check(key)?;
+        }
+        let storage_class = match request.storage_class {
+            Some(storage_class) => storage_class,
+            None => config
+                .map(|config| config.default_storage_class())
+                .unwrap_or_default(),
+        };
+
+        // 1. Reserve in DB (status=WRITING)
+        let mut object = Object::new(namespace, tenant_id, request.key, storage_class);
+        if let Some(content_type) = request.content_type {
+            object.set_content_type(content_type);
+        }
+        object.set_content_encoding(request.content_encoding);
+        self.object_repo
+            .save(&object)
+            .await
+            .map_err(|e| reservation_error(&object, e))?;
+
+        // 2. Create the empty staged upload the chunks are appended to
+        self.blob_store_for(&object)
+            .append_staged(
+                *object.id().as_uuid(),
+                0,
+                Box::pin(tokio::io::empty()),
+                storage_class,
+            )
+            .await?;
+
+        Ok(ResumableUploadDto {
+            upload_id: object.id().to_string(),
+            offset: 0,
+        })
+    }
+
+    /// Bytes received so far for a resumable upload
+    pub async fn upload_offset(
+        &self,
+        upload_id: &ObjectId,
+        tenant_id: &TenantId,
+    ) -> Result<u64, ObjectUseCaseError> {
+        let object = self.resumable_object(upload_id, tenant_id).await?;
+        self.staged_len(&object).await
+    }
+
+    /// Receive a chunk of a resumable upload, committing it once complete
+    ///
+    /// The chunk must start at the upload's offset. Bytes received before the
+    /// body breaks off stay staged, so the client can read the offset
+    /// ([`Self::upload_offset`]) and send the rest. Once the staged bytes
+    /// reach `chunk.total_size` the whole content is hashed, checked against
+    /// `chunk.expected_hash` and committed.
+    #[tracing::instrument(
+        name = "UploadObjectUseCase::resume",
+        level = "debug",
+        skip_all,
+        fields(upload_id = %upload_id)
+    )]
+    pub async fn resume(
+        &self,
+        upload_id: &ObjectId,
+        tenant_id: &TenantId,
+        chunk: UploadChunk,
+        reader: BlobReader,
+    ) -> Result<ResumeOutcome, ObjectUseCaseError> {
+        let object = self.resumable_object(upload_id, tenant_id).await?;
+        let storage_class = object.storage_class();
+        if let Some(total_size) = chunk.total_size {
+            Object::check_size(total_size, self.max_object_size_bytes)?;
+        }
+
+        // 1. One chunk at a time, so appends cannot interleave
+        let _receiving = ReceivingChunk::claim(&self.receiving, upload_id)?;
+        let staged = self.staged_len(&object).await?;
+
+        // 2. Append the chunk to the staged bytes
+        let offset = match chunk.range {
+            Some((start, end)) => {
+                if start != staged {
+                    return Err(Self::offset_conflict(staged));
+                }
+                if chunk.total_size.is_some_and(|total_size| end >= total_size) {
+                    return Err(ObjectUseCaseError::InvalidRequest(
+                        "Content-Range ends past the total size".to_string(),
+                    ));
+                }
+
+                let reader = if start == 0 {
+                    self.check_content_policy(&Self::resumed_request(&object), reader)
+                        .await?
+                } else {
+                    reader
+                };
+                // Bytes past the end of the range are ignored
+                let reader: BlobReader = Box::pin(UploadGuard::new(
+                    Box::pin(reader.take(end - start + 1)),
+                    self.max_object_size_bytes.saturating_sub(start),
+                    None,
+                ));
+                match self
+                    .blob_store_for(&object)
+                    .append_staged(*upload_id.as_uuid(), start, reader, storage_class)
+                    .await
+                {
+                    Ok(offset) => offset,
+                    Err(StorageError::OffsetMismatch { staged, .. }) => {
+                        return Err(Self::offset_conflict(staged));
+                    }
+                    Err(e) => {
+                        return Err(upload_guard::violation(&e)
+                            .map_or(ObjectUseCaseError::Storage(e), ObjectUseCaseError::Domain));
+                    }
+                }
+            }
+            None => staged,
+        };
+
+        // 3. Commit once every byte has arrived
+        match chunk.total_size {
+            Some(total_size) if offset == total_size => self
+                .commit_resumable(object, chunk.expected_hash)
+                .await
+                .map(ResumeOutcome::Committed),
+            _ => Ok(ResumeOutcome::Incomplete(ResumableUploadDto {
+                upload_id: upload_id.to_string(),
+                offset,
+            })),
+        }
+    }
 
-        // 4. Write blob to storage (computes hash during write)
-        let (content_hash, size_bytes) = self.blob_store.write(reader, storage_class).await?;
+    /// Move a fully received upload into blob storage and commit its object
+    ///
+    /// Content that does not hash to `expected_hash` is discarded; the
+    /// reservation is left to the stuck upload collector.
+    async fn commit_resumable(
+        &self,
+        object: Object,
+        expected_hash: Option<ContentHash>,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        let upload_id = *object.id().as_uuid();
+        let storage_class = object.storage_class();
+        let blob_store = self.blob_store_for(&object);
+        let pending = PendingUpload {
+            status_watch: self.status_watch.as_deref(),
+            object_id: *object.id(),
+            committed: false,
+        };
 
-        // 5. Get or create blob entry with ref counting
+        // 1. Hash the staged content while copying it into blob storage
+        let staged = blob_store.read_staged(upload_id, storage_class).await?;
+        let reader: BlobReader = Box::pin(UploadGuard::new(
+            staged,
+            self.max_object_size_bytes,
+            expected_hash,
+        ));
+        let (content_hash, size_bytes) = match self.write_blob(&object, reader).await {
+            Err(e @ ObjectUseCaseError::Domain(DomainError::ContentHashMismatch { .. })) => {
+                Self::discard_staged(blob_store.as_ref(), upload_id, storage_class).await;
+                return Err(e);
+            }
+            result => result?,
+        };
         self.blob_repo
             .get_or_create(&content_hash, storage_class, size_bytes)
             .await?;
 
-        // 6. Commit: update object state to COMMITTED
-        object.commit(&content_hash, size_bytes)?;
-        self.object_repo.save(&object).await?;
+        // 2. Scan, stage, commit and index the content
+        let dto = match self.finish(object, content_hash, size_bytes, pending).await {
+            Err(e @ ObjectUseCaseError::ContentRejected(_)) => {
+                Self::discard_staged(blob_store.as_ref(), upload_id, storage_class).await;
+                return Err(e);
+            }
+            result => result?,
+        };
+        Self::discard_staged(blob_store.as_ref(), upload_id, storage_class).await;
+
+        Ok(dto)
+    }
+
+    /// The tenant's reserved object for a resumable upload
+    async fn resumable_object(
+        &self,
+        upload_id: &ObjectId,
+        tenant_id: &TenantId,
+    ) -> Result<Object, ObjectUseCaseError> {
+        self.object_repo
+            .find_writing(upload_id)
+            .await?
+            .filter(|object| object.tenant_id() == tenant_id)
+            .ok_or_else(|| ObjectUseCaseError::NotFound(format!("Upload {upload_id}")))
+    }
+
+    /// Bytes staged for a resumable upload; uploads sent in one request
+    /// have nothing staged under their ID
+    async fn staged_len(&self, object: &Object) -> Result<u64, ObjectUseCaseError> {
+        self.blob_store_for(object)
+            .staged_len(*object.id().as_uuid(), object.storage_class())
+            .await?
+            .ok_or_else(|| ObjectUseCaseError::NotFound(format!("Upload {}", object.id())))
+    }
+
+    /// The upload request a resumable upload was started with
+    fn resumed_request(object: &Object) -> UploadRequest {
+        UploadRequest {
+            namespace: object.namespace().to_string(),
+            tenant_id: object.tenant_id().to_string(),
+            key: object.key().map(str::to_string),
+            storage_class: Some(object.storage_class()),
+            content_type: object.content_type().map(str::to_string),
+            content_encoding: object.content_encoding(),
+            expected_hash: None,
+            idempotency_key: None,
+        }
+    }
+
+    fn offset_conflict(staged: u64) -> ObjectUseCaseError {
+        ObjectUseCaseError::Conflict(format!(
+            "Upload has {staged} bytes; resume from offset {staged}"
+        ))
+    }
+
+    async fn discard_staged(
+        blob_store: &dyn BlobStore,
+        upload_id: uuid::Uuid,
+        storage_class: StorageClass,
+    ) {
+        if let Err(e) = blob_store.discard_staged(upload_id, storage_class).await {
+            tracing::warn!(%upload_id, "Failed to discard staged upload: {}", e);
+        }
+    }
+
+    /// Overwrite a committed object's content if it still has `expected` content
+    async fn replace(
+        &self,
+        mut object: Object,
+        expected: ContentHash,
+        reader: BlobReader,
+        content_type: Option<String>,
+        content_encoding: Option<ContentEncoding>,
+    ) -> Result<ObjectDto, ObjectUseCaseError> {
+        let storage_class = object.storage_class();
+        // Locked objects keep their content; refuse before writing a blob
+        object.ensure_unlocked()?;
+
+        // 1. Write the new blob and take a reference on it
+        let (content_hash, size_bytes) = self.write_blob(&object, reader).await?;
+        Object::check_size(size_bytes, self.max_object_size_bytes)?;
+        self.blob_repo
+            .get_or_create(&content_hash, storage_class, size_bytes)
+            .await?;
+        self.scan_content(&object, &content_hash, size_bytes)
+            .await?;
+
+        // 2. Compare-and-swap the object's content
+        object.set_content_encoding(content_encoding);
+        object.replace_content(&content_hash, size_bytes)?;
+        if !self
+            .object_repo
+            .replace_content_if_match(&object, &expected)
+            .await?
+        {
+            // Lost the race: release the new reference (GC reclaims the blob)
+            self.blob_repo.decrement_ref(&content_hash).await?;
+            return Err(ObjectUseCaseError::PreconditionFailed(
+                "Object was modified concurrently".to_string(),
+            ));
+        }
+
+        // 3. Release the reference held by the replaced content
+        self.blob_repo.decrement_ref(&expected).await?;
+
+        // 4. Re-index extracted text, clearing text of the replaced content
+        if self.text_extractor.is_some() {
+            let content_type = content_type.as_deref().or(object.content_type());
+            let text = self
+                .extract_text(
+                    content_type,
+                    content_encoding,
+                    &content_hash,
+                    size_bytes,
+                    storage_class,
+                )
+                .await;
+            self.store_extracted_text(object.id(), text).await;
+        }
+
+        Ok(self.dto(object).await)
+    }
+
+    /// Response for a stored object, with its short hash when short hashes
+    /// are on
+    ///
+    /// The object is already stored, so a failed short hash lookup only
+    /// leaves the short hash out.
+    async fn dto(&self, object: Object) -> ObjectDto {
+        let short_hash = self
+            .short_hashes
+            .short_hash(object.content_hash())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(object_id = %object.id(), "Failed to find short hash: {}", e);
+                None
+            });
+        ObjectDto {
+            short_hash,
+            ..ObjectDto::from(object)
+        }
+    }
+
+    /// Take a reference on the tenant's existing blob for a client-supplied hash
+    ///
+    /// Only content the tenant already stores qualifies, so knowing a hash is
+    /// not enough to obtain someone else's content. Without a match the
+    /// caller streams the body, which is then verified against the hash.
+    ///
+    /// The body is never read, so the content policy is checked against the
+    /// stored blob instead; the scan runs on it before staging, as for
+    /// streamed content. A rejection releases the reference.
+    async fn reference_existing_blob(
+        &self,
+        object: &Object,
+        content_hash: &ContentHash,
+    ) -> Result<Option<(ContentHash, u64)>, ObjectUseCaseError> {
+        let Some(blob) = self
+            .blob_repo
+            .reference_existing(content_hash, object.storage_class(), object.tenant_id())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let checked = match Object::check_size(blob.size_bytes(), self.max_object_size_bytes) {
+            Ok(()) => self.check_stored_content(object, content_hash).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = checked {
+            self.blob_repo.decrement_ref(content_hash).await?;
+            return Err(e);
+        }
+        tracing::debug!(content_hash = %content_hash, "Deduplicated upload without reading its content");
+
+        Ok(Some((content_hash.clone(), blob.size_bytes())))
+    }
+
+    /// Enforce the content policy before anything is stored
+    ///
+    /// The first bytes are read to sniff the content's real type, decoded
+    /// first if the content is compressed; the returned reader yields the
+    /// full content again.
+    async fn check_content_policy(
+        &self,
+        request: &UploadRequest,
+        mut reader: BlobReader,
+    ) -> Result<BlobReader, ObjectUseCaseError> {
+        if self.content_policy.is_empty() {
+            return Ok(reader);
+        }
+
+        let (prefix, sniffed_type) = sniff_prefix(&mut reader, request.content_encoding).await?;
 
-        // 7. Return DTO
-        Ok(ObjectDto::from(object))
+        self.content_policy
+            .check(
+                &request.namespace,
+                request.key.as_deref(),
+                request.content_type.as_deref(),
+                sniffed_type,
+            )
+            .map_err(ObjectUseCaseError::InvalidRequest)?;
+
+        Ok(Box::pin(Cursor::new(prefix).chain(reader)))
+    }
+
+    /// Check an object about to be created over an already stored blob as
+    /// an upload of the same content would be checked
+    ///
+    /// Runs the namespace's key policy and the content policy, sniffing the
+    /// blob's first bytes; for operations that reference existing content
+    /// instead of uploading it.
+    pub async fn check_stored_content(
+        &self,
+        object: &Object,
+        content_hash: &ContentHash,
+    ) -> Result<(), ObjectUseCaseError> {
+        let config = self.namespace_config(object.namespace()).await?;
+        if let (Some(key), Some(policy)) =
+            (object.key(), config.as_ref().and_then(|c| c.key_policy()))
+        {
+            policy.check(key)?;
+        }
+
+        if self.content_policy.is_empty() {
+            return Ok(());
+        }
+        let mut reader = self
+            .blob_store_for(object)
+            .read(content_hash, object.storage_class())
+            .await?;
+        let (_, sniffed_type) = sniff_prefix(&mut reader, object.content_encoding()).await?;
+
+        self.content_policy
+            .check(
+                object.namespace().as_str(),
+                object.key(),
+                object.content_type(),
+                sniffed_type,
+            )
+            .map_err(ObjectUseCaseError::InvalidRequest)
+    }
+
+    /// Run the content scanner over a written blob before it commits
+    ///
+    /// A rejection releases the upload's reference on the blob, leaving it
+    /// to GC unless other objects share it. Scanner failures fail the upload
+    /// unless the scanner is configured to fail open.
+    async fn scan_content(
+        &self,
+        object: &Object,
+        content_hash: &ContentHash,
+        size_bytes: u64,
+    ) -> Result<(), ObjectUseCaseError> {
+        let Some(scanner) = &self.content_scanner else {
+            return Ok(());
+        };
+        let subject = ScanSubject {
+            namespace: object.namespace().to_string(),
+            tenant_id: object.tenant_id().to_string(),
+            key: object.key().map(str::to_string),
+            content_type: object.content_type().map(str::to_string),
+            content_hash: content_hash.to_string(),
+            size_bytes,
+        };
+
+        let reader = self
+            .blob_store_for(object)
+            .read(content_hash, object.storage_class())
+            .await?;
+        match scanner.scan(&subject, reader).await {
+            Ok(ScanVerdict::Clean) => Ok(()),
+            Ok(ScanVerdict::Rejected(reason)) => {
+                tracing::warn!(object_id = %object.id(), content_hash = %content_hash, "Upload rejected by content scan: {}", reason);
+                self.blob_repo.decrement_ref(content_hash).await?;
+                Err(ObjectUseCaseError::ContentRejected(reason))
+            }
+            Err(e) if self.scan_fail_open => {
+                tracing::warn!(object_id = %object.id(), content_hash = %content_hash, "Committing unscanned upload: {}", e);
+                Ok(())
+            }
+            Err(e) => {
+                self.blob_repo.decrement_ref(content_hash).await?;
+                Err(ObjectUseCaseError::ScanFailed(e.to_string()))
+            }
+        }
+    }
+
+    /// Store that takes the blobs of `object`
+    fn blob_store_for(&self, object: &Object) -> Arc<dyn BlobStore> {
+        match &self.blob_router {
+            Some(router) => router.store_for(object.namespace(), object.tenant_id()),
+            None => Arc::clone(&self.blob_store),
+        }
+    }
+
+    /// Stream guarded content to the store for `object`
+    ///
+    /// A size or hash violation is reported as the domain error, not as I/O.
+    async fn write_blob(
+        &self,
+        object: &Object,
+        reader: BlobReader,
+    ) -> Result<(ContentHash, u64), ObjectUseCaseError> {
+        self.blob_store_for(object)
+            .write(reader, object.storage_class())
+            .await
+            .map_err(|e| {
+                upload_guard::violation(&e)
+                    .map_or(ObjectUseCaseError::Storage(e), ObjectUseCaseError::Domain)
+            })
+    }
+
+    /// Read committed content back and run the text extractor over it
+    ///
+    /// Returns `None` when no extractor is configured, the content type is
+    /// unsupported or the content is too large; extraction errors are logged.
+    /// Encoded content is decoded first, and skipped if it decodes to more
+    /// than the size limit.
+    async fn extract_text(
+        &self,
+        content_type: Option<&str>,
+        content_encoding: Option<ContentEncoding>,
+        content_hash: &ContentHash,
+        size_bytes: u64,
+        storage_class: StorageClass,
+    ) -> Option<String> {
+        let extractor = self.text_extractor.as_ref()?;
+        // Drop parameters such as "; charset=utf-8"
+        let mime = content_type?.split(';').next()?.trim().to_ascii_lowercase();
+        if !extractor.supports(&mime) || size_bytes > self.text_extraction_max_bytes {
+            return None;
+        }
+
+        let result: Result<Option<String>, String> = async {
+            let mut reader = self
+                .blob_store
+                .read(content_hash, storage_class)
+                .await
+                .map_err(|e| e.to_string())?;
+            if let Some(encoding) = content_encoding {
+                reader = content_encoding::decode(reader, encoding);
+            }
+            let mut content = Vec::with_capacity(size_bytes as usize);
+            (&mut reader)
+                .take(self.text_extraction_max_bytes + 1)
+                .read_to_end(&mut content)
+                .await
+                .map_err(|e| e.to_string())?;
+            if content.len() as u64 > self.text_extraction_max_bytes {
+                return Ok(None);
+            }
+            extractor
+                .extract(&mime, &content)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        .await;
+
+        result.unwrap_or_else(|e| {
+            tracing::warn!(content_hash = %content_hash, content_type = %mime, "Text extraction skipped: {}", e);
+            None
+        })
+    }
+
+    async fn store_extracted_text(&self, id: &ObjectId, text: Option<String>) {
+        if let Err(e) = self.object_repo.set_extracted_text(id, text).await {
+            tracing::warn!(object_id = %id, "Failed to store extracted text: {}", e);
+        }
+    }
+}
+
+/// Read the first bytes of content and sniff their type, decoding them
+/// first when the content is compressed
+async fn sniff_prefix(
+    reader: &mut BlobReader,
+    encoding: Option<ContentEncoding>,
+) -> Result<(Vec<u8>, Option<&'static str>), StorageError> {
+    let mut prefix = Vec::with_capacity(SNIFF_PREFIX_BYTES);
+    reader
+        .take(SNIFF_PREFIX_BYTES as u64)
+        .read_to_end(&mut prefix)
+        .await?;
+    let sniffed_type = match encoding {
+        Some(encoding) => sniff_content_type(
+            &content_encoding::decode_prefix(&prefix, encoding, SNIFF_PREFIX_BYTES).await,
+        ),
+        None => sniff_content_type(&prefix),
+    };
+
+    Ok((prefix, sniffed_type))
+}
+
+/// A reservation that lost its key to another object, under the configured
+/// key uniqueness scope, becomes `AlreadyExists`
+pub(super) fn reservation_error(object: &Object, e: RepositoryError) -> ObjectUseCaseError {
+    match object.key() {
+        Some(key) if e.is_unique_violation() => {
+            DomainError::AlreadyExists(format!("'{key}'")).into()
+        }
+        _ => e.into(),
+    }
+}
+
+/// A reserved upload whose waiters hear `FAILED` unless it commits
+///
+/// Publishing on drop also covers uploads abandoned mid-way, e.g. by a
+/// request timeout.
+struct PendingUpload<'a> {
+    status_watch: Option<&'a StatusWatch>,
+    object_id: ObjectId,
+    committed: bool,
+}
+
+impl PendingUpload<'_> {
+    fn committed(mut self) {
+        self.committed = true;
+        if let Some(status_watch) = self.status_watch {
+            status_watch.publish(&self.object_id, UploadStatus::Committed);
+        }
+    }
+}
+
+impl Drop for PendingUpload<'_> {
+    fn drop(&mut self) {
+        if let (false, Some(status_watch)) = (self.committed, self.status_watch) {
+            status_watch.publish(&self.object_id, UploadStatus::Failed);
+        }
+    }
+}
+
+/// A chunk of a resumable upload being received
+struct ReceivingChunk<'a> {
+    receiving: &'a DashSet<ObjectId>,
+    upload_id: ObjectId,
+}
+
+impl<'a> ReceivingChunk<'a> {
+    fn claim(
+        receiving: &'a DashSet<ObjectId>,
+        upload_id: &ObjectId,
+    ) -> Result<Self, ObjectUseCaseError> {
+        if !receiving.insert(*upload_id) {
+            return Err(ObjectUseCaseError::Conflict(
+                "Another chunk of this upload is being received".to_string(),
+            ));
+        }
+        Ok(Self {
+            receiving,
+            upload_id: *upload_id,
+        })
+    }
+}
+
+impl Drop for ReceivingChunk<'_> {
+    fn drop(&mut self) {
+        self.receiving.remove(&self.upload_id);
     }
 }
 
@@ -85,8 +1254,14 @@ impl UploadObjectUseCase {
 mod tests {
     use super::*;
 
-    use crate::application::ports::{MockBlobRepository, MockBlobStore, MockObjectRepository};
+    use crate::application::ports::{
+        MockBlobRepository, MockBlobRouter, MockBlobStore, MockContentScanner,
+        MockIdempotencyRepository, MockNamespaceConfigRepository, MockObjectRepository,
+        MockTextExtractor, ScanError,
+    };
+    use crate::domain::entities::KeyPolicy;
     use crate::domain::value_objects::{ContentHash, ObjectStatus, StorageClass};
+    use futures_util::FutureExt;
     use std::io::Cursor;
     use std::str::FromStr;
     use std::sync::Arc;
@@ -103,6 +1278,10 @@ mod tests {
             tenant_id: "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_string(),
             key: Some("test-key".to_string()),
             storage_class: Some(StorageClass::Hot),
+            content_type: None,
+            content_encoding: None,
+            expected_hash: None,
+            idempotency_key: None,
         };
         let reader = Box::pin(Cursor::new("test data"));
 
@@ -112,7 +1291,7 @@ mod tests {
         // Expectations
         mock_object_repo
             .expect_save()
-            .times(2)
+            .times(3)
             .returning(|_| Ok(()));
         mock_blob_store
             .expect_write()
@@ -146,10 +1325,895 @@ mod tests {
         assert_eq!(dto.size_bytes, Some(size_bytes));
     }
 
-    #[test]
-    fn test_upload_limit_is_configurable() {
-        let mock_object_repo = MockObjectRepository::new();
-        let mock_blob_repo = MockBlobRepository::new();
+    #[tokio::test]
+    async fn test_upload_written_to_routed_store() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_get_or_create()
+            .returning(|hash, class, size| {
+                Ok(crate::domain::entities::Blob::new(
+                    hash.clone(),
+                    class,
+                    size,
+                ))
+            });
+        let mut default_store = MockBlobStore::new();
+        default_store.expect_write().never();
+        let mut routed_store = MockBlobStore::new();
+        routed_store
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Ok((ContentHash::from_str(&"a".repeat(64)).unwrap(), 9)));
+        let routed_store: Arc<dyn BlobStore> = Arc::new(routed_store);
+        let mut router = MockBlobRouter::new();
+        router
+            .expect_store_for()
+            .withf(|namespace, _| namespace.as_str() == "images")
+            .returning(move |_, _| Arc::clone(&routed_store));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(default_store),
+        )
+        .with_blob_router(Arc::new(router));
+        let request = UploadRequest {
+            namespace: "images".to_string(),
+            tenant_id: "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_string(),
+            key: Some("cat.png".to_string()),
+            storage_class: Some(StorageClass::Hot),
+            content_type: None,
+            content_encoding: None,
+            expected_hash: None,
+            idempotency_key: None,
+        };
+
+        let dto = use_case
+            .execute(request, Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+        assert_eq!(dto.status, ObjectStatus::Committed);
+    }
+
+    #[tokio::test]
+    async fn test_failed_upload_wakes_status_waiters() {
+        // Arrange: wait on the object as soon as it is reserved
+        let status_watch = Arc::new(StatusWatch::new());
+        let subscription = Arc::new(std::sync::Mutex::new(None));
+        let mut mock_object_repo = MockObjectRepository::new();
+        let (watch, waiter) = (Arc::clone(&status_watch), Arc::clone(&subscription));
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(move |object| {
+                *waiter.lock().unwrap() = Some(watch.subscribe(*object.id()));
+                Ok(())
+            });
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Err(StorageError::Internal("disk full".to_string())));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(mock_blob_store),
+        )
+        .with_status_watch(Arc::clone(&status_watch));
+
+        // Act
+        let result = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await;
+
+        // Assert
+        assert!(result.is_err());
+        let mut subscription = subscription.lock().unwrap().take().unwrap();
+        assert_eq!(
+            subscription
+                .wait(std::time::Duration::from_millis(10))
+                .await,
+            Some(UploadStatus::Failed)
+        );
+    }
+
+    fn writing_object() -> Object {
+        let request = keyed_request();
+        let (namespace, tenant_id) =
+            validate_namespace_and_tenant(&request.namespace, &request.tenant_id).unwrap();
+        Object::new(namespace, tenant_id, request.key, StorageClass::Hot)
+    }
+
+    fn resumable_use_case(
+        object: &Object,
+        mock_blob_store: MockBlobStore,
+    ) -> (UploadObjectUseCase, ObjectId, TenantId) {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let writing = object.clone();
+        mock_object_repo
+            .expect_find_writing()
+            .returning(move |_| Ok(Some(writing.clone())));
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(mock_blob_store),
+        );
+        (use_case, *object.id(), object.tenant_id().clone())
+    }
+
+    #[tokio::test]
+    async fn test_resume_appends_chunk_at_offset() {
+        let object = writing_object();
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_staged_len()
+            .returning(|_, _| Ok(Some(5)));
+        mock_blob_store
+            .expect_append_staged()
+            .withf(|_, offset, _, _| *offset == 5)
+            .times(1)
+            .returning(|_, offset, _, _| Ok(offset + 4));
+        let (use_case, upload_id, tenant_id) = resumable_use_case(&object, mock_blob_store);
+
+        let chunk = UploadChunk {
+            range: Some((5, 8)),
+            total_size: Some(20),
+            expected_hash: None,
+        };
+        let outcome = use_case
+            .resume(&upload_id, &tenant_id, chunk, Box::pin(Cursor::new("data")))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            outcome,
+            ResumeOutcome::Incomplete(ResumableUploadDto { offset: 9, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resume_rejects_chunk_at_wrong_offset() {
+        let object = writing_object();
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_staged_len()
+            .returning(|_, _| Ok(Some(5)));
+        mock_blob_store.expect_append_staged().never();
+        let (use_case, upload_id, tenant_id) = resumable_use_case(&object, mock_blob_store);
+
+        let chunk = UploadChunk {
+            range: Some((0, 8)),
+            total_size: None,
+            expected_hash: None,
+        };
+        let result = use_case
+            .resume(
+                &upload_id,
+                &tenant_id,
+                chunk,
+                Box::pin(Cursor::new("test data")),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resume_discards_upload_with_wrong_hash() {
+        let object = writing_object();
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_staged_len()
+            .returning(|_, _| Ok(Some(9)));
+        mock_blob_store
+            .expect_read_staged()
+            .returning(|_, _| Ok(Box::pin(Cursor::new("test data"))));
+        mock_blob_store
+            .expect_write()
+            .times(1)
+            .returning(|mut reader, _| {
+                let mut content = Vec::new();
+                reader
+                    .read_to_end(&mut content)
+                    .now_or_never()
+                    .expect("in-memory reader is always ready")?;
+                Ok((
+                    ContentHash::from_str(&"a".repeat(64)).unwrap(),
+                    content.len() as u64,
+                ))
+            });
+        mock_blob_store
+            .expect_discard_staged()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let (use_case, upload_id, tenant_id) = resumable_use_case(&object, mock_blob_store);
+
+        // All bytes are staged; an empty chunk completes the upload
+        let chunk = UploadChunk {
+            range: None,
+            total_size: Some(9),
+            expected_hash: Some(ContentHash::from_str(&"b".repeat(64)).unwrap()),
+        };
+        let result = use_case
+            .resume(&upload_id, &tenant_id, chunk, Box::pin(Cursor::new("")))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(
+                DomainError::ContentHashMismatch { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resume_of_other_tenants_upload_is_not_found() {
+        let object = writing_object();
+        let (use_case, upload_id, _) = resumable_use_case(&object, MockBlobStore::new());
+
+        let chunk = UploadChunk {
+            range: Some((0, 3)),
+            total_size: None,
+            expected_hash: None,
+        };
+        let result = use_case
+            .resume(
+                &upload_id,
+                &TenantId::new(uuid::Uuid::new_v4()),
+                chunk,
+                Box::pin(Cursor::new("data")),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::NotFound(_))));
+    }
+
+    fn keyed_request() -> UploadRequest {
+        UploadRequest {
+            namespace: "test-namespace".to_string(),
+            tenant_id: "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_string(),
+            key: Some("test-key".to_string()),
+            storage_class: Some(StorageClass::Hot),
+            content_type: None,
+            content_encoding: None,
+            expected_hash: None,
+            idempotency_key: None,
+        }
+    }
+
+    fn committed_object(content_hash: &ContentHash) -> Object {
+        let request = keyed_request();
+        let (namespace, tenant_id) =
+            validate_namespace_and_tenant(&request.namespace, &request.tenant_id).unwrap();
+        let mut object = Object::new(namespace, tenant_id, request.key, StorageClass::Hot);
+        object.commit(content_hash, 9).unwrap();
+        object
+    }
+
+    fn blob_for(content_hash: &ContentHash) -> crate::domain::entities::Blob {
+        crate::domain::entities::Blob::new(content_hash.clone(), StorageClass::Hot, 9)
+    }
+
+    /// Blob store that drains the (guarded) reader like a real store would
+    fn draining_blob_store() -> MockBlobStore {
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_write()
+            .times(1)
+            .returning(|mut reader, _| {
+                let mut content = Vec::new();
+                reader
+                    .read_to_end(&mut content)
+                    .now_or_never()
+                    .expect("in-memory reader is always ready")?;
+                Ok((
+                    ContentHash::from_str(&"a".repeat(64)).unwrap(),
+                    content.len() as u64,
+                ))
+            });
+        mock_blob_store
+    }
+
+    #[tokio::test]
+    async fn test_upload_over_limit_fails_before_commit() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        // Only the WRITING reservation is saved
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_blob_repo.expect_get_or_create().never();
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(draining_blob_store()),
+        )
+        .with_max_object_size_bytes(4);
+
+        let result = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(
+                DomainError::SizeExceedsMaximum { max: 4, .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_hash_mismatch_fails_before_commit() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_blob_repo.expect_get_or_create().never();
+        mock_blob_repo
+            .expect_reference_existing()
+            .returning(|_, _, _| Ok(None));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(draining_blob_store()),
+        );
+        let request = UploadRequest {
+            expected_hash: Some(ContentHash::from_str(&"b".repeat(64)).unwrap()),
+            ..keyed_request()
+        };
+
+        let result = use_case
+            .execute(request, Box::pin(Cursor::new("test data")))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(
+                DomainError::ContentHashMismatch { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_matching_hash_commits() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        mock_object_repo
+            .expect_save()
+            .times(3)
+            .returning(|_| Ok(()));
+        mock_blob_repo
+            .expect_get_or_create()
+            .times(1)
+            .returning(|hash, _, _| Ok(blob_for(hash)));
+        mock_blob_repo
+            .expect_reference_existing()
+            .returning(|_, _, _| Ok(None));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(draining_blob_store()),
+        );
+        // sha256("test data")
+        let request = UploadRequest {
+            expected_hash: Some(
+                ContentHash::from_str(
+                    "916f0027a575074ce72a331777c3478d6513f786a591bd892da1a577bf2335f9",
+                )
+                .unwrap(),
+            ),
+            ..keyed_request()
+        };
+
+        let dto = use_case
+            .execute(request, Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+
+        assert_eq!(dto.status, ObjectStatus::Committed);
+        assert_eq!(dto.size_bytes, Some(9));
+    }
+
+    /// Request claiming content the tenant already stores under hash "a…"
+    fn known_hash_request() -> UploadRequest {
+        UploadRequest {
+            expected_hash: Some(ContentHash::from_str(&"a".repeat(64)).unwrap()),
+            ..keyed_request()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_known_hash_skips_content() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+
+        mock_object_repo
+            .expect_save()
+            .times(3)
+            .returning(|_| Ok(()));
+        mock_blob_repo
+            .expect_reference_existing()
+            .times(1)
+            .returning(|hash, _, _| Ok(Some(blob_for(hash))));
+        mock_blob_repo.expect_get_or_create().never();
+        mock_blob_store.expect_write().never();
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        );
+
+        // The body does not match the claimed hash, but is never read
+        let dto = use_case
+            .execute(known_hash_request(), Box::pin(Cursor::new("unread")))
+            .await
+            .unwrap();
+
+        assert_eq!(dto.status, ObjectStatus::Committed);
+        assert_eq!(dto.content_hash, Some("a".repeat(64)));
+        assert_eq!(dto.size_bytes, Some(9));
+    }
+
+    #[tokio::test]
+    async fn test_upload_known_hash_over_limit_releases_reference() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_blob_repo
+            .expect_reference_existing()
+            .returning(|hash, _, _| Ok(Some(blob_for(hash))));
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(1));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(MockBlobStore::new()),
+        )
+        .with_max_object_size_bytes(4);
+
+        let result = use_case
+            .execute(known_hash_request(), Box::pin(Cursor::new("")))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(
+                DomainError::SizeExceedsMaximum { max: 4, .. }
+            ))
+        ));
+    }
+
+    fn executable_blocklist() -> ContentPolicy {
+        ContentPolicy::parse("", "*=application/x-msdownload,.exe").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_disguised_executable() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store.expect_write().never();
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(mock_blob_store),
+        )
+        .with_content_policy(executable_blocklist());
+        let request = UploadRequest {
+            key: Some("cat.jpg".to_string()),
+            content_type: Some("image/jpeg".to_string()),
+            ..keyed_request()
+        };
+
+        let result = use_case
+            .execute(request, Box::pin(Cursor::new(b"MZ\x90\x00\x03".to_vec())))
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_without_content_type_rejected_when_required() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().never();
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store.expect_write().never();
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(mock_blob_store),
+        )
+        .with_content_policy(ContentPolicy::default().with_required_type(true));
+
+        let result = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_content_survives_sniffing() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+
+        mock_object_repo
+            .expect_save()
+            .times(3)
+            .returning(|_| Ok(()));
+        mock_blob_repo
+            .expect_get_or_create()
+            .times(1)
+            .returning(|hash, _, _| Ok(blob_for(hash)));
+        mock_blob_repo
+            .expect_reference_existing()
+            .returning(|_, _, _| Ok(None));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(draining_blob_store()),
+        )
+        .with_content_policy(executable_blocklist());
+        // sha256("test data")
+        let request = UploadRequest {
+            expected_hash: Some(
+                ContentHash::from_str(
+                    "916f0027a575074ce72a331777c3478d6513f786a591bd892da1a577bf2335f9",
+                )
+                .unwrap(),
+            ),
+            ..keyed_request()
+        };
+
+        let dto = use_case
+            .execute(request, Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+
+        assert_eq!(dto.size_bytes, Some(9));
+    }
+
+    #[tokio::test]
+    async fn test_upload_known_hash_checks_stored_content() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+
+        // Only the WRITING reservation is saved
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_blob_repo
+            .expect_reference_existing()
+            .times(1)
+            .returning(|hash, _, _| Ok(Some(blob_for(hash))));
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(1));
+        mock_blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"MZ\x90\x00\x03".to_vec()))));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_content_policy(executable_blocklist());
+        // A harmless body does not vouch for the stored executable
+        let request = UploadRequest {
+            key: Some("cat.jpg".to_string()),
+            content_type: Some("image/jpeg".to_string()),
+            ..known_hash_request()
+        };
+
+        let result = use_case
+            .execute(request, Box::pin(Cursor::new("test data")))
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_stages_content_before_commit() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut seq = mockall::Sequence::new();
+
+        // Reserve, then stage the written content, then commit
+        mock_object_repo
+            .expect_save()
+            .withf(|object| object.content_hash().is_none())
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+        mock_object_repo
+            .expect_save()
+            .withf(|object| object.staged_content().is_some_and(|(_, size)| size == 9))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+        mock_object_repo
+            .expect_save()
+            .withf(|object| object.status() == ObjectStatus::Committed)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+        mock_blob_repo
+            .expect_get_or_create()
+            .times(1)
+            .returning(|hash, _, _| Ok(blob_for(hash)));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(draining_blob_store()),
+        );
+
+        let dto = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+
+        assert_eq!(dto.status, ObjectStatus::Committed);
+    }
+
+    #[tokio::test]
+    async fn test_if_none_match_rejects_existing_key() {
+        // Arrange: blob store must not be touched
+        let mut mock_object_repo = MockObjectRepository::new();
+        let existing = committed_object(&ContentHash::from_str(&"a".repeat(64)).unwrap());
+        mock_object_repo
+            .expect_find_by_key()
+            .times(1)
+            .returning(move |_, _, _| Ok(Some(existing.clone())));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        // Act
+        let result = use_case
+            .execute_with_precondition(
+                keyed_request(),
+                Box::pin(Cursor::new("test data")),
+                UploadPrecondition::IfNoneMatch,
+            )
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::PreconditionFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_taken_key_is_already_exists() {
+        // Arrange: the key's unique index rejects the reservation
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().times(1).returning(|_| {
+            Err(RepositoryError::ConstraintViolation(
+                "unique_key_per_tenant_scope".to_string(),
+            ))
+        });
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        // Act
+        let result = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(DomainError::AlreadyExists(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_if_match_rejects_stale_etag() {
+        // Arrange
+        let mut mock_object_repo = MockObjectRepository::new();
+        let existing = committed_object(&ContentHash::from_str(&"a".repeat(64)).unwrap());
+        mock_object_repo
+            .expect_find_by_key()
+            .returning(move |_, _, _| Ok(Some(existing.clone())));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        // Act
+        let stale = ContentHash::from_str(&"c".repeat(64)).unwrap();
+        let result = use_case
+            .execute_with_precondition(
+                keyed_request(),
+                Box::pin(Cursor::new("test data")),
+                UploadPrecondition::IfMatch(Some(stale)),
+            )
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::PreconditionFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_if_match_replaces_content_and_releases_old_blob() {
+        // Arrange
+        let old_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+        let new_hash = ContentHash::from_str(&"b".repeat(64)).unwrap();
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+
+        let existing = committed_object(&old_hash);
+        let existing_id = *existing.id();
+        mock_object_repo
+            .expect_find_by_key()
+            .returning(move |_, _, _| Ok(Some(existing.clone())));
+        let written = new_hash.clone();
+        mock_blob_store
+            .expect_write()
+            .times(1)
+            .returning(move |_, _| Ok((written.clone(), 9)));
+        let created = new_hash.clone();
+        mock_blob_repo
+            .expect_get_or_create()
+            .times(1)
+            .returning(move |_, _, _| Ok(blob_for(&created)));
+        let expected_old = old_hash.clone();
+        mock_object_repo
+            .expect_replace_content_if_match()
+            .withf(move |_, expected| *expected == expected_old)
+            .times(1)
+            .returning(|_, _| Ok(true));
+        let released = old_hash.clone();
+        mock_blob_repo
+            .expect_decrement_ref()
+            .withf(move |hash| *hash == released)
+            .times(1)
+            .returning(|_| Ok(0));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        );
+
+        // Act
+        let dto = use_case
+            .execute_with_precondition(
+                keyed_request(),
+                Box::pin(Cursor::new("new data!")),
+                UploadPrecondition::IfMatch(Some(old_hash)),
+            )
+            .await
+            .unwrap();
+
+        // Assert: same object, new content
+        assert_eq!(dto.id, existing_id.to_string());
+        assert_eq!(dto.content_hash, Some(new_hash.as_hex().to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_if_match_lost_race_releases_new_blob() {
+        // Arrange: object changes between the check and the swap
+        let old_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+        let new_hash = ContentHash::from_str(&"b".repeat(64)).unwrap();
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+
+        let existing = committed_object(&old_hash);
+        mock_object_repo
+            .expect_find_by_key()
+            .returning(move |_, _, _| Ok(Some(existing.clone())));
+        let written = new_hash.clone();
+        mock_blob_store
+            .expect_write()
+            .returning(move |_, _| Ok((written.clone(), 9)));
+        let created = new_hash.clone();
+        mock_blob_repo
+            .expect_get_or_create()
+            .returning(move |_, _, _| Ok(blob_for(&created)));
+        mock_object_repo
+            .expect_replace_content_if_match()
+            .returning(|_, _| Ok(false));
+        let released = new_hash.clone();
+        mock_blob_repo
+            .expect_decrement_ref()
+            .withf(move |hash| *hash == released)
+            .times(1)
+            .returning(|_| Ok(0));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        );
+
+        // Act
+        let result = use_case
+            .execute_with_precondition(
+                keyed_request(),
+                Box::pin(Cursor::new("new data!")),
+                UploadPrecondition::IfMatch(Some(old_hash)),
+            )
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::PreconditionFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_upload_requires_key() {
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(MockObjectRepository::new()),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let request = UploadRequest {
+            key: None,
+            ..keyed_request()
+        };
+
+        let result = use_case
+            .execute_with_precondition(
+                request,
+                Box::pin(Cursor::new("test data")),
+                UploadPrecondition::IfNoneMatch,
+            )
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_upload_limit_is_configurable() {
+        let mock_object_repo = MockObjectRepository::new();
+        let mock_blob_repo = MockBlobRepository::new();
         let mock_blob_store = MockBlobStore::new();
 
         let use_case = UploadObjectUseCase::with_max_upload_size_bytes(
@@ -161,4 +2225,546 @@ mod tests {
 
         assert_eq!(use_case.max_upload_size_bytes(), 4096);
     }
+
+    /// Mocks for a plain upload of `content` that is committed under hash "a…"
+    fn committing_mocks(
+        content: &'static str,
+    ) -> (MockObjectRepository, MockBlobRepository, MockBlobStore) {
+        let content_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+        let size_bytes = content.len() as u64;
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+
+        mock_object_repo
+            .expect_save()
+            .times(3)
+            .returning(|_| Ok(()));
+        let written = content_hash.clone();
+        mock_blob_store
+            .expect_write()
+            .returning(move |_, _| Ok((written.clone(), size_bytes)));
+        mock_blob_store
+            .expect_read()
+            .returning(move |_, _| Ok(Box::pin(Cursor::new(content.as_bytes()))));
+        mock_blob_repo
+            .expect_get_or_create()
+            .returning(move |_, _, _| Ok(blob_for(&content_hash)));
+
+        (mock_object_repo, mock_blob_repo, mock_blob_store)
+    }
+
+    fn text_extractor() -> MockTextExtractor {
+        let mut extractor = MockTextExtractor::new();
+        extractor
+            .expect_supports()
+            .returning(|content_type| content_type == "text/plain");
+        extractor
+            .expect_extract()
+            .returning(|_, content| Ok(Some(String::from_utf8_lossy(content).trim().to_string())));
+        extractor
+    }
+
+    #[tokio::test]
+    async fn test_text_upload_stores_extracted_text() {
+        // Arrange
+        let (mut mock_object_repo, mock_blob_repo, mock_blob_store) =
+            committing_mocks("refund policy 2025\n");
+        mock_object_repo
+            .expect_set_extracted_text()
+            .withf(|_, text| text.as_deref() == Some("refund policy 2025"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_text_extractor(Arc::new(text_extractor()), 1024);
+
+        let request = UploadRequest {
+            content_type: Some("text/plain; charset=utf-8".to_string()),
+            expected_hash: None,
+            ..keyed_request()
+        };
+
+        // Act
+        let dto = use_case
+            .execute(request, Box::pin(Cursor::new("refund policy 2025\n")))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(dto.status, ObjectStatus::Committed);
+        assert_eq!(
+            dto.content_type.as_deref(),
+            Some("text/plain; charset=utf-8")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_binary_upload_skips_extraction() {
+        // Arrange: set_extracted_text must not be called
+        let (mock_object_repo, mock_blob_repo, mock_blob_store) = committing_mocks("\u{0}\u{1}");
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_text_extractor(Arc::new(text_extractor()), 1024);
+
+        let request = UploadRequest {
+            content_type: Some("application/octet-stream".to_string()),
+            expected_hash: None,
+            ..keyed_request()
+        };
+
+        // Act
+        let result = use_case
+            .execute(request, Box::pin(Cursor::new("\u{0}\u{1}")))
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_failed_extraction_does_not_fail_upload() {
+        // Arrange
+        let (mock_object_repo, mock_blob_repo, mock_blob_store) = committing_mocks("text");
+        let mut extractor = MockTextExtractor::new();
+        extractor.expect_supports().returning(|_| true);
+        extractor.expect_extract().returning(|_, _| {
+            Err(crate::application::ports::ExtractionError::Failed(
+                "corrupt".to_string(),
+            ))
+        });
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_text_extractor(Arc::new(extractor), 1024);
+
+        let request = UploadRequest {
+            content_type: Some("text/plain".to_string()),
+            expected_hash: None,
+            ..keyed_request()
+        };
+
+        // Act
+        let result = use_case
+            .execute(request, Box::pin(Cursor::new("text")))
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    fn idempotent_request() -> UploadRequest {
+        UploadRequest {
+            idempotency_key: Some("retry-1".to_string()),
+            ..keyed_request()
+        }
+    }
+
+    fn sha256(content: &str) -> ContentHash {
+        ContentHash::from_hex(hex::encode(Sha256::digest(content.as_bytes()))).unwrap()
+    }
+
+    /// Repository holding a completed upload of `content` under "retry-1"
+    fn completed_idempotency_repo(object: &Object, content: &str) -> MockIdempotencyRepository {
+        let record = IdempotencyRecord {
+            namespace: keyed_request().namespace,
+            object_key: keyed_request().key,
+            object_id: Some(*object.id()),
+            content_hash: Some(sha256(content)),
+        };
+        let mut mock_idempotency_repo = MockIdempotencyRepository::new();
+        mock_idempotency_repo
+            .expect_reserve()
+            .returning(|_, _, _, _| Ok(false));
+        mock_idempotency_repo
+            .expect_find()
+            .returning(move |_, _| Ok(Some(record.clone())));
+        mock_idempotency_repo
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_upload_records_new_object() {
+        let (mock_object_repo, mock_blob_repo, mock_blob_store) = committing_mocks("text");
+        let mut mock_idempotency_repo = MockIdempotencyRepository::new();
+        mock_idempotency_repo
+            .expect_reserve()
+            .withf(|_, key, record, lease| {
+                *key == "retry-1" && record.object_id.is_none() && *lease == IDEMPOTENCY_LEASE_SECS
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(true));
+        mock_idempotency_repo
+            .expect_complete()
+            .withf(|_, key, _, hash, ttl| {
+                *key == "retry-1" && hash.as_hex() == "a".repeat(64) && *ttl == 3600
+            })
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(()));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_idempotency(Arc::new(mock_idempotency_repo), 3600);
+
+        let result = use_case
+            .execute(idempotent_request(), Box::pin(Cursor::new("text")))
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retry_returns_original_object() {
+        // Arrange: nothing is written for the retry
+        let original = committed_object(&sha256("test data"));
+        let original_id = *original.id();
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_save().never();
+        let found = original.clone();
+        mock_object_repo
+            .expect_find_by_id()
+            .withf(move |id| *id == original_id)
+            .returning(move |_| Ok(Some(found.clone())));
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store.expect_write().never();
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(mock_blob_store),
+        )
+        .with_idempotency(
+            Arc::new(completed_idempotency_repo(&original, "test data")),
+            3600,
+        );
+
+        // Act
+        let dto = use_case
+            .execute(idempotent_request(), Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(dto.id, original_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retry_with_different_payload_conflicts() {
+        let original = committed_object(&sha256("test data"));
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo.expect_find_by_id().never();
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        )
+        .with_idempotency(
+            Arc::new(completed_idempotency_repo(&original, "test data")),
+            3600,
+        );
+
+        let result = use_case
+            .execute(idempotent_request(), Box::pin(Cursor::new("other data")))
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retry_while_first_upload_in_progress_conflicts() {
+        let mut mock_idempotency_repo = MockIdempotencyRepository::new();
+        mock_idempotency_repo
+            .expect_reserve()
+            .returning(|_, _, _, _| Ok(false));
+        mock_idempotency_repo.expect_find().returning(|_, _| {
+            Ok(Some(IdempotencyRecord {
+                namespace: keyed_request().namespace,
+                object_key: keyed_request().key,
+                object_id: None,
+                content_hash: None,
+            }))
+        });
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(MockObjectRepository::new()),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        )
+        .with_idempotency(Arc::new(mock_idempotency_repo), 3600);
+
+        let result = use_case
+            .execute(idempotent_request(), Box::pin(Cursor::new("test data")))
+            .await;
+
+        assert!(matches!(result, Err(ObjectUseCaseError::Conflict(_))));
+    }
+
+    /// Use case whose blob store expects `expected_class` and whose namespace defaults to cold
+    fn cold_namespace_use_case(expected_class: StorageClass) -> UploadObjectUseCase {
+        let content_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+        let mut mock_object_repo = MockObjectRepository::new();
+        let mut mock_blob_repo = MockBlobRepository::new();
+        let mut mock_blob_store = MockBlobStore::new();
+        let mut mock_namespace_configs = MockNamespaceConfigRepository::new();
+
+        mock_object_repo.expect_save().returning(|_| Ok(()));
+        mock_blob_store
+            .expect_write()
+            .withf(move |_, storage_class| *storage_class == expected_class)
+            .times(1)
+            .returning(move |_, _| Ok((content_hash.clone(), 9)));
+        mock_blob_repo
+            .expect_get_or_create()
+            .returning(|hash, storage_class, size| {
+                Ok(crate::domain::entities::Blob::new(
+                    hash.clone(),
+                    storage_class,
+                    size,
+                ))
+            });
+        mock_namespace_configs.expect_find().returning(|namespace| {
+            Ok(Some(NamespaceConfig::new(
+                namespace.clone(),
+                StorageClass::Cold,
+                None,
+                None,
+            )))
+        });
+
+        UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_namespace_configs(Arc::new(mock_namespace_configs))
+    }
+
+    #[tokio::test]
+    async fn test_upload_uses_namespace_default_storage_class() {
+        // Arrange
+        let use_case = cold_namespace_use_case(StorageClass::Cold);
+        let request = UploadRequest {
+            storage_class: None,
+            ..keyed_request()
+        };
+
+        // Act
+        let dto = use_case
+            .execute(request, Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(dto.storage_class, StorageClass::Cold);
+    }
+
+    #[tokio::test]
+    async fn test_upload_storage_class_overrides_namespace_default() {
+        // Arrange
+        let use_case = cold_namespace_use_case(StorageClass::Hot);
+
+        // Act
+        let dto = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(dto.storage_class, StorageClass::Hot);
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_key_outside_namespace_policy() {
+        // Arrange: nothing is written when the key is rejected
+        let mut mock_namespace_configs = MockNamespaceConfigRepository::new();
+        mock_namespace_configs.expect_find().returning(|namespace| {
+            let policy = KeyPolicy::new(None, None, Some("reports/".to_string())).unwrap();
+            Ok(Some(NamespaceConfig::new(
+                namespace.clone(),
+                StorageClass::Hot,
+                None,
+                Some(policy),
+            )))
+        });
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(MockObjectRepository::new()),
+            Arc::new(MockBlobRepository::new()),
+            Arc::new(MockBlobStore::new()),
+        )
+        .with_namespace_configs(Arc::new(mock_namespace_configs));
+
+        // Act
+        let result = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::Domain(DomainError::ValidationError { ref field, .. }))
+                if field == "key"
+        ));
+    }
+
+    /// A use case whose store holds "test data" under `a...a` and whose
+    /// uploads are checked by `scanner`
+    fn scanned_use_case(
+        scanner: MockContentScanner,
+        fail_open: bool,
+        saves: usize,
+        decrements: usize,
+    ) -> UploadObjectUseCase {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_save()
+            .times(saves)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_get_or_create()
+            .returning(|hash, class, size| {
+                Ok(crate::domain::entities::Blob::new(
+                    hash.clone(),
+                    class,
+                    size,
+                ))
+            });
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(decrements)
+            .returning(|_| Ok(0));
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store
+            .expect_write()
+            .returning(|_, _| Ok((ContentHash::from_str(&"a".repeat(64)).unwrap(), 9)));
+        mock_blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new("test data"))));
+
+        UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_content_scanner(Arc::new(scanner), fail_open)
+    }
+
+    #[tokio::test]
+    async fn test_clean_scan_commits_upload() {
+        let mut scanner = MockContentScanner::new();
+        scanner
+            .expect_scan()
+            .times(1)
+            .withf(|subject, _| {
+                subject.key.as_deref() == Some("test-key") && subject.size_bytes == 9
+            })
+            .returning(|_, _| Ok(ScanVerdict::Clean));
+        let use_case = scanned_use_case(scanner, false, 3, 0);
+
+        let dto = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+
+        assert_eq!(dto.status, ObjectStatus::Committed);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_scan_fails_upload_and_releases_blob() {
+        // Only the WRITING reservation is saved; the blob reference is released
+        let mut scanner = MockContentScanner::new();
+        scanner
+            .expect_scan()
+            .returning(|_, _| Ok(ScanVerdict::Rejected("Eicar-Test-Signature".to_string())));
+        let use_case = scanned_use_case(scanner, false, 1, 1);
+
+        let result = use_case
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::ContentRejected(ref reason)) if reason == "Eicar-Test-Signature"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scanner_failure_respects_fail_open() {
+        let failing = || {
+            let mut scanner = MockContentScanner::new();
+            scanner
+                .expect_scan()
+                .returning(|_, _| Err(ScanError::Unavailable("connection refused".to_string())));
+            scanner
+        };
+
+        let closed = scanned_use_case(failing(), false, 1, 1)
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await;
+        assert!(matches!(closed, Err(ObjectUseCaseError::ScanFailed(_))));
+
+        let open = scanned_use_case(failing(), true, 3, 0)
+            .execute(keyed_request(), Box::pin(Cursor::new("test data")))
+            .await
+            .unwrap();
+        assert_eq!(open.status, ObjectStatus::Committed);
+    }
+
+    #[tokio::test]
+    async fn test_upload_known_hash_is_scanned() {
+        let mut mock_object_repo = MockObjectRepository::new();
+        mock_object_repo
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_blob_repo = MockBlobRepository::new();
+        mock_blob_repo
+            .expect_reference_existing()
+            .times(1)
+            .returning(|hash, _, _| Ok(Some(blob_for(hash))));
+        mock_blob_repo
+            .expect_decrement_ref()
+            .times(1)
+            .returning(|_| Ok(1));
+        let mut mock_blob_store = MockBlobStore::new();
+        mock_blob_store.expect_write().never();
+        mock_blob_store
+            .expect_read()
+            .returning(|_, _| Ok(Box::pin(Cursor::new("test data"))));
+        let mut scanner = MockContentScanner::new();
+        scanner
+            .expect_scan()
+            .times(1)
+            .returning(|_, _| Ok(ScanVerdict::Rejected("Eicar-Test-Signature".to_string())));
+
+        let use_case = UploadObjectUseCase::new(
+            Arc::new(mock_object_repo),
+            Arc::new(mock_blob_repo),
+            Arc::new(mock_blob_store),
+        )
+        .with_content_scanner(Arc::new(scanner), false);
+
+        let result = use_case
+            .execute(known_hash_request(), Box::pin(Cursor::new("unread")))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ObjectUseCaseError::ContentRejected(_))
+        ));
+    }
 }
@@ -4,7 +4,54 @@
 //! duplication across use case implementations.
 
 use crate::application::errors::ObjectUseCaseError;
-use crate::domain::value_objects::{Namespace, TenantId};
+use crate::domain::error_types::ValidationError;
+use crate::domain::value_objects::{Namespace, ObjectMetadata, TenantId};
+
+/// Default maximum serialized size of an object's metadata
+pub const MAX_METADATA_BYTES: usize = 64 * 1024;
+
+/// Default maximum length of any single string value in metadata
+pub const MAX_METADATA_STRING_CHARS: usize = 4096;
+
+/// Maximum length of `summary_short`
+pub const MAX_SUMMARY_SHORT_CHARS: usize = 1024;
+
+/// Default maximum number of custom tags and length of a tag key
+pub const MAX_METADATA_TAGS: usize = 100;
+pub const MAX_TAG_KEY_CHARS: usize = 128;
+
+/// Size limits on object metadata, set from `METADATA_MAX_*`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetadataLimits {
+    /// Serialized JSON size in bytes
+    pub max_bytes: usize,
+    /// Number of custom tags
+    pub max_tags: usize,
+    pub max_tag_key_chars: usize,
+    /// Length of any string value, tag values included
+    pub max_string_chars: usize,
+}
+
+impl Default for MetadataLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: MAX_METADATA_BYTES,
+            max_tags: MAX_METADATA_TAGS,
+            max_tag_key_chars: MAX_TAG_KEY_CHARS,
+            max_string_chars: MAX_METADATA_STRING_CHARS,
+        }
+    }
+}
+
+fn too_long(field: impl Into<String>, max: usize) -> ObjectUseCaseError {
+    ObjectUseCaseError::Validation(ValidationError::TooLong {
+        field: field.into(),
+        max,
+    })
+}
+
+/// Maximum length of an object key
+pub const MAX_KEY_CHARS: usize = 255;
 
 /// Validate namespace and tenant_id for object operations
 ///
@@ -53,3 +100,129 @@ pub fn validate_search_query(
     }
     Ok(())
 }
+
+/// Validate a JSON Merge Patch for object metadata before applying it
+///
+/// The patch must be a JSON object within the metadata size limit, and no
+/// string value may exceed the string length limit.
+pub fn validate_metadata_patch(
+    patch: &serde_json::Value,
+    limits: &MetadataLimits,
+) -> Result<(), ObjectUseCaseError> {
+    if !patch.is_object() {
+        return Err(ObjectUseCaseError::InvalidRequest(
+            "Metadata patch must be a JSON object".to_string(),
+        ));
+    }
+
+    if patch.to_string().len() > limits.max_bytes {
+        return Err(too_long("metadata", limits.max_bytes));
+    }
+
+    fn check_strings(
+        value: &serde_json::Value,
+        path: &str,
+        max_chars: usize,
+    ) -> Result<(), ObjectUseCaseError> {
+        match value {
+            serde_json::Value::String(s) if s.chars().count() > max_chars => {
+                Err(too_long(path, max_chars))
+            }
+            serde_json::Value::Array(items) => items
+                .iter()
+                .try_for_each(|item| check_strings(item, path, max_chars)),
+            serde_json::Value::Object(members) => members.iter().try_for_each(|(key, value)| {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                check_strings(value, &path, max_chars)
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    check_strings(patch, "", limits.max_string_chars)
+}
+
+/// Validate merged object metadata against the size limits
+pub fn validate_metadata(
+    metadata: &ObjectMetadata,
+    limits: &MetadataLimits,
+) -> Result<(), ObjectUseCaseError> {
+    if metadata
+        .summary_short
+        .as_ref()
+        .is_some_and(|s| s.chars().count() > MAX_SUMMARY_SHORT_CHARS)
+    {
+        return Err(too_long("summary_short", MAX_SUMMARY_SHORT_CHARS));
+    }
+
+    if metadata.tags.len() > limits.max_tags {
+        return Err(too_long("tags", limits.max_tags));
+    }
+
+    if metadata.tags.keys().any(String::is_empty) {
+        return Err(ObjectUseCaseError::InvalidRequest(
+            "Tag keys must not be empty".to_string(),
+        ));
+    }
+    if let Some(key) = metadata
+        .tags
+        .keys()
+        .find(|key| key.chars().count() > limits.max_tag_key_chars)
+    {
+        return Err(too_long(format!("tags.{key}"), limits.max_tag_key_chars));
+    }
+
+    let size = metadata
+        .to_json()
+        .map_err(|e| ObjectUseCaseError::InvalidRequest(e.to_string()))?
+        .to_string()
+        .len();
+    if size > limits.max_bytes {
+        return Err(too_long("metadata", limits.max_bytes));
+    }
+
+    Ok(())
+}
+
+/// Turn an archive entry path into an object key
+///
+/// Rejects absolute paths, `..` components and Windows-style separators so
+/// an entry like `../../../etc/passwd` can never name a key outside the
+/// archive root; `.` and empty components are dropped.
+pub fn archive_entry_key(path: &str) -> Result<String, ObjectUseCaseError> {
+    let invalid = |reason: &str| {
+        Err(ObjectUseCaseError::InvalidRequest(format!(
+            "Invalid archive entry path '{path}': {reason}"
+        )))
+    };
+
+    if path.contains('\\') || path.contains('\0') {
+        return invalid("contains a backslash or NUL");
+    }
+    if path.starts_with('/') || path.split('/').next().is_some_and(|c| c.ends_with(':')) {
+        return invalid("absolute path");
+    }
+
+    let mut components = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => return invalid("parent directory component"),
+            component => components.push(component),
+        }
+    }
+
+    let key = components.join("/");
+    if key.is_empty() {
+        return invalid("empty path");
+    }
+    if key.chars().count() > MAX_KEY_CHARS {
+        return invalid(&format!("longer than {MAX_KEY_CHARS} characters"));
+    }
+
+    Ok(key)
+}
@@ -0,0 +1,204 @@
+//! Signature checks for inbound webhooks
+//!
+//! External systems call back with an HMAC-SHA256 of `<timestamp>.<body>`
+//! keyed with their tenant's shared secret. The timestamp is signed too, so a
+//! captured request can only be replayed within the tolerance window.
+
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use thiserror::Error;
+
+use crate::domain::value_objects::TenantId;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Prefix of the hex digest in the signature header
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum WebhookError {
+    /// Missing or wrong signature, or no secret for the tenant
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+
+    /// Correctly signed, but sent outside the tolerance window
+    #[error("Webhook timestamp is outside the {tolerance_secs}s tolerance")]
+    StaleTimestamp { tolerance_secs: u64 },
+}
+
+/// Per-tenant shared secrets and the accepted clock skew
+#[derive(Clone, Default)]
+pub struct WebhookVerifier {
+    secrets: HashMap<TenantId, Vec<u8>>,
+    tolerance_secs: u64,
+}
+
+impl WebhookVerifier {
+    /// Parse `tenant_id=secret` pairs separated by commas
+    pub fn parse(spec: &str, tolerance_secs: u64) -> Result<Self, String> {
+        let mut secrets = HashMap::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (tenant_id, secret) = entry
+                .split_once('=')
+                .ok_or_else(|| "expected tenant_id=secret pairs".to_string())?;
+            let tenant_id = TenantId::from_string(tenant_id.trim())
+                .map_err(|e| format!("Invalid tenant ID '{}': {}", tenant_id.trim(), e))?;
+            if secret.is_empty() {
+                return Err(format!("Empty secret for tenant {tenant_id}"));
+            }
+            if secrets
+                .insert(tenant_id.clone(), secret.as_bytes().to_vec())
+                .is_some()
+            {
+                return Err(format!("Tenant {tenant_id} is listed twice"));
+            }
+        }
+        Ok(Self {
+            secrets,
+            tolerance_secs,
+        })
+    }
+
+    /// Whether any tenant has a secret, i.e. webhooks can be accepted at all
+    pub fn is_enabled(&self) -> bool {
+        !self.secrets.is_empty()
+    }
+
+    /// Check `signature` over `timestamp` and `body`, then the timestamp
+    /// against `now` (both Unix seconds)
+    ///
+    /// The signature is checked first, so unauthenticated callers learn
+    /// nothing about the clock.
+    pub fn verify(
+        &self,
+        tenant_id: &TenantId,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), WebhookError> {
+        let secret = self
+            .secrets
+            .get(tenant_id)
+            .ok_or(WebhookError::InvalidSignature)?;
+        let expected = signature
+            .trim()
+            .strip_prefix(SIGNATURE_PREFIX)
+            .and_then(|digest| hex::decode(digest).ok())
+            .ok_or(WebhookError::InvalidSignature)?;
+
+        let mut mac = <HmacSha256 as KeyInit>::new_from_slice(secret)
+            .map_err(|_| WebhookError::InvalidSignature)?;
+        mac.update(timestamp.as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        // Constant-time comparison
+        mac.verify_slice(&expected)
+            .map_err(|_| WebhookError::InvalidSignature)?;
+
+        let stale = WebhookError::StaleTimestamp {
+            tolerance_secs: self.tolerance_secs,
+        };
+        let sent: i64 = timestamp.trim().parse().map_err(|_| stale.clone())?;
+        if now.abs_diff(sent) > self.tolerance_secs {
+            return Err(stale);
+        }
+        Ok(())
+    }
+}
+
+/// Signature header value for `body` sent at `timestamp`
+pub fn sign(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        <HmacSha256 as KeyInit>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!(
+        "{SIGNATURE_PREFIX}{}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    const NOW: i64 = 1_700_000_000;
+    const BODY: &[u8] = br#"{"event":"upload.completed"}"#;
+
+    fn verifier() -> (WebhookVerifier, TenantId) {
+        let tenant_id = TenantId::new(Uuid::new_v4());
+        let verifier = WebhookVerifier::parse(&format!("{tenant_id}=s3cret"), 300).unwrap();
+        (verifier, tenant_id)
+    }
+
+    #[test]
+    fn test_signed_request_within_tolerance_accepted() {
+        let (verifier, tenant_id) = verifier();
+        let signature = sign(b"s3cret", NOW - 60, BODY);
+
+        let timestamp = (NOW - 60).to_string();
+        assert_eq!(
+            verifier.verify(&tenant_id, &timestamp, &signature, BODY, NOW),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_tampered_or_unknown_requests_rejected() {
+        let (verifier, tenant_id) = verifier();
+        let timestamp = NOW.to_string();
+        let signature = sign(b"s3cret", NOW, BODY);
+        let other_tenant = TenantId::new(Uuid::new_v4());
+
+        let cases = [
+            (&tenant_id, sign(b"wrong", NOW, BODY), BODY),
+            (
+                &tenant_id,
+                signature.clone(),
+                br#"{"event":"other"}"#.as_slice(),
+            ),
+            (
+                &tenant_id,
+                signature.trim_start_matches("sha256=").to_string(),
+                BODY,
+            ),
+            (&other_tenant, signature, BODY),
+        ];
+        for (tenant_id, signature, body) in cases {
+            assert_eq!(
+                verifier.verify(tenant_id, &timestamp, &signature, body, NOW),
+                Err(WebhookError::InvalidSignature),
+                "{signature}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_replayed_timestamp_rejected() {
+        let (verifier, tenant_id) = verifier();
+        let sent = NOW - 301;
+        let signature = sign(b"s3cret", sent, BODY);
+
+        assert_eq!(
+            verifier.verify(&tenant_id, &sent.to_string(), &signature, BODY, NOW),
+            Err(WebhookError::StaleTimestamp {
+                tolerance_secs: 300
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_specs() {
+        let tenant_id = Uuid::new_v4();
+        assert!(WebhookVerifier::parse("", 300).unwrap().secrets.is_empty());
+        assert!(WebhookVerifier::parse("not-a-tenant=secret", 300).is_err());
+        assert!(WebhookVerifier::parse(&format!("{tenant_id}"), 300).is_err());
+        assert!(WebhookVerifier::parse(&format!("{tenant_id}="), 300).is_err());
+        assert!(WebhookVerifier::parse(&format!("{tenant_id}=a,{tenant_id}=b"), 300).is_err());
+    }
+}
@@ -6,6 +6,8 @@ pub mod permissions {
     pub const OBJECTS_READ: &str = "objects:read";
     pub const OBJECTS_WRITE: &str = "objects:write";
     pub const OBJECTS_DELETE: &str = "objects:delete";
+    /// Set or extend object retention and legal holds (WORM locks)
+    pub const OBJECTS_RETENTION: &str = "objects:retention";
 
     // API key management
     pub const API_KEYS_READ: &str = "api_keys:read";
@@ -24,6 +26,7 @@ pub mod permissions {
         OBJECTS_READ,
         OBJECTS_WRITE,
         OBJECTS_DELETE,
+        OBJECTS_RETENTION,
         API_KEYS_READ,
         API_KEYS_WRITE,
         API_KEYS_DELETE,
@@ -43,6 +46,7 @@ pub mod roles {
         OBJECTS_READ,
         OBJECTS_WRITE,
         OBJECTS_DELETE,
+        OBJECTS_RETENTION,
         API_KEYS_READ,
         API_KEYS_WRITE,
         API_KEYS_DELETE,
@@ -460,7 +464,7 @@ mod tests {
         // Test that ALL contains all permissions
         assert!(permissions::ALL.contains(&permissions::OBJECTS_READ));
         assert!(permissions::ALL.contains(&permissions::ADMIN));
-        assert_eq!(permissions::ALL.len(), 9); // Should have 9 permissions
+        assert_eq!(permissions::ALL.len(), 10); // Should have 10 permissions
     }
 
     #[test]
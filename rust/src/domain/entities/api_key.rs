@@ -1,13 +1,14 @@
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use crate::domain::value_objects::{ApiKeyId, ApiKeyPermissions, ApiKeyValue};
+use crate::domain::value_objects::{ApiKeyHashAlgorithm, ApiKeyId, ApiKeyPermissions, ApiKeyValue};
 
 /// Data structure for reconstructing API keys from database
 #[derive(Debug, Clone)]
 pub struct ApiKeyDbData {
     pub id: ApiKeyId,
     pub api_key: ApiKeyValue,
+    pub key_prefix: Option<String>,
     pub tenant_id: String,
     pub name: String,
     pub description: Option<String>,
@@ -24,6 +25,7 @@ pub struct ApiKeyDbData {
 pub struct ApiKey {
     id: ApiKeyId,
     api_key: ApiKeyValue,
+    key_prefix: Option<String>,
     tenant_id: String,
     name: String,
     description: Option<String>,
@@ -36,20 +38,24 @@ pub struct ApiKey {
 }
 
 impl ApiKey {
+    /// Create a key with a new secret, returned in plaintext only here
     pub fn new(
         tenant_id: String,
         name: String,
         description: Option<String>,
         permissions: ApiKeyPermissions,
         expires_at: Option<OffsetDateTime>,
+        hash_algorithm: ApiKeyHashAlgorithm,
     ) -> (Self, String) {
         let now = OffsetDateTime::now_utc();
         let plain_key = ApiKeyValue::generate_plaintext();
-        let api_key = ApiKeyValue::hash(&plain_key);
-        
+        let api_key = ApiKeyValue::hash(&plain_key, hash_algorithm);
+        let key_prefix = ApiKeyValue::prefix(&plain_key).map(str::to_string);
+
         let entity = Self {
             id: ApiKeyId::new(),
             api_key,
+            key_prefix,
             tenant_id,
             name,
             description,
@@ -68,6 +74,7 @@ impl ApiKey {
         Self {
             id: db_data.id,
             api_key: db_data.api_key,
+            key_prefix: db_data.key_prefix,
             tenant_id: db_data.tenant_id,
             name: db_data.name,
             description: db_data.description,
@@ -89,6 +96,12 @@ impl ApiKey {
         &self.api_key
     }
 
+    /// Clear-text start of the key that finds its record; `None` for keys
+    /// stored before salting
+    pub fn key_prefix(&self) -> Option<&str> {
+        self.key_prefix.as_deref()
+    }
+
     pub fn tenant_id(&self) -> &str {
         &self.tenant_id
     }
@@ -151,6 +164,27 @@ impl ApiKey {
         self.updated_at = OffsetDateTime::now_utc();
     }
 
+    /// Replace the secret with a new one, returned in plaintext only here
+    ///
+    /// The ID, permissions and expiry are kept.
+    pub fn rotate_secret(&mut self, hash_algorithm: ApiKeyHashAlgorithm) -> String {
+        let plain_key = ApiKeyValue::generate_plaintext();
+        self.rehash_secret(&plain_key, hash_algorithm);
+        plain_key
+    }
+
+    /// Store a fresh salted hash of the current secret
+    pub fn rehash_secret(&mut self, plain_key: &str, hash_algorithm: ApiKeyHashAlgorithm) {
+        self.api_key = ApiKeyValue::hash(plain_key, hash_algorithm);
+        self.key_prefix = ApiKeyValue::prefix(plain_key).map(str::to_string);
+        self.updated_at = OffsetDateTime::now_utc();
+    }
+
+    /// Check a plaintext secret against the stored hash in constant time
+    pub fn verify_secret(&self, plain_key: &str) -> bool {
+        self.api_key.verify(plain_key)
+    }
+
     pub fn mark_used(&mut self) {
         self.last_used_at = Some(OffsetDateTime::now_utc());
     }
@@ -177,3 +211,46 @@ impl ApiKey {
         self.is_active && !self.is_expired() && self.permissions.admin
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_key() -> (ApiKey, String) {
+        ApiKey::new(
+            "tenant".to_string(),
+            "key".to_string(),
+            None,
+            ApiKeyPermissions::default(),
+            None,
+            ApiKeyHashAlgorithm::Sha256,
+        )
+    }
+
+    #[test]
+    fn test_new_key_stores_only_a_salted_hash() {
+        let (api_key, plain_key) = new_key();
+
+        assert!(api_key.verify_secret(&plain_key));
+        assert!(!api_key.api_key().as_str().contains(&plain_key));
+        assert!(!serde_json::to_string(&api_key)
+            .unwrap()
+            .contains(&plain_key));
+        assert_eq!(api_key.key_prefix(), ApiKeyValue::prefix(&plain_key));
+    }
+
+    #[test]
+    fn test_rotate_secret_keeps_identity_and_revokes_old_secret() {
+        let (mut api_key, old_key) = new_key();
+        let id = *api_key.id();
+
+        let new_key = api_key.rotate_secret(ApiKeyHashAlgorithm::Sha256);
+
+        assert_ne!(new_key, old_key);
+        assert_eq!(*api_key.id(), id);
+        assert_eq!(api_key.permissions(), &ApiKeyPermissions::default());
+        assert!(api_key.verify_secret(&new_key));
+        assert!(!api_key.verify_secret(&old_key));
+        assert_eq!(api_key.key_prefix(), ApiKeyValue::prefix(&new_key));
+    }
+}
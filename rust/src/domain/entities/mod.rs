@@ -1,7 +1,9 @@
 mod api_key;
 mod blob;
+mod namespace_config;
 mod object;
 
 pub use api_key::{ApiKey, ApiKeyDbData};
 pub use blob::Blob;
+pub use namespace_config::{KeyPolicy, NamespaceConfig, TieringPolicy};
 pub use object::Object;
@@ -0,0 +1,304 @@
+use time::OffsetDateTime;
+
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Namespace, StorageClass};
+
+/// When objects in a namespace move from hot to cold storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TieringPolicy {
+    /// Objects not downloaded for this many days belong in cold storage
+    cold_after_days: u32,
+}
+
+impl TieringPolicy {
+    /// Longest supported delay, 100 years; keeps the cutoff date in range
+    pub const MAX_COLD_AFTER_DAYS: u32 = 36_500;
+
+    pub fn new(cold_after_days: u32) -> Result<Self, DomainError> {
+        if !(1..=Self::MAX_COLD_AFTER_DAYS).contains(&cold_after_days) {
+            return Err(DomainError::ValidationError {
+                field: "cold_after_days".to_string(),
+                message: format!("must be between 1 and {}", Self::MAX_COLD_AFTER_DAYS),
+            });
+        }
+        Ok(Self { cold_after_days })
+    }
+
+    pub fn cold_after_days(&self) -> u32 {
+        self.cold_after_days
+    }
+}
+
+/// Constraints on object keys uploaded to a namespace
+///
+/// Every constraint is optional; keys only need to satisfy the ones set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPolicy {
+    max_length: Option<u32>,
+    /// Allowed characters as written, e.g. `a-z0-9._/-`
+    allowed_chars: Option<String>,
+    /// Inclusive character ranges parsed from `allowed_chars`
+    allowed_ranges: Vec<(char, char)>,
+    required_prefix: Option<String>,
+}
+
+impl KeyPolicy {
+    /// Build a policy; `allowed_chars` lists characters and `x-y` ranges
+    /// (a `-` at either end is literal)
+    pub fn new(
+        max_length: Option<u32>,
+        allowed_chars: Option<String>,
+        required_prefix: Option<String>,
+    ) -> Result<Self, DomainError> {
+        let invalid = |field: &str, message: String| DomainError::ValidationError {
+            field: field.to_string(),
+            message,
+        };
+
+        match max_length {
+            Some(0) => return Err(invalid("max_length", "must be at least 1".to_string())),
+            // Stored as a Postgres INTEGER
+            Some(len) if len > i32::MAX as u32 => {
+                return Err(invalid(
+                    "max_length",
+                    format!("must be at most {}", i32::MAX),
+                ))
+            }
+            _ => {}
+        }
+        let allowed_ranges = match &allowed_chars {
+            Some(spec) => parse_char_ranges(spec).map_err(|e| invalid("allowed_chars", e))?,
+            None => Vec::new(),
+        };
+
+        let policy = Self {
+            max_length,
+            allowed_chars,
+            allowed_ranges,
+            required_prefix: None,
+        };
+        if let Some(prefix) = &required_prefix {
+            if prefix.is_empty() {
+                return Err(invalid("required_prefix", "must not be empty".to_string()));
+            }
+            if let Err(DomainError::ValidationError { message, .. }) = policy.check(prefix) {
+                return Err(invalid("required_prefix", message));
+            }
+        }
+
+        Ok(Self {
+            required_prefix,
+            ..policy
+        })
+    }
+
+    pub fn max_length(&self) -> Option<u32> {
+        self.max_length
+    }
+
+    pub fn allowed_chars(&self) -> Option<&str> {
+        self.allowed_chars.as_deref()
+    }
+
+    pub fn required_prefix(&self) -> Option<&str> {
+        self.required_prefix.as_deref()
+    }
+
+    /// Check a key, naming the first constraint it breaks
+    pub fn check(&self, key: &str) -> Result<(), DomainError> {
+        let invalid = |message: String| {
+            Err(DomainError::ValidationError {
+                field: "key".to_string(),
+                message,
+            })
+        };
+
+        if let Some(prefix) = &self.required_prefix {
+            if !key.starts_with(prefix.as_str()) {
+                return invalid(format!("must start with '{prefix}'"));
+            }
+        }
+        if let Some(max_length) = self.max_length {
+            if key.chars().count() > max_length as usize {
+                return invalid(format!("must be at most {max_length} characters"));
+            }
+        }
+        if let Some(spec) = &self.allowed_chars {
+            let allowed = |c: char| {
+                self.allowed_ranges
+                    .iter()
+                    .any(|&(start, end)| (start..=end).contains(&c))
+            };
+            if let Some(c) = key.chars().find(|&c| !allowed(c)) {
+                return invalid(format!("character {c:?} is not allowed (allowed: {spec})"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a character set such as `a-z0-9._/-` into inclusive ranges
+fn parse_char_ranges(spec: &str) -> Result<Vec<(char, char)>, String> {
+    let chars: Vec<char> = spec.chars().collect();
+    if chars.is_empty() {
+        return Err("must list at least one character".to_string());
+    }
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' {
+            let (start, end) = (chars[i], chars[i + 2]);
+            if start > end {
+                return Err(format!("range {start}-{end} is reversed"));
+            }
+            ranges.push((start, end));
+            i += 3;
+        } else {
+            ranges.push((chars[i], chars[i]));
+            i += 1;
+        }
+    }
+
+    Ok(ranges)
+}
+
+/// NamespaceConfig entity - operator defaults for one namespace
+#[derive(Debug, Clone)]
+pub struct NamespaceConfig {
+    namespace: Namespace,
+    default_storage_class: StorageClass,
+    tiering: Option<TieringPolicy>,
+    key_policy: Option<KeyPolicy>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl NamespaceConfig {
+    pub fn new(
+        namespace: Namespace,
+        default_storage_class: StorageClass,
+        tiering: Option<TieringPolicy>,
+        key_policy: Option<KeyPolicy>,
+    ) -> Self {
+        let now = OffsetDateTime::now_utc();
+        Self {
+            namespace,
+            default_storage_class,
+            tiering,
+            key_policy,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Reconstruct from storage
+    pub fn reconstruct(
+        namespace: Namespace,
+        default_storage_class: StorageClass,
+        tiering: Option<TieringPolicy>,
+        key_policy: Option<KeyPolicy>,
+        created_at: OffsetDateTime,
+        updated_at: OffsetDateTime,
+    ) -> Self {
+        Self {
+            namespace,
+            default_storage_class,
+            tiering,
+            key_policy,
+            created_at,
+            updated_at,
+        }
+    }
+
+    // Getters
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Storage class for uploads that do not name one
+    pub fn default_storage_class(&self) -> StorageClass {
+        self.default_storage_class
+    }
+
+    pub fn tiering(&self) -> Option<TieringPolicy> {
+        self.tiering
+    }
+
+    /// Constraints on keys uploaded to the namespace
+    pub fn key_policy(&self) -> Option<&KeyPolicy> {
+        self.key_policy.as_ref()
+    }
+
+    pub fn created_at(&self) -> OffsetDateTime {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> OffsetDateTime {
+        self.updated_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiering_policy_requires_days_in_range() {
+        assert!(TieringPolicy::new(0).is_err());
+        assert!(TieringPolicy::new(TieringPolicy::MAX_COLD_AFTER_DAYS + 1).is_err());
+        assert!(TieringPolicy::new(u32::MAX).is_err());
+        assert_eq!(TieringPolicy::new(30).unwrap().cold_after_days(), 30);
+    }
+
+    fn key_error(policy: &KeyPolicy, key: &str) -> String {
+        match policy.check(key) {
+            Err(DomainError::ValidationError { field, message }) => {
+                assert_eq!(field, "key");
+                message
+            }
+            other => panic!("expected a key validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_key_policy_checks_each_constraint() {
+        let policy = KeyPolicy::new(
+            Some(16),
+            Some("a-z0-9/_-".to_string()),
+            Some("proj-".to_string()),
+        )
+        .unwrap();
+
+        assert!(policy.check("proj-a/b_c-1").is_ok());
+        assert_eq!(key_error(&policy, "other/a"), "must start with 'proj-'");
+        assert_eq!(
+            key_error(&policy, "proj-0123456789ab"),
+            "must be at most 16 characters"
+        );
+        assert_eq!(
+            key_error(&policy, "proj-A"),
+            "character 'A' is not allowed (allowed: a-z0-9/_-)"
+        );
+    }
+
+    #[test]
+    fn test_empty_key_policy_allows_any_key() {
+        let policy = KeyPolicy::new(None, None, None).unwrap();
+
+        assert!(policy.check("Anything goes!").is_ok());
+    }
+
+    #[test]
+    fn test_key_policy_rejects_invalid_constraints() {
+        assert!(KeyPolicy::new(Some(0), None, None).is_err());
+        assert!(KeyPolicy::new(Some(u32::MAX), None, None).is_err());
+        assert!(KeyPolicy::new(None, Some(String::new()), None).is_err());
+        assert!(KeyPolicy::new(None, Some("z-a".to_string()), None).is_err());
+        assert!(KeyPolicy::new(None, None, Some(String::new())).is_err());
+        // The prefix itself must satisfy the policy
+        assert!(KeyPolicy::new(None, Some("a-z".to_string()), Some("Proj".to_string())).is_err());
+        assert!(KeyPolicy::new(Some(3), None, Some("proj".to_string())).is_err());
+    }
+}
@@ -1,10 +1,12 @@
 use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
 use crate::domain::{
     errors::DomainError,
     value_objects::{
-        ContentHash, Namespace, ObjectId, ObjectMetadata, ObjectStatus, StorageClass, TenantId,
+        ContentEncoding, ContentHash, Namespace, ObjectId, ObjectMetadata, ObjectStatus,
+        StorageClass, TenantId,
     },
 };
 
@@ -20,9 +22,18 @@ pub struct Object {
     content_hash: Option<ContentHash>,
     size_bytes: Option<u64>,
     content_type: Option<String>,
+    /// Compression of the stored bytes; hash and size describe those bytes
+    content_encoding: Option<ContentEncoding>,
     metadata: ObjectMetadata,
     created_at: OffsetDateTime,
     updated_at: OffsetDateTime,
+    download_count: u64,
+    last_accessed_at: Option<OffsetDateTime>,
+    /// Write-once lock: content can't be replaced, nor the object deleted,
+    /// before this time
+    retention_until: Option<OffsetDateTime>,
+    /// Lock without an end, until the hold is released
+    legal_hold: bool,
 }
 
 impl Object {
@@ -44,9 +55,14 @@ impl Object {
             content_hash: None,
             size_bytes: None,
             content_type: None,
+            content_encoding: None,
             metadata: ObjectMetadata::default(),
             created_at: now,
             updated_at: now,
+            download_count: 0,
+            last_accessed_at: None,
+            retention_until: None,
+            legal_hold: false,
         }
     }
 
@@ -62,9 +78,14 @@ impl Object {
         content_hash: Option<ContentHash>,
         size_bytes: Option<u64>,
         content_type: Option<String>,
+        content_encoding: Option<ContentEncoding>,
         metadata: ObjectMetadata,
         created_at: OffsetDateTime,
         updated_at: OffsetDateTime,
+        download_count: u64,
+        last_accessed_at: Option<OffsetDateTime>,
+        retention_until: Option<OffsetDateTime>,
+        legal_hold: bool,
     ) -> Self {
         Self {
             id,
@@ -76,12 +97,48 @@ impl Object {
             content_hash,
             size_bytes,
             content_type,
+            content_encoding,
             metadata,
             created_at,
             updated_at,
+            download_count,
+            last_accessed_at,
+            retention_until,
+            legal_hold,
         }
     }
 
+    /// Record written content on a WRITING object ahead of its commit
+    ///
+    /// A staged object whose upload was interrupted can later be committed
+    /// from its blob, or rolled back if the blob never landed.
+    pub fn stage_content(
+        &mut self,
+        content_hash: &ContentHash,
+        size_bytes: u64,
+    ) -> Result<(), DomainError> {
+        if self.status != ObjectStatus::Writing {
+            return Err(DomainError::InvalidStateTransition {
+                from: self.status,
+                to: ObjectStatus::Writing,
+            });
+        }
+
+        self.content_hash = Some(content_hash.clone());
+        self.size_bytes = Some(size_bytes);
+        self.updated_at = OffsetDateTime::now_utc();
+
+        Ok(())
+    }
+
+    /// Content recorded by `stage_content` on a still-WRITING object
+    pub fn staged_content(&self) -> Option<(&ContentHash, u64)> {
+        if self.status != ObjectStatus::Writing {
+            return None;
+        }
+        self.content_hash.as_ref().zip(self.size_bytes)
+    }
+
     /// Commit object after successful upload
     pub fn commit(
         &mut self,
@@ -103,11 +160,55 @@ impl Object {
         Ok(())
     }
 
+    /// Commit object, rejecting content larger than `max_size_bytes`
+    pub fn commit_within_limit(
+        &mut self,
+        content_hash: &ContentHash,
+        size_bytes: u64,
+        max_size_bytes: u64,
+    ) -> Result<(), DomainError> {
+        Self::check_size(size_bytes, max_size_bytes)?;
+        self.commit(content_hash, size_bytes)
+    }
+
+    /// Reject object content larger than `max_size_bytes`
+    pub fn check_size(size_bytes: u64, max_size_bytes: u64) -> Result<(), DomainError> {
+        if size_bytes > max_size_bytes {
+            return Err(DomainError::SizeExceedsMaximum {
+                size: size_bytes,
+                max: max_size_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Replace the content of a committed object (conditional overwrite)
+    pub fn replace_content(
+        &mut self,
+        content_hash: &ContentHash,
+        size_bytes: u64,
+    ) -> Result<(), DomainError> {
+        if self.status != ObjectStatus::Committed {
+            return Err(DomainError::InvalidStateTransition {
+                from: self.status,
+                to: ObjectStatus::Committed,
+            });
+        }
+        self.ensure_unlocked()?;
+
+        self.content_hash = Some(content_hash.clone());
+        self.size_bytes = Some(size_bytes);
+        self.updated_at = OffsetDateTime::now_utc();
+
+        Ok(())
+    }
+
     /// Mark object for deletion
     pub fn mark_for_deletion(&mut self) -> Result<(), DomainError> {
         if self.status != ObjectStatus::Committed {
             return Err(DomainError::CannotDeleteNonCommitted);
         }
+        self.ensure_unlocked()?;
 
         self.status = ObjectStatus::Deleting;
         self.updated_at = OffsetDateTime::now_utc();
@@ -115,6 +216,61 @@ impl Object {
         Ok(())
     }
 
+    /// Whether the object is under legal hold or its retention has not ended
+    pub fn is_locked(&self) -> bool {
+        self.legal_hold
+            || self
+                .retention_until
+                .is_some_and(|until| until > OffsetDateTime::now_utc())
+    }
+
+    /// Refuse to modify a locked object's content or metadata, or delete it
+    pub fn ensure_unlocked(&self) -> Result<(), DomainError> {
+        if self.legal_hold {
+            return Err(DomainError::ObjectLocked("under legal hold".to_string()));
+        }
+        match self.retention_until {
+            Some(until) if until > OffsetDateTime::now_utc() => Err(DomainError::ObjectLocked(
+                format!("retained until {}", format_timestamp(until)),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Retain the object until `until`; retention can be extended, never
+    /// shortened
+    pub fn extend_retention(&mut self, until: OffsetDateTime) -> Result<(), DomainError> {
+        if self.status != ObjectStatus::Committed {
+            return Err(DomainError::InvalidStateTransition {
+                from: self.status,
+                to: ObjectStatus::Committed,
+            });
+        }
+        if let Some(current) = self.retention_until.filter(|current| until < *current) {
+            return Err(DomainError::RetentionShortened(format_timestamp(current)));
+        }
+
+        self.retention_until = Some(until);
+        self.updated_at = OffsetDateTime::now_utc();
+
+        Ok(())
+    }
+
+    /// Place or release a legal hold
+    pub fn set_legal_hold(&mut self, legal_hold: bool) -> Result<(), DomainError> {
+        if self.status != ObjectStatus::Committed {
+            return Err(DomainError::InvalidStateTransition {
+                from: self.status,
+                to: ObjectStatus::Committed,
+            });
+        }
+
+        self.legal_hold = legal_hold;
+        self.updated_at = OffsetDateTime::now_utc();
+
+        Ok(())
+    }
+
     /// Mark as fully deleted (tombstone)
     pub fn mark_deleted(&mut self) -> Result<(), DomainError> {
         if self.status != ObjectStatus::Deleting {
@@ -167,6 +323,10 @@ impl Object {
         self.content_type.as_deref()
     }
 
+    pub fn content_encoding(&self) -> Option<ContentEncoding> {
+        self.content_encoding
+    }
+
     pub fn metadata(&self) -> &ObjectMetadata {
         &self.metadata
     }
@@ -180,6 +340,24 @@ impl Object {
         self.updated_at = OffsetDateTime::now_utc();
     }
 
+    /// Record the compression of the content about to be written
+    pub fn set_content_encoding(&mut self, content_encoding: Option<ContentEncoding>) {
+        self.content_encoding = content_encoding;
+        self.updated_at = OffsetDateTime::now_utc();
+    }
+
+    pub fn set_metadata(&mut self, metadata: ObjectMetadata) {
+        self.metadata = metadata;
+        self.updated_at = OffsetDateTime::now_utc();
+    }
+
+    /// Record that the blob moved to another storage class
+    ///
+    /// The content is unchanged, so `updated_at` is left alone.
+    pub fn set_storage_class(&mut self, storage_class: StorageClass) {
+        self.storage_class = storage_class;
+    }
+
     pub fn created_at(&self) -> OffsetDateTime {
         self.created_at
     }
@@ -188,6 +366,25 @@ impl Object {
         self.updated_at
     }
 
+    /// Number of recorded downloads
+    pub fn download_count(&self) -> u64 {
+        self.download_count
+    }
+
+    /// When the object was last downloaded, if ever
+    pub fn last_accessed_at(&self) -> Option<OffsetDateTime> {
+        self.last_accessed_at
+    }
+
+    /// End of the object's retention, if set
+    pub fn retention_until(&self) -> Option<OffsetDateTime> {
+        self.retention_until
+    }
+
+    pub fn legal_hold(&self) -> bool {
+        self.legal_hold
+    }
+
     /// Check if object is in terminal state
     pub fn is_terminal(&self) -> bool {
         matches!(self.status, ObjectStatus::Deleted)
@@ -199,6 +396,10 @@ impl Object {
     }
 }
 
+fn format_timestamp(at: OffsetDateTime) -> String {
+    at.format(&Rfc3339).unwrap_or_else(|_| at.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +449,47 @@ mod tests {
         assert!(matches!(err, DomainError::InvalidStateTransition { .. }));
     }
 
+    #[test]
+    fn test_object_stage_then_commit() {
+        let mut object = create_test_object();
+        let content_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+
+        object.stage_content(&content_hash, 123).unwrap();
+        assert_eq!(object.status(), ObjectStatus::Writing);
+        assert_eq!(object.staged_content(), Some((&content_hash, 123)));
+
+        object.commit(&content_hash, 123).unwrap();
+        assert!(object.staged_content().is_none());
+        assert!(matches!(
+            object.stage_content(&content_hash, 123),
+            Err(DomainError::InvalidStateTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_object_replace_content_valid() {
+        let mut object = create_test_object();
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 123)
+            .unwrap();
+        let new_hash = ContentHash::from_str(&"b".repeat(64)).unwrap();
+
+        object.replace_content(&new_hash, 456).unwrap();
+
+        assert_eq!(object.status(), ObjectStatus::Committed);
+        assert_eq!(object.content_hash(), Some(&new_hash));
+        assert_eq!(object.size_bytes(), Some(456));
+    }
+
+    #[test]
+    fn test_object_replace_content_requires_committed() {
+        let mut object = create_test_object();
+        let content_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+
+        let err = object.replace_content(&content_hash, 123).unwrap_err();
+        assert!(matches!(err, DomainError::InvalidStateTransition { .. }));
+    }
+
     #[test]
     fn test_object_mark_for_deletion_valid() {
         let mut object = create_test_object();
@@ -265,6 +507,68 @@ mod tests {
         assert!(matches!(err, DomainError::CannotDeleteNonCommitted));
     }
 
+    #[test]
+    fn test_object_retention_blocks_delete_and_overwrite() {
+        let mut object = create_test_object();
+        let content_hash = ContentHash::from_str(&"a".repeat(64)).unwrap();
+        object.commit(&content_hash, 123).unwrap();
+
+        object
+            .extend_retention(OffsetDateTime::now_utc() + time::Duration::days(1))
+            .unwrap();
+        assert!(object.is_locked());
+        assert!(matches!(
+            object.mark_for_deletion(),
+            Err(DomainError::ObjectLocked(_))
+        ));
+        assert!(matches!(
+            object.replace_content(&content_hash, 456),
+            Err(DomainError::ObjectLocked(_))
+        ));
+        assert_eq!(object.status(), ObjectStatus::Committed);
+        assert_eq!(object.size_bytes(), Some(123));
+    }
+
+    #[test]
+    fn test_object_retention_can_only_be_extended() {
+        let mut object = create_test_object();
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 123)
+            .unwrap();
+        let until = OffsetDateTime::now_utc() + time::Duration::days(30);
+
+        object.extend_retention(until).unwrap();
+        assert!(matches!(
+            object.extend_retention(until - time::Duration::days(1)),
+            Err(DomainError::RetentionShortened(_))
+        ));
+        object
+            .extend_retention(until + time::Duration::days(1))
+            .unwrap();
+        assert_eq!(
+            object.retention_until(),
+            Some(until + time::Duration::days(1))
+        );
+    }
+
+    #[test]
+    fn test_object_legal_hold_locks_until_released() {
+        let mut object = create_test_object();
+        object
+            .commit(&ContentHash::from_str(&"a".repeat(64)).unwrap(), 123)
+            .unwrap();
+
+        object.set_legal_hold(true).unwrap();
+        assert!(matches!(
+            object.mark_for_deletion(),
+            Err(DomainError::ObjectLocked(_))
+        ));
+
+        object.set_legal_hold(false).unwrap();
+        assert!(!object.is_locked());
+        object.mark_for_deletion().unwrap();
+    }
+
     #[test]
     fn test_object_mark_deleted_valid() {
         let mut object = create_test_object();
@@ -460,6 +764,29 @@ mod tests {
         assert!(object.updated_at() >= object.created_at());
     }
 
+    #[test]
+    fn test_commit_within_limit() {
+        let content_hash = ContentHash::from_str(&"d".repeat(64)).unwrap();
+
+        let mut object = create_test_object();
+        let err = object
+            .commit_within_limit(&content_hash, 1025, 1024)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::SizeExceedsMaximum {
+                size: 1025,
+                max: 1024
+            }
+        ));
+        assert_eq!(object.status(), ObjectStatus::Writing);
+
+        object
+            .commit_within_limit(&content_hash, 1024, 1024)
+            .unwrap();
+        assert_eq!(object.size_bytes(), Some(1024));
+    }
+
     #[test]
     fn test_object_invalid_commit_parameters() {
         let mut object = create_test_object();
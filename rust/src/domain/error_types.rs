@@ -149,7 +149,9 @@ pub enum ValidationError {
     #[error("Field '{field}' is invalid: {message}")]
     Invalid { field: String, message: String },
 
-    #[error("Field '{field}' exceeds maximum length: {max} characters")]
+    /// `max` counts characters for strings, bytes for serialized documents
+    /// and entries for collections
+    #[error("Field '{field}' exceeds maximum length: {max}")]
     TooLong { field: String, max: usize },
 
     #[error("Field '{field}' is too short: minimum {min} characters")]
@@ -173,6 +175,22 @@ pub enum ValidationError {
     },
 }
 
+impl ValidationError {
+    /// Name of the offending field
+    pub fn field(&self) -> &str {
+        match self {
+            Self::Required { field }
+            | Self::Invalid { field, .. }
+            | Self::TooLong { field, .. }
+            | Self::TooShort { field, .. }
+            | Self::InvalidCharacters { field }
+            | Self::BlockedContent { field }
+            | Self::InvalidFormat { field, .. }
+            | Self::OutOfRange { field, .. } => field,
+        }
+    }
+}
+
 /// External service/API errors
 #[derive(Debug, thiserror::Error)]
 pub enum ExternalError {
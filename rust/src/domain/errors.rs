@@ -30,4 +30,13 @@ pub enum DomainError {
 
     #[error("Object size exceeds maximum allowed: {size} > {max}")]
     SizeExceedsMaximum { size: u64, max: u64 },
+
+    #[error("Object is locked: {0}")]
+    ObjectLocked(String),
+
+    #[error("Retention cannot be shortened: object is retained until {0}")]
+    RetentionShortened(String),
+
+    #[error("Object key already exists: {0}")]
+    AlreadyExists(String),
 }
@@ -1,3 +1,5 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -41,7 +43,44 @@ impl std::str::FromStr for ApiKeyId {
     }
 }
 
-/// API key value (the actual secret)
+/// Characters at the start of a key stored in clear to find its record
+pub const API_KEY_PREFIX_LEN: usize = 12;
+
+/// Scheme marker of salted SHA-256 hashes: `$sha256$<salt>$<digest>`, in hex
+const SHA256_SCHEME: &str = "$sha256$";
+
+/// Algorithm new API key hashes are computed with
+///
+/// Stored hashes name their algorithm, so a change applies to keys created
+/// or rotated afterwards while existing keys keep verifying.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApiKeyHashAlgorithm {
+    /// Argon2id, deliberately slow to compute
+    #[default]
+    Argon2,
+    /// Salted SHA-256, for request rates where Argon2 costs too much per
+    /// call; generated keys are random enough that a fast hash is not
+    /// brute-forceable
+    Sha256,
+}
+
+impl ApiKeyHashAlgorithm {
+    /// Parse `argon2` or `sha256`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "argon2" => Ok(Self::Argon2),
+            "sha256" => Ok(Self::Sha256),
+            other => Err(format!("expected 'argon2' or 'sha256', got '{other}'")),
+        }
+    }
+}
+
+/// Stored hash of an API key secret
+///
+/// Salted hashes are Argon2 PHC strings (`$argon2id$...`) or
+/// `$sha256$<salt>$<digest>`. A bare hex digest is an unsalted hash of a
+/// key stored before salting; it still verifies, and is replaced the next
+/// time the key is used.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ApiKeyValue(String);
@@ -57,13 +96,75 @@ impl ApiKeyValue {
             .collect()
     }
 
-    /// Hash a plaintext API key to create an ApiKeyValue
-    pub fn hash(plaintext: &str) -> Self {
+    /// Hash a plaintext API key with a fresh random salt
+    pub fn hash(plaintext: &str, algorithm: ApiKeyHashAlgorithm) -> Self {
+        use rand::RngExt;
+        let mut salt = [0u8; 16];
+        rand::rng().fill(&mut salt);
+
+        match algorithm {
+            ApiKeyHashAlgorithm::Argon2 => {
+                let salt = SaltString::encode_b64(&salt).expect("16 bytes is a valid salt");
+                let hash = Argon2::default()
+                    .hash_password(plaintext.as_bytes(), &salt)
+                    .expect("default Argon2 parameters are valid");
+                Self(hash.to_string())
+            }
+            ApiKeyHashAlgorithm::Sha256 => Self(format!(
+                "{}{}${}",
+                SHA256_SCHEME,
+                hex::encode(salt),
+                hex::encode(salted_sha256(&salt, plaintext))
+            )),
+        }
+    }
+
+    /// The start of a plaintext key that finds its record, if the key is
+    /// longer than that
+    pub fn prefix(plaintext: &str) -> Option<&str> {
+        if plaintext.len() <= API_KEY_PREFIX_LEN {
+            return None;
+        }
+        plaintext.get(..API_KEY_PREFIX_LEN)
+    }
+
+    /// Unsalted SHA-256 hex digest, which finds keys stored before salting
+    pub fn legacy_digest(plaintext: &str) -> String {
         use sha2::{Digest, Sha256};
-        let mut hasher = Sha256::new();
-        hasher.update(plaintext.as_bytes());
-        let hash_bytes = hasher.finalize();
-        Self(hex::encode(hash_bytes))
+        hex::encode(Sha256::digest(plaintext.as_bytes()))
+    }
+
+    /// Whether this is an unsalted hash of a key stored before salting
+    pub fn is_legacy(&self) -> bool {
+        !self.0.starts_with('$')
+    }
+
+    /// Check a plaintext key against this hash
+    ///
+    /// Digests are compared in constant time, so response times do not
+    /// reveal how close a guess came.
+    pub fn verify(&self, plaintext: &str) -> bool {
+        if self.0.starts_with("$argon2") {
+            // The verifier takes its parameters from the hash and compares
+            // in constant time
+            return PasswordHash::new(&self.0).is_ok_and(|hash| {
+                Argon2::default()
+                    .verify_password(plaintext.as_bytes(), &hash)
+                    .is_ok()
+            });
+        }
+
+        if let Some(salted) = self.0.strip_prefix(SHA256_SCHEME) {
+            let Some((salt, digest)) = salted.split_once('$') else {
+                return false;
+            };
+            let (Ok(salt), Ok(digest)) = (hex::decode(salt), hex::decode(digest)) else {
+                return false;
+            };
+            return constant_time_eq(&salted_sha256(&salt, plaintext), &digest);
+        }
+
+        constant_time_eq(Self::legacy_digest(plaintext).as_bytes(), self.0.as_bytes())
     }
 
     /// Create from existing string (for loading from DB)
@@ -82,6 +183,21 @@ impl ApiKeyValue {
     }
 }
 
+fn salted_sha256(salt: &[u8], plaintext: &str) -> Vec<u8> {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(plaintext.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Compare two byte strings without stopping at the first difference
+///
+/// Only the lengths, which are fixed per hash scheme, can leak.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
 impl std::fmt::Display for ApiKeyValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -234,12 +350,67 @@ mod tests {
         }
 
         #[test]
-        fn test_api_key_value_hash_produces_hex() {
+        fn test_api_key_value_hash_is_salted() {
             let plaintext = "test_key";
-            let hash = ApiKeyValue::hash(plaintext);
-            
-            assert_eq!(hash.as_str().len(), 64); // SHA-256 is 64 hex chars
-            assert!(hash.as_str().chars().all(|c| c.is_ascii_hexdigit()));
+
+            for algorithm in [ApiKeyHashAlgorithm::Argon2, ApiKeyHashAlgorithm::Sha256] {
+                let first = ApiKeyValue::hash(plaintext, algorithm);
+                let second = ApiKeyValue::hash(plaintext, algorithm);
+
+                assert_ne!(first, second, "Each hash should get its own salt");
+                assert!(!first.as_str().contains(plaintext));
+                assert!(!first.is_legacy());
+                assert!(first.verify(plaintext));
+                assert!(!first.verify("test_kez"));
+            }
+        }
+
+        #[test]
+        fn test_api_key_value_verifies_legacy_digest() {
+            let hash = ApiKeyValue::from_string(ApiKeyValue::legacy_digest("test_key"));
+
+            assert!(hash.is_legacy());
+            assert!(hash.verify("test_key"));
+            assert!(!hash.verify("other_key"));
+        }
+
+        #[test]
+        fn test_api_key_value_rejects_malformed_hashes() {
+            for stored in ["$sha256$zz$00", "$sha256$00", "$argon2id$garbage", ""] {
+                assert!(!ApiKeyValue::from_string(stored.to_string()).verify("test_key"));
+            }
+        }
+
+        #[test]
+        fn test_api_key_value_prefix() {
+            let key = ApiKeyValue::generate_plaintext();
+
+            assert_eq!(ApiKeyValue::prefix(&key), Some(&key[..API_KEY_PREFIX_LEN]));
+            assert_eq!(ApiKeyValue::prefix("short"), None);
+            assert_eq!(ApiKeyValue::prefix("abcdefghijkl"), None);
+        }
+
+        #[test]
+        fn test_constant_time_eq() {
+            assert!(constant_time_eq(b"same digest", b"same digest"));
+            // A difference in the last byte is found like one in the first
+            assert!(!constant_time_eq(b"same digest", b"same digesT"));
+            assert!(!constant_time_eq(b"same digest", b"Same digest"));
+            assert!(!constant_time_eq(b"same digest", b"same"));
+            assert!(constant_time_eq(b"", b""));
+        }
+
+        #[test]
+        fn test_api_key_hash_algorithm_parse() {
+            assert_eq!(
+                ApiKeyHashAlgorithm::parse("Argon2"),
+                Ok(ApiKeyHashAlgorithm::Argon2)
+            );
+            assert_eq!(
+                ApiKeyHashAlgorithm::parse("sha256"),
+                Ok(ApiKeyHashAlgorithm::Sha256)
+            );
+            assert!(ApiKeyHashAlgorithm::parse("md5").is_err());
         }
 
         #[test]
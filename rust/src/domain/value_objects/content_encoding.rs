@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Compression the stored bytes were uploaded with (`Content-Encoding`)
+///
+/// The content hash and size always describe the stored, encoded bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentEncoding {
+    Gzip,
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Read a `Content-Encoding` header value; `identity` means no encoding
+    pub fn from_header(value: &str) -> Result<Option<Self>, String> {
+        match value.trim() {
+            "" => Ok(None),
+            v if v.eq_ignore_ascii_case("identity") => Ok(None),
+            v => v.parse().map(Some),
+        }
+    }
+}
+
+impl std::fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentEncoding::Gzip => write!(f, "gzip"),
+            ContentEncoding::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+impl std::str::FromStr for ContentEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "gzip" | "x-gzip" => Ok(ContentEncoding::Gzip),
+            "zstd" => Ok(ContentEncoding::Zstd),
+            _ => Err(format!(
+                "Unsupported content encoding: {} (expected gzip or zstd)",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_content_encoding_round_trip() {
+        for encoding in [ContentEncoding::Gzip, ContentEncoding::Zstd] {
+            assert_eq!(
+                ContentEncoding::from_str(&encoding.to_string()).unwrap(),
+                encoding
+            );
+        }
+        assert_eq!(
+            ContentEncoding::from_str("X-GZIP").unwrap(),
+            ContentEncoding::Gzip
+        );
+    }
+
+    #[test]
+    fn test_content_encoding_from_header() {
+        assert_eq!(ContentEncoding::from_header("identity"), Ok(None));
+        assert_eq!(
+            ContentEncoding::from_header(" zstd "),
+            Ok(Some(ContentEncoding::Zstd))
+        );
+        assert!(ContentEncoding::from_header("br").is_err());
+        assert!(ContentEncoding::from_header("gzip, zstd").is_err());
+    }
+}
@@ -42,6 +42,87 @@ impl ContentHash {
     pub fn prefix(&self) -> &str {
         &self.0[0..2]
     }
+
+    /// First `len` hex characters, for short references; the whole hash
+    /// when `len` is 64 or more
+    pub fn short(&self, len: usize) -> &str {
+        &self.0[..len.min(self.0.len())]
+    }
+
+    /// First `len` hex characters as a prefix to look the hash up by, no
+    /// shorter than the shortest accepted prefix
+    pub fn short_prefix(&self, len: usize) -> ContentHashPrefix {
+        ContentHashPrefix(self.short(len.max(MIN_CONTENT_HASH_PREFIX_LEN)).to_string())
+    }
+
+    /// Whether this hash starts with `prefix`
+    pub fn matches(&self, prefix: &ContentHashPrefix) -> bool {
+        self.0.starts_with(prefix.as_str())
+    }
+}
+
+/// Shortest content hash prefix accepted as a reference
+pub const MIN_CONTENT_HASH_PREFIX_LEN: usize = 4;
+
+/// Leading hex characters of a content hash, like a git short hash
+///
+/// A full 64-character hash is a prefix of itself, so every lookup by
+/// prefix also accepts full hashes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentHashPrefix(String);
+
+impl ContentHashPrefix {
+    /// Create from 4 to 64 hex characters
+    pub fn from_hex(hex: &str) -> Result<Self, DomainError> {
+        if !(MIN_CONTENT_HASH_PREFIX_LEN..=64).contains(&hex.len()) {
+            return Err(DomainError::ValidationError {
+                field: "content_hash".to_string(),
+                message: format!(
+                    "must be {MIN_CONTENT_HASH_PREFIX_LEN} to 64 hex characters, got {}",
+                    hex.len()
+                ),
+            });
+        }
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(DomainError::ValidationError {
+                field: "content_hash".to_string(),
+                message: "must contain hex characters only".to_string(),
+            });
+        }
+
+        Ok(Self(hex.to_lowercase()))
+    }
+
+    /// Get hex string representation
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the prefix is a whole hash
+    pub fn is_full(&self) -> bool {
+        self.0.len() == 64
+    }
+}
+
+impl From<&ContentHash> for ContentHashPrefix {
+    fn from(hash: &ContentHash) -> Self {
+        Self(hash.as_hex().to_string())
+    }
+}
+
+impl std::fmt::Display for ContentHashPrefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for ContentHashPrefix {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
 }
 
 impl std::fmt::Display for ContentHash {
@@ -104,4 +185,33 @@ mod tests {
         let content_hash = ContentHash::from_hex(hex).unwrap();
         assert_eq!(content_hash.prefix(), "ab");
     }
+
+    #[test]
+    fn test_content_hash_short() {
+        let hex = "abcdef".to_string() + &"0".repeat(58);
+        let content_hash = ContentHash::from_hex(hex.clone()).unwrap();
+        assert_eq!(content_hash.short(6), "abcdef");
+        assert_eq!(content_hash.short(100), hex);
+    }
+
+    #[test]
+    fn test_content_hash_prefix_from_hex() {
+        let prefix = ContentHashPrefix::from_str("ABCD12").unwrap();
+        assert_eq!(prefix.as_str(), "abcd12");
+        assert!(!prefix.is_full());
+
+        let hash = ContentHash::from_hex("abcd12".to_string() + &"0".repeat(58)).unwrap();
+        assert!(hash.matches(&prefix));
+        assert!(ContentHashPrefix::from(&hash).is_full());
+
+        assert!(matches!(
+            ContentHashPrefix::from_str("abc"),
+            Err(DomainError::ValidationError { .. })
+        ));
+        assert!(ContentHashPrefix::from_str(&"a".repeat(65)).is_err());
+        assert!(matches!(
+            ContentHashPrefix::from_str("abcg"),
+            Err(DomainError::ValidationError { .. })
+        ));
+    }
 }
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::Namespace;
+
+/// Where an object key must be unique (`KEY_UNIQUENESS`)
+///
+/// Keys are always scoped to their tenant; the scope decides whether two
+/// namespaces of a tenant may hold the same key, and whether keys may repeat
+/// at all. Objects are then told apart by their ID.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyUniqueness {
+    /// One object per key in each namespace of a tenant
+    #[default]
+    Namespace,
+    /// One object per key across all namespaces of a tenant
+    Tenant,
+    /// Any number of objects per key
+    None,
+}
+
+impl KeyUniqueness {
+    /// Scope a key is unique within, besides its tenant: the namespace, the
+    /// whole tenant (empty), or `None` when keys may repeat
+    pub fn scope<'a>(&self, namespace: &'a Namespace) -> Option<&'a str> {
+        match self {
+            KeyUniqueness::Namespace => Some(namespace.as_str()),
+            KeyUniqueness::Tenant => Some(""),
+            KeyUniqueness::None => None,
+        }
+    }
+}
+
+impl std::fmt::Display for KeyUniqueness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyUniqueness::Namespace => write!(f, "namespace"),
+            KeyUniqueness::Tenant => write!(f, "tenant"),
+            KeyUniqueness::None => write!(f, "none"),
+        }
+    }
+}
+
+impl std::str::FromStr for KeyUniqueness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "namespace" => Ok(KeyUniqueness::Namespace),
+            "tenant" => Ok(KeyUniqueness::Tenant),
+            "none" => Ok(KeyUniqueness::None),
+            _ => Err(format!(
+                "Unknown key uniqueness scope: {} (expected namespace, tenant or none)",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_key_uniqueness_round_trip() {
+        for scope in [
+            KeyUniqueness::Namespace,
+            KeyUniqueness::Tenant,
+            KeyUniqueness::None,
+        ] {
+            assert_eq!(KeyUniqueness::from_str(&scope.to_string()).unwrap(), scope);
+        }
+        assert!(KeyUniqueness::from_str("global").is_err());
+    }
+
+    #[test]
+    fn test_key_uniqueness_scope() {
+        let namespace = Namespace::new("photos".to_string()).unwrap();
+        assert_eq!(KeyUniqueness::Namespace.scope(&namespace), Some("photos"));
+        assert_eq!(KeyUniqueness::Tenant.scope(&namespace), Some(""));
+        assert_eq!(KeyUniqueness::None.scope(&namespace), None);
+    }
+}
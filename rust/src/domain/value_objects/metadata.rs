@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 use utoipa::ToSchema;
 
+use crate::domain::errors::DomainError;
+
 /// Object kind/category for domain-specific metadata
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
@@ -166,6 +169,99 @@ impl ObjectMetadata {
     pub fn from_json(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
         serde_json::from_value(value.clone())
     }
+
+    /// Apply an RFC 7386 JSON Merge Patch and return the resulting metadata
+    ///
+    /// Fields the metadata schema does not define are rejected instead of
+    /// being silently dropped; `tags` values stay free-form.
+    pub fn merge_patch(&self, patch: &Value) -> Result<Self, DomainError> {
+        reject_unknown_fields(patch, &Self::schema_sample(), "")?;
+
+        let mut merged = self
+            .to_json()
+            .map_err(|e| invalid_metadata(e.to_string()))?;
+        apply_merge_patch(&mut merged, patch);
+
+        Self::from_json(&merged).map_err(|e| invalid_metadata(e.to_string()))
+    }
+
+    /// Metadata with every optional section present, used as the field schema
+    fn schema_sample() -> Value {
+        let mut sample = Self::new_model(
+            String::new(),
+            String::new(),
+            String::new(),
+            ModelFormat::Gguf,
+        );
+        sample.kb_doc = Self::new_kb_doc(String::new(), String::new()).kb_doc;
+        sample.origin = Some(OriginInfo {
+            source_system: None,
+            s3_bucket: None,
+            s3_key: None,
+            upload_ip: None,
+            upload_user: None,
+        });
+        serde_json::to_value(sample).unwrap_or_default()
+    }
+}
+
+fn invalid_metadata(message: String) -> DomainError {
+    DomainError::ValidationError {
+        field: "metadata".to_string(),
+        message,
+    }
+}
+
+/// RFC 7386: objects merge recursively, `null` removes a member and any
+/// other value replaces the target
+fn apply_merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                apply_merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Reject patch members that have no counterpart in `known`
+///
+/// An empty object in `known` (such as `tags`) accepts any members.
+fn reject_unknown_fields(patch: &Value, known: &Value, path: &str) -> Result<(), DomainError> {
+    let (Value::Object(patch), Value::Object(known)) = (patch, known) else {
+        return Ok(());
+    };
+    if known.is_empty() {
+        return Ok(());
+    }
+
+    for (key, value) in patch {
+        let field = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        match known.get(key) {
+            Some(known) => reject_unknown_fields(value, known, &field)?,
+            None => {
+                return Err(DomainError::ValidationError {
+                    field,
+                    message: "unknown metadata field".to_string(),
+                })
+            }
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -202,4 +298,64 @@ mod tests {
         let json = meta.to_json().unwrap();
         assert!(json["kb_doc"]["embedding_index_id"].is_string());
     }
+
+    #[test]
+    fn test_merge_patch_sets_and_removes_members() {
+        let mut meta = ObjectMetadata {
+            description: Some("old".to_string()),
+            ..Default::default()
+        };
+        meta.tags
+            .insert("stale".to_string(), serde_json::json!(true));
+
+        let merged = meta
+            .merge_patch(&serde_json::json!({
+                "description": null,
+                "summary_short": "quarterly report",
+                "tags": { "stale": null, "team": "finance" }
+            }))
+            .unwrap();
+
+        assert_eq!(merged.description, None);
+        assert_eq!(merged.summary_short.as_deref(), Some("quarterly report"));
+        assert!(!merged.tags.contains_key("stale"));
+        assert_eq!(merged.tags["team"], serde_json::json!("finance"));
+    }
+
+    #[test]
+    fn test_merge_patch_merges_nested_sections() {
+        let meta = ObjectMetadata::new_kb_doc("Refund Policy".to_string(), "pdf".to_string());
+
+        let merged = meta
+            .merge_patch(&serde_json::json!({ "kb_doc": { "language": "de" } }))
+            .unwrap();
+
+        let kb_doc = merged.kb_doc.unwrap();
+        assert_eq!(kb_doc.title, "Refund Policy");
+        assert_eq!(kb_doc.language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn test_merge_patch_rejects_unknown_fields() {
+        let meta = ObjectMetadata::default();
+
+        let top_level = meta.merge_patch(&serde_json::json!({ "colour": "red" }));
+        let nested = meta.merge_patch(&serde_json::json!({ "origin": { "bucket": "x" } }));
+
+        assert!(matches!(
+            top_level,
+            Err(DomainError::ValidationError { ref field, .. }) if field == "colour"
+        ));
+        assert!(matches!(
+            nested,
+            Err(DomainError::ValidationError { ref field, .. }) if field == "origin.bucket"
+        ));
+    }
+
+    #[test]
+    fn test_merge_patch_rejects_removing_required_fields() {
+        let result = ObjectMetadata::default().merge_patch(&serde_json::json!({ "kind": null }));
+
+        assert!(result.is_err());
+    }
 }
@@ -1,5 +1,7 @@
 pub mod api_key;
+mod content_encoding;
 mod content_hash;
+mod key_uniqueness;
 mod metadata;
 mod namespace;
 mod object_id;
@@ -8,7 +10,9 @@ mod storage_class;
 mod tenant_id;
 
 pub use api_key::*;
-pub use content_hash::ContentHash;
+pub use content_encoding::ContentEncoding;
+pub use content_hash::{ContentHash, ContentHashPrefix, MIN_CONTENT_HASH_PREFIX_LEN};
+pub use key_uniqueness::KeyUniqueness;
 pub use metadata::*;
 pub use namespace::Namespace;
 pub use object_id::ObjectId;
@@ -0,0 +1,5 @@
+mod noop_extractor;
+mod plain_text_extractor;
+
+pub use noop_extractor::NoopTextExtractor;
+pub use plain_text_extractor::PlainTextExtractor;
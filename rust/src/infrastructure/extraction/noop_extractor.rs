@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use crate::application::ports::{ExtractionError, TextExtractor};
+
+/// Extractor that handles no content types (extraction disabled)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopTextExtractor;
+
+#[async_trait]
+impl TextExtractor for NoopTextExtractor {
+    fn supports(&self, _content_type: &str) -> bool {
+        false
+    }
+
+    async fn extract(
+        &self,
+        _content_type: &str,
+        _content: &[u8],
+    ) -> Result<Option<String>, ExtractionError> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+
+use crate::application::ports::{ExtractionError, TextExtractor};
+
+/// Passes UTF-8 text content through unchanged
+///
+/// Handles `text/*` plus JSON and XML payloads.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PlainTextExtractor;
+
+#[async_trait]
+impl TextExtractor for PlainTextExtractor {
+    fn supports(&self, content_type: &str) -> bool {
+        let content_type = content_type.to_ascii_lowercase();
+        content_type.starts_with("text/")
+            || matches!(
+                content_type.as_str(),
+                "application/json" | "application/xml"
+            )
+            || content_type.ends_with("+json")
+            || content_type.ends_with("+xml")
+    }
+
+    async fn extract(
+        &self,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<Option<String>, ExtractionError> {
+        let text = std::str::from_utf8(content).map_err(|e| {
+            ExtractionError::UnsupportedEncoding(format!("{content_type} is not UTF-8: {e}"))
+        })?;
+        let text = text.trim();
+
+        Ok((!text.is_empty()).then(|| text.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supports_text_types() {
+        let extractor = PlainTextExtractor;
+
+        assert!(extractor.supports("text/plain"));
+        assert!(extractor.supports("text/markdown"));
+        assert!(extractor.supports("application/json"));
+        assert!(extractor.supports("application/ld+json"));
+        assert!(!extractor.supports("application/octet-stream"));
+        assert!(!extractor.supports("image/png"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_passes_text_through() {
+        let text = PlainTextExtractor
+            .extract("text/plain", b"  refund policy\n")
+            .await
+            .unwrap();
+
+        assert_eq!(text.as_deref(), Some("refund policy"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_empty_content_yields_none() {
+        let text = PlainTextExtractor
+            .extract("text/plain", b" \n")
+            .await
+            .unwrap();
+
+        assert!(text.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_extract_rejects_invalid_utf8() {
+        let result = PlainTextExtractor
+            .extract("text/plain", &[0xff, 0xfe, 0x00])
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ExtractionError::UnsupportedEncoding(_))
+        ));
+    }
+}
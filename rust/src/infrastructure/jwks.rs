@@ -0,0 +1,178 @@
+//! JSON Web Key Set fetching for JWT verification
+//!
+//! Keys are cached by `kid`. Each refresh replaces the cached set: keys the
+//! issuer no longer publishes are dropped immediately, and every key expires
+//! on its own if refreshes keep failing, so a JWKS outage ends in rejected
+//! tokens rather than trusting stale keys indefinitely.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use jsonwebtoken::jwk::{Jwk, PublicKeyUse};
+use jsonwebtoken::DecodingKey;
+use moka::future::Cache;
+use openidconnect::reqwest::Client as ReqwestClient;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Verification keys by `kid`
+pub type JwksCache = Cache<String, DecodingKey>;
+
+const MAX_KEYS: u64 = 100;
+
+#[derive(Debug, Error)]
+pub enum JwksError {
+    #[error("JWKS request failed: {0}")]
+    Http(#[from] openidconnect::reqwest::Error),
+
+    #[error("invalid JWKS document: {0}")]
+    Invalid(#[from] serde_json::Error),
+
+    #[error("JWKS contains no usable signing keys")]
+    NoKeys,
+}
+
+/// Raw key set; keys are parsed one by one so an unsupported key type does
+/// not reject the whole document
+#[derive(Deserialize)]
+struct RawKeySet {
+    keys: Vec<serde_json::Value>,
+}
+
+/// Create an empty key cache whose entries expire after `max_stale`
+pub fn new_cache(max_stale: Duration) -> Arc<JwksCache> {
+    Arc::new(
+        Cache::builder()
+            .max_capacity(MAX_KEYS)
+            .time_to_live(max_stale)
+            .build(),
+    )
+}
+
+/// Signing keys with a `kid` from a JWKS document
+pub fn parse_keys(body: &[u8]) -> Result<Vec<(String, DecodingKey)>, JwksError> {
+    let raw: RawKeySet = serde_json::from_slice(body)?;
+
+    let keys: Vec<(String, DecodingKey)> = raw
+        .keys
+        .into_iter()
+        .filter_map(|value| serde_json::from_value::<Jwk>(value).ok())
+        .filter(|jwk| !matches!(jwk.common.public_key_use, Some(PublicKeyUse::Encryption)))
+        .filter_map(|jwk| {
+            let kid = jwk.common.key_id.clone()?;
+            DecodingKey::from_jwk(&jwk).ok().map(|key| (kid, key))
+        })
+        .collect();
+
+    if keys.is_empty() {
+        return Err(JwksError::NoKeys);
+    }
+    Ok(keys)
+}
+
+/// Fetch the key set at `url` and replace the cached keys with it
+///
+/// On failure the cache is left untouched. Returns the number of keys cached.
+pub async fn refresh(
+    http_client: &ReqwestClient,
+    url: &str,
+    cache: &JwksCache,
+) -> Result<usize, JwksError> {
+    let body = http_client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let keys = parse_keys(&body)?;
+
+    Ok(replace_keys(cache, keys).await)
+}
+
+async fn replace_keys(cache: &JwksCache, keys: Vec<(String, DecodingKey)>) -> usize {
+    let current: HashSet<String> = keys.iter().map(|(kid, _)| kid.clone()).collect();
+
+    // Rotated-out keys stop verifying as soon as the issuer drops them
+    let removed: Vec<Arc<String>> = cache
+        .iter()
+        .map(|(kid, _)| kid)
+        .filter(|kid| !current.contains(kid.as_str()))
+        .collect();
+    for kid in removed {
+        cache.invalidate(kid.as_str()).await;
+    }
+
+    for (kid, key) in keys {
+        cache.insert(kid, key).await;
+    }
+    current.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSA_N: &str = "0r1f0-VgJwayLIEGkDf0aAYpnSdKeWUYoFz8Pu57iJICuOejQb8jR_PXbSbT61Qv97OtIMbiaiDkAWqCUIH1kAo916zblWd5j_d_K8N8wQvpaFZEd1hrHtfA656fSCgpFhxIRts0pikvLndYqIWIWHkjTSeXnHHKtqd6pkGzCzM_Uqb3v2DYy1rNs0d486flJHsrSeRU5sn7bLzzWIMRoGWHEaf7HXJPlamfp0DN_2kCXSEhZaQa7n6u_cnuDn7i6YW4IxzUCxYfFlaYDYJrUJRrqxKVbL__JLzUEi8Z33R6nH9eZh-0IF-1mfBgovPh7Q4y7dnikphHCO_n_TzIvQ";
+    const EC_X: &str = "LLLZz-xGvcjMwdmpsn_0jiWUKh7iSF_a5vD-FCjBZIk";
+    const EC_Y: &str = "TH1X7iJvJDFqNdEVvUM_5rf9qhSBjVowa_DRPJyi1kY";
+
+    fn rsa_key(kid: &str) -> serde_json::Value {
+        serde_json::json!({
+            "kty": "RSA", "kid": kid, "use": "sig", "alg": "RS256", "n": RSA_N, "e": "AQAB"
+        })
+    }
+
+    fn ec_key(kid: &str) -> serde_json::Value {
+        serde_json::json!({
+            "kty": "EC", "kid": kid, "crv": "P-256", "x": EC_X, "y": EC_Y
+        })
+    }
+
+    fn jwks(keys: Vec<serde_json::Value>) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({ "keys": keys })).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rsa_and_ec_keys() {
+        let keys = parse_keys(&jwks(vec![rsa_key("rsa-1"), ec_key("ec-1")])).unwrap();
+
+        let kids: Vec<&str> = keys.iter().map(|(kid, _)| kid.as_str()).collect();
+        assert_eq!(kids, vec!["rsa-1", "ec-1"]);
+    }
+
+    #[test]
+    fn test_parse_skips_unusable_keys() {
+        let mut no_kid = rsa_key("unused");
+        no_kid.as_object_mut().unwrap().remove("kid");
+        let mut encryption = rsa_key("enc-1");
+        encryption["use"] = "enc".into();
+        let unknown = serde_json::json!({ "kty": "XYZ", "kid": "unknown" });
+
+        let keys = parse_keys(&jwks(vec![no_kid, encryption, unknown, ec_key("ec-1")])).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].0, "ec-1");
+    }
+
+    #[test]
+    fn test_parse_rejects_set_without_keys() {
+        assert!(matches!(parse_keys(&jwks(vec![])), Err(JwksError::NoKeys)));
+        assert!(matches!(parse_keys(b"<html>"), Err(JwksError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_replace_drops_rotated_out_keys() {
+        let cache = new_cache(Duration::from_secs(60));
+        let first = parse_keys(&jwks(vec![rsa_key("old"), ec_key("kept")])).unwrap();
+        replace_keys(&cache, first).await;
+
+        let rotated = parse_keys(&jwks(vec![ec_key("kept"), rsa_key("new")])).unwrap();
+        assert_eq!(replace_keys(&cache, rotated).await, 2);
+
+        assert!(cache.get("old").await.is_none());
+        assert!(cache.get("kept").await.is_some());
+        assert!(cache.get("new").await.is_some());
+    }
+}
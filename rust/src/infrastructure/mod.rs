@@ -1,2 +1,6 @@
+pub mod extraction;
+pub mod jwks;
 pub mod persistence;
+pub mod scanning;
 pub mod storage;
+pub mod telemetry;
@@ -0,0 +1,390 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+
+use crate::application::ports::{BlobRepository, RepositoryError};
+use crate::domain::entities::Blob;
+use crate::domain::value_objects::{ContentHash, ContentHashPrefix, StorageClass, TenantId};
+
+/// Blob entries read per query while the filter is populated
+const POPULATE_PAGE_SIZE: i64 = 10_000;
+
+/// Sizing of [`FilteredBlobRepository`]'s filter
+#[derive(Debug, Clone)]
+pub struct BlobFilterConfig {
+    /// Blobs the filter is sized for; beyond it false positives grow
+    pub expected_blobs: u64,
+    /// Target share of absent hashes reported as possibly present
+    pub false_positive_rate: f64,
+    /// Most memory the filter may take
+    pub max_bytes: u64,
+}
+
+/// Point-in-time blob filter statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct BlobFilterStats {
+    /// Whether lookups consult the filter (false while it is populated)
+    pub ready: bool,
+    pub hashes: u64,
+    pub expected_blobs: u64,
+    pub size_bytes: u64,
+    /// Lookups answered without a query
+    pub skipped_lookups: u64,
+}
+
+/// Counting bloom filter of content hashes
+///
+/// Counters stick at their maximum, so an overflowed slot never reaches zero.
+struct HashFilter {
+    counters: Box<[AtomicU8]>,
+    probes: u32,
+    hashes: AtomicU64,
+}
+
+impl HashFilter {
+    fn new(config: &BlobFilterConfig) -> Result<Self, String> {
+        let rate = config.false_positive_rate;
+        if !(rate > 0.0 && rate < 1.0) {
+            return Err(format!("false positive rate {rate} is not between 0 and 1"));
+        }
+        let expected = config.expected_blobs.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let slots = (-expected * rate.ln() / (ln2 * ln2)).ceil() as u64;
+        if slots > config.max_bytes {
+            return Err(format!(
+                "{slots} bytes needed for {} blobs, above the {} byte limit",
+                config.expected_blobs, config.max_bytes
+            ));
+        }
+        let probes = ((slots as f64 / expected) * ln2).round().clamp(1.0, 16.0) as u32;
+
+        let slots = usize::try_from(slots).map_err(|e| e.to_string())?;
+        let mut counters = Vec::new();
+        counters
+            .try_reserve_exact(slots)
+            .map_err(|e| format!("cannot allocate {slots} bytes: {e}"))?;
+        counters.resize_with(slots, || AtomicU8::new(0));
+
+        Ok(Self {
+            counters: counters.into_boxed_slice(),
+            probes,
+            hashes: AtomicU64::new(0),
+        })
+    }
+
+    /// Slots of `content_hash`, by double hashing its (already uniform) bits
+    fn slots(&self, content_hash: &ContentHash) -> impl Iterator<Item = usize> + '_ {
+        let hex = content_hash.as_hex();
+        let word = |range: std::ops::Range<usize>| {
+            hex.get(range)
+                .and_then(|part| u64::from_str_radix(part, 16).ok())
+                .unwrap_or_default()
+        };
+        let (first, second) = (word(0..16), word(16..32) | 1);
+        let len = self.counters.len() as u64;
+        (0..u64::from(self.probes))
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % len) as usize)
+    }
+
+    fn may_contain(&self, content_hash: &ContentHash) -> bool {
+        self.slots(content_hash)
+            .all(|slot| self.counters[slot].load(Ordering::Relaxed) > 0)
+    }
+
+    /// Add a hash once; adding a hash it may already hold is a no-op
+    fn insert(&self, content_hash: &ContentHash) {
+        if self.may_contain(content_hash) {
+            return;
+        }
+        for slot in self.slots(content_hash) {
+            let _ = self.counters[slot]
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_add(1));
+        }
+        self.hashes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn remove(&self, content_hash: &ContentHash) {
+        if !self.may_contain(content_hash) {
+            return;
+        }
+        for slot in self.slots(content_hash) {
+            let _ = self.counters[slot].fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                (n != u8::MAX).then_some(n.saturating_sub(1))
+            });
+        }
+        let _ = self
+            .hashes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+}
+
+/// In-memory filter of known content hashes in front of another [`BlobRepository`]
+///
+/// Dedup lookups (`reference_existing`) for a hash the filter has never seen
+/// return `None` without a query; a possible hit is confirmed by the inner
+/// repository. Blob entries created or deleted through this repository
+/// update the filter. A miss it gets wrong, e.g. for a blob created by
+/// another instance, only costs the dedup shortcut: the upload streams its
+/// body as it would without the filter.
+///
+/// Lookups bypass the filter until [`Self::populate`] has loaded the
+/// existing hashes.
+pub struct FilteredBlobRepository {
+    inner: Arc<dyn BlobRepository>,
+    filter: HashFilter,
+    expected_blobs: u64,
+    ready: AtomicBool,
+    skipped_lookups: AtomicU64,
+}
+
+impl FilteredBlobRepository {
+    /// Wrap `inner`, or fail when the filter does not fit `config.max_bytes`
+    /// or cannot be allocated
+    pub fn new(inner: Arc<dyn BlobRepository>, config: BlobFilterConfig) -> Result<Self, String> {
+        Ok(Self {
+            filter: HashFilter::new(&config)?,
+            inner,
+            expected_blobs: config.expected_blobs,
+            ready: AtomicBool::new(false),
+            skipped_lookups: AtomicU64::new(0),
+        })
+    }
+
+    /// Load every blob entry's hash, then start answering lookups
+    ///
+    /// On error the filter stays unused and lookups keep querying.
+    pub async fn populate(&self) -> Result<u64, RepositoryError> {
+        let mut after = None;
+        loop {
+            let page = self.inner.list_hashes(after, POPULATE_PAGE_SIZE).await?;
+            for content_hash in &page {
+                self.filter.insert(content_hash);
+            }
+            if (page.len() as i64) < POPULATE_PAGE_SIZE {
+                break;
+            }
+            after = page.last().cloned();
+        }
+
+        let hashes = self.filter.hashes.load(Ordering::Relaxed);
+        if hashes > self.expected_blobs {
+            tracing::warn!(
+                hashes,
+                expected_blobs = self.expected_blobs,
+                "Blob filter holds more hashes than it is sized for; more lookups will query"
+            );
+        }
+        self.ready.store(true, Ordering::Release);
+        Ok(hashes)
+    }
+
+    pub fn stats(&self) -> BlobFilterStats {
+        BlobFilterStats {
+            ready: self.ready.load(Ordering::Acquire),
+            hashes: self.filter.hashes.load(Ordering::Relaxed),
+            expected_blobs: self.expected_blobs,
+            size_bytes: self.filter.counters.len() as u64,
+            skipped_lookups: self.skipped_lookups.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[async_trait]
+impl BlobRepository for FilteredBlobRepository {
+    async fn get_or_create(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+        size_bytes: u64,
+    ) -> Result<Blob, RepositoryError> {
+        let blob = self
+            .inner
+            .get_or_create(content_hash, storage_class, size_bytes)
+            .await?;
+        self.filter.insert(content_hash);
+        Ok(blob)
+    }
+
+    async fn reference_existing(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+        tenant_id: &TenantId,
+    ) -> Result<Option<Blob>, RepositoryError> {
+        if self.ready.load(Ordering::Acquire) && !self.filter.may_contain(content_hash) {
+            self.skipped_lookups.fetch_add(1, Ordering::Relaxed);
+            return Ok(None);
+        }
+        self.inner
+            .reference_existing(content_hash, storage_class, tenant_id)
+            .await
+    }
+
+    async fn increment_ref(&self, content_hash: &ContentHash) -> Result<(), RepositoryError> {
+        self.inner.increment_ref(content_hash).await
+    }
+
+    async fn decrement_ref(&self, content_hash: &ContentHash) -> Result<i32, RepositoryError> {
+        self.inner.decrement_ref(content_hash).await
+    }
+
+    async fn find_orphaned(&self, limit: i64) -> Result<Vec<Blob>, RepositoryError> {
+        self.inner.find_orphaned(limit).await
+    }
+
+    async fn delete(&self, content_hash: &ContentHash) -> Result<(), RepositoryError> {
+        self.inner.delete(content_hash).await?;
+        self.filter.remove(content_hash);
+        Ok(())
+    }
+
+    async fn move_storage_class(
+        &self,
+        content_hash: &ContentHash,
+        from: StorageClass,
+        to: StorageClass,
+    ) -> Result<bool, RepositoryError> {
+        self.inner.move_storage_class(content_hash, from, to).await
+    }
+
+    // Decides which blob files GC keeps, so a filter miss must not answer it
+    async fn find_known(
+        &self,
+        content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        self.inner.find_known(content_hashes).await
+    }
+
+    async fn list_hashes(
+        &self,
+        after: Option<ContentHash>,
+        limit: i64,
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        self.inner.list_hashes(after, limit).await
+    }
+
+    async fn list(
+        &self,
+        after: Option<ContentHash>,
+        orphaned_only: bool,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        self.inner.list(after, orphaned_only, limit).await
+    }
+
+    async fn find_by_prefix(
+        &self,
+        prefix: &ContentHashPrefix,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        self.inner.find_by_prefix(prefix, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockBlobRepository;
+    use sha2::{Digest, Sha256};
+    use uuid::Uuid;
+
+    fn hash(seed: &str) -> ContentHash {
+        ContentHash::from_hex(hex::encode(Sha256::digest(seed.as_bytes()))).unwrap()
+    }
+
+    fn config() -> BlobFilterConfig {
+        BlobFilterConfig {
+            expected_blobs: 1_000,
+            false_positive_rate: 0.01,
+            max_bytes: 1024 * 1024,
+        }
+    }
+
+    fn blob(content_hash: &ContentHash) -> Blob {
+        Blob::new(content_hash.clone(), StorageClass::Hot, 42)
+    }
+
+    #[tokio::test]
+    async fn test_unknown_hash_skips_lookup() {
+        let known = hash("known");
+        let mut inner = MockBlobRepository::new();
+        let page = vec![known.clone()];
+        inner
+            .expect_list_hashes()
+            .returning(move |_, _| Ok(page.clone()));
+        let confirmed = known.clone();
+        inner
+            .expect_reference_existing()
+            .withf(move |content_hash, _, _| *content_hash == confirmed)
+            .times(1)
+            .returning(|content_hash, _, _| Ok(Some(blob(content_hash))));
+        let repo = FilteredBlobRepository::new(Arc::new(inner), config()).unwrap();
+        let tenant_id = TenantId::new(Uuid::new_v4());
+
+        assert_eq!(repo.populate().await.unwrap(), 1);
+        let missing = repo
+            .reference_existing(&hash("missing"), StorageClass::Hot, &tenant_id)
+            .await
+            .unwrap();
+        let found = repo
+            .reference_existing(&known, StorageClass::Hot, &tenant_id)
+            .await
+            .unwrap();
+
+        assert!(missing.is_none());
+        assert!(found.is_some());
+        assert_eq!(repo.stats().skipped_lookups, 1);
+    }
+
+    #[tokio::test]
+    async fn test_created_and_deleted_blobs_update_filter() {
+        let content_hash = hash("new");
+        let mut inner = MockBlobRepository::new();
+        inner.expect_list_hashes().returning(|_, _| Ok(vec![]));
+        inner
+            .expect_get_or_create()
+            .returning(|content_hash, _, _| Ok(blob(content_hash)));
+        inner.expect_delete().returning(|_| Ok(()));
+        let repo = FilteredBlobRepository::new(Arc::new(inner), config()).unwrap();
+        repo.populate().await.unwrap();
+
+        repo.get_or_create(&content_hash, StorageClass::Hot, 42)
+            .await
+            .unwrap();
+        assert!(repo.filter.may_contain(&content_hash));
+
+        repo.delete(&content_hash).await.unwrap();
+        assert!(!repo.filter.may_contain(&content_hash));
+        assert_eq!(repo.stats().hashes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_lookups_query_until_populated() {
+        let mut inner = MockBlobRepository::new();
+        inner
+            .expect_reference_existing()
+            .times(1)
+            .returning(|_, _, _| Ok(None));
+        let repo = FilteredBlobRepository::new(Arc::new(inner), config()).unwrap();
+
+        repo.reference_existing(
+            &hash("any"),
+            StorageClass::Hot,
+            &TenantId::new(Uuid::new_v4()),
+        )
+        .await
+        .unwrap();
+        assert!(!repo.stats().ready);
+    }
+
+    #[test]
+    fn test_filter_over_memory_limit_is_refused() {
+        let config = BlobFilterConfig {
+            max_bytes: 1024,
+            ..config()
+        };
+
+        assert!(FilteredBlobRepository::new(Arc::new(MockBlobRepository::new()), config).is_err());
+    }
+}
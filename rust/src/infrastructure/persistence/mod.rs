@@ -1,13 +1,43 @@
+mod filtered_blob_repository;
+mod object_table_required;
+mod pool_monitor;
 mod postgres_api_key_repository;
 mod postgres_audit_repository;
 mod postgres_blob_repository;
+mod postgres_deletion_failure_repository;
+mod postgres_idempotency_repository;
+mod postgres_namespace_config_repository;
+mod postgres_namespace_deletion_repository;
 mod postgres_object_repository;
+mod postgres_object_share_repository;
+mod postgres_refcount_repository;
+mod postgres_scrub_repository;
+mod postgres_stats_repository;
+mod postgres_tenant_limit_provider;
 mod query_builder;
+#[cfg(feature = "redis")]
+mod redis_object_repository;
+mod retry;
 mod sessions;
 
+pub use filtered_blob_repository::{BlobFilterConfig, BlobFilterStats, FilteredBlobRepository};
+pub use object_table_required::ObjectTableRequired;
+pub use pool_monitor::{PoolHealth, PoolMonitor, PoolMonitorConfig, PoolStats};
 pub use postgres_api_key_repository::PostgresApiKeyRepository;
 pub use postgres_audit_repository::PostgresAuditRepository;
 pub use postgres_blob_repository::PostgresBlobRepository;
+pub use postgres_deletion_failure_repository::PostgresBlobDeletionFailureRepository;
+pub use postgres_idempotency_repository::PostgresIdempotencyRepository;
+pub use postgres_namespace_config_repository::PostgresNamespaceConfigRepository;
+pub use postgres_namespace_deletion_repository::PostgresNamespaceDeletionRepository;
 pub use postgres_object_repository::PostgresObjectRepository;
+pub use postgres_object_share_repository::PostgresObjectShareRepository;
+pub use postgres_refcount_repository::PostgresRefcountRepository;
+pub use postgres_scrub_repository::PostgresScrubRepository;
+pub use postgres_stats_repository::PostgresStatsRepository;
+pub use postgres_tenant_limit_provider::PostgresTenantLimitProvider;
 pub use query_builder::QueryBuilder;
+#[cfg(feature = "redis")]
+pub use redis_object_repository::RedisObjectRepository;
+pub use retry::{is_retryable, RetryPolicy};
 pub use sessions::EncryptedPostgresStore;
@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+
+use crate::application::dto::{DedupStats, TenantDedupStats};
+use crate::application::ports::{
+    LockedObject, NamespaceDeleteBatch, NamespaceDeletionRepository, NamespaceUsage, RefcountEntry,
+    RefcountRepository, RepositoryError, StatsRepository,
+};
+use crate::domain::value_objects::{ContentHash, Namespace, TenantId};
+
+/// Stands in for repositories that work on the Postgres `objects` table
+/// when object metadata lives in another store
+///
+/// Reconciling reference counts against an empty table would zero every
+/// count and let the garbage collector delete live blobs, and statistics
+/// would report no logical bytes at all, so these operations refuse instead.
+pub struct ObjectTableRequired {
+    metadata_store: String,
+}
+
+impl ObjectTableRequired {
+    pub fn new(metadata_store: impl Into<String>) -> Self {
+        Self {
+            metadata_store: metadata_store.into(),
+        }
+    }
+
+    fn unavailable<T>(&self, operation: &str) -> Result<T, RepositoryError> {
+        Err(RepositoryError::Store(format!(
+            "{operation} requires METADATA_STORE=postgres (objects are in {})",
+            self.metadata_store
+        )))
+    }
+}
+
+#[async_trait]
+impl RefcountRepository for ObjectTableRequired {
+    async fn scan_batch(
+        &self,
+        _after: Option<ContentHash>,
+        _limit: i64,
+    ) -> Result<Vec<RefcountEntry>, RepositoryError> {
+        self.unavailable("Refcount reconciliation")
+    }
+
+    async fn set_ref_count(
+        &self,
+        _content_hash: &ContentHash,
+        _expected: i64,
+        _ref_count: i64,
+    ) -> Result<bool, RepositoryError> {
+        self.unavailable("Refcount reconciliation")
+    }
+
+    async fn load_checkpoint(&self, _task: &str) -> Result<Option<ContentHash>, RepositoryError> {
+        self.unavailable("Refcount reconciliation")
+    }
+
+    async fn save_checkpoint(
+        &self,
+        _task: &str,
+        _cursor: Option<ContentHash>,
+    ) -> Result<(), RepositoryError> {
+        self.unavailable("Refcount reconciliation")
+    }
+}
+
+#[async_trait]
+impl NamespaceDeletionRepository for ObjectTableRequired {
+    async fn usage(
+        &self,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+    ) -> Result<NamespaceUsage, RepositoryError> {
+        self.unavailable("Namespace deletion")
+    }
+
+    async fn find_locked(
+        &self,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+        _limit: i64,
+    ) -> Result<Vec<LockedObject>, RepositoryError> {
+        self.unavailable("Namespace deletion")
+    }
+
+    async fn delete_batch(
+        &self,
+        _namespace: &Namespace,
+        _tenant_id: &TenantId,
+        _limit: i64,
+    ) -> Result<NamespaceDeleteBatch, RepositoryError> {
+        self.unavailable("Namespace deletion")
+    }
+}
+
+#[async_trait]
+impl StatsRepository for ObjectTableRequired {
+    async fn global_usage(&self) -> Result<DedupStats, RepositoryError> {
+        self.unavailable("Deduplication statistics")
+    }
+
+    async fn usage_by_tenant(&self) -> Result<Vec<TenantDedupStats>, RepositoryError> {
+        self.unavailable("Deduplication statistics")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_refuses_to_reconcile() {
+        let repo = ObjectTableRequired::new("redis");
+
+        let error = repo.scan_batch(None, 100).await.unwrap_err();
+
+        assert!(matches!(error, RepositoryError::Store(msg) if msg.contains("METADATA_STORE")));
+    }
+
+    #[tokio::test]
+    async fn test_refuses_stats() {
+        let repo = ObjectTableRequired::new("redis");
+
+        let error = repo.global_usage().await.unwrap_err();
+
+        assert!(matches!(error, RepositoryError::Store(msg) if msg.contains("METADATA_STORE")));
+    }
+}
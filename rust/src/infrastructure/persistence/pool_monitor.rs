@@ -0,0 +1,265 @@
+use serde::Serialize;
+use sqlx::PgPool;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Health of the database connection pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PoolHealth {
+    Healthy,
+    /// Connections in use at or above the saturation threshold
+    Degraded,
+    /// Probes failed repeatedly; the pool is being reconnected
+    Dead,
+}
+
+impl fmt::Display for PoolHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolHealth::Healthy => write!(f, "healthy"),
+            PoolHealth::Degraded => write!(f, "degraded"),
+            PoolHealth::Dead => write!(f, "dead"),
+        }
+    }
+}
+
+/// Point-in-time connection pool statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolStats {
+    pub health: PoolHealth,
+    /// Open connections, in use or idle
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max_connections: u32,
+    /// How long the last probe waited for a connection; requests queue for
+    /// about as long while the pool is saturated
+    pub acquire_wait_ms: u64,
+    /// Probes failed in a row
+    pub consecutive_failures: u32,
+    /// Times the pool was reconnected since startup
+    pub reconnects: u64,
+}
+
+/// Thresholds for [`PoolMonitor`]
+#[derive(Debug, Clone)]
+pub struct PoolMonitorConfig {
+    /// Time between probes
+    pub interval: Duration,
+    /// Longest a probe waits for a connection and its query
+    pub probe_timeout: Duration,
+    /// Percentage of `max_connections` in use that degrades the pool
+    pub saturation_percent: u32,
+    /// Failed probes in a row after which the pool is dead
+    pub dead_after_failures: u32,
+}
+
+impl Default for PoolMonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            probe_timeout: Duration::from_secs(30),
+            saturation_percent: 90,
+            dead_after_failures: 3,
+        }
+    }
+}
+
+/// Watches the connection pool: samples its usage, probes it with a query
+/// and reconnects it once it is dead, instead of failing every request
+/// until a restart
+pub struct PoolMonitor {
+    pool: Arc<PgPool>,
+    max_connections: u32,
+    config: PoolMonitorConfig,
+    acquire_wait_ms: AtomicU64,
+    consecutive_failures: AtomicU32,
+    reconnects: AtomicU64,
+    /// Health at the last probe, to log transitions
+    last_health: Mutex<PoolHealth>,
+}
+
+impl PoolMonitor {
+    pub fn new(pool: Arc<PgPool>, max_connections: u32, config: PoolMonitorConfig) -> Self {
+        Self {
+            pool,
+            max_connections,
+            config,
+            acquire_wait_ms: AtomicU64::new(0),
+            consecutive_failures: AtomicU32::new(0),
+            reconnects: AtomicU64::new(0),
+            last_health: Mutex::new(PoolHealth::Healthy),
+        }
+    }
+
+    /// Current usage together with the outcome of the last probe
+    pub fn stats(&self) -> PoolStats {
+        let size = self.pool.size();
+        let idle = u32::try_from(self.pool.num_idle())
+            .unwrap_or(u32::MAX)
+            .min(size);
+        let in_use = size - idle;
+        let consecutive_failures = self.consecutive_failures.load(Ordering::Relaxed);
+
+        PoolStats {
+            health: classify(
+                in_use,
+                self.max_connections,
+                consecutive_failures,
+                &self.config,
+            ),
+            size,
+            idle,
+            in_use,
+            max_connections: self.max_connections,
+            acquire_wait_ms: self.acquire_wait_ms.load(Ordering::Relaxed),
+            consecutive_failures,
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Probe the pool every interval until the process exits
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.check().await;
+        }
+    }
+
+    /// Probe the pool once, log a change of health and reconnect a dead pool
+    pub async fn check(&self) -> PoolStats {
+        match self.probe().await {
+            Ok(wait) => {
+                self.acquire_wait_ms
+                    .store(wait.as_millis() as u64, Ordering::Relaxed);
+                self.consecutive_failures.store(0, Ordering::Relaxed);
+            }
+            Err(e) => {
+                let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+                tracing::warn!(failures, "Database pool probe failed: {}", e);
+            }
+        }
+
+        let stats = self.stats();
+        self.log_transition(&stats);
+        if stats.health == PoolHealth::Dead {
+            self.reconnect().await;
+        }
+        stats
+    }
+
+    /// Wait for a connection and run a query on it; returns the wait
+    async fn probe(&self) -> Result<Duration, String> {
+        let probe = async {
+            let start = Instant::now();
+            let mut conn = self.pool.acquire().await?;
+            let wait = start.elapsed();
+            sqlx::query("SELECT 1").execute(&mut *conn).await?;
+            Ok::<_, sqlx::Error>(wait)
+        };
+
+        match tokio::time::timeout(self.config.probe_timeout, probe).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(_) => Err(format!(
+                "timed out after {} seconds",
+                self.config.probe_timeout.as_secs()
+            )),
+        }
+    }
+
+    fn log_transition(&self, stats: &PoolStats) {
+        let mut last_health = self
+            .last_health
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *last_health == stats.health {
+            return;
+        }
+
+        let previous = std::mem::replace(&mut *last_health, stats.health);
+        match stats.health {
+            PoolHealth::Healthy => tracing::info!(
+                in_use = stats.in_use,
+                max_connections = stats.max_connections,
+                "Database pool recovered ({} -> healthy)",
+                previous
+            ),
+            PoolHealth::Degraded => tracing::warn!(
+                in_use = stats.in_use,
+                max_connections = stats.max_connections,
+                acquire_wait_ms = stats.acquire_wait_ms,
+                "Database pool saturated ({} -> degraded)",
+                previous
+            ),
+            PoolHealth::Dead => tracing::error!(
+                failures = stats.consecutive_failures,
+                "Database pool unreachable ({} -> dead)",
+                previous
+            ),
+        }
+    }
+
+    /// Close the idle connections, so the next requests open fresh ones
+    /// instead of reusing connections to a server that went away
+    async fn reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+        let mut closed = 0;
+        // Bounded by the pool size: connections opened meanwhile are fresh
+        for _ in 0..self.pool.size() {
+            let Some(conn) = self.pool.try_acquire() else {
+                break;
+            };
+            let _ = conn.close().await;
+            closed += 1;
+        }
+        tracing::warn!(closed, "Reconnecting database pool");
+    }
+}
+
+/// Failed probes take precedence: a dead pool has no meaningful usage
+fn classify(
+    in_use: u32,
+    max_connections: u32,
+    consecutive_failures: u32,
+    config: &PoolMonitorConfig,
+) -> PoolHealth {
+    if consecutive_failures >= config.dead_after_failures {
+        PoolHealth::Dead
+    } else if max_connections > 0
+        && u64::from(in_use) * 100
+            >= u64::from(max_connections) * u64::from(config.saturation_percent)
+    {
+        PoolHealth::Degraded
+    } else {
+        PoolHealth::Healthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_usage() {
+        let config = PoolMonitorConfig::default();
+
+        assert_eq!(classify(0, 20, 0, &config), PoolHealth::Healthy);
+        assert_eq!(classify(17, 20, 0, &config), PoolHealth::Healthy);
+        assert_eq!(classify(18, 20, 0, &config), PoolHealth::Degraded);
+        assert_eq!(classify(20, 20, 0, &config), PoolHealth::Degraded);
+    }
+
+    #[test]
+    fn test_classify_repeated_failures_as_dead() {
+        let config = PoolMonitorConfig::default();
+
+        assert_eq!(classify(0, 20, 2, &config), PoolHealth::Healthy);
+        assert_eq!(classify(0, 20, 3, &config), PoolHealth::Dead);
+        assert_eq!(classify(20, 20, 3, &config), PoolHealth::Dead);
+    }
+}
@@ -1,37 +1,129 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use moka::future::Cache;
+use sha2::{Digest, Sha256};
+use sqlx::postgres::PgRow;
+use sqlx::{AssertSqlSafe, PgPool, Row};
+use std::time::Duration;
 
 use crate::application::ports::{ApiKeyRepository, ApiKeyRepositoryError};
 use crate::domain::{
     entities::{ApiKey, ApiKeyDbData},
-    value_objects::{ApiKeyId, ApiKeyPermissions, ApiKeyValue},
+    value_objects::{ApiKeyHashAlgorithm, ApiKeyId, ApiKeyPermissions, ApiKeyValue},
 };
 
+/// Columns of an API key record
+const API_KEY_COLUMNS: &str = r#"
+    id, api_key, key_prefix, tenant_id, name, description,
+    permissions, is_active, expires_at,
+    created_at, updated_at, last_used_at
+"#;
+
+/// How long a verified key is trusted without checking its hash again
+const VERIFIED_KEY_TTL: Duration = Duration::from_secs(30);
+
+/// Verified keys held at once
+const VERIFIED_KEY_CAPACITY: u64 = 10_000;
+
 /// PostgreSQL implementation of API key repository
+///
+/// Keys that passed verification are cached by the SHA-256 of the
+/// plaintext, so a client sending the same key on every request pays for
+/// Argon2 once per TTL. Updating, rotating or deleting a key evicts it here
+/// at once; other replicas drop it when the TTL runs out.
 pub struct PostgresApiKeyRepository {
     pool: PgPool,
+    hash_algorithm: ApiKeyHashAlgorithm,
+    verified: Cache<String, ApiKey>,
 }
 
 impl PostgresApiKeyRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            hash_algorithm: ApiKeyHashAlgorithm::default(),
+            verified: Cache::builder()
+                .max_capacity(VERIFIED_KEY_CAPACITY)
+                .time_to_live(VERIFIED_KEY_TTL)
+                .support_invalidation_closures()
+                .build(),
+        }
+    }
+
+    /// Drop any cached verification of a key
+    fn forget(&self, id: &ApiKeyId) {
+        let id = *id;
+        // Only fails when invalidation closures are not enabled
+        let _ = self
+            .verified
+            .invalidate_entries_if(move |_, api_key| *api_key.id() == id);
+    }
+
+    /// Algorithm unsalted legacy hashes are replaced with on use
+    pub fn with_hash_algorithm(mut self, hash_algorithm: ApiKeyHashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// Active key record whose prefix, or for keys stored before salting
+    /// whose unsalted digest, matches a plaintext key
+    async fn find_candidate(&self, key: &str) -> Result<Option<ApiKey>, ApiKeyRepositoryError> {
+        if let Some(prefix) = ApiKeyValue::prefix(key) {
+            let row = sqlx::query(AssertSqlSafe(format!(
+                "SELECT {API_KEY_COLUMNS} FROM api_keys WHERE key_prefix = $1 AND is_active = true"
+            )))
+            .bind(prefix)
+            .fetch_optional(&self.pool)
+            .await?;
+            if let Some(row) = row {
+                return api_key_from_row(&row).map(Some);
+            }
+        }
+
+        let row = sqlx::query(AssertSqlSafe(format!(
+            "SELECT {API_KEY_COLUMNS} FROM api_keys \
+             WHERE key_prefix IS NULL AND api_key = $1 AND is_active = true"
+        )))
+        .bind(ApiKeyValue::legacy_digest(key))
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(api_key_from_row).transpose()
     }
 }
 
+fn api_key_from_row(row: &PgRow) -> Result<ApiKey, ApiKeyRepositoryError> {
+    let permissions: ApiKeyPermissions = serde_json::from_value(row.try_get("permissions")?)?;
+
+    Ok(ApiKey::from_db(ApiKeyDbData {
+        id: ApiKeyId::from_uuid(row.try_get("id")?),
+        api_key: ApiKeyValue::from_string(row.try_get("api_key")?),
+        key_prefix: row.try_get("key_prefix")?,
+        tenant_id: row.try_get("tenant_id")?,
+        name: row.try_get("name")?,
+        description: row.try_get("description")?,
+        permissions,
+        is_active: row.try_get("is_active")?,
+        expires_at: row.try_get("expires_at")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        last_used_at: row.try_get("last_used_at")?,
+    }))
+}
+
 #[async_trait]
 impl ApiKeyRepository for PostgresApiKeyRepository {
     async fn create(&self, api_key: ApiKey) -> Result<(), ApiKeyRepositoryError> {
         sqlx::query(
             r#"
             INSERT INTO api_keys (
-                id, api_key, tenant_id, name, description,
+                id, api_key, key_prefix, tenant_id, name, description,
                 permissions, is_active, expires_at, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
         .bind(api_key.id().as_uuid())
         .bind(api_key.api_key().as_str())
+        .bind(api_key.key_prefix())
         .bind(api_key.tenant_id())
         .bind(api_key.name())
         .bind(api_key.description())
@@ -47,89 +139,53 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
     }
 
     async fn find_by_id(&self, id: &ApiKeyId) -> Result<Option<ApiKey>, ApiKeyRepositoryError> {
-        let row = sqlx::query(
-            r#"
-            SELECT
-                id, api_key, tenant_id, name, description,
-                permissions, is_active, expires_at, 
-                created_at, updated_at, last_used_at
-            FROM api_keys
-            WHERE id = $1
-            "#,
-        )
+        let row = sqlx::query(AssertSqlSafe(format!(
+            "SELECT {API_KEY_COLUMNS} FROM api_keys WHERE id = $1"
+        )))
         .bind(id.as_uuid())
         .fetch_optional(&self.pool)
         .await?;
 
-        match row {
-            Some(row) => {
-                let permissions: ApiKeyPermissions =
-                    serde_json::from_value(row.try_get("permissions")?)?;
-                let api_key_value = ApiKeyValue::from_string(row.try_get("api_key")?);
-
-                let db_data = ApiKeyDbData {
-                    id: ApiKeyId::from_uuid(row.try_get("id")?),
-                    api_key: api_key_value,
-                    tenant_id: row.try_get("tenant_id")?,
-                    name: row.try_get("name")?,
-                    description: row.try_get("description")?,
-                    permissions,
-                    is_active: row.try_get("is_active")?,
-                    expires_at: row.try_get("expires_at")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
-                    last_used_at: row.try_get("last_used_at")?,
-                };
-
-                let api_key = ApiKey::from_db(db_data);
-
-                Ok(Some(api_key))
-            }
-            None => Ok(None),
-        }
+        row.as_ref().map(api_key_from_row).transpose()
     }
 
     async fn find_by_key(&self, key: &str) -> Result<Option<ApiKey>, ApiKeyRepositoryError> {
-        let row = sqlx::query(
-            r#"
-            SELECT
-                id, api_key, tenant_id, name, description,
-                permissions, is_active, expires_at, 
-                created_at, updated_at, last_used_at
-            FROM api_keys
-            WHERE api_key = $1 AND is_active = true
-            "#,
-        )
-        .bind(key)
-        .fetch_optional(&self.pool)
-        .await?;
+        let digest = hex::encode(Sha256::digest(key.as_bytes()));
+        if let Some(api_key) = self.verified.get(&digest).await {
+            return Ok(Some(api_key));
+        }
+
+        let Some(api_key) = self.find_candidate(key).await? else {
+            return Ok(None);
+        };
 
-        match row {
-            Some(row) => {
-                let permissions: ApiKeyPermissions =
-                    serde_json::from_value(row.try_get("permissions")?)?;
-                let api_key_value = ApiKeyValue::from_string(row.try_get("api_key")?);
-
-                let db_data = ApiKeyDbData {
-                    id: ApiKeyId::from_uuid(row.try_get("id")?),
-                    api_key: api_key_value,
-                    tenant_id: row.try_get("tenant_id")?,
-                    name: row.try_get("name")?,
-                    description: row.try_get("description")?,
-                    permissions,
-                    is_active: row.try_get("is_active")?,
-                    expires_at: row.try_get("expires_at")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
-                    last_used_at: row.try_get("last_used_at")?,
-                };
-
-                let api_key = ApiKey::from_db(db_data);
-
-                Ok(Some(api_key))
+        // Argon2 is slow by design, so verify off the async workers. A key
+        // still under an unsalted hash is rehashed while the plaintext is
+        // at hand.
+        let key = key.to_string();
+        let hash_algorithm = self.hash_algorithm;
+        let verified = tokio::task::spawn_blocking(move || {
+            if !api_key.verify_secret(&key) {
+                return None;
             }
-            None => Ok(None),
+            let legacy = api_key.api_key().is_legacy();
+            let mut api_key = api_key;
+            if legacy {
+                api_key.rehash_secret(&key, hash_algorithm);
+            }
+            Some((api_key, legacy))
+        })
+        .await
+        .map_err(|e| ApiKeyRepositoryError::Internal(format!("Key verification failed: {e}")))?;
+
+        let Some((api_key, rehashed)) = verified else {
+            return Ok(None);
+        };
+        if rehashed {
+            self.update_secret(&api_key).await?;
         }
+        self.verified.insert(digest, api_key.clone()).await;
+        Ok(Some(api_key))
     }
 
     async fn list_by_tenant(
@@ -138,48 +194,19 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         limit: i64,
         offset: i64,
     ) -> Result<Vec<ApiKey>, ApiKeyRepositoryError> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                id, api_key, tenant_id, name, description,
-                permissions, is_active, expires_at, 
-                created_at, updated_at, last_used_at
-            FROM api_keys
-            WHERE tenant_id = $1
-            ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3
-            "#,
-        )
+        let rows = sqlx::query(AssertSqlSafe(format!(
+            "SELECT {API_KEY_COLUMNS} FROM api_keys \
+             WHERE tenant_id = $1 \
+             ORDER BY created_at DESC \
+             LIMIT $2 OFFSET $3"
+        )))
         .bind(tenant_id)
         .bind(limit)
         .bind(offset)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut api_keys = Vec::new();
-        for row in rows {
-            let permissions: ApiKeyPermissions =
-                serde_json::from_value(row.try_get("permissions")?)?;
-            let api_key_value = ApiKeyValue::from_string(row.try_get("api_key")?);
-
-            let db_data = ApiKeyDbData {
-                id: ApiKeyId::from_uuid(row.try_get("id")?),
-                api_key: api_key_value,
-                tenant_id: row.try_get("tenant_id")?,
-                name: row.try_get("name")?,
-                description: row.try_get("description")?,
-                permissions,
-                is_active: row.try_get("is_active")?,
-                expires_at: row.try_get("expires_at")?,
-                created_at: row.try_get("created_at")?,
-                updated_at: row.try_get("updated_at")?,
-                last_used_at: row.try_get("last_used_at")?,
-            };
-
-            api_keys.push(ApiKey::from_db(db_data));
-        }
-
-        Ok(api_keys)
+        rows.iter().map(api_key_from_row).collect()
     }
 
     async fn count_by_tenant(&self, tenant_id: &str) -> Result<i64, ApiKeyRepositoryError> {
@@ -223,6 +250,30 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         .bind(api_key.last_used_at().cloned())
         .execute(&self.pool)
         .await?;
+        self.forget(api_key.id());
+
+        Ok(())
+    }
+
+    async fn update_secret(&self, api_key: &ApiKey) -> Result<(), ApiKeyRepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET api_key = $2, key_prefix = $3, updated_at = $4
+            WHERE id = $1
+            "#,
+        )
+        .bind(api_key.id().as_uuid())
+        .bind(api_key.api_key().as_str())
+        .bind(api_key.key_prefix())
+        .bind(*api_key.updated_at())
+        .execute(&self.pool)
+        .await?;
+        self.forget(api_key.id());
+
+        if result.rows_affected() == 0 {
+            return Err(ApiKeyRepositoryError::NotFound(api_key.id().to_string()));
+        }
 
         Ok(())
     }
@@ -237,6 +288,7 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         .bind(id.as_uuid())
         .execute(&self.pool)
         .await?;
+        self.forget(id);
 
         if result.rows_affected() == 0 {
             return Err(ApiKeyRepositoryError::NotFound(id.to_string()));
@@ -269,6 +321,7 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         )
         .execute(&self.pool)
         .await?;
+        self.verified.invalidate_all();
 
         Ok(result.rows_affected() as i64)
     }
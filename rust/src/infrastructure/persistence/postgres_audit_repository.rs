@@ -1,5 +1,7 @@
 use crate::api::middleware::audit::AuditLogEntry;
+use crate::application::pagination::{PageLimits, Pagination};
 use crate::application::ports::{AuditQueryFilter, AuditRepository, AuditRepositoryError};
+use crate::infrastructure::persistence::query_builder::QueryBuilder;
 use async_trait::async_trait;
 use sqlx::{PgPool, Row};
 
@@ -56,79 +58,14 @@ impl AuditRepository for PostgresAuditRepository {
         limit: i64,
         offset: i64,
     ) -> Result<Vec<AuditLogEntry>, AuditRepositoryError> {
-        let mut query_builder = sqlx::QueryBuilder::new("SELECT * FROM audit_logs WHERE 1=1");
-
-        // Build WHERE clauses based on filter
-        if let Some(event_types) = &filter.event_types {
-            if !event_types.is_empty() {
-                query_builder.push(" AND event_type = ANY(");
-                query_builder.push_bind(event_types.as_slice());
-                query_builder.push(")");
-            }
-        }
-
-        if let Some(user_id) = &filter.user_id {
-            query_builder.push(" AND user_id = ");
-            query_builder.push_bind(user_id);
-        }
-
-        if let Some(tenant_id) = &filter.tenant_id {
-            query_builder.push(" AND tenant_id = ");
-            query_builder.push_bind(tenant_id);
-        }
-
-        if let Some(api_key_id) = &filter.api_key_id {
-            query_builder.push(" AND api_key_id = ");
-            query_builder.push_bind(api_key_id);
-        }
-
-        if let Some(ip_address) = &filter.ip_address {
-            query_builder.push(" AND ip_address = CAST(");
-            query_builder.push_bind(ip_address.clone());
-            query_builder.push(" AS inet)");
-        }
-
-        if let Some(path_pattern) = &filter.path_pattern {
-            query_builder.push(" AND path LIKE ");
-            query_builder.push_bind(format!("%{}%", path_pattern));
-        }
-
-        if let Some(min_code) = filter.status_code_min {
-            query_builder.push(" AND status_code >= ");
-            query_builder.push_bind(min_code);
-        }
-
-        if let Some(max_code) = filter.status_code_max {
-            query_builder.push(" AND status_code <= ");
-            query_builder.push_bind(max_code);
-        }
-
-        if let Some(from_ts) = filter.from_timestamp {
-            query_builder.push(" AND timestamp >= ");
-            query_builder.push_bind(from_ts);
-        }
-
-        if let Some(to_ts) = filter.to_timestamp {
-            query_builder.push(" AND timestamp <= ");
-            query_builder.push_bind(to_ts);
-        }
+        let page = Pagination::new(Some(limit), Some(offset), PageLimits::AUDIT_LOGS);
 
-        if let Some(has_error) = filter.has_error {
-            if has_error {
-                query_builder.push(" AND error_message IS NOT NULL");
-            } else {
-                query_builder.push(" AND error_message IS NULL");
-            }
-        }
+        let mut query_builder = sqlx::QueryBuilder::new("SELECT * FROM audit_logs WHERE 1=1");
+        QueryBuilder::push_audit_filter_conditions(&mut query_builder, &filter);
 
         // Order by timestamp descending (most recent first)
         query_builder.push(" ORDER BY timestamp DESC");
-
-        // Add pagination
-        query_builder.push(" LIMIT ");
-        query_builder.push_bind(limit);
-        query_builder.push(" OFFSET ");
-        query_builder.push_bind(offset);
+        QueryBuilder::push_page(&mut query_builder, page.limit, page.offset);
 
         let query = query_builder.build();
 
@@ -178,6 +115,12 @@ impl AuditRepository for PostgresAuditRepository {
                 "backup_operation" => {
                     crate::api::middleware::audit::AuditEventType::BackupOperation
                 }
+                "ghost_object_detected" => {
+                    crate::api::middleware::audit::AuditEventType::GhostObjectDetected
+                }
+                "garbage_collection" => {
+                    crate::api::middleware::audit::AuditEventType::GarbageCollection
+                }
                 _ => continue, // Skip unknown event types
             };
 
@@ -216,68 +159,7 @@ impl AuditRepository for PostgresAuditRepository {
         let mut query_builder =
             sqlx::QueryBuilder::new("SELECT COUNT(*) FROM audit_logs WHERE 1=1");
 
-        // Apply same filters as query method
-        if let Some(event_types) = &filter.event_types {
-            if !event_types.is_empty() {
-                query_builder.push(" AND event_type = ANY(");
-                query_builder.push_bind(event_types.as_slice());
-                query_builder.push(")");
-            }
-        }
-
-        if let Some(user_id) = &filter.user_id {
-            query_builder.push(" AND user_id = ");
-            query_builder.push_bind(user_id);
-        }
-
-        if let Some(tenant_id) = &filter.tenant_id {
-            query_builder.push(" AND tenant_id = ");
-            query_builder.push_bind(tenant_id);
-        }
-
-        if let Some(api_key_id) = &filter.api_key_id {
-            query_builder.push(" AND api_key_id = ");
-            query_builder.push_bind(api_key_id);
-        }
-
-        if let Some(ip_address) = &filter.ip_address {
-            query_builder.push(" AND ip_address = CAST(");
-            query_builder.push_bind(ip_address.clone());
-            query_builder.push(" AS inet)");
-        }
-
-        if let Some(path_pattern) = &filter.path_pattern {
-            query_builder.push(" AND path LIKE ");
-            query_builder.push_bind(format!("%{}%", path_pattern));
-        }
-
-        if let Some(min_code) = filter.status_code_min {
-            query_builder.push(" AND status_code >= ");
-            query_builder.push_bind(min_code);
-        }
-
-        if let Some(max_code) = filter.status_code_max {
-            query_builder.push(" AND status_code <= ");
-            query_builder.push_bind(max_code);
-        }
-
-        if let Some(from_ts) = filter.from_timestamp {
-            query_builder.push(" AND timestamp >= ");
-            query_builder.push_bind(from_ts);
-        }
-
-        if let Some(to_ts) = filter.to_timestamp {
-            query_builder.push(" AND timestamp <= ");
-            query_builder.push_bind(to_ts);
-        }
-
-        if let Some(has_error) = filter.has_error {
-            if has_error {
-                query_builder.push(" AND error_message IS NOT NULL");
-            } else {
-                query_builder.push(" AND error_message IS NULL");
-            }
-        }
+        QueryBuilder::push_audit_filter_conditions(&mut query_builder, &filter);
 
         let query = query_builder.build();
 
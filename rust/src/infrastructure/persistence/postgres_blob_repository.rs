@@ -4,20 +4,34 @@ use time::OffsetDateTime;
 
 use crate::application::ports::{BlobRepository, RepositoryError};
 use crate::domain::entities::Blob;
-use crate::domain::value_objects::{ContentHash, StorageClass};
+use crate::domain::value_objects::{ContentHash, ContentHashPrefix, StorageClass, TenantId};
+use crate::infrastructure::persistence::retry::RetryPolicy;
 
 pub struct PostgresBlobRepository {
     pool: PgPool,
+    retry: RetryPolicy,
 }
 
 impl PostgresBlobRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Retry idempotent operations on transient errors with `retry`
+    ///
+    /// Reference count changes are never retried.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
     }
 }
 
 #[async_trait]
 impl BlobRepository for PostgresBlobRepository {
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn get_or_create(
         &self,
         content_hash: &ContentHash,
@@ -28,13 +42,20 @@ impl BlobRepository for PostgresBlobRepository {
         let class = storage_class.to_string();
         let size = size_bytes as i64;
 
-        // Try insert, on conflict do nothing and select
+        // Try insert, on conflict take a reference. A blob referenced again
+        // is no longer pending deletion, so a failed deletion recorded while
+        // it was orphaned is dropped with it.
         let row = sqlx::query_as::<_, BlobRow>(
             r"
-            INSERT INTO blobs (content_hash, storage_class, size_bytes, ref_count)
-            VALUES ($1, $2, $3, 1)
-            ON CONFLICT (content_hash) DO UPDATE SET ref_count = blobs.ref_count + 1, last_used_at = now()
-            RETURNING content_hash, storage_class, size_bytes, ref_count, created_at
+            WITH blob AS (
+                INSERT INTO blobs (content_hash, storage_class, size_bytes, ref_count)
+                VALUES ($1, $2, $3, 1)
+                ON CONFLICT (content_hash) DO UPDATE SET ref_count = blobs.ref_count + 1, last_used_at = now()
+                RETURNING content_hash, storage_class, size_bytes, ref_count, created_at
+            ), cleared AS (
+                DELETE FROM blob_deletion_failures WHERE content_hash = $1
+            )
+            SELECT content_hash, storage_class, size_bytes, ref_count, created_at FROM blob
             ",
         )
         .bind(hash)
@@ -46,6 +67,38 @@ impl BlobRepository for PostgresBlobRepository {
         Ok(row.into_domain())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn reference_existing(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+        tenant_id: &TenantId,
+    ) -> Result<Option<Blob>, RepositoryError> {
+        // Orphans (ref_count = 0) may be mid-collection, so they don't count
+        let row = sqlx::query_as::<_, BlobRow>(
+            r"
+            UPDATE blobs
+            SET ref_count = ref_count + 1, last_used_at = now()
+            WHERE content_hash = $1
+              AND storage_class = $2
+              AND ref_count > 0
+              AND EXISTS (
+                  SELECT 1 FROM objects
+                  WHERE tenant_id = $3 AND content_hash = $1 AND status = 'COMMITTED'
+              )
+            RETURNING content_hash, storage_class, size_bytes, ref_count, created_at
+            ",
+        )
+        .bind(content_hash.as_hex())
+        .bind(storage_class.to_string())
+        .bind(tenant_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(BlobRow::into_domain))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn increment_ref(&self, content_hash: &ContentHash) -> Result<(), RepositoryError> {
         sqlx::query(
             r"
@@ -61,6 +114,7 @@ impl BlobRepository for PostgresBlobRepository {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn decrement_ref(&self, content_hash: &ContentHash) -> Result<i32, RepositoryError> {
         let row = sqlx::query_as::<_, (i64,)>(
             r"
@@ -77,30 +131,210 @@ impl BlobRepository for PostgresBlobRepository {
         Ok(row.0 as i32)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn find_orphaned(&self, limit: i64) -> Result<Vec<Blob>, RepositoryError> {
-        let rows = sqlx::query_as::<_, BlobRow>(
-            r"
-            SELECT content_hash, storage_class, size_bytes, ref_count, created_at
-            FROM blobs
-            WHERE ref_count = 0
-            LIMIT $1
-            ",
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+        let rows = self
+            .retry
+            .run("find_orphaned", || {
+                sqlx::query_as::<_, BlobRow>(
+                    r"
+                    SELECT content_hash, storage_class, size_bytes, ref_count, created_at
+                    FROM blobs
+                    WHERE ref_count = 0
+                      AND NOT EXISTS (
+                          SELECT 1 FROM objects o
+                          WHERE o.content_hash = blobs.content_hash
+                            AND (o.legal_hold OR o.retention_until > now())
+                      )
+                      AND NOT EXISTS (
+                          SELECT 1 FROM blob_deletion_failures f
+                          WHERE f.content_hash = blobs.content_hash
+                            AND (f.dead_lettered_at IS NOT NULL OR f.next_attempt_at > now())
+                      )
+                    LIMIT $1
+                    ",
+                )
+                .bind(limit)
+                .fetch_all(&self.pool)
+            })
+            .await?;
 
         Ok(rows.into_iter().map(|r| r.into_domain()).collect())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn delete(&self, content_hash: &ContentHash) -> Result<(), RepositoryError> {
-        sqlx::query("DELETE FROM blobs WHERE content_hash = $1")
-            .bind(content_hash.as_hex())
-            .execute(&self.pool)
+        self.retry
+            .run("delete_blob", || {
+                sqlx::query("DELETE FROM blobs WHERE content_hash = $1")
+                    .bind(content_hash.as_hex())
+                    .execute(&self.pool)
+            })
             .await?;
 
         Ok(())
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn move_storage_class(
+        &self,
+        content_hash: &ContentHash,
+        from: StorageClass,
+        to: StorageClass,
+    ) -> Result<bool, RepositoryError> {
+        // Conditional on the current class, so concurrent moves of one blob
+        // take effect once
+        let result = self
+            .retry
+            .run("move_blob_storage_class", || {
+                sqlx::query(
+                    r"
+                    UPDATE blobs
+                    SET storage_class = $3
+                    WHERE content_hash = $1 AND storage_class = $2
+                    ",
+                )
+                .bind(content_hash.as_hex())
+                .bind(from.to_string())
+                .bind(to.to_string())
+                .execute(&self.pool)
+            })
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn find_known(
+        &self,
+        content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        let hashes: Vec<String> = content_hashes
+            .iter()
+            .map(|h| h.as_hex().to_string())
+            .collect();
+
+        let rows: Vec<String> = self
+            .retry
+            .run("find_known", || {
+                sqlx::query_scalar(
+                    r"
+                    SELECT content_hash FROM blobs WHERE content_hash = ANY($1)
+                    UNION
+                    SELECT content_hash FROM objects
+                    WHERE status = 'WRITING' AND content_hash = ANY($1)
+                    ",
+                )
+                .bind(&hashes)
+                .fetch_all(&self.pool)
+            })
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|hash| ContentHash::from_hex(hash).ok())
+            .collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn list_hashes(
+        &self,
+        after: Option<ContentHash>,
+        limit: i64,
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        let after = after.map(|h| h.as_hex().to_string());
+
+        let rows: Vec<String> = self
+            .retry
+            .run("list_blob_hashes", || {
+                sqlx::query_scalar(
+                    r"
+                    SELECT content_hash FROM blobs
+                    WHERE $1::text IS NULL OR content_hash > $1
+                    ORDER BY content_hash
+                    LIMIT $2
+                    ",
+                )
+                .bind(&after)
+                .bind(limit)
+                .fetch_all(&self.pool)
+            })
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|hash| ContentHash::from_hex(hash).ok())
+            .collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn list(
+        &self,
+        after: Option<ContentHash>,
+        orphaned_only: bool,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        let after = after.map(|h| h.as_hex().to_string());
+        // Separate statements so orphan pages always plan on the partial
+        // index; a generic plan for a `$n OR ref_count = 0` filter would not
+        let query = if orphaned_only {
+            r"
+            SELECT content_hash, storage_class, size_bytes, ref_count, created_at
+            FROM blobs
+            WHERE ref_count = 0 AND ($1::text IS NULL OR content_hash > $1)
+            ORDER BY content_hash
+            LIMIT $2
+            "
+        } else {
+            r"
+            SELECT content_hash, storage_class, size_bytes, ref_count, created_at
+            FROM blobs
+            WHERE $1::text IS NULL OR content_hash > $1
+            ORDER BY content_hash
+            LIMIT $2
+            "
+        };
+
+        let rows = self
+            .retry
+            .run("list_blobs", || {
+                sqlx::query_as::<_, BlobRow>(query)
+                    .bind(&after)
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+            })
+            .await?;
+
+        Ok(rows.into_iter().map(BlobRow::into_domain).collect())
+    }
+
+    async fn find_by_prefix(
+        &self,
+        prefix: &ContentHashPrefix,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        // A range instead of LIKE so the primary key index serves it under
+        // any collation; 'g' sorts after every hex digit
+        let rows = self
+            .retry
+            .run("find_blobs_by_prefix", || {
+                sqlx::query_as::<_, BlobRow>(
+                    r"
+                    SELECT content_hash, storage_class, size_bytes, ref_count, created_at
+                    FROM blobs
+                    WHERE content_hash >= $1 AND content_hash < $1 || 'g'
+                    ORDER BY content_hash
+                    LIMIT $2
+                    ",
+                )
+                .bind(prefix.as_str())
+                .bind(limit)
+                .fetch_all(&self.pool)
+            })
+            .await?;
+
+        Ok(rows.into_iter().map(BlobRow::into_domain).collect())
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use crate::application::ports::{
+    BlobDeletionFailure, BlobDeletionFailureRepository, RepositoryError,
+};
+use crate::domain::value_objects::{ContentHash, StorageClass};
+
+pub struct PostgresBlobDeletionFailureRepository {
+    pool: PgPool,
+}
+
+impl PostgresBlobDeletionFailureRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BlobDeletionFailureRepository for PostgresBlobDeletionFailureRepository {
+    async fn find(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<Option<BlobDeletionFailure>, RepositoryError> {
+        let row = sqlx::query_as::<_, BlobDeletionFailureRow>(
+            r"
+            SELECT content_hash, storage_class, attempts, last_error,
+                   next_attempt_at, dead_lettered_at
+            FROM blob_deletion_failures
+            WHERE content_hash = $1
+            ",
+        )
+        .bind(content_hash.as_hex())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(BlobDeletionFailureRow::into_domain).transpose()
+    }
+
+    async fn record(&self, failure: &BlobDeletionFailure) -> Result<(), RepositoryError> {
+        // A blob deleted in the meantime has nothing left to retry
+        sqlx::query(
+            r"
+            INSERT INTO blob_deletion_failures (
+                content_hash, storage_class, attempts, last_error,
+                next_attempt_at, dead_lettered_at
+            )
+            SELECT $1, $2, $3, $4, $5, $6
+            WHERE EXISTS (SELECT 1 FROM blobs WHERE content_hash = $1)
+            ON CONFLICT (content_hash) DO UPDATE
+                SET storage_class = EXCLUDED.storage_class,
+                    attempts = EXCLUDED.attempts,
+                    last_error = EXCLUDED.last_error,
+                    next_attempt_at = EXCLUDED.next_attempt_at,
+                    dead_lettered_at = EXCLUDED.dead_lettered_at,
+                    last_failed_at = now()
+            ",
+        )
+        .bind(failure.content_hash.as_hex())
+        .bind(failure.storage_class.to_string())
+        .bind(failure.attempts as i32)
+        .bind(&failure.last_error)
+        .bind(failure.next_attempt_at)
+        .bind(failure.dead_lettered_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn clear(&self, content_hash: &ContentHash) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM blob_deletion_failures WHERE content_hash = $1")
+            .bind(content_hash.as_hex())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct BlobDeletionFailureRow {
+    content_hash: String,
+    storage_class: String,
+    attempts: i32,
+    last_error: String,
+    next_attempt_at: Option<OffsetDateTime>,
+    dead_lettered_at: Option<OffsetDateTime>,
+}
+
+impl BlobDeletionFailureRow {
+    fn into_domain(self) -> Result<BlobDeletionFailure, RepositoryError> {
+        Ok(BlobDeletionFailure {
+            content_hash: ContentHash::from_hex(self.content_hash)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            storage_class: self
+                .storage_class
+                .parse::<StorageClass>()
+                .map_err(RepositoryError::SerializationError)?,
+            attempts: self.attempts.max(0) as u32,
+            last_error: self.last_error,
+            next_attempt_at: self.next_attempt_at,
+            dead_lettered_at: self.dead_lettered_at,
+        })
+    }
+}
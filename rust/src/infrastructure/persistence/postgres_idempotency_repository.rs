@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::{IdempotencyRecord, IdempotencyRepository, RepositoryError};
+use crate::domain::value_objects::{ContentHash, ObjectId, TenantId};
+
+pub struct PostgresIdempotencyRepository {
+    pool: PgPool,
+}
+
+impl PostgresIdempotencyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for PostgresIdempotencyRepository {
+    async fn find(
+        &self,
+        tenant_id: &TenantId,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>, RepositoryError> {
+        let row = sqlx::query_as::<_, (String, Option<String>, Option<Uuid>, Option<String>)>(
+            r"
+            SELECT namespace, object_key, object_id, content_hash
+            FROM upload_idempotency_keys
+            WHERE tenant_id = $1 AND idempotency_key = $2 AND expires_at > now()
+            ",
+        )
+        .bind(tenant_id.to_string())
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|(namespace, object_key, object_id, content_hash)| {
+            Ok(IdempotencyRecord {
+                namespace,
+                object_key,
+                object_id: object_id.map(ObjectId::from_uuid),
+                content_hash: content_hash
+                    .map(ContentHash::from_hex)
+                    .transpose()
+                    .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn reserve(
+        &self,
+        tenant_id: &TenantId,
+        key: &str,
+        record: &IdempotencyRecord,
+        lease_secs: i64,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r"
+            INSERT INTO upload_idempotency_keys
+                (tenant_id, idempotency_key, namespace, object_key,
+                 object_id, content_hash, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, now() + make_interval(secs => $7))
+            ON CONFLICT (tenant_id, idempotency_key) DO UPDATE
+            SET namespace = EXCLUDED.namespace,
+                object_key = EXCLUDED.object_key,
+                object_id = EXCLUDED.object_id,
+                content_hash = EXCLUDED.content_hash,
+                created_at = now(),
+                expires_at = EXCLUDED.expires_at
+            WHERE upload_idempotency_keys.expires_at <= now()
+            ",
+        )
+        .bind(tenant_id.to_string())
+        .bind(key)
+        .bind(&record.namespace)
+        .bind(&record.object_key)
+        .bind(record.object_id.as_ref().map(|id| *id.as_uuid()))
+        .bind(record.content_hash.as_ref().map(|h| h.as_hex()))
+        .bind(lease_secs as f64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn complete(
+        &self,
+        tenant_id: &TenantId,
+        key: &str,
+        object_id: &ObjectId,
+        content_hash: &ContentHash,
+        ttl_secs: i64,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r"
+            UPDATE upload_idempotency_keys
+            SET object_id = $3, content_hash = $4,
+                expires_at = now() + make_interval(secs => $5)
+            WHERE tenant_id = $1 AND idempotency_key = $2 AND object_id IS NULL
+            ",
+        )
+        .bind(tenant_id.to_string())
+        .bind(key)
+        .bind(object_id.as_uuid())
+        .bind(content_hash.as_hex())
+        .bind(ttl_secs as f64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn release(&self, tenant_id: &TenantId, key: &str) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r"
+            DELETE FROM upload_idempotency_keys
+            WHERE tenant_id = $1 AND idempotency_key = $2 AND object_id IS NULL
+            ",
+        )
+        .bind(tenant_id.to_string())
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM upload_idempotency_keys WHERE expires_at <= now()")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
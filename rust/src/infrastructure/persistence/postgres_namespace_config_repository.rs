@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use crate::application::ports::{NamespaceConfigRepository, RepositoryError};
+use crate::domain::entities::{KeyPolicy, NamespaceConfig, TieringPolicy};
+use crate::domain::value_objects::{Namespace, StorageClass};
+
+pub struct PostgresNamespaceConfigRepository {
+    pool: PgPool,
+}
+
+impl PostgresNamespaceConfigRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NamespaceConfigRepository for PostgresNamespaceConfigRepository {
+    async fn find(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Option<NamespaceConfig>, RepositoryError> {
+        let row = sqlx::query_as::<_, NamespaceConfigRow>(
+            r"
+            SELECT namespace, default_storage_class, cold_after_days,
+                   key_max_length, key_allowed_chars, key_required_prefix,
+                   created_at, updated_at
+            FROM namespace_configs
+            WHERE namespace = $1
+            ",
+        )
+        .bind(namespace.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(NamespaceConfigRow::into_domain).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<NamespaceConfig>, RepositoryError> {
+        let rows = sqlx::query_as::<_, NamespaceConfigRow>(
+            r"
+            SELECT namespace, default_storage_class, cold_after_days,
+                   key_max_length, key_allowed_chars, key_required_prefix,
+                   created_at, updated_at
+            FROM namespace_configs
+            ORDER BY namespace
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(NamespaceConfigRow::into_domain)
+            .collect()
+    }
+
+    async fn upsert(&self, config: &NamespaceConfig) -> Result<NamespaceConfig, RepositoryError> {
+        let row = sqlx::query_as::<_, NamespaceConfigRow>(
+            r"
+            INSERT INTO namespace_configs (
+                namespace, default_storage_class, cold_after_days,
+                key_max_length, key_allowed_chars, key_required_prefix
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (namespace) DO UPDATE
+            SET default_storage_class = EXCLUDED.default_storage_class,
+                cold_after_days = EXCLUDED.cold_after_days,
+                key_max_length = EXCLUDED.key_max_length,
+                key_allowed_chars = EXCLUDED.key_allowed_chars,
+                key_required_prefix = EXCLUDED.key_required_prefix
+            RETURNING namespace, default_storage_class, cold_after_days,
+                      key_max_length, key_allowed_chars, key_required_prefix,
+                      created_at, updated_at
+            ",
+        )
+        .bind(config.namespace().as_str())
+        .bind(config.default_storage_class().to_string())
+        .bind(config.tiering().map(|t| t.cold_after_days() as i32))
+        .bind(
+            config
+                .key_policy()
+                .and_then(|p| p.max_length())
+                .map(|len| len as i32),
+        )
+        .bind(config.key_policy().and_then(|p| p.allowed_chars()))
+        .bind(config.key_policy().and_then(|p| p.required_prefix()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        row.into_domain()
+    }
+
+    async fn delete(&self, namespace: &Namespace) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM namespace_configs WHERE namespace = $1")
+            .bind(namespace.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct NamespaceConfigRow {
+    namespace: String,
+    default_storage_class: String,
+    cold_after_days: Option<i32>,
+    key_max_length: Option<i32>,
+    key_allowed_chars: Option<String>,
+    key_required_prefix: Option<String>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl NamespaceConfigRow {
+    fn into_domain(self) -> Result<NamespaceConfig, RepositoryError> {
+        let namespace = Namespace::new(self.namespace)
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        let default_storage_class = self
+            .default_storage_class
+            .parse::<StorageClass>()
+            .map_err(RepositoryError::SerializationError)?;
+        let tiering = self
+            .cold_after_days
+            .map(|days| TieringPolicy::new(days.max(0) as u32))
+            .transpose()
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        let key_policy = match (
+            self.key_max_length,
+            self.key_allowed_chars,
+            self.key_required_prefix,
+        ) {
+            (None, None, None) => None,
+            (max_length, allowed_chars, required_prefix) => Some(
+                KeyPolicy::new(
+                    max_length.map(|len| len.max(0) as u32),
+                    allowed_chars,
+                    required_prefix,
+                )
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            ),
+        };
+
+        Ok(NamespaceConfig::reconstruct(
+            namespace,
+            default_storage_class,
+            tiering,
+            key_policy,
+            self.created_at,
+            self.updated_at,
+        ))
+    }
+}
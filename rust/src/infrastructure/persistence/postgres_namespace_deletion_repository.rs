@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::ports::{
+    LockedObject, NamespaceDeleteBatch, NamespaceDeletionRepository, NamespaceUsage,
+    RepositoryError,
+};
+use crate::domain::value_objects::{Namespace, ObjectId, TenantId};
+
+pub struct PostgresNamespaceDeletionRepository {
+    pool: PgPool,
+}
+
+impl PostgresNamespaceDeletionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NamespaceDeletionRepository for PostgresNamespaceDeletionRepository {
+    async fn usage(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+    ) -> Result<NamespaceUsage, RepositoryError> {
+        let (objects, bytes) = sqlx::query_as::<_, (i64, i64)>(
+            r"
+            SELECT COUNT(*), COALESCE(SUM(size_bytes), 0)::BIGINT
+            FROM objects
+            WHERE tenant_id = $1 AND namespace = $2 AND status = 'COMMITTED'
+            ",
+        )
+        .bind(tenant_id.to_string())
+        .bind(namespace.as_str())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(NamespaceUsage {
+            objects: objects as u64,
+            bytes: bytes as u64,
+        })
+    }
+
+    async fn find_locked(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        limit: i64,
+    ) -> Result<Vec<LockedObject>, RepositoryError> {
+        let rows = sqlx::query_as::<_, (Uuid, Option<String>, Option<OffsetDateTime>, bool)>(
+            r"
+            SELECT id, key, retention_until, legal_hold
+            FROM objects
+            WHERE tenant_id = $1 AND namespace = $2 AND status = 'COMMITTED'
+              AND (legal_hold OR retention_until > now())
+            ORDER BY id
+            LIMIT $3
+            ",
+        )
+        .bind(tenant_id.to_string())
+        .bind(namespace.as_str())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, key, retention_until, legal_hold)| LockedObject {
+                id: ObjectId::from_uuid(id),
+                key,
+                retention_until,
+                legal_hold,
+            })
+            .collect())
+    }
+
+    async fn delete_batch(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        limit: i64,
+    ) -> Result<NamespaceDeleteBatch, RepositoryError> {
+        // One statement, so the objects and their blob references change
+        // together; blobs left at zero references are reclaimed by GC
+        let (objects, bytes, released_blobs) = sqlx::query_as::<_, (i64, i64, i64)>(
+            r"
+            WITH doomed AS (
+                SELECT id FROM objects
+                WHERE tenant_id = $1 AND namespace = $2 AND status = 'COMMITTED'
+                  AND NOT legal_hold
+                  AND (retention_until IS NULL OR retention_until <= now())
+                ORDER BY id
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            ),
+            deleted AS (
+                UPDATE objects o
+                SET status = 'DELETED', updated_at = now()
+                FROM doomed
+                WHERE o.id = doomed.id
+                RETURNING o.content_hash, o.size_bytes
+            ),
+            released AS (
+                UPDATE blobs b
+                SET ref_count = GREATEST(b.ref_count - refs.n, 0)
+                FROM (
+                    SELECT content_hash, COUNT(*) AS n
+                    FROM deleted
+                    WHERE content_hash IS NOT NULL
+                    GROUP BY content_hash
+                ) refs
+                WHERE b.content_hash = refs.content_hash
+                RETURNING b.ref_count
+            )
+            SELECT
+                (SELECT COUNT(*) FROM deleted),
+                (SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM deleted),
+                (SELECT COUNT(*) FROM released WHERE ref_count = 0)
+            ",
+        )
+        .bind(tenant_id.to_string())
+        .bind(namespace.as_str())
+        .bind(limit)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(NamespaceDeleteBatch {
+            objects: objects as u64,
+            bytes: bytes as u64,
+            released_blobs: released_blobs as u64,
+        })
+    }
+}
@@ -1,27 +1,159 @@
 use async_trait::async_trait;
+use futures_util::StreamExt;
+use sqlx::postgres::PgRow;
 use sqlx::{AssertSqlSafe, PgPool, Row};
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
+use tokio::sync::mpsc;
 
-use crate::application::dto::{SearchRequest, TextSearchRequest};
-use crate::application::ports::{ObjectRepository, RepositoryError};
+use crate::application::dto::{
+    ObjectAccess, ObjectField, ObjectHead, ObjectProjection, ObjectRecord, SearchRequest,
+    SortDirection, SortField, TextSearchMatch, TextSearchPage, TextSearchRequest,
+};
+use crate::application::key_prefix_query::KeyPrefixQuery;
+use crate::application::list_cursor::ListCursor;
+use crate::application::metadata_index::MetadataIndexConfig;
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::pagination::{PageLimits, Pagination};
+use crate::application::ports::{ObjectRepository, ObjectStream, RepositoryError};
 use crate::domain::entities::Object;
 use crate::domain::value_objects::{
-    ContentHash, Namespace, ObjectId, ObjectMetadata, ObjectStatus, StorageClass, TenantId,
+    ContentEncoding, ContentHash, KeyUniqueness, Namespace, ObjectId, ObjectMetadata, ObjectStatus,
+    StorageClass, TenantId,
 };
 use crate::infrastructure::persistence::query_builder::QueryBuilder;
+use crate::infrastructure::persistence::retry::RetryPolicy;
+
+/// Rows a streamed listing reads ahead of its consumer
+const STREAM_BUFFER_ROWS: usize = 256;
 
 pub struct PostgresObjectRepository {
     pool: PgPool,
+    metadata_index: MetadataIndexConfig,
+    text_search_config: String,
+    retry: RetryPolicy,
+    key_uniqueness: KeyUniqueness,
 }
 
 impl PostgresObjectRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self::with_metadata_index(pool, MetadataIndexConfig::default())
+    }
+
+    /// Create a repository that full-text indexes the given custom metadata keys
+    pub fn with_metadata_index(pool: PgPool, metadata_index: MetadataIndexConfig) -> Self {
+        Self {
+            pool,
+            metadata_index,
+            text_search_config: "simple".to_string(),
+            retry: RetryPolicy::default(),
+            key_uniqueness: KeyUniqueness::default(),
+        }
+    }
+
+    /// Retry idempotent operations on transient errors with `retry`
+    ///
+    /// Compare-and-swap updates and access counting are never retried.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Use a Postgres text search configuration (e.g. `english`) for search
+    /// vectors and queries instead of `simple`
+    pub fn with_text_search_config(mut self, config: impl Into<String>) -> Self {
+        self.text_search_config = config.into();
+        self
+    }
+
+    /// Scope new objects' keys are unique within
+    ///
+    /// Objects keep the scope they were created with; the unique index
+    /// compares keys within a tenant and scope.
+    pub fn with_key_uniqueness(mut self, key_uniqueness: KeyUniqueness) -> Self {
+        self.key_uniqueness = key_uniqueness;
+        self
+    }
+
+    /// Push a `matches` subquery of the objects matching a text search, with
+    /// their `rank` and parsed `query`, filtered by the rank threshold
+    ///
+    /// Key substring matches add 1 to the rank so they sort above matches
+    /// found only through the search vectors; plain metadata substring
+    /// matches contribute nothing.
+    fn push_text_search_matches<'a>(
+        &'a self,
+        qb: &mut sqlx::QueryBuilder<'a, sqlx::Postgres>,
+        request: &'a TextSearchRequest,
+    ) {
+        let search_in_metadata = request.search_in_metadata.unwrap_or(true);
+        let search_in_key = request.search_in_key.unwrap_or(true);
+        let search_in_custom_metadata = request.search_in_custom_metadata.unwrap_or(true);
+        let search_in_content = request.search_in_content.unwrap_or(true);
+        let query_param = format!("%{}%", request.query);
+
+        qb.push("(SELECT objects.*, query, (0");
+        if search_in_key {
+            qb.push(" + CASE WHEN key ILIKE ");
+            qb.push_bind(query_param.clone());
+            qb.push(" THEN 1 ELSE 0 END");
+        }
+        let mut vectors = Vec::new();
+        if search_in_custom_metadata {
+            vectors.push("setweight(metadata_search, 'A')");
+        }
+        if search_in_content {
+            vectors.push("setweight(content_search, 'B')");
+        }
+        if !vectors.is_empty() {
+            qb.push(" + ts_rank(");
+            qb.push(vectors.join(" || "));
+            qb.push(", query)");
+        }
+        qb.push(")::real AS rank FROM objects, to_tsquery(");
+        qb.push_bind(&self.text_search_config);
+        qb.push("::regconfig, ");
+        qb.push_bind(QueryBuilder::tsquery(
+            &request.query,
+            request.prefix.unwrap_or(false),
+        ));
+        qb.push(") AS query ");
+        qb.push(QueryBuilder::COMMITTED_WHERE);
+        qb.push(" AND namespace = ");
+        qb.push_bind(&request.namespace);
+        qb.push(" AND tenant_id = ");
+        qb.push_bind(&request.tenant_id);
+        qb.push(" AND (");
+
+        if !search_in_key && !search_in_metadata && !search_in_custom_metadata && !search_in_content
+        {
+            qb.push("FALSE");
+        }
+
+        let mut conditions = qb.separated(" OR ");
+        if search_in_key {
+            conditions.push("key ILIKE ");
+            conditions.push_bind_unseparated(query_param.clone());
+        }
+        if search_in_metadata {
+            // Tags included: undesignated ones are only found here, unranked
+            conditions.push("metadata::text ILIKE ");
+            conditions.push_bind_unseparated(query_param);
+        }
+        if search_in_custom_metadata {
+            conditions.push("metadata_search @@ query");
+        }
+        if search_in_content {
+            conditions.push("content_search @@ query");
+        }
+        qb.push(")) matches WHERE rank >= ");
+        qb.push_bind(request.min_rank.unwrap_or(0.0));
     }
 }
 
 #[async_trait]
 impl ObjectRepository for PostgresObjectRepository {
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn save(&self, object: &Object) -> Result<(), RepositoryError> {
         let id = object.id().as_uuid();
         let namespace = object.namespace().as_str();
@@ -32,56 +164,82 @@ impl ObjectRepository for PostgresObjectRepository {
         let content_hash = object.content_hash().map(|h| h.as_hex().to_string());
         let size_bytes = object.size_bytes().map(|s| s as i64);
         let content_type = object.content_type();
+        let content_encoding = object.content_encoding().map(|e| e.to_string());
         let metadata = object
             .metadata()
             .to_json()
             .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
         let created_at = object.created_at();
         let updated_at = object.updated_at();
+        // Custom metadata designated for the namespace is folded into the search vector
+        let metadata_search_text = self
+            .metadata_index
+            .searchable_text(namespace, object.metadata());
+        let key_scope = key.and(self.key_uniqueness.scope(object.namespace()));
 
-        sqlx::query(
-            r"
-            INSERT INTO objects (
-                id, namespace, tenant_id, key, status, storage_class,
-                content_hash, size_bytes, content_type, metadata,
-                created_at, updated_at
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-            ON CONFLICT (id) DO UPDATE SET
-                status = EXCLUDED.status,
-                content_hash = EXCLUDED.content_hash,
-                size_bytes = EXCLUDED.size_bytes,
-                content_type = EXCLUDED.content_type,
-                metadata = EXCLUDED.metadata,
-                updated_at = EXCLUDED.updated_at
-            ",
-        )
-        .bind(id)
-        .bind(namespace)
-        .bind(tenant_id)
-        .bind(key)
-        .bind(status)
-        .bind(storage_class)
-        .bind(content_hash)
-        .bind(size_bytes)
-        .bind(content_type)
-        .bind(metadata)
-        .bind(created_at)
-        .bind(updated_at)
-        .execute(&self.pool)
-        .await?;
+        // An upsert of the full row, so repeating it is safe; the key scope is
+        // only set on insert
+        self.retry
+            .run("save_object", || {
+                sqlx::query(
+                    r"
+                    INSERT INTO objects (
+                        id, namespace, tenant_id, key, status, storage_class,
+                        content_hash, size_bytes, content_type, metadata,
+                        created_at, updated_at, metadata_search, content_encoding,
+                        key_scope
+                    )
+                    VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12,
+                        to_tsvector($14::regconfig, $13), $15, $16
+                    )
+                    ON CONFLICT (id) DO UPDATE SET
+                        status = EXCLUDED.status,
+                        content_hash = EXCLUDED.content_hash,
+                        size_bytes = EXCLUDED.size_bytes,
+                        content_type = EXCLUDED.content_type,
+                        content_encoding = EXCLUDED.content_encoding,
+                        metadata = EXCLUDED.metadata,
+                        updated_at = EXCLUDED.updated_at,
+                        metadata_search = EXCLUDED.metadata_search
+                    ",
+                )
+                .bind(id)
+                .bind(namespace)
+                .bind(&tenant_id)
+                .bind(key)
+                .bind(&status)
+                .bind(&storage_class)
+                .bind(&content_hash)
+                .bind(size_bytes)
+                .bind(content_type)
+                .bind(&metadata)
+                .bind(created_at)
+                .bind(updated_at)
+                .bind(&metadata_search_text)
+                .bind(&self.text_search_config)
+                .bind(&content_encoding)
+                .bind(key_scope)
+                .execute(&self.pool)
+            })
+            .await?;
 
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn find_by_id(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError> {
         let sql = format!(
             "{} WHERE id = $1 AND status = 'COMMITTED'",
             QueryBuilder::OBJECT_SELECT
         );
-        let row = sqlx::query_as::<_, ObjectRow>(AssertSqlSafe(sql))
-            .bind(id.as_uuid())
-            .fetch_optional(&self.pool)
+        let row = self
+            .retry
+            .run("find_by_id", || {
+                sqlx::query_as::<_, ObjectRow>(AssertSqlSafe(sql.clone()))
+                    .bind(id.as_uuid())
+                    .fetch_optional(&self.pool)
+            })
             .await?;
 
         match row {
@@ -90,24 +248,279 @@ impl ObjectRepository for PostgresObjectRepository {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn find_writing(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError> {
+        let sql = format!(
+            "{} WHERE id = $1 AND status = 'WRITING'",
+            QueryBuilder::OBJECT_SELECT
+        );
+        let row = self
+            .retry
+            .run("find_writing", || {
+                sqlx::query_as::<_, ObjectRow>(AssertSqlSafe(sql.clone()))
+                    .bind(id.as_uuid())
+                    .fetch_optional(&self.pool)
+            })
+            .await?;
+
+        row.map(ObjectRow::into_domain).transpose()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn head(&self, id: &ObjectId) -> Result<Option<ObjectHead>, RepositoryError> {
+        let sql = format!(
+            "{} WHERE id = $1 AND status = 'COMMITTED'",
+            QueryBuilder::OBJECT_HEAD_SELECT
+        );
+        let row = self
+            .retry
+            .run("head", || {
+                sqlx::query_as::<_, ObjectHeadRow>(AssertSqlSafe(sql.clone()))
+                    .bind(id.as_uuid())
+                    .fetch_optional(&self.pool)
+            })
+            .await?;
+
+        row.map(ObjectHeadRow::into_head).transpose()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn find_record(&self, id: &ObjectId) -> Result<Option<ObjectRecord>, RepositoryError> {
+        let sql = format!(
+            "{} WHERE o.id = $1 AND o.status = 'COMMITTED'",
+            QueryBuilder::OBJECT_RECORD_SELECT
+        );
+        let row = self
+            .retry
+            .run("find_record", || {
+                sqlx::query_as::<_, ObjectRecordRow>(AssertSqlSafe(sql.clone()))
+                    .bind(id.as_uuid())
+                    .fetch_optional(&self.pool)
+            })
+            .await?;
+
+        row.map(ObjectRecordRow::into_record).transpose()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn status(
+        &self,
+        id: &ObjectId,
+    ) -> Result<Option<(TenantId, ObjectStatus)>, RepositoryError> {
+        let row: Option<(String, String)> = self
+            .retry
+            .run("status", || {
+                sqlx::query_as("SELECT tenant_id, status FROM objects WHERE id = $1")
+                    .bind(id.as_uuid())
+                    .fetch_optional(&self.pool)
+            })
+            .await?;
+
+        row.map(|(tenant_id, status)| {
+            let tenant_id = TenantId::from_string(&tenant_id).map_err(|e| {
+                RepositoryError::SerializationError(format!("Invalid tenant_id: {}", e))
+            })?;
+            let status = status
+                .parse::<ObjectStatus>()
+                .map_err(RepositoryError::SerializationError)?;
+            Ok((tenant_id, status))
+        })
+        .transpose()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn head_by_key(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        key: &str,
+    ) -> Result<Option<ObjectHead>, RepositoryError> {
+        let row = self
+            .retry
+            .run("head_by_key", || async {
+                let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_HEAD_SELECT);
+                qb.push(" ");
+                qb.push(QueryBuilder::COMMITTED_WHERE);
+                qb.push(" AND namespace = ");
+                qb.push_bind(namespace.as_str());
+                qb.push(" AND tenant_id = ");
+                qb.push_bind(tenant_id.to_string());
+                qb.push(" AND key = ");
+                qb.push_bind(key);
+                // Same object as find_by_key when keys may repeat
+                qb.push(" ORDER BY created_at DESC LIMIT 1");
+
+                qb.build_query_as::<ObjectHeadRow>()
+                    .fetch_optional(&self.pool)
+                    .await
+            })
+            .await?;
+
+        row.map(ObjectHeadRow::into_head).transpose()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn replace_content_if_match(
+        &self,
+        object: &Object,
+        expected: &ContentHash,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r"
+            UPDATE objects
+            SET content_hash = $3, size_bytes = $4, updated_at = $5, content_encoding = $6
+            WHERE id = $1 AND status = 'COMMITTED' AND content_hash = $2
+              AND NOT legal_hold AND (retention_until IS NULL OR retention_until <= now())
+            ",
+        )
+        .bind(object.id().as_uuid())
+        .bind(expected.as_hex())
+        .bind(object.content_hash().map(|h| h.as_hex().to_string()))
+        .bind(object.size_bytes().map(|s| s as i64))
+        .bind(object.updated_at())
+        .bind(object.content_encoding().map(|e| e.to_string()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn update_metadata_if_match(
+        &self,
+        object: &Object,
+        expected: &ObjectMetadata,
+    ) -> Result<bool, RepositoryError> {
+        let metadata = object
+            .metadata()
+            .to_json()
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        let expected = expected
+            .to_json()
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        let metadata_search_text = self
+            .metadata_index
+            .searchable_text(object.namespace().as_str(), object.metadata());
+
+        let result = sqlx::query(
+            r"
+            UPDATE objects
+            SET metadata = $3, updated_at = $4,
+                metadata_search = to_tsvector($6::regconfig, $5)
+            WHERE id = $1 AND status = 'COMMITTED' AND metadata = $2
+              AND NOT legal_hold AND (retention_until IS NULL OR retention_until <= now())
+            ",
+        )
+        .bind(object.id().as_uuid())
+        .bind(expected)
+        .bind(metadata)
+        .bind(object.updated_at())
+        .bind(metadata_search_text)
+        .bind(&self.text_search_config)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn update_retention(&self, object: &Object) -> Result<bool, RepositoryError> {
+        // GREATEST ignores NULL and keeps a retention extended concurrently,
+        // so setting it again is safe
+        let result = self
+            .retry
+            .run("update_retention", || {
+                sqlx::query(
+                    r"
+                    UPDATE objects
+                    SET retention_until = GREATEST(retention_until, $2),
+                        legal_hold = $3, updated_at = $4
+                    WHERE id = $1 AND status = 'COMMITTED'
+                    ",
+                )
+                .bind(object.id().as_uuid())
+                .bind(object.retention_until())
+                .bind(object.legal_hold())
+                .bind(object.updated_at())
+                .execute(&self.pool)
+            })
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn mark_deleting_if_unlocked(&self, object: &Object) -> Result<bool, RepositoryError> {
+        // The lock is checked in the same statement, so a retention or hold
+        // set after the object was loaded still refuses the delete
+        let result = sqlx::query(
+            r"
+            UPDATE objects
+            SET status = 'DELETING', updated_at = $2
+            WHERE id = $1 AND status = 'COMMITTED'
+              AND NOT legal_hold AND (retention_until IS NULL OR retention_until <= now())
+            ",
+        )
+        .bind(object.id().as_uuid())
+        .bind(object.updated_at())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn set_extracted_text(
+        &self,
+        id: &ObjectId,
+        text: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        self.retry
+            .run("set_extracted_text", || {
+                sqlx::query(
+                    r"
+                    UPDATE objects
+                    SET extracted_text = $2,
+                        content_search = to_tsvector($3::regconfig, coalesce($2, ''))
+                    WHERE id = $1
+                    ",
+                )
+                .bind(id.as_uuid())
+                .bind(&text)
+                .bind(&self.text_search_config)
+                .execute(&self.pool)
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn find_by_key(
         &self,
         namespace: &Namespace,
         tenant_id: &TenantId,
         key: &str,
     ) -> Result<Option<Object>, RepositoryError> {
-        let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
-        qb.push(" ");
-        qb.push(QueryBuilder::COMMITTED_WHERE);
-        qb.push(" AND namespace = ");
-        qb.push_bind(namespace.as_str());
-        qb.push(" AND tenant_id = ");
-        qb.push_bind(tenant_id.to_string());
-        qb.push(" AND key = ");
-        qb.push_bind(key);
+        let row = self
+            .retry
+            .run("find_by_key", || async {
+                let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
+                qb.push(" ");
+                qb.push(QueryBuilder::COMMITTED_WHERE);
+                qb.push(" AND namespace = ");
+                qb.push_bind(namespace.as_str());
+                qb.push(" AND tenant_id = ");
+                qb.push_bind(tenant_id.to_string());
+                qb.push(" AND key = ");
+                qb.push_bind(key);
+                // Without key uniqueness several objects may match; the newest wins
+                qb.push(" ORDER BY created_at DESC LIMIT 1");
 
-        let query = qb.build_query_as::<ObjectRow>();
-        let row = query.fetch_optional(&self.pool).await?;
+                let query = qb.build_query_as::<ObjectRow>();
+                query.fetch_optional(&self.pool).await
+            })
+            .await?;
 
         match row {
             Some(r) => Ok(Some(r.into_domain()?)),
@@ -115,134 +528,393 @@ impl ObjectRepository for PostgresObjectRepository {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn list(
         &self,
         namespace: &Namespace,
         tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        after: Option<ListCursor>,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<Object>, RepositoryError> {
-        let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
-        qb.push(" ");
-        qb.push(QueryBuilder::COMMITTED_WHERE);
-        qb.push(" AND namespace = ");
-        qb.push_bind(namespace.as_str());
-        qb.push(" AND tenant_id = ");
-        qb.push_bind(tenant_id.to_string());
-        qb.push(" ORDER BY created_at DESC LIMIT ");
-        qb.push_bind(limit);
-        qb.push(" OFFSET ");
-        qb.push_bind(offset);
+        let rows = self
+            .retry
+            .run("list", || async {
+                let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
+                qb.push(" ");
+                qb.push(QueryBuilder::COMMITTED_WHERE);
+                qb.push(" AND namespace = ");
+                qb.push_bind(namespace.as_str());
+                qb.push(" AND tenant_id = ");
+                qb.push_bind(tenant_id.to_string());
+                QueryBuilder::push_key_prefix_conditions(&mut qb, keys);
+                QueryBuilder::push_metadata_conditions(&mut qb, metadata);
+                if let Some(after) = &after {
+                    QueryBuilder::push_after_cursor(&mut qb, after);
+                }
+                qb.push(" ORDER BY ");
+                qb.push(QueryBuilder::paged_order_by(sort_by, sort_direction));
+                QueryBuilder::push_page(&mut qb, limit, offset);
 
-        let query = qb.build_query_as::<ObjectRow>();
-        let rows = query.fetch_all(&self.pool).await?;
+                let query = qb.build_query_as::<ObjectRow>();
+                query.fetch_all(&self.pool).await
+            })
+            .await?;
 
         rows.into_iter().map(|r| r.into_domain()).collect()
     }
 
-    async fn search(&self, request: &SearchRequest) -> Result<Vec<Object>, RepositoryError> {
-        use crate::application::dto::{SortDirection, SortField};
-
-        let sort_field = request.sort_by.as_ref().unwrap_or(&SortField::CreatedAt);
-        let sort_direction = request
-            .sort_direction
-            .as_ref()
-            .unwrap_or(&SortDirection::Desc);
-        let limit = request.limit.unwrap_or(100).min(1000);
-        let offset = request.offset.unwrap_or(0);
-
-        // Validate sort column to prevent SQL injection
-        let sort_column = match sort_field {
-            SortField::CreatedAt => "created_at",
-            SortField::UpdatedAt => "updated_at",
-            SortField::SizeBytes => "size_bytes",
-            SortField::Key => "key",
-            SortField::ContentType => "content_type",
-        };
-        let sort_dir = match sort_direction {
-            SortDirection::Asc => "ASC",
-            SortDirection::Desc => "DESC",
-        };
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn list_projected(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        fields: &[ObjectField],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ObjectProjection>, RepositoryError> {
+        let rows = self
+            .retry
+            .run("list_projected", || async {
+                let mut qb = sqlx::QueryBuilder::new("SELECT ");
+                qb.push(QueryBuilder::object_columns(fields));
+                qb.push(" FROM objects ");
+                qb.push(QueryBuilder::COMMITTED_WHERE);
+                qb.push(" AND namespace = ");
+                qb.push_bind(namespace.as_str());
+                qb.push(" AND tenant_id = ");
+                qb.push_bind(tenant_id.to_string());
+                QueryBuilder::push_key_prefix_conditions(&mut qb, keys);
+                QueryBuilder::push_metadata_conditions(&mut qb, metadata);
+                qb.push(" ORDER BY ");
+                qb.push(QueryBuilder::paged_order_by(sort_by, sort_direction));
+                QueryBuilder::push_page(&mut qb, limit, offset);
 
-        let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
-        qb.push(" ");
-        qb.push(QueryBuilder::COMMITTED_WHERE);
-        qb.push(" AND namespace = ");
-        qb.push_bind(&request.namespace);
-        qb.push(" AND tenant_id = ");
-        qb.push_bind(&request.tenant_id);
-        
-        qb.push(" ORDER BY ");
-        qb.push(sort_column);
-        qb.push(" ");
-        qb.push(sort_dir);
-        qb.push(" LIMIT ");
-        qb.push_bind(limit);
-        qb.push(" OFFSET ");
-        qb.push_bind(offset);
-
-        let query = qb.build_query_as::<ObjectRow>();
-        let rows = query.fetch_all(&self.pool).await?;
+                qb.build().fetch_all(&self.pool).await
+            })
+            .await?;
+
+        rows.iter()
+            .map(|row| into_projection(row, fields))
+            .collect()
+    }
+
+    fn stream(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+    ) -> ObjectStream {
+        let pool = self.pool.clone();
+        let namespace = namespace.as_str().to_string();
+        let tenant_id = tenant_id.to_string();
+        let keys = keys.clone();
+        let metadata = metadata.clone();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER_ROWS);
+
+        // The query runs on its own task so the stream owns nothing borrowed;
+        // the bounded channel holds it back while the consumer is slow
+        tokio::spawn(async move {
+            let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
+            qb.push(" ");
+            qb.push(QueryBuilder::COMMITTED_WHERE);
+            qb.push(" AND namespace = ");
+            qb.push_bind(namespace.as_str());
+            qb.push(" AND tenant_id = ");
+            qb.push_bind(tenant_id.as_str());
+            QueryBuilder::push_key_prefix_conditions(&mut qb, &keys);
+            QueryBuilder::push_metadata_conditions(&mut qb, &metadata);
+            qb.push(" ORDER BY ");
+            qb.push(QueryBuilder::order_by(sort_by, sort_direction));
+
+            let mut rows = qb.build_query_as::<ObjectRow>().fetch(&pool);
+            while let Some(row) = rows.next().await {
+                let object = row
+                    .map_err(RepositoryError::from)
+                    .and_then(ObjectRow::into_domain);
+                let failed = object.is_err();
+                // Sending fails once the consumer has dropped the stream
+                if tx.send(object).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|object| (object, rx))
+        }))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn count(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        max: i64,
+    ) -> Result<i64, RepositoryError> {
+        let count: i64 = self
+            .retry
+            .run("count", || async {
+                // Counting a limited subquery stops the index scan after `max` rows
+                let mut qb =
+                    sqlx::QueryBuilder::new("SELECT COUNT(*) FROM (SELECT 1 FROM objects ");
+                qb.push(QueryBuilder::COMMITTED_WHERE);
+                qb.push(" AND namespace = ");
+                qb.push_bind(namespace.as_str());
+                qb.push(" AND tenant_id = ");
+                qb.push_bind(tenant_id.to_string());
+                QueryBuilder::push_key_prefix_conditions(&mut qb, keys);
+                QueryBuilder::push_metadata_conditions(&mut qb, metadata);
+                qb.push(" LIMIT ");
+                qb.push_bind(max);
+                qb.push(") AS bounded");
+
+                qb.build_query_scalar().fetch_one(&self.pool).await
+            })
+            .await?;
+        Ok(count)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn common_prefixes(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        max: i64,
+    ) -> Result<Vec<String>, RepositoryError> {
+        if keys.delimiter().is_none() {
+            return Ok(Vec::new());
+        }
+
+        let prefixes: Vec<String> = self
+            .retry
+            .run("common_prefixes", || async {
+                let mut qb = sqlx::QueryBuilder::new("");
+                QueryBuilder::push_common_prefix_select(&mut qb, keys);
+                qb.push(" AND namespace = ");
+                qb.push_bind(namespace.as_str());
+                qb.push(" AND tenant_id = ");
+                qb.push_bind(tenant_id.to_string());
+                QueryBuilder::push_metadata_conditions(&mut qb, metadata);
+                qb.push(" ORDER BY common_prefix LIMIT ");
+                qb.push_bind(max);
+
+                qb.build_query_scalar().fetch_all(&self.pool).await
+            })
+            .await?;
+        Ok(prefixes)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn namespaces(&self, tenant_id: &TenantId) -> Result<Vec<Namespace>, RepositoryError> {
+        let names: Vec<String> = self
+            .retry
+            .run("namespaces", || {
+                sqlx::query_scalar(
+                    r"
+                    SELECT DISTINCT namespace
+                    FROM objects
+                    WHERE status = 'COMMITTED' AND tenant_id = $1
+                    ORDER BY namespace
+                    ",
+                )
+                .bind(tenant_id.to_string())
+                .fetch_all(&self.pool)
+            })
+            .await?;
+
+        names
+            .into_iter()
+            .map(|name| {
+                Namespace::new(name).map_err(|e| RepositoryError::SerializationError(e.to_string()))
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn search(
+        &self,
+        request: &SearchRequest,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        after: Option<ListCursor>,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        let Pagination { limit, offset } =
+            Pagination::new(request.limit, request.offset, PageLimits::OBJECTS);
+        let sort_by = request.sort_by.unwrap_or_default();
+        let sort_direction = request.sort_direction.unwrap_or_default();
+
+        let rows = self
+            .retry
+            .run("search", || async {
+                let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
+                qb.push(" ");
+                qb.push(QueryBuilder::COMMITTED_WHERE);
+                qb.push(" AND namespace = ");
+                qb.push_bind(&request.namespace);
+                qb.push(" AND tenant_id = ");
+                qb.push_bind(&request.tenant_id);
+                QueryBuilder::push_key_prefix_conditions(&mut qb, keys);
+                QueryBuilder::push_metadata_conditions(&mut qb, metadata);
+                if let Some(after) = &after {
+                    QueryBuilder::push_after_cursor(&mut qb, after);
+                }
+
+                // Sort columns are whitelisted to prevent SQL injection
+                qb.push(" ORDER BY ");
+                qb.push(if after.is_some() {
+                    QueryBuilder::paged_order_by(sort_by, sort_direction)
+                } else {
+                    QueryBuilder::order_by(sort_by, sort_direction)
+                });
+                QueryBuilder::push_page(&mut qb, limit, offset);
+
+                let query = qb.build_query_as::<ObjectRow>();
+                query.fetch_all(&self.pool).await
+            })
+            .await?;
 
         rows.into_iter().map(|r| r.into_domain()).collect()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn text_search(
         &self,
         request: &TextSearchRequest,
-    ) -> Result<Vec<Object>, RepositoryError> {
-        let search_in_metadata = request.search_in_metadata.unwrap_or(true);
-        let search_in_key = request.search_in_key.unwrap_or(true);
-        let limit = request.limit.unwrap_or(100).min(1000);
-        let offset = request.offset.unwrap_or(0);
+    ) -> Result<TextSearchPage, RepositoryError> {
+        let Pagination { limit, offset } =
+            Pagination::new(request.limit, request.offset, PageLimits::OBJECTS);
 
-        let mut qb = sqlx::QueryBuilder::new(QueryBuilder::OBJECT_SELECT);
-        qb.push(" ");
-        qb.push(QueryBuilder::COMMITTED_WHERE);
-        qb.push(" AND namespace = ");
-        qb.push_bind(&request.namespace);
-        qb.push(" AND tenant_id = ");
-        qb.push_bind(&request.tenant_id);
-        qb.push(" AND ");
+        let total: i64 = self
+            .retry
+            .run("text_search_count", || async {
+                let mut count_qb = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM ");
+                self.push_text_search_matches(&mut count_qb, request);
+                count_qb.build_query_scalar().fetch_one(&self.pool).await
+            })
+            .await?;
 
-        let query_param = format!("%{}%", request.query);
+        let rows = self
+            .retry
+            .run("text_search", || async {
+                let mut qb = sqlx::QueryBuilder::new(
+                    r"
+                    SELECT id, namespace, tenant_id, key, status, storage_class,
+                           content_hash, size_bytes, content_type, content_encoding, metadata,
+                           created_at, updated_at, download_count, last_access_at,
+                           retention_until, legal_hold, rank,
+                           CASE WHEN content_search @@ query
+                                THEN ts_headline(",
+                );
+                qb.push_bind(&self.text_search_config);
+                qb.push("::regconfig, extracted_text, query, ");
+                qb.push_bind(QueryBuilder::HEADLINE_OPTIONS);
+                qb.push(") END AS highlight FROM ");
+                self.push_text_search_matches(&mut qb, request);
+                qb.push(" ORDER BY rank DESC, created_at DESC");
+                QueryBuilder::push_page(&mut qb, limit, offset);
 
-        if search_in_key && search_in_metadata {
-            qb.push("(key ILIKE ");
-            qb.push_bind(&query_param);
-            qb.push(" OR metadata::text ILIKE ");
-            qb.push_bind(&query_param);
-            qb.push(")");
-        } else if search_in_key {
-            qb.push("key ILIKE ");
-            qb.push_bind(&query_param);
-        } else if search_in_metadata {
-            qb.push("metadata::text ILIKE ");
-            qb.push_bind(&query_param);
-        } else {
-            qb.push("FALSE");
+                qb.build_query_as::<TextSearchRow>()
+                    .fetch_all(&self.pool)
+                    .await
+            })
+            .await?;
+
+        Ok(TextSearchPage {
+            matches: rows
+                .into_iter()
+                .map(TextSearchRow::into_match)
+                .collect::<Result<_, _>>()?,
+            total: total as u64,
+        })
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn record_access(&self, accesses: &[ObjectAccess]) -> Result<(), RepositoryError> {
+        if accesses.is_empty() {
+            return Ok(());
         }
 
-        qb.push(" ORDER BY created_at DESC LIMIT ");
-        qb.push_bind(limit);
-        qb.push(" OFFSET ");
-        qb.push_bind(offset);
+        let ids: Vec<uuid::Uuid> = accesses.iter().map(|a| *a.object_id.as_uuid()).collect();
+        let counts: Vec<i64> = accesses.iter().map(|a| a.count as i64).collect();
+        let accessed_at: Vec<OffsetDateTime> =
+            accesses.iter().map(|a| a.last_accessed_at).collect();
 
-        let query = qb.build_query_as::<ObjectRow>();
-        let rows = query.fetch_all(&self.pool).await?;
+        // GREATEST ignores NULL, so the first access sets last_access_at
+        sqlx::query(
+            r"
+            UPDATE objects AS o
+            SET download_count = o.download_count + a.count,
+                last_access_at = GREATEST(o.last_access_at, a.accessed_at)
+            FROM UNNEST($1::uuid[], $2::bigint[], $3::timestamptz[]) AS a(id, count, accessed_at)
+            WHERE o.id = a.id
+            ",
+        )
+        .bind(ids)
+        .bind(counts)
+        .bind(accessed_at)
+        .execute(&self.pool)
+        .await?;
 
-        rows.into_iter().map(|r| r.into_domain()).collect()
+        Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn move_storage_class(
+        &self,
+        content_hash: &ContentHash,
+        from: StorageClass,
+        to: StorageClass,
+    ) -> Result<u64, RepositoryError> {
+        let result = self
+            .retry
+            .run("move_object_storage_class", || {
+                sqlx::query(
+                    r"
+                    UPDATE objects
+                    SET storage_class = $3
+                    WHERE content_hash = $1 AND storage_class = $2
+                    ",
+                )
+                .bind(content_hash.as_hex())
+                .bind(from.to_string())
+                .bind(to.to_string())
+                .execute(&self.pool)
+            })
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn delete(&self, id: &ObjectId) -> Result<(), RepositoryError> {
-        sqlx::query("DELETE FROM objects WHERE id = $1")
-            .bind(id.as_uuid())
-            .execute(&self.pool)
+        self.retry
+            .run("delete_object", || {
+                sqlx::query("DELETE FROM objects WHERE id = $1")
+                    .bind(id.as_uuid())
+                    .execute(&self.pool)
+            })
             .await?;
 
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn find_stuck_writing_objects(
         &self,
         age_hours: i64,
@@ -253,27 +925,62 @@ impl ObjectRepository for PostgresObjectRepository {
             id: uuid::Uuid,
         }
 
-        let rows = sqlx::query_as::<_, StuckObjectRow>(
+        let rows = self
+            .retry
+            .run("find_stuck_writing_objects", || {
+                sqlx::query_as::<_, StuckObjectRow>(
+                    r"
+                    SELECT id
+                    FROM objects
+                    WHERE status = 'WRITING'
+                      AND created_at < now() - ($1 || ' hours')::interval
+                    ORDER BY created_at ASC
+                    LIMIT $2
+                    ",
+                )
+                .bind(age_hours)
+                .bind(limit)
+                .fetch_all(&self.pool)
+            })
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ObjectId::from_uuid(row.id))
+            .collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn find_stuck_staged_objects(
+        &self,
+        age_minutes: i64,
+        limit: i64,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        let sql = format!(
             r"
-            SELECT id
-            FROM objects
+            {}
             WHERE status = 'WRITING'
-              AND created_at < now() - ($1 || ' hours')::interval
+              AND content_hash IS NOT NULL
+              AND created_at < now() - ($1 || ' minutes')::interval
             ORDER BY created_at ASC
             LIMIT $2
             ",
-        )
-        .bind(age_hours)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+            QueryBuilder::OBJECT_SELECT
+        );
+        let rows = self
+            .retry
+            .run("find_stuck_staged_objects", || {
+                sqlx::query_as::<_, ObjectRow>(AssertSqlSafe(sql.clone()))
+                    .bind(age_minutes)
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+            })
+            .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|row| ObjectId::from_uuid(row.id))
-            .collect())
+        rows.into_iter().map(ObjectRow::into_domain).collect()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
     async fn cleanup_stuck_uploads(&self, age_hours: i64) -> Result<usize, RepositoryError> {
         // Use the database function for atomic cleanup
         // Be tolerant of both INT4 and INT8 return types from Postgres
@@ -290,6 +997,68 @@ impl ObjectRepository for PostgresObjectRepository {
 
         Ok(count as usize)
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql"))]
+    async fn find_locked_hashes(
+        &self,
+        content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        let hashes: Vec<&str> = content_hashes.iter().map(|h| h.as_hex()).collect();
+        let rows: Vec<String> = sqlx::query_scalar(
+            r"
+            SELECT DISTINCT content_hash
+            FROM objects
+            WHERE content_hash = ANY($1)
+              AND (legal_hold OR retention_until > now())
+            ",
+        )
+        .bind(&hashes)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|hex| {
+                ContentHash::from_hex(hex)
+                    .map_err(|e| RepositoryError::SerializationError(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+// Text search row: an object plus its rank and excerpt
+#[derive(sqlx::FromRow)]
+struct TextSearchRow {
+    #[sqlx(flatten)]
+    object: ObjectRow,
+    rank: f32,
+    highlight: Option<String>,
+}
+
+impl TextSearchRow {
+    fn into_match(self) -> Result<TextSearchMatch, RepositoryError> {
+        Ok(TextSearchMatch {
+            object: self.object.into_domain()?,
+            rank: self.rank,
+            highlight: self.highlight,
+        })
+    }
+}
+
+// Object row joined with its blob's reference count
+#[derive(sqlx::FromRow)]
+struct ObjectRecordRow {
+    #[sqlx(flatten)]
+    object: ObjectRow,
+    blob_ref_count: Option<i64>,
+}
+
+impl ObjectRecordRow {
+    fn into_record(self) -> Result<ObjectRecord, RepositoryError> {
+        Ok(ObjectRecord {
+            object: self.object.into_domain()?,
+            blob_ref_count: self.blob_ref_count.map(|count| count.max(0) as u64),
+        })
+    }
 }
 
 // Internal row mapping struct
@@ -304,9 +1073,14 @@ struct ObjectRow {
     content_hash: Option<String>,
     size_bytes: Option<i64>,
     content_type: Option<String>,
+    content_encoding: Option<String>,
     metadata: serde_json::Value,
     created_at: OffsetDateTime,
     updated_at: OffsetDateTime,
+    download_count: i64,
+    last_access_at: Option<OffsetDateTime>,
+    retention_until: Option<OffsetDateTime>,
+    legal_hold: bool,
 }
 
 impl ObjectRow {
@@ -339,6 +1113,12 @@ impl ObjectRow {
             None => None,
         };
 
+        let content_encoding = self
+            .content_encoding
+            .map(|e| e.parse::<ContentEncoding>())
+            .transpose()
+            .map_err(RepositoryError::SerializationError)?;
+
         let metadata = ObjectMetadata::from_json(&self.metadata)
             .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
 
@@ -352,9 +1132,128 @@ impl ObjectRow {
             content_hash,
             self.size_bytes.map(|s| s as u64),
             self.content_type,
+            content_encoding,
             metadata,
             self.created_at,
             self.updated_at,
+            self.download_count as u64,
+            self.last_access_at,
+            self.retention_until,
+            self.legal_hold,
         ))
     }
 }
+
+// Read the columns of a projected listing row
+fn into_projection(
+    row: &PgRow,
+    fields: &[ObjectField],
+) -> Result<ObjectProjection, RepositoryError> {
+    let timestamp = |at: OffsetDateTime| at.format(&Rfc3339).unwrap_or_default();
+
+    let mut projection = ObjectProjection::default();
+    for field in fields {
+        match field {
+            ObjectField::Id => {
+                projection.id = Some(row.try_get::<uuid::Uuid, _>("id")?.to_string())
+            }
+            ObjectField::Namespace => projection.namespace = Some(row.try_get("namespace")?),
+            ObjectField::TenantId => projection.tenant_id = Some(row.try_get("tenant_id")?),
+            ObjectField::Key => projection.key = Some(row.try_get("key")?),
+            ObjectField::Status => {
+                let status: String = row.try_get("status")?;
+                projection.status = Some(
+                    status
+                        .parse::<ObjectStatus>()
+                        .map_err(RepositoryError::SerializationError)?,
+                );
+            }
+            ObjectField::StorageClass => {
+                let storage_class: String = row.try_get("storage_class")?;
+                projection.storage_class = Some(
+                    storage_class
+                        .parse::<StorageClass>()
+                        .map_err(RepositoryError::SerializationError)?,
+                );
+            }
+            ObjectField::ContentHash => {
+                projection.content_hash = Some(row.try_get("content_hash")?)
+            }
+            ObjectField::SizeBytes => {
+                let size: Option<i64> = row.try_get("size_bytes")?;
+                projection.size_bytes = Some(size.map(|s| s as u64));
+            }
+            ObjectField::ContentType => {
+                projection.content_type = Some(row.try_get("content_type")?)
+            }
+            ObjectField::Metadata => {
+                let metadata: serde_json::Value = row.try_get("metadata")?;
+                projection.metadata = Some(
+                    ObjectMetadata::from_json(&metadata)
+                        .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+                );
+            }
+            ObjectField::CreatedAt => {
+                projection.created_at = Some(timestamp(row.try_get("created_at")?))
+            }
+            ObjectField::UpdatedAt => {
+                projection.updated_at = Some(timestamp(row.try_get("updated_at")?))
+            }
+            ObjectField::DownloadCount => {
+                let count: i64 = row.try_get("download_count")?;
+                projection.download_count = Some(count as u64);
+            }
+            ObjectField::LastAccessedAt => {
+                let at: Option<OffsetDateTime> = row.try_get("last_access_at")?;
+                projection.last_accessed_at = Some(at.map(timestamp));
+            }
+        }
+    }
+    Ok(projection)
+}
+
+// Header-only row mapping struct
+#[derive(sqlx::FromRow)]
+struct ObjectHeadRow {
+    id: uuid::Uuid,
+    tenant_id: String,
+    storage_class: String,
+    content_hash: Option<String>,
+    size_bytes: Option<i64>,
+    content_type: Option<String>,
+    content_encoding: Option<String>,
+    updated_at: OffsetDateTime,
+}
+
+impl ObjectHeadRow {
+    fn into_head(self) -> Result<ObjectHead, RepositoryError> {
+        let storage_class = self
+            .storage_class
+            .parse::<StorageClass>()
+            .map_err(RepositoryError::SerializationError)?;
+
+        // Committed objects always carry a hash and size
+        let content_hash = self.content_hash.ok_or_else(|| {
+            RepositoryError::SerializationError("Committed object without content hash".into())
+        })?;
+        let size_bytes = self.size_bytes.ok_or_else(|| {
+            RepositoryError::SerializationError("Committed object without size".into())
+        })?;
+        let content_encoding = self
+            .content_encoding
+            .map(|e| e.parse::<ContentEncoding>())
+            .transpose()
+            .map_err(RepositoryError::SerializationError)?;
+
+        Ok(ObjectHead {
+            object_id: ObjectId::from_uuid(self.id),
+            tenant_id: self.tenant_id,
+            size_bytes: size_bytes as u64,
+            content_hash,
+            content_type: self.content_type,
+            content_encoding,
+            storage_class,
+            updated_at: self.updated_at,
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::ports::{ObjectShare, ObjectShareRepository, RepositoryError};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{ContentHash, Namespace, ObjectId, TenantId};
+
+pub struct PostgresObjectShareRepository {
+    pool: PgPool,
+}
+
+impl PostgresObjectShareRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ObjectShareRepository for PostgresObjectShareRepository {
+    async fn create(&self, share: &ObjectShare) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r"
+            INSERT INTO object_shares (
+                id, object_id, owner_tenant_id, grantee_tenant_id,
+                grantee_object_id, grantee_namespace, grantee_key,
+                content_hash, created_at, accepted_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ",
+        )
+        .bind(share.id)
+        .bind(share.object_id.as_uuid())
+        .bind(share.owner_tenant_id.to_string())
+        .bind(share.grantee_tenant_id.to_string())
+        .bind(share.grantee_object_id.map(|id| *id.as_uuid()))
+        .bind(share.grantee_namespace.as_str())
+        .bind(share.grantee_key.as_deref())
+        .bind(share.content_hash.as_hex())
+        .bind(share.created_at)
+        .bind(share.accepted_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: &Uuid) -> Result<Option<ObjectShare>, RepositoryError> {
+        let row = sqlx::query_as::<_, ObjectShareRow>(
+            r"
+            SELECT id, object_id, owner_tenant_id, grantee_tenant_id,
+                   grantee_object_id, grantee_namespace, grantee_key,
+                   content_hash, created_at, accepted_at
+            FROM object_shares
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(ObjectShareRow::into_domain).transpose()
+    }
+
+    async fn list_for_object(
+        &self,
+        object_id: &ObjectId,
+    ) -> Result<Vec<ObjectShare>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ObjectShareRow>(
+            r"
+            SELECT id, object_id, owner_tenant_id, grantee_tenant_id,
+                   grantee_object_id, grantee_namespace, grantee_key,
+                   content_hash, created_at, accepted_at
+            FROM object_shares
+            WHERE object_id = $1
+            ORDER BY created_at, id
+            ",
+        )
+        .bind(object_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(ObjectShareRow::into_domain).collect()
+    }
+
+    async fn list_for_grantee(
+        &self,
+        grantee_tenant_id: &TenantId,
+    ) -> Result<Vec<ObjectShare>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ObjectShareRow>(
+            r"
+            SELECT id, object_id, owner_tenant_id, grantee_tenant_id,
+                   grantee_object_id, grantee_namespace, grantee_key,
+                   content_hash, created_at, accepted_at
+            FROM object_shares
+            WHERE grantee_tenant_id = $1
+            ORDER BY created_at, id
+            ",
+        )
+        .bind(grantee_tenant_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(ObjectShareRow::into_domain).collect()
+    }
+
+    async fn accept(
+        &self,
+        id: &Uuid,
+        grantee_object_id: &ObjectId,
+        accepted_at: OffsetDateTime,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r"
+            UPDATE object_shares
+            SET grantee_object_id = $2, accepted_at = $3
+            WHERE id = $1 AND accepted_at IS NULL
+            ",
+        )
+        .bind(id)
+        .bind(grantee_object_id.as_uuid())
+        .bind(accepted_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM object_shares WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ObjectShareRow {
+    id: Uuid,
+    object_id: Uuid,
+    owner_tenant_id: String,
+    grantee_tenant_id: String,
+    grantee_object_id: Option<Uuid>,
+    grantee_namespace: String,
+    grantee_key: Option<String>,
+    content_hash: String,
+    created_at: OffsetDateTime,
+    accepted_at: Option<OffsetDateTime>,
+}
+
+impl ObjectShareRow {
+    fn into_domain(self) -> Result<ObjectShare, RepositoryError> {
+        let serialization = |e: DomainError| RepositoryError::SerializationError(e.to_string());
+
+        Ok(ObjectShare {
+            id: self.id,
+            object_id: ObjectId::from_uuid(self.object_id),
+            owner_tenant_id: TenantId::from_string(&self.owner_tenant_id).map_err(serialization)?,
+            grantee_tenant_id: TenantId::from_string(&self.grantee_tenant_id)
+                .map_err(serialization)?,
+            grantee_object_id: self.grantee_object_id.map(ObjectId::from_uuid),
+            grantee_namespace: Namespace::new(self.grantee_namespace).map_err(serialization)?,
+            grantee_key: self.grantee_key,
+            content_hash: ContentHash::from_hex(self.content_hash).map_err(serialization)?,
+            created_at: self.created_at,
+            accepted_at: self.accepted_at,
+        })
+    }
+}
@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::application::ports::{RefcountEntry, RefcountRepository, RepositoryError};
+use crate::domain::value_objects::ContentHash;
+
+/// Blobs touched more recently than this are skipped: an upload increments
+/// the blob before its object is committed, so a fresh blob can look
+/// over-counted while the upload is still in flight.
+const RECONCILE_GRACE_PERIOD_SECS: i64 = 300;
+
+pub struct PostgresRefcountRepository {
+    pool: PgPool,
+}
+
+impl PostgresRefcountRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefcountRepository for PostgresRefcountRepository {
+    async fn scan_batch(
+        &self,
+        after: Option<ContentHash>,
+        limit: i64,
+    ) -> Result<Vec<RefcountEntry>, RepositoryError> {
+        let rows = sqlx::query_as::<_, (String, i64, i64)>(
+            r"
+            SELECT
+                b.content_hash,
+                b.ref_count,
+                (SELECT COUNT(*) FROM objects o
+                    WHERE o.content_hash = b.content_hash
+                      AND o.status IN ('COMMITTED', 'WRITING'))
+            FROM blobs b
+            WHERE ($1::TEXT IS NULL OR b.content_hash > $1)
+              AND COALESCE(b.last_used_at, b.created_at)
+                    < now() - make_interval(secs => $3)
+            ORDER BY b.content_hash
+            LIMIT $2
+            ",
+        )
+        .bind(after.as_ref().map(|h| h.as_hex()))
+        .bind(limit)
+        .bind(RECONCILE_GRACE_PERIOD_SECS as f64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(hash, stored_ref_count, actual_ref_count)| {
+                Ok(RefcountEntry {
+                    content_hash: ContentHash::from_hex(hash)
+                        .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+                    stored_ref_count,
+                    actual_ref_count,
+                })
+            })
+            .collect()
+    }
+
+    async fn set_ref_count(
+        &self,
+        content_hash: &ContentHash,
+        expected: i64,
+        ref_count: i64,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r"
+            UPDATE blobs
+            SET ref_count = $3
+            WHERE content_hash = $1 AND ref_count = $2
+            ",
+        )
+        .bind(content_hash.as_hex())
+        .bind(expected)
+        .bind(ref_count)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn load_checkpoint(&self, task: &str) -> Result<Option<ContentHash>, RepositoryError> {
+        let cursor = sqlx::query_scalar::<_, String>(
+            "SELECT cursor FROM maintenance_checkpoints WHERE task = $1",
+        )
+        .bind(task)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        cursor
+            .map(|c| {
+                ContentHash::from_hex(c)
+                    .map_err(|e| RepositoryError::SerializationError(e.to_string()))
+            })
+            .transpose()
+    }
+
+    async fn save_checkpoint(
+        &self,
+        task: &str,
+        cursor: Option<ContentHash>,
+    ) -> Result<(), RepositoryError> {
+        match cursor {
+            Some(cursor) => {
+                sqlx::query(
+                    r"
+                    INSERT INTO maintenance_checkpoints (task, cursor, updated_at)
+                    VALUES ($1, $2, now())
+                    ON CONFLICT (task) DO UPDATE SET cursor = EXCLUDED.cursor, updated_at = now()
+                    ",
+                )
+                .bind(task)
+                .bind(cursor.as_hex())
+                .execute(&self.pool)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM maintenance_checkpoints WHERE task = $1")
+                    .bind(task)
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::application::ports::{RepositoryError, ScrubCheckpoint, ScrubFinding, ScrubRepository};
+use crate::domain::entities::Blob;
+use crate::domain::value_objects::{ContentHash, StorageClass};
+
+/// Checkpoint key of the pass in progress (cursor: last verified hash)
+const SCRUB_TASK: &str = "blob_scrub";
+/// Checkpoint key of the last completed pass (cursor: RFC 3339 time)
+const SCRUB_PASS_TASK: &str = "blob_scrub:pass_completed";
+
+pub struct PostgresScrubRepository {
+    pool: PgPool,
+}
+
+impl PostgresScrubRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn load_cursor(&self, task: &str) -> Result<Option<String>, RepositoryError> {
+        Ok(sqlx::query_scalar::<_, String>(
+            "SELECT cursor FROM maintenance_checkpoints WHERE task = $1",
+        )
+        .bind(task)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+}
+
+fn parse_hash(hash: String) -> Result<ContentHash, RepositoryError> {
+    ContentHash::from_hex(hash).map_err(|e| RepositoryError::SerializationError(e.to_string()))
+}
+
+fn parse_storage_class(storage_class: &str) -> Result<StorageClass, RepositoryError> {
+    storage_class
+        .parse()
+        .map_err(RepositoryError::SerializationError)
+}
+
+#[async_trait]
+impl ScrubRepository for PostgresScrubRepository {
+    async fn scan_blobs(
+        &self,
+        after: Option<ContentHash>,
+        limit: i64,
+    ) -> Result<Vec<Blob>, RepositoryError> {
+        let rows = sqlx::query_as::<_, (String, String, i64, i64, OffsetDateTime)>(
+            r"
+            SELECT content_hash, storage_class, size_bytes, ref_count, created_at
+            FROM blobs
+            WHERE ($1::TEXT IS NULL OR content_hash > $1)
+            ORDER BY content_hash
+            LIMIT $2
+            ",
+        )
+        .bind(after.as_ref().map(|h| h.as_hex()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(hash, storage_class, size_bytes, ref_count, created_at)| {
+                Ok(Blob::reconstruct(
+                    parse_hash(hash)?,
+                    parse_storage_class(&storage_class)?,
+                    size_bytes as u64,
+                    ref_count as i32,
+                    created_at,
+                ))
+            })
+            .collect()
+    }
+
+    async fn load_checkpoint(&self) -> Result<ScrubCheckpoint, RepositoryError> {
+        let cursor = self
+            .load_cursor(SCRUB_TASK)
+            .await?
+            .map(parse_hash)
+            .transpose()?;
+        let last_pass_completed_at = self
+            .load_cursor(SCRUB_PASS_TASK)
+            .await?
+            .map(|at| {
+                OffsetDateTime::parse(&at, &Rfc3339)
+                    .map_err(|e| RepositoryError::SerializationError(e.to_string()))
+            })
+            .transpose()?;
+
+        Ok(ScrubCheckpoint {
+            cursor,
+            last_pass_completed_at,
+        })
+    }
+
+    async fn save_checkpoint(&self, checkpoint: &ScrubCheckpoint) -> Result<(), RepositoryError> {
+        let last_pass_completed_at = checkpoint
+            .last_pass_completed_at
+            .map(|at| at.format(&Rfc3339))
+            .transpose()
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+
+        let mut tx = self.pool.begin().await?;
+        for (task, cursor) in [
+            (SCRUB_TASK, checkpoint.cursor.as_ref().map(|h| h.as_hex())),
+            (SCRUB_PASS_TASK, last_pass_completed_at),
+        ] {
+            match cursor {
+                Some(cursor) => {
+                    sqlx::query(
+                        r"
+                        INSERT INTO maintenance_checkpoints (task, cursor, updated_at)
+                        VALUES ($1, $2, now())
+                        ON CONFLICT (task) DO UPDATE SET cursor = EXCLUDED.cursor, updated_at = now()
+                        ",
+                    )
+                    .bind(task)
+                    .bind(cursor)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                None => {
+                    sqlx::query("DELETE FROM maintenance_checkpoints WHERE task = $1")
+                        .bind(task)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn flag(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+        error: &str,
+    ) -> Result<(), RepositoryError> {
+        // A blob deleted since it was scanned has nothing left to flag
+        sqlx::query(
+            r"
+            INSERT INTO blob_scrub_findings (content_hash, storage_class, error)
+            SELECT $1, $2, $3
+            WHERE EXISTS (SELECT 1 FROM blobs WHERE content_hash = $1)
+            ON CONFLICT (content_hash) DO UPDATE
+                SET error = EXCLUDED.error, last_seen_at = now()
+            ",
+        )
+        .bind(content_hash.as_hex())
+        .bind(storage_class.to_string())
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn clear(&self, content_hash: &ContentHash) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM blob_scrub_findings WHERE content_hash = $1")
+            .bind(content_hash.as_hex())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn findings(&self, limit: i64) -> Result<Vec<ScrubFinding>, RepositoryError> {
+        let rows = sqlx::query_as::<_, (String, String, String, OffsetDateTime, OffsetDateTime)>(
+            r"
+            SELECT f.content_hash, f.storage_class, f.error, f.detected_at, f.last_seen_at
+            FROM blob_scrub_findings f
+            JOIN blobs b ON b.content_hash = f.content_hash
+            WHERE b.ref_count > 0
+            ORDER BY f.last_seen_at DESC
+            LIMIT $1
+            ",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(hash, storage_class, error, detected_at, last_seen_at)| {
+                Ok(ScrubFinding {
+                    content_hash: parse_hash(hash)?,
+                    storage_class: parse_storage_class(&storage_class)?,
+                    error,
+                    detected_at,
+                    last_seen_at,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::application::dto::{DedupStats, TenantDedupStats};
+use crate::application::ports::{RepositoryError, StatsRepository};
+
+pub struct PostgresStatsRepository {
+    pool: PgPool,
+}
+
+impl PostgresStatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StatsRepository for PostgresStatsRepository {
+    async fn global_usage(&self) -> Result<DedupStats, RepositoryError> {
+        // Physical usage comes straight from the blobs table: each row is one
+        // distinct content hash on disk.
+        let (object_count, logical_bytes, blob_count, physical_bytes) =
+            sqlx::query_as::<_, (i64, i64, i64, i64)>(
+                r"
+                SELECT
+                    (SELECT COUNT(*) FROM objects WHERE status = 'COMMITTED'),
+                    (SELECT COALESCE(SUM(size_bytes), 0)::BIGINT
+                        FROM objects WHERE status = 'COMMITTED'),
+                    (SELECT COUNT(*) FROM blobs WHERE ref_count > 0),
+                    (SELECT COALESCE(SUM(size_bytes), 0)::BIGINT
+                        FROM blobs WHERE ref_count > 0)
+                ",
+            )
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(DedupStats::new(
+            object_count,
+            logical_bytes,
+            blob_count,
+            physical_bytes,
+        ))
+    }
+
+    async fn usage_by_tenant(&self) -> Result<Vec<TenantDedupStats>, RepositoryError> {
+        // A blob shared by two tenants counts towards both tenants' physical
+        // usage, so per-tenant figures do not sum to the global ones.
+        let rows = sqlx::query_as::<_, (String, i64, i64, i64, i64)>(
+            r"
+            SELECT
+                logical.tenant_id,
+                logical.object_count,
+                logical.logical_bytes,
+                COALESCE(physical.blob_count, 0),
+                COALESCE(physical.physical_bytes, 0)
+            FROM (
+                SELECT tenant_id,
+                       COUNT(*) AS object_count,
+                       COALESCE(SUM(size_bytes), 0)::BIGINT AS logical_bytes
+                FROM objects
+                WHERE status = 'COMMITTED'
+                GROUP BY tenant_id
+            ) logical
+            LEFT JOIN (
+                SELECT refs.tenant_id,
+                       COUNT(*) AS blob_count,
+                       SUM(b.size_bytes)::BIGINT AS physical_bytes
+                FROM (
+                    SELECT DISTINCT tenant_id, content_hash
+                    FROM objects
+                    WHERE status = 'COMMITTED' AND content_hash IS NOT NULL
+                ) refs
+                JOIN blobs b ON b.content_hash = refs.content_hash
+                GROUP BY refs.tenant_id
+            ) physical ON physical.tenant_id = logical.tenant_id
+            ORDER BY logical.tenant_id
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(tenant_id, object_count, logical_bytes, blob_count, physical_bytes)| {
+                    TenantDedupStats {
+                        tenant_id,
+                        stats: DedupStats::new(
+                            object_count,
+                            logical_bytes,
+                            blob_count,
+                            physical_bytes,
+                        ),
+                    }
+                },
+            )
+            .collect())
+    }
+}
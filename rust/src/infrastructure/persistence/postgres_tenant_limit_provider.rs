@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::application::ports::{RepositoryError, TenantLimitProvider};
+
+/// Reads tenant rate limit tiers from `tenant_rate_limit_tiers`
+pub struct PostgresTenantLimitProvider {
+    pool: PgPool,
+}
+
+impl PostgresTenantLimitProvider {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TenantLimitProvider for PostgresTenantLimitProvider {
+    async fn requests_per_minute(&self, tenant_id: &str) -> Result<Option<u32>, RepositoryError> {
+        let limit = sqlx::query_scalar::<_, i32>(
+            r"
+            SELECT tiers.requests_per_minute
+            FROM tenant_rate_limit_tiers tenants
+            JOIN rate_limit_tiers tiers ON tiers.name = tenants.tier
+            WHERE tenants.tenant_id = $1
+            ",
+        )
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        // The column is constrained to be positive
+        Ok(limit.map(|limit| limit.max(1) as u32))
+    }
+}
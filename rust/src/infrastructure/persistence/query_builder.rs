@@ -1,3 +1,11 @@
+use crate::api::middleware::input_sanitization::sanitize_sql_input;
+use crate::application::dto::{ObjectField, SortDirection, SortField};
+use crate::application::key_prefix_query::KeyPrefixQuery;
+use crate::application::list_cursor::{CursorValue, ListCursor};
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::ports::AuditQueryFilter;
+use sqlx::Postgres;
+
 /// Common SQL query fragments to reduce duplication and ensure consistency
 pub struct QueryBuilder;
 
@@ -5,14 +13,320 @@ impl QueryBuilder {
     /// Base SELECT clause for object queries
     pub const OBJECT_SELECT: &'static str = r#"
         SELECT id, namespace, tenant_id, key, status, storage_class,
-               content_hash, size_bytes, content_type, metadata,
-               created_at, updated_at
+               content_hash, size_bytes, content_type, content_encoding, metadata,
+               created_at, updated_at, download_count, last_access_at,
+               retention_until, legal_hold
+        FROM objects
+    "#;
+
+    /// SELECT clause for objects with their blob's reference count
+    pub const OBJECT_RECORD_SELECT: &'static str = r#"
+        SELECT o.id, o.namespace, o.tenant_id, o.key, o.status, o.storage_class,
+               o.content_hash, o.size_bytes, o.content_type, o.content_encoding,
+               o.metadata, o.created_at, o.updated_at, o.download_count,
+               o.last_access_at, o.retention_until, o.legal_hold,
+               b.ref_count AS blob_ref_count
+        FROM objects o
+        LEFT JOIN blobs b ON b.content_hash = o.content_hash
+    "#;
+
+    /// SELECT clause for header-only object queries (no metadata document)
+    pub const OBJECT_HEAD_SELECT: &'static str = r#"
+        SELECT id, tenant_id, storage_class, content_hash, size_bytes,
+               content_type, content_encoding, updated_at
         FROM objects
     "#;
 
     /// WHERE clause for committed objects only
     pub const COMMITTED_WHERE: &'static str = "WHERE status = 'COMMITTED'";
 
+    /// `ts_headline` options for search result excerpts
+    pub const HEADLINE_OPTIONS: &'static str =
+        "StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2";
+
+    /// Build a `to_tsquery` expression requiring every word of a free-text query
+    ///
+    /// Each word becomes a quoted lexeme, so tsquery operators typed by the
+    /// user (`&`, `|`, `!`, `<->`, `:*`) are matched literally rather than
+    /// parsed. With `prefix`, each lexeme also matches longer words. A query
+    /// without words gives an empty tsquery, which matches nothing.
+    pub fn tsquery(query: &str, prefix: bool) -> String {
+        let suffix = if prefix { ":*" } else { "" };
+        query
+            .split_whitespace()
+            .map(|word| format!("'{}'{}", sanitize_sql_input(word), suffix))
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+
+    /// Build an ORDER BY expression from a whitelisted sort column
+    ///
+    /// Objects that were never downloaded have no last access time and sort
+    /// last in either direction.
+    pub fn order_by(sort_by: SortField, sort_direction: SortDirection) -> String {
+        let column = Self::sort_column(sort_by);
+        let direction = Self::sort_direction(sort_direction);
+        let nulls = if sort_by == SortField::LastAccessedAt {
+            " NULLS LAST"
+        } else {
+            ""
+        };
+        format!("{column} {direction}{nulls}")
+    }
+
+    /// ORDER BY expression for paged listings: [`Self::order_by`], then the
+    /// ID, so objects with equal sort values keep their order across pages
+    pub fn paged_order_by(sort_by: SortField, sort_direction: SortDirection) -> String {
+        format!(
+            "{}, id {}",
+            Self::order_by(sort_by, sort_direction),
+            Self::sort_direction(sort_direction)
+        )
+    }
+
+    /// Append the condition for objects after a cursor to a query with an
+    /// open WHERE clause, for a listing in [`Self::paged_order_by`] order
+    ///
+    /// Unset sort values sort like Postgres does by default: last when
+    /// ascending, first when descending. Only cursors for
+    /// [`ListCursor::supports`] orders are expected here.
+    pub fn push_after_cursor<'a>(
+        qb: &mut sqlx::QueryBuilder<'a, Postgres>,
+        cursor: &'a ListCursor,
+    ) {
+        let column = Self::sort_column(cursor.sort_by);
+        let (after, nulls_last) = match cursor.sort_direction {
+            SortDirection::Asc => (" > ", true),
+            SortDirection::Desc => (" < ", false),
+        };
+
+        qb.push(" AND (");
+        match &cursor.value {
+            Some(value) => {
+                // (column, id) past the cursor, or an unset value sorted after it
+                qb.push(column);
+                qb.push(after);
+                Self::push_cursor_value(qb, value);
+                qb.push(" OR (");
+                qb.push(column);
+                qb.push(" = ");
+                Self::push_cursor_value(qb, value);
+                qb.push(" AND id");
+                qb.push(after);
+                qb.push_bind(*cursor.id.as_uuid());
+                qb.push(")");
+                if nulls_last {
+                    qb.push(" OR ");
+                    qb.push(column);
+                    qb.push(" IS NULL");
+                }
+            }
+            None => {
+                qb.push("(");
+                qb.push(column);
+                qb.push(" IS NULL AND id");
+                qb.push(after);
+                qb.push_bind(*cursor.id.as_uuid());
+                qb.push(")");
+                if !nulls_last {
+                    qb.push(" OR ");
+                    qb.push(column);
+                    qb.push(" IS NOT NULL");
+                }
+            }
+        }
+        qb.push(")");
+    }
+
+    fn push_cursor_value<'a>(qb: &mut sqlx::QueryBuilder<'a, Postgres>, value: &'a CursorValue) {
+        match value {
+            CursorValue::Time(at) => qb.push_bind(*at),
+            CursorValue::Int(n) => qb.push_bind(*n),
+            CursorValue::Text(text) => qb.push_bind(text.as_str()),
+        };
+    }
+
+    /// Whitelisted column behind a sort field
+    fn sort_column(sort_by: SortField) -> &'static str {
+        match sort_by {
+            SortField::CreatedAt => "created_at",
+            SortField::UpdatedAt => "updated_at",
+            SortField::SizeBytes => "size_bytes",
+            SortField::Key => "key",
+            SortField::ContentType => "content_type",
+            SortField::DownloadCount => "download_count",
+            SortField::LastAccessedAt => "last_access_at",
+        }
+    }
+
+    fn sort_direction(sort_direction: SortDirection) -> &'static str {
+        match sort_direction {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    /// Build a SELECT list of the whitelisted columns behind projected fields
+    pub fn object_columns(fields: &[ObjectField]) -> String {
+        fields
+            .iter()
+            .map(|field| match field {
+                ObjectField::LastAccessedAt => "last_access_at",
+                field => field.as_str(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Append metadata filter conditions to a query with an open WHERE clause
+    ///
+    /// Containment uses `@>` and key existence `@?`, both served by the
+    /// `jsonb_path_ops` GIN index. Documents and paths are bound, not inlined.
+    pub fn push_metadata_conditions<'a>(
+        qb: &mut sqlx::QueryBuilder<'a, Postgres>,
+        query: &'a MetadataQuery,
+    ) {
+        if let Some(contains) = query.contains() {
+            qb.push(" AND metadata @> ");
+            qb.push_bind(sqlx::types::Json(contains));
+        }
+        for path in query.jsonpaths() {
+            qb.push(" AND metadata @? ");
+            qb.push_bind(path);
+            qb.push("::jsonpath");
+        }
+    }
+
+    /// Append key prefix conditions to a query with an open WHERE clause
+    ///
+    /// With a delimiter, keys folded into a common prefix are left out; they
+    /// are listed through [`Self::push_common_prefix_select`] instead.
+    pub fn push_key_prefix_conditions<'a>(
+        qb: &mut sqlx::QueryBuilder<'a, Postgres>,
+        keys: &'a KeyPrefixQuery,
+    ) {
+        if !keys.prefix().is_empty() {
+            qb.push(" AND key LIKE ");
+            qb.push_bind(keys.like_pattern());
+            qb.push(" ESCAPE '\\'");
+        }
+        if let Some(delimiter) = keys.delimiter() {
+            qb.push(" AND strpos(substr(key, ");
+            qb.push_bind(keys.remainder_start());
+            qb.push("), ");
+            qb.push_bind(delimiter);
+            qb.push(") = 0");
+        }
+    }
+
+    /// Append audit log filter conditions to a query with an open WHERE clause
+    ///
+    /// Shared by the audit query and its count, so both see the same entries.
+    pub fn push_audit_filter_conditions<'a>(
+        qb: &mut sqlx::QueryBuilder<'a, Postgres>,
+        filter: &'a AuditQueryFilter,
+    ) {
+        if let Some(event_types) = filter.event_types.as_deref() {
+            if !event_types.is_empty() {
+                qb.push(" AND event_type = ANY(");
+                qb.push_bind(event_types);
+                qb.push(")");
+            }
+        }
+        if let Some(user_id) = &filter.user_id {
+            qb.push(" AND user_id = ");
+            qb.push_bind(user_id);
+        }
+        if let Some(tenant_id) = &filter.tenant_id {
+            qb.push(" AND tenant_id = ");
+            qb.push_bind(tenant_id);
+        }
+        if let Some(api_key_id) = &filter.api_key_id {
+            qb.push(" AND api_key_id = ");
+            qb.push_bind(api_key_id);
+        }
+        if let Some(ip_address) = &filter.ip_address {
+            qb.push(" AND ip_address = CAST(");
+            qb.push_bind(ip_address);
+            qb.push(" AS inet)");
+        }
+        if let Some(path_pattern) = &filter.path_pattern {
+            qb.push(" AND path LIKE ");
+            qb.push_bind(format!("%{}%", path_pattern));
+        }
+        if let Some(min_code) = filter.status_code_min {
+            qb.push(" AND status_code >= ");
+            qb.push_bind(min_code);
+        }
+        if let Some(max_code) = filter.status_code_max {
+            qb.push(" AND status_code <= ");
+            qb.push_bind(max_code);
+        }
+        if let Some(from_ts) = filter.from_timestamp {
+            qb.push(" AND timestamp >= ");
+            qb.push_bind(from_ts);
+        }
+        if let Some(to_ts) = filter.to_timestamp {
+            qb.push(" AND timestamp <= ");
+            qb.push_bind(to_ts);
+        }
+        match filter.has_error {
+            Some(true) => {
+                qb.push(" AND error_message IS NOT NULL");
+            }
+            Some(false) => {
+                qb.push(" AND error_message IS NULL");
+            }
+            None => {}
+        }
+    }
+
+    /// Append `LIMIT` and `OFFSET` for a page resolved by
+    /// [`Pagination`](crate::application::pagination::Pagination)
+    pub fn push_page(qb: &mut sqlx::QueryBuilder<'_, Postgres>, limit: i64, offset: i64) {
+        qb.push(" LIMIT ");
+        qb.push_bind(limit);
+        qb.push(" OFFSET ");
+        qb.push_bind(offset);
+    }
+
+    /// Start a query selecting the distinct common prefixes of a delimited
+    /// listing, leaving its WHERE clause open
+    ///
+    /// Each prefix runs from the start of the key to the end of the first
+    /// delimiter after the listing prefix. Without a delimiter the query
+    /// matches nothing.
+    pub fn push_common_prefix_select<'a>(
+        qb: &mut sqlx::QueryBuilder<'a, Postgres>,
+        keys: &'a KeyPrefixQuery,
+    ) {
+        let Some(delimiter) = keys.delimiter() else {
+            qb.push("SELECT NULL::text AS common_prefix FROM objects WHERE FALSE");
+            return;
+        };
+        let start = keys.remainder_start();
+        let delimiter_len = delimiter.chars().count() as i32;
+
+        qb.push("SELECT DISTINCT left(key, ");
+        qb.push_bind(start + delimiter_len - 2);
+        qb.push(" + strpos(substr(key, ");
+        qb.push_bind(start);
+        qb.push("), ");
+        qb.push_bind(delimiter);
+        qb.push(")) AS common_prefix FROM objects ");
+        qb.push(Self::COMMITTED_WHERE);
+        if !keys.prefix().is_empty() {
+            qb.push(" AND key LIKE ");
+            qb.push_bind(keys.like_pattern());
+            qb.push(" ESCAPE '\\'");
+        }
+        qb.push(" AND strpos(substr(key, ");
+        qb.push_bind(start);
+        qb.push("), ");
+        qb.push_bind(delimiter);
+        qb.push(") > 0");
+    }
+
     /// Build WHERE clause with namespace and tenant filter
     pub fn namespace_tenant_where(_namespace: &str, _tenant_id: &str) -> String {
         format!(
@@ -30,3 +344,155 @@ impl QueryBuilder {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::ObjectId;
+
+    #[test]
+    fn test_object_columns_map_projected_fields() {
+        assert_eq!(
+            QueryBuilder::object_columns(&[
+                ObjectField::Id,
+                ObjectField::SizeBytes,
+                ObjectField::LastAccessedAt
+            ]),
+            "id, size_bytes, last_access_at"
+        );
+    }
+
+    #[test]
+    fn test_tsquery_quotes_each_word() {
+        assert_eq!(
+            QueryBuilder::tsquery("llama  weights", false),
+            "'llama' & 'weights'"
+        );
+        assert_eq!(QueryBuilder::tsquery("lla", true), "'lla':*");
+        assert_eq!(QueryBuilder::tsquery("   ", false), "");
+    }
+
+    #[test]
+    fn test_tsquery_escapes_operators_and_quotes() {
+        assert_eq!(
+            QueryBuilder::tsquery("it's !a|b\\", false),
+            "'it''s' & '!a|b\\\\'"
+        );
+        assert_eq!(QueryBuilder::tsquery("nul\0l", false), "'null'");
+    }
+
+    #[test]
+    fn test_push_metadata_conditions_binds_filters() {
+        let query = MetadataQuery::new(
+            Some(serde_json::json!({"tags": {"author": "jane"}})),
+            &["tags.license".to_string()],
+        )
+        .unwrap();
+        let mut qb = sqlx::QueryBuilder::<Postgres>::new("SELECT 1 FROM objects WHERE true");
+
+        QueryBuilder::push_metadata_conditions(&mut qb, &query);
+
+        assert_eq!(
+            qb.sql(),
+            "SELECT 1 FROM objects WHERE true AND metadata @> $1 AND metadata @? $2::jsonpath"
+        );
+    }
+
+    #[test]
+    fn test_push_metadata_conditions_empty_query() {
+        let query = MetadataQuery::default();
+        let mut qb = sqlx::QueryBuilder::<Postgres>::new("SELECT 1");
+
+        QueryBuilder::push_metadata_conditions(&mut qb, &query);
+
+        assert_eq!(qb.sql(), "SELECT 1");
+    }
+
+    #[test]
+    fn test_push_key_prefix_conditions_binds_prefix_and_delimiter() {
+        let keys = KeyPrefixQuery::new(Some("photos/".to_string()), Some("/".to_string())).unwrap();
+        let mut qb = sqlx::QueryBuilder::<Postgres>::new("SELECT 1 FROM objects WHERE true");
+
+        QueryBuilder::push_key_prefix_conditions(&mut qb, &keys);
+
+        assert_eq!(
+            qb.sql(),
+            "SELECT 1 FROM objects WHERE true AND key LIKE $1 ESCAPE '\\' \
+             AND strpos(substr(key, $2), $3) = 0"
+        );
+    }
+
+    #[test]
+    fn test_push_key_prefix_conditions_empty_query() {
+        let keys = KeyPrefixQuery::default();
+        let mut qb = sqlx::QueryBuilder::<Postgres>::new("SELECT 1");
+
+        QueryBuilder::push_key_prefix_conditions(&mut qb, &keys);
+
+        assert_eq!(qb.sql(), "SELECT 1");
+    }
+
+    #[test]
+    fn test_push_common_prefix_select() {
+        let keys = KeyPrefixQuery::new(None, Some("/".to_string())).unwrap();
+        let mut qb = sqlx::QueryBuilder::<Postgres>::new("");
+
+        QueryBuilder::push_common_prefix_select(&mut qb, &keys);
+
+        assert_eq!(
+            qb.sql(),
+            "SELECT DISTINCT left(key, $1 + strpos(substr(key, $2), $3)) AS common_prefix \
+             FROM objects WHERE status = 'COMMITTED' AND strpos(substr(key, $4), $5) > 0"
+        );
+    }
+
+    #[test]
+    fn test_order_by() {
+        assert_eq!(
+            QueryBuilder::order_by(SortField::default(), SortDirection::default()),
+            "created_at DESC"
+        );
+        assert_eq!(
+            QueryBuilder::order_by(SortField::DownloadCount, SortDirection::Asc),
+            "download_count ASC"
+        );
+        assert_eq!(
+            QueryBuilder::order_by(SortField::LastAccessedAt, SortDirection::Desc),
+            "last_access_at DESC NULLS LAST"
+        );
+    }
+
+    #[test]
+    fn test_paged_order_by_breaks_ties_by_id() {
+        assert_eq!(
+            QueryBuilder::paged_order_by(SortField::SizeBytes, SortDirection::Asc),
+            "size_bytes ASC, id ASC"
+        );
+    }
+
+    #[test]
+    fn test_push_after_cursor() {
+        let mut cursor = ListCursor {
+            sort_by: SortField::Key,
+            sort_direction: SortDirection::Asc,
+            value: Some(CursorValue::Text("b.txt".to_string())),
+            id: ObjectId::new(),
+        };
+        let mut qb = sqlx::QueryBuilder::new("WHERE TRUE");
+        QueryBuilder::push_after_cursor(&mut qb, &cursor);
+        assert_eq!(
+            qb.sql(),
+            "WHERE TRUE AND (key > $1 OR (key = $2 AND id > $3) OR key IS NULL)"
+        );
+
+        // After an object without a key, descending: keyed objects come next
+        cursor.sort_direction = SortDirection::Desc;
+        cursor.value = None;
+        let mut qb = sqlx::QueryBuilder::new("WHERE TRUE");
+        QueryBuilder::push_after_cursor(&mut qb, &cursor);
+        assert_eq!(
+            qb.sql(),
+            "WHERE TRUE AND ((key IS NULL AND id < $1) OR key IS NOT NULL)"
+        );
+    }
+}
@@ -0,0 +1,1416 @@
+//! Object metadata in Redis
+//!
+//! Each object is a hash holding its JSON document and a version that every
+//! write bumps. Writes go through a Lua script that checks the version and
+//! maintains the indexes in the same step, so concurrent updates of one
+//! object never overwrite each other: the loser reloads and retries.
+//!
+//! Keys, all under a configurable prefix:
+//!
+//! - `object:{id}`: hash of `doc` and `version`
+//! - `key:{tenant}:{namespace}:{key}`: ID of the object holding the key
+//! - `namespace:{tenant}:{namespace}`: set of the namespace's object IDs
+//! - `namespaces:{tenant}`: set of the tenant's namespace names
+//! - `writing`: IDs of uploads in progress, scored by creation time
+//! - `locked`: IDs of objects under retention or legal hold, scored by the
+//!   end of the retention (`+inf` under legal hold)
+//!
+//! There is no secondary index on metadata or sort columns: listings,
+//! searches and counts load the namespace and filter it in memory, so their
+//! cost grows with the namespace rather than the page.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use once_cell::sync::Lazy;
+use redis::aio::ConnectionManager;
+use redis::Script;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::application::dto::{
+    ObjectAccess, ObjectDto, ObjectField, ObjectHead, ObjectProjection, ObjectRecord,
+    SearchRequest, SortDirection, SortField, TextSearchMatch, TextSearchPage, TextSearchRequest,
+};
+use crate::application::key_prefix_query::KeyPrefixQuery;
+use crate::application::list_cursor::{CursorValue, ListCursor};
+use crate::application::metadata_index::MetadataIndexConfig;
+use crate::application::metadata_query::MetadataQuery;
+use crate::application::pagination::{PageLimits, Pagination};
+use crate::application::ports::{ObjectRepository, ObjectStream, RepositoryError};
+use crate::domain::entities::Object;
+use crate::domain::value_objects::{
+    ContentEncoding, ContentHash, Namespace, ObjectId, ObjectMetadata, ObjectStatus, StorageClass,
+    TenantId,
+};
+
+/// Times a compare-and-swap update reloads an object that changed under it
+const MAX_UPDATE_ATTEMPTS: usize = 8;
+
+/// Object documents fetched per round trip when loading a namespace
+const LOAD_BATCH: usize = 500;
+
+/// Write an object document if its version is still `ARGV[1]` (0: absent)
+///
+/// `ARGV[4]` says what to do with the key index `KEYS[2]`: `claim` it for
+/// the object, `release` it if the object holds it, or leave it (`none`).
+/// `ARGV[6]` and `ARGV[7]` score the object in `writing` and `locked`; an
+/// empty score removes it. Returns 1 when written, 0 on a version conflict and -1 when another
+/// object holds the key.
+static PUT_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r"
+        local version = tonumber(redis.call('HGET', KEYS[1], 'version') or '0')
+        if version ~= tonumber(ARGV[1]) then
+            return 0
+        end
+        if ARGV[4] == 'claim' then
+            local owner = redis.call('GET', KEYS[2])
+            if owner and owner ~= ARGV[3] then
+                return -1
+            end
+            redis.call('SET', KEYS[2], ARGV[3])
+        elseif ARGV[4] == 'release' then
+            if redis.call('GET', KEYS[2]) == ARGV[3] then
+                redis.call('DEL', KEYS[2])
+            end
+        end
+        redis.call('HSET', KEYS[1], 'doc', ARGV[2], 'version', version + 1)
+        redis.call('SADD', KEYS[3], ARGV[3])
+        redis.call('SADD', KEYS[4], ARGV[5])
+        if ARGV[6] == '' then
+            redis.call('ZREM', KEYS[5], ARGV[3])
+        else
+            redis.call('ZADD', KEYS[5], ARGV[6], ARGV[3])
+        end
+        if ARGV[7] == '' then
+            redis.call('ZREM', KEYS[6], ARGV[3])
+        else
+            redis.call('ZADD', KEYS[6], ARGV[7], ARGV[3])
+        end
+        return 1
+        ",
+    )
+});
+
+/// Remove an object document and its index entries
+///
+/// The key index `KEYS[2]` is only touched when `ARGV[2]` is `1`, and only
+/// removed if the object still holds it.
+static DELETE_SCRIPT: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r"
+        if ARGV[2] == '1' and redis.call('GET', KEYS[2]) == ARGV[1] then
+            redis.call('DEL', KEYS[2])
+        end
+        redis.call('DEL', KEYS[1])
+        redis.call('SREM', KEYS[3], ARGV[1])
+        redis.call('ZREM', KEYS[4], ARGV[1])
+        redis.call('ZREM', KEYS[6], ARGV[1])
+        if redis.call('SCARD', KEYS[3]) == 0 then
+            redis.call('SREM', KEYS[5], ARGV[3])
+        end
+        return 1
+        ",
+    )
+});
+
+/// What a write does with the object's key index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyClaim {
+    Claim,
+    Release,
+    Keep,
+}
+
+impl KeyClaim {
+    fn as_arg(self) -> &'static str {
+        match self {
+            Self::Claim => "claim",
+            Self::Release => "release",
+            Self::Keep => "none",
+        }
+    }
+}
+
+/// Outcome of a versioned write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Put {
+    Written,
+    Conflict,
+    KeyTaken,
+}
+
+#[derive(Clone)]
+pub struct RedisObjectRepository {
+    conn: ConnectionManager,
+    prefix: Arc<str>,
+    metadata_index: MetadataIndexConfig,
+}
+
+impl RedisObjectRepository {
+    /// Use `conn`, keeping every key under `prefix`
+    pub fn new(conn: ConnectionManager, prefix: impl Into<String>) -> Self {
+        Self {
+            conn,
+            prefix: Arc::from(prefix.into()),
+            metadata_index: MetadataIndexConfig::default(),
+        }
+    }
+
+    /// Connect to the Redis server at `url`
+    ///
+    /// The connection reconnects on its own after the server goes away.
+    pub async fn connect(url: &str, prefix: impl Into<String>) -> Result<Self, RepositoryError> {
+        let client = redis::Client::open(url).map_err(store_error)?;
+        let conn = client.get_connection_manager().await.map_err(store_error)?;
+        Ok(Self::new(conn, prefix))
+    }
+
+    /// Also match the given custom metadata keys in text searches
+    pub fn with_metadata_index(mut self, metadata_index: MetadataIndexConfig) -> Self {
+        self.metadata_index = metadata_index;
+        self
+    }
+
+    fn object_key(&self, id: &uuid::Uuid) -> String {
+        format!("{}object:{}", self.prefix, id)
+    }
+
+    fn key_index(&self, tenant_id: &str, namespace: &str, key: &str) -> String {
+        format!("{}key:{}:{}:{}", self.prefix, tenant_id, namespace, key)
+    }
+
+    fn namespace_set(&self, tenant_id: &str, namespace: &str) -> String {
+        format!("{}namespace:{}:{}", self.prefix, tenant_id, namespace)
+    }
+
+    fn namespaces_set(&self, tenant_id: &str) -> String {
+        format!("{}namespaces:{}", self.prefix, tenant_id)
+    }
+
+    fn writing_set(&self) -> String {
+        format!("{}writing", self.prefix)
+    }
+
+    fn locked_set(&self) -> String {
+        format!("{}locked", self.prefix)
+    }
+
+    /// Load an object document in any state, with its version
+    async fn load(&self, id: &uuid::Uuid) -> Result<Option<(StoredObject, u64)>, RepositoryError> {
+        let mut conn = self.conn.clone();
+        let (doc, version): (Option<String>, Option<u64>) = redis::cmd("HMGET")
+            .arg(self.object_key(id))
+            .arg("doc")
+            .arg("version")
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+
+        match (doc, version) {
+            (Some(doc), Some(version)) => Ok(Some((StoredObject::parse(&doc)?, version))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Load an object if it is in `status`
+    async fn load_in(
+        &self,
+        id: &ObjectId,
+        status: ObjectStatus,
+    ) -> Result<Option<Object>, RepositoryError> {
+        match self.load(id.as_uuid()).await? {
+            Some((stored, _)) if stored.status == status.to_string() => {
+                Ok(Some(stored.into_domain()?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Write `stored` if its version is still `version`
+    async fn put(
+        &self,
+        stored: &StoredObject,
+        version: u64,
+        claim: KeyClaim,
+    ) -> Result<Put, RepositoryError> {
+        let key_index = match &stored.key {
+            Some(key) => self.key_index(&stored.tenant_id, &stored.namespace, key),
+            // Never read: keyless objects leave the key index alone
+            None => self.object_key(&stored.id),
+        };
+        let claim = if stored.key.is_some() {
+            claim
+        } else {
+            KeyClaim::Keep
+        };
+        let writing_score = if stored.status == ObjectStatus::Writing.to_string() {
+            stored.created_at.unix_timestamp().to_string()
+        } else {
+            String::new()
+        };
+        let locked_score = match stored.retention_until {
+            _ if stored.legal_hold => "+inf".to_string(),
+            Some(until) if stored.is_locked() => until.unix_timestamp().to_string(),
+            _ => String::new(),
+        };
+
+        let mut conn = self.conn.clone();
+        let outcome: i64 = PUT_SCRIPT
+            .key(self.object_key(&stored.id))
+            .key(key_index)
+            .key(self.namespace_set(&stored.tenant_id, &stored.namespace))
+            .key(self.namespaces_set(&stored.tenant_id))
+            .key(self.writing_set())
+            .key(self.locked_set())
+            .arg(version)
+            .arg(stored.to_json()?)
+            .arg(stored.id.to_string())
+            .arg(claim.as_arg())
+            .arg(&stored.namespace)
+            .arg(writing_score)
+            .arg(locked_score)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+
+        Ok(match outcome {
+            1 => Put::Written,
+            -1 => Put::KeyTaken,
+            _ => Put::Conflict,
+        })
+    }
+
+    /// Apply `change` to an object and write it back, reloading and
+    /// reapplying it when the object changed in between
+    ///
+    /// `change` returns false to leave the object alone. Returns whether the
+    /// object was written.
+    async fn update<F>(&self, id: &uuid::Uuid, mut change: F) -> Result<bool, RepositoryError>
+    where
+        F: FnMut(&mut StoredObject) -> bool + Send,
+    {
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let Some((mut stored, version)) = self.load(id).await? else {
+                return Ok(false);
+            };
+            if !change(&mut stored) {
+                return Ok(false);
+            }
+            match self.put(&stored, version, KeyClaim::Keep).await? {
+                Put::Written => return Ok(true),
+                Put::Conflict => continue,
+                Put::KeyTaken => {
+                    return Err(RepositoryError::Internal(
+                        "Update without a key claim reported a taken key".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Err(RepositoryError::Store(format!(
+            "Object {id} kept changing; gave up after {MAX_UPDATE_ATTEMPTS} attempts"
+        )))
+    }
+
+    /// Load every committed object of a namespace
+    async fn committed_in(
+        &self,
+        namespace: &str,
+        tenant_id: &str,
+    ) -> Result<Vec<StoredObject>, RepositoryError> {
+        let mut conn = self.conn.clone();
+        let ids: Vec<String> = redis::cmd("SMEMBERS")
+            .arg(self.namespace_set(tenant_id, namespace))
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+
+        let committed = ObjectStatus::Committed.to_string();
+        let mut objects = Vec::new();
+        for batch in ids.chunks(LOAD_BATCH) {
+            let mut pipe = redis::pipe();
+            for id in batch {
+                pipe.cmd("HGET")
+                    .arg(format!("{}object:{}", self.prefix, id))
+                    .arg("doc");
+            }
+            let docs: Vec<Option<String>> =
+                pipe.query_async(&mut conn).await.map_err(store_error)?;
+
+            // Documents deleted since the set was read come back empty
+            for doc in docs.into_iter().flatten() {
+                let stored = StoredObject::parse(&doc)?;
+                if stored.status == committed
+                    && stored.namespace == namespace
+                    && stored.tenant_id == tenant_id
+                {
+                    objects.push(stored);
+                }
+            }
+        }
+        Ok(objects)
+    }
+
+    /// Committed objects of a namespace matching `keys` and `metadata`
+    async fn matching(
+        &self,
+        namespace: &str,
+        tenant_id: &str,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        self.committed_in(namespace, tenant_id)
+            .await?
+            .into_iter()
+            .filter(|stored| {
+                lists_key(stored.key.as_deref(), keys) && metadata.matches(&stored.metadata)
+            })
+            .map(StoredObject::into_domain)
+            .collect()
+    }
+
+    /// IDs of every object in any state, found by scanning the keyspace
+    async fn all_ids(&self) -> Result<Vec<uuid::Uuid>, RepositoryError> {
+        let pattern = format!("{}object:*", self.prefix);
+        let mut conn = self.conn.clone();
+        let mut ids = Vec::new();
+        let mut cursor = 0u64;
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(LOAD_BATCH)
+                .query_async(&mut conn)
+                .await
+                .map_err(store_error)?;
+            ids.extend(
+                keys.iter()
+                    .filter_map(|key| key.rsplit(':').next())
+                    .filter_map(|id| uuid::Uuid::parse_str(id).ok()),
+            );
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        // SCAN may return a key more than once
+        ids.sort_unstable();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    /// Remove `id` from the uploads in progress
+    async fn forget_writing(&self, id: &uuid::Uuid) -> Result<(), RepositoryError> {
+        let mut conn = self.conn.clone();
+        let _: i64 = redis::cmd("ZREM")
+            .arg(self.writing_set())
+            .arg(id.to_string())
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+        Ok(())
+    }
+
+    /// IDs of uploads in progress created more than `age` ago, oldest
+    /// first
+    async fn stuck_ids(
+        &self,
+        age: time::Duration,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<uuid::Uuid>, RepositoryError> {
+        let cutoff = OffsetDateTime::now_utc() - age;
+        let mut conn = self.conn.clone();
+        let ids: Vec<String> = redis::cmd("ZRANGEBYSCORE")
+            .arg(self.writing_set())
+            .arg("-inf")
+            .arg(format!("({}", cutoff.unix_timestamp()))
+            .arg("LIMIT")
+            .arg(offset)
+            .arg(limit)
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+
+        Ok(ids
+            .iter()
+            .filter_map(|id| uuid::Uuid::parse_str(id).ok())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ObjectRepository for RedisObjectRepository {
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn save(&self, object: &Object) -> Result<(), RepositoryError> {
+        let written = StoredObject::from_domain(object)?;
+        let claim = if object.status() == ObjectStatus::Deleted {
+            KeyClaim::Release
+        } else {
+            KeyClaim::Claim
+        };
+
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            // Like the Postgres upsert, saving over an existing object keeps
+            // its identity, access counts, locks and extracted text
+            let (stored, version) = match self.load(object.id().as_uuid()).await? {
+                Some((mut stored, version)) => {
+                    stored.status = written.status.clone();
+                    stored.content_hash = written.content_hash.clone();
+                    stored.size_bytes = written.size_bytes;
+                    stored.content_type = written.content_type.clone();
+                    stored.content_encoding = written.content_encoding.clone();
+                    stored.metadata = written.metadata.clone();
+                    stored.updated_at = written.updated_at;
+                    (stored, version)
+                }
+                None => (written.clone(), 0),
+            };
+
+            match self.put(&stored, version, claim).await? {
+                Put::Written => return Ok(()),
+                Put::Conflict => continue,
+                Put::KeyTaken => {
+                    return Err(RepositoryError::ConstraintViolation(format!(
+                        "key {} already exists in namespace {}",
+                        stored.key.as_deref().unwrap_or_default(),
+                        stored.namespace
+                    )))
+                }
+            }
+        }
+
+        Err(RepositoryError::Store(format!(
+            "Object {} kept changing; gave up after {MAX_UPDATE_ATTEMPTS} attempts",
+            object.id()
+        )))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError> {
+        self.load_in(id, ObjectStatus::Committed).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn find_writing(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError> {
+        self.load_in(id, ObjectStatus::Writing).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn head(&self, id: &ObjectId) -> Result<Option<ObjectHead>, RepositoryError> {
+        // The document is loaded whole; there is no cheaper header read
+        Ok(self
+            .find_by_id(id)
+            .await?
+            .as_ref()
+            .and_then(ObjectHead::from_committed))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn find_record(&self, id: &ObjectId) -> Result<Option<ObjectRecord>, RepositoryError> {
+        // Blob reference counts live in Postgres and are not joined in
+        Ok(self.find_by_id(id).await?.map(|object| ObjectRecord {
+            object,
+            blob_ref_count: None,
+        }))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn status(
+        &self,
+        id: &ObjectId,
+    ) -> Result<Option<(TenantId, ObjectStatus)>, RepositoryError> {
+        self.load(id.as_uuid())
+            .await?
+            .map(|(stored, _)| {
+                let tenant_id = TenantId::from_string(&stored.tenant_id).map_err(|e| {
+                    RepositoryError::SerializationError(format!("Invalid tenant_id: {}", e))
+                })?;
+                let status = stored
+                    .status
+                    .parse::<ObjectStatus>()
+                    .map_err(RepositoryError::SerializationError)?;
+                Ok((tenant_id, status))
+            })
+            .transpose()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn head_by_key(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        key: &str,
+    ) -> Result<Option<ObjectHead>, RepositoryError> {
+        Ok(self
+            .find_by_key(namespace, tenant_id, key)
+            .await?
+            .as_ref()
+            .and_then(ObjectHead::from_committed))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn replace_content_if_match(
+        &self,
+        object: &Object,
+        expected: &ContentHash,
+    ) -> Result<bool, RepositoryError> {
+        let replacement = StoredObject::from_domain(object)?;
+        self.update(object.id().as_uuid(), |stored| {
+            if !stored.is_committed()
+                || stored.content_hash.as_deref() != Some(expected.as_hex())
+                || stored.is_locked()
+            {
+                return false;
+            }
+            stored.content_hash = replacement.content_hash.clone();
+            stored.size_bytes = replacement.size_bytes;
+            stored.updated_at = replacement.updated_at;
+            stored.content_encoding = replacement.content_encoding.clone();
+            true
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn update_metadata_if_match(
+        &self,
+        object: &Object,
+        expected: &ObjectMetadata,
+    ) -> Result<bool, RepositoryError> {
+        let replacement = StoredObject::from_domain(object)?;
+        let expected = expected
+            .to_json()
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        self.update(object.id().as_uuid(), |stored| {
+            if !stored.is_committed() || stored.metadata != expected || stored.is_locked() {
+                return false;
+            }
+            stored.metadata = replacement.metadata.clone();
+            stored.updated_at = replacement.updated_at;
+            true
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn update_retention(&self, object: &Object) -> Result<bool, RepositoryError> {
+        self.update(object.id().as_uuid(), |stored| {
+            if !stored.is_committed() {
+                return false;
+            }
+            // Keep a retention extended concurrently, as GREATEST does
+            stored.retention_until = stored.retention_until.max(object.retention_until());
+            stored.legal_hold = object.legal_hold();
+            stored.updated_at = object.updated_at();
+            true
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn mark_deleting_if_unlocked(&self, object: &Object) -> Result<bool, RepositoryError> {
+        self.update(object.id().as_uuid(), |stored| {
+            if !stored.is_committed() || stored.is_locked() {
+                return false;
+            }
+            stored.status = ObjectStatus::Deleting.to_string();
+            stored.updated_at = object.updated_at();
+            true
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn set_extracted_text(
+        &self,
+        id: &ObjectId,
+        text: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        self.update(id.as_uuid(), |stored| {
+            stored.extracted_text = text.clone();
+            true
+        })
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn find_by_key(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        key: &str,
+    ) -> Result<Option<Object>, RepositoryError> {
+        let tenant_id = tenant_id.to_string();
+        let mut conn = self.conn.clone();
+        let id: Option<String> = redis::cmd("GET")
+            .arg(self.key_index(&tenant_id, namespace.as_str(), key))
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+        let Some(id) = id.and_then(|id| uuid::Uuid::parse_str(&id).ok()) else {
+            return Ok(None);
+        };
+
+        match self.load(&id).await? {
+            Some((stored, _))
+                if stored.is_committed()
+                    && stored.tenant_id == tenant_id
+                    && stored.namespace == namespace.as_str()
+                    && stored.key.as_deref() == Some(key) =>
+            {
+                Ok(Some(stored.into_domain()?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn list(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        after: Option<ListCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        let mut objects = self
+            .matching(namespace.as_str(), &tenant_id.to_string(), keys, metadata)
+            .await?;
+        sort_objects(&mut objects, sort_by, sort_direction);
+        if let Some(after) = after {
+            let after = SortPosition::of_cursor(after);
+            objects.retain(|object| {
+                SortPosition::of(object, sort_by)
+                    .cmp_in(&after, sort_direction)
+                    .is_gt()
+            });
+        }
+
+        Ok(page(objects, limit, offset))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn list_projected(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        fields: &[ObjectField],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ObjectProjection>, RepositoryError> {
+        // Documents are loaded whole, so projecting saves nothing but bytes
+        // on the wire to the client
+        let objects = self
+            .list(
+                namespace,
+                tenant_id,
+                keys,
+                metadata,
+                sort_by,
+                sort_direction,
+                None,
+                limit,
+                offset,
+            )
+            .await?;
+
+        Ok(objects
+            .into_iter()
+            .map(|object| ObjectProjection::new(&ObjectDto::from(object), fields))
+            .collect())
+    }
+
+    fn stream(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+    ) -> ObjectStream {
+        let repo = self.clone();
+        let namespace = namespace.as_str().to_string();
+        let tenant_id = tenant_id.to_string();
+        let keys = keys.clone();
+        let metadata = metadata.clone();
+
+        // Sorting needs the whole namespace, so unlike the Postgres stream
+        // this one holds it in memory
+        let objects = stream::once(async move {
+            let mut objects = repo
+                .matching(&namespace, &tenant_id, &keys, &metadata)
+                .await?;
+            sort_objects(&mut objects, sort_by, sort_direction);
+            Ok::<_, RepositoryError>(objects)
+        });
+
+        Box::pin(objects.flat_map(|result| match result {
+            Ok(objects) => stream::iter(objects.into_iter().map(Ok)).left_stream(),
+            Err(e) => stream::iter(std::iter::once(Err(e))).right_stream(),
+        }))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn count(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        max: i64,
+    ) -> Result<i64, RepositoryError> {
+        let count = self
+            .committed_in(namespace.as_str(), &tenant_id.to_string())
+            .await?
+            .iter()
+            .filter(|stored| {
+                lists_key(stored.key.as_deref(), keys) && metadata.matches(&stored.metadata)
+            })
+            .count() as i64;
+        Ok(count.min(max))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn common_prefixes(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        max: i64,
+    ) -> Result<Vec<String>, RepositoryError> {
+        if keys.delimiter().is_none() {
+            return Ok(Vec::new());
+        }
+
+        let mut prefixes: Vec<String> = self
+            .committed_in(namespace.as_str(), &tenant_id.to_string())
+            .await?
+            .iter()
+            .filter(|stored| metadata.matches(&stored.metadata))
+            .filter_map(|stored| {
+                stored
+                    .key
+                    .as_deref()
+                    .and_then(|key| keys.common_prefix(key))
+                    .map(str::to_string)
+            })
+            .collect();
+        prefixes.sort();
+        prefixes.dedup();
+        prefixes.truncate(max.max(0) as usize);
+        Ok(prefixes)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn namespaces(&self, tenant_id: &TenantId) -> Result<Vec<Namespace>, RepositoryError> {
+        let tenant_id = tenant_id.to_string();
+        let mut conn = self.conn.clone();
+        let mut names: Vec<String> = redis::cmd("SMEMBERS")
+            .arg(self.namespaces_set(&tenant_id))
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+        names.sort();
+
+        // The set also names namespaces left with only uploads or tombstones
+        let mut namespaces = Vec::new();
+        for name in names {
+            if !self.committed_in(&name, &tenant_id).await?.is_empty() {
+                namespaces.push(
+                    Namespace::new(name)
+                        .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+                );
+            }
+        }
+        Ok(namespaces)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn search(
+        &self,
+        request: &SearchRequest,
+        keys: &KeyPrefixQuery,
+        metadata: &MetadataQuery,
+        after: Option<ListCursor>,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        let Pagination { limit, offset } =
+            Pagination::new(request.limit, request.offset, PageLimits::OBJECTS);
+        let sort_by = request.sort_by.unwrap_or_default();
+        let sort_direction = request.sort_direction.unwrap_or_default();
+
+        let mut objects = self
+            .matching(&request.namespace, &request.tenant_id, keys, metadata)
+            .await?;
+        sort_objects(&mut objects, sort_by, sort_direction);
+        if let Some(after) = after {
+            let after = SortPosition::of_cursor(after);
+            objects.retain(|object| {
+                SortPosition::of(object, sort_by)
+                    .cmp_in(&after, sort_direction)
+                    .is_gt()
+            });
+        }
+        Ok(page(objects, limit, offset))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn text_search(
+        &self,
+        request: &TextSearchRequest,
+    ) -> Result<TextSearchPage, RepositoryError> {
+        let Pagination { limit, offset } =
+            Pagination::new(request.limit, request.offset, PageLimits::OBJECTS);
+        let min_rank = request.min_rank.unwrap_or(0.0);
+        let query = request.query.to_lowercase();
+        let contains = |text: &str| text.to_lowercase().contains(&query);
+
+        // Without search vectors, every field is a case-insensitive
+        // substring match; a key match still ranks above the rest
+        let mut matches = Vec::new();
+        for stored in self
+            .committed_in(&request.namespace, &request.tenant_id)
+            .await?
+        {
+            let in_key = request.search_in_key.unwrap_or(true)
+                && stored.key.as_deref().is_some_and(contains);
+            let in_metadata = request.search_in_metadata.unwrap_or(true)
+                && contains(&stored.metadata.to_string());
+            let in_custom_metadata = request.search_in_custom_metadata.unwrap_or(true) && {
+                let metadata = ObjectMetadata::from_json(&stored.metadata)
+                    .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+                contains(
+                    &self
+                        .metadata_index
+                        .searchable_text(&stored.namespace, &metadata),
+                )
+            };
+            let in_content = request.search_in_content.unwrap_or(true)
+                && stored.extracted_text.as_deref().is_some_and(contains);
+            if !(in_key || in_metadata || in_custom_metadata || in_content) {
+                continue;
+            }
+
+            let mut rank = if in_key { 1.0 } else { 0.0 };
+            if in_custom_metadata || in_content {
+                rank += 0.5;
+            }
+            if rank >= min_rank {
+                matches.push(TextSearchMatch {
+                    object: stored.into_domain()?,
+                    rank,
+                    highlight: None,
+                });
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.rank
+                .partial_cmp(&a.rank)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| b.object.created_at().cmp(&a.object.created_at()))
+        });
+        let total = matches.len() as u64;
+
+        Ok(TextSearchPage {
+            matches: page(matches, limit, offset),
+            total,
+        })
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn record_access(&self, accesses: &[ObjectAccess]) -> Result<(), RepositoryError> {
+        for access in accesses {
+            // A conflicting write is not applied, so retrying can't double count
+            self.update(access.object_id.as_uuid(), |stored| {
+                stored.download_count = stored.download_count.saturating_add(access.count);
+                stored.last_access_at = stored.last_access_at.max(Some(access.last_accessed_at));
+                true
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Without an index by content hash, every object is scanned
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn move_storage_class(
+        &self,
+        content_hash: &ContentHash,
+        from: StorageClass,
+        to: StorageClass,
+    ) -> Result<u64, RepositoryError> {
+        let (from, to) = (from.to_string(), to.to_string());
+        let mut moved = 0;
+        for id in self.all_ids().await? {
+            let changed = self
+                .update(&id, |stored| {
+                    if stored.content_hash.as_deref() != Some(content_hash.as_hex())
+                        || stored.storage_class != from
+                    {
+                        return false;
+                    }
+                    stored.storage_class = to.clone();
+                    true
+                })
+                .await?;
+            if changed {
+                moved += 1;
+            }
+        }
+        Ok(moved)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn delete(&self, id: &ObjectId) -> Result<(), RepositoryError> {
+        let Some((stored, _)) = self.load(id.as_uuid()).await? else {
+            // The document is gone; drop an upload entry that outlived it
+            return self.forget_writing(id.as_uuid()).await;
+        };
+        let (key_index, has_key) = match &stored.key {
+            Some(key) => (
+                self.key_index(&stored.tenant_id, &stored.namespace, key),
+                "1",
+            ),
+            None => (self.object_key(&stored.id), "0"),
+        };
+
+        let mut conn = self.conn.clone();
+        let _: i64 = DELETE_SCRIPT
+            .key(self.object_key(&stored.id))
+            .key(key_index)
+            .key(self.namespace_set(&stored.tenant_id, &stored.namespace))
+            .key(self.writing_set())
+            .key(self.namespaces_set(&stored.tenant_id))
+            .key(self.locked_set())
+            .arg(stored.id.to_string())
+            .arg(has_key)
+            .arg(&stored.namespace)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn find_stuck_writing_objects(
+        &self,
+        age_hours: i64,
+        limit: i64,
+    ) -> Result<Vec<ObjectId>, RepositoryError> {
+        Ok(self
+            .stuck_ids(time::Duration::hours(age_hours), 0, limit.max(0) as usize)
+            .await?
+            .into_iter()
+            .map(ObjectId::from_uuid)
+            .collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn find_stuck_staged_objects(
+        &self,
+        age_minutes: i64,
+        limit: i64,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        let limit = limit.max(0) as usize;
+        let mut staged = Vec::new();
+        let mut offset = 0;
+
+        while staged.len() < limit {
+            let ids = self
+                .stuck_ids(time::Duration::minutes(age_minutes), offset, LOAD_BATCH)
+                .await?;
+            offset += ids.len();
+            for id in &ids {
+                if let Some(object) = self
+                    .load_in(&ObjectId::from_uuid(*id), ObjectStatus::Writing)
+                    .await?
+                    .filter(|object| object.staged_content().is_some())
+                {
+                    staged.push(object);
+                }
+            }
+            if ids.len() < LOAD_BATCH {
+                break;
+            }
+        }
+
+        staged.truncate(limit);
+        Ok(staged)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn cleanup_stuck_uploads(&self, age_hours: i64) -> Result<usize, RepositoryError> {
+        let mut deleted = 0;
+        loop {
+            let ids = self
+                .stuck_ids(time::Duration::hours(age_hours), 0, LOAD_BATCH)
+                .await?;
+            if ids.is_empty() {
+                return Ok(deleted);
+            }
+            for id in &ids {
+                match self.load(id).await? {
+                    Some((stored, _)) if stored.status == ObjectStatus::Writing.to_string() => {
+                        self.delete(&ObjectId::from_uuid(*id)).await?;
+                        deleted += 1;
+                    }
+                    // Committed since it was listed, or the document is
+                    // gone: drop only the entry, which would otherwise be
+                    // listed again forever
+                    _ => self.forget_writing(id).await?,
+                }
+            }
+            if ids.len() < LOAD_BATCH {
+                return Ok(deleted);
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "redis"))]
+    async fn find_locked_hashes(
+        &self,
+        content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        if content_hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Retentions that ended drop out of the index here
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let mut conn = self.conn.clone();
+        let (_, ids): (i64, Vec<String>) = redis::pipe()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(self.locked_set())
+            .arg("-inf")
+            .arg(now)
+            .cmd("ZRANGEBYSCORE")
+            .arg(self.locked_set())
+            .arg(format!("({now}"))
+            .arg("+inf")
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+
+        let wanted: HashSet<&str> = content_hashes.iter().map(|h| h.as_hex()).collect();
+        let mut locked = HashSet::new();
+        for batch in ids.chunks(LOAD_BATCH) {
+            let mut pipe = redis::pipe();
+            for id in batch {
+                pipe.cmd("HGET")
+                    .arg(format!("{}object:{}", self.prefix, id))
+                    .arg("doc");
+            }
+            let docs: Vec<Option<String>> =
+                pipe.query_async(&mut conn).await.map_err(store_error)?;
+
+            for doc in docs.into_iter().flatten() {
+                let stored = StoredObject::parse(&doc)?;
+                if let Some(hash) = stored.content_hash.filter(|h| wanted.contains(h.as_str())) {
+                    if stored.is_locked() {
+                        locked.insert(hash);
+                    }
+                }
+            }
+        }
+
+        locked
+            .into_iter()
+            .map(|hex| {
+                ContentHash::from_hex(hex)
+                    .map_err(|e| RepositoryError::SerializationError(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+fn store_error(e: redis::RedisError) -> RepositoryError {
+    RepositoryError::Store(e.to_string())
+}
+
+/// Whether `key` is listed by `keys` rather than filtered out or folded
+/// into a common prefix
+fn lists_key(key: Option<&str>, keys: &KeyPrefixQuery) -> bool {
+    if keys.is_empty() {
+        return true;
+    }
+    key.is_some_and(|key| key.starts_with(keys.prefix()) && keys.common_prefix(key).is_none())
+}
+
+fn page<T>(items: Vec<T>, limit: i64, offset: i64) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect()
+}
+
+/// Position of an object in a listing order
+///
+/// Like Postgres: unset values sort last when ascending, ties by ID.
+#[derive(Debug, Clone, PartialEq)]
+struct SortPosition {
+    unset: bool,
+    value: Option<CursorValue>,
+    id: uuid::Uuid,
+}
+
+impl SortPosition {
+    fn of(object: &Object, sort_by: SortField) -> Self {
+        let value = ListCursor::sort_value(object, sort_by);
+        Self {
+            unset: value.is_none(),
+            value,
+            id: *object.id().as_uuid(),
+        }
+    }
+
+    fn of_cursor(cursor: ListCursor) -> Self {
+        Self {
+            unset: cursor.value.is_none(),
+            value: cursor.value,
+            id: *cursor.id.as_uuid(),
+        }
+    }
+
+    fn cmp_in(&self, other: &Self, direction: SortDirection) -> Ordering {
+        let ordering = (self.unset, &self.value, self.id)
+            .partial_cmp(&(other.unset, &other.value, other.id))
+            .unwrap_or(Ordering::Equal);
+        match direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    }
+}
+
+fn sort_objects(objects: &mut [Object], sort_by: SortField, direction: SortDirection) {
+    objects.sort_by_cached_key(|object| SortKey(SortPosition::of(object, sort_by), direction));
+}
+
+/// [`SortPosition`] in a direction, as a key for sorting
+struct SortKey(SortPosition, SortDirection);
+
+impl PartialEq for SortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for SortKey {}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_in(&other.0, self.1)
+    }
+}
+
+/// Object document as stored in Redis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct StoredObject {
+    id: uuid::Uuid,
+    namespace: String,
+    tenant_id: String,
+    key: Option<String>,
+    status: String,
+    storage_class: String,
+    content_hash: Option<String>,
+    size_bytes: Option<u64>,
+    content_type: Option<String>,
+    content_encoding: Option<String>,
+    metadata: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    updated_at: OffsetDateTime,
+    download_count: u64,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    last_access_at: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    retention_until: Option<OffsetDateTime>,
+    legal_hold: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extracted_text: Option<String>,
+}
+
+impl StoredObject {
+    fn from_domain(object: &Object) -> Result<Self, RepositoryError> {
+        Ok(Self {
+            id: *object.id().as_uuid(),
+            namespace: object.namespace().as_str().to_string(),
+            tenant_id: object.tenant_id().to_string(),
+            key: object.key().map(str::to_string),
+            status: object.status().to_string(),
+            storage_class: object.storage_class().to_string(),
+            content_hash: object.content_hash().map(|h| h.as_hex().to_string()),
+            size_bytes: object.size_bytes(),
+            content_type: object.content_type().map(str::to_string),
+            content_encoding: object.content_encoding().map(|e| e.to_string()),
+            metadata: object
+                .metadata()
+                .to_json()
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            created_at: object.created_at(),
+            updated_at: object.updated_at(),
+            download_count: object.download_count(),
+            last_access_at: object.last_accessed_at(),
+            retention_until: object.retention_until(),
+            legal_hold: object.legal_hold(),
+            extracted_text: None,
+        })
+    }
+
+    fn parse(doc: &str) -> Result<Self, RepositoryError> {
+        serde_json::from_str(doc).map_err(|e| RepositoryError::SerializationError(e.to_string()))
+    }
+
+    fn to_json(&self) -> Result<String, RepositoryError> {
+        serde_json::to_string(self).map_err(|e| RepositoryError::SerializationError(e.to_string()))
+    }
+
+    fn is_committed(&self) -> bool {
+        self.status == ObjectStatus::Committed.to_string()
+    }
+
+    fn is_locked(&self) -> bool {
+        self.legal_hold
+            || self
+                .retention_until
+                .is_some_and(|until| until > OffsetDateTime::now_utc())
+    }
+
+    fn into_domain(self) -> Result<Object, RepositoryError> {
+        let namespace = Namespace::new(self.namespace)
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        let tenant_id = TenantId::from_string(&self.tenant_id).map_err(|e| {
+            RepositoryError::SerializationError(format!("Invalid tenant_id: {}", e))
+        })?;
+        let status = self
+            .status
+            .parse::<ObjectStatus>()
+            .map_err(RepositoryError::SerializationError)?;
+        let storage_class = self
+            .storage_class
+            .parse::<StorageClass>()
+            .map_err(RepositoryError::SerializationError)?;
+        let content_hash = self
+            .content_hash
+            .map(ContentHash::from_hex)
+            .transpose()
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        let content_encoding = self
+            .content_encoding
+            .map(|e| e.parse::<ContentEncoding>())
+            .transpose()
+            .map_err(RepositoryError::SerializationError)?;
+        let metadata = ObjectMetadata::from_json(&self.metadata)
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+
+        Ok(Object::reconstruct(
+            ObjectId::from_uuid(self.id),
+            namespace,
+            tenant_id,
+            self.key,
+            status,
+            storage_class,
+            content_hash,
+            self.size_bytes,
+            self.content_type,
+            content_encoding,
+            metadata,
+            self.created_at,
+            self.updated_at,
+            self.download_count,
+            self.last_access_at,
+            self.retention_until,
+            self.legal_hold,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn object(key: &str) -> Object {
+        Object::new(
+            Namespace::from_str("test").unwrap(),
+            TenantId::new(uuid::Uuid::new_v4()),
+            Some(key.to_string()),
+            StorageClass::Hot,
+        )
+    }
+
+    #[test]
+    fn test_stored_object_round_trips() {
+        let mut object = object("a/b.txt");
+        object.metadata_mut().tags.insert(
+            "author".to_string(),
+            serde_json::Value::String("jane".to_string()),
+        );
+        let hash = ContentHash::from_hex("a".repeat(64)).unwrap();
+        object.commit(&hash, 42).unwrap();
+
+        let stored = StoredObject::from_domain(&object).unwrap();
+        let parsed = StoredObject::parse(&stored.to_json().unwrap()).unwrap();
+
+        assert_eq!(parsed, stored);
+        assert_eq!(parsed.into_domain().unwrap(), object);
+    }
+
+    #[test]
+    fn test_sort_puts_unset_values_last_when_ascending() {
+        let mut objects = vec![object("b"), object("a")];
+        objects.push(Object::new(
+            Namespace::from_str("test").unwrap(),
+            TenantId::new(uuid::Uuid::new_v4()),
+            None,
+            StorageClass::Hot,
+        ));
+
+        sort_objects(&mut objects, SortField::Key, SortDirection::Asc);
+        let keys: Vec<_> = objects.iter().map(Object::key).collect();
+        assert_eq!(keys, vec![Some("a"), Some("b"), None]);
+
+        sort_objects(&mut objects, SortField::Key, SortDirection::Desc);
+        let keys: Vec<_> = objects.iter().map(Object::key).collect();
+        assert_eq!(keys, vec![None, Some("b"), Some("a")]);
+    }
+
+    #[test]
+    fn test_listed_keys_follow_prefix_and_delimiter() {
+        let keys = KeyPrefixQuery::new(Some("logs/".to_string()), Some("/".to_string())).unwrap();
+
+        assert!(lists_key(Some("logs/a.txt"), &keys));
+        assert!(!lists_key(Some("logs/2024/a.txt"), &keys));
+        assert!(!lists_key(Some("data/a.txt"), &keys));
+        assert!(!lists_key(None, &keys));
+        assert!(lists_key(None, &KeyPrefixQuery::default()));
+    }
+}
@@ -0,0 +1,183 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Retries idempotent database operations that failed with a transient error
+///
+/// Attempts back off exponentially from `base_delay`, capped at `max_delay`.
+/// Clones share one retry counter.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    retries: Arc<AtomicU64>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(50), Duration::from_secs(1))
+    }
+}
+
+impl RetryPolicy {
+    /// `max_attempts` counts the first try; 1 disables retries
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay,
+            retries: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Number of retries made since startup
+    pub fn retries(&self) -> u64 {
+        self.retries.load(Ordering::Relaxed)
+    }
+
+    /// Delay before retry number `retry` (starting at 1)
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry - 1).unwrap_or(u32::MAX);
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Run `operation`, retrying it while it fails with a retryable error
+    ///
+    /// Only pass operations that are safe to repeat: a connection error can
+    /// hide a statement that did commit.
+    pub async fn run<T, F, Fut>(
+        &self,
+        name: &'static str,
+        mut operation: F,
+    ) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(e) if attempt < self.max_attempts && is_retryable(&e) => {
+                    let delay = self.backoff(attempt);
+                    self.retries.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        operation = name,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying database operation after transient error: {}",
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Whether an error is transient: connection failures, serialization
+/// failures and deadlocks. Constraint violations and other errors are not.
+pub fn is_retryable(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(e) => e.code().is_some_and(|code| {
+            // 40001 serialization_failure, 40P01 deadlock_detected,
+            // 57P01 admin_shutdown, class 08 connection exceptions
+            matches!(code.as_ref(), "40001" | "40P01" | "57P01") || code.starts_with("08")
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new(
+            max_attempts,
+            Duration::from_millis(1),
+            Duration::from_millis(2),
+        )
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(50), Duration::from_millis(150));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(50));
+        assert_eq!(policy.backoff(2), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(150));
+        assert_eq!(policy.backoff(40), Duration::from_millis(150));
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&sqlx::Error::PoolTimedOut));
+        assert!(is_retryable(&sqlx::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset
+        ))));
+        assert!(!is_retryable(&sqlx::Error::RowNotFound));
+        assert!(!is_retryable(&sqlx::Error::PoolClosed));
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_transient_errors() {
+        let policy = fast_policy(3);
+        let mut calls = 0;
+
+        let result = policy
+            .run("test", || {
+                calls += 1;
+                let result = if calls < 3 {
+                    Err(sqlx::Error::PoolTimedOut)
+                } else {
+                    Ok(calls)
+                };
+                async move { result }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(policy.retries(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_gives_up_after_max_attempts() {
+        let policy = fast_policy(2);
+        let mut calls = 0;
+
+        let result: Result<(), _> = policy
+            .run("test", || {
+                calls += 1;
+                async { Err(sqlx::Error::PoolTimedOut) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(sqlx::Error::PoolTimedOut)));
+        assert_eq!(calls, 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_does_not_retry_permanent_errors() {
+        let policy = fast_policy(3);
+        let mut calls = 0;
+
+        let result: Result<(), _> = policy
+            .run("test", || {
+                calls += 1;
+                async { Err(sqlx::Error::RowNotFound) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert_eq!(calls, 1);
+        assert_eq!(policy.retries(), 0);
+    }
+}
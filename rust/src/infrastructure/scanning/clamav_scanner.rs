@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::application::ports::{BlobReader, ContentScanner, ScanError, ScanSubject, ScanVerdict};
+
+/// Bytes sent per `INSTREAM` chunk
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// Longest reply read from clamd
+const MAX_REPLY_BYTES: u64 = 4096;
+
+/// Scans content with a clamd daemon over TCP
+///
+/// Content is streamed with the `INSTREAM` command in chunks, so uploads are
+/// never buffered whole. Content larger than clamd's `StreamMaxLength` fails
+/// the scan rather than passing unscanned.
+#[derive(Debug, Clone)]
+pub struct ClamAvScanner {
+    addr: String,
+    timeout: Duration,
+}
+
+impl ClamAvScanner {
+    /// Scanner for the clamd listening on `addr` (`host:port`), giving up on
+    /// a scan after `timeout`
+    pub fn new(addr: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            addr: addr.into(),
+            timeout,
+        }
+    }
+
+    async fn instream(&self, mut content: BlobReader) -> Result<String, ScanError> {
+        let mut stream = TcpStream::connect(&self.addr)
+            .await
+            .map_err(|e| ScanError::Unavailable(format!("clamd at {}: {e}", self.addr)))?;
+        let failed = |e: std::io::Error| ScanError::Failed(format!("clamd stream: {e}"));
+
+        stream.write_all(b"zINSTREAM\0").await.map_err(failed)?;
+        let mut buf = vec![0u8; CHUNK_BYTES];
+        loop {
+            let n = content
+                .read(&mut buf)
+                .await
+                .map_err(|e| ScanError::Failed(format!("reading content: {e}")))?;
+            if n == 0 {
+                break;
+            }
+            let sent = async {
+                stream.write_all(&(n as u32).to_be_bytes()).await?;
+                stream.write_all(&buf[..n]).await
+            }
+            .await;
+            // clamd closes the stream once StreamMaxLength is reached; its
+            // reply says why
+            if let Err(e) = sent {
+                return match read_reply(&mut stream).await {
+                    Ok(reply) if !reply.is_empty() => Ok(reply),
+                    _ => Err(failed(e)),
+                };
+            }
+        }
+        stream
+            .write_all(&0u32.to_be_bytes())
+            .await
+            .map_err(failed)?;
+
+        read_reply(&mut stream).await.map_err(failed)
+    }
+}
+
+/// Read clamd's NUL-terminated reply
+async fn read_reply(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut reply = Vec::new();
+    stream.take(MAX_REPLY_BYTES).read_to_end(&mut reply).await?;
+    let reply = String::from_utf8_lossy(&reply);
+    Ok(reply.trim_end_matches(['\0', '\n']).to_string())
+}
+
+/// Interpret a reply such as `stream: OK` or `stream: Eicar-Signature FOUND`
+fn verdict(reply: &str) -> Result<ScanVerdict, ScanError> {
+    let result = reply.strip_prefix("stream:").unwrap_or(reply).trim();
+    if result == "OK" {
+        Ok(ScanVerdict::Clean)
+    } else if let Some(signature) = result.strip_suffix(" FOUND") {
+        Ok(ScanVerdict::Rejected(signature.trim().to_string()))
+    } else {
+        Err(ScanError::Failed(format!("clamd replied '{reply}'")))
+    }
+}
+
+#[async_trait]
+impl ContentScanner for ClamAvScanner {
+    async fn scan(
+        &self,
+        _subject: &ScanSubject,
+        content: BlobReader,
+    ) -> Result<ScanVerdict, ScanError> {
+        let reply = tokio::time::timeout(self.timeout, self.instream(content))
+            .await
+            .map_err(|_| {
+                ScanError::Unavailable(format!("clamd did not answer within {:?}", self.timeout))
+            })??;
+        verdict(&reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tokio::net::TcpListener;
+
+    fn subject() -> ScanSubject {
+        ScanSubject {
+            namespace: "uploads".to_string(),
+            tenant_id: "tenant".to_string(),
+            key: None,
+            content_type: None,
+            content_hash: "a".repeat(64),
+            size_bytes: 0,
+        }
+    }
+
+    /// A clamd that answers one scan with `reply` and returns what it received
+    async fn fake_clamd(reply: &'static str) -> (String, tokio::task::JoinHandle<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut command = [0u8; 10];
+            socket.read_exact(&mut command).await.unwrap();
+            assert_eq!(&command, b"zINSTREAM\0");
+
+            let mut received = Vec::new();
+            loop {
+                let len = socket.read_u32().await.unwrap() as usize;
+                if len == 0 {
+                    break;
+                }
+                let mut chunk = vec![0u8; len];
+                socket.read_exact(&mut chunk).await.unwrap();
+                received.extend_from_slice(&chunk);
+            }
+            socket.write_all(reply.as_bytes()).await.unwrap();
+            received
+        });
+        (addr, handle)
+    }
+
+    #[tokio::test]
+    async fn test_clean_content_streams_to_clamd() {
+        let (addr, clamd) = fake_clamd("stream: OK\0").await;
+        let content = vec![7u8; CHUNK_BYTES * 2 + 10];
+
+        let verdict = ClamAvScanner::new(addr, Duration::from_secs(5))
+            .scan(&subject(), Box::pin(Cursor::new(content.clone())))
+            .await
+            .unwrap();
+
+        assert_eq!(verdict, ScanVerdict::Clean);
+        assert_eq!(clamd.await.unwrap(), content);
+    }
+
+    #[tokio::test]
+    async fn test_found_signature_rejects() {
+        let (addr, _clamd) = fake_clamd("stream: Eicar-Test-Signature FOUND\0").await;
+
+        let verdict = ClamAvScanner::new(addr, Duration::from_secs(5))
+            .scan(&subject(), Box::pin(Cursor::new(b"X5O!P%@AP".to_vec())))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            verdict,
+            ScanVerdict::Rejected("Eicar-Test-Signature".to_string())
+        );
+    }
+
+    #[test]
+    fn test_error_reply_fails_scan() {
+        assert!(matches!(
+            verdict("INSTREAM size limit exceeded. ERROR"),
+            Err(ScanError::Failed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_clamd_is_unavailable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let result = ClamAvScanner::new(addr, Duration::from_secs(5))
+            .scan(&subject(), Box::pin(Cursor::new(Vec::new())))
+            .await;
+
+        assert!(matches!(result, Err(ScanError::Unavailable(_))));
+    }
+}
@@ -0,0 +1,5 @@
+mod clamav_scanner;
+mod noop_scanner;
+
+pub use clamav_scanner::ClamAvScanner;
+pub use noop_scanner::NoopContentScanner;
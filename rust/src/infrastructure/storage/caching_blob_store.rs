@@ -0,0 +1,298 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use moka::future::Cache;
+use serde::Serialize;
+use std::io::Cursor;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+
+use crate::application::ports::{BlobReader, BlobStore, RestoreJob, RestoreStatus, StorageError};
+use crate::domain::value_objects::{ContentHash, StorageClass};
+
+/// Limits of [`CachingBlobStore`]
+#[derive(Debug, Clone)]
+pub struct BlobCacheConfig {
+    /// Largest blob that is cached; larger blobs are always streamed
+    pub max_blob_bytes: u64,
+    /// Total bytes held across all cached blobs
+    pub capacity_bytes: u64,
+    /// How long a blob stays cached after it was read from the store
+    pub ttl: Duration,
+}
+
+/// Point-in-time blob cache statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct BlobCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+    pub size_bytes: u64,
+}
+
+/// In-memory cache of small blobs in front of another [`BlobStore`]
+///
+/// Blobs are addressed by content hash, so a cached blob never goes stale;
+/// entries only leave to make room or when their TTL runs out. Deleting a
+/// blob evicts it, so a deleted blob is not served from memory. The cache
+/// always holds whole blobs: byte ranges are cut from the reader above this
+/// port, exactly as for an uncached read.
+pub struct CachingBlobStore {
+    inner: Arc<dyn BlobStore>,
+    cache: Cache<(ContentHash, StorageClass), Bytes>,
+    max_blob_bytes: u64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CachingBlobStore {
+    pub fn new(inner: Arc<dyn BlobStore>, config: BlobCacheConfig) -> Self {
+        let cache = Cache::<(ContentHash, StorageClass), Bytes>::builder()
+            .max_capacity(config.capacity_bytes)
+            .weigher(|_, blob: &Bytes| u32::try_from(blob.len()).unwrap_or(u32::MAX))
+            .time_to_live(config.ttl)
+            .build();
+        Self {
+            inner,
+            cache,
+            max_blob_bytes: config.max_blob_bytes,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// The uncached store, for reads that must reach the backend
+    pub fn inner(&self) -> &Arc<dyn BlobStore> {
+        &self.inner
+    }
+
+    /// Hit and miss counts since startup, with the current cache size
+    ///
+    /// Entry count and size lag behind inserts and evictions slightly.
+    pub fn stats(&self) -> BlobCacheStats {
+        BlobCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.cache.entry_count(),
+            size_bytes: self.cache.weighted_size(),
+        }
+    }
+}
+
+#[async_trait]
+impl BlobStore for CachingBlobStore {
+    async fn write(
+        &self,
+        reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<(ContentHash, u64), StorageError> {
+        self.inner.write(reader, storage_class).await
+    }
+
+    async fn read(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError> {
+        let key = (content_hash.clone(), storage_class);
+        if let Some(blob) = self.cache.get(&key).await {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Box::pin(Cursor::new(blob)));
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        // Buffer one byte past the limit to learn whether the blob fits
+        // without knowing its size up front
+        let mut reader = self.inner.read(content_hash, storage_class).await?;
+        let mut head = Vec::new();
+        (&mut reader)
+            .take(self.max_blob_bytes + 1)
+            .read_to_end(&mut head)
+            .await?;
+
+        if head.len() as u64 > self.max_blob_bytes {
+            // Too large to cache: replay the buffered bytes, then stream the rest
+            return Ok(Box::pin(Cursor::new(head).chain(reader)));
+        }
+
+        let blob = Bytes::from(head);
+        self.cache.insert(key, blob.clone()).await;
+        Ok(Box::pin(Cursor::new(blob)))
+    }
+
+    async fn delete(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        self.cache
+            .invalidate(&(content_hash.clone(), storage_class))
+            .await;
+        self.inner.delete(content_hash, storage_class).await
+    }
+
+    async fn exists(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<bool, StorageError> {
+        self.inner.exists(content_hash, storage_class).await
+    }
+
+    async fn get_total_size(&self, storage_class: StorageClass) -> Result<u64, StorageError> {
+        self.inner.get_total_size(storage_class).await
+    }
+
+    async fn append_staged(
+        &self,
+        upload_id: Uuid,
+        offset: u64,
+        reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<u64, StorageError> {
+        self.inner
+            .append_staged(upload_id, offset, reader, storage_class)
+            .await
+    }
+
+    async fn staged_len(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<Option<u64>, StorageError> {
+        self.inner.staged_len(upload_id, storage_class).await
+    }
+
+    async fn read_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError> {
+        self.inner.read_staged(upload_id, storage_class).await
+    }
+
+    async fn discard_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        self.inner.discard_staged(upload_id, storage_class).await
+    }
+
+    async fn initiate_restore(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreJob, StorageError> {
+        self.inner.initiate_restore(content_hash).await
+    }
+
+    async fn restore_status(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreStatus, StorageError> {
+        self.inner.restore_status(content_hash).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockBlobStore;
+    use std::str::FromStr;
+
+    fn hash(c: char) -> ContentHash {
+        ContentHash::from_str(&c.to_string().repeat(64)).unwrap()
+    }
+
+    fn cached(inner: MockBlobStore, max_blob_bytes: u64) -> CachingBlobStore {
+        CachingBlobStore::new(
+            Arc::new(inner),
+            BlobCacheConfig {
+                max_blob_bytes,
+                capacity_bytes: 1024 * 1024,
+                ttl: Duration::from_secs(60),
+            },
+        )
+    }
+
+    async fn read_all(store: &CachingBlobStore, content_hash: &ContentHash) -> Vec<u8> {
+        let mut content = Vec::new();
+        store
+            .read(content_hash, StorageClass::Hot)
+            .await
+            .unwrap()
+            .read_to_end(&mut content)
+            .await
+            .unwrap();
+        content
+    }
+
+    #[tokio::test]
+    async fn test_small_blob_read_once_from_store() {
+        let mut inner = MockBlobStore::new();
+        inner
+            .expect_read()
+            .times(1)
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"hello".to_vec()))));
+        let store = cached(inner, 16);
+
+        assert_eq!(read_all(&store, &hash('a')).await, b"hello");
+        assert_eq!(read_all(&store, &hash('a')).await, b"hello");
+
+        let stats = store.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_large_blob_streamed_and_not_cached() {
+        let content: Vec<u8> = (0..100u8).collect();
+        let mut inner = MockBlobStore::new();
+        let blob = content.clone();
+        inner
+            .expect_read()
+            .times(2)
+            .returning(move |_, _| Ok(Box::pin(Cursor::new(blob.clone()))));
+        let store = cached(inner, 16);
+
+        // The buffered head and the streamed rest make up the whole blob
+        assert_eq!(read_all(&store, &hash('a')).await, content);
+        assert_eq!(read_all(&store, &hash('a')).await, content);
+        assert_eq!(store.stats().misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_delete_evicts_cached_blob() {
+        let mut inner = MockBlobStore::new();
+        inner
+            .expect_read()
+            .times(2)
+            .returning(|_, _| Ok(Box::pin(Cursor::new(b"hello".to_vec()))));
+        inner.expect_delete().times(1).returning(|_, _| Ok(()));
+        let store = cached(inner, 16);
+
+        read_all(&store, &hash('a')).await;
+        store.delete(&hash('a'), StorageClass::Hot).await.unwrap();
+        read_all(&store, &hash('a')).await;
+
+        assert_eq!(store.stats().misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_errors_not_cached() {
+        let mut inner = MockBlobStore::new();
+        inner
+            .expect_read()
+            .times(2)
+            .returning(|hash, _| Err(StorageError::NotFound(hash.to_string())));
+        let store = cached(inner, 16);
+
+        for _ in 0..2 {
+            assert!(matches!(
+                store.read(&hash('a'), StorageClass::Hot).await,
+                Err(StorageError::NotFound(_))
+            ));
+        }
+    }
+}
@@ -115,6 +115,40 @@ impl ContentHasher {
         Self::write_and_hash_simple(dest_path, reader, durable).await
     }
 
+    /// Stream into a new file while hashing, in `BUFFER_SIZE` chunks
+    ///
+    /// With `durable`, the file is fsynced before returning.
+    async fn write_and_hash_simple(
+        dest_path: &Path,
+        mut reader: impl AsyncRead + Unpin,
+        durable: bool,
+    ) -> Result<(ContentHash, u64), StorageError> {
+        let mut file = File::create(dest_path).await?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; BUFFER_SIZE];
+        let mut size_bytes = 0u64;
+
+        loop {
+            let n = reader.read(&mut buffer).await?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+            file.write_all(&buffer[..n]).await?;
+            size_bytes += n as u64;
+        }
+
+        file.flush().await?;
+        if durable {
+            file.sync_all().await?;
+        }
+
+        let hash_hex = hex::encode(hasher.finalize());
+        let content_hash =
+            ContentHash::from_hex(hash_hex).map_err(|e| StorageError::Internal(e.to_string()))?;
+        Ok((content_hash, size_bytes))
+    }
+
     /// Compute SHA-256 hash of an existing file.
     ///
     /// This method reads the file and computes its hash. For new content,
@@ -1,16 +1,38 @@
 use async_trait::async_trait;
 use std::collections::HashSet;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 use tokio::fs::{self, File};
-use tokio::io::BufReader;
+use tokio::io::{AsyncWriteExt, BufReader};
 use tracing::{debug, warn};
 use uuid::Uuid;
 
-use crate::application::ports::{BlobReader, BlobStore, StorageError};
+use crate::application::ports::{
+    BlobInventory, BlobReader, BlobStore, RestoreJob, RestoreStatus, StorageError, StoredFile,
+    StoredFileKind,
+};
 use crate::domain::value_objects::{ContentHash, StorageClass};
-use crate::infrastructure::storage::{ContentHasher, PathBuilder};
+use crate::infrastructure::storage::{ContentHasher, PathBuilder, ShardLayout};
+
+/// Shard trees with more leaf directories than this are created lazily on
+/// write instead of at startup
+const PRECREATE_MAX_DIRS: u64 = 4096;
+
+/// Written to each content root once no blob is left in the flat layout
+const FLAT_LAYOUT_MIGRATED_MARKER: &str = ".flat-layout-migrated";
+
+/// Outcome of moving blobs into the configured shard layout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RehashReport {
+    /// Blob files found under the content root
+    pub scanned: u64,
+    /// Blobs moved to their sharded location
+    pub moved: u64,
+    /// Misplaced copies removed because the sharded copy already existed
+    pub duplicates_removed: u64,
+}
 
 /// Simple directory caching strategy
 #[derive(Clone)]
@@ -48,6 +70,34 @@ impl DirectoryCache {
     }
 }
 
+/// When blob writes are flushed to disk
+///
+/// Blobs are always written to a temp file and renamed into place, so a
+/// crash never leaves a partial blob at its content-addressed path. With
+/// `Always`, the blob and its directory entry are also fsynced before the
+/// write returns, so a committed object survives power loss. With `None`,
+/// a crash shortly after an upload can lose a blob whose object is already
+/// committed; downloads then report it as a ghost object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FsyncPolicy {
+    /// Fsync each blob and its parent directory
+    #[default]
+    Always,
+    /// Leave flushing to the OS
+    None,
+}
+
+impl FsyncPolicy {
+    /// Parse `always` or `none`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "always" => Ok(Self::Always),
+            "none" => Ok(Self::None),
+            other => Err(format!("expected 'always' or 'none', got '{other}'")),
+        }
+    }
+}
+
 /// Local filesystem blob store implementation with adaptive caching
 pub struct LocalFilesystemStore {
     path_builder: PathBuilder,
@@ -61,6 +111,8 @@ pub struct LocalFilesystemStore {
     concurrent_threshold: usize,
     // Whether to use adaptive buffering for I/O operations
     adaptive_buffering: bool,
+    // Blobs missing from the shard layout are looked up in the flat layout
+    flat_fallback: AtomicBool,
 }
 
 impl LocalFilesystemStore {
@@ -114,38 +166,269 @@ impl LocalFilesystemStore {
             concurrent_ops: Arc::new(AtomicUsize::new(0)),
             concurrent_threshold,
             adaptive_buffering,
+            flat_fallback: AtomicBool::new(false),
         }
     }
 
+    /// Set when blob writes are flushed to disk
+    pub fn with_fsync_policy(mut self, policy: FsyncPolicy) -> Self {
+        self.durable_writes = policy == FsyncPolicy::Always;
+        self
+    }
+
+    /// Use a different shard layout for content-addressable paths
+    pub fn with_shard_layout(mut self, shard_layout: ShardLayout) -> Self {
+        let mut path_builder = PathBuilder::with_shard_layout(
+            self.path_builder.root(StorageClass::Hot).to_path_buf(),
+            self.path_builder.root(StorageClass::Cold).to_path_buf(),
+            shard_layout,
+        );
+        if let Some(temp_dir) = self.path_builder.temp_dir() {
+            path_builder = path_builder.with_temp_dir(temp_dir.to_path_buf());
+        }
+        self.path_builder = path_builder;
+        self
+    }
+
+    /// Stage blob writes and resumable uploads under `temp_dir` instead of
+    /// `temp/` under each root
+    ///
+    /// Must be on the same filesystem as the roots: blobs are renamed from
+    /// there into place, and a rename across filesystems fails. [`Self::init`]
+    /// warns when it is not.
+    pub fn with_temp_dir(mut self, temp_dir: PathBuf) -> Self {
+        self.path_builder = self.path_builder.with_temp_dir(temp_dir);
+        self
+    }
+
+    /// Migrate blobs from the flat layout to the shard layout as they are
+    /// accessed
+    ///
+    /// A blob missing from its sharded path is looked up directly under
+    /// `sha256/` and moved into place, so existing deployments can switch
+    /// layouts without stopping to rehash. [`Self::migrate_flat_layout`]
+    /// moves the blobs nobody accesses; once it has finished the fallback is
+    /// off, also after a restart.
+    pub fn with_flat_layout_migration(self, enabled: bool) -> Self {
+        self.flat_fallback.store(enabled, Ordering::Relaxed);
+        self
+    }
+
+    /// Whether blobs may still be in the flat layout
+    pub fn flat_layout_migration_pending(&self) -> bool {
+        self.flat_fallback.load(Ordering::Relaxed)
+    }
+
     /// Initialize storage directories
     pub async fn init(&self) -> Result<(), StorageError> {
         // Create directory structure
         for class in [StorageClass::Hot, StorageClass::Cold] {
             // Create temp directory
-            let root = self.path_builder.root(class);
-            fs::create_dir_all(root.join("temp")).await?;
+            let temp_root = self.path_builder.temp_root(class);
+            fs::create_dir_all(&temp_root).await?;
 
             // Create sha256 directory
-            let root = self.path_builder.root(class);
-            let sha256_root = root.join("sha256");
+            let sha256_root = self.path_builder.content_root(class);
             fs::create_dir_all(&sha256_root).await?;
 
-            // Pre-create all 256 hex prefix directories to avoid doing it on every write
-            // This is a one-time cost at startup that significantly speeds up write operations
-            if self.precreate_dirs {
-                for i in 0..=255 {
-                    let prefix = format!("{:02x}", i);
-                    fs::create_dir_all(sha256_root.join(prefix)).await?;
+            // Blobs are renamed from the temp directory into place; across
+            // filesystems that rename fails instead of being atomic
+            if !same_filesystem(&temp_root, &sha256_root).await? {
+                warn!(
+                    "Blob temp directory {} is not on the same filesystem as {}: \
+                     {} blob writes will fail; point BLOB_TEMP_DIR at the storage volume",
+                    temp_root.display(),
+                    sha256_root.display(),
+                    class
+                );
+            }
+
+            // Pre-create shard directories to avoid doing it on every write.
+            // This is a one-time cost at startup that significantly speeds up write
+            // operations; large trees are left to be created lazily on write.
+            let layout = self.path_builder.shard_layout();
+            if self.precreate_dirs && layout.depth > 0 && layout.leaf_count() <= PRECREATE_MAX_DIRS
+            {
+                let digits = layout.depth * layout.width;
+                for i in 0..layout.leaf_count() {
+                    let prefix = format!("{:0digits$x}", i);
+                    let mut dir = sha256_root.clone();
+                    dir.extend(layout.segments(&prefix));
+                    fs::create_dir_all(dir).await?;
                 }
             }
         }
 
+        if self.flat_layout_migration_pending() && self.flat_layout_migrated().await {
+            debug!("Flat layout migration already finished; fallback disabled");
+            self.flat_fallback.store(false, Ordering::Relaxed);
+        }
+
         Ok(())
     }
+
+    /// Whether both content roots carry the migration marker
+    async fn flat_layout_migrated(&self) -> bool {
+        for class in [StorageClass::Hot, StorageClass::Cold] {
+            let marker = self
+                .path_builder
+                .content_root(class)
+                .join(FLAT_LAYOUT_MIGRATED_MARKER);
+            if fs::metadata(&marker).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Move a blob still at its flat-layout path to its sharded path
+    ///
+    /// Returns whether the blob is now at its sharded path, which includes
+    /// losing a race with another migration of the same blob.
+    async fn migrate_from_flat(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<bool, StorageError> {
+        if !self.flat_layout_migration_pending() {
+            return Ok(false);
+        }
+        let flat = self
+            .path_builder
+            .content_root(storage_class)
+            .join(content_hash.as_hex());
+        let target = self.path_builder.final_path(storage_class, content_hash);
+        if flat == target || fs::metadata(&flat).await.is_err() {
+            return Ok(false);
+        }
+
+        let moved = move_into_place(&flat, &target).await?;
+        if moved {
+            debug!("Migrated blob {} out of the flat layout", content_hash);
+        }
+        Ok(moved)
+    }
+
+    /// Move every blob left in the flat layout to its sharded path
+    ///
+    /// Safe to run while serving: blobs are renamed one at a time, a blob
+    /// moved meanwhile by a read is skipped, and no directory is removed.
+    /// When it finishes, a marker in each content root records the
+    /// migration and the flat-layout fallback is turned off. Instances still
+    /// writing with the flat layout must be stopped first.
+    pub async fn migrate_flat_layout(&self) -> Result<RehashReport, StorageError> {
+        let mut report = RehashReport::default();
+        if !self.flat_layout_migration_pending() {
+            return Ok(report);
+        }
+
+        for class in [StorageClass::Hot, StorageClass::Cold] {
+            let content_root = self.path_builder.content_root(class);
+            let mut entries = match fs::read_dir(&content_root).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(StorageError::Io(e)),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                if !entry.file_type().await?.is_file() {
+                    continue;
+                }
+                let Some(hash) = entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| ContentHash::from_hex(name.to_string()).ok())
+                else {
+                    continue;
+                };
+                report.scanned += 1;
+
+                let target = self.path_builder.final_path(class, &hash);
+                if target != entry.path() && move_into_place(&entry.path(), &target).await? {
+                    report.moved += 1;
+                }
+            }
+        }
+
+        for class in [StorageClass::Hot, StorageClass::Cold] {
+            let content_root = self.path_builder.content_root(class);
+            fs::create_dir_all(&content_root).await?;
+            fs::write(content_root.join(FLAT_LAYOUT_MIGRATED_MARKER), b"").await?;
+        }
+        self.flat_fallback.store(false, Ordering::Relaxed);
+
+        Ok(report)
+    }
+
+    /// Move blobs written under another layout (e.g. flat) to their location
+    /// in the configured shard layout
+    ///
+    /// One-shot and idempotent; run while the service is stopped. Emptied
+    /// shard directories are removed.
+    pub async fn rehash(&self) -> Result<RehashReport, StorageError> {
+        let mut report = RehashReport::default();
+
+        for class in [StorageClass::Hot, StorageClass::Cold] {
+            let content_root = self.path_builder.content_root(class);
+            if fs::metadata(&content_root).await.is_err() {
+                continue;
+            }
+
+            let mut pending = vec![content_root.clone()];
+            let mut visited_dirs = Vec::new();
+
+            while let Some(dir) = pending.pop() {
+                let mut entries = fs::read_dir(&dir).await?;
+                while let Some(entry) = entries.next_entry().await? {
+                    let path = entry.path();
+                    if entry.file_type().await?.is_dir() {
+                        pending.push(path);
+                        continue;
+                    }
+
+                    let Some(hash) = path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(|name| ContentHash::from_hex(name.to_string()).ok())
+                    else {
+                        continue;
+                    };
+                    report.scanned += 1;
+
+                    let target = self.path_builder.final_path(class, &hash);
+                    if target == path {
+                        continue;
+                    }
+
+                    if fs::metadata(&target).await.is_ok() {
+                        fs::remove_file(&path).await?;
+                        report.duplicates_removed += 1;
+                    } else {
+                        if let Some(parent) = target.parent() {
+                            fs::create_dir_all(parent).await?;
+                        }
+                        fs::rename(&path, &target).await?;
+                        report.moved += 1;
+                    }
+                }
+                if dir != content_root {
+                    visited_dirs.push(dir);
+                }
+            }
+
+            // Deepest directories first; non-empty ones are left in place
+            visited_dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+            for dir in visited_dirs {
+                let _ = fs::remove_dir(&dir).await;
+            }
+        }
+
+        Ok(report)
+    }
 }
 
 #[async_trait]
 impl BlobStore for LocalFilesystemStore {
+    #[tracing::instrument(level = "debug", skip_all, fields(storage_class = %storage_class))]
     async fn write(
         &self,
         reader: BlobReader,
@@ -226,19 +509,36 @@ impl BlobStore for LocalFilesystemStore {
 
         // Check if file already exists (deduplication)
         // Use fs::metadata which is optimized for existence checks
-        let file_exists = fs::metadata(&final_path).await.is_ok();
+        let file_exists = fs::metadata(&final_path).await.is_ok()
+            || matches!(
+                self.migrate_from_flat(&content_hash, storage_class).await,
+                Ok(true)
+            );
 
         if file_exists {
             // File exists, just delete temp (deduplication case)
             debug!("Blob already exists (deduplication): {}", content_hash);
             // Best effort cleanup - ignore errors
             let _ = fs::remove_file(&temp_path).await;
+            // Compaction spares files modified within its grace window; the
+            // existing file may be an orphan this write is about to claim
+            if let Err(e) = touch(&final_path).await {
+                warn!("Failed to touch deduplicated blob {:?}: {}", final_path, e);
+            }
         } else {
             debug!("Moving blob to final location: {:?}", final_path);
             // Atomic rename - file doesn't exist
             if let Err(e) = fs::rename(&temp_path, &final_path).await {
                 // If rename fails, try to clean up temp file (best effort)
                 let _ = fs::remove_file(&temp_path).await;
+                if e.kind() == std::io::ErrorKind::CrossesDevices {
+                    return Err(StorageError::Internal(format!(
+                        "cannot move blob from {} to {} atomically: \
+                         the temp directory is on another filesystem",
+                        temp_path.display(),
+                        final_path.display()
+                    )));
+                }
                 return Err(StorageError::Io(e));
             }
 
@@ -266,6 +566,7 @@ impl BlobStore for LocalFilesystemStore {
         Ok((content_hash, size_bytes))
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(storage_class = %storage_class))]
     async fn read(
         &self,
         content_hash: &ContentHash,
@@ -273,7 +574,14 @@ impl BlobStore for LocalFilesystemStore {
     ) -> Result<BlobReader, StorageError> {
         let path = self.path_builder.final_path(storage_class, content_hash);
 
-        let file = File::open(&path).await.map_err(|e| {
+        let mut opened = File::open(&path).await;
+        if matches!(&opened, Err(e) if e.kind() == std::io::ErrorKind::NotFound)
+            && self.migrate_from_flat(content_hash, storage_class).await?
+        {
+            opened = File::open(&path).await;
+        }
+
+        let file = opened.map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 StorageError::NotFound(content_hash.to_string())
             } else {
@@ -284,6 +592,7 @@ impl BlobStore for LocalFilesystemStore {
         Ok(Box::pin(BufReader::new(file)))
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(storage_class = %storage_class))]
     async fn delete(
         &self,
         content_hash: &ContentHash,
@@ -291,7 +600,14 @@ impl BlobStore for LocalFilesystemStore {
     ) -> Result<(), StorageError> {
         let path = self.path_builder.final_path(storage_class, content_hash);
 
-        fs::remove_file(&path).await.map_err(|e| {
+        let mut removed = fs::remove_file(&path).await;
+        if matches!(&removed, Err(e) if e.kind() == std::io::ErrorKind::NotFound)
+            && self.migrate_from_flat(content_hash, storage_class).await?
+        {
+            removed = fs::remove_file(&path).await;
+        }
+
+        removed.map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 StorageError::NotFound(content_hash.to_string())
             } else {
@@ -302,13 +618,15 @@ impl BlobStore for LocalFilesystemStore {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(storage_class = %storage_class))]
     async fn exists(
         &self,
         content_hash: &ContentHash,
         storage_class: StorageClass,
     ) -> Result<bool, StorageError> {
         let path = self.path_builder.final_path(storage_class, content_hash);
-        Ok(fs::metadata(&path).await.is_ok())
+        Ok(fs::metadata(&path).await.is_ok()
+            || self.migrate_from_flat(content_hash, storage_class).await?)
     }
 
     async fn get_total_size(&self, storage_class: StorageClass) -> Result<u64, StorageError> {
@@ -317,6 +635,232 @@ impl BlobStore for LocalFilesystemStore {
             .await
             .map_err(StorageError::Io)
     }
+
+    /// Staged uploads are temp files named by the upload ID, so compaction
+    /// reclaims abandoned ones once they stop growing
+    #[tracing::instrument(level = "debug", skip_all, fields(storage_class = %storage_class))]
+    async fn append_staged(
+        &self,
+        upload_id: Uuid,
+        offset: u64,
+        mut reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<u64, StorageError> {
+        let path = self.path_builder.temp_path(storage_class, upload_id);
+        let mut file = fs::OpenOptions::new()
+            .create(offset == 0)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    StorageError::NotFound(upload_id.to_string())
+                } else {
+                    StorageError::Io(e)
+                }
+            })?;
+
+        let staged = file.metadata().await?.len();
+        if staged != offset {
+            return Err(StorageError::OffsetMismatch { staged, offset });
+        }
+
+        let copied = tokio::io::copy(&mut reader, &mut file).await;
+        // Keep what arrived before a failure; the client resumes from there
+        file.flush().await?;
+        if self.durable_writes {
+            file.sync_data().await?;
+        }
+
+        Ok(offset + copied?)
+    }
+
+    async fn staged_len(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<Option<u64>, StorageError> {
+        let path = self.path_builder.temp_path(storage_class, upload_id);
+        match fs::metadata(&path).await {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(StorageError::Io(e)),
+        }
+    }
+
+    async fn read_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError> {
+        let path = self.path_builder.temp_path(storage_class, upload_id);
+        let file = File::open(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                StorageError::NotFound(upload_id.to_string())
+            } else {
+                StorageError::Io(e)
+            }
+        })?;
+
+        Ok(Box::pin(BufReader::new(file)))
+    }
+
+    async fn discard_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        let path = self.path_builder.temp_path(storage_class, upload_id);
+        match fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::Io(e)),
+        }
+    }
+
+    /// Cold blobs sit on local disk, so they are always restored
+    async fn initiate_restore(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreJob, StorageError> {
+        Ok(RestoreJob {
+            id: content_hash.to_string(),
+            status: self.restore_status(content_hash).await?,
+        })
+    }
+
+    async fn restore_status(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreStatus, StorageError> {
+        if !self.exists(content_hash, StorageClass::Cold).await? {
+            return Err(StorageError::NotFound(content_hash.to_string()));
+        }
+        Ok(RestoreStatus::Restored)
+    }
+}
+
+#[async_trait]
+impl BlobInventory for LocalFilesystemStore {
+    async fn list_files(
+        &self,
+        storage_class: StorageClass,
+        modified_before: SystemTime,
+    ) -> Result<Vec<StoredFile>, StorageError> {
+        let mut files = Vec::new();
+
+        let temp_root = self.path_builder.temp_root(storage_class);
+        let content_root = self.path_builder.content_root(storage_class);
+        let mut pending = vec![(temp_root, true), (content_root, false)];
+
+        while let Some((dir, is_temp)) = pending.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(StorageError::Io(e)),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_dir() {
+                    // Temp files are written flat; only the content tree is sharded
+                    if !is_temp {
+                        pending.push((entry.path(), false));
+                    }
+                    continue;
+                }
+
+                let modified = metadata.modified()?;
+                if modified >= modified_before {
+                    continue;
+                }
+
+                let name = entry.file_name();
+                let Some(name) = name.to_str() else {
+                    continue;
+                };
+                let kind = if is_temp {
+                    match Uuid::parse_str(name) {
+                        Ok(_) => StoredFileKind::Temp,
+                        Err(_) => continue,
+                    }
+                } else {
+                    match ContentHash::from_hex(name.to_string()) {
+                        Ok(hash) => StoredFileKind::Blob(hash),
+                        Err(_) => continue,
+                    }
+                };
+
+                files.push(StoredFile {
+                    path: entry.path(),
+                    storage_class,
+                    kind,
+                    size_bytes: metadata.len(),
+                    modified,
+                });
+            }
+        }
+
+        Ok(files)
+    }
+
+    async fn remove_file(&self, file: &StoredFile) -> Result<(), StorageError> {
+        let inside = match file.kind {
+            StoredFileKind::Temp => self.path_builder.temp_root(file.storage_class),
+            StoredFileKind::Blob(_) => self.path_builder.content_root(file.storage_class),
+        };
+        if !file.path.starts_with(&inside) {
+            return Err(StorageError::Internal(format!(
+                "{} is outside the {} storage root",
+                file.path.display(),
+                file.storage_class
+            )));
+        }
+
+        match fs::remove_file(&file.path).await {
+            Ok(()) => Ok(()),
+            // Removed concurrently; the goal is met
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::Io(e)),
+        }
+    }
+}
+
+/// Rename a blob to `target`, creating its shard directories
+///
+/// Returns whether the blob is at `target` afterwards: a source moved away
+/// concurrently is fine as long as it arrived. An existing `target` holds
+/// the same content and is replaced.
+async fn move_into_place(source: &Path, target: &Path) -> Result<bool, StorageError> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    match fs::rename(source, target).await {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Ok(fs::metadata(target).await.is_ok())
+        }
+        Err(e) => Err(StorageError::Io(e)),
+    }
+}
+
+/// Set a file's modification time to now
+async fn touch(path: &Path) -> std::io::Result<()> {
+    let file = fs::OpenOptions::new().write(true).open(path).await?;
+    file.into_std().await.set_modified(SystemTime::now())
+}
+
+/// Whether two existing paths are on the same filesystem, so a rename from
+/// one to the other is atomic
+#[cfg(unix)]
+async fn same_filesystem(a: &Path, b: &Path) -> std::io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    Ok(fs::metadata(a).await?.dev() == fs::metadata(b).await?.dev())
+}
+
+#[cfg(not(unix))]
+async fn same_filesystem(_a: &Path, _b: &Path) -> std::io::Result<bool> {
+    Ok(true)
 }
 
 async fn calculate_dir_size(path: PathBuf) -> std::io::Result<u64> {
@@ -396,6 +940,74 @@ mod tests {
         assert!(!store.exists(&hash, StorageClass::Cold).await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_staged_upload_appends_at_offset() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let store =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf());
+        store.init().await.unwrap();
+
+        let upload_id = Uuid::new_v4();
+        let chunk =
+            |content: &'static [u8]| -> BlobReader { Box::pin(std::io::Cursor::new(content)) };
+
+        assert_eq!(
+            store
+                .staged_len(upload_id, StorageClass::Hot)
+                .await
+                .unwrap(),
+            None
+        );
+        let len = store
+            .append_staged(upload_id, 0, chunk(b"Hello, "), StorageClass::Hot)
+            .await
+            .unwrap();
+        assert_eq!(len, 7);
+
+        // A chunk at a stale offset is refused
+        let err = store
+            .append_staged(upload_id, 3, chunk(b"lo, "), StorageClass::Hot)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            StorageError::OffsetMismatch {
+                staged: 7,
+                offset: 3
+            }
+        ));
+
+        let len = store
+            .append_staged(upload_id, 7, chunk(b"World!"), StorageClass::Hot)
+            .await
+            .unwrap();
+        assert_eq!(len, 13);
+
+        let mut content = Vec::new();
+        store
+            .read_staged(upload_id, StorageClass::Hot)
+            .await
+            .unwrap()
+            .read_to_end(&mut content)
+            .await
+            .unwrap();
+        assert_eq!(content, b"Hello, World!");
+
+        store
+            .discard_staged(upload_id, StorageClass::Hot)
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .staged_len(upload_id, StorageClass::Hot)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_delete() {
         let hot_dir = TempDir::new().unwrap();
@@ -416,6 +1028,314 @@ mod tests {
         assert!(!store.exists(&hash, StorageClass::Hot).await.unwrap());
     }
 
+    #[test]
+    fn test_parse_fsync_policy() {
+        assert_eq!(FsyncPolicy::parse("always"), Ok(FsyncPolicy::Always));
+        assert_eq!(FsyncPolicy::parse(" NONE "), Ok(FsyncPolicy::None));
+        assert!(FsyncPolicy::parse("sometimes").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_without_fsync() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let store =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_fsync_policy(FsyncPolicy::None);
+        store.init().await.unwrap();
+
+        let content = b"non-critical";
+        let reader = Box::pin(std::io::Cursor::new(content));
+        let (hash, _) = store.write(reader, StorageClass::Hot).await.unwrap();
+
+        let mut reader = store.read(&hash, StorageClass::Hot).await.unwrap();
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).await.unwrap();
+        assert_eq!(buffer, content);
+    }
+
+    #[tokio::test]
+    async fn test_cold_blobs_are_always_restored() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let store =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf());
+        store.init().await.unwrap();
+
+        let reader = Box::pin(std::io::Cursor::new(b"archived"));
+        let (hash, _) = store.write(reader, StorageClass::Cold).await.unwrap();
+
+        let job = store.initiate_restore(&hash).await.unwrap();
+        assert_eq!(job.status, RestoreStatus::Restored);
+        assert_eq!(
+            store.restore_status(&hash).await.unwrap(),
+            RestoreStatus::Restored
+        );
+
+        store.delete(&hash, StorageClass::Cold).await.unwrap();
+        assert!(matches!(
+            store.restore_status(&hash).await,
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_inventory_lists_blobs_and_temp_files() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let store =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf());
+        store.init().await.unwrap();
+
+        let reader = Box::pin(std::io::Cursor::new(b"inventory"));
+        let (hash, _) = store.write(reader, StorageClass::Hot).await.unwrap();
+        let temp_path = hot_dir.path().join("temp").join(Uuid::new_v4().to_string());
+        fs::write(&temp_path, b"partial").await.unwrap();
+        fs::write(hot_dir.path().join("sha256").join("README"), b"not a blob")
+            .await
+            .unwrap();
+
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        let mut files = store.list_files(StorageClass::Hot, later).await.unwrap();
+        files.sort_by_key(|f| matches!(f.kind, StoredFileKind::Temp));
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].kind, StoredFileKind::Blob(hash.clone()));
+        assert_eq!(files[0].size_bytes, 9);
+        assert_eq!(files[1].path, temp_path);
+
+        // Files modified within the window are left out
+        let earlier = SystemTime::UNIX_EPOCH;
+        assert!(store
+            .list_files(StorageClass::Hot, earlier)
+            .await
+            .unwrap()
+            .is_empty());
+
+        store.remove_file(&files[0]).await.unwrap();
+        assert!(!store.exists(&hash, StorageClass::Hot).await.unwrap());
+        // Already gone is not an error
+        store.remove_file(&files[0]).await.unwrap();
+
+        let outside = StoredFile {
+            path: cold_dir.path().join("elsewhere"),
+            ..files[1].clone()
+        };
+        assert!(store.remove_file(&outside).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deduplicated_write_touches_existing_blob() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let store =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf());
+        store.init().await.unwrap();
+
+        let reader = Box::pin(std::io::Cursor::new(b"dedup"));
+        let (hash, _) = store.write(reader, StorageClass::Hot).await.unwrap();
+        let path = store.path_builder.final_path(StorageClass::Hot, &hash);
+        let long_ago = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(long_ago)
+            .unwrap();
+
+        let reader = Box::pin(std::io::Cursor::new(b"dedup"));
+        store.write(reader, StorageClass::Hot).await.unwrap();
+
+        // No longer old enough for compaction to consider
+        let cutoff = long_ago + std::time::Duration::from_secs(60);
+        assert!(store
+            .list_files(StorageClass::Hot, cutoff)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_temp_dir_stages_writes_outside_roots() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let store =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_temp_dir(temp_dir.path().to_path_buf())
+                .with_shard_layout(ShardLayout::flat());
+        store.init().await.unwrap();
+
+        assert!(temp_dir.path().join("hot").is_dir());
+        assert!(temp_dir.path().join("cold").is_dir());
+        assert!(!hot_dir.path().join("temp").exists());
+        assert!(same_filesystem(temp_dir.path(), hot_dir.path())
+            .await
+            .unwrap());
+
+        let reader = Box::pin(std::io::Cursor::new(b"staged"));
+        let (hash, _) = store.write(reader, StorageClass::Hot).await.unwrap();
+        assert!(store.exists(&hash, StorageClass::Hot).await.unwrap());
+
+        let upload_id = Uuid::new_v4();
+        let reader = Box::pin(std::io::Cursor::new(b"partial"));
+        store
+            .append_staged(upload_id, 0, reader, StorageClass::Hot)
+            .await
+            .unwrap();
+        let staged = temp_dir.path().join("hot").join(upload_id.to_string());
+        assert!(staged.is_file());
+
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        let files = store.list_files(StorageClass::Hot, later).await.unwrap();
+        let temp = files
+            .iter()
+            .find(|f| f.kind == StoredFileKind::Temp)
+            .unwrap();
+        assert_eq!(temp.path, staged);
+        store.remove_file(temp).await.unwrap();
+        assert!(!staged.exists());
+    }
+
+    #[tokio::test]
+    async fn test_init_skips_precreating_large_shard_trees() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let store =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_shard_layout(ShardLayout::new(2, 2));
+        store.init().await.unwrap();
+
+        let sha256_root = hot_dir.path().join("sha256");
+        assert!(sha256_root.exists());
+        assert!(!sha256_root.join("00").exists());
+
+        // Shard directories are created on write
+        let reader = Box::pin(std::io::Cursor::new(b"sharded"));
+        let (hash, _) = store.write(reader, StorageClass::Hot).await.unwrap();
+        let hex = hash.as_hex();
+        assert!(sha256_root
+            .join(&hex[0..2])
+            .join(&hex[2..4])
+            .join(hex)
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn test_rehash_moves_flat_layout_into_shards() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        // Write under a flat layout
+        let flat =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_shard_layout(ShardLayout::flat());
+        flat.init().await.unwrap();
+        let reader = Box::pin(std::io::Cursor::new(b"legacy blob"));
+        let (hash, _) = flat.write(reader, StorageClass::Hot).await.unwrap();
+        assert!(hot_dir.path().join("sha256").join(hash.as_hex()).exists());
+
+        // Rehash into a nested layout
+        let sharded =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_shard_layout(ShardLayout::new(2, 2));
+        assert!(!sharded.exists(&hash, StorageClass::Hot).await.unwrap());
+
+        let report = sharded.rehash().await.unwrap();
+
+        assert_eq!(report.scanned, 1);
+        assert_eq!(report.moved, 1);
+        assert!(sharded.exists(&hash, StorageClass::Hot).await.unwrap());
+        assert!(!hot_dir.path().join("sha256").join(hash.as_hex()).exists());
+
+        // Second run is a no-op
+        let again = sharded.rehash().await.unwrap();
+        assert_eq!(again.moved, 0);
+        assert_eq!(again.scanned, 1);
+    }
+
+    #[tokio::test]
+    async fn test_flat_layout_read_through_migrates_on_access() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let flat =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_shard_layout(ShardLayout::flat());
+        flat.init().await.unwrap();
+        let reader = Box::pin(std::io::Cursor::new(b"legacy blob"));
+        let (hash, _) = flat.write(reader, StorageClass::Hot).await.unwrap();
+        let flat_path = hot_dir.path().join("sha256").join(hash.as_hex());
+
+        let sharded =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_shard_layout(ShardLayout::new(2, 2))
+                .with_flat_layout_migration(true);
+        sharded.init().await.unwrap();
+        assert!(sharded.flat_layout_migration_pending());
+
+        let mut reader = sharded.read(&hash, StorageClass::Hot).await.unwrap();
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).await.unwrap();
+
+        assert_eq!(content, b"legacy blob");
+        assert!(!flat_path.exists());
+        let hex = hash.as_hex();
+        assert!(hot_dir
+            .path()
+            .join("sha256")
+            .join(&hex[0..2])
+            .join(&hex[2..4])
+            .join(hex)
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_flat_layout_records_completion() {
+        let hot_dir = TempDir::new().unwrap();
+        let cold_dir = TempDir::new().unwrap();
+
+        let flat =
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_shard_layout(ShardLayout::flat());
+        flat.init().await.unwrap();
+        let mut hashes = Vec::new();
+        for (content, class) in [
+            (&b"hot blob"[..], StorageClass::Hot),
+            (&b"cold blob"[..], StorageClass::Cold),
+        ] {
+            let reader = Box::pin(std::io::Cursor::new(content));
+            hashes.push((flat.write(reader, class).await.unwrap().0, class));
+        }
+
+        let sharded = || {
+            LocalFilesystemStore::new(hot_dir.path().to_path_buf(), cold_dir.path().to_path_buf())
+                .with_flat_layout_migration(true)
+        };
+        let store = sharded();
+        store.init().await.unwrap();
+
+        let report = store.migrate_flat_layout().await.unwrap();
+
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.moved, 2);
+        assert!(!store.flat_layout_migration_pending());
+        for (hash, class) in &hashes {
+            assert!(store.exists(hash, *class).await.unwrap());
+        }
+
+        // A restart sees the marker and skips the fallback
+        let restarted = sharded();
+        restarted.init().await.unwrap();
+        assert!(!restarted.flat_layout_migration_pending());
+    }
+
     #[tokio::test]
     async fn test_deduplication() {
         let hot_dir = TempDir::new().unwrap();
@@ -1,7 +1,15 @@
+mod caching_blob_store;
 mod content_hasher;
 mod local_filesystem_store;
 mod path_builder;
+mod resilient_blob_store;
+mod routing_blob_store;
 
+pub use caching_blob_store::{BlobCacheConfig, BlobCacheStats, CachingBlobStore};
 pub use content_hasher::ContentHasher;
-pub use local_filesystem_store::LocalFilesystemStore;
-pub use path_builder::PathBuilder;
+pub use local_filesystem_store::{FsyncPolicy, LocalFilesystemStore, RehashReport};
+pub use path_builder::{PathBuilder, ShardLayout};
+pub use resilient_blob_store::{
+    BreakerState, ResilienceConfig, ResilienceStats, ResilientBlobStore,
+};
+pub use routing_blob_store::{BlobBackend, BlobBackendRoots, RoutingBlobStore};
@@ -2,20 +2,105 @@ use std::path::{Path, PathBuf};
 
 use crate::domain::value_objects::{ContentHash, StorageClass};
 
+/// Directory fan-out for content-addressable paths
+///
+/// A layout of `depth` levels of `width` hex characters places
+/// `abcdef…` at `sha256/ab/cd/abcdef…` for depth 2, width 2. Depth 0 is a
+/// flat layout with every blob directly under `sha256/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardLayout {
+    pub depth: usize,
+    pub width: usize,
+}
+
+impl Default for ShardLayout {
+    /// One level of two hex characters (256 directories)
+    fn default() -> Self {
+        Self { depth: 1, width: 2 }
+    }
+}
+
+impl ShardLayout {
+    pub fn new(depth: usize, width: usize) -> Self {
+        Self { depth, width }
+    }
+
+    /// Flat layout without shard directories
+    pub fn flat() -> Self {
+        Self { depth: 0, width: 0 }
+    }
+
+    /// Validate that the shard prefixes fit inside a SHA-256 hex digest
+    pub fn validate(&self) -> Result<(), String> {
+        if self.depth > 0 && self.width == 0 {
+            return Err("shard width must be > 0 when depth > 0".to_string());
+        }
+        if self.depth * self.width > 8 {
+            return Err(format!(
+                "shard depth * width must be <= 8 (got {} * {})",
+                self.depth, self.width
+            ));
+        }
+        Ok(())
+    }
+
+    /// Number of leaf shard directories (1 for a flat layout)
+    pub fn leaf_count(&self) -> u64 {
+        16u64.saturating_pow((self.depth * self.width) as u32)
+    }
+
+    /// Shard directory components for a hex digest
+    pub fn segments<'a>(&self, hex: &'a str) -> impl Iterator<Item = &'a str> {
+        let width = self.width;
+        (0..self.depth).map(move |level| &hex[level * width..(level + 1) * width])
+    }
+}
+
 /// Utility for generating storage paths
 pub struct PathBuilder {
     hot_root: PathBuf,
     cold_root: PathBuf,
+    shard_layout: ShardLayout,
+    /// Staging directory for temp files instead of `temp/` under each root
+    temp_dir: Option<PathBuf>,
 }
 
 impl PathBuilder {
     pub fn new(hot_root: PathBuf, cold_root: PathBuf) -> Self {
+        Self::with_shard_layout(hot_root, cold_root, ShardLayout::default())
+    }
+
+    pub fn with_shard_layout(
+        hot_root: PathBuf,
+        cold_root: PathBuf,
+        shard_layout: ShardLayout,
+    ) -> Self {
         Self {
             hot_root,
             cold_root,
+            shard_layout,
+            temp_dir: None,
         }
     }
 
+    /// Stage temp files under `temp_dir/{hot,cold}` instead of each root's
+    /// `temp/`
+    ///
+    /// Blobs are renamed from there into place, which is only atomic when
+    /// `temp_dir` is on the same filesystem as the roots.
+    pub fn with_temp_dir(mut self, temp_dir: PathBuf) -> Self {
+        self.temp_dir = Some(temp_dir);
+        self
+    }
+
+    pub fn shard_layout(&self) -> ShardLayout {
+        self.shard_layout
+    }
+
+    pub fn temp_dir(&self) -> Option<&Path> {
+        self.temp_dir.as_deref()
+    }
+
     /// Get root path for storage class
     pub fn root(&self, storage_class: StorageClass) -> &Path {
         match storage_class {
@@ -24,17 +109,91 @@ impl PathBuilder {
         }
     }
 
+    /// Root of the content-addressable tree: /root/sha256
+    pub fn content_root(&self, storage_class: StorageClass) -> PathBuf {
+        self.root(storage_class).join("sha256")
+    }
+
+    /// Directory of temp files: /root/temp, or /temp_dir/{hot,cold}
+    pub fn temp_root(&self, storage_class: StorageClass) -> PathBuf {
+        match &self.temp_dir {
+            Some(temp_dir) => temp_dir.join(storage_class.to_string()),
+            None => self.root(storage_class).join("temp"),
+        }
+    }
+
     /// Generate temp upload path: /root/temp/{uuid}
     pub fn temp_path(&self, storage_class: StorageClass, id: uuid::Uuid) -> PathBuf {
-        self.root(storage_class).join("temp").join(id.to_string())
+        self.temp_root(storage_class).join(id.to_string())
     }
 
-    /// Generate final content-addressable path: /root/sha256/{prefix}/{hash}
+    /// Generate final content-addressable path: /root/sha256/{shard}/…/{hash}
     pub fn final_path(&self, storage_class: StorageClass, hash: &ContentHash) -> PathBuf {
-        let prefix = hash.prefix();
-        self.root(storage_class)
-            .join("sha256")
-            .join(prefix)
-            .join(hash.as_hex())
+        let mut path = self.content_root(storage_class);
+        path.extend(self.shard_layout.segments(hash.as_hex()));
+        path.join(hash.as_hex())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn hash() -> ContentHash {
+        ContentHash::from_str(&format!("abcdef{}", "0".repeat(58))).unwrap()
+    }
+
+    fn builder(layout: ShardLayout) -> PathBuilder {
+        PathBuilder::with_shard_layout(PathBuf::from("/hot"), PathBuf::from("/cold"), layout)
+    }
+
+    #[test]
+    fn test_default_layout_uses_two_char_prefix() {
+        let path = builder(ShardLayout::default()).final_path(StorageClass::Hot, &hash());
+
+        assert_eq!(path, PathBuf::from("/hot/sha256/ab").join(hash().as_hex()));
+    }
+
+    #[test]
+    fn test_nested_layout() {
+        let path = builder(ShardLayout::new(2, 2)).final_path(StorageClass::Cold, &hash());
+
+        assert_eq!(
+            path,
+            PathBuf::from("/cold/sha256/ab/cd").join(hash().as_hex())
+        );
+    }
+
+    #[test]
+    fn test_flat_layout() {
+        let path = builder(ShardLayout::flat()).final_path(StorageClass::Hot, &hash());
+
+        assert_eq!(path, PathBuf::from("/hot/sha256").join(hash().as_hex()));
+    }
+
+    #[test]
+    fn test_temp_dir_separates_storage_classes() {
+        let id = uuid::Uuid::new_v4();
+        let default = builder(ShardLayout::default());
+        let staged = builder(ShardLayout::default()).with_temp_dir(PathBuf::from("/staging"));
+
+        assert_eq!(
+            default.temp_path(StorageClass::Hot, id),
+            PathBuf::from("/hot/temp").join(id.to_string())
+        );
+        assert_eq!(
+            staged.temp_path(StorageClass::Cold, id),
+            PathBuf::from("/staging/cold").join(id.to_string())
+        );
+    }
+
+    #[test]
+    fn test_layout_validation() {
+        assert!(ShardLayout::default().validate().is_ok());
+        assert!(ShardLayout::flat().validate().is_ok());
+        assert!(ShardLayout::new(2, 0).validate().is_err());
+        assert!(ShardLayout::new(3, 3).validate().is_err());
+        assert_eq!(ShardLayout::new(2, 2).leaf_count(), 65_536);
     }
 }
@@ -0,0 +1,479 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use std::fmt;
+use std::future::Future;
+use std::io::ErrorKind;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::application::ports::{BlobReader, BlobStore, RestoreJob, RestoreStatus, StorageError};
+use crate::domain::value_objects::{ContentHash, StorageClass};
+
+/// Retry and circuit breaker settings of [`ResilientBlobStore`]
+#[derive(Debug, Clone)]
+pub struct ResilienceConfig {
+    /// Attempts per call, counting the first; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles with every further retry
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Longest a single attempt may take; streamed writes are not limited
+    pub attempt_timeout: Duration,
+    /// Transient failures in a row that open the breaker
+    pub failure_threshold: u32,
+    /// How long an open breaker rejects calls before letting one through
+    pub open_duration: Duration,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+            attempt_timeout: Duration::from_secs(30),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+/// State of a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Calls go through
+    Closed,
+    /// The backend failed repeatedly; calls fail fast
+    Open,
+    /// The open period passed; the next call decides whether to close
+    HalfOpen,
+}
+
+impl fmt::Display for BreakerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakerState::Closed => write!(f, "closed"),
+            BreakerState::Open => write!(f, "open"),
+            BreakerState::HalfOpen => write!(f, "half_open"),
+        }
+    }
+}
+
+/// Point-in-time retry and breaker statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct ResilienceStats {
+    pub state: BreakerState,
+    /// Transient failures in a row
+    pub consecutive_failures: u32,
+    /// Retries made since startup
+    pub retries: u64,
+    /// Calls rejected by the open breaker since startup
+    pub rejected: u64,
+}
+
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    /// Set once the breaker opened; cleared by a successful call
+    opened_at: Option<Instant>,
+}
+
+/// Retries and a circuit breaker in front of another [`BlobStore`]
+///
+/// Calls failing with a transient error (a timeout, a dropped connection or
+/// [`StorageError::Transient`]) are retried with exponential backoff, except
+/// for writes, whose body cannot be replayed. Any other error, such as a
+/// missing blob, is returned at once and counts as the backend answering.
+/// After `failure_threshold` transient failures in a row the breaker opens
+/// and calls fail with [`StorageError::Unavailable`] instead of waiting on
+/// the backend; once `open_duration` passes, the next call is let through
+/// and closes the breaker if it succeeds.
+pub struct ResilientBlobStore {
+    inner: Arc<dyn BlobStore>,
+    config: ResilienceConfig,
+    breaker: Mutex<Breaker>,
+    retries: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl ResilientBlobStore {
+    pub fn new(inner: Arc<dyn BlobStore>, config: ResilienceConfig) -> Self {
+        Self {
+            inner,
+            config,
+            breaker: Mutex::new(Breaker::default()),
+            retries: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stats(&self) -> ResilienceStats {
+        let breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        ResilienceStats {
+            state: self.state(&breaker),
+            consecutive_failures: breaker.consecutive_failures,
+            retries: self.retries.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    fn state(&self, breaker: &Breaker) -> BreakerState {
+        match breaker.opened_at {
+            None => BreakerState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.config.open_duration => {
+                BreakerState::Open
+            }
+            Some(_) => BreakerState::HalfOpen,
+        }
+    }
+
+    /// Fail fast while the breaker is open
+    fn admit(&self) -> Result<(), StorageError> {
+        let breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        match breaker.opened_at {
+            Some(opened_at) if self.state(&breaker) == BreakerState::Open => {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                let remaining = self
+                    .config
+                    .open_duration
+                    .saturating_sub(opened_at.elapsed());
+                Err(StorageError::Unavailable {
+                    retry_after_secs: remaining.as_secs().max(1),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn record<T>(&self, result: &Result<T, StorageError>) {
+        let mut breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        match result {
+            Err(e) if is_transient(e) => {
+                breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+                let reopen = self.state(&breaker) != BreakerState::Open;
+                if breaker.consecutive_failures >= self.config.failure_threshold && reopen {
+                    tracing::warn!(
+                        consecutive_failures = breaker.consecutive_failures,
+                        "Blob store circuit breaker opened: {}",
+                        e
+                    );
+                    breaker.opened_at = Some(Instant::now());
+                }
+            }
+            _ => {
+                if breaker.opened_at.is_some() {
+                    tracing::info!("Blob store circuit breaker closed");
+                }
+                *breaker = Breaker::default();
+            }
+        }
+    }
+
+    /// Delay before retry number `retry` (starting at 1)
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry - 1).unwrap_or(u32::MAX);
+        self.config
+            .base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.config.max_delay)
+            .min(self.config.max_delay)
+    }
+
+    /// One attempt bounded by the attempt timeout
+    async fn attempt<T>(
+        &self,
+        operation: &'static str,
+        call: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        self.admit()?;
+        let result = tokio::time::timeout(self.config.attempt_timeout, call)
+            .await
+            .unwrap_or_else(|_| {
+                Err(StorageError::Transient(format!(
+                    "{operation} timed out after {}s",
+                    self.config.attempt_timeout.as_secs()
+                )))
+            });
+        self.record(&result);
+        result
+    }
+
+    /// Run a repeatable call, retrying transient failures
+    async fn retry<T, F, Fut>(
+        &self,
+        operation: &'static str,
+        mut call: F,
+    ) -> Result<T, StorageError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, StorageError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match self.attempt(operation, call()).await {
+                Err(e) if attempt < self.config.max_attempts && is_transient(&e) => {
+                    let delay = self.backoff(attempt);
+                    self.retries.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        operation,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying blob store call after transient error: {}",
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Run a call that consumes a body: once, without a timeout
+    async fn once<T>(
+        &self,
+        call: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        self.admit()?;
+        let result = call.await;
+        self.record(&result);
+        result
+    }
+}
+
+/// Whether a call failed in a way that retrying may fix
+pub fn is_transient(error: &StorageError) -> bool {
+    match error {
+        StorageError::Transient(_) => true,
+        StorageError::Io(e) => matches!(
+            e.kind(),
+            ErrorKind::TimedOut
+                | ErrorKind::Interrupted
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::ConnectionRefused
+                | ErrorKind::NotConnected
+                | ErrorKind::BrokenPipe
+        ),
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl BlobStore for ResilientBlobStore {
+    async fn write(
+        &self,
+        reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<(ContentHash, u64), StorageError> {
+        self.once(self.inner.write(reader, storage_class)).await
+    }
+
+    async fn read(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError> {
+        self.retry("read", || self.inner.read(content_hash, storage_class))
+            .await
+    }
+
+    async fn delete(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        self.retry("delete", || self.inner.delete(content_hash, storage_class))
+            .await
+    }
+
+    async fn exists(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<bool, StorageError> {
+        self.retry("exists", || self.inner.exists(content_hash, storage_class))
+            .await
+    }
+
+    async fn get_total_size(&self, storage_class: StorageClass) -> Result<u64, StorageError> {
+        self.retry("get_total_size", || {
+            self.inner.get_total_size(storage_class)
+        })
+        .await
+    }
+
+    async fn append_staged(
+        &self,
+        upload_id: Uuid,
+        offset: u64,
+        reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<u64, StorageError> {
+        self.once(
+            self.inner
+                .append_staged(upload_id, offset, reader, storage_class),
+        )
+        .await
+    }
+
+    async fn staged_len(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<Option<u64>, StorageError> {
+        self.retry("staged_len", || {
+            self.inner.staged_len(upload_id, storage_class)
+        })
+        .await
+    }
+
+    async fn read_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError> {
+        self.retry("read_staged", || {
+            self.inner.read_staged(upload_id, storage_class)
+        })
+        .await
+    }
+
+    async fn discard_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        self.retry("discard_staged", || {
+            self.inner.discard_staged(upload_id, storage_class)
+        })
+        .await
+    }
+
+    async fn initiate_restore(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreJob, StorageError> {
+        self.retry("initiate_restore", || {
+            self.inner.initiate_restore(content_hash)
+        })
+        .await
+    }
+
+    async fn restore_status(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreStatus, StorageError> {
+        self.retry("restore_status", || self.inner.restore_status(content_hash))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MockBlobStore;
+    use std::str::FromStr;
+
+    fn hash() -> ContentHash {
+        ContentHash::from_str(&"a".repeat(64)).unwrap()
+    }
+
+    fn resilient(inner: MockBlobStore, failure_threshold: u32) -> ResilientBlobStore {
+        ResilientBlobStore::new(
+            Arc::new(inner),
+            ResilienceConfig {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(2),
+                attempt_timeout: Duration::from_secs(5),
+                failure_threshold,
+                open_duration: Duration::from_secs(60),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_retried_until_success() {
+        let mut inner = MockBlobStore::new();
+        let mut calls = 0;
+        inner.expect_exists().times(3).returning(move |_, _| {
+            calls += 1;
+            if calls < 3 {
+                Err(StorageError::Transient("503 Slow Down".to_string()))
+            } else {
+                Ok(true)
+            }
+        });
+        let store = resilient(inner, 10);
+
+        assert!(store.exists(&hash(), StorageClass::Hot).await.unwrap());
+        let stats = store.stats();
+        assert_eq!(stats.retries, 2);
+        assert_eq!(stats.state, BreakerState::Closed);
+        assert_eq!(stats.consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_passes_through_without_retry() {
+        let mut inner = MockBlobStore::new();
+        inner
+            .expect_read()
+            .times(1)
+            .returning(|hash, _| Err(StorageError::NotFound(hash.to_string())));
+        let store = resilient(inner, 1);
+
+        assert!(matches!(
+            store.read(&hash(), StorageClass::Hot).await,
+            Err(StorageError::NotFound(_))
+        ));
+        assert_eq!(store.stats().retries, 0);
+        assert_eq!(store.stats().state, BreakerState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failures_open_breaker_and_fail_fast() {
+        let mut inner = MockBlobStore::new();
+        // Two calls of three attempts reach the threshold; later calls never
+        // reach the backend
+        inner
+            .expect_delete()
+            .times(6)
+            .returning(|_, _| Err(StorageError::Io(std::io::Error::from(ErrorKind::TimedOut))));
+        let store = resilient(inner, 6);
+
+        for _ in 0..2 {
+            assert!(matches!(
+                store.delete(&hash(), StorageClass::Hot).await,
+                Err(StorageError::Io(_))
+            ));
+        }
+        assert!(matches!(
+            store.delete(&hash(), StorageClass::Hot).await,
+            Err(StorageError::Unavailable { .. })
+        ));
+
+        let stats = store.stats();
+        assert_eq!(stats.state, BreakerState::Open);
+        assert_eq!(stats.rejected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_writes_not_retried() {
+        let mut inner = MockBlobStore::new();
+        inner
+            .expect_write()
+            .times(1)
+            .returning(|_, _| Err(StorageError::Transient("connection reset".to_string())));
+        let store = resilient(inner, 10);
+
+        let result = store
+            .write(Box::pin(tokio::io::empty()), StorageClass::Hot)
+            .await;
+        assert!(matches!(result, Err(StorageError::Transient(_))));
+        assert_eq!(store.stats().consecutive_failures, 1);
+    }
+}
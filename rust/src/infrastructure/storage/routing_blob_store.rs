@@ -0,0 +1,524 @@
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+use crate::application::blob_routing::BlobRoutes;
+use crate::application::ports::{
+    BlobInventory, BlobReader, BlobRouter, BlobStore, RestoreJob, RestoreStatus, StorageError,
+    StoredFile,
+};
+use crate::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
+use crate::infrastructure::storage::LocalFilesystemStore;
+
+/// Storage roots of a named local backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobBackendRoots {
+    pub name: String,
+    pub hot_root: PathBuf,
+    pub cold_root: PathBuf,
+}
+
+impl BlobBackendRoots {
+    /// Parse backends like `nvme=/mnt/nvme/hot,/mnt/nvme/cold;archive=...`
+    pub fn parse_list(spec: &str) -> Result<Vec<Self>, String> {
+        let mut backends: Vec<Self> = Vec::new();
+
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, roots) = entry.split_once('=').ok_or_else(|| {
+                format!("Invalid blob backend '{entry}': expected name=hot_root,cold_root")
+            })?;
+            let name = name.trim();
+            let (hot_root, cold_root) = roots
+                .split_once(',')
+                .map(|(hot, cold)| (hot.trim(), cold.trim()))
+                .filter(|(hot, cold)| !hot.is_empty() && !cold.is_empty())
+                .ok_or_else(|| {
+                    format!("Invalid blob backend '{entry}': expected name=hot_root,cold_root")
+                })?;
+            if name.is_empty() {
+                return Err(format!("Invalid blob backend '{entry}': empty name"));
+            }
+            if backends.iter().any(|b| b.name == name) {
+                return Err(format!("Blob backend '{name}' is defined twice"));
+            }
+
+            backends.push(Self {
+                name: name.to_string(),
+                hot_root: PathBuf::from(hot_root),
+                cold_root: PathBuf::from(cold_root),
+            });
+        }
+
+        Ok(backends)
+    }
+}
+
+/// A store the router can write to, with the inventory of its files
+pub struct BlobBackend {
+    pub name: String,
+    pub store: Arc<dyn BlobStore>,
+    pub inventory: Arc<dyn BlobInventory>,
+}
+
+/// [`BlobStore`] spread over several backends, chosen by [`BlobRoutes`]
+///
+/// New blobs and staged uploads go to the primary backend: the routed one
+/// for stores handed out by [`BlobRouter::store_for`], the fallback one
+/// otherwise. Blobs are content-addressed and deduplicated across
+/// namespaces, so lookups by hash search the primary backend first and then
+/// every other one, and deletes remove the blob from all of them. Changing
+/// a route therefore only affects where new blobs land.
+#[derive(Clone)]
+pub struct RoutingBlobStore {
+    backends: Arc<Vec<BlobBackend>>,
+    routes: Arc<BlobRoutes>,
+    /// Index of the backend written to, and searched first
+    primary: usize,
+}
+
+impl RoutingBlobStore {
+    /// Fails when a route names a backend that is not given
+    pub fn new(backends: Vec<BlobBackend>, routes: BlobRoutes) -> Result<Self, String> {
+        let position = |name: &str| backends.iter().position(|b| b.name == name);
+        let primary = position(routes.fallback())
+            .ok_or_else(|| format!("Unknown blob backend '{}'", routes.fallback()))?;
+        Ok(Self {
+            backends: Arc::new(backends),
+            routes: Arc::new(routes),
+            primary,
+        })
+    }
+
+    /// Route over one plain local store per backend, built by `store` from
+    /// its roots
+    ///
+    /// For maintenance tools working on the stored files: the stores get
+    /// none of the server's retries or caching.
+    pub fn local(
+        roots: &[BlobBackendRoots],
+        routes: BlobRoutes,
+        store: impl Fn(&BlobBackendRoots) -> LocalFilesystemStore,
+    ) -> Result<Self, String> {
+        let backends = roots
+            .iter()
+            .map(|roots| {
+                let store = Arc::new(store(roots));
+                BlobBackend {
+                    name: roots.name.clone(),
+                    store: Arc::clone(&store) as Arc<dyn BlobStore>,
+                    inventory: store,
+                }
+            })
+            .collect();
+        Self::new(backends, routes)
+    }
+
+    /// Backends in search order: the primary one first
+    fn search_order(&self) -> impl Iterator<Item = &BlobBackend> {
+        let primary = &self.backends[self.primary];
+        std::iter::once(primary).chain(
+            self.backends
+                .iter()
+                .enumerate()
+                .filter(move |(i, _)| *i != self.primary)
+                .map(|(_, backend)| backend),
+        )
+    }
+
+    /// First backend holding the blob, or the primary one
+    async fn locate(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<&BlobBackend, StorageError> {
+        for backend in self.search_order() {
+            if backend.store.exists(content_hash, storage_class).await? {
+                return Ok(backend);
+            }
+        }
+        Ok(&self.backends[self.primary])
+    }
+
+    /// First backend with the staged upload, or the primary one
+    async fn locate_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<&BlobBackend, StorageError> {
+        for backend in self.search_order() {
+            if backend
+                .store
+                .staged_len(upload_id, storage_class)
+                .await?
+                .is_some()
+            {
+                return Ok(backend);
+            }
+        }
+        Ok(&self.backends[self.primary])
+    }
+}
+
+impl BlobRouter for RoutingBlobStore {
+    fn store_for(&self, namespace: &Namespace, tenant_id: &TenantId) -> Arc<dyn BlobStore> {
+        let name = self.routes.backend_for(namespace.as_str(), tenant_id);
+        // Routes are checked against the backends at startup
+        let primary = self
+            .backends
+            .iter()
+            .position(|b| b.name == name)
+            .unwrap_or(self.primary);
+        Arc::new(Self {
+            primary,
+            ..self.clone()
+        })
+    }
+}
+
+#[async_trait]
+impl BlobStore for RoutingBlobStore {
+    async fn write(
+        &self,
+        reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<(ContentHash, u64), StorageError> {
+        self.backends[self.primary]
+            .store
+            .write(reader, storage_class)
+            .await
+    }
+
+    async fn read(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError> {
+        for backend in self.search_order() {
+            match backend.store.read(content_hash, storage_class).await {
+                Err(StorageError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(StorageError::NotFound(content_hash.to_string()))
+    }
+
+    async fn delete(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        let mut deleted = false;
+        for backend in self.backends.iter() {
+            match backend.store.delete(content_hash, storage_class).await {
+                Ok(()) => deleted = true,
+                Err(StorageError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if !deleted {
+            return Err(StorageError::NotFound(content_hash.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn exists(
+        &self,
+        content_hash: &ContentHash,
+        storage_class: StorageClass,
+    ) -> Result<bool, StorageError> {
+        for backend in self.search_order() {
+            if backend.store.exists(content_hash, storage_class).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn get_total_size(&self, storage_class: StorageClass) -> Result<u64, StorageError> {
+        let mut total = 0;
+        for backend in self.backends.iter() {
+            total += backend.store.get_total_size(storage_class).await?;
+        }
+        Ok(total)
+    }
+
+    async fn append_staged(
+        &self,
+        upload_id: Uuid,
+        offset: u64,
+        reader: BlobReader,
+        storage_class: StorageClass,
+    ) -> Result<u64, StorageError> {
+        let backend = if offset == 0 {
+            &self.backends[self.primary]
+        } else {
+            self.locate_staged(upload_id, storage_class).await?
+        };
+        backend
+            .store
+            .append_staged(upload_id, offset, reader, storage_class)
+            .await
+    }
+
+    async fn staged_len(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<Option<u64>, StorageError> {
+        for backend in self.search_order() {
+            if let Some(len) = backend.store.staged_len(upload_id, storage_class).await? {
+                return Ok(Some(len));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn read_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<BlobReader, StorageError> {
+        self.locate_staged(upload_id, storage_class)
+            .await?
+            .store
+            .read_staged(upload_id, storage_class)
+            .await
+    }
+
+    async fn discard_staged(
+        &self,
+        upload_id: Uuid,
+        storage_class: StorageClass,
+    ) -> Result<(), StorageError> {
+        for backend in self.backends.iter() {
+            backend
+                .store
+                .discard_staged(upload_id, storage_class)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn initiate_restore(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreJob, StorageError> {
+        self.locate(content_hash, StorageClass::Cold)
+            .await?
+            .store
+            .initiate_restore(content_hash)
+            .await
+    }
+
+    async fn restore_status(
+        &self,
+        content_hash: &ContentHash,
+    ) -> Result<RestoreStatus, StorageError> {
+        self.locate(content_hash, StorageClass::Cold)
+            .await?
+            .store
+            .restore_status(content_hash)
+            .await
+    }
+}
+
+#[async_trait]
+impl BlobInventory for RoutingBlobStore {
+    async fn list_files(
+        &self,
+        storage_class: StorageClass,
+        modified_before: SystemTime,
+    ) -> Result<Vec<StoredFile>, StorageError> {
+        let mut files = Vec::new();
+        for backend in self.backends.iter() {
+            files.extend(
+                backend
+                    .inventory
+                    .list_files(storage_class, modified_before)
+                    .await?,
+            );
+        }
+        Ok(files)
+    }
+
+    /// Backends refuse files outside their own roots, so the file is removed
+    /// by the backend that listed it
+    async fn remove_file(&self, file: &StoredFile) -> Result<(), StorageError> {
+        let mut last_error = None;
+        for backend in self.backends.iter() {
+            match backend.inventory.remove_file(file).await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            StorageError::Internal(format!("No blob backend holds {}", file.path.display()))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::str::FromStr;
+    use tempfile::TempDir;
+    use tokio::io::AsyncReadExt;
+
+    struct Backends {
+        store: RoutingBlobStore,
+        nvme: Arc<LocalFilesystemStore>,
+        default: Arc<LocalFilesystemStore>,
+        _dirs: Vec<TempDir>,
+    }
+
+    async fn backends(routes: &str) -> Backends {
+        let mut dirs = Vec::new();
+        let mut local = Vec::new();
+        for _ in ["default", "nvme"] {
+            let (hot, cold) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+            let store = Arc::new(LocalFilesystemStore::new(
+                hot.path().to_path_buf(),
+                cold.path().to_path_buf(),
+            ));
+            store.init().await.unwrap();
+            local.push(store);
+            dirs.extend([hot, cold]);
+        }
+        let backend = |name: &str, store: &Arc<LocalFilesystemStore>| BlobBackend {
+            name: name.to_string(),
+            store: Arc::clone(store) as Arc<dyn BlobStore>,
+            inventory: Arc::clone(store) as Arc<dyn BlobInventory>,
+        };
+        let store = RoutingBlobStore::new(
+            vec![backend("default", &local[0]), backend("nvme", &local[1])],
+            BlobRoutes::parse(routes, &["default", "nvme"]).unwrap(),
+        )
+        .unwrap();
+        Backends {
+            store,
+            nvme: Arc::clone(&local[1]),
+            default: Arc::clone(&local[0]),
+            _dirs: dirs,
+        }
+    }
+
+    fn routed(store: &RoutingBlobStore, namespace: &str) -> Arc<dyn BlobStore> {
+        store.store_for(
+            &Namespace::from_str(namespace).unwrap(),
+            &TenantId::new(Uuid::new_v4()),
+        )
+    }
+
+    async fn write(store: &dyn BlobStore, content: &'static [u8]) -> ContentHash {
+        store
+            .write(Box::pin(Cursor::new(content)), StorageClass::Hot)
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn test_routed_writes_land_on_their_backend_and_read_back() {
+        let backends = backends("images=nvme").await;
+
+        let image = write(routed(&backends.store, "images").as_ref(), b"image").await;
+        let doc = write(routed(&backends.store, "docs").as_ref(), b"doc").await;
+
+        assert!(backends
+            .nvme
+            .exists(&image, StorageClass::Hot)
+            .await
+            .unwrap());
+        assert!(!backends
+            .default
+            .exists(&image, StorageClass::Hot)
+            .await
+            .unwrap());
+        assert!(backends
+            .default
+            .exists(&doc, StorageClass::Hot)
+            .await
+            .unwrap());
+
+        // Reads through any store find the blob wherever it was written
+        for store in [
+            routed(&backends.store, "docs"),
+            Arc::new(backends.store.clone()) as Arc<dyn BlobStore>,
+        ] {
+            let mut content = Vec::new();
+            store
+                .read(&image, StorageClass::Hot)
+                .await
+                .unwrap()
+                .read_to_end(&mut content)
+                .await
+                .unwrap();
+            assert_eq!(content, b"image");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_copies_from_every_backend() {
+        let backends = backends("images=nvme").await;
+        let hash = write(routed(&backends.store, "images").as_ref(), b"shared").await;
+        write(routed(&backends.store, "docs").as_ref(), b"shared").await;
+
+        backends
+            .store
+            .delete(&hash, StorageClass::Hot)
+            .await
+            .unwrap();
+
+        assert!(!backends
+            .store
+            .exists(&hash, StorageClass::Hot)
+            .await
+            .unwrap());
+        assert!(matches!(
+            backends.store.delete(&hash, StorageClass::Hot).await,
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_inventory_lists_and_removes_files_of_every_backend() {
+        let backends = backends("images=nvme").await;
+        write(routed(&backends.store, "images").as_ref(), b"image").await;
+        write(routed(&backends.store, "docs").as_ref(), b"doc").await;
+
+        let modified_before = SystemTime::now() + std::time::Duration::from_secs(60);
+        let files = backends
+            .store
+            .list_files(StorageClass::Hot, modified_before)
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 2);
+
+        for file in &files {
+            backends.store.remove_file(file).await.unwrap();
+        }
+        assert!(backends
+            .store
+            .list_files(StorageClass::Hot, modified_before)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_backend_roots() {
+        let backends =
+            BlobBackendRoots::parse_list("nvme=/mnt/nvme/hot,/mnt/nvme/cold; archive=/a,/b")
+                .unwrap();
+        assert_eq!(backends.len(), 2);
+        assert_eq!(backends[0].name, "nvme");
+        assert_eq!(backends[1].cold_root, PathBuf::from("/b"));
+
+        assert!(BlobBackendRoots::parse_list("nvme=/mnt/nvme/hot").is_err());
+        assert!(BlobBackendRoots::parse_list("=/a,/b").is_err());
+        assert!(BlobBackendRoots::parse_list("a=/a,/b;a=/c,/d").is_err());
+    }
+}
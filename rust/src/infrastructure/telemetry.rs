@@ -0,0 +1,163 @@
+//! Tracing setup and OpenTelemetry export
+//!
+//! Logs always go to stdout. When `OTEL_EXPORTER_OTLP_ENDPOINT` is set, spans
+//! are additionally exported over OTLP/HTTP and W3C `traceparent` headers are
+//! honoured on inbound requests and added to outbound ones.
+//!
+//! Use case, blob store and query spans are `debug` level. Without an
+//! exporter no layer enables them, so their call sites stay disabled and the
+//! propagation helpers return immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::http::HeaderMap;
+use opentelemetry::{global, trace::TracerProvider as _};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource};
+use tracing::{level_filters::LevelFilter, Level, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{filter::filter_fn, layer::SubscriberExt, util::SubscriberInitExt, Layer};
+
+use crate::config::Config;
+
+static EXPORT_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether spans are being exported
+pub fn is_enabled() -> bool {
+    EXPORT_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Keeps the tracer provider alive; call `shutdown` to flush pending spans
+pub struct TelemetryGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl TelemetryGuard {
+    /// Flush and stop the span exporter
+    pub fn shutdown(self) {
+        if let Some(provider) = self.provider {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!("Failed to flush OpenTelemetry spans: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global `tracing` subscriber
+pub fn init_tracing(config: &Config) -> Result<TelemetryGuard, ExporterBuildError> {
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_target(true)
+        .with_thread_ids(true)
+        .with_filter(LevelFilter::INFO);
+
+    let Some(endpoint) = config.otel_exporter_otlp_endpoint.as_deref() else {
+        tracing_subscriber::registry().with(fmt_layer).init();
+        return Ok(TelemetryGuard { provider: None });
+    };
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(traces_endpoint(endpoint))
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.otel_service_name.clone())
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer("just_storage");
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+    EXPORT_ENABLED.store(true, Ordering::Relaxed);
+
+    // Export debug-level spans, but only the events that are also logged
+    let otel_layer = tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter_fn(|metadata| {
+            let max_level = if metadata.is_span() {
+                Level::DEBUG
+            } else {
+                Level::INFO
+            };
+            *metadata.level() <= max_level
+        }));
+
+    tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(otel_layer)
+        .init();
+
+    Ok(TelemetryGuard {
+        provider: Some(provider),
+    })
+}
+
+/// OTLP/HTTP traces URL for a collector base URL
+///
+/// Follows the `OTEL_EXPORTER_OTLP_ENDPOINT` convention of appending the
+/// signal path to the base URL.
+pub fn traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}/v1/traces")
+    }
+}
+
+/// Continue the trace carried by inbound W3C trace context headers
+pub fn set_remote_parent(span: &Span, headers: &HeaderMap) {
+    if !is_enabled() {
+        return;
+    }
+
+    let parent =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    if let Err(e) = span.set_parent(parent) {
+        tracing::debug!("Ignoring inbound trace context: {}", e);
+    }
+}
+
+/// Add W3C trace context headers for the current span to an outbound request
+pub fn inject_trace_context(headers: &mut HeaderMap) {
+    if !is_enabled() {
+        return;
+    }
+
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traces_endpoint_appends_signal_path() {
+        assert_eq!(
+            traces_endpoint("http://collector:4318"),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("http://collector:4318/"),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("http://collector:4318/v1/traces"),
+            "http://collector:4318/v1/traces"
+        );
+    }
+
+    #[test]
+    fn test_propagation_is_noop_when_disabled() {
+        let mut headers = HeaderMap::new();
+        inject_trace_context(&mut headers);
+        assert!(headers.is_empty());
+    }
+}
@@ -1,30 +1,46 @@
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::net::TcpListener;
-use tracing::{error, info, Level};
+use tracing::{error, info};
 
 use just_storage::api::internal::create_internal_router;
+use just_storage::api::server::{self, ConnectionLimiter, HttpServerConfig};
+use just_storage::infrastructure::telemetry;
 use just_storage::{api::create_router, ApplicationBuilder, Config};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize tracing with structured logging
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .with_target(true)
-        .with_thread_ids(true)
-        .init();
-
-    info!("Starting JustStorage service");
-
     // Load and validate configuration
     let config = Config::from_env();
     config.validate()?;
+
+    // Initialize tracing with structured logging (and span export if configured)
+    let telemetry = telemetry::init_tracing(&config)?;
+
+    info!("Starting JustStorage service");
     info!("Configuration loaded and validated");
+    if telemetry::is_enabled() {
+        info!(
+            "Exporting OpenTelemetry spans to {:?}",
+            config.otel_exporter_otlp_endpoint
+        );
+    }
 
     // Build application using builder pattern
     let listen_addr = config.listen_addr.clone();
     let admin_port = config.admin_port;
+    let http_server_config = HttpServerConfig::new()
+        .with_http2(config.http2_enabled)
+        .with_http2_max_concurrent_streams(config.http2_max_concurrent_streams)
+        .with_http2_keep_alive_interval(
+            (config.http2_keep_alive_interval_secs > 0)
+                .then(|| Duration::from_secs(config.http2_keep_alive_interval_secs)),
+        )
+        .with_http2_keep_alive_timeout(Duration::from_secs(config.http2_keep_alive_timeout_secs))
+        .with_http1_keep_alive(config.http1_keep_alive)
+        .with_header_read_timeout(Duration::from_secs(config.http_header_read_timeout_secs));
 
     let builder = ApplicationBuilder::new(config)
         .with_database()
@@ -40,16 +56,35 @@ async fn main() -> anyhow::Result<()> {
     let (state, api_key_repo, audit_repo) = builder.build()?;
 
     if let Some(gc) = &state.gc {
+        // Settle uploads interrupted by a previous crash before GC can delete them
+        if let Some(report) = gc.recover_interrupted_writes().await {
+            if report.has_recoveries() || !report.errors.is_empty() {
+                info!("{}", report.summary());
+            }
+        }
         tokio::spawn(Arc::clone(gc).run());
         info!("Garbage collector started");
     }
 
+    if let Some(access_recorder) = &state.access_recorder {
+        tokio::spawn(Arc::clone(access_recorder).run());
+    }
+
+    if let Some(scrubber) = &state.scrubber {
+        tokio::spawn(Arc::clone(scrubber).run());
+        info!("Blob scrubber started");
+    }
+
+    // Watch the connection pool for saturation and reconnect it if it dies
+    tokio::spawn(Arc::clone(&state.pool_monitor).run());
+
     // Create main router
     let app = create_router(state.clone(), api_key_repo, audit_repo).await;
 
     // Setup graceful shutdown signal
     let (shutdown_tx, _) = tokio::sync::broadcast::channel::<()>(1);
     let mut shutdown_rx = shutdown_tx.subscribe();
+    let shutting_down = Arc::clone(&state.shutting_down);
 
     let shutdown_signal = async move {
         let ctrl_c = async {
@@ -75,6 +110,7 @@ async fn main() -> anyhow::Result<()> {
         }
 
         info!("Shutdown signal received, starting graceful shutdown...");
+        shutting_down.store(true, Ordering::SeqCst);
         let _ = shutdown_tx.send(());
     };
 
@@ -83,16 +119,24 @@ async fn main() -> anyhow::Result<()> {
 
     // Prepare main server
     info!("Listening on {}", listen_addr);
+    info!("Serving {}", http_server_config.summary());
     let listener = TcpListener::bind(&listen_addr).await?;
     let main_shutdown_rx = shutdown_rx;
     shutdown_rx = main_shutdown_rx.resubscribe();
 
+    let main_http_server_config = http_server_config.clone();
+    let connections = Arc::clone(&state.connection_limiter);
     let main_server = async move {
-        if let Err(e) = axum::serve(listener, app)
-            .with_graceful_shutdown(async move {
+        if let Err(e) = server::serve(
+            listener,
+            app,
+            &main_http_server_config,
+            connections,
+            async move {
                 let _ = main_shutdown_rx.resubscribe().recv().await;
-            })
-            .await
+            },
+        )
+        .await
         {
             error!("Main server error: {}", e);
         }
@@ -103,15 +147,20 @@ async fn main() -> anyhow::Result<()> {
         let admin_addr = format!("0.0.0.0:{}", port);
         info!("Internal admin listening on {}", admin_addr);
         let admin_listener = TcpListener::bind(&admin_addr).await?;
-        let admin_router = create_internal_router(state).await;
+        let admin_router = create_internal_router(state.clone()).await;
         let admin_shutdown_rx = shutdown_rx;
 
         let admin_server = async move {
-            if let Err(e) = axum::serve(admin_listener, admin_router)
-                .with_graceful_shutdown(async move {
+            if let Err(e) = server::serve(
+                admin_listener,
+                admin_router,
+                &http_server_config,
+                Arc::new(ConnectionLimiter::new(0)),
+                async move {
                     let _ = admin_shutdown_rx.resubscribe().recv().await;
-                })
-                .await
+                },
+            )
+            .await
             {
                 error!("Admin server error: {}", e);
             }
@@ -122,6 +171,14 @@ async fn main() -> anyhow::Result<()> {
         main_server.await;
     }
 
+    // Write download counts gathered since the last flush
+    if let Some(access_recorder) = &state.access_recorder {
+        if let Err(e) = access_recorder.flush().await {
+            error!("Failed to flush access counts on shutdown: {}", e);
+        }
+    }
+
     info!("Server shutdown complete");
+    telemetry.shutdown();
     Ok(())
 }
@@ -187,6 +187,10 @@ impl UploadRequestBuilder {
             tenant_id: self.tenant_id,
             key: self.key,
             storage_class: self.storage_class,
+            content_type: None,
+            content_encoding: None,
+            expected_hash: None,
+            idempotency_key: None,
         }
     }
 }
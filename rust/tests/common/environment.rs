@@ -164,6 +164,18 @@ pub async fn setup_test_api_server() -> (
     axum::Router,
     testcontainers::ContainerAsync<testcontainers_modules::postgres::Postgres>,
     tempfile::TempDir,
+) {
+    setup_test_api_server_with_config(|_| {}).await
+}
+
+/// Like `setup_test_api_server`, with a hook to adjust the config first
+pub async fn setup_test_api_server_with_config(
+    configure: impl FnOnce(&mut just_storage::Config),
+) -> (
+    axum::Router,
+    axum::Router,
+    testcontainers::ContainerAsync<testcontainers_modules::postgres::Postgres>,
+    tempfile::TempDir,
 ) {
     use just_storage::{
         api::{
@@ -198,6 +210,8 @@ pub async fn setup_test_api_server() -> (
     std::fs::create_dir_all(&config.hot_storage_root).expect("Failed to create hot storage");
     std::fs::create_dir_all(&config.cold_storage_root).expect("Failed to create cold storage");
 
+    configure(&mut config);
+
     // Build application
     let builder = ApplicationBuilder::new(config)
         .with_database()
@@ -6,10 +6,19 @@ use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Mutex;
 
+use just_storage::application::dto::{
+    ObjectAccess, ObjectDto, ObjectField, ObjectHead, ObjectProjection, ObjectRecord,
+    SortDirection, SortField,
+};
+use just_storage::application::key_prefix_query::KeyPrefixQuery;
+use just_storage::application::list_cursor::{CursorValue, ListCursor};
 use just_storage::application::ports::ObjectRepository;
+use just_storage::application::ports::ObjectStream;
 use just_storage::application::ports::RepositoryError;
 use just_storage::domain::entities::Object;
-use just_storage::domain::value_objects::{Namespace, ObjectId, TenantId};
+use just_storage::domain::value_objects::{
+    ContentHash, Namespace, ObjectId, ObjectMetadata, ObjectStatus, StorageClass, TenantId,
+};
 
 /// In-memory object repository for testing
 pub struct InMemoryObjectRepository {
@@ -23,6 +32,15 @@ impl InMemoryObjectRepository {
         }
     }
 
+    /// Whether `obj` is listed by `keys` rather than folded into a common prefix
+    fn matches_keys(obj: &Object, keys: &KeyPrefixQuery) -> bool {
+        if keys.is_empty() {
+            return true;
+        }
+        obj.key()
+            .is_some_and(|key| key.starts_with(keys.prefix()) && keys.common_prefix(key).is_none())
+    }
+
     pub fn with_objects(objects: Vec<Object>) -> Self {
         let mut map = HashMap::new();
         for obj in objects {
@@ -47,6 +65,118 @@ impl ObjectRepository for InMemoryObjectRepository {
         Ok(objects.get(id).cloned())
     }
 
+    async fn find_writing(&self, id: &ObjectId) -> Result<Option<Object>, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        Ok(objects
+            .get(id)
+            .filter(|object| object.status() == ObjectStatus::Writing)
+            .cloned())
+    }
+
+    async fn head(&self, id: &ObjectId) -> Result<Option<ObjectHead>, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        Ok(objects.get(id).and_then(ObjectHead::from_committed))
+    }
+
+    /// Counts the readable objects sharing the content as its references
+    async fn find_record(&self, id: &ObjectId) -> Result<Option<ObjectRecord>, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        let Some(object) = objects.get(id).filter(|object| object.is_readable()) else {
+            return Ok(None);
+        };
+        let blob_ref_count = objects
+            .values()
+            .filter(|other| other.is_readable() && other.content_hash() == object.content_hash())
+            .count() as u64;
+        Ok(Some(ObjectRecord {
+            object: object.clone(),
+            blob_ref_count: Some(blob_ref_count),
+        }))
+    }
+
+    async fn status(
+        &self,
+        id: &ObjectId,
+    ) -> Result<Option<(TenantId, ObjectStatus)>, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        Ok(objects
+            .get(id)
+            .map(|object| (object.tenant_id().clone(), object.status())))
+    }
+
+    async fn head_by_key(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        key: &str,
+    ) -> Result<Option<ObjectHead>, RepositoryError> {
+        Ok(self
+            .find_by_key(namespace, tenant_id, key)
+            .await?
+            .as_ref()
+            .and_then(ObjectHead::from_committed))
+    }
+
+    async fn replace_content_if_match(
+        &self,
+        object: &Object,
+        expected: &ContentHash,
+    ) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().unwrap();
+        match objects.get(object.id()) {
+            Some(stored) if stored.is_readable() && stored.content_hash() == Some(expected) => {
+                objects.insert(*object.id(), object.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_metadata_if_match(
+        &self,
+        object: &Object,
+        expected: &ObjectMetadata,
+    ) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().unwrap();
+        match objects.get(object.id()) {
+            Some(stored) if stored.is_readable() && stored.metadata() == expected => {
+                objects.insert(*object.id(), object.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_retention(&self, object: &Object) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().unwrap();
+        match objects.get(object.id()) {
+            Some(stored) if stored.is_readable() => {
+                objects.insert(*object.id(), object.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn mark_deleting_if_unlocked(&self, object: &Object) -> Result<bool, RepositoryError> {
+        let mut objects = self.objects.lock().unwrap();
+        match objects.get(object.id()) {
+            Some(stored) if stored.is_readable() && !stored.is_locked() => {
+                objects.insert(*object.id(), object.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn set_extracted_text(
+        &self,
+        _id: &ObjectId,
+        _text: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        Ok(())
+    }
+
     async fn find_by_key(
         &self,
         namespace: &Namespace,
@@ -68,25 +198,163 @@ impl ObjectRepository for InMemoryObjectRepository {
         &self,
         namespace: &Namespace,
         tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        _metadata: &just_storage::application::metadata_query::MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        after: Option<ListCursor>,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<Object>, RepositoryError> {
         let objects = self.objects.lock().unwrap();
         let mut filtered: Vec<_> = objects
             .values()
-            .filter(|obj| obj.namespace() == namespace && obj.tenant_id() == tenant_id)
+            .filter(|obj| {
+                obj.namespace() == namespace
+                    && obj.tenant_id() == tenant_id
+                    && Self::matches_keys(obj, keys)
+            })
             .cloned()
             .collect();
 
-        filtered.sort_by_key(|a| a.created_at());
+        // Like Postgres: unset values last when ascending, ties by ID
+        let position = |value: Option<CursorValue>, id: &ObjectId| (value.is_none(), value, *id);
+        let ordering = |a: &(bool, Option<CursorValue>, ObjectId),
+                        b: &(bool, Option<CursorValue>, ObjectId)| {
+            let ordering = (a.0, &a.1, a.2.as_uuid())
+                .partial_cmp(&(b.0, &b.1, b.2.as_uuid()))
+                .unwrap_or(std::cmp::Ordering::Equal);
+            match sort_direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            }
+        };
+        let object_position =
+            |obj: &Object| position(ListCursor::sort_value(obj, sort_by), obj.id());
+        filtered.sort_by(|a, b| ordering(&object_position(a), &object_position(b)));
+        if let Some(after) = after {
+            let after = position(after.value, &after.id);
+            filtered.retain(|obj| ordering(&object_position(obj), &after).is_gt());
+        }
         let start = offset as usize;
         let end = (offset + limit) as usize;
         Ok(filtered.into_iter().skip(start).take(end - start).collect())
     }
 
+    async fn list_projected(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        metadata: &just_storage::application::metadata_query::MetadataQuery,
+        sort_by: SortField,
+        sort_direction: SortDirection,
+        fields: &[ObjectField],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ObjectProjection>, RepositoryError> {
+        let objects = self
+            .list(
+                namespace,
+                tenant_id,
+                keys,
+                metadata,
+                sort_by,
+                sort_direction,
+                None,
+                limit,
+                offset,
+            )
+            .await?;
+        Ok(objects
+            .into_iter()
+            .map(|obj| ObjectProjection::new(&ObjectDto::from(obj), fields))
+            .collect())
+    }
+
+    fn stream(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        _metadata: &just_storage::application::metadata_query::MetadataQuery,
+        _sort_by: SortField,
+        _sort_direction: SortDirection,
+    ) -> ObjectStream {
+        let objects = self.objects.lock().unwrap();
+        let mut filtered: Vec<_> = objects
+            .values()
+            .filter(|obj| {
+                obj.namespace() == namespace
+                    && obj.tenant_id() == tenant_id
+                    && Self::matches_keys(obj, keys)
+            })
+            .cloned()
+            .collect();
+
+        filtered.sort_by_key(|a| a.created_at());
+        Box::pin(futures_util::stream::iter(filtered.into_iter().map(Ok)))
+    }
+
+    async fn common_prefixes(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        _metadata: &just_storage::application::metadata_query::MetadataQuery,
+        max: i64,
+    ) -> Result<Vec<String>, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        let mut prefixes: Vec<String> = objects
+            .values()
+            .filter(|obj| obj.namespace() == namespace && obj.tenant_id() == tenant_id)
+            .filter_map(|obj| obj.key().and_then(|key| keys.common_prefix(key)))
+            .map(str::to_string)
+            .collect();
+        prefixes.sort();
+        prefixes.dedup();
+        prefixes.truncate(max as usize);
+        Ok(prefixes)
+    }
+
+    async fn count(
+        &self,
+        namespace: &Namespace,
+        tenant_id: &TenantId,
+        keys: &KeyPrefixQuery,
+        _metadata: &just_storage::application::metadata_query::MetadataQuery,
+        max: i64,
+    ) -> Result<i64, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        let count = objects
+            .values()
+            .filter(|obj| {
+                obj.namespace() == namespace
+                    && obj.tenant_id() == tenant_id
+                    && Self::matches_keys(obj, keys)
+            })
+            .count() as i64;
+        Ok(count.min(max))
+    }
+
+    async fn namespaces(&self, tenant_id: &TenantId) -> Result<Vec<Namespace>, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        let mut namespaces: Vec<Namespace> = objects
+            .values()
+            .filter(|obj| obj.tenant_id() == tenant_id)
+            .map(|obj| obj.namespace().clone())
+            .collect();
+        namespaces.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        namespaces.dedup();
+        Ok(namespaces)
+    }
+
     async fn search(
         &self,
         _request: &just_storage::application::dto::SearchRequest,
+        _keys: &KeyPrefixQuery,
+        _metadata: &just_storage::application::metadata_query::MetadataQuery,
+        _after: Option<ListCursor>,
     ) -> Result<Vec<Object>, RepositoryError> {
         Ok(vec![])
     }
@@ -94,8 +362,29 @@ impl ObjectRepository for InMemoryObjectRepository {
     async fn text_search(
         &self,
         _request: &just_storage::application::dto::TextSearchRequest,
-    ) -> Result<Vec<Object>, RepositoryError> {
-        Ok(vec![])
+    ) -> Result<just_storage::application::dto::TextSearchPage, RepositoryError> {
+        Ok(Default::default())
+    }
+
+    async fn record_access(&self, _accesses: &[ObjectAccess]) -> Result<(), RepositoryError> {
+        Ok(())
+    }
+
+    async fn move_storage_class(
+        &self,
+        content_hash: &ContentHash,
+        from: StorageClass,
+        to: StorageClass,
+    ) -> Result<u64, RepositoryError> {
+        let mut objects = self.objects.lock().unwrap();
+        let mut moved = 0;
+        for object in objects.values_mut() {
+            if object.content_hash() == Some(content_hash) && object.storage_class() == from {
+                object.set_storage_class(to);
+                moved += 1;
+            }
+        }
+        Ok(moved)
     }
 
     async fn delete(&self, id: &ObjectId) -> Result<(), RepositoryError> {
@@ -112,7 +401,31 @@ impl ObjectRepository for InMemoryObjectRepository {
         Ok(vec![])
     }
 
+    async fn find_stuck_staged_objects(
+        &self,
+        _age_minutes: i64,
+        _limit: i64,
+    ) -> Result<Vec<Object>, RepositoryError> {
+        Ok(vec![])
+    }
+
     async fn cleanup_stuck_uploads(&self, _age_hours: i64) -> Result<usize, RepositoryError> {
         Ok(0)
     }
+
+    async fn find_locked_hashes(
+        &self,
+        content_hashes: &[ContentHash],
+    ) -> Result<Vec<ContentHash>, RepositoryError> {
+        let objects = self.objects.lock().unwrap();
+        Ok(content_hashes
+            .iter()
+            .filter(|hash| {
+                objects
+                    .values()
+                    .any(|o| o.is_locked() && o.content_hash() == Some(*hash))
+            })
+            .cloned()
+            .collect())
+    }
 }
@@ -44,6 +44,11 @@ async fn api_key_management_full_flow_succeeds() {
         .iter()
         .any(|k| k.get("id").unwrap().as_str().unwrap() == key_id));
 
+    // The key authenticates (and is now cached as verified)
+    let req = http::authenticated_request(Method::GET, "/v1/objects", &key_secret);
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+
     // 3. Update API key
     let update_req = http::authenticated_json_request(
         Method::PUT,
@@ -74,3 +79,108 @@ async fn api_key_management_full_flow_succeeds() {
     let response = app.oneshot(get_req).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+async fn api_key_rotation_revokes_old_secret() {
+    let (app, _, _container, _temp_dir) = env::setup_test_api_server().await;
+    let api_key = "test-key";
+
+    let create_req = http::authenticated_json_request(
+        Method::POST,
+        "/v1/api-keys",
+        api_key,
+        json!({
+            "name": "Rotated Key",
+            "permissions": {
+                "read": true,
+                "write": false,
+                "delete": false,
+                "admin": false
+            }
+        }),
+    );
+    let response = app.clone().oneshot(create_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = http::extract_json_response(response).await;
+    let key_id = body.get("id").unwrap().as_str().unwrap().to_string();
+    let old_secret = body.get("key").unwrap().as_str().unwrap().to_string();
+
+    // Used once before rotating, so its verification is cached
+    let req = http::authenticated_request(Method::GET, "/v1/objects", &old_secret);
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Rotate: same ID, new secret returned once
+    let rotate_req = http::authenticated_request(
+        Method::POST,
+        &format!("/v1/api-keys/{}/rotate", key_id),
+        api_key,
+    );
+    let response = app.clone().oneshot(rotate_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = http::extract_json_response(response).await;
+    assert_eq!(body.get("id").unwrap().as_str().unwrap(), key_id);
+    let new_secret = body.get("key").unwrap().as_str().unwrap().to_string();
+    assert_ne!(new_secret, old_secret);
+
+    // The old secret stops validating at once
+    let req = http::authenticated_request(Method::GET, "/v1/objects", &old_secret);
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let req = http::authenticated_request(Method::GET, "/v1/objects", &new_secret);
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // The secret is never handed out again
+    let get_req =
+        http::authenticated_request(Method::GET, &format!("/v1/api-keys/{}", key_id), api_key);
+    let response = app.oneshot(get_req).await.unwrap();
+    let body = http::extract_json_response(response).await;
+    assert!(body.get("key").is_none_or(|key| key.is_null()));
+}
+
+#[tokio::test]
+async fn deleted_api_key_stops_authenticating_at_once() {
+    let (app, _, _container, _temp_dir) = env::setup_test_api_server().await;
+    let api_key = "test-key";
+
+    let create_req = http::authenticated_json_request(
+        Method::POST,
+        "/v1/api-keys",
+        api_key,
+        json!({
+            "name": "Short-lived Key",
+            "permissions": {
+                "read": true,
+                "write": false,
+                "delete": false,
+                "admin": false
+            }
+        }),
+    );
+    let response = app.clone().oneshot(create_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = http::extract_json_response(response).await;
+    let key_id = body.get("id").unwrap().as_str().unwrap().to_string();
+    let key_secret = body.get("key").unwrap().as_str().unwrap().to_string();
+
+    // Repeated use is served from the verified-key cache
+    for _ in 0..2 {
+        let req = http::authenticated_request(Method::GET, "/v1/objects", &key_secret);
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let delete_req =
+        http::authenticated_request(Method::DELETE, &format!("/v1/api-keys/{}", key_id), api_key);
+    let response = app.clone().oneshot(delete_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let req = http::authenticated_request(Method::GET, "/v1/objects", &key_secret);
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
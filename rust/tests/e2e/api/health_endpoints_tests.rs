@@ -20,3 +20,28 @@ async fn health_endpoint_returns_ok_with_healthy_status() {
     let response = app.oneshot(req).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn liveness_and_startup_probes_report_per_check_status() {
+    let (app, _, _container, _temp_dir) = env::setup_test_api_server().await;
+
+    let response = app
+        .clone()
+        .oneshot(http::get_request("/health/live"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = http::extract_json_response(response).await;
+    assert_eq!(json["checks"]["process"], "up");
+
+    // Migrations ran in the builder; no garbage collector is configured
+    let response = app
+        .oneshot(http::get_request("/health/startup"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = http::extract_json_response(response).await;
+    assert_eq!(json["status"], "started");
+    assert_eq!(json["checks"]["migrations"]["status"], "ready");
+    assert_eq!(json["checks"]["gc"]["status"], "disabled");
+}
@@ -1,4 +1,5 @@
-use axum::http::{Method, StatusCode};
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
 use serde_json::json;
 use tower::ServiceExt;
 
@@ -43,6 +44,54 @@ async fn object_lifecycle_full_flow_succeeds() {
         .iter()
         .any(|obj| obj.get("id").unwrap().as_str().unwrap() == object_id));
 
+    // Projected listing returns only the requested fields
+    let list_req = http::authenticated_request(
+        Method::GET,
+        "/v1/objects?namespace=test&tenant_id=550e8400-e29b-41d4-a716-446655440000&fields=id,key",
+        api_key,
+    );
+    let response = app.clone().oneshot(list_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = http::extract_json_response(response).await;
+    let objects = body.get("objects").unwrap().as_array().unwrap();
+    assert!(objects.contains(&json!({"id": object_id, "key": "test-file.txt"})));
+
+    let list_req = http::authenticated_request(
+        Method::GET,
+        "/v1/objects?namespace=test&tenant_id=550e8400-e29b-41d4-a716-446655440000&fields=id,password",
+        api_key,
+    );
+    let response = app.clone().oneshot(list_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Streamed listing returns one object per line
+    let mut list_req = http::authenticated_request(
+        Method::GET,
+        "/v1/objects?namespace=test&tenant_id=550e8400-e29b-41d4-a716-446655440000",
+        api_key,
+    );
+    list_req.headers_mut().insert(
+        axum::http::header::ACCEPT,
+        axum::http::HeaderValue::from_static("application/x-ndjson"),
+    );
+    let response = app.clone().oneshot(list_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[axum::http::header::CONTENT_TYPE],
+        "application/x-ndjson"
+    );
+
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let lines: Vec<serde_json::Value> = String::from_utf8(body_bytes.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(lines.iter().any(|obj| obj["id"] == object_id.as_str()));
+
     // 3. Download object by ID
     let download_req = http::authenticated_request(
         Method::GET,
@@ -74,6 +123,27 @@ async fn object_lifecycle_full_flow_succeeds() {
         .unwrap();
     assert_eq!(body_bytes, "Hello, E2E!");
 
+    // A committed object's status is returned without waiting
+    let status_req = http::authenticated_request(
+        Method::GET,
+        &format!(
+            "/v1/objects/{}/status?tenant_id=550e8400-e29b-41d4-a716-446655440000&wait=30",
+            object_id
+        ),
+        api_key,
+    );
+    let response = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        app.clone().oneshot(status_req),
+    )
+    .await
+    .expect("status of a committed object should not wait")
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = http::extract_json_response(response).await;
+    assert_eq!(body, json!({"id": object_id, "status": "COMMITTED"}));
+
     // 5. Delete object
     let delete_req = http::authenticated_request(
         Method::DELETE,
@@ -98,3 +168,180 @@ async fn object_lifecycle_full_flow_succeeds() {
     let response = app.oneshot(get_req).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+async fn resumable_upload_commits_after_last_chunk() {
+    let (app, _, _container, _temp_dir) = env::setup_test_api_server().await;
+    let api_key = "test-key";
+    let tenant_id = "550e8400-e29b-41d4-a716-446655440000";
+
+    // 1. Start the upload
+    let start_req = http::authenticated_request(
+        Method::POST,
+        &format!("/v1/objects/uploads?namespace=test&tenant_id={tenant_id}&key=resumed.txt"),
+        api_key,
+    );
+    let response = app.clone().oneshot(start_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = http::extract_json_response(response).await;
+    let upload_id = body["upload_id"].as_str().unwrap().to_string();
+    let upload_uri = format!("/v1/objects/uploads/{upload_id}?tenant_id={tenant_id}");
+    let chunk = |content_range: &str, data: &'static str| {
+        let mut req = http::authenticated_request(Method::PUT, &upload_uri, api_key);
+        req.headers_mut().insert(
+            axum::http::header::CONTENT_RANGE,
+            content_range.parse().unwrap(),
+        );
+        *req.body_mut() = axum::body::Body::from(data);
+        req
+    };
+
+    // 2. First chunk
+    let response = app
+        .clone()
+        .oneshot(chunk("bytes 0-6/13", "Hello, "))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(response.headers()["upload-offset"], "7");
+
+    // 3. The offset survives between requests
+    let head_req = http::authenticated_request(Method::HEAD, &upload_uri, api_key);
+    let response = app.clone().oneshot(head_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["upload-offset"], "7");
+
+    // A chunk that skips ahead is refused
+    let response = app
+        .clone()
+        .oneshot(chunk("bytes 9-12/13", "rld!"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // 4. The last chunk commits the object
+    let response = app
+        .clone()
+        .oneshot(chunk("bytes 7-12/13", "World!"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = http::extract_json_response(response).await;
+    assert_eq!(body["size_bytes"], 13);
+
+    let download_req = http::authenticated_request(
+        Method::GET,
+        &format!("/v1/objects/by-key/test/{tenant_id}/resumed.txt"),
+        api_key,
+    );
+    let response = app.oneshot(download_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body_bytes, "Hello, World!");
+}
+
+#[tokio::test]
+async fn retained_object_cannot_be_deleted() {
+    let (app, _, _container, _temp_dir) = env::setup_test_api_server().await;
+    let api_key = "test-key";
+    let tenant_id = "550e8400-e29b-41d4-a716-446655440000";
+
+    let upload_req = http::authenticated_json_request(
+        Method::POST,
+        "/v1/objects",
+        api_key,
+        json!({
+            "namespace": "test",
+            "tenant_id": tenant_id,
+            "key": "ledger.csv",
+            "data": "2026,100"
+        }),
+    );
+    let response = app.clone().oneshot(upload_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = http::extract_json_response(response).await;
+    let object_id = body["id"].as_str().unwrap().to_string();
+    let retention_uri = format!("/v1/objects/{object_id}/retention?tenant_id={tenant_id}");
+
+    // 1. Retain the object until 2099
+    let retain_req = http::authenticated_json_request(
+        Method::PUT,
+        &retention_uri,
+        api_key,
+        json!({ "retention_until": "2099-01-01T00:00:00Z" }),
+    );
+    let response = app.clone().oneshot(retain_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = http::extract_json_response(response).await;
+    assert_eq!(body["retention_until"], "2099-01-01T00:00:00Z");
+
+    // 2. Deleting it within the retention window is forbidden
+    let delete_req = http::authenticated_request(
+        Method::DELETE,
+        &format!("/v1/objects/{object_id}?tenant_id={tenant_id}"),
+        api_key,
+    );
+    let response = app.clone().oneshot(delete_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 3. So is shortening the retention
+    let shorten_req = http::authenticated_json_request(
+        Method::PUT,
+        &retention_uri,
+        api_key,
+        json!({ "retention_until": "2098-01-01T00:00:00Z" }),
+    );
+    let response = app.clone().oneshot(shorten_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The object is still there
+    let get_req = http::authenticated_request(
+        Method::GET,
+        &format!("/v1/objects/{object_id}?tenant_id={tenant_id}"),
+        api_key,
+    );
+    let response = app.oneshot(get_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn object_metadata_record_reports_shared_content() {
+    let (app, _, _container, _temp_dir) = env::setup_test_api_server().await;
+    let api_key = "test-key";
+    let tenant_id = "550e8400-e29b-41d4-a716-446655440000";
+
+    // Two objects with the same content share one blob
+    let mut ids = Vec::new();
+    for key in ["a.txt", "b.txt"] {
+        let upload_req = Request::builder()
+            .method(Method::POST)
+            .uri(format!(
+                "/v1/objects?namespace=test&tenant_id={tenant_id}&key={key}"
+            ))
+            .header("authorization", format!("Bearer {api_key}"))
+            .body(Body::from("same content"))
+            .unwrap();
+        let response = app.clone().oneshot(upload_req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = http::extract_json_response(response).await;
+        ids.push(body["id"].as_str().unwrap().to_string());
+    }
+
+    let metadata_req = http::authenticated_request(
+        Method::GET,
+        &format!("/v1/objects/{}/metadata?tenant_id={tenant_id}", ids[0]),
+        api_key,
+    );
+    let response = app.oneshot(metadata_req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = http::extract_json_response(response).await;
+    assert_eq!(body["id"], ids[0].as_str());
+    assert_eq!(body["key"], "a.txt");
+    assert_eq!(body["dedup"]["ref_count"], 2);
+    assert_eq!(body["dedup"]["shared"], true);
+}
@@ -4,25 +4,49 @@ use tower::ServiceExt;
 
 use crate::common::{environment as env, http};
 
-#[tokio::test]
-async fn cors_preflight_returns_cors_headers() {
-    let (app, _, _container, _temp_dir) = env::setup_test_api_server().await;
-
-    let req = Request::builder()
+fn preflight_request(origin: &str) -> Request<Body> {
+    Request::builder()
         .method(Method::OPTIONS)
         .uri("/v1/objects")
-        .header("origin", "http://localhost:3000")
+        .header("origin", origin)
         .header("access-control-request-method", "POST")
         .body(Body::empty())
-        .unwrap();
+        .unwrap()
+}
 
-    let response = app.clone().oneshot(req).await.unwrap();
+#[tokio::test]
+async fn cors_preflight_returns_cors_headers() {
+    let (app, _, _container, _temp_dir) = env::setup_test_api_server_with_config(|config| {
+        config.allowed_origins = "https://app.example.com,http://localhost:3000".to_string();
+        config.cors_allow_credentials = true;
+    })
+    .await;
+
+    // Allowlisted origins are echoed back, never replaced by a wildcard
+    let response = app
+        .clone()
+        .oneshot(preflight_request("https://app.example.com"))
+        .await
+        .unwrap();
 
     let headers = response.headers();
-    assert!(
-        headers.contains_key("access-control-allow-origin")
-            || headers.contains_key("access-control-allow-headers")
+    assert_eq!(
+        headers["access-control-allow-origin"],
+        "https://app.example.com"
     );
+    assert_eq!(headers["access-control-allow-credentials"], "true");
+    assert!(headers.contains_key("access-control-allow-methods"));
+
+    // Other origins get no CORS headers, so browsers block the response
+    let response = app
+        .clone()
+        .oneshot(preflight_request("https://evil.example.com"))
+        .await
+        .unwrap();
+
+    assert!(!response
+        .headers()
+        .contains_key("access-control-allow-origin"));
 }
 
 #[tokio::test]
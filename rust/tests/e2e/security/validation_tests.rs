@@ -131,3 +131,29 @@ async fn input_sanitization_handles_malicious_input() {
     let response = app.clone().oneshot(req).await.unwrap();
     assert!(response.status().is_client_error() || response.status().is_success());
 }
+
+#[tokio::test]
+async fn sanitization_level_decides_which_query_values_are_refused() {
+    let uri = "/v1/objects?namespace=test&tenant_id=550e8400-e29b-41d4-a716-446655440000&prefix=docs/2024/";
+    let list = || {
+        Request::builder()
+            .uri(uri)
+            .header("authorization", "Bearer test-key")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let (strict, _, _container, _temp_dir) = env::setup_test_api_server_with_config(|config| {
+        config.sanitization_level = "strict".to_string();
+    })
+    .await;
+    let response = strict.oneshot(list()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let (lenient, _, _container, _temp_dir) = env::setup_test_api_server_with_config(|config| {
+        config.sanitization_level = "lenient".to_string();
+    })
+    .await;
+    let response = lenient.oneshot(list()).await.unwrap();
+    assert_ne!(response.status(), StatusCode::BAD_REQUEST);
+}
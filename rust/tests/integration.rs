@@ -5,6 +5,12 @@
 // Import common test utilities
 mod common;
 
+#[path = "integration/use_cases/extracted_text_search.rs"]
+mod extracted_text_search;
+#[path = "integration/use_cases/metadata_text_search.rs"]
+mod metadata_text_search;
+#[path = "integration/use_cases/migrate_tool.rs"]
+mod migrate_tool;
 #[path = "integration/use_cases/multi_object_operations.rs"]
 mod multi_object_operations;
 #[path = "integration/use_cases/namespace_validation.rs"]
@@ -0,0 +1,172 @@
+//! Text search over text extracted from uploaded content
+
+use crate::common::environment as env;
+use std::sync::Arc;
+
+use just_storage::application::{
+    dto::{TextSearchRequest, UploadRequest},
+    use_cases::{TextSearchObjectsUseCase, UploadObjectUseCase},
+};
+use just_storage::domain::value_objects::StorageClass;
+use just_storage::infrastructure::extraction::PlainTextExtractor;
+use uuid::Uuid;
+
+fn upload_request(tenant_id: &str, key: &str, content_type: &str) -> UploadRequest {
+    UploadRequest {
+        namespace: "documents".to_string(),
+        tenant_id: tenant_id.to_string(),
+        key: Some(key.to_string()),
+        storage_class: Some(StorageClass::Hot),
+        content_type: Some(content_type.to_string()),
+        content_encoding: None,
+        expected_hash: None,
+        idempotency_key: None,
+    }
+}
+
+fn search_request(tenant_id: &str, query: &str) -> TextSearchRequest {
+    TextSearchRequest {
+        namespace: "documents".to_string(),
+        tenant_id: tenant_id.to_string(),
+        limit: Some(10),
+        offset: Some(0),
+        query: query.to_string(),
+        search_in_metadata: Some(false),
+        search_in_key: Some(false),
+        search_in_custom_metadata: Some(false),
+        search_in_content: Some(true),
+        prefix: None,
+        min_rank: None,
+    }
+}
+
+#[tokio::test]
+async fn test_text_upload_is_searchable_by_content() {
+    let common_env = env::TestEnvironment::builder()
+        .with_database(true)
+        .build()
+        .await;
+
+    let upload_use_case = UploadObjectUseCase::new(
+        Arc::clone(&common_env.object_repo),
+        Arc::clone(&common_env.blob_repo),
+        Arc::clone(&common_env.blob_store),
+    )
+    .with_text_extractor(Arc::new(PlainTextExtractor), 1024 * 1024);
+    let search_use_case = TextSearchObjectsUseCase::new(Arc::clone(&common_env.object_repo));
+
+    let tenant_id = Uuid::new_v4().to_string();
+    let object = upload_use_case
+        .execute(
+            upload_request(&tenant_id, "policy.txt", "text/plain; charset=utf-8"),
+            Box::pin(std::io::Cursor::new(
+                b"Refunds are issued within thirty days",
+            )),
+        )
+        .await
+        .expect("Upload failed");
+
+    let response = search_use_case
+        .execute(search_request(&tenant_id, "thirty"))
+        .await
+        .expect("Search failed");
+
+    assert_eq!(response.objects.len(), 1);
+    assert_eq!(response.objects[0].object.id, object.id);
+}
+
+#[tokio::test]
+async fn test_content_matches_are_ranked_and_highlighted() {
+    let common_env = env::TestEnvironment::builder()
+        .with_database(true)
+        .build()
+        .await;
+
+    let upload_use_case = UploadObjectUseCase::new(
+        Arc::clone(&common_env.object_repo),
+        Arc::clone(&common_env.blob_repo),
+        Arc::clone(&common_env.blob_store),
+    )
+    .with_text_extractor(Arc::new(PlainTextExtractor), 1024 * 1024);
+    let search_use_case = TextSearchObjectsUseCase::new(Arc::clone(&common_env.object_repo));
+
+    let tenant_id = Uuid::new_v4().to_string();
+    upload_use_case
+        .execute(
+            upload_request(&tenant_id, "shipping.txt", "text/plain"),
+            Box::pin(std::io::Cursor::new(
+                b"Shipping takes a week; see the refund page",
+            )),
+        )
+        .await
+        .expect("Upload failed");
+    let refunds = upload_use_case
+        .execute(
+            upload_request(&tenant_id, "refunds.txt", "text/plain"),
+            Box::pin(std::io::Cursor::new(
+                b"Refund requests: a refund is issued once the refund is approved",
+            )),
+        )
+        .await
+        .expect("Upload failed");
+
+    let mut request = search_request(&tenant_id, "refund");
+    request.limit = Some(1);
+    let response = search_use_case
+        .execute(request)
+        .await
+        .expect("Search failed");
+
+    assert_eq!(response.total, 2);
+    assert_eq!(response.objects.len(), 1);
+    assert_eq!(response.objects[0].object.id, refunds.id);
+    assert!(response.objects[0].rank > 0.0);
+    assert!(response.objects[0]
+        .highlight
+        .as_deref()
+        .is_some_and(|h| h.contains("<mark>refund</mark>")));
+
+    // Prefix matching and the rank threshold
+    let mut request = search_request(&tenant_id, "refu");
+    request.prefix = Some(true);
+    let response = search_use_case.execute(request).await.unwrap();
+    assert_eq!(response.total, 2);
+
+    let mut request = search_request(&tenant_id, "refund");
+    request.min_rank = Some(10.0);
+    let response = search_use_case.execute(request).await.unwrap();
+    assert_eq!(response.total, 0);
+}
+
+#[tokio::test]
+async fn test_binary_upload_skips_extraction() {
+    let common_env = env::TestEnvironment::builder()
+        .with_database(true)
+        .build()
+        .await;
+
+    let upload_use_case = UploadObjectUseCase::new(
+        Arc::clone(&common_env.object_repo),
+        Arc::clone(&common_env.blob_repo),
+        Arc::clone(&common_env.blob_store),
+    )
+    .with_text_extractor(Arc::new(PlainTextExtractor), 1024 * 1024);
+    let search_use_case = TextSearchObjectsUseCase::new(Arc::clone(&common_env.object_repo));
+
+    // Bytes that happen to spell a word must not be indexed for a binary type
+    let tenant_id = Uuid::new_v4().to_string();
+    upload_use_case
+        .execute(
+            upload_request(&tenant_id, "image.bin", "application/octet-stream"),
+            Box::pin(std::io::Cursor::new(b"\x00\x01 payload \xff")),
+        )
+        .await
+        .expect("Binary upload should succeed without extraction");
+
+    let response = search_use_case
+        .execute(search_request(&tenant_id, "payload"))
+        .await
+        .expect("Search failed");
+
+    assert!(response.objects.is_empty());
+}
@@ -0,0 +1,108 @@
+//! Text search over designated custom metadata fields
+
+use crate::common::environment as env;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use just_storage::application::{
+    dto::TextSearchRequest, metadata_index::MetadataIndexConfig, ports::ObjectRepository,
+    use_cases::TextSearchObjectsUseCase,
+};
+use just_storage::domain::entities::Object;
+use just_storage::domain::value_objects::{ContentHash, Namespace, StorageClass, TenantId};
+use just_storage::infrastructure::persistence::PostgresObjectRepository;
+use uuid::Uuid;
+
+fn committed_object_with_author(tenant_id: &TenantId, author: &str) -> Object {
+    let mut object = Object::new(
+        Namespace::from_str("models").unwrap(),
+        tenant_id.clone(),
+        Some("weights.bin".to_string()),
+        StorageClass::Hot,
+    );
+    object
+        .metadata_mut()
+        .tags
+        .insert("author".to_string(), serde_json::json!(author));
+    object
+        .commit(&ContentHash::from_str(&"b".repeat(64)).unwrap(), 42)
+        .unwrap();
+    object
+}
+
+fn search_request(tenant_id: &TenantId, query: &str) -> TextSearchRequest {
+    TextSearchRequest {
+        namespace: "models".to_string(),
+        tenant_id: tenant_id.to_string(),
+        limit: Some(10),
+        offset: Some(0),
+        query: query.to_string(),
+        search_in_metadata: Some(true),
+        search_in_key: Some(true),
+        search_in_custom_metadata: Some(true),
+        search_in_content: Some(true),
+        prefix: None,
+        min_rank: None,
+    }
+}
+
+#[tokio::test]
+async fn test_text_search_matches_indexed_custom_metadata() {
+    let common_env = env::TestEnvironment::builder()
+        .with_database(true)
+        .build()
+        .await;
+
+    let object_repo: Arc<dyn ObjectRepository> =
+        Arc::new(PostgresObjectRepository::with_metadata_index(
+            common_env.pool.clone(),
+            MetadataIndexConfig::default().with_keys("models", &["author"]),
+        ));
+    let use_case = TextSearchObjectsUseCase::new(Arc::clone(&object_repo));
+
+    let tenant_id = TenantId::new(Uuid::new_v4());
+    let object = committed_object_with_author(&tenant_id, "zephyrine");
+    object_repo.save(&object).await.expect("Save failed");
+
+    let response = use_case
+        .execute(search_request(&tenant_id, "zephyrine"))
+        .await
+        .expect("Search failed");
+
+    assert_eq!(response.objects.len(), 1);
+    assert_eq!(response.objects[0].object.id, object.id().to_string());
+}
+
+#[tokio::test]
+async fn test_text_search_ignores_unindexed_custom_metadata() {
+    let common_env = env::TestEnvironment::builder()
+        .with_database(true)
+        .build()
+        .await;
+
+    // No keys designated for the namespace
+    let object_repo: Arc<dyn ObjectRepository> =
+        Arc::new(PostgresObjectRepository::new(common_env.pool.clone()));
+    let use_case = TextSearchObjectsUseCase::new(Arc::clone(&object_repo));
+
+    let tenant_id = TenantId::new(Uuid::new_v4());
+    let object = committed_object_with_author(&tenant_id, "zephyrine");
+    object_repo.save(&object).await.expect("Save failed");
+
+    // Not in the search vector...
+    let response = use_case
+        .execute(TextSearchRequest {
+            search_in_metadata: Some(false),
+            ..search_request(&tenant_id, "zephyrine")
+        })
+        .await
+        .expect("Search failed");
+    assert!(response.objects.is_empty());
+
+    // ...but still found by plain metadata search
+    let response = use_case
+        .execute(search_request(&tenant_id, "zephyrine"))
+        .await
+        .expect("Search failed");
+    assert_eq!(response.objects.len(), 1);
+}
@@ -46,6 +46,10 @@ async fn test_multiple_objects_same_namespace() {
             tenant_id: tenant_id.to_string(),
             key: Some(filename.to_string()),
             storage_class: Some(StorageClass::Hot),
+            content_type: None,
+            content_encoding: None,
+            expected_hash: None,
+            idempotency_key: None,
         };
 
         let test_data = format!("Content of {}", filename).into_bytes();
@@ -44,6 +44,10 @@ async fn test_namespace_validation_with_testcontainers() {
         tenant_id: tenant_id.to_string(),
         key: Some("validation_test".to_string()),
         storage_class: Some(StorageClass::Cold),
+        content_type: None,
+        content_encoding: None,
+        expected_hash: None,
+        idempotency_key: None,
     };
 
     let test_data = b"Validation test data";
@@ -44,6 +44,10 @@ async fn test_full_object_lifecycle_with_testcontainers() {
         tenant_id: Uuid::new_v4().to_string(),
         key: Some("test_key_containers".to_string()),
         storage_class: Some(StorageClass::Hot),
+        content_type: None,
+        content_encoding: None,
+        expected_hash: None,
+        idempotency_key: None,
     };
 
     // Test upload
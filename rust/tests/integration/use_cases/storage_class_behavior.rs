@@ -42,6 +42,10 @@ async fn test_storage_class_behavior() {
         tenant_id: Uuid::new_v4().to_string(),
         key: Some("storage_class_file".to_string()),
         storage_class: Some(StorageClass::Cold), // Test cold storage
+        content_type: None,
+        content_encoding: None,
+        expected_hash: None,
+        idempotency_key: None,
     };
 
     let object = upload_use_case
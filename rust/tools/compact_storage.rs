@@ -0,0 +1,117 @@
+//! Find files in blob storage the database has no record of, and optionally
+//! remove them.
+//!
+//! Reads DATABASE_URL, HOT_STORAGE_ROOT, COLD_STORAGE_ROOT, BLOB_BACKENDS,
+//! BLOB_TEMP_DIR, the shard layout and GC_STUCK_UPLOAD_AGE_HOURS (the grace
+//! window) like the server does.
+//! Files modified within the grace window are never touched, so the server
+//! can stay up.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::Parser;
+use just_storage::application::blob_routing::DEFAULT_BLOB_BACKEND;
+use just_storage::application::use_cases::CompactionUseCase;
+use just_storage::config::Config;
+use just_storage::infrastructure::persistence::PostgresBlobRepository;
+use just_storage::infrastructure::storage::{LocalFilesystemStore, RoutingBlobStore, ShardLayout};
+use sqlx::postgres::PgPoolOptions;
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    database_url: Option<String>,
+
+    /// Remove unreferenced files (default: report only).
+    #[arg(long)]
+    remove: bool,
+
+    /// Override GC_STUCK_UPLOAD_AGE_HOURS.
+    #[arg(long)]
+    grace_hours: Option<i64>,
+
+    /// Maximum files listed in the report.
+    #[arg(long, default_value_t = 10_000)]
+    sample_limit: usize,
+
+    /// Write the JSON report to this file.
+    #[arg(long)]
+    report: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = Config::from_env();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(cli.database_url.as_deref().unwrap_or(&config.database_url))
+        .await?;
+
+    // Every backend the server routes blobs to, so none of their files is
+    // mistaken for an orphan or left out
+    let roots = config
+        .blob_backend_roots()
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let names: Vec<&str> = roots.iter().map(|r| r.name.as_str()).collect();
+    let routes = config
+        .blob_routing(&names)
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let store = RoutingBlobStore::local(&roots, routes, |roots| {
+        let store = LocalFilesystemStore::new(roots.hot_root.clone(), roots.cold_root.clone())
+            .with_shard_layout(ShardLayout::new(
+                config.storage_shard_depth,
+                config.storage_shard_width,
+            ));
+        // Extra backends stage under their own roots, like in the server
+        match &config.blob_temp_dir {
+            Some(temp_dir) if roots.name == DEFAULT_BLOB_BACKEND => {
+                store.with_temp_dir(temp_dir.clone())
+            }
+            _ => store,
+        }
+    })
+    .map_err(|e| anyhow::anyhow!(e))?;
+    let use_case = CompactionUseCase::new(
+        Arc::new(store),
+        Arc::new(PostgresBlobRepository::new(pool)),
+        cli.grace_hours.unwrap_or(config.gc_stuck_upload_age_hours),
+    )
+    .with_sample_limit(cli.sample_limit);
+
+    let dry_run = !cli.remove;
+    println!(
+        "Scanning blob storage for unreferenced files ({})",
+        if dry_run { "report only" } else { "removing" }
+    );
+
+    let report = use_case.execute(dry_run).await?;
+
+    for candidate in &report.sample {
+        println!(
+            "{} {} bytes{}",
+            candidate.path,
+            candidate.size_bytes,
+            if candidate.removed { " (removed)" } else { "" }
+        );
+    }
+    println!(
+        "Scanned {} files: {} orphaned blobs, {} stale temp files, {} removed, {} bytes {}, {} errors",
+        report.scanned,
+        report.orphaned_blobs,
+        report.stale_temp_files,
+        report.removed,
+        report.bytes_reclaimed,
+        if dry_run { "reclaimable" } else { "reclaimed" },
+        report.errors
+    );
+
+    if let Some(path) = cli.report {
+        std::fs::write(&path, serde_json::to_vec_pretty(&report)?)?;
+        println!("Report written to {}", path.display());
+    }
+
+    Ok(())
+}
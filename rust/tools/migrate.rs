@@ -0,0 +1,528 @@
+//! Export a tenant's objects to a directory tree and import them back, for
+//! backups and moves between environments.
+//!
+//! Reads DATABASE_URL, HOT_STORAGE_ROOT, COLD_STORAGE_ROOT, BLOB_BACKENDS,
+//! BLOB_ROUTES and the shard layout like the server does, and talks to the
+//! database and blob stores directly; the server can stay up.
+//!
+//! Layout of an export directory:
+//!
+//! - `objects/<namespace>/<key>`: content of objects with a key, the key's
+//!   `/`-separated segments as directories
+//! - `by-id/<namespace>/<id>`: content of objects without a key, or whose
+//!   key is not a safe relative path
+//! - `<content file>.meta.json`: sidecar with the key, content type, content
+//!   encoding, storage class, hash and metadata
+//! - `manifest.jsonl`: objects exported completely, one per line
+//! - `import_manifest.jsonl`: exported objects imported completely
+//!
+//! Content is exported as stored: objects uploaded with a `Content-Encoding`
+//! stay compressed, and are imported with the same encoding.
+//!
+//! Both directions stream content and skip objects already in their manifest,
+//! so an interrupted run picks up where it stopped.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use just_storage::application::blob_routing::DEFAULT_BLOB_BACKEND;
+use just_storage::application::dto::{SortDirection, SortField, UploadPrecondition, UploadRequest};
+use just_storage::application::errors::ObjectUseCaseError;
+use just_storage::application::key_prefix_query::KeyPrefixQuery;
+use just_storage::application::list_cursor::ListCursor;
+use just_storage::application::metadata_query::MetadataQuery;
+use just_storage::application::ports::{BlobStore, ObjectRepository, TextExtractor};
+use just_storage::application::use_cases::UploadObjectUseCase;
+use just_storage::config::Config;
+use just_storage::domain::entities::Object;
+use just_storage::domain::value_objects::{
+    ContentEncoding, ContentHash, Namespace, ObjectId, ObjectMetadata, StorageClass, TenantId,
+};
+use just_storage::infrastructure::extraction::{NoopTextExtractor, PlainTextExtractor};
+use just_storage::infrastructure::persistence::{PostgresBlobRepository, PostgresObjectRepository};
+use just_storage::infrastructure::storage::{
+    FsyncPolicy, LocalFilesystemStore, RoutingBlobStore, ShardLayout,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPoolOptions;
+use tokio::io::AsyncWriteExt;
+
+const MANIFEST: &str = "manifest.jsonl";
+const IMPORT_MANIFEST: &str = "import_manifest.jsonl";
+const SIDECAR_SUFFIX: &str = ".meta.json";
+const PAGE_SIZE: i64 = 500;
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    database_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write every committed object of a tenant to a directory.
+    Export {
+        #[arg(long)]
+        tenant_id: String,
+
+        /// Only export these namespaces (default: all of the tenant's).
+        #[arg(long)]
+        namespace: Vec<String>,
+
+        /// Export directory; an existing export there is resumed.
+        #[arg(long)]
+        dir: PathBuf,
+    },
+    /// Upload the objects of an export directory.
+    Import {
+        /// Export directory.
+        #[arg(long)]
+        dir: PathBuf,
+
+        /// Import into this tenant instead of the exported one.
+        #[arg(long)]
+        tenant_id: Option<String>,
+    },
+}
+
+/// Sidecar describing one exported object
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportedObject {
+    id: String,
+    namespace: String,
+    tenant_id: String,
+    key: Option<String>,
+    storage_class: StorageClass,
+    content_type: Option<String>,
+    /// Absent in exports made before encodings were recorded
+    #[serde(default)]
+    content_encoding: Option<ContentEncoding>,
+    content_hash: ContentHash,
+    size_bytes: u64,
+    metadata: ObjectMetadata,
+    created_at: String,
+}
+
+/// Manifest line of a completed object
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    id: String,
+    path: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = Config::from_env();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(cli.database_url.as_deref().unwrap_or(&config.database_url))
+        .await?;
+    let object_repo = Arc::new(PostgresObjectRepository::new(pool.clone()));
+    // Blobs are read from, and imported into, the backends the server
+    // routes them to
+    let roots = config
+        .blob_backend_roots()
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let names: Vec<&str> = roots.iter().map(|r| r.name.as_str()).collect();
+    let routes = config
+        .blob_routing(&names)
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let blob_store = Arc::new(
+        RoutingBlobStore::local(&roots, routes, |roots| {
+            let store = LocalFilesystemStore::new(roots.hot_root.clone(), roots.cold_root.clone())
+                .with_fsync_policy(FsyncPolicy::parse(&config.blob_fsync).unwrap_or_default())
+                .with_shard_layout(ShardLayout::new(
+                    config.storage_shard_depth,
+                    config.storage_shard_width,
+                ));
+            match &config.blob_temp_dir {
+                Some(temp_dir) if roots.name == DEFAULT_BLOB_BACKEND => {
+                    store.with_temp_dir(temp_dir.clone())
+                }
+                _ => store,
+            }
+        })
+        .map_err(|e| anyhow::anyhow!(e))?,
+    );
+
+    match cli.command {
+        Command::Export {
+            tenant_id,
+            namespace,
+            dir,
+        } => {
+            let tenant_id = TenantId::from_str(&tenant_id).context("Invalid tenant id")?;
+            export(
+                object_repo.as_ref(),
+                blob_store.as_ref(),
+                &tenant_id,
+                namespace,
+                &dir,
+            )
+            .await
+        }
+        Command::Import { dir, tenant_id } => {
+            let text_extractor: Arc<dyn TextExtractor> = match config.text_extractor.as_str() {
+                "plain_text" => Arc::new(PlainTextExtractor),
+                _ => Arc::new(NoopTextExtractor),
+            };
+            let upload = UploadObjectUseCase::with_max_upload_size_bytes(
+                Arc::clone(&object_repo) as Arc<dyn ObjectRepository>,
+                Arc::new(PostgresBlobRepository::new(pool)),
+                Arc::clone(&blob_store) as Arc<dyn BlobStore>,
+                config.max_upload_size_bytes,
+            )
+            .with_max_object_size_bytes(config.max_object_size_bytes)
+            .with_text_extractor(text_extractor, config.text_extraction_max_bytes)
+            .with_blob_router(blob_store);
+            import(object_repo.as_ref(), &upload, &dir, tenant_id.as_deref()).await
+        }
+    }
+}
+
+async fn export(
+    object_repo: &dyn ObjectRepository,
+    blob_store: &dyn BlobStore,
+    tenant_id: &TenantId,
+    namespaces: Vec<String>,
+    dir: &Path,
+) -> anyhow::Result<()> {
+    let namespaces = if namespaces.is_empty() {
+        object_repo.namespaces(tenant_id).await?
+    } else {
+        namespaces
+            .iter()
+            .map(|name| {
+                name.parse::<Namespace>()
+                    .with_context(|| format!("Invalid namespace '{name}'"))
+            })
+            .collect::<anyhow::Result<_>>()?
+    };
+
+    tokio::fs::create_dir_all(dir).await?;
+    let manifest_path = dir.join(MANIFEST);
+    let done = read_manifest(&manifest_path).await?;
+    let mut manifest = open_manifest(&manifest_path).await?;
+    let (mut exported, mut skipped) = (0u64, 0u64);
+
+    for namespace in &namespaces {
+        let mut after = None;
+        loop {
+            // Oldest first, so objects created during the export land on later
+            // pages; the cursor keeps deletions from shifting objects past us
+            let page = object_repo
+                .list(
+                    namespace,
+                    tenant_id,
+                    &KeyPrefixQuery::default(),
+                    &MetadataQuery::default(),
+                    SortField::CreatedAt,
+                    SortDirection::Asc,
+                    after.take(),
+                    PAGE_SIZE,
+                    0,
+                )
+                .await?;
+            after = page
+                .last()
+                .map(|last| ListCursor::after(last, SortField::CreatedAt, SortDirection::Asc));
+
+            for object in &page {
+                if done.contains(&object.id().to_string()) {
+                    skipped += 1;
+                    continue;
+                }
+                let path = export_object(blob_store, object, dir)
+                    .await
+                    .with_context(|| format!("Exporting object {}", object.id()))?;
+                append_manifest(&mut manifest, &object.id().to_string(), &path).await?;
+                exported += 1;
+            }
+
+            if (page.len() as i64) < PAGE_SIZE {
+                break;
+            }
+        }
+        println!("Exported namespace {namespace}");
+    }
+
+    println!(
+        "Exported {exported} objects to {} ({skipped} already exported)",
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Stream one object's content and write its sidecar; returns the content
+/// path relative to `dir`
+async fn export_object(
+    blob_store: &dyn BlobStore,
+    object: &Object,
+    dir: &Path,
+) -> anyhow::Result<PathBuf> {
+    let content_hash = object
+        .content_hash()
+        .context("Committed object has no content hash")?;
+    let size_bytes = object.size_bytes().unwrap_or_default();
+
+    let by_id = Path::new("by-id")
+        .join(object.namespace().as_str())
+        .join(object.id().to_string());
+    let relative = match object.key().and_then(key_path) {
+        // Keys clashing with another key's file or directory (`a` and
+        // `a/b`) fall back to their ID
+        Some(key_in_namespace) => {
+            let keyed = Path::new("objects")
+                .join(object.namespace().as_str())
+                .join(key_in_namespace);
+            if tokio::fs::create_dir_all(dir.join(&keyed).parent().unwrap_or(dir))
+                .await
+                .is_ok()
+                && !dir.join(&keyed).is_dir()
+            {
+                keyed
+            } else {
+                by_id
+            }
+        }
+        None => by_id,
+    };
+    let path = dir.join(&relative);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    // Write under a temporary name so a partial file is never mistaken for content
+    let partial = path.with_file_name(format!(
+        "{}.partial",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    let mut reader = blob_store
+        .read(content_hash, object.storage_class())
+        .await?;
+    let mut file = tokio::fs::File::create(&partial).await?;
+    let written = tokio::io::copy(&mut reader, &mut file).await?;
+    file.sync_all().await?;
+    if written != size_bytes {
+        tokio::fs::remove_file(&partial).await?;
+        anyhow::bail!("Read {written} bytes, expected {size_bytes}");
+    }
+    tokio::fs::rename(&partial, &path).await?;
+
+    let sidecar = ExportedObject {
+        id: object.id().to_string(),
+        namespace: object.namespace().to_string(),
+        tenant_id: object.tenant_id().to_string(),
+        key: object.key().map(str::to_string),
+        storage_class: object.storage_class(),
+        content_type: object.content_type().map(str::to_string),
+        content_encoding: object.content_encoding(),
+        content_hash: content_hash.clone(),
+        size_bytes,
+        metadata: object.metadata().clone(),
+        created_at: object.created_at().to_string(),
+    };
+    tokio::fs::write(sidecar_path(&path), serde_json::to_vec_pretty(&sidecar)?).await?;
+
+    Ok(relative)
+}
+
+async fn import(
+    object_repo: &dyn ObjectRepository,
+    upload: &UploadObjectUseCase,
+    dir: &Path,
+    tenant_id: Option<&str>,
+) -> anyhow::Result<()> {
+    let manifest_path = dir.join(IMPORT_MANIFEST);
+    let done = read_manifest(&manifest_path).await?;
+    let mut manifest = open_manifest(&manifest_path).await?;
+    let (mut imported, mut existing, mut skipped) = (0u64, 0u64, 0u64);
+
+    for sidecar_file in find_sidecars(dir)? {
+        let sidecar: ExportedObject =
+            serde_json::from_slice(&tokio::fs::read(&sidecar_file).await?)
+                .with_context(|| format!("Reading {}", sidecar_file.display()))?;
+        if done.contains(&sidecar.id) {
+            skipped += 1;
+            continue;
+        }
+        let content_path = content_path(&sidecar_file);
+
+        let created = import_object(object_repo, upload, &sidecar, &content_path, tenant_id)
+            .await
+            .with_context(|| format!("Importing {}", content_path.display()))?;
+        if created {
+            imported += 1;
+        } else {
+            existing += 1;
+        }
+        let relative = content_path.strip_prefix(dir).unwrap_or(&content_path);
+        append_manifest(&mut manifest, &sidecar.id, relative).await?;
+    }
+
+    println!(
+        "Imported {imported} objects from {} ({existing} keys already present, {skipped} already imported)",
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Upload one exported object and restore its metadata
+///
+/// Returns false when an object already exists under its key.
+async fn import_object(
+    object_repo: &dyn ObjectRepository,
+    upload: &UploadObjectUseCase,
+    sidecar: &ExportedObject,
+    content_path: &Path,
+    tenant_id: Option<&str>,
+) -> anyhow::Result<bool> {
+    let request = UploadRequest {
+        namespace: sidecar.namespace.clone(),
+        tenant_id: tenant_id.unwrap_or(&sidecar.tenant_id).to_string(),
+        key: sidecar.key.clone(),
+        storage_class: Some(sidecar.storage_class),
+        content_type: sidecar.content_type.clone(),
+        content_encoding: sidecar.content_encoding,
+        // Verifies the content, and reuses a blob the tenant already stores
+        expected_hash: Some(sidecar.content_hash.clone()),
+        idempotency_key: None,
+    };
+    // Keyed objects are only created, so a rerun never overwrites newer content
+    let precondition = match sidecar.key {
+        Some(_) => UploadPrecondition::IfNoneMatch,
+        None => UploadPrecondition::None,
+    };
+    let reader = Box::pin(tokio::fs::File::open(content_path).await?);
+
+    let dto = match upload
+        .execute_with_precondition(request, reader, precondition)
+        .await
+    {
+        Ok(dto) => dto,
+        Err(ObjectUseCaseError::PreconditionFailed(_)) => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+
+    let id = ObjectId::from_str(&dto.id)?;
+    let mut object = object_repo
+        .find_by_id(&id)
+        .await?
+        .context("Imported object disappeared")?;
+    let expected = object.metadata().clone();
+    object.set_metadata(sidecar.metadata.clone());
+    if !object_repo
+        .update_metadata_if_match(&object, &expected)
+        .await?
+    {
+        anyhow::bail!("Metadata of {id} changed during import");
+    }
+    Ok(true)
+}
+
+/// Relative path for a key, or `None` when the key could escape its
+/// namespace directory or collide with a sidecar
+fn key_path(key: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(key);
+    let safe = !key.is_empty()
+        && !key.ends_with(SIDECAR_SUFFIX)
+        && !key.ends_with(".partial")
+        && !key.contains(['\\', '\0'])
+        && key.split('/').all(|segment| !segment.is_empty())
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    safe.then_some(path)
+}
+
+fn sidecar_path(content_path: &Path) -> PathBuf {
+    let mut name = content_path.as_os_str().to_owned();
+    name.push(SIDECAR_SUFFIX);
+    PathBuf::from(name)
+}
+
+fn content_path(sidecar_path: &Path) -> PathBuf {
+    let name = sidecar_path.to_string_lossy();
+    PathBuf::from(name.strip_suffix(SIDECAR_SUFFIX).unwrap_or(&name))
+}
+
+/// Sidecar files under `dir`, in path order
+fn find_sidecars(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut sidecars = Vec::new();
+    let mut pending = vec![dir.join("objects"), dir.join("by-id")];
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+            } else if path.to_string_lossy().ends_with(SIDECAR_SUFFIX) {
+                sidecars.push(path);
+            }
+        }
+    }
+    sidecars.sort();
+    Ok(sidecars)
+}
+
+/// IDs already recorded in a manifest; a torn last line is ignored
+async fn read_manifest(path: &Path) -> anyhow::Result<HashSet<String>> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ManifestEntry>(line).ok())
+        .map(|entry| entry.id)
+        .collect())
+}
+
+async fn open_manifest(path: &Path) -> anyhow::Result<tokio::fs::File> {
+    let torn = match tokio::fs::read(path).await {
+        Ok(contents) => contents.last().is_some_and(|byte| *byte != b'\n'),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+        Err(e) => return Err(e.into()),
+    };
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    // Start on a fresh line after a torn write
+    if torn {
+        file.write_all(b"\n").await?;
+    }
+    Ok(file)
+}
+
+async fn append_manifest(
+    manifest: &mut tokio::fs::File,
+    id: &str,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let entry = ManifestEntry {
+        id: id.to_string(),
+        path: path.to_path_buf(),
+    };
+    let mut line = serde_json::to_vec(&entry)?;
+    line.push(b'\n');
+    manifest.write_all(&line).await?;
+    manifest.sync_data().await?;
+    Ok(())
+}
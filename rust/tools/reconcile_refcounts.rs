@@ -0,0 +1,110 @@
+//! Verify blob reference counts against committed objects, and optionally
+//! repair them.
+//!
+//! Reads DATABASE_URL, RECONCILE_BATCH_SIZE and RECONCILE_PARALLELISM like the
+//! server does, and refuses to run when METADATA_STORE is not postgres. Runs are resumable: an interrupted run continues from its
+//! checkpoint, shared with POST /internal/actions/refcounts/reconcile, so do
+//! not run both at once. Fixes only apply where the stored count is unchanged
+//! since the scan, so the server can stay up.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::Parser;
+use just_storage::application::use_cases::ReconcileRefcountsUseCase;
+use just_storage::config::Config;
+use just_storage::infrastructure::persistence::PostgresRefcountRepository;
+use sqlx::postgres::PgPoolOptions;
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    database_url: Option<String>,
+
+    /// Correct mismatched counts (default: report only).
+    #[arg(long)]
+    fix: bool,
+
+    /// Override RECONCILE_BATCH_SIZE.
+    #[arg(long)]
+    batch_size: Option<i64>,
+
+    /// Override RECONCILE_PARALLELISM.
+    #[arg(long)]
+    parallelism: Option<usize>,
+
+    /// Stop after this many batches; the next run resumes from the checkpoint.
+    #[arg(long)]
+    max_batches: Option<u64>,
+
+    /// Maximum discrepancies listed in the report.
+    #[arg(long, default_value_t = 10_000)]
+    sample_limit: usize,
+
+    /// Write the JSON report to this file.
+    #[arg(long)]
+    report: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = Config::from_env();
+    if config.metadata_store != "postgres" {
+        anyhow::bail!(
+            "Objects are in {}; counting them in Postgres would zero live blobs' counts",
+            config.metadata_store
+        );
+    }
+
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(cli.database_url.as_deref().unwrap_or(&config.database_url))
+        .await?;
+
+    let mut use_case =
+        ReconcileRefcountsUseCase::new(Arc::new(PostgresRefcountRepository::new(pool)))
+            .with_batch_size(cli.batch_size.unwrap_or(config.reconcile_batch_size))
+            .with_parallelism(cli.parallelism.unwrap_or(config.reconcile_parallelism))
+            .with_sample_limit(cli.sample_limit);
+    if let Some(max_batches) = cli.max_batches {
+        use_case = use_case.with_max_batches(max_batches);
+    }
+
+    let dry_run = !cli.fix;
+    println!(
+        "Verifying blob reference counts ({})",
+        if dry_run { "report only" } else { "fixing" }
+    );
+
+    let report = use_case.execute(dry_run).await?;
+
+    for discrepancy in &report.sample {
+        println!(
+            "{} stored={} actual={}{}",
+            discrepancy.content_hash,
+            discrepancy.stored_ref_count,
+            discrepancy.actual_ref_count,
+            if discrepancy.fixed { " (fixed)" } else { "" }
+        );
+    }
+    println!(
+        "Scanned {} blobs in {} batches: {} mismatched, {} fixed{}",
+        report.scanned,
+        report.batches,
+        report.discrepancies,
+        report.fixed,
+        if report.completed {
+            ""
+        } else {
+            " (stopped early; rerun to continue)"
+        }
+    );
+
+    if let Some(path) = cli.report {
+        std::fs::write(&path, serde_json::to_vec_pretty(&report)?)?;
+        println!("Report written to {}", path.display());
+    }
+
+    Ok(())
+}
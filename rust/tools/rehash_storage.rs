@@ -0,0 +1,57 @@
+//! One-shot migration of stored blobs into the configured shard layout.
+//!
+//! Reads HOT_STORAGE_ROOT, COLD_STORAGE_ROOT, BLOB_BACKENDS,
+//! STORAGE_SHARD_DEPTH and STORAGE_SHARD_WIDTH like the server does, and
+//! rehashes every backend. Stop the server first.
+
+use clap::Parser;
+use just_storage::config::Config;
+use just_storage::infrastructure::storage::{LocalFilesystemStore, ShardLayout};
+
+#[derive(Parser)]
+struct Cli {
+    /// Override STORAGE_SHARD_DEPTH.
+    #[arg(long)]
+    depth: Option<usize>,
+
+    /// Override STORAGE_SHARD_WIDTH.
+    #[arg(long)]
+    width: Option<usize>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = Config::from_env();
+
+    let layout = ShardLayout::new(
+        cli.depth.unwrap_or(config.storage_shard_depth),
+        cli.width.unwrap_or(config.storage_shard_width),
+    );
+    layout.validate().map_err(|e| anyhow::anyhow!(e))?;
+
+    for roots in config
+        .blob_backend_roots()
+        .map_err(|e| anyhow::anyhow!(e))?
+    {
+        println!(
+            "Rehashing backend {} ({} and {}) into depth={} width={}",
+            roots.name,
+            roots.hot_root.display(),
+            roots.cold_root.display(),
+            layout.depth,
+            layout.width
+        );
+
+        let store =
+            LocalFilesystemStore::new(roots.hot_root, roots.cold_root).with_shard_layout(layout);
+        let report = store.rehash().await?;
+
+        println!(
+            "Scanned {} blobs: {} moved, {} duplicate copies removed",
+            report.scanned, report.moved, report.duplicates_removed
+        );
+    }
+
+    Ok(())
+}